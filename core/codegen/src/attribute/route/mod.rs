@@ -151,8 +151,8 @@ fn param_guard_decl(guard: &Guard) -> TokenStream {
     // that's the point of statically checking the URI parameters.
     let expr = match guard.trailing {
         false => quote_spanned! { ty.span() =>
-            match #__req.routed_segment(#i) {
-                #_Some(__s) => match <#ty as #FromParam>::from_param(__s) {
+            match #__req.routed_raw_segment(#i) {
+                #_Some((__r, __s)) => match <#ty as #FromParam>::from_raw_param(__r, __s) {
                     #_Ok(__v) => __v,
                     #_Err(__error) => return #parse_error,
                 },
@@ -349,6 +349,8 @@ fn codegen_route(route: Route) -> Result<TokenStream> {
                     #__data: #Data<'__r>
                 ) -> #_route::BoxFuture<'__r> {
                     #_Box::pin(async move {
+                        // Request guards are awaited one at a time, in
+                        // declaration order, never concurrently.
                         #(#request_guards)*
                         #(#param_guards)*
                         #query_guards
@@ -6,6 +6,7 @@ use quote::{ToTokens, TokenStreamExt};
 
 use crate::syn_ext::IdentExt;
 use crate::name::Name;
+use crate::derive::from_param::VariantExt as _;
 
 #[derive(Debug)]
 pub enum FieldName {
@@ -51,15 +52,13 @@ pub(crate) trait VariantExt {
 
 impl VariantExt for Variant<'_> {
     fn first_form_field_value(&self) -> Result<FieldName> {
-        let value = VariantAttr::from_attrs(VariantAttr::NAME, &self.attrs)?
-            .into_iter()
-            .next()
-            .map(|attr| FieldName::Uncased(attr.value))
-            .unwrap_or_else(|| FieldName::Uncased(Name::from(&self.ident)));
-
-        Ok(value)
+        let mut values = self.form_field_values()?;
+        Ok(values.remove(0))
     }
 
+    /// Returns the variant's `#[field(value)]`s if it has any, otherwise the
+    /// values the `FromParam` derive would match: the variant's name or
+    /// `#[param(rename)]` followed by its `#[param(alias)]`es.
     fn form_field_values(&self) -> Result<Vec<FieldName>> {
         let attr_values = VariantAttr::from_attrs(VariantAttr::NAME, &self.attrs)?
             .into_iter()
@@ -67,7 +66,15 @@ impl VariantExt for Variant<'_> {
             .collect::<Vec<_>>();
 
         if attr_values.is_empty() {
-            return Ok(vec![FieldName::Uncased(Name::from(&self.ident))]);
+            return self.param_values();
+        }
+
+        let param_name = self.renamed()?.or(self.aliases()?.into_iter().next());
+        if let Some(name) = param_name {
+            return Err(name.span()
+                .error("variant cannot mix `#[field(value)]` with `#[param]` values")
+                .help("declare the variant's values with either attribute, not both")
+                .span_note(attr_values[0].span(), "`#[field(value)]` declared here"));
         }
 
         Ok(attr_values)
@@ -42,6 +42,10 @@ fn fields_map<F>(fields: Fields<'_>, map_f: F) -> Result<TokenStream>
 
         match __f.name.key_lossy().as_str() {
             #(#matchers,)*
+            // A value for this form itself, not a field without a name.
+            "" if !__f.name.source().is_empty() => {
+                __c.__scalar.get_or_insert_with(|| __f.unexpected());
+            },
             __k if __k == "_method" || !__c.__opts.strict => { /* ok */ },
             _ => __c.__errors.push(__f.unexpected()),
         }
@@ -128,6 +132,7 @@ pub fn derive_from_form(input: proc_macro::TokenStream) -> TokenStream {
                         __opts: #_form::Options,
                         __errors: #_form::Errors<'r>,
                         __parent: #_Option<&'r #_form::Name>,
+                        __scalar: #_Option<#_form::Error<'r>>,
                         #output
                     }
                 })
@@ -163,6 +168,7 @@ pub fn derive_from_form(input: proc_macro::TokenStream) -> TokenStream {
                             __opts,
                             __errors: #_form::Errors::new(),
                             __parent: #_None,
+                            __scalar: #_None,
                             #output
                         }
                     }
@@ -236,6 +242,17 @@ pub fn derive_from_form(input: proc_macro::TokenStream) -> TokenStream {
                 });
 
                 Ok(quote_spanned! { fields.span() =>
+                    // A value for the form itself, as in `a=1` for `a.b=2`.
+                    if let #_Some(mut __e) = __c.__scalar.take() {
+                        if #(__c.#ident.is_some())||* {
+                            __e.kind = #_form::ErrorKind::Conflict;
+                            __e.entity = #_form::error::Entity::Field;
+                            __c.__errors.push(__e);
+                        } else if __c.__opts.strict {
+                            __c.__errors.push(__e);
+                        }
+                    }
+
                     #(let #ident = match #finalize_field {
                         #_ok(#ident) => #_some(#ident),
                         #_err(__e) => { __c.__errors.extend(__e); #_none }
@@ -3,6 +3,7 @@ use proc_macro2::TokenStream;
 
 use crate::exports::*;
 use crate::derive::form_field::{VariantExt, first_duplicate};
+use crate::derive::from_param::{EnumAttr, VariantExt as _};
 
 pub fn derive_from_form_field(input: proc_macro::TokenStream) -> TokenStream {
     DeriveGenerator::build_for(input, quote!(impl<'__v> #_form::FromFormField<'__v>))
@@ -21,6 +22,21 @@ pub fn derive_from_form_field(input: proc_macro::TokenStream) -> TokenStream {
                     return Err(data.span().error("enum must have at least one variant"));
                 }
 
+                // Only `rename` and `alias` carry over from `FromParam`.
+                if EnumAttr::one_from_attrs("param", &data.attrs)?.map_or(false, |a| a.numeric) {
+                    return Err(data.span()
+                        .error("`#[param(numeric)]` enums cannot derive `FromFormField`")
+                        .help("declare the variants' values with `#[field(value)]` instead"));
+                }
+
+                for variant in data.variants() {
+                    if variant.is_other()? {
+                        return Err(variant.ident.span()
+                            .error("`#[param(other)]` variants cannot derive `FromFormField`")
+                            .help("values matching no variant are form errors instead"));
+                    }
+                }
+
                 if let Some(d) = first_duplicate(data.variants(), |v| v.form_field_values())? {
                     let (variant_a_i, variant_a, value_a) = d.0;
                     let (variant_b_i, variant_b, value_b) = d.1;
@@ -0,0 +1,302 @@
+use devise::{*, ext::SpanDiagnosticExt};
+use proc_macro2::TokenStream;
+
+use crate::exports::*;
+use crate::name::Name;
+use crate::derive::form_field::{FieldName, first_duplicate};
+
+#[derive(FromMeta)]
+pub struct VariantAttr {
+    pub rename: Option<Name>,
+    pub alias: Option<Name>,
+    pub other: Option<bool>,
+}
+
+impl VariantAttr {
+    const NAME: &'static str = "param";
+}
+
+#[derive(Default, FromMeta)]
+pub struct EnumAttr {
+    pub numeric: bool,
+}
+
+const INT_REPRS: &[&str] = &[
+    "u8", "u16", "u32", "u64", "u128", "usize",
+    "i8", "i16", "i32", "i64", "i128", "isize",
+];
+
+trait EnumExt {
+    fn numeric_repr(&self) -> Result<Option<syn::Ident>>;
+}
+
+impl EnumExt for Enum<'_> {
+    /// Returns the integer `#[repr]` of the enum if it is `#[param(numeric)]`.
+    fn numeric_repr(&self) -> Result<Option<syn::Ident>> {
+        let attr = EnumAttr::one_from_attrs(VariantAttr::NAME, &self.attrs)?;
+        if !attr.unwrap_or_default().numeric {
+            return Ok(None);
+        }
+
+        for attr in self.attrs.iter().filter(|a| a.path.is_ident("repr")) {
+            if let syn::Meta::List(list) = attr.parse_meta()? {
+                for nested in list.nested.iter() {
+                    if let syn::NestedMeta::Meta(syn::Meta::Path(path)) = nested {
+                        match path.get_ident() {
+                            Some(i) if INT_REPRS.iter().any(|r| i == r) => {
+                                return Ok(Some(i.clone()));
+                            }
+                            _ => continue,
+                        }
+                    }
+                }
+            }
+        }
+
+        Err(self.span().error("`#[param(numeric)]` requires an integer `#[repr]`")
+            .help("add a representation such as `#[repr(u16)]` to the enum"))
+    }
+}
+
+pub(crate) trait VariantExt {
+    fn renamed(&self) -> Result<Option<Name>>;
+    fn aliases(&self) -> Result<Vec<Name>>;
+    fn is_other(&self) -> Result<bool>;
+    fn param_value(&self) -> Result<FieldName>;
+    fn param_values(&self) -> Result<Vec<FieldName>>;
+}
+
+impl VariantExt for Variant<'_> {
+    /// Returns the value of the variant's `rename`, if it has one.
+    fn renamed(&self) -> Result<Option<Name>> {
+        let attrs = VariantAttr::from_attrs(VariantAttr::NAME, &self.attrs)?;
+        let mut renames = attrs.into_iter().filter_map(|attr| attr.rename);
+        let name = renames.next();
+        if let (Some(first), Some(second)) = (&name, renames.next()) {
+            return Err(second.span()
+                .error("duplicate `rename` for variant")
+                .span_note(first.span(), "previous `rename` here"));
+        }
+
+        Ok(name)
+    }
+
+    /// Returns the values of the variant's `alias`es, in declaration order.
+    fn aliases(&self) -> Result<Vec<Name>> {
+        let attrs = VariantAttr::from_attrs(VariantAttr::NAME, &self.attrs)?;
+        Ok(attrs.into_iter().filter_map(|attr| attr.alias).collect())
+    }
+
+    /// Returns `true` if the variant is the `#[param(other)]` catch-all.
+    fn is_other(&self) -> Result<bool> {
+        let attrs = VariantAttr::from_attrs(VariantAttr::NAME, &self.attrs)?;
+        Ok(attrs.iter().any(|attr| attr.other.unwrap_or(false)))
+    }
+
+    fn param_value(&self) -> Result<FieldName> {
+        match self.renamed()? {
+            Some(name) => validated(name, "renamed"),
+            None => Ok(FieldName::Uncased(Name::from(&self.ident))),
+        }
+    }
+
+    /// Returns the variant's value followed by its aliases.
+    fn param_values(&self) -> Result<Vec<FieldName>> {
+        let mut values = vec![self.param_value()?];
+        for alias in self.aliases()? {
+            values.push(validated(alias, "aliased")?);
+        }
+
+        Ok(values)
+    }
+}
+
+/// Checks that the `kind` value `name` is a valid parameter value.
+fn validated(name: Name, kind: &str) -> Result<FieldName> {
+    if name.as_str().is_empty() || name.as_str().contains('/') {
+        return Err(name.span()
+            .error("invalid parameter value")
+            .help(format!("{} values must be non-empty and cannot contain `/`", kind)));
+    }
+
+    Ok(FieldName::Uncased(name))
+}
+
+pub fn derive_from_param(input: proc_macro::TokenStream) -> TokenStream {
+    DeriveGenerator::build_for(input, quote!(impl<'__a> #_request::FromParam<'__a>))
+        .support(Support::Enum)
+        .validator(ValidatorBuild::new()
+            // We only accept C-like enums with at least one variant, save for
+            // one single-field `#[param(other)]` catch-all variant.
+            .enum_validate(|_, data| {
+                if data.variants.is_empty() {
+                    return Err(data.span().error("enum must have at least one variant"));
+                }
+
+                let mut catch_all: Option<Variant<'_>> = None;
+                for variant in data.variants() {
+                    if !variant.is_other()? {
+                        if !variant.fields().is_empty() {
+                            return Err(variant.fields().span()
+                                .error("variants cannot have fields")
+                                .help("only a `#[param(other)]` variant can have a field"));
+                        }
+
+                        continue;
+                    }
+
+                    if let Some(previous) = catch_all {
+                        return Err(variant.ident.span()
+                            .error("only one variant can be `#[param(other)]`")
+                            .span_note(previous.ident.span(), "previous catch-all variant here"));
+                    }
+
+                    if variant.fields().count() != 1 {
+                        return Err(variant.ident.span()
+                            .error("`#[param(other)]` variant must have exactly one field")
+                            .help("the field receives the unmatched value, e.g. `Other(String)`"));
+                    }
+
+                    if let Some(name) = variant.renamed()? {
+                        return Err(name.span()
+                            .error("`#[param(other)]` variant cannot be renamed")
+                            .help("the variant matches every value not matched by another"));
+                    }
+
+                    if let Some(alias) = variant.aliases()?.first() {
+                        return Err(alias.span()
+                            .error("`#[param(other)]` variant cannot have aliases")
+                            .help("the variant matches every value not matched by another"));
+                    }
+
+                    catch_all = Some(variant);
+                }
+
+                if data.numeric_repr()?.is_some() {
+                    if let Some(variant) = catch_all {
+                        return Err(variant.ident.span()
+                            .error("numeric parameters cannot have a catch-all variant"));
+                    }
+
+                    for variant in data.variants() {
+                        if let Some(name) = variant.renamed()? {
+                            return Err(name.span()
+                                .error("numeric parameters cannot be renamed")
+                                .help("variants are matched by their discriminant"));
+                        }
+
+                        if let Some(alias) = variant.aliases()?.first() {
+                            return Err(alias.span()
+                                .error("numeric parameters cannot have aliases")
+                                .help("variants are matched by their discriminant"));
+                        }
+                    }
+
+                    return Ok(());
+                }
+
+                let values = |v: &Variant<'_>| match v.is_other()? {
+                    true => Ok(vec![]),
+                    false => v.param_values(),
+                };
+
+                if let Some(d) = first_duplicate(data.variants(), values)? {
+                    let ((variant_a_i, _, value_a), (variant_b_i, _, value_b)) = d;
+                    if variant_a_i == variant_b_i {
+                        return Err(value_b.error("duplicate parameter value for variant")
+                            .span_note(value_a, "case-insensitively equal to this value"));
+                    }
+
+                    return Err(value_b.error("parameter value is ambiguous")
+                        .span_note(value_a, "case-insensitively equal to this value"));
+                }
+
+                Ok(())
+            })
+        )
+        .outer_mapper(quote! {
+            #[allow(unused_imports)]
+            use #_http::uncased::AsUncased;
+        })
+        .inner_mapper(MapperBuild::new()
+            .try_enum_map(|mapper, data| {
+                if let Some(repr) = data.numeric_repr()? {
+                    let enum_name = &data.ident;
+                    let variant_condition = data.variants().map(|v| {
+                        let variant = &v.ident;
+                        quote_spanned! { v.span() =>
+                            if __n == #enum_name::#variant as #repr {
+                                return #_Ok(#enum_name::#variant);
+                            }
+                        }
+                    });
+
+                    return Ok(quote! {
+                        type Error = #_request::DiscriminantError<'__a>;
+
+                        fn from_param(__p: &'__a str) -> #_Result<Self, Self::Error> {
+                            let __n = __p.parse::<#repr>()
+                                .map_err(|e| #_request::DiscriminantError::Parse(__p, e))?;
+
+                            #(#variant_condition)*
+
+                            #_Err(#_request::DiscriminantError::Unknown(__p))
+                        }
+                    });
+                }
+
+                let mut catch_all = None;
+                let mut variants = vec![];
+                for variant in data.variants() {
+                    match variant.is_other()? {
+                        true => catch_all = Some(variant),
+                        false => variants.push(variant),
+                    }
+                }
+
+                let variant_condition = variants.iter()
+                    .map(|v| mapper.map_variant(*v))
+                    .collect::<Result<Vec<_>>>()?;
+
+                if let Some(variant) = catch_all {
+                    let builder = variant.builder(|f| quote_spanned!(f.ty.span() => __p.into()));
+                    return Ok(quote! {
+                        type Error = #_Infallible;
+
+                        fn from_param(__p: &'__a str) -> #_Result<Self, Self::Error> {
+                            #(#variant_condition)*
+
+                            #_Ok(#builder)
+                        }
+                    });
+                }
+
+                let mut variant_value = vec![];
+                for values in variants.iter().map(|v| v.param_values()) {
+                    variant_value.append(&mut values?);
+                }
+
+                Ok(quote! {
+                    type Error = #_request::InvalidOption<'__a>;
+
+                    fn from_param(__p: &'__a str) -> #_Result<Self, Self::Error> {
+                        #(#variant_condition)*
+
+                        const OPTIONS: &'static [&'static str] = &[#(#variant_value),*];
+                        #_Err(#_request::InvalidOption::new(__p, OPTIONS))
+                    }
+                })
+            })
+            .try_variant_map(|_, variant| {
+                let builder = variant.builder(|_| unreachable!("fieldless"));
+                let value = variant.param_values()?;
+
+                Ok(quote_spanned! { variant.span() =>
+                    if #(__p.as_uncased() == #value)||* {
+                        return #_Ok(#builder);
+                    }
+                })
+            })
+        )
+        .to_tokens()
+}
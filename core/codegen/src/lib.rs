@@ -237,6 +237,9 @@ macro_rules! route_attribute {
         ///
         ///         1. Request guards from left to right.
         ///
+        ///            Each guard is awaited to completion before the next is
+        ///            invoked; guards are never polled concurrently.
+        ///
         ///            If a request guard fails, the request is forwarded if the
         ///            [`Outcome`] is `Forward` or failed if the [`Outcome`] is
         ///            `Failure`. See [`FromRequest` Outcomes] for further
@@ -480,6 +483,160 @@ pub fn launch(args: TokenStream, input: TokenStream) -> TokenStream {
     emit!(attribute::entry::launch_attribute(args, input))
 }
 
+/// Derive for the [`FromParam`] trait.
+///
+/// The [`FromParam`] derive can be applied to enums with nullary (zero-length)
+/// fields:
+///
+/// ```rust
+/// # #[macro_use] extern crate rocket;
+/// #
+/// #[derive(FromParam)]
+/// enum SortOrder {
+///     Asc,
+///     Desc,
+/// }
+///
+/// #[get("/sort/<order>")]
+/// fn sort(order: SortOrder) { /* .. */ }
+/// ```
+///
+/// The derive generates an implementation of the [`FromParam`] trait for the
+/// decorated `enum`. The implementation returns successfully when the dynamic
+/// path segment matches, case insensitively, the stringified version of a
+/// variant's name, returning an instance of said variant. If there is no
+/// match, an [`InvalidOption`] error recording the segment and all of the
+/// accepted values is returned.
+///
+/// As an example, for the `enum` above, the segments `asc`, `ASC`, `aSc`, and
+/// so on would parse as `SortOrder::Asc`, while `desc` (in any casing) would
+/// parse as `SortOrder::Desc`.
+///
+/// The `param` field attribute can be used to change the string value that is
+/// compared against for a given variant:
+///
+/// ```rust
+/// # #[macro_use] extern crate rocket;
+/// #
+/// #[derive(FromParam)]
+/// enum Color {
+///     Red,
+///     #[param(rename = "green")]
+///     Verde,
+///     #[param(rename = "dark-blue")]
+///     DarkBlue,
+/// }
+/// ```
+///
+/// A renamed variant matches _only_ its new value; in the example above,
+/// `green` and `GREEN` parse as `Color::Verde` while `verde` does not parse.
+/// Declaring two variants whose values are case-insensitively equal is a
+/// compile-time error.
+///
+/// A variant can additionally accept any number of alternative spellings, each
+/// declared with its own `alias` attribute:
+///
+/// ```rust
+/// # #[macro_use] extern crate rocket;
+/// #
+/// #[derive(FromParam)]
+/// enum Country {
+///     #[param(rename = "us")]
+///     #[param(alias = "usa")]
+///     #[param(alias = "united-states")]
+///     Us,
+///     #[param(alias = "gb")]
+///     #[param(alias = "united-kingdom")]
+///     Uk,
+/// }
+/// ```
+///
+/// A variant matches its value or any of its aliases, case-insensitively:
+/// above, `us`, `USA`, and `united-states` all parse as `Country::Us`, while
+/// `uk` and `GB` parse as `Country::Uk`. Aliases are subject to the same rules
+/// as renamed values, and an alias that is case-insensitively equal to any
+/// other value or alias is a compile-time error.
+///
+/// An enum with an integer `#[repr]` can instead be matched by its variants'
+/// discriminants by applying `#[param(numeric)]` to the enum itself:
+///
+/// ```rust
+/// # #[macro_use] extern crate rocket;
+/// #
+/// #[derive(FromParam)]
+/// #[param(numeric)]
+/// #[repr(u16)]
+/// enum Code {
+///     Ok = 200,
+///     NotFound = 404,
+/// }
+///
+/// #[get("/status/<code>")]
+/// fn status(code: Code) { /* .. */ }
+/// ```
+///
+/// The segment is parsed as the `#[repr]` type and mapped to the variant with
+/// the equal discriminant; above, `404` parses as `Code::NotFound`. A segment
+/// that is not an integer, or an integer that no variant has as its
+/// discriminant, fails with a [`DiscriminantError`]. Numeric variants cannot be
+/// renamed, and `#[param(numeric)]` without an integer `#[repr]` is a
+/// compile-time error.
+///
+/// Instead of failing, a segment that matches no variant can be routed to a
+/// catch-all variant with a single field, designated by `#[param(other)]`:
+///
+/// ```rust
+/// # #[macro_use] extern crate rocket;
+/// #
+/// #[derive(FromParam)]
+/// enum Lang {
+///     Rust,
+///     #[param(rename = "c++")]
+///     Cpp,
+///     #[param(other)]
+///     Other(String),
+/// }
+///
+/// #[get("/lang/<lang>")]
+/// fn lang(lang: Lang) -> String {
+///     match lang {
+///         Lang::Rust | Lang::Cpp => "a known language".into(),
+///         Lang::Other(name) => format!("never heard of {}", name),
+///     }
+/// }
+/// ```
+///
+/// The field is created via `From<&str>` from the decoded segment, verbatim;
+/// above, `zig` parses as `Lang::Other("zig")`. With a catch-all variant,
+/// parsing never fails: the error type is [`Infallible`]. At most one variant
+/// can be `#[param(other)]`, it can't be renamed, and numeric enums can't have
+/// one.
+///
+/// The `#[param]` attribute's grammar is:
+///
+/// ```text
+/// param := 'rename' '=' STRING_LIT
+///        | 'alias' '=' STRING_LIT
+///        | 'other'
+///        | 'numeric'
+///
+/// STRING_LIT := any valid, non-empty string literal without a `/`
+/// ```
+///
+/// `rename`, `alias`, and `other` apply to variants while `numeric` applies to
+/// the enum. `alias` may be repeated; `rename` may not. Neither a
+/// `#[param(other)]` variant nor the variants of a numeric enum can have
+/// aliases.
+///
+/// [`FromParam`]: ../rocket/request/trait.FromParam.html
+/// [`InvalidOption`]: ../rocket/request/struct.InvalidOption.html
+/// [`DiscriminantError`]: ../rocket/request/enum.DiscriminantError.html
+/// [`Infallible`]: std::convert::Infallible
+#[proc_macro_derive(FromParam, attributes(param))]
+pub fn derive_from_param(input: TokenStream) -> TokenStream {
+    emit!(derive::from_param::derive_from_param(input))
+}
+
 /// Derive for the [`FromFormField`] trait.
 ///
 /// The [`FromFormField`] derive can be applied to enums with nullary
@@ -541,8 +698,35 @@ pub fn launch(args: TokenStream, input: TokenStream) -> TokenStream {
 /// variant. In the example above, the the strings `"fourth"`, `"FOUrth"`,
 /// `"fiFTH"` and so on would parse as `MyValue::Third`.
 ///
+/// ## Sharing Values with `FromParam`
+///
+/// A variant without a `#[field(value)]` attribute is matched exactly as the
+/// `FromParam` derive would match it: by its name or its `#[param(rename)]`
+/// value, and by any of its `#[param(alias)]` values. An enum can thus derive
+/// both traits and accept the same values in paths and forms:
+///
+/// ```rust
+/// # #[macro_use] extern crate rocket;
+/// #
+/// #[derive(FromParam, FromFormField)]
+/// enum Size {
+///     #[param(rename = "sm")]
+///     #[param(alias = "s")]
+///     Small,
+///     #[param(alias = "l")]
+///     Large,
+/// }
+/// ```
+///
+/// Here, the form values `"sm"` and `"s"` parse as `Size::Small` while
+/// `"large"` and `"l"` parse as `Size::Large`. The error for any other value
+/// names the field and lists every accepted value, aliases included. A variant
+/// cannot declare values with both `#[field(value)]` and `#[param]`. Enums
+/// that are `#[param(numeric)]` or have a `#[param(other)]` catch-all variant
+/// cannot derive `FromFormField`.
+///
 /// [`FromFormField`]: ../rocket/form/trait.FromFormField.html
-#[proc_macro_derive(FromFormField, attributes(field))]
+#[proc_macro_derive(FromFormField, attributes(field, param))]
 pub fn derive_from_form_field(input: TokenStream) -> TokenStream {
     emit!(derive::from_form_field::derive_from_form_field(input))
 }
@@ -498,6 +498,47 @@ struct Person<'r> {
     sitting: Dog,
 }
 
+#[test]
+fn test_repeated_fields() {
+    #[derive(Debug, PartialEq, FromForm)]
+    struct Tags<'r> {
+        tag: Vec<&'r str>,
+        n: Vec<usize>,
+    }
+
+    let none: Tags = strict("").unwrap();
+    assert_eq!(none, Tags { tag: vec![], n: vec![] });
+    let none: Tags = lenient("").unwrap();
+    assert_eq!(none, Tags { tag: vec![], n: vec![] });
+
+    let one: Tags = strict("tag=a").unwrap();
+    assert_eq!(one, Tags { tag: vec!["a"], n: vec![] });
+
+    // Repeats are collected in submission order, even when interleaved.
+    let many: Tags = strict("tag=c&n=3&tag=a&n=1&tag=b&n=2").unwrap();
+    assert_eq!(many, Tags { tag: vec!["c", "a", "b"], n: vec![3, 1, 2] });
+
+    // Errors are reported in order, named with the index of the element.
+    let errors = strict::<Tags>("n=1&n=x&n=2&n=-3").unwrap_err();
+    let names: Vec<_> = errors.iter().map(|e| e.name.as_ref().unwrap().to_string()).collect();
+    assert_eq!(names, ["n.1", "n.3"]);
+    assert_eq!(errors[0].value.as_deref(), Some("x"));
+    assert!(matches!(errors[0].kind, ErrorKind::Int(..)));
+    assert_eq!(errors[1].value.as_deref(), Some("-3"));
+
+    let errors = strict::<Tags>("n[]=x").unwrap_err();
+    assert_eq!(errors[0].name.as_ref().unwrap(), "n.0");
+
+    // Errors for fields nested within an element keep their own names.
+    #[derive(Debug, PartialEq, FromForm)]
+    struct Pets {
+        dogs: Vec<Dog>,
+    }
+
+    let errors = strict::<Pets>("dogs[0].barks=yes&dogs[0].trained=maybe").unwrap_err();
+    assert_eq!(errors[0].name.as_ref().unwrap(), "dogs[0].trained");
+}
+
 #[test]
 fn test_nested_multi() {
     let person: Person = lenient("sitting.barks=true&sitting.trained=true").unwrap();
@@ -507,8 +548,13 @@ fn test_nested_multi() {
         dogs: vec![],
     });
 
-    let person = strict::<Person>("sitting.barks=true&sitting.trained=true");
-    assert!(person.is_err());
+    // Missing vectors are empty, even when parsing strictly.
+    let person: Person = strict("sitting.barks=true&sitting.trained=true").unwrap();
+    assert_eq!(person, Person {
+        sitting: Dog { barks: true, trained: true },
+        cats: vec![],
+        dogs: vec![],
+    });
 
     let person: Person = lenient("sitting.barks=true&sitting.trained=true\
         &dogs[0].name=fido&dogs[0].pet.trained=yes&dogs[0].age=7&dogs[0].pet.barks=no\
@@ -523,9 +569,11 @@ fn test_nested_multi() {
         }]
     });
 
-    let person = strict::<Person>("sitting.barks=true&sitting.trained=true\
-        &dogs[0].name=fido&dogs[0].pet.trained=yes&dogs[0].age=7&dogs[0].pet.barks=no");
-    assert!(person.is_err());
+    let person: Person = strict("sitting.barks=true&sitting.trained=true\
+        &dogs[0].name=fido&dogs[0].pet.trained=yes&dogs[0].age=7&dogs[0].pet.barks=no")
+        .unwrap();
+    assert_eq!(person.cats, vec![]);
+    assert_eq!(person.dogs.len(), 1);
 
     let person: Person = lenient("sitting.trained=no&sitting.barks=true\
         &dogs[0].name=fido&dogs[0].pet.trained=yes&dogs[0].age=7&dogs[0].pet.barks=no\
@@ -578,6 +626,96 @@ fn test_nested_multi() {
     });
 }
 
+#[test]
+fn test_nested_names() {
+    #[derive(Debug, PartialEq, FromForm)]
+    struct Address {
+        city: String,
+        zip: String,
+    }
+
+    #[derive(Debug, PartialEq, FromForm)]
+    struct User {
+        name: String,
+        address: Address,
+        tags: Vec<String>,
+    }
+
+    #[derive(Debug, PartialEq, FromForm)]
+    struct Signup {
+        user: User,
+        items: Vec<Address>,
+    }
+
+    let expected = Signup {
+        user: User {
+            name: "Bob".into(),
+            address: Address { city: "Helsinki".into(), zip: "00100".into() },
+            tags: vec!["a".into(), "b".into()],
+        },
+        items: vec![
+            Address { city: "Oulu".into(), zip: "90100".into() },
+            Address { city: "Turku".into(), zip: "20100".into() },
+        ],
+    };
+
+    let dotted = "user.name=Bob&user.address.city=Helsinki&user.address.zip=00100\
+        &user.tags[0]=a&user.tags[1]=b\
+        &items[0].city=Oulu&items[0].zip=90100&items[1].city=Turku&items[1].zip=20100";
+
+    let bracketed = "user[name]=Bob&user[address][city]=Helsinki&user[address][zip]=00100\
+        &user[tags][0]=a&user[tags][1]=b\
+        &items[0][city]=Oulu&items[0][zip]=90100&items[1][city]=Turku&items[1][zip]=20100";
+
+    let mixed = "user.address[city]=Helsinki&user[name]=Bob&user.tags[]=a&user.tags[]=b\
+        &user[address].zip=00100&items[x].city=Oulu&items[x].zip=90100\
+        &items[y].zip=20100&items[y].city=Turku";
+
+    for string in [dotted, bracketed, mixed] {
+        assert_eq!(strict::<Signup>(string).as_ref(), Ok(&expected), "{}", string);
+        assert_eq!(lenient::<Signup>(string).as_ref(), Ok(&expected), "{}", string);
+    }
+
+    // A field can't be both a value and the parent of nested fields.
+    let conflicts = [
+        ("user.address=x&user.address.city=Oulu&user.address.zip=90100", "user.address"),
+        ("user.address.city=Oulu&user.address.zip=90100&user[address]=x", "user[address]"),
+        ("user.address.city=Oulu&user.address.city.name=x&user.address.zip=1", "user.address.city"),
+        ("items[0].city=Oulu&items[0].city[name]=x&items[0].zip=1", "items[0].city"),
+    ];
+
+    for (string, name) in conflicts {
+        let string = format!("user.name=Bob&{}", string);
+        for errors in [strict::<Signup>(&string), lenient::<Signup>(&string)] {
+            let errors = errors.unwrap_err();
+            assert!(errors.iter().any(|e| {
+                e.name.as_ref().unwrap() == name
+                    && e.entity == Entity::Field
+                    && matches!(e.kind, ErrorKind::Conflict)
+            }), "{}: {:?}", string, errors);
+        }
+    }
+
+    // Without nested fields, a value for a form is only unexpected.
+    let errors = strict::<Signup>("user.name=Bob&user.address=x").unwrap_err();
+    assert!(errors.iter().any(|e| matches!(e.kind, ErrorKind::Unexpected)));
+    assert!(!errors.iter().any(|e| matches!(e.kind, ErrorKind::Conflict)));
+
+    // A field without a name isn't a value for the form: it's ignored when
+    // lenient and unexpected when strict.
+    #[derive(Debug, PartialEq, FromForm)]
+    struct Flat {
+        a: usize,
+    }
+
+    assert_eq!(lenient::<Flat>("a=1&=x"), Ok(Flat { a: 1 }));
+    assert_eq!(lenient::<Flat>("=x&a=1&="), Ok(Flat { a: 1 }));
+    let errors = strict::<Flat>("a=1&=x").unwrap_err();
+    assert!(errors.iter().any(|e| matches!(e.kind, ErrorKind::Unexpected)));
+    assert!(!errors.iter().any(|e| matches!(e.kind, ErrorKind::Conflict)));
+    assert_eq!(lenient::<Signup>(&format!("{}&=x", dotted)).as_ref(), Ok(&expected));
+}
+
 #[test]
 fn test_multipart() {
     use rocket::http::ContentType;
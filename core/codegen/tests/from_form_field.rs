@@ -1,4 +1,5 @@
 use rocket::form::{FromFormField, ValueField, FromForm, Options, Errors};
+use rocket::request::FromParam;
 
 fn parse<'v, T: FromForm<'v>>(value: &'v str) -> Result<T, Errors<'v>> {
     let mut context = T::init(Options::Lenient);
@@ -104,3 +105,46 @@ fn form_value_errors() {
         }
     }));
 }
+
+#[derive(Debug, FromParam, FromFormField)]
+enum Size {
+    #[param(rename = "sm")]
+    #[param(alias = "s")]
+    Small,
+    Medium,
+    #[param(alias = "l")]
+    #[param(alias = "big")]
+    Large,
+}
+
+#[test]
+fn from_form_value_param_values() {
+    assert_parse!("sm", "SM", "s" => Size::Small);
+    assert_parse!("medium", "MEDIUM" => Size::Medium);
+    assert_parse!("large", "l", "BIG" => Size::Large);
+    assert_no_parse!("small", "m", "xl", "" => Size);
+
+    for value in &["sm", "s", "medium", "large", "l", "big"] {
+        let form = format!("{:?}", parse::<Size>(value).unwrap());
+        let param = format!("{:?}", Size::from_param(value).unwrap());
+        assert_eq!(form, param);
+    }
+}
+
+#[test]
+fn form_value_param_errors() {
+    use rocket::form::error::{ErrorKind, Entity};
+
+    let errors = Size::from_value(ValueField::parse("size=xl")).unwrap_err();
+    assert!(errors.iter().any(|e| {
+        e.name.as_ref().unwrap() == "size"
+        && e.value.as_deref() == Some("xl")
+        && e.entity == Entity::Value
+        && match &e.kind {
+            ErrorKind::InvalidChoice { choices } => {
+                &choices[..] == &["sm", "s", "Medium", "Large", "l", "big"]
+            }
+            _ => false
+        }
+    }));
+}
@@ -0,0 +1,274 @@
+use rocket::request::{FromParam, InvalidOption};
+
+macro_rules! assert_parse {
+    ($($string:expr),* => $item:ident :: $variant:ident) => ($(
+        match $item::from_param($string) {
+            Ok($item::$variant) => { /* okay */ },
+            Ok(item) => panic!("Failed to parse {} as {:?}. Got {:?} instead.",
+                               $string, $item::$variant, item),
+            Err(e) => panic!("Failed to parse {} as {}: {}",
+                             $string, stringify!($item), e),
+        }
+    )*)
+}
+
+macro_rules! assert_no_parse {
+    ($($string:expr),* => $item:ident) => ($(
+        match $item::from_param($string) {
+            Err(_) => { /* okay */ },
+            Ok(item) => panic!("Unexpectedly parsed {} as {:?}", $string, item)
+        }
+    )*)
+}
+
+#[test]
+fn from_param_exact() {
+    #[derive(Debug, FromParam)]
+    enum SortOrder { Asc, Desc }
+
+    assert_parse!("Asc" => SortOrder::Asc);
+    assert_parse!("Desc" => SortOrder::Desc);
+}
+
+#[test]
+fn from_param_case_insensitive() {
+    #[allow(non_camel_case_types)]
+    #[derive(Debug, FromParam)]
+    enum Color { Red, DarkBlue, light_green }
+
+    assert_parse!("red", "RED", "rEd" => Color::Red);
+    assert_parse!("darkblue", "DARKBLUE", "darkBlue" => Color::DarkBlue);
+    assert_parse!("light_green", "LIGHT_GREEN", "Light_Green" => Color::light_green);
+}
+
+#[test]
+fn from_param_rename() {
+    #[derive(Debug, FromParam)]
+    enum Color {
+        Red,
+        #[param(rename = "green")]
+        Verde,
+        #[param(rename = "dark-blue")]
+        DarkBlue,
+        #[param(rename = "ünïcödé")]
+        Unicode,
+    }
+
+    assert_parse!("red", "Red" => Color::Red);
+    assert_parse!("green", "GREEN", "gReEn" => Color::Verde);
+    assert_parse!("dark-blue", "DARK-BLUE" => Color::DarkBlue);
+    assert_parse!("ünïcödé" => Color::Unicode);
+    assert_no_parse!("verde", "Verde", "darkblue", "DarkBlue", "unicode" => Color);
+}
+
+#[test]
+fn from_param_unknown() {
+    #[derive(Debug, FromParam)]
+    enum SortOrder {
+        Asc,
+        #[param(rename = "descending")]
+        Desc,
+    }
+
+    assert_no_parse!("", "asc ", " asc", "as", "ascc", "desc", "up" => SortOrder);
+
+    let error = SortOrder::from_param("up").unwrap_err();
+    assert_eq!(error, InvalidOption { value: "up", options: &["Asc", "descending"] });
+    assert_eq!(error.to_string(), "unexpected value `up`, expected one of: Asc, descending");
+}
+
+#[test]
+fn from_param_in_route() {
+    use rocket::local::blocking::Client;
+    use rocket::http::Status;
+
+    #[derive(Debug, FromParam)]
+    enum SortOrder { Asc, Desc }
+
+    #[rocket::get("/sort/<order>")]
+    fn sort(order: SortOrder) -> String {
+        format!("{:?}", order)
+    }
+
+    #[rocket::get("/check/<order>")]
+    fn check(order: Result<SortOrder, InvalidOption<'_>>) -> String {
+        match order {
+            Ok(order) => format!("{:?}", order),
+            Err(e) => e.to_string(),
+        }
+    }
+
+    let client = Client::debug_with(rocket::routes![sort, check]).unwrap();
+    assert_eq!(client.get("/sort/ASC").dispatch().into_string().unwrap(), "Asc");
+    assert_eq!(client.get("/sort/desc").dispatch().into_string().unwrap(), "Desc");
+    assert_eq!(client.get("/sort/up").dispatch().status(), Status::NotFound);
+    assert_eq!(client.get("/check/up").dispatch().into_string().unwrap(),
+        "unexpected value `up`, expected one of: Asc, Desc");
+}
+
+#[test]
+fn from_param_numeric() {
+    use rocket::request::DiscriminantError;
+
+    #[derive(Debug, FromParam)]
+    #[param(numeric)]
+    #[repr(u16)]
+    enum Code {
+        Ok = 200,
+        Created,
+        NotFound = 404,
+    }
+
+    assert_parse!("200" => Code::Ok);
+    assert_parse!("201" => Code::Created);
+    assert_parse!("404", "0404" => Code::NotFound);
+    assert_no_parse!("", "0", "202", "500", "65536", "-1", " 200", "Ok", "ok", "2oo" => Code);
+
+    assert_eq!(Code::from_param("500").unwrap_err(), DiscriminantError::Unknown("500"));
+    assert_eq!(Code::from_param("500").unwrap_err().to_string(),
+        "no variant has discriminant `500`");
+
+    let error = Code::from_param("Ok").unwrap_err();
+    assert!(matches!(error, DiscriminantError::Parse("Ok", _)));
+    assert!(error.to_string().starts_with("invalid integer `Ok`"));
+
+    #[derive(Debug, FromParam)]
+    #[param(numeric)]
+    #[repr(i8)]
+    enum Level { Low = -1, Normal, High }
+
+    assert_parse!("-1" => Level::Low);
+    assert_parse!("0" => Level::Normal);
+    assert_parse!("1", "+1" => Level::High);
+    assert_no_parse!("2", "-2", "128", "high" => Level);
+}
+
+#[test]
+fn from_param_numeric_in_route() {
+    use rocket::local::blocking::Client;
+    use rocket::http::Status;
+    use rocket::request::DiscriminantError;
+
+    #[derive(Debug, FromParam)]
+    #[param(numeric)]
+    #[repr(u16)]
+    enum Code { Ok = 200, NotFound = 404 }
+
+    #[rocket::get("/code/<code>")]
+    fn code(code: Code) -> String {
+        format!("{:?}", code)
+    }
+
+    #[rocket::get("/check/<code>")]
+    fn check(code: Result<Code, DiscriminantError<'_>>) -> String {
+        match code {
+            Ok(code) => format!("{:?}", code),
+            Err(e) => e.to_string(),
+        }
+    }
+
+    let client = Client::debug_with(rocket::routes![code, check]).unwrap();
+    assert_eq!(client.get("/code/404").dispatch().into_string().unwrap(), "NotFound");
+    assert_eq!(client.get("/code/500").dispatch().status(), Status::NotFound);
+    assert_eq!(client.get("/code/ok").dispatch().status(), Status::NotFound);
+    assert_eq!(client.get("/check/200").dispatch().into_string().unwrap(), "Ok");
+    assert_eq!(client.get("/check/201").dispatch().into_string().unwrap(),
+        "no variant has discriminant `201`");
+}
+
+#[test]
+fn from_param_other() {
+    #[derive(Debug, PartialEq, FromParam)]
+    enum Lang {
+        Rust,
+        #[param(rename = "c++")]
+        Cpp,
+        #[param(other)]
+        Other(String),
+        Go,
+    }
+
+    assert_parse!("rust", "RUST" => Lang::Rust);
+    assert_parse!("c++", "C++" => Lang::Cpp);
+    assert_parse!("go", "Go" => Lang::Go);
+    assert_eq!(Lang::from_param("cpp"), Ok(Lang::Other("cpp".into())));
+    assert_eq!(Lang::from_param("Zig"), Ok(Lang::Other("Zig".into())));
+    assert_eq!(Lang::from_param("rust "), Ok(Lang::Other("rust ".into())));
+
+    #[derive(Debug, PartialEq, FromParam)]
+    enum Named {
+        Known,
+        #[param(other)]
+        Unknown { value: Box<str> },
+    }
+
+    assert_parse!("known" => Named::Known);
+    assert_eq!(Named::from_param("new"), Ok(Named::Unknown { value: "new".into() }));
+}
+
+#[test]
+fn from_param_other_in_route() {
+    use rocket::local::blocking::Client;
+
+    #[derive(Debug, FromParam)]
+    enum Lang {
+        Rust,
+        #[param(other)]
+        Other(String),
+    }
+
+    #[rocket::get("/lang/<lang>")]
+    fn lang(lang: Lang) -> String {
+        match lang {
+            Lang::Rust => "rust!".into(),
+            Lang::Other(other) => format!("other: {}", other),
+        }
+    }
+
+    let client = Client::debug_with(rocket::routes![lang]).unwrap();
+    assert_eq!(client.get("/lang/Rust").dispatch().into_string().unwrap(), "rust!");
+    assert_eq!(client.get("/lang/zig").dispatch().into_string().unwrap(), "other: zig");
+    assert_eq!(client.get("/lang/c%2B%2B").dispatch().into_string().unwrap(), "other: c++");
+    assert_eq!(client.get("/lang/a%20b").dispatch().into_string().unwrap(), "other: a b");
+}
+
+#[test]
+fn from_param_alias() {
+    #[derive(Debug, FromParam)]
+    enum Country {
+        #[param(rename = "us")]
+        #[param(alias = "usa")]
+        #[param(alias = "united-states")]
+        Us,
+        #[param(alias = "gb")]
+        #[param(alias = "united-kingdom")]
+        Uk,
+        Fi,
+    }
+
+    assert_parse!("us", "US", "usa", "USA", "united-states", "United-States" => Country::Us);
+    assert_parse!("uk", "UK", "gb", "Gb", "united-kingdom", "UNITED-KINGDOM" => Country::Uk);
+    assert_parse!("fi", "FI" => Country::Fi);
+    assert_no_parse!("", "unitedstates", "united", "usa ", "gbr", "england" => Country);
+
+    let error = Country::from_param("de").unwrap_err();
+    let options = &["us", "usa", "united-states", "Uk", "gb", "united-kingdom", "Fi"];
+    assert_eq!(error, InvalidOption { value: "de", options });
+}
+
+#[test]
+fn from_param_alias_with_other() {
+    #[derive(Debug, PartialEq, FromParam)]
+    enum Unit {
+        #[param(alias = "metre")]
+        #[param(alias = "meter")]
+        M,
+        #[param(other)]
+        Other(String),
+    }
+
+    assert_eq!(Unit::from_param("m"), Ok(Unit::M));
+    assert_eq!(Unit::from_param("Metre"), Ok(Unit::M));
+    assert_eq!(Unit::from_param("METER"), Ok(Unit::M));
+    assert_eq!(Unit::from_param("meters"), Ok(Unit::Other("meters".into())));
+}
@@ -111,4 +111,25 @@ struct Renamed3 {
     hello_there: usize,
 }
 
+#[derive(FromFormField)]
+enum MixedValues {
+    #[field(value = "a")]
+    #[param(alias = "b")]
+    A,
+}
+
+#[derive(FromFormField)]
+#[repr(u8)]
+#[param(numeric)]
+enum NumericForm {
+    A = 1,
+}
+
+#[derive(FromFormField)]
+enum OtherForm {
+    A,
+    #[param(other)]
+    B(String),
+}
+
 fn main() { }
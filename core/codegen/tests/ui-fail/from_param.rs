@@ -0,0 +1,121 @@
+#[macro_use] extern crate rocket;
+
+#[derive(FromParam)]
+struct Foo1;
+
+#[derive(FromParam)]
+enum Foo2 {
+    A(usize),
+}
+
+#[derive(FromParam)]
+enum Foo3 { }
+
+#[derive(FromParam)]
+enum Bar1 {
+    #[param(rename = 123)]
+    A,
+}
+
+#[derive(FromParam)]
+enum Bar2 {
+    #[param(rename = "")]
+    A,
+}
+
+#[derive(FromParam)]
+enum Bar3 {
+    #[param(rename = "a/b")]
+    A,
+}
+
+#[derive(FromParam)]
+enum Bar4 {
+    #[param(rename = "a")]
+    #[param(rename = "b")]
+    A,
+}
+
+#[derive(FromParam)]
+enum Dup1 {
+    A,
+    #[param(rename = "a")]
+    B,
+}
+
+#[derive(FromParam)]
+enum Dup2 {
+    #[param(rename = "Same")]
+    A,
+    #[param(rename = "sAME")]
+    B,
+}
+
+#[derive(FromParam)]
+enum Dup3 {
+    Abc,
+    ABC,
+}
+
+#[derive(FromParam)]
+enum Other1 {
+    A,
+    #[param(other)]
+    B(String),
+    #[param(other)]
+    C(String),
+}
+
+#[derive(FromParam)]
+enum Other2 {
+    A,
+    #[param(other)]
+    B,
+}
+
+#[derive(FromParam)]
+enum Other3 {
+    A,
+    #[param(other)]
+    #[param(rename = "b")]
+    B(String),
+}
+
+#[derive(FromParam)]
+enum Other4 {
+    A(String),
+    #[param(other)]
+    B(String),
+}
+
+#[derive(FromParam)]
+enum Alias1 {
+    #[param(alias = "usa")]
+    Us,
+    #[param(alias = "USA")]
+    Uk,
+}
+
+#[derive(FromParam)]
+enum Alias2 {
+    #[param(alias = "b")]
+    A,
+    B,
+}
+
+#[derive(FromParam)]
+enum Alias3 {
+    #[param(alias = "x")]
+    #[param(alias = "X")]
+    A,
+}
+
+#[derive(FromParam)]
+enum Alias4 {
+    A,
+    #[param(other)]
+    #[param(alias = "b")]
+    B(String),
+}
+
+fn main() { }
@@ -171,6 +171,44 @@ impl Accept {
         preferred
     }
 
+    /// Returns `true` if a representation of the concrete `media_type` is
+    /// acceptable according to `self`.
+    ///
+    /// The media range in `self` that matches `media_type` most specifically
+    /// decides: `text/html` takes precedence over `text/*`, which takes
+    /// precedence over `*/*`, and a range with matching parameters takes
+    /// precedence over one with fewer. The media type is acceptable if that
+    /// range has a weight other than `0`. If no range matches, the media type
+    /// is not acceptable.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # extern crate rocket;
+    /// use rocket::http::{Accept, MediaType};
+    ///
+    /// let accept: Accept = "text/*, */*; q=0.1, image/png; q=0".parse().unwrap();
+    /// assert!(accept.accepts(&MediaType::HTML));
+    /// assert!(accept.accepts(&MediaType::JSON));
+    /// assert!(!accept.accepts(&MediaType::PNG));
+    ///
+    /// let accept: Accept = "application/json".parse().unwrap();
+    /// assert!(accept.accepts(&MediaType::JSON));
+    /// assert!(!accept.accepts(&MediaType::HTML));
+    /// ```
+    pub fn accepts(&self, media_type: &MediaType) -> bool {
+        let mut best: Option<((u8, usize), &QMediaType)> = None;
+        for range in self.iter() {
+            if let Some(precedence) = range.precedence(media_type) {
+                if best.map_or(true, |(p, _)| precedence > p) {
+                    best = Some((precedence, range));
+                }
+            }
+        }
+
+        best.map_or(false, |(_, range)| range.weight_or(1.0) > 0.0)
+    }
+
     /// Retrieve the first media type in `self`, if any.
     ///
     /// # Example
@@ -343,6 +381,28 @@ impl QMediaType {
     pub fn media_type(&self) -> &MediaType {
         &self.0
     }
+
+    /// If the media range `self` matches `media_type`, returns its precedence:
+    /// its specificity and the number of its parameters, all of which must be
+    /// present in `media_type`. Parameters from `q` on are accept parameters.
+    fn precedence(&self, media_type: &MediaType) -> Option<(u8, usize)> {
+        let range = self.media_type();
+        let top = range.top() == "*" || range.top() == media_type.top();
+        let sub = range.sub() == "*" || range.sub() == media_type.sub();
+        if !top || !sub {
+            return None;
+        }
+
+        let mut params = 0;
+        for (name, value) in range.params().take_while(|(name, _)| name != "q") {
+            match media_type.param(name.as_str()) {
+                Some(v) if v.eq_ignore_ascii_case(value) => params += 1,
+                _ => return None,
+            }
+        }
+
+        Some((range.specificity(), params))
+    }
 }
 
 impl From<MediaType> for QMediaType {
@@ -391,6 +451,48 @@ mod test {
         }
     }
 
+    #[track_caller]
+    fn assert_accepts(string: &str, media_type: &str, expect: bool) {
+        let accept: Accept = string.parse().expect("accept string parse");
+        let media_type: MediaType = media_type.parse().expect("media type parse");
+        if accept.accepts(&media_type) != expect {
+            panic!("{} should accept {}: {}, but doesn't", string, media_type, expect)
+        }
+    }
+
+    #[test]
+    fn test_accepts() {
+        // Explicit media types.
+        assert_accepts("application/json", "application/json", true);
+        assert_accepts("application/json", "text/html", false);
+        assert_accepts("text/html, application/json; q=0.5", "application/json", true);
+        assert_accepts("APPLICATION/JSON", "application/json", true);
+
+        // Wildcards.
+        assert_accepts("*/*", "application/json", true);
+        assert_accepts("*/*", "image/png", true);
+        assert_accepts("text/*", "text/plain", true);
+        assert_accepts("text/*", "application/json", false);
+
+        // Exclusions, with the most specific range deciding.
+        assert_accepts("application/json; q=0", "application/json", false);
+        assert_accepts("*/*, application/json; q=0", "application/json", false);
+        assert_accepts("application/json; q=0, */*", "application/json", false);
+        assert_accepts("*/*, application/json; q=0", "text/html", true);
+        assert_accepts("*/*; q=0, text/html", "text/html", true);
+        assert_accepts("*/*; q=0", "text/html", false);
+        assert_accepts("text/*; q=0, */*", "text/html", false);
+        assert_accepts("text/*; q=0, */*", "image/png", true);
+        assert_accepts("text/*, text/html; q=0", "text/plain", true);
+        assert_accepts("text/*, text/html; q=0.0", "text/html", false);
+
+        // Parameters.
+        let accept = "text/plain; charset=utf-8; q=0, text/*";
+        assert_accepts(accept, "text/plain; charset=UTF-8", false);
+        assert_accepts(accept, "text/plain", true);
+        assert_accepts("text/plain; format=flowed", "text/plain", false);
+    }
+
     #[test]
     fn test_preferred() {
         assert_preference("text/*", "text/*");
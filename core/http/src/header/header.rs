@@ -181,6 +181,14 @@ impl fmt::Display for Header<'_> {
 
 /// A collection of headers, mapping a header name to its many ordered values.
 ///
+/// # Ordering
+///
+/// Iteration order is deterministic. Header names are iterated in the order in
+/// which they were first added, and the values for a given name are iterated
+/// in the order in which they were added. For headers whose values are
+/// semantically ordered, like `Via` or `Forwarded`, this is the order in which
+/// they arrived. Removing a header does not reorder the remaining headers.
+///
 /// # Case-Insensitivity
 ///
 /// All header names, including those passed in to `HeaderMap` methods and those
@@ -547,13 +555,13 @@ impl<'h> HeaderMap<'h> {
     /// assert_eq!(map.len(), 1);
     #[inline(always)]
     pub fn remove(&mut self, name: &str) {
-        self.headers.swap_remove(UncasedStr::new(name));
+        self.headers.shift_remove(UncasedStr::new(name));
     }
 
     /// Removes all of the headers stored in this map and returns a vector
-    /// containing them. Header names are returned in no specific order, but all
-    /// values for a given header name are grouped together, and values are in
-    /// FIFO order.
+    /// containing them. Header names are returned in the order they were first
+    /// added, all values for a given header name are grouped together, and
+    /// values are in FIFO order.
     ///
     /// # Example
     ///
@@ -593,8 +601,8 @@ impl<'h> HeaderMap<'h> {
     }
 
     /// Returns an iterator over all of the `Header`s stored in the map. Header
-    /// names are returned in no specific order, but all values for a given
-    /// header name are grouped together, and values are in FIFO order.
+    /// names are returned in the order they were first added, all values for a
+    /// given header name are grouped together, and values are in FIFO order.
     ///
     /// # Example
     ///
@@ -637,9 +645,9 @@ impl<'h> HeaderMap<'h> {
     }
 
     /// Consumes `self` and returns an iterator over all of the `Header`s stored
-    /// in the map. Header names are returned in no specific order, but all
-    /// values for a given header name are grouped together, and values are in
-    /// FIFO order.
+    /// in the map. Header names are returned in the order they were first
+    /// added, all values for a given header name are grouped together, and
+    /// values are in FIFO order.
     ///
     /// # Example
     ///
@@ -733,4 +741,18 @@ mod tests {
         let vals: Vec<_> = map.get("x-CuStOm").collect();
         assert_eq!(vals, vec!["a", "b", "c"]);
     }
+
+    #[test]
+    fn remove_preserves_order() {
+        let mut map = HeaderMap::new();
+        map.add_raw("Via", "1.0 a");
+        map.add_raw("X-One", "1");
+        map.add_raw("X-Two", "2");
+        map.add_raw("via", "1.1 b");
+        map.add_raw("X-Three", "3");
+
+        map.remove("X-One");
+        let headers: Vec<_> = map.iter().map(|h| h.to_string()).collect();
+        assert_eq!(headers, vec!["Via: 1.0 a", "Via: 1.1 b", "X-Two: 2", "X-Three: 3"]);
+    }
 }
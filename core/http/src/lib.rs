@@ -24,6 +24,7 @@ mod docify;
 #[macro_use]
 mod header;
 mod method;
+mod version;
 mod status;
 mod raw_str;
 mod parse;
@@ -45,6 +46,9 @@ pub mod private {
     pub use crate::parse::Indexed;
     pub use smallvec::{SmallVec, Array};
     pub use crate::listener::{TcpListener, Incoming, Listener, Connection, Certificates};
+    pub use crate::listener::{TlsSession, TlsParameters};
+    #[cfg(unix)]
+    pub use crate::listener::{UnixListener, UnixStream};
     pub use cookie;
 }
 
@@ -53,6 +57,8 @@ pub mod private {
 pub mod tls;
 
 pub use crate::method::Method;
+pub use crate::version::HttpVersion;
+pub use crate::listener::{UnixPeer, PeerCredentials};
 pub use crate::status::{Status, StatusClass};
 pub use crate::raw_str::{RawStr, RawStrBuf};
 pub use crate::header::*;
@@ -2,6 +2,7 @@ use std::fmt;
 use std::future::Future;
 use std::io;
 use std::net::SocketAddr;
+use std::path::PathBuf;
 use std::pin::Pin;
 use std::task::{Context, Poll};
 use std::time::Duration;
@@ -45,6 +46,57 @@ impl Certificates {
     }
 }
 
+/// The protocol version, cipher suite, and server name negotiated for a TLS
+/// connection.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TlsParameters {
+    /// The negotiated protocol version, e.g. `TLSv1_3`.
+    pub protocol: String,
+    /// The negotiated cipher suite, e.g. `TLS13_AES_128_GCM_SHA256`.
+    pub cipher_suite: String,
+    /// The server name the client sent via SNI, if any.
+    pub server_name: Option<String>,
+}
+
+/// The parameters of a TLS session, available once the handshake completes.
+#[derive(Clone, Default)]
+pub struct TlsSession(Arc<Storage<TlsParameters>>);
+
+impl TlsSession {
+    /// Set the negotiated parameters. Only the first call actually sets the
+    /// data; the remaining do nothing.
+    pub fn set(&self, parameters: TlsParameters) {
+        self.0.set(parameters);
+    }
+
+    /// Returns the negotiated parameters, if the handshake has completed.
+    pub fn parameters(&self) -> Option<&TlsParameters> {
+        self.0.try_get()
+    }
+}
+
+/// The credentials of the process on the other end of a Unix domain socket,
+/// as reported by the operating system (`SO_PEERCRED` on Linux).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct PeerCredentials {
+    /// The effective user ID of the peer process.
+    pub uid: u32,
+    /// The effective group ID of the peer process.
+    pub gid: u32,
+    /// The process ID of the peer process, if the platform reports it.
+    pub pid: Option<i32>,
+}
+
+/// The peer of a connection made over a Unix domain socket.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct UnixPeer {
+    /// The filesystem path the peer's socket is bound to, if any. Clients
+    /// rarely bind their sockets, so this is usually `None`.
+    pub path: Option<PathBuf>,
+    /// The peer process's credentials, if the platform makes them available.
+    pub credentials: Option<PeerCredentials>,
+}
+
 // TODO.async: 'Listener' and 'Connection' provide common enough functionality
 // that they could be introduced in upstream libraries.
 /// A 'Listener' yields incoming connections
@@ -82,6 +134,16 @@ pub trait Connection: AsyncRead + AsyncWrite {
     /// Defaults to an empty vector to indicate that no certificates were
     /// presented.
     fn peer_certificates(&self) -> Option<Certificates> { None }
+
+    /// The TLS session of the connection, if it is a TLS connection.
+    ///
+    /// Defaults to `None` to indicate a plaintext connection.
+    fn tls_session(&self) -> Option<TlsSession> { None }
+
+    /// The peer of the connection, if it is a Unix domain socket connection.
+    ///
+    /// Defaults to `None` to indicate a TCP connection.
+    fn unix_peer(&self) -> Option<UnixPeer> { None }
 }
 
 pin_project_lite::pin_project! {
@@ -239,3 +301,52 @@ impl Connection for TcpStream {
         self.set_nodelay(true)
     }
 }
+
+#[cfg(unix)]
+pub use tokio::net::{UnixListener, UnixStream};
+
+#[cfg(unix)]
+impl Listener for UnixListener {
+    type Connection = UnixStream;
+
+    /// Unix domain sockets have no `SocketAddr`; this always returns `None`.
+    #[inline]
+    fn local_addr(&self) -> Option<SocketAddr> {
+        None
+    }
+
+    #[inline]
+    fn poll_accept(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>
+    ) -> Poll<io::Result<Self::Connection>> {
+        (*self).poll_accept(cx).map_ok(|(stream, _addr)| stream)
+    }
+}
+
+#[cfg(unix)]
+impl Connection for UnixStream {
+    #[inline]
+    fn peer_address(&self) -> Option<SocketAddr> {
+        None
+    }
+
+    /// Unix domain sockets don't delay writes; this is a no-op.
+    #[inline]
+    fn enable_nodelay(&self) -> io::Result<()> {
+        Ok(())
+    }
+
+    fn unix_peer(&self) -> Option<UnixPeer> {
+        let path = self.peer_addr().ok()
+            .and_then(|addr| addr.as_pathname().map(|p| p.to_path_buf()));
+
+        let credentials = self.peer_cred().ok().map(|cred| PeerCredentials {
+            uid: cred.uid(),
+            gid: cred.gid(),
+            pid: cred.pid(),
+        });
+
+        Some(UnixPeer { path, credentials })
+    }
+}
@@ -11,10 +11,7 @@ type Result<'a, T> = pear::input::Result<T, Input<'a>>;
 #[parser]
 fn weighted_media_type<'a>(input: &mut Input<'a>) -> Result<'a, QMediaType> {
     let media_type = media_type()?;
-    let q = match media_type.params().next() {
-        Some((name, value)) if name  == "q" => Some(value),
-        _ => None
-    };
+    let q = media_type.params().find(|(name, _)| name == "q").map(|(_, value)| value);
 
     let weight = match q {
         Some(value) if value.len() <= 5 => match value.parse::<f32>().ok() {
@@ -87,4 +84,11 @@ mod test {
         assert_parse_eq!("text/html, text/html; q=0.1, text/html; q=0.2",
                          [MediaType::HTML, MediaType::HTML, MediaType::HTML]);
     }
+
+    #[test]
+    fn check_weights() {
+        let accept = assert_parse!("text/html, a/b; q=0.5, text/html;level=2;q=0, */*;q=1");
+        let weights: Vec<_> = accept.iter().map(|m| m.weight()).collect();
+        assert_eq!(weights, [None, Some(0.5), Some(0.0), Some(1.0)]);
+    }
 }
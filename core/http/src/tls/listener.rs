@@ -10,7 +10,7 @@ use tokio::io::{AsyncRead, AsyncWrite};
 use tokio_rustls::{Accept, TlsAcceptor, server::TlsStream as BareTlsStream};
 
 use crate::tls::util::{load_certs, load_private_key, load_ca_certs};
-use crate::listener::{Connection, Listener, Certificates};
+use crate::listener::{Connection, Listener, Certificates, TlsSession, TlsParameters};
 
 /// A TLS listener over TCP.
 pub struct TlsListener {
@@ -47,10 +47,13 @@ pub struct TlsListener {
 /// certificate data when we have a `Request` object, which implies we're
 /// receiving payload data, which implies the TLS handshake has finished, so the
 /// certificate data as seen by a Rocket application will always be "fresh".
+/// The same is true of the negotiated protocol version and cipher suite,
+/// which are stored in a `TlsSession`.
 pub struct TlsStream {
     remote: SocketAddr,
     state: TlsState,
     certs: Certificates,
+    session: TlsSession,
 }
 
 /// State of `TlsStream`.
@@ -136,6 +139,7 @@ impl Listener for TlsListener {
                 state: TlsState::Handshaking(self.acceptor.accept(io)),
                 // These are empty and filled in after handshake is complete.
                 certs: Certificates::default(),
+                session: TlsSession::default(),
             })),
             Err(e) => Poll::Ready(Err(e)),
         }
@@ -164,6 +168,10 @@ impl Connection for TlsStream {
     fn peer_certificates(&self) -> Option<Certificates> {
         Some(self.certs.clone())
     }
+
+    fn tls_session(&self) -> Option<TlsSession> {
+        Some(self.session.clone())
+    }
 }
 
 impl TlsStream {
@@ -179,10 +187,20 @@ impl TlsStream {
                 TlsState::Handshaking(ref mut accept) => {
                     match futures::ready!(Pin::new(accept).poll(cx)) {
                         Ok(stream) => {
-                            if let Some(cert_chain) = stream.get_ref().1.peer_certificates() {
+                            let conn = stream.get_ref().1;
+                            if let Some(cert_chain) = conn.peer_certificates() {
                                 self.certs.set(cert_chain.to_vec());
                             }
 
+                            let version = conn.protocol_version();
+                            if let (Some(v), Some(s)) = (version, conn.negotiated_cipher_suite()) {
+                                self.session.set(TlsParameters {
+                                    protocol: format!("{:?}", v),
+                                    cipher_suite: format!("{:?}", s.suite()),
+                                    server_name: conn.sni_hostname().map(String::from),
+                                });
+                            }
+
                             self.state = TlsState::Streaming(stream);
                         }
                         Err(e) => {
@@ -137,6 +137,68 @@ impl<'a> Segments<'a, Path> {
             .map(|i| i.from_source(Some(self.source.as_str())))
     }
 
+    /// Returns an iterator over the remaining non-empty segments in their raw,
+    /// undecoded form.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::http::uri::Origin;
+    ///
+    /// let uri = Origin::parse("/a%20z//b%2Fc/d").unwrap();
+    /// let raw: Vec<_> = uri.path().segments().raw().collect();
+    /// assert_eq!(raw, &["a%20z", "b%2Fc", "d"]);
+    ///
+    /// let raw: Vec<_> = uri.path().segments().skip(1).raw().collect();
+    /// assert_eq!(raw, &["b%2Fc", "d"]);
+    /// ```
+    #[inline]
+    pub fn raw(&self) -> impl Iterator<Item = &'a RawStr> {
+        self.source.split(Path::DELIMITER)
+            .filter(|s| !s.is_empty())
+            .skip(self.pos)
+    }
+
+    /// Returns an iterator over the remaining percent-decoded segments,
+    /// including the empty segments between two non-empty segments, as in
+    /// `a//b`. Leading and trailing slashes do not produce empty segments.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::http::uri::Origin;
+    ///
+    /// let uri = Origin::parse("/a%20z//b///c/").unwrap();
+    /// let segments: Vec<_> = uri.path().segments().with_empty().collect();
+    /// assert_eq!(segments, &["a z", "", "b", "", "", "c"]);
+    ///
+    /// let segments: Vec<_> = uri.path().segments().skip(1).with_empty().collect();
+    /// assert_eq!(segments, &["b", "", "", "c"]);
+    /// ```
+    pub fn with_empty(&self) -> impl Iterator<Item = &'a str> {
+        let mut decoded = Vec::with_capacity(self.len());
+        let (mut index, mut empty) = (0, 0);
+        for raw in self.source.split(Path::DELIMITER) {
+            if raw.is_empty() {
+                empty += 1;
+                continue;
+            }
+
+            if index > self.pos {
+                decoded.extend(std::iter::repeat("").take(empty));
+            }
+
+            if index >= self.pos {
+                decoded.extend(self.get(index - self.pos));
+            }
+
+            index += 1;
+            empty = 0;
+        }
+
+        decoded.into_iter()
+    }
+
     /// Returns `true` if `self` is a prefix of `other`.
     ///
     /// # Example
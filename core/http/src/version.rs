@@ -0,0 +1,93 @@
+use std::fmt;
+
+use self::HttpVersion::*;
+
+use crate::hyper;
+
+/// The version of HTTP a request was received over.
+///
+/// Versions are ordered by recency, so a version can be compared against a
+/// minimum:
+///
+/// ```rust
+/// # extern crate rocket;
+/// use rocket::http::HttpVersion;
+///
+/// assert!(HttpVersion::Http2 >= HttpVersion::Http11);
+/// assert!(HttpVersion::Http10 < HttpVersion::Http11);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum HttpVersion {
+    /// `HTTP/1.0`.
+    Http10,
+    /// `HTTP/1.1`.
+    Http11,
+    /// `HTTP/2`.
+    Http2,
+    /// `HTTP/3`.
+    Http3,
+}
+
+impl HttpVersion {
+    /// WARNING: This is unstable! Do not use this method outside of Rocket!
+    #[doc(hidden)]
+    pub fn from_hyp(version: &hyper::Version) -> Option<HttpVersion> {
+        match *version {
+            hyper::Version::HTTP_10 => Some(Http10),
+            hyper::Version::HTTP_11 => Some(Http11),
+            hyper::Version::HTTP_2 => Some(Http2),
+            hyper::Version::HTTP_3 => Some(Http3),
+            _ => None,
+        }
+    }
+
+    /// Returns `true` if `self` multiplexes requests over a single
+    /// connection, that is, if `self` is `HTTP/2` or `HTTP/3`. Connection
+    /// management headers like `Connection: keep-alive` have no meaning in
+    /// these versions.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # extern crate rocket;
+    /// use rocket::http::HttpVersion;
+    ///
+    /// assert!(HttpVersion::Http2.is_multiplexed());
+    /// assert!(!HttpVersion::Http11.is_multiplexed());
+    /// ```
+    #[inline]
+    pub fn is_multiplexed(self) -> bool {
+        match self {
+            Http2 | Http3 => true,
+            Http10 | Http11 => false,
+        }
+    }
+
+    /// Returns the string representation of `self`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # extern crate rocket;
+    /// use rocket::http::HttpVersion;
+    ///
+    /// assert_eq!(HttpVersion::Http11.as_str(), "HTTP/1.1");
+    /// assert_eq!(HttpVersion::Http2.as_str(), "HTTP/2");
+    /// ```
+    #[inline]
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Http10 => "HTTP/1.0",
+            Http11 => "HTTP/1.1",
+            Http2 => "HTTP/2",
+            Http3 => "HTTP/3",
+        }
+    }
+}
+
+impl fmt::Display for HttpVersion {
+    #[inline(always)]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.as_str().fmt(f)
+    }
+}
@@ -0,0 +1,44 @@
+//! Compares the cost of extracting a path segment as a `String` and as a
+//! `Cow<str>`, which only allocates when the segment has percent-escapes.
+//!
+//! Run with `cargo bench --bench segment-params`.
+
+use std::borrow::Cow;
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+use rocket::http::RawStr;
+use rocket::request::FromParam;
+
+const ITERATIONS: u32 = 1_000_000;
+
+fn time<F: FnMut()>(mut f: F) -> Duration {
+    let start = Instant::now();
+    for _ in 0..ITERATIONS {
+        f();
+    }
+
+    start.elapsed() / ITERATIONS
+}
+
+fn bench(name: &str, raw: &str) {
+    let raw = RawStr::new(raw);
+    let decoded = raw.percent_decode_lossy();
+
+    let string = time(|| {
+        let param = <String as FromParam>::from_raw_param(black_box(raw), &decoded);
+        black_box(param.unwrap());
+    });
+
+    let cow = time(|| {
+        let param = <Cow<'_, str> as FromParam>::from_raw_param(black_box(raw), &decoded);
+        black_box(param.unwrap());
+    });
+
+    println!("{:<10} String: {:>6?}/iter  Cow<str>: {:>6?}/iter", name, string, cow);
+}
+
+fn main() {
+    bench("plain", "some-fairly-long-path-segment-without-escapes");
+    bench("escaped", "some%20fairly%20long%20path%20segment%20with%20escapes");
+}
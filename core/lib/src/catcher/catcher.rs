@@ -29,6 +29,16 @@ use yansi::Paint;
 /// response. If an error handler _does_ fail, Rocket invokes its default `500`
 /// error catcher. Error handlers cannot forward.
 ///
+/// ### Request-Local State
+///
+/// An error handler is invoked with the same [`Request`] that caused the
+/// error. Values stored in its [local cache](Request::local_cache()),
+/// including by [`local_cache!`](crate::request::local_cache), by guards or
+/// handlers that ran before the error persist and can be read by the error
+/// handler. Guards that the error handler runs can use
+/// [`Request::is_in_catcher()`] to detect that they are being run during
+/// error handling.
+///
 /// # Routing
 ///
 /// An error arising from a particular request _matches_ a catcher _iff_:
@@ -0,0 +1,320 @@
+//! Response compression negotiated via `Accept-Encoding`.
+//!
+//! The [`Compression`] fairing compresses response bodies with the best
+//! [`Encoding`] accepted by the client, as determined by the q-values in the
+//! request's `Accept-Encoding` header. The body is compressed as it's streamed
+//! to the client, so it's never buffered in its entirety. Compressed responses
+//! carry a `Content-Encoding` header; every negotiated response carries `Vary:
+//! Accept-Encoding` so that caches keep the representations apart.
+//!
+//! A response is left as-is if it:
+//!
+//!   * has no body or already has a `Content-Encoding`,
+//!   * has a status of `204`, `206`, or `304`,
+//!   * has a `Cache-Control: no-transform` header,
+//!   * has a content type that is already compressed, like images other than
+//!     SVG, video, audio, and archives, or that must not be buffered, like
+//!     `text/event-stream`, or
+//!   * has a body smaller than the configured [threshold], which defaults to
+//!     [`Compression::DEFAULT_THRESHOLD`] bytes.
+//!
+//! Unless, that is, the client has refused the `identity` encoding with
+//! `identity;q=0` or `*;q=0`: such a response is then compressed anyway. If
+//! the client accepts neither `identity` nor any supported encoding, the
+//! response is replaced with an empty `406 Not Acceptable`.
+//!
+//! A request without an `Accept-Encoding` header is sent an uncompressed
+//! response.
+//!
+//! [threshold]: Compression::threshold()
+//!
+//! # Example
+//!
+//! ```rust
+//! # #[macro_use] extern crate rocket;
+//! use rocket::compress::Compression;
+//!
+//! #[get("/")]
+//! fn index() -> String {
+//!     "All work and no play makes Jack a dull boy. ".repeat(100)
+//! }
+//!
+//! #[launch]
+//! fn rocket() -> _ {
+//!     rocket::build()
+//!         .attach(Compression::new().threshold(512))
+//!         .mount("/", routes![index])
+//! }
+//! ```
+
+use std::fmt;
+
+use async_compression::Level;
+use async_compression::tokio::bufread::{BrotliEncoder, GzipEncoder};
+use tokio::io::BufReader;
+
+use crate::{Request, Response};
+use crate::fairing::{Fairing, Info, Kind};
+use crate::http::{ContentType, Header, Status};
+
+/// Content types, as `(top, sub)`, whose bodies are left uncompressed in
+/// addition to `image/*` other than `image/svg+xml`, `video/*`, and `audio/*`.
+const INCOMPRESSIBLE: &[(&str, &str)] = &[
+    ("application", "zip"), ("application", "gzip"), ("application", "x-gzip"),
+    ("application", "x-bzip2"), ("application", "x-xz"), ("application", "zstd"),
+    ("application", "x-7z-compressed"), ("application", "vnd.rar"),
+    ("application", "x-rar-compressed"), ("application", "pdf"),
+    ("font", "woff"), ("font", "woff2"), ("text", "event-stream"),
+];
+
+/// A content coding supported by the [`Compression`] fairing.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Encoding {
+    /// `br`: Brotli, at quality 4. Preferred over `gzip` when the client
+    /// accepts both equally.
+    Brotli,
+    /// `gzip`, also accepted as `x-gzip`, at the default level.
+    Gzip,
+}
+
+impl Encoding {
+    /// Returns the name of the encoding as used in a `Content-Encoding`
+    /// header.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::compress::Encoding;
+    ///
+    /// assert_eq!(Encoding::Brotli.name(), "br");
+    /// assert_eq!(Encoding::Gzip.name(), "gzip");
+    /// ```
+    pub fn name(self) -> &'static str {
+        match self {
+            Encoding::Brotli => "br",
+            Encoding::Gzip => "gzip",
+        }
+    }
+
+    /// Returns `true` if `coding` is a name for `self`, ignoring case.
+    fn matches(self, coding: &str) -> bool {
+        match self {
+            Encoding::Brotli => coding.eq_ignore_ascii_case("br"),
+            Encoding::Gzip => {
+                coding.eq_ignore_ascii_case("gzip") || coding.eq_ignore_ascii_case("x-gzip")
+            }
+        }
+    }
+}
+
+impl fmt::Display for Encoding {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.name().fmt(f)
+    }
+}
+
+/// A fairing that compresses response bodies as negotiated via
+/// `Accept-Encoding`.
+///
+/// See the [module level docs](self) for details and an example.
+#[derive(Debug, Clone)]
+pub struct Compression {
+    threshold: usize,
+}
+
+/// A parsed `Accept-Encoding` header.
+struct AcceptEncoding<'a> {
+    /// Each coding with its q-value in thousandths, in order of appearance.
+    codings: Vec<(&'a str, u16)>,
+}
+
+impl Compression {
+    /// The default minimum size of a body that is compressed: 1KiB.
+    pub const DEFAULT_THRESHOLD: usize = 1024;
+
+    /// Returns a compression fairing with the default threshold.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::compress::Compression;
+    ///
+    /// let rocket = rocket::build().attach(Compression::new());
+    /// ```
+    pub fn new() -> Self {
+        Compression { threshold: Self::DEFAULT_THRESHOLD }
+    }
+
+    /// Sets the minimum size, in bytes, of a body that is compressed. Bodies
+    /// of an unknown size, like streamed bodies, are always compressed.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::compress::Compression;
+    ///
+    /// // Compress bodies of at least 256 bytes.
+    /// let compression = Compression::new().threshold(256);
+    /// ```
+    pub fn threshold(mut self, bytes: usize) -> Self {
+        self.threshold = bytes;
+        self
+    }
+}
+
+impl Default for Compression {
+    fn default() -> Self {
+        Compression::new()
+    }
+}
+
+impl<'a> AcceptEncoding<'a> {
+    /// Parses `header`. Entries with an invalid q-value are ignored.
+    fn parse(header: &'a str) -> Self {
+        let codings = header.split(',')
+            .filter_map(|entry| {
+                let mut parts = entry.split(';').map(|part| part.trim());
+                let coding = parts.next().filter(|coding| !coding.is_empty())?;
+                let q = parts
+                    .find_map(|param| param.strip_prefix("q=").or(param.strip_prefix("Q=")))
+                    .map_or(Some(1000), parse_q)?;
+
+                Some((coding, q))
+            })
+            .collect();
+
+        AcceptEncoding { codings }
+    }
+
+    /// Returns the q-value, in thousandths, of `encoding`, where `None` is
+    /// `identity`.
+    fn q(&self, encoding: Option<Encoding>) -> u16 {
+        let explicit = self.codings.iter().find(|(coding, _)| match encoding {
+            Some(encoding) => encoding.matches(coding),
+            None => coding.eq_ignore_ascii_case("identity"),
+        });
+
+        let wildcard = || self.codings.iter().find(|(coding, _)| *coding == "*");
+        match explicit.or_else(wildcard) {
+            Some(&(_, q)) => q,
+            None if encoding.is_none() => 1000,
+            None => 0,
+        }
+    }
+
+    /// Returns the acceptable candidate with the highest q-value, preferring
+    /// earlier candidates, or `None` if no candidate is acceptable.
+    fn best(&self, candidates: &[Option<Encoding>]) -> Option<Option<Encoding>> {
+        let mut best = None;
+        for &candidate in candidates {
+            let q = self.q(candidate);
+            if q > 0 && best.map_or(true, |(_, best_q)| q > best_q) {
+                best = Some((candidate, q));
+            }
+        }
+
+        best.map(|(candidate, _)| candidate)
+    }
+}
+
+/// Parses a q-value into thousandths.
+fn parse_q(value: &str) -> Option<u16> {
+    let q: f32 = value.parse().ok()?;
+    match (0.0..=1.0).contains(&q) {
+        true => Some((q * 1000.0).round() as u16),
+        false => None,
+    }
+}
+
+/// Returns `true` if a body of type `content_type` is worth compressing.
+fn is_compressible(content_type: Option<ContentType>) -> bool {
+    let content_type = match content_type {
+        Some(content_type) => content_type,
+        None => return true,
+    };
+
+    let (top, sub) = (content_type.top(), content_type.sub());
+    if top == "image" {
+        return sub == "svg+xml";
+    }
+
+    if top == "video" || top == "audio" {
+        return false;
+    }
+
+    !INCOMPRESSIBLE.iter().any(|&(t, s)| top == t && sub == s)
+}
+
+#[crate::async_trait]
+impl Fairing for Compression {
+    fn info(&self) -> Info {
+        Info {
+            name: "Compression",
+            kind: Kind::Response | Kind::Singleton,
+        }
+    }
+
+    async fn on_response<'r>(&self, req: &'r Request<'_>, res: &mut Response<'r>) {
+        if res.body().is_none() || res.headers().contains("Content-Encoding") {
+            return;
+        }
+
+        if matches!(res.status().code, 204 | 206 | 304) {
+            return;
+        }
+
+        let no_transform = res.headers().get("Cache-Control")
+            .flat_map(|value| value.split(','))
+            .any(|directive| directive.trim().eq_ignore_ascii_case("no-transform"));
+
+        if no_transform {
+            return;
+        }
+
+        let varies = res.headers().get("Vary")
+            .flat_map(|value| value.split(','))
+            .any(|v| v.trim() == "*" || v.trim().eq_ignore_ascii_case("Accept-Encoding"));
+
+        if !varies {
+            res.adjoin_header(Header::new("Vary", "Accept-Encoding"));
+        }
+
+        let accept = AcceptEncoding::parse(req.headers().get_one("Accept-Encoding").unwrap_or(""));
+        let eligible = is_compressible(res.content_type())
+            && res.body_mut().size().await.map_or(true, |size| size >= self.threshold);
+
+        let choice = match eligible {
+            true => accept.best(&[Some(Encoding::Brotli), Some(Encoding::Gzip), None]),
+            false if accept.q(None) > 0 => return,
+            false => accept.best(&[Some(Encoding::Brotli), Some(Encoding::Gzip)]),
+        };
+
+        let encoding = match choice {
+            Some(Some(encoding)) => encoding,
+            Some(None) => return,
+            None => {
+                res.set_status(Status::NotAcceptable);
+                res.remove_header("Content-Type");
+                res.body_mut().take();
+                return;
+            }
+        };
+
+        // The compressed body is a different representation: a strong entity
+        // tag of the uncompressed body no longer applies to it byte-for-byte.
+        if let Some(etag) = res.headers().get_one("ETag").filter(|tag| tag.starts_with('"')) {
+            let weak = format!("W/{}", etag);
+            res.set_header(Header::new("ETag", weak));
+        }
+
+        let body = BufReader::new(res.body_mut().take());
+        match encoding {
+            Encoding::Brotli => {
+                res.set_streamed_body(BrotliEncoder::with_quality(body, Level::Precise(4)))
+            }
+            Encoding::Gzip => res.set_streamed_body(GzipEncoder::new(body)),
+        }
+
+        res.set_header(Header::new("Content-Encoding", encoding.name()));
+    }
+}
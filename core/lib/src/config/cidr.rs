@@ -0,0 +1,207 @@
+use std::fmt;
+use std::str::FromStr;
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+
+use serde::{de, Serialize, Serializer, Deserialize, Deserializer};
+
+/// A block of IP addresses in CIDR notation.
+///
+/// An `IpCidr` is an IP address and a prefix length. It contains every address
+/// whose first `prefix` bits match those of the address. `IpCidr`s are used in
+/// [`Config::trusted_proxies`](crate::Config::trusted_proxies) to identify the
+/// reverse proxies whose `X-Forwarded-For` headers Rocket should believe.
+///
+/// # Parsing
+///
+/// An `IpCidr` parses from a string of the form `address/prefix`, such as
+/// `10.0.0.0/8` or `fd00::/8`, or from a bare IP address, which is treated as
+/// a block containing only that address. The prefix must be at most `32` for
+/// IPv4 and `128` for IPv6 addresses.
+///
+/// ## (De)serialization
+///
+/// An `IpCidr` serializes and deserializes as a string in the format above.
+///
+/// # Example
+///
+/// ```rust
+/// use std::net::IpAddr;
+/// use rocket::config::IpCidr;
+///
+/// let block: IpCidr = "10.0.0.0/8".parse().unwrap();
+/// assert!(block.contains("10.1.2.3".parse().unwrap()));
+/// assert!(!block.contains("11.0.0.1".parse().unwrap()));
+///
+/// let single: IpCidr = "::1".parse().unwrap();
+/// assert_eq!(single.prefix(), 128);
+/// assert!(single.contains(IpAddr::from([0, 0, 0, 0, 0, 0, 0, 1])));
+///
+/// assert!("10.0.0.0/33".parse::<IpCidr>().is_err());
+/// assert!("localhost".parse::<IpCidr>().is_err());
+/// ```
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct IpCidr {
+    addr: IpAddr,
+    prefix: u8,
+}
+
+impl IpCidr {
+    /// Returns a new `IpCidr` for the block of addresses matching the first
+    /// `prefix` bits of `addr`. Returns `None` if `prefix` exceeds the length
+    /// of `addr` in bits.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use std::net::Ipv4Addr;
+    /// use rocket::config::IpCidr;
+    ///
+    /// let block = IpCidr::new(Ipv4Addr::new(192, 168, 0, 0).into(), 16).unwrap();
+    /// assert_eq!(block.to_string(), "192.168.0.0/16");
+    ///
+    /// assert!(IpCidr::new(Ipv4Addr::LOCALHOST.into(), 33).is_none());
+    /// ```
+    pub fn new(addr: IpAddr, prefix: u8) -> Option<IpCidr> {
+        let max = if addr.is_ipv4() { 32 } else { 128 };
+        if prefix > max {
+            return None;
+        }
+
+        Some(IpCidr { addr, prefix })
+    }
+
+    /// Returns the network address of `self`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use std::net::Ipv4Addr;
+    /// use rocket::config::IpCidr;
+    ///
+    /// let block: IpCidr = "172.16.0.0/12".parse().unwrap();
+    /// assert_eq!(block.addr(), Ipv4Addr::new(172, 16, 0, 0));
+    /// ```
+    pub fn addr(&self) -> IpAddr {
+        self.addr
+    }
+
+    /// Returns the prefix length of `self` in bits.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::config::IpCidr;
+    ///
+    /// let block: IpCidr = "172.16.0.0/12".parse().unwrap();
+    /// assert_eq!(block.prefix(), 12);
+    /// ```
+    pub fn prefix(&self) -> u8 {
+        self.prefix
+    }
+
+    /// Returns `true` if `ip` is in the block of addresses `self` describes.
+    ///
+    /// IPv4-mapped IPv6 addresses, such as `::ffff:10.0.0.1`, are compared as
+    /// the IPv4 address they map to.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::config::IpCidr;
+    ///
+    /// let block: IpCidr = "10.0.0.0/8".parse().unwrap();
+    /// assert!(block.contains("10.20.30.40".parse().unwrap()));
+    /// assert!(block.contains("::ffff:10.0.0.1".parse().unwrap()));
+    /// assert!(!block.contains("192.168.1.1".parse().unwrap()));
+    /// assert!(!block.contains("fd00::1".parse().unwrap()));
+    /// ```
+    pub fn contains(&self, ip: IpAddr) -> bool {
+        fn mask(bits: u8, prefix: u8) -> u128 {
+            match prefix {
+                0 => 0,
+                n => (!0u128 << (bits - n)) & (!0u128 >> (128 - bits))
+            }
+        }
+
+        match (self.addr, to_canonical(ip)) {
+            (IpAddr::V4(net), IpAddr::V4(ip)) => {
+                let mask = mask(32, self.prefix);
+                u32::from(net) as u128 & mask == u32::from(ip) as u128 & mask
+            }
+            (IpAddr::V6(net), IpAddr::V6(ip)) => {
+                let mask = mask(128, self.prefix);
+                u128::from(net) & mask == u128::from(ip) & mask
+            }
+            _ => false,
+        }
+    }
+}
+
+/// Converts IPv4-mapped IPv6 addresses to IPv4 and leaves all others be.
+fn to_canonical(ip: IpAddr) -> IpAddr {
+    match ip {
+        IpAddr::V6(v6) => match v6.segments() {
+            [0, 0, 0, 0, 0, 0xffff, ..] => IpAddr::V4(to_v4(v6)),
+            _ => IpAddr::V6(v6),
+        },
+        ip => ip,
+    }
+}
+
+fn to_v4(v6: Ipv6Addr) -> Ipv4Addr {
+    let [.., a, b, c, d] = v6.octets();
+    Ipv4Addr::new(a, b, c, d)
+}
+
+impl FromStr for IpCidr {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || format!("invalid CIDR block: {:?}", s);
+        let (addr, prefix) = match s.split_once('/') {
+            Some((addr, prefix)) => (addr, Some(prefix)),
+            None => (s, None),
+        };
+
+        let addr: IpAddr = addr.parse().map_err(|_| invalid())?;
+        let prefix = match prefix {
+            Some(prefix) => prefix.parse().map_err(|_| invalid())?,
+            None if addr.is_ipv4() => 32,
+            None => 128,
+        };
+
+        IpCidr::new(addr, prefix).ok_or_else(invalid)
+    }
+}
+
+impl fmt::Display for IpCidr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}/{}", self.addr, self.prefix)
+    }
+}
+
+impl Serialize for IpCidr {
+    fn serialize<S: Serializer>(&self, ser: S) -> Result<S::Ok, S::Error> {
+        ser.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for IpCidr {
+    fn deserialize<D: Deserializer<'de>>(de: D) -> Result<Self, D::Error> {
+        struct Visitor;
+
+        impl<'de> de::Visitor<'de> for Visitor {
+            type Value = IpCidr;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+                formatter.write_str("an IP address or CIDR block string")
+            }
+
+            fn visit_str<E: de::Error>(self, v: &str) -> Result<Self::Value, E> {
+                v.parse().map_err(|_| E::invalid_value(de::Unexpected::Str(v), &self))
+            }
+        }
+
+        de.deserialize_str(Visitor)
+    }
+}
@@ -1,4 +1,5 @@
 use std::net::{IpAddr, Ipv4Addr};
+use std::path::PathBuf;
 
 use figment::{Figment, Profile, Provider, Metadata, error::Result};
 use figment::providers::{Serialized, Env, Toml, Format};
@@ -6,7 +7,8 @@ use figment::value::{Map, Dict, magic::RelativePathBuf};
 use serde::{Deserialize, Serialize};
 use yansi::Paint;
 
-use crate::config::{LogLevel, Shutdown, Ident};
+use crate::config::{LogLevel, Shutdown, Deadline, HeaderLimits, Ident, IpCidr};
+use crate::config::{TrailingSlash, ForwardedHeader};
 use crate::request::{self, Request, FromRequest};
 use crate::data::Limits;
 
@@ -68,6 +70,15 @@ pub struct Config {
     pub address: IpAddr,
     /// Port to serve on. **(default: `8000`)**
     pub port: u16,
+    /// Path of a Unix domain socket to serve on instead of `address` and
+    /// `port`, if any. **(default: `None`)**
+    ///
+    /// A stale socket left at the path by a previous run is removed before
+    /// binding, and the socket is removed again on shutdown. Requests received
+    /// over the socket report it via [`Request::is_unix_socket()`]. TLS can't
+    /// be enabled along with a Unix domain socket, and Unix domain sockets are
+    /// only supported on Unix platforms.
+    pub unix_socket: Option<PathBuf>,
     /// Number of threads to use for executing futures. **(default: `num_cores`)**
     ///
     /// _**Note:** Rocket only reads this value from sources in the [default
@@ -96,13 +107,76 @@ pub struct Config {
     #[cfg_attr(nightly, doc(cfg(feature = "secrets")))]
     #[serde(serialize_with = "SecretKey::serialize_zero")]
     pub secret_key: SecretKey,
+    /// Whether flash cookies are sent and accepted without a signature.
+    /// **(default: `false`)**
+    ///
+    /// By default, flash cookies are signed with [`Config::secret_key`] and
+    /// flash cookies without a valid signature are ignored and removed. Set to
+    /// `true` to accept flash cookies set by versions of Rocket that didn't
+    /// sign them, at the cost of letting clients forge flash messages.
+    #[cfg(feature = "secrets")]
+    #[cfg_attr(nightly, doc(cfg(feature = "secrets")))]
+    #[serde(deserialize_with = "figment::util::bool_from_str_or_int")]
+    pub unsigned_flash: bool,
     /// Graceful shutdown configuration. **(default: [`Shutdown::default()`])**
     pub shutdown: Shutdown,
+    /// Per-request deadline configuration. **(default:
+    /// [`Deadline::default()`])**
+    pub deadline: Deadline,
+    /// Limits on the number and size of request headers. **(default:
+    /// [`HeaderLimits::default()`])**
+    pub header_limits: HeaderLimits,
+    /// Whether the method of a `POST` request may be overridden by an
+    /// `X-HTTP-Method-Override` header. **(default: `false`)**
+    ///
+    /// When enabled, a `POST` request with an `X-HTTP-Method-Override` header
+    /// of `PUT`, `PATCH`, or `DELETE` is routed as a request with that method.
+    /// The header is ignored on requests with any other method and when it has
+    /// any other value, so a `GET` can never be turned into a `DELETE`. The
+    /// received method remains available via
+    /// [`Request::original_method()`](crate::Request::original_method()).
+    ///
+    /// HTML forms can also override the method of a `POST` request with a
+    /// `_method` field; that field is honored irrespective of this setting.
+    #[serde(deserialize_with = "figment::util::bool_from_str_or_int")]
+    pub method_override: bool,
+    /// Whether `OPTIONS` requests without a matching route are answered
+    /// automatically and `405 Method Not Allowed` responses carry an `Allow`
+    /// header. **(default: `false`)**
+    ///
+    /// When enabled, the methods of all routes whose path and query match a
+    /// request, irrespective of method and format, are the methods allowed
+    /// for that request, always along with `OPTIONS`. An `OPTIONS` request
+    /// that isn't handled by a route is answered with a `204 No Content` with
+    /// an `Allow` header listing these methods. A request that no route
+    /// handles is answered with a `405 Method Not Allowed`, rather than a `404
+    /// Not Found`, when routes exist for its path but none for its method. Any
+    /// `405` response, including those returned by handlers, gets an `Allow`
+    /// header unless it already has one.
+    #[serde(deserialize_with = "figment::util::bool_from_str_or_int")]
+    pub auto_options: bool,
+    /// Whether and how requests are redirected to the canonical trailing
+    /// slash form of their path. **(default: [`TrailingSlash::Strict`])**
+    ///
+    /// See [`TrailingSlash`] for details.
+    pub trailing_slash: TrailingSlash,
     /// Max level to log. **(default: _debug_ `normal` / _release_ `critical`)**
     pub log_level: LogLevel,
     /// Whether to use colors and emoji when logging. **(default: `true`)**
     #[serde(deserialize_with = "figment::util::bool_from_str_or_int")]
     pub cli_colors: bool,
+    /// Reverse proxies trusted to report the client's IP address and the
+    /// request's scheme in the [`forwarded_header`](Config::forwarded_header).
+    /// **(default: `[]`)**
+    ///
+    /// See [`Request::real_client_ip()`] and [`Request::scheme()`] for details
+    /// on how this list is used.
+    pub trusted_proxies: Vec<IpCidr>,
+    /// The header trusted proxies report the client's IP address and the
+    /// request's scheme in. **(default: [`ForwardedHeader::XForwarded`])**
+    ///
+    /// See [`ForwardedHeader`] for details.
+    pub forwarded_header: ForwardedHeader,
     /// PRIVATE: This structure may grow (but never change otherwise) in a
     /// non-breaking release. As such, constructing this structure should
     /// _always_ be done using a public constructor or update syntax:
@@ -169,6 +243,7 @@ impl Config {
             profile: Self::DEBUG_PROFILE,
             address: Ipv4Addr::new(127, 0, 0, 1).into(),
             port: 8000,
+            unix_socket: None,
             workers: num_cpus::get(),
             ident: Ident::default(),
             limits: Limits::default(),
@@ -178,9 +253,18 @@ impl Config {
             tls: None,
             #[cfg(feature = "secrets")]
             secret_key: SecretKey::zero(),
+            #[cfg(feature = "secrets")]
+            unsigned_flash: false,
             shutdown: Shutdown::default(),
+            deadline: Deadline::default(),
+            header_limits: HeaderLimits::default(),
+            method_override: false,
+            auto_options: false,
+            trailing_slash: TrailingSlash::Strict,
             log_level: LogLevel::Normal,
             cli_colors: true,
+            trusted_proxies: vec![],
+            forwarded_header: ForwardedHeader::XForwarded,
             __non_exhaustive: (),
         }
     }
@@ -357,6 +441,11 @@ impl Config {
         launch_info!("{}Configured for {}.", Paint::emoji("🔧 "), self.profile);
         launch_info_!("address: {}", bold(&self.address));
         launch_info_!("port: {}", bold(&self.port));
+
+        if let Some(path) = &self.unix_socket {
+            launch_info_!("unix socket: {}", bold(path.display()));
+        }
+
         launch_info_!("workers: {}", bold(self.workers));
         launch_info_!("ident: {}", bold(&self.ident));
         launch_info_!("limits: {}", bold(&self.limits));
@@ -381,12 +470,31 @@ impl Config {
                 launch_info_!("disable `secrets` feature or configure a `secret_key`");
                 launch_info_!("this becomes an {} in non-debug profiles", Paint::red("error"));
             }
+
+            if self.unsigned_flash {
+                launch_info_!("flash cookies: {}", bold("unsigned"));
+            }
         }
 
         launch_info_!("shutdown: {}", bold(&self.shutdown));
+        launch_info_!("deadline: {}", bold(&self.deadline));
+        launch_info_!("header limits: {}", bold(&self.header_limits));
+        launch_info_!("method override: {}", bold(&self.method_override));
+        launch_info_!("auto options: {}", bold(&self.auto_options));
+        launch_info_!("trailing slash: {}", bold(&self.trailing_slash));
         launch_info_!("log level: {}", bold(self.log_level));
         launch_info_!("cli colors: {}", bold(&self.cli_colors));
 
+        match &*self.trusted_proxies {
+            [] => launch_info_!("trusted proxies: {}", bold("none")),
+            proxies => {
+                let proxies = proxies.iter().map(|p| p.to_string()).collect::<Vec<_>>();
+                launch_info_!("trusted proxies: {}", bold(proxies.join(", ")));
+            }
+        }
+
+        launch_info_!("forwarded header: {}", bold(&self.forwarded_header));
+
         // Check for now depreacted config values.
         for (key, replacement) in Self::DEPRECATED_KEYS {
             if let Some(md) = figment.find_metadata(key) {
@@ -448,6 +556,9 @@ impl Config {
     /// The stringy parameter name for setting/extracting [`Config::port`].
     pub const PORT: &'static str = "port";
 
+    /// The stringy parameter name for setting/extracting [`Config::unix_socket`].
+    pub const UNIX_SOCKET: &'static str = "unix_socket";
+
     /// The stringy parameter name for setting/extracting [`Config::workers`].
     pub const WORKERS: &'static str = "workers";
 
@@ -463,6 +574,10 @@ impl Config {
     /// The stringy parameter name for setting/extracting [`Config::secret_key`].
     pub const SECRET_KEY: &'static str = "secret_key";
 
+    /// The stringy parameter name for setting/extracting
+    /// [`Config::unsigned_flash`].
+    pub const UNSIGNED_FLASH: &'static str = "unsigned_flash";
+
     /// The stringy parameter name for setting/extracting [`Config::temp_dir`].
     pub const TEMP_DIR: &'static str = "temp_dir";
 
@@ -472,8 +587,35 @@ impl Config {
     /// The stringy parameter name for setting/extracting [`Config::shutdown`].
     pub const SHUTDOWN: &'static str = "shutdown";
 
+    /// The stringy parameter name for setting/extracting [`Config::deadline`].
+    pub const DEADLINE: &'static str = "deadline";
+
+    /// The stringy parameter name for setting/extracting
+    /// [`Config::header_limits`].
+    pub const HEADER_LIMITS: &'static str = "header_limits";
+
+    /// The stringy parameter name for setting/extracting
+    /// [`Config::method_override`].
+    pub const METHOD_OVERRIDE: &'static str = "method_override";
+
+    /// The stringy parameter name for setting/extracting
+    /// [`Config::auto_options`].
+    pub const AUTO_OPTIONS: &'static str = "auto_options";
+
+    /// The stringy parameter name for setting/extracting
+    /// [`Config::trailing_slash`].
+    pub const TRAILING_SLASH: &'static str = "trailing_slash";
+
     /// The stringy parameter name for setting/extracting [`Config::cli_colors`].
     pub const CLI_COLORS: &'static str = "cli_colors";
+
+    /// The stringy parameter name for setting/extracting
+    /// [`Config::trusted_proxies`].
+    pub const TRUSTED_PROXIES: &'static str = "trusted_proxies";
+
+    /// The stringy parameter name for setting/extracting
+    /// [`Config::forwarded_header`].
+    pub const FORWARDED_HEADER: &'static str = "forwarded_header";
 }
 
 impl Provider for Config {
@@ -0,0 +1,107 @@
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+/// Per-request deadline configuration.
+///
+/// A request's deadline, as returned by
+/// [`Request::deadline()`](crate::Request::deadline()), is the instant the
+/// request was received plus a timeout in milliseconds. The timeout is read
+/// from the request's `header`, if it holds a positive integer, and is
+/// `default` otherwise. Either way, it is clamped to at most `max`. A header
+/// value that isn't a positive integer is ignored.
+///
+/// Rocket itself never enforces the deadline; it is advisory, for handlers
+/// and guards that want to abandon expensive work that can no longer complete
+/// in time.
+///
+/// # Example
+///
+/// ```rust
+/// # use rocket::figment::{Figment, providers::{Format, Toml}};
+/// use rocket::Config;
+///
+/// // If these are the contents of `Rocket.toml`...
+/// # let toml = Toml::string(r#"
+/// [default.deadline]
+/// header = "X-Request-Timeout"
+/// default = 5000
+/// max = 10000
+/// # "#).nested();
+///
+/// // The config parses as follows:
+/// # let config = Config::from(Figment::from(Config::debug_default()).merge(toml));
+/// assert_eq!(config.deadline.header, "X-Request-Timeout");
+/// assert_eq!(config.deadline.default, 5000);
+/// assert_eq!(config.deadline.max, 10000);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Deadline {
+    /// The header to read a request's timeout, in milliseconds, from. The
+    /// header is not read when empty.
+    ///
+    /// **default: `"X-Timeout-Ms"`**
+    pub header: String,
+    /// The timeout, in milliseconds, of requests without a valid timeout
+    /// header. Such requests have no deadline when `0`.
+    ///
+    /// **default: `0`**
+    pub default: u64,
+    /// The maximum timeout, in milliseconds. Longer timeouts are clamped.
+    ///
+    /// **default: `60000`**
+    pub max: u64,
+    /// PRIVATE: This structure may grow (but never change otherwise) in a
+    /// non-breaking release. As such, constructing this structure should
+    /// _always_ be done using a public constructor or update syntax:
+    ///
+    /// ```rust
+    /// use rocket::config::Deadline;
+    ///
+    /// let config = Deadline {
+    ///     default: 5000,
+    ///     ..Default::default()
+    /// };
+    /// ```
+    #[doc(hidden)]
+    #[serde(skip)]
+    pub __non_exhaustive: (),
+}
+
+impl Deadline {
+    /// Returns the timeout, in milliseconds, for a request whose timeout
+    /// header has the value `header`, if any. Returns `None` if the request
+    /// has no deadline.
+    pub(crate) fn timeout_ms(&self, header: Option<&str>) -> Option<u64> {
+        let requested = header.and_then(|v| v.trim().parse::<u64>().ok()).filter(|&ms| ms > 0);
+        let ms = requested.unwrap_or(self.default);
+        (ms > 0).then(|| ms.min(self.max))
+    }
+}
+
+impl fmt::Display for Deadline {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.header.as_str() {
+            "" => write!(f, "header = none, ")?,
+            header => write!(f, "header = {}, ", header)?,
+        }
+
+        match self.default {
+            0 => write!(f, "default = none, ")?,
+            ms => write!(f, "default = {}ms, ", ms)?,
+        }
+
+        write!(f, "max = {}ms", self.max)
+    }
+}
+
+impl Default for Deadline {
+    fn default() -> Self {
+        Deadline {
+            header: "X-Timeout-Ms".into(),
+            default: 0,
+            max: 60_000,
+            __non_exhaustive: (),
+        }
+    }
+}
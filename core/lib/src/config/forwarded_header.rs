@@ -0,0 +1,62 @@
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+/// The header trusted proxies report forwarding information in.
+///
+/// Requests from a [trusted proxy](crate::Config::trusted_proxies) carry the
+/// client's address and scheme in forwarding headers. Only the headers named
+/// by this setting are read; the others are ignored, as a proxy that doesn't
+/// set them passes along whatever the client sent. Set this to match what
+/// your proxies actually append.
+///
+/// See [`Request::real_client_ip()`](crate::Request::real_client_ip()) and
+/// [`Request::scheme()`](crate::Request::scheme()) for how the header is used.
+///
+/// # Example
+///
+/// ```rust
+/// # use rocket::figment::{Figment, providers::{Format, Toml}};
+/// use rocket::config::{Config, ForwardedHeader};
+///
+/// // If these are the contents of `Rocket.toml`...
+/// # let toml = Toml::string(r#"
+/// [default]
+/// forwarded_header = "forwarded"
+/// # "#).nested();
+///
+/// // The config parses as follows:
+/// # let config = Config::from(Figment::from(Config::debug_default()).merge(toml));
+/// assert_eq!(config.forwarded_header, ForwardedHeader::Forwarded);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ForwardedHeader {
+    /// The `X-Forwarded-For` and `X-Forwarded-Proto` headers:
+    /// `"x_forwarded"`. **(default)**
+    XForwarded,
+    /// The `for` and `proto` parameters of the RFC 7239 `Forwarded` header:
+    /// `"forwarded"`.
+    Forwarded,
+}
+
+impl ForwardedHeader {
+    fn as_str(&self) -> &'static str {
+        match self {
+            ForwardedHeader::XForwarded => "x_forwarded",
+            ForwardedHeader::Forwarded => "forwarded",
+        }
+    }
+}
+
+impl fmt::Display for ForwardedHeader {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl Default for ForwardedHeader {
+    fn default() -> Self {
+        ForwardedHeader::XForwarded
+    }
+}
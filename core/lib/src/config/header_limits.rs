@@ -0,0 +1,90 @@
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+use crate::data::{ByteUnit, ToByteUnit};
+
+/// Limits on the headers of incoming requests.
+///
+/// A request with more than `count` headers, or whose header names and values
+/// total more than `size` bytes, is rejected with a `431 Request Header Fields
+/// Too Large` before a [`Request`](crate::Request) is constructed for it and
+/// before any fairing or route sees it. Repeated headers count once per value,
+/// so a single header repeated many times trips the `count` limit, and a single
+/// oversized header value trips the `size` limit.
+///
+/// These limits are checked _after_ the HTTP server has read and parsed the
+/// request's head, so they do not bound the memory used to read it. That is
+/// bounded separately, and independently of this configuration, by the
+/// server's own read buffer and header count limits.
+///
+/// # Example
+///
+/// ```rust
+/// # use rocket::figment::{Figment, providers::{Format, Toml}};
+/// use rocket::Config;
+/// use rocket::data::ToByteUnit;
+///
+/// // If these are the contents of `Rocket.toml`...
+/// # let toml = Toml::string(r#"
+/// [default.header_limits]
+/// count = 50
+/// size = "16KiB"
+/// # "#).nested();
+///
+/// // The config parses as follows:
+/// # let config = Config::from(Figment::from(Config::debug_default()).merge(toml));
+/// assert_eq!(config.header_limits.count, 50);
+/// assert_eq!(config.header_limits.size, 16.kibibytes());
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HeaderLimits {
+    /// The maximum number of headers in a request.
+    ///
+    /// **default: `100`**
+    pub count: usize,
+    /// The maximum combined size of the names and values of the headers in a
+    /// request.
+    ///
+    /// **default: `32KiB`**
+    pub size: ByteUnit,
+    /// PRIVATE: This structure may grow (but never change otherwise) in a
+    /// non-breaking release. As such, constructing this structure should
+    /// _always_ be done using a public constructor or update syntax:
+    ///
+    /// ```rust
+    /// use rocket::config::HeaderLimits;
+    ///
+    /// let config = HeaderLimits {
+    ///     count: 50,
+    ///     ..Default::default()
+    /// };
+    /// ```
+    #[doc(hidden)]
+    #[serde(skip)]
+    pub __non_exhaustive: (),
+}
+
+impl HeaderLimits {
+    /// Returns `true` if `count` headers totalling `size` bytes are within
+    /// these limits.
+    pub(crate) fn allows(&self, count: usize, size: usize) -> bool {
+        count <= self.count && size.bytes() <= self.size
+    }
+}
+
+impl fmt::Display for HeaderLimits {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "count = {}, size = {}", self.count, self.size)
+    }
+}
+
+impl Default for HeaderLimits {
+    fn default() -> Self {
+        HeaderLimits {
+            count: 100,
+            size: 32.kibibytes(),
+            __non_exhaustive: (),
+        }
+    }
+}
@@ -114,6 +114,11 @@
 mod ident;
 mod config;
 mod shutdown;
+mod deadline;
+mod header_limits;
+mod trailing_slash;
+mod forwarded_header;
+mod cidr;
 
 #[cfg(feature = "tls")]
 mod tls;
@@ -126,7 +131,12 @@ pub use config::pretty_print_error;
 pub use config::Config;
 pub use crate::log::LogLevel;
 pub use shutdown::Shutdown;
+pub use deadline::Deadline;
+pub use header_limits::HeaderLimits;
+pub use trailing_slash::TrailingSlash;
+pub use forwarded_header::ForwardedHeader;
 pub use ident::Ident;
+pub use cidr::IpCidr;
 
 #[cfg(feature = "tls")]
 pub use tls::{TlsConfig, CipherSuite};
@@ -268,6 +278,213 @@ mod tests {
         });
     }
 
+    #[test]
+    fn test_trusted_proxies() {
+        figment::Jail::expect_with(|jail| {
+            jail.create_file("Rocket.toml", r#"
+                [default]
+                trusted_proxies = ["10.0.0.0/8", "192.168.1.1", "fd00::/8"]
+            "#)?;
+
+            let config = Config::from(Config::figment());
+            assert_eq!(config, Config {
+                trusted_proxies: vec![
+                    "10.0.0.0/8".parse().unwrap(),
+                    "192.168.1.1/32".parse().unwrap(),
+                    "fd00::/8".parse().unwrap(),
+                ],
+                ..Config::default()
+            });
+
+            jail.set_env("ROCKET_TRUSTED_PROXIES", r#"["127.0.0.1"]"#);
+            let config = Config::from(Config::figment());
+            assert_eq!(config.trusted_proxies, vec!["127.0.0.1".parse().unwrap()]);
+
+            for bad in &[r#"["10.0.0.0/33"]"#, r#"["::/129"]"#, r#"["localhost"]"#] {
+                jail.set_env("ROCKET_TRUSTED_PROXIES", bad);
+                assert!(Config::try_from(Config::figment()).is_err());
+            }
+
+            Ok(())
+        });
+    }
+
+    #[test]
+    fn test_deadline() {
+        use crate::config::Deadline;
+
+        figment::Jail::expect_with(|jail| {
+            jail.create_file("Rocket.toml", r#"
+                [default.deadline]
+                header = "X-Budget"
+                default = 250
+            "#)?;
+
+            let config = Config::from(Config::figment());
+            assert_eq!(config, Config {
+                deadline: Deadline {
+                    header: "X-Budget".into(),
+                    default: 250,
+                    ..Default::default()
+                },
+                ..Config::default()
+            });
+
+            jail.set_env("ROCKET_DEADLINE", "{max=1000}");
+            let config = Config::from(Config::figment());
+            assert_eq!(config.deadline.max, 1000);
+            assert_eq!(config.deadline.default, 250);
+
+            jail.set_env("ROCKET_DEADLINE", "{max=-1}");
+            assert!(Config::try_from(Config::figment()).is_err());
+
+            Ok(())
+        });
+    }
+
+    #[test]
+    fn test_header_limits() {
+        use crate::config::HeaderLimits;
+        use crate::data::ToByteUnit;
+
+        figment::Jail::expect_with(|jail| {
+            jail.create_file("Rocket.toml", r#"
+                [default.header_limits]
+                count = 20
+            "#)?;
+
+            let config = Config::from(Config::figment());
+            assert_eq!(config, Config {
+                header_limits: HeaderLimits {
+                    count: 20,
+                    ..Default::default()
+                },
+                ..Config::default()
+            });
+
+            jail.set_env("ROCKET_HEADER_LIMITS", r#"{size=1KiB}"#);
+            let config = Config::from(Config::figment());
+            assert_eq!(config.header_limits.size, 1.kibibytes());
+            assert_eq!(config.header_limits.count, 20);
+
+            jail.set_env("ROCKET_HEADER_LIMITS", "{count=-1}");
+            assert!(Config::try_from(Config::figment()).is_err());
+
+            Ok(())
+        });
+    }
+
+    #[test]
+    fn test_method_override() {
+        figment::Jail::expect_with(|jail| {
+            assert!(!Config::from(Config::figment()).method_override);
+
+            jail.create_file("Rocket.toml", r#"
+                [default]
+                method_override = true
+            "#)?;
+
+            assert!(Config::from(Config::figment()).method_override);
+
+            jail.set_env("ROCKET_METHOD_OVERRIDE", 0);
+            assert!(!Config::from(Config::figment()).method_override);
+
+            Ok(())
+        });
+    }
+
+    #[test]
+    fn test_auto_options() {
+        figment::Jail::expect_with(|jail| {
+            assert!(!Config::from(Config::figment()).auto_options);
+
+            jail.create_file("Rocket.toml", r#"
+                [default]
+                auto_options = true
+            "#)?;
+
+            assert!(Config::from(Config::figment()).auto_options);
+
+            jail.set_env("ROCKET_AUTO_OPTIONS", 0);
+            assert!(!Config::from(Config::figment()).auto_options);
+
+            Ok(())
+        });
+    }
+
+    #[test]
+    fn test_forwarded_header() {
+        use crate::config::ForwardedHeader;
+
+        figment::Jail::expect_with(|jail| {
+            let config = Config::from(Config::figment());
+            assert_eq!(config.forwarded_header, ForwardedHeader::XForwarded);
+
+            jail.create_file("Rocket.toml", r#"
+                [default]
+                forwarded_header = "forwarded"
+            "#)?;
+
+            let config = Config::from(Config::figment());
+            assert_eq!(config.forwarded_header, ForwardedHeader::Forwarded);
+
+            jail.set_env("ROCKET_FORWARDED_HEADER", "x_forwarded");
+            let config = Config::from(Config::figment());
+            assert_eq!(config.forwarded_header, ForwardedHeader::XForwarded);
+
+            Ok(())
+        });
+    }
+
+    #[test]
+    fn test_unix_socket() {
+        figment::Jail::expect_with(|jail| {
+            let config = Config::from(Config::figment());
+            assert_eq!(config.unix_socket, None);
+
+            jail.create_file("Rocket.toml", r#"
+                [default]
+                unix_socket = "/run/app.sock"
+            "#)?;
+
+            let config = Config::from(Config::figment());
+            assert_eq!(config.unix_socket, Some("/run/app.sock".into()));
+
+            jail.set_env("ROCKET_UNIX_SOCKET", "/tmp/other.sock");
+            let config = Config::from(Config::figment());
+            assert_eq!(config.unix_socket, Some("/tmp/other.sock".into()));
+
+            Ok(())
+        });
+    }
+
+    #[test]
+    fn test_trailing_slash() {
+        use crate::config::TrailingSlash;
+
+        figment::Jail::expect_with(|jail| {
+            let config = Config::from(Config::figment());
+            assert_eq!(config.trailing_slash, TrailingSlash::Strict);
+
+            jail.create_file("Rocket.toml", r#"
+                [default]
+                trailing_slash = "redirect_to_slash"
+            "#)?;
+
+            let config = Config::from(Config::figment());
+            assert_eq!(config.trailing_slash, TrailingSlash::RedirectToSlash);
+
+            jail.set_env("ROCKET_TRAILING_SLASH", "redirect_to_no_slash");
+            let config = Config::from(Config::figment());
+            assert_eq!(config.trailing_slash, TrailingSlash::RedirectToNoSlash);
+
+            jail.set_env("ROCKET_TRAILING_SLASH", "sometimes");
+            assert!(Config::figment().extract::<Config>().is_err());
+
+            Ok(())
+        });
+    }
+
     #[test]
     #[cfg(feature = "tls")]
     fn test_tls_config_from_file() {
@@ -0,0 +1,87 @@
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+/// Trailing slash normalization policy.
+///
+/// Rocket's router ignores trailing slashes: a route for `/foo` matches
+/// requests to both `/foo` and `/foo/`, so the same resource is reachable at
+/// two URLs. A policy other than `Strict` picks one of the two forms as
+/// canonical and permanently redirects requests for the other form to it.
+///
+/// A request is only redirected if a route would handle it, so unroutable
+/// requests still fail with a `404`. Repeated slashes are collapsed in the
+/// redirect's target, which keeps the request's query. The redirect is a `301
+/// Moved Permanently` for `GET` and `HEAD` requests and a `308 Permanent
+/// Redirect`, which requires clients to keep the method and body, otherwise.
+/// The path `/` is never redirected. Neither are requests handled by a route
+/// ending in a `<path..>` segment, such as a
+/// [`FileServer`](crate::fs::FileServer), as such routes see the raw path and
+/// may normalize it themselves.
+///
+/// # Example
+///
+/// ```rust
+/// # use rocket::figment::{Figment, providers::{Format, Toml}};
+/// use rocket::config::{Config, TrailingSlash};
+///
+/// // If these are the contents of `Rocket.toml`...
+/// # let toml = Toml::string(r#"
+/// [default]
+/// trailing_slash = "redirect_to_no_slash"
+/// # "#).nested();
+///
+/// // The config parses as follows:
+/// # let config = Config::from(Figment::from(Config::debug_default()).merge(toml));
+/// assert_eq!(config.trailing_slash, TrailingSlash::RedirectToNoSlash);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TrailingSlash {
+    /// Never redirect: `"strict"`. **(default)**
+    Strict,
+    /// Redirect `/foo/` to `/foo`: `"redirect_to_no_slash"`.
+    RedirectToNoSlash,
+    /// Redirect `/foo` to `/foo/`: `"redirect_to_slash"`.
+    RedirectToSlash,
+}
+
+impl TrailingSlash {
+    /// Returns the canonical form of `path` under this policy if `path` is
+    /// not already in it. Runs of `/` are collapsed in the canonical form so
+    /// that it is never a protocol-relative reference such as `//host`.
+    pub(crate) fn canonicalize(self, path: &str) -> Option<String> {
+        let segments: Vec<_> = path.split('/').filter(|s| !s.is_empty()).collect();
+        match self {
+            TrailingSlash::Strict => None,
+            _ if segments.is_empty() => None,
+            TrailingSlash::RedirectToNoSlash if path.ends_with('/') => {
+                Some(format!("/{}", segments.join("/")))
+            }
+            TrailingSlash::RedirectToSlash if !path.ends_with('/') => {
+                Some(format!("/{}/", segments.join("/")))
+            }
+            _ => None,
+        }
+    }
+
+    fn as_str(&self) -> &'static str {
+        match self {
+            TrailingSlash::Strict => "strict",
+            TrailingSlash::RedirectToNoSlash => "redirect_to_no_slash",
+            TrailingSlash::RedirectToSlash => "redirect_to_slash",
+        }
+    }
+}
+
+impl fmt::Display for TrailingSlash {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl Default for TrailingSlash {
+    fn default() -> Self {
+        TrailingSlash::Strict
+    }
+}
@@ -1,4 +1,5 @@
 use std::fmt;
+use std::collections::HashMap;
 
 use parking_lot::Mutex;
 
@@ -153,16 +154,59 @@ pub use self::cookie::{Cookie, SameSite, Iter};
 /// 32`.
 pub struct CookieJar<'a> {
     jar: cookie::CookieJar,
+    duplicates: HashMap<String, Vec<Cookie<'static>>>,
     ops: Mutex<Vec<Op>>,
     config: &'a Config,
+    secure: bool,
 }
 
 impl<'a> Clone for CookieJar<'a> {
     fn clone(&self) -> Self {
         CookieJar {
             jar: self.jar.clone(),
+            duplicates: self.duplicates.clone(),
             ops: Mutex::new(self.ops.lock().clone()),
             config: self.config,
+            secure: self.secure,
+        }
+    }
+}
+
+/// A change to a [`CookieJar`] queued during a request, as returned by
+/// [`Request::pending_cookies()`](crate::Request::pending_cookies()).
+///
+/// Each cookie carries the attributes it will be sent with, including any
+/// defaults set by the jar. Cookies added by Rocket on the application's behalf,
+/// such as the cookie of a [`Flash`](crate::response::Flash) message, appear as
+/// [`PendingCookie::Add`].
+#[derive(Debug, Clone, PartialEq)]
+pub enum PendingCookie {
+    /// A cookie added via [`CookieJar::add()`].
+    Add(Cookie<'static>),
+    /// A cookie added via [`CookieJar::add_private()`]. The cookie is in
+    /// plaintext; it is encrypted when the response is sent.
+    AddPrivate(Cookie<'static>),
+    /// A cookie removed via [`CookieJar::remove()`].
+    Remove(Cookie<'static>),
+    /// A cookie removed via [`CookieJar::remove_private()`].
+    RemovePrivate(Cookie<'static>),
+}
+
+impl PendingCookie {
+    /// Returns the added or removed cookie.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::http::{Cookie, PendingCookie};
+    ///
+    /// let pending = PendingCookie::Remove(Cookie::named("session"));
+    /// assert_eq!(pending.cookie().name(), "session");
+    /// ```
+    pub fn cookie(&self) -> &Cookie<'static> {
+        match self {
+            PendingCookie::Add(c) | PendingCookie::AddPrivate(c) => c,
+            PendingCookie::Remove(c) | PendingCookie::RemovePrivate(c) => c,
         }
     }
 }
@@ -170,13 +214,17 @@ impl<'a> Clone for CookieJar<'a> {
 #[derive(Clone)]
 enum Op {
     Add(Cookie<'static>, bool),
+    #[cfg(feature = "secrets")]
+    AddSigned(Cookie<'static>),
     Remove(Cookie<'static>, bool),
 }
 
 impl Op {
     fn cookie(&self) -> &Cookie<'static> {
         match self {
-            Op::Add(c, _) | Op::Remove(c, _) => c
+            Op::Add(c, _) | Op::Remove(c, _) => c,
+            #[cfg(feature = "secrets")]
+            Op::AddSigned(c) => c,
         }
     }
 }
@@ -188,12 +236,24 @@ impl<'a> CookieJar<'a> {
     }
 
     pub(crate) fn from(jar: cookie::CookieJar, config: &'a Config) -> Self {
-        CookieJar { jar, config, ops: Mutex::new(Vec::new()) }
+        let secure = config.tls_enabled();
+        let duplicates = HashMap::new();
+        CookieJar { jar, duplicates, config, secure, ops: Mutex::new(Vec::new()) }
+    }
+
+    /// Sets whether the request was made over `https`, as reported by
+    /// [`Request::scheme()`](crate::Request::scheme()). Determines the default
+    /// of the `Secure` attribute for private cookies.
+    pub(crate) fn set_secure(&mut self, secure: bool) {
+        self.secure = secure;
     }
 
     /// Returns a reference to the _original_ `Cookie` inside this container
     /// with the name `name`. If no such cookie exists, returns `None`.
     ///
+    /// If the client sent several cookies named `name`, this is the first one
+    /// received. To retrieve all of them, use [`CookieJar::get_all()`].
+    ///
     /// **Note:** This method _does not_ observe changes made via additions and
     /// removals to the cookie jar. To observe those changes, use
     /// [`CookieJar::get_pending()`].
@@ -213,6 +273,39 @@ impl<'a> CookieJar<'a> {
         self.jar.get(name)
     }
 
+    /// Returns an iterator over all of the _original_ cookies with the name
+    /// `name`, in the order they were received.
+    ///
+    /// Clients may send several cookies with the same name, for instance when
+    /// cookies with the same name were set for different paths or domains.
+    /// [`CookieJar::get()`] only returns the first of these. Since a client can
+    /// append a cookie with any name, code making a security decision based on
+    /// a cookie should consider whether a request with more than one is
+    /// suspect.
+    ///
+    /// **Note:** This method _does not_ observe changes made via additions and
+    /// removals to the cookie jar.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # #[macro_use] extern crate rocket;
+    /// use rocket::http::{Cookie, CookieJar};
+    ///
+    /// #[get("/")]
+    /// fn handler(jar: &CookieJar<'_>) -> Option<&'static str> {
+    ///     match jar.get_all("session").count() {
+    ///         0 => None,
+    ///         1 => Some("one session"),
+    ///         _ => Some("ambiguous session"),
+    ///     }
+    /// }
+    /// ```
+    pub fn get_all(&self, name: &str) -> impl Iterator<Item = &Cookie<'static>> {
+        let duplicates = self.duplicates.get(name).into_iter().flatten();
+        self.jar.get(name).into_iter().chain(duplicates)
+    }
+
     /// Retrieves the _original_ `Cookie` inside this collection with the name
     /// `name` and authenticates and decrypts the cookie's value. If the cookie
     /// cannot be found, or the cookie fails to authenticate or decrypt, `None`
@@ -239,6 +332,15 @@ impl<'a> CookieJar<'a> {
         self.jar.private(&self.config.secret_key.key).get(name)
     }
 
+    /// Retrieves the _original_ `Cookie` with name `name` if its value carries
+    /// a valid signature from the configured `secret_key`, stripping the
+    /// signature. If no such cookie exists or it fails verification, returns
+    /// `None`.
+    #[cfg(feature = "secrets")]
+    pub(crate) fn get_signed(&self, name: &str) -> Option<Cookie<'static>> {
+        self.jar.signed(&self.config.secret_key.key).get(name)
+    }
+
     /// Returns a reference to the _original or pending_ `Cookie` inside this
     /// container with the name `name`, irrespective of whether the cookie was
     /// private or not. If no such cookie exists, returns `None`.
@@ -263,6 +365,8 @@ impl<'a> CookieJar<'a> {
         for op in ops.iter().rev().filter(|op| op.cookie().name() == name) {
             match op {
                 Op::Add(c, _) => return Some(c.clone()),
+                #[cfg(feature = "secrets")]
+                Op::AddSigned(c) => return Some(c.clone()),
                 Op::Remove(_, _) => return None,
             }
         }
@@ -314,29 +418,49 @@ impl<'a> CookieJar<'a> {
     ///    * `path`: `"/"`
     ///    * `SameSite`: `Strict`
     ///    * `HttpOnly`: `true`
-    ///    * `Expires`: 1 week from now
+    ///    * `Secure`: `true` if the request's scheme is `https`
+    ///    * `Expires`: 1 week from now, unless `Max-Age` is set
     ///
-    /// These defaults ensure maximum usability and security. For additional
-    /// security, you may wish to set the `secure` flag.
+    /// These defaults ensure maximum usability and security. Any of them can
+    /// be overridden by setting the attribute on `cookie`, typically via
+    /// [`Cookie::build()`].
     ///
     /// # Example
     ///
     /// ```rust
     /// # #[macro_use] extern crate rocket;
-    /// use rocket::http::{Cookie, CookieJar};
+    /// use rocket::http::{Cookie, CookieJar, SameSite};
+    /// use rocket::time::Duration;
     ///
     /// #[get("/")]
     /// fn handler(jar: &CookieJar<'_>) {
     ///     jar.add_private(Cookie::new("name", "value"));
+    ///
+    ///     let cookie = Cookie::build("session", "value")
+    ///         .same_site(SameSite::Lax)
+    ///         .secure(true)
+    ///         .max_age(Duration::hours(1));
+    ///
+    ///     jar.add_private(cookie.finish());
     /// }
     /// ```
     #[cfg(feature = "secrets")]
     #[cfg_attr(nightly, doc(cfg(feature = "secrets")))]
     pub fn add_private(&self, mut cookie: Cookie<'static>) {
-        Self::set_private_defaults(&mut cookie);
+        self.set_private_defaults(&mut cookie);
         self.ops.lock().push(Op::Add(cookie, true));
     }
 
+    /// Adds `cookie` to the collection, signed with the configured
+    /// `secret_key`. Unlike a private cookie, a signed cookie's value is
+    /// readable by the client but can't be modified or forged by it. No
+    /// defaults are set on `cookie` beyond those set by [`CookieJar::add()`].
+    #[cfg(feature = "secrets")]
+    pub(crate) fn add_signed(&self, mut cookie: Cookie<'static>) {
+        Self::set_defaults(&mut cookie);
+        self.ops.lock().push(Op::AddSigned(cookie));
+    }
+
     /// Removes `cookie` from this collection and generates a "removal" cookies
     /// to send to the client on response. For correctness, `cookie` must
     /// contain the same `path` and `domain` as the cookie that was initially
@@ -394,8 +518,50 @@ impl<'a> CookieJar<'a> {
         self.ops.lock().push(Op::Remove(cookie, true));
     }
 
+    /// Stages the changes made by `f` and applies them to `self` only if `f`
+    /// returns `Ok`.
+    ///
+    /// `f` is called with a staged copy of this jar. Changes made to the copy,
+    /// via [`CookieJar::add()`], [`CookieJar::remove()`], and their private
+    /// variants, are visible to it through [`CookieJar::get_pending()`] but
+    /// not to `self`. If `f` returns `Ok`, the staged changes are applied to
+    /// `self`, in order, after any changes already pending. If `f` returns
+    /// `Err` or panics, they are discarded, so a group of cookies that belong
+    /// together is never half-applied. The result of `f` is returned.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # #[macro_use] extern crate rocket;
+    /// use rocket::http::{Cookie, CookieJar};
+    ///
+    /// #[post("/login/<user>")]
+    /// fn login(jar: &CookieJar<'_>, user: &str) -> Result<(), &'static str> {
+    ///     jar.batch(|jar| {
+    ///         jar.add(Cookie::new("session", user.to_string()));
+    ///         jar.add(Cookie::new("csrf", "token"));
+    ///         match user {
+    ///             "root" => Err("root may not log in"),
+    ///             _ => Ok(())
+    ///         }
+    ///     })
+    /// }
+    /// ```
+    pub fn batch<T, E, F>(&self, f: F) -> Result<T, E>
+        where F: FnOnce(&CookieJar<'a>) -> Result<T, E>
+    {
+        let staged = self.clone();
+        let committed = staged.ops.lock().len();
+        let value = f(&staged)?;
+
+        let staged_ops = staged.ops.into_inner();
+        self.ops.lock().extend(staged_ops.into_iter().skip(committed));
+        Ok(value)
+    }
+
     /// Returns an iterator over all of the _original_ cookies present in this
-    /// collection.
+    /// collection. Of several cookies with the same name, only the first is
+    /// returned; use [`CookieJar::get_all()`] to retrieve the rest.
     ///
     /// **Note:** This method _does not_ observe changes made via additions and
     /// removals to the cookie jar.
@@ -417,16 +583,34 @@ impl<'a> CookieJar<'a> {
         self.jar.iter()
     }
 
+    /// Returns the pending changes to this jar in the order they were made.
+    pub(crate) fn pending(&self) -> Vec<PendingCookie> {
+        self.ops.lock()
+            .iter()
+            .map(|op| match op.clone() {
+                Op::Add(c, false) => PendingCookie::Add(c),
+                Op::Add(c, true) => PendingCookie::AddPrivate(c),
+                #[cfg(feature = "secrets")]
+                Op::AddSigned(c) => PendingCookie::Add(c),
+                Op::Remove(c, false) => PendingCookie::Remove(c),
+                Op::Remove(c, true) => PendingCookie::RemovePrivate(c),
+            })
+            .collect()
+    }
+
     /// Removes all delta cookies.
     #[inline(always)]
     pub(crate) fn reset_delta(&self) {
         self.ops.lock().clear();
     }
 
+    /// Returns a jar whose delta contains the pending changes. The changes
+    /// remain pending so that they're visible to response fairings.
+    ///
     /// TODO: This could be faster by just returning the cookies directly via
     /// an ordered hash-set of sorts.
-    pub(crate) fn take_delta_jar(&self) -> cookie::CookieJar {
-        let ops = std::mem::take(&mut *self.ops.lock());
+    pub(crate) fn delta_jar(&self) -> cookie::CookieJar {
+        let ops = self.ops.lock().clone();
         let mut jar = cookie::CookieJar::new();
 
         for op in ops {
@@ -436,6 +620,10 @@ impl<'a> CookieJar<'a> {
                 Op::Add(c, true) => {
                     jar.private_mut(&self.config.secret_key.key).add(c);
                 }
+                #[cfg(feature = "secrets")]
+                Op::AddSigned(c) => {
+                    jar.signed_mut(&self.config.secret_key.key).add(c);
+                }
                 Op::Remove(mut c, _) => {
                     if self.jar.get(c.name()).is_some() {
                         c.make_removal();
@@ -452,20 +640,35 @@ impl<'a> CookieJar<'a> {
         jar
     }
 
-    /// Adds an original `cookie` to this collection.
+    /// Adds an original `cookie` to this collection, replacing any original
+    /// cookies with the same name.
     #[inline(always)]
     pub(crate) fn add_original(&mut self, cookie: Cookie<'static>) {
+        self.duplicates.remove(cookie.name());
         self.jar.add_original(cookie)
     }
 
-    /// Adds an original, private `cookie` to the collection.
+    /// Adds an original, private `cookie` to the collection, replacing any
+    /// original cookies with the same name.
     #[cfg(feature = "secrets")]
     #[cfg_attr(nightly, doc(cfg(feature = "secrets")))]
     #[inline(always)]
     pub(crate) fn add_original_private(&mut self, cookie: Cookie<'static>) {
+        self.duplicates.remove(cookie.name());
         self.jar.private_mut(&self.config.secret_key.key).add_original(cookie);
     }
 
+    /// Adds an original `cookie` received from the client to this collection.
+    /// Unlike [`CookieJar::add_original()`], an existing original cookie with
+    /// the same name is kept: `cookie` is only visible via `get_all()`.
+    pub(crate) fn add_received(&mut self, cookie: Cookie<'static>) {
+        if self.jar.get(cookie.name()).is_none() {
+            self.jar.add_original(cookie);
+        } else {
+            self.duplicates.entry(cookie.name().to_string()).or_default().push(cookie);
+        }
+    }
+
     /// For each property mentioned below, this method checks if there is a
     /// provided value and if there is none, sets a default value. Default
     /// values are:
@@ -490,11 +693,12 @@ impl<'a> CookieJar<'a> {
     ///    * `path`: `"/"`
     ///    * `SameSite`: `Strict`
     ///    * `HttpOnly`: `true`
-    ///    * `Expires`: 1 week from now
+    ///    * `Secure`: `true` if the request's scheme is `https`
+    ///    * `Expires`: 1 week from now, unless `Max-Age` is set
     ///
     #[cfg(feature = "secrets")]
     #[cfg_attr(nightly, doc(cfg(feature = "secrets")))]
-    fn set_private_defaults(cookie: &mut Cookie<'static>) {
+    fn set_private_defaults(&self, cookie: &mut Cookie<'static>) {
         if cookie.path().is_none() {
             cookie.set_path("/");
         }
@@ -507,7 +711,11 @@ impl<'a> CookieJar<'a> {
             cookie.set_http_only(true);
         }
 
-        if cookie.expires().is_none() {
+        if cookie.secure().is_none() && self.secure {
+            cookie.set_secure(true);
+        }
+
+        if cookie.expires().is_none() && cookie.max_age().is_none() {
             cookie.set_expires(time::OffsetDateTime::now_utc() + time::Duration::weeks(1));
         }
     }
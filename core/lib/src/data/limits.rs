@@ -63,8 +63,10 @@ use crate::http::uncased::Uncased;
 /// | `bytes`           | 8KiB    | [`Vec<u8>`]  | data guard                            |
 /// | `json`            | 1MiB    | [`Json`]     | JSON data and form payloads           |
 /// | `msgpack`         | 1MiB    | [`MsgPack`]  | MessagePack data and form payloads    |
+/// | `raw-body`        | 1MiB    | [`RawBody`]  | data guard                            |
 ///
 /// [`TempFile`]: crate::fs::TempFile
+/// [`RawBody`]: crate::data::RawBody
 /// [`Json`]: crate::serde::json::Json
 /// [`MsgPack`]: crate::serde::msgpack::MsgPack
 ///
@@ -136,6 +138,7 @@ impl Default for Limits {
             .limit("bytes", Limits::BYTES)
             .limit("json", Limits::JSON)
             .limit("msgpack", Limits::MESSAGE_PACK)
+            .limit("raw-body", Limits::RAW_BODY)
     }
 }
 
@@ -161,6 +164,9 @@ impl Limits {
     /// Default limit for MessagePack payloads.
     pub const MESSAGE_PACK: ByteUnit = ByteUnit::Mebibyte(1);
 
+    /// Default limit for raw, unparsed request bodies.
+    pub const RAW_BODY: ByteUnit = ByteUnit::Mebibyte(1);
+
     /// Construct a new `Limits` structure with no limits set.
     ///
     /// # Example
@@ -0,0 +1,135 @@
+use std::{fmt, io};
+use std::ops::Deref;
+
+use crate::http::Status;
+use crate::request::Request;
+use crate::data::{ByteUnit, Data, FromData, Outcome, ToByteUnit};
+use crate::outcome::Outcome::*;
+
+/// A data guard that reads a request's body, failing if it is larger than
+/// `BYTES` bytes.
+///
+/// Unlike the built-in data guards, which read up to a named, globally
+/// configured [limit](crate::data::Limits), `MaxBodySize` carries its limit in
+/// its type, so each route can impose its own ceiling.
+///
+/// The limit is enforced in two steps:
+///
+///   1. If the request declares a `Content-Length` greater than `BYTES`, the
+///      guard fails immediately, without reading any of the body.
+///   2. Otherwise, the body is read up to `BYTES` bytes. If any data remains,
+///      reading stops and the guard fails. This is the case when there is no
+///      `Content-Length` header or when it understates the length of the
+///      body; in either case, at most `BYTES + 1` bytes are ever read.
+///
+/// Both failures have a status of `413 Payload Too Large` and an error of
+/// [`BodySizeError`]. An I/O error while reading fails with `400 Bad Request`.
+///
+/// # Example
+///
+/// ```rust
+/// # #[macro_use] extern crate rocket;
+/// use rocket::data::MaxBodySize;
+///
+/// #[post("/avatar", data = "<image>")]
+/// fn avatar(image: MaxBodySize<{ 64 * 1024 }>) -> String {
+///     format!("stored {} bytes", image.len())
+/// }
+///
+/// #[post("/video", data = "<video>")]
+/// fn video(video: MaxBodySize<{ 256 * 1024 * 1024 }>) -> String {
+///     format!("stored {} bytes", video.len())
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MaxBodySize<const BYTES: u64>(Vec<u8>);
+
+impl<const BYTES: u64> MaxBodySize<BYTES> {
+    /// The maximum size of the body, in bytes.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::data::{MaxBodySize, ToByteUnit};
+    ///
+    /// assert_eq!(MaxBodySize::<2048>::LIMIT, 2.kibibytes());
+    /// ```
+    pub const LIMIT: ByteUnit = ByteUnit::Byte(BYTES);
+
+    /// Consumes `self` and returns the body.
+    #[inline(always)]
+    pub fn into_inner(self) -> Vec<u8> {
+        self.0
+    }
+}
+
+impl<const BYTES: u64> Deref for MaxBodySize<BYTES> {
+    type Target = [u8];
+
+    fn deref(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+/// Error returned by the [`MaxBodySize`] data guard.
+#[derive(Debug)]
+pub enum BodySizeError {
+    /// The declared `Content-Length` exceeds the limit.
+    Declared {
+        /// The declared length.
+        length: u64,
+        /// The limit.
+        limit: u64,
+    },
+    /// The body exceeded the limit while being read.
+    Exceeded {
+        /// The limit.
+        limit: u64,
+    },
+    /// An I/O error occurred while reading the body.
+    Io(io::Error),
+}
+
+impl fmt::Display for BodySizeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BodySizeError::Declared { length, limit } => {
+                write!(f, "declared length {} exceeds the limit of {}", length, limit)
+            }
+            BodySizeError::Exceeded { limit } => {
+                write!(f, "body exceeds the limit of {}", limit)
+            }
+            BodySizeError::Io(e) => write!(f, "i/o error: {}", e),
+        }
+    }
+}
+
+impl std::error::Error for BodySizeError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            BodySizeError::Io(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+#[crate::async_trait]
+impl<'r, const BYTES: u64> FromData<'r> for MaxBodySize<BYTES> {
+    type Error = BodySizeError;
+
+    async fn from_data(req: &'r Request<'_>, data: Data<'r>) -> Outcome<'r, Self> {
+        let declared = req.headers().get_one("Content-Length")
+            .and_then(|length| length.trim().parse::<u64>().ok());
+
+        if let Some(length) = declared.filter(|&length| length > BYTES) {
+            let error = BodySizeError::Declared { length, limit: BYTES };
+            return Failure((Status::PayloadTooLarge, error));
+        }
+
+        match data.open(BYTES.bytes()).into_bytes().await {
+            Ok(bytes) if bytes.is_complete() => Success(MaxBodySize(bytes.into_inner())),
+            Ok(_) => Failure((Status::PayloadTooLarge, BodySizeError::Exceeded { limit: BYTES })),
+            Err(e) => Failure((Status::BadRequest, BodySizeError::Io(e))),
+        }
+    }
+}
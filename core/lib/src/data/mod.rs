@@ -6,11 +6,17 @@ mod data;
 mod data_stream;
 mod from_data;
 mod limits;
+mod max_body_size;
+mod raw_body;
+mod text;
 
 pub use self::data::Data;
 pub use self::data_stream::DataStream;
 pub use self::from_data::{FromData, Outcome};
 pub use self::limits::Limits;
+pub use self::raw_body::RawBody;
+pub use self::max_body_size::{MaxBodySize, BodySizeError};
+pub use self::text::{Text, Charset, TextError};
 pub use self::capped::{N, Capped};
 pub use ubyte::{ByteUnit, ToByteUnit};
 
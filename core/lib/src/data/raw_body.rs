@@ -0,0 +1,153 @@
+use std::io;
+use std::ops::Deref;
+
+use crate::http::Status;
+use crate::request::Request;
+use crate::data::{Data, FromData, Limits, Outcome};
+use crate::outcome::Outcome::*;
+
+/// The request-local cache entry holding a request's raw body.
+struct Cached(Vec<u8>);
+
+/// A data guard for the exact, unparsed bytes of a request's body.
+///
+/// `RawBody` reads the entire body, up to the `raw-body` [limit](Limits), and
+/// stores it in the request-local cache. The returned slice borrows from that
+/// cache, so it is stable for the lifetime of the request: every subsequent
+/// `RawBody` for the same request, whether from
+/// [`from_data()`](FromData::from_data()) or [`RawBody::cached()`], observes
+/// the same bytes at the same address. This makes it suitable for webhook
+/// signature verification, where a MAC must be computed over the body exactly
+/// as received before the same bytes are parsed.
+///
+/// Because the body can only be read once, if `RawBody` has already read the
+/// body of the request, `from_data()` returns the cached bytes and ignores
+/// `data`.
+///
+/// If the body exceeds the limit, the guard fails with a status of `413
+/// Payload Too Large` and an error of kind [`io::ErrorKind::UnexpectedEof`],
+/// and nothing is cached. The default limit is [`Limits::RAW_BODY`].
+///
+/// # Example
+///
+/// A data guard that verifies a signature header over the raw body before
+/// parsing it as JSON:
+///
+/// ```rust
+/// # #[macro_use] extern crate rocket;
+/// # #[cfg(feature = "json")] mod _main {
+/// use rocket::request::Request;
+/// use rocket::data::{self, Data, FromData, RawBody};
+/// use rocket::http::Status;
+/// use rocket::outcome::Outcome::*;
+/// use rocket::serde::{Deserialize, json};
+///
+/// # fn mac(_: &[u8]) -> String { "valid".into() }
+/// #[derive(Deserialize)]
+/// # #[serde(crate = "rocket::serde")]
+/// struct Event { kind: String }
+///
+/// #[rocket::async_trait]
+/// impl<'r> FromData<'r> for Event {
+///     type Error = &'static str;
+///
+///     async fn from_data(req: &'r Request<'_>, data: Data<'r>) -> data::Outcome<'r, Self> {
+///         let body = match RawBody::from_data(req, data).await {
+///             Success(body) => body,
+///             Failure((status, _)) => return Failure((status, "unreadable body")),
+///             Forward(data) => return Forward(data),
+///         };
+///
+///         if req.headers().get_one("X-Signature") != Some(&mac(&body)) {
+///             return Failure((Status::Unauthorized, "bad signature"));
+///         }
+///
+///         match json::from_slice(&body) {
+///             Ok(event) => Success(event),
+///             Err(_) => Failure((Status::UnprocessableEntity, "malformed event")),
+///         }
+///     }
+/// }
+///
+/// #[post("/webhook", data = "<event>")]
+/// fn webhook(event: Event) -> String {
+///     event.kind
+/// }
+/// # }
+/// ```
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct RawBody<'r>(&'r [u8]);
+
+impl<'r> RawBody<'r> {
+    /// Returns the body of `request` if it has already been read by
+    /// `RawBody`, and `None` otherwise.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::data::RawBody;
+    ///
+    /// # let client = rocket::local::blocking::Client::debug_with(vec![]).unwrap();
+    /// # let request = client.post("/").body("hi");
+    /// assert!(RawBody::cached(&request).is_none());
+    /// ```
+    pub fn cached(request: &'r Request<'_>) -> Option<RawBody<'r>> {
+        request.local_cache_get::<Cached>().map(|cached| RawBody(&cached.0))
+    }
+
+    /// Returns the raw body bytes.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # #[macro_use] extern crate rocket;
+    /// use rocket::data::RawBody;
+    ///
+    /// #[post("/echo", data = "<body>")]
+    /// fn echo(body: RawBody<'_>) -> Vec<u8> {
+    ///     body.as_bytes().to_vec()
+    /// }
+    /// ```
+    #[inline(always)]
+    pub fn as_bytes(&self) -> &'r [u8] {
+        self.0
+    }
+}
+
+impl Deref for RawBody<'_> {
+    type Target = [u8];
+
+    fn deref(&self) -> &[u8] {
+        self.0
+    }
+}
+
+impl AsRef<[u8]> for RawBody<'_> {
+    fn as_ref(&self) -> &[u8] {
+        self.0
+    }
+}
+
+#[crate::async_trait]
+impl<'r> FromData<'r> for RawBody<'r> {
+    type Error = io::Error;
+
+    async fn from_data(req: &'r Request<'_>, data: Data<'r>) -> Outcome<'r, Self> {
+        if let Some(body) = RawBody::cached(req) {
+            return Success(body);
+        }
+
+        let limit = req.limits().get("raw-body").unwrap_or(Limits::RAW_BODY);
+        let bytes = match data.open(limit).into_bytes().await {
+            Ok(bytes) if bytes.is_complete() => bytes.into_inner(),
+            Ok(_) => {
+                let eof = io::ErrorKind::UnexpectedEof;
+                let error = io::Error::new(eof, "data limit exceeded");
+                return Failure((Status::PayloadTooLarge, error));
+            },
+            Err(e) => return Failure((Status::BadRequest, e)),
+        };
+
+        Success(RawBody(&req.local_cache(|| Cached(bytes)).0))
+    }
+}
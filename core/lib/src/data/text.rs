@@ -0,0 +1,222 @@
+use std::{fmt, io};
+use std::ops::Deref;
+
+use crate::http::Status;
+use crate::request::Request;
+use crate::data::{Data, FromData, Limits, Outcome};
+use crate::outcome::Outcome::*;
+
+/// A data guard that decodes a textual body according to its declared charset.
+///
+/// The built-in `String` and `&str` data guards assume that a body is UTF-8.
+/// `Text` instead reads the `charset` parameter of the request's `Content-Type`
+/// and decodes the body from that [`Charset`] into a `String`. When there is no
+/// `Content-Type` or it has no `charset` parameter, the body is decoded as
+/// UTF-8. Charset names are matched case-insensitively, including common
+/// aliases like `latin1`.
+///
+/// Decoding is strict: a body is never decoded lossily. The guard fails with
+/// a [`TextError`] and a status of:
+///
+///   * `415 Unsupported Media Type` if the declared charset is not supported.
+///   * `400 Bad Request` if the body contains a byte sequence that is invalid
+///     in the declared charset, or on an I/O error.
+///   * `413 Payload Too Large` if the body exceeds the `string`
+///     [limit](Limits), which defaults to [`Limits::STRING`].
+///
+/// # Example
+///
+/// ```rust
+/// # #[macro_use] extern crate rocket;
+/// use rocket::data::Text;
+///
+/// #[post("/notes", data = "<note>")]
+/// fn note(note: Text) -> String {
+///     format!("{} characters", note.chars().count())
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Text(String);
+
+impl Text {
+    /// Consumes `self` and returns the decoded body.
+    #[inline(always)]
+    pub fn into_inner(self) -> String {
+        self.0
+    }
+}
+
+impl Deref for Text {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        &self.0
+    }
+}
+
+/// A character encoding supported by the [`Text`] data guard.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum Charset {
+    /// `UTF-8`, the default.
+    Utf8,
+    /// `US-ASCII`: only bytes below `0x80` are valid.
+    UsAscii,
+    /// `ISO-8859-1`, or Latin-1: every byte is the code point of the same
+    /// value.
+    Latin1,
+}
+
+impl Charset {
+    /// Returns the charset named `name`, ignoring case, or `None` if `name`
+    /// isn't the name or an alias of a supported charset.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::data::Charset;
+    ///
+    /// assert_eq!(Charset::from_name("utf-8"), Some(Charset::Utf8));
+    /// assert_eq!(Charset::from_name("ISO-8859-1"), Some(Charset::Latin1));
+    /// assert_eq!(Charset::from_name("latin1"), Some(Charset::Latin1));
+    /// assert_eq!(Charset::from_name("Shift_JIS"), None);
+    /// ```
+    pub fn from_name(name: &str) -> Option<Charset> {
+        const ALIASES: &[(&str, Charset)] = &[
+            ("utf-8", Charset::Utf8),
+            ("utf8", Charset::Utf8),
+            ("us-ascii", Charset::UsAscii),
+            ("ascii", Charset::UsAscii),
+            ("iso-8859-1", Charset::Latin1),
+            ("iso8859-1", Charset::Latin1),
+            ("iso_8859-1", Charset::Latin1),
+            ("latin1", Charset::Latin1),
+            ("l1", Charset::Latin1),
+        ];
+
+        ALIASES.iter()
+            .find(|(alias, _)| alias.eq_ignore_ascii_case(name))
+            .map(|&(_, charset)| charset)
+    }
+
+    /// Returns the canonical name of `self`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::data::Charset;
+    ///
+    /// assert_eq!(Charset::from_name("latin1").unwrap().name(), "ISO-8859-1");
+    /// ```
+    pub fn name(self) -> &'static str {
+        match self {
+            Charset::Utf8 => "UTF-8",
+            Charset::UsAscii => "US-ASCII",
+            Charset::Latin1 => "ISO-8859-1",
+        }
+    }
+
+    /// Decodes `bytes` from `self`. On failure, returns the offset of the
+    /// first byte that is invalid in `self`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::data::Charset;
+    ///
+    /// assert_eq!(Charset::Latin1.decode(b"caf\xe9").unwrap(), "café");
+    /// assert_eq!(Charset::Utf8.decode(b"caf\xe9"), Err(3));
+    /// assert_eq!(Charset::UsAscii.decode("café".as_bytes()), Err(3));
+    /// ```
+    pub fn decode(self, bytes: &[u8]) -> Result<String, usize> {
+        match self {
+            Charset::Utf8 => std::str::from_utf8(bytes)
+                .map(|string| string.to_owned())
+                .map_err(|e| e.valid_up_to()),
+            Charset::UsAscii => match bytes.iter().position(|b| !b.is_ascii()) {
+                Some(offset) => Err(offset),
+                None => Ok(bytes.iter().map(|&b| b as char).collect()),
+            },
+            Charset::Latin1 => Ok(bytes.iter().map(|&b| b as char).collect()),
+        }
+    }
+}
+
+impl fmt::Display for Charset {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.name().fmt(f)
+    }
+}
+
+/// Error returned by the [`Text`] data guard.
+#[derive(Debug)]
+pub enum TextError {
+    /// The declared charset is not supported.
+    UnsupportedCharset(String),
+    /// The body contains a byte sequence that is invalid in the charset.
+    InvalidBytes {
+        /// The charset the body was decoded from.
+        charset: Charset,
+        /// The offset of the first invalid byte.
+        offset: usize,
+    },
+    /// The body exceeded the `string` limit.
+    TooLarge,
+    /// An I/O error occurred while reading the body.
+    Io(io::Error),
+}
+
+impl fmt::Display for TextError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TextError::UnsupportedCharset(name) => write!(f, "unsupported charset `{}`", name),
+            TextError::InvalidBytes { charset, offset } => {
+                write!(f, "invalid {} byte sequence at offset {}", charset, offset)
+            }
+            TextError::TooLarge => write!(f, "body exceeds the `string` limit"),
+            TextError::Io(e) => write!(f, "i/o error: {}", e),
+        }
+    }
+}
+
+impl std::error::Error for TextError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            TextError::Io(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+#[crate::async_trait]
+impl<'r> FromData<'r> for Text {
+    type Error = TextError;
+
+    async fn from_data(req: &'r Request<'_>, data: Data<'r>) -> Outcome<'r, Self> {
+        let declared = req.content_type().and_then(|ct| ct.param("charset"));
+        let charset = match declared.map(|name| name.trim_matches('"')) {
+            Some(name) => match Charset::from_name(name) {
+                Some(charset) => charset,
+                None => {
+                    let error = TextError::UnsupportedCharset(name.to_string());
+                    return Failure((Status::UnsupportedMediaType, error));
+                }
+            },
+            None => Charset::Utf8,
+        };
+
+        let limit = req.limits().get("string").unwrap_or(Limits::STRING);
+        let bytes = match data.open(limit).into_bytes().await {
+            Ok(bytes) if bytes.is_complete() => bytes.into_inner(),
+            Ok(_) => return Failure((Status::PayloadTooLarge, TextError::TooLarge)),
+            Err(e) => return Failure((Status::BadRequest, TextError::Io(e))),
+        };
+
+        match charset.decode(&bytes) {
+            Ok(string) => Success(Text(string)),
+            Err(offset) => {
+                let error = TextError::InvalidBytes { charset, offset };
+                Failure((Status::BadRequest, error))
+            }
+        }
+    }
+}
@@ -265,7 +265,8 @@ impl<F: Future, I: AsyncWrite> AsyncWrite for CancellableIo<F, I> {
     }
 }
 
-use crate::http::private::{Listener, Connection, Certificates};
+use crate::http::UnixPeer;
+use crate::http::private::{Listener, Connection, Certificates, TlsSession};
 
 impl<F: Future, C: Connection> Connection for CancellableIo<F, C> {
     fn peer_address(&self) -> Option<std::net::SocketAddr> {
@@ -276,6 +277,14 @@ impl<F: Future, C: Connection> Connection for CancellableIo<F, C> {
         self.io().and_then(|io| io.peer_certificates())
     }
 
+    fn tls_session(&self) -> Option<TlsSession> {
+        self.io().and_then(|io| io.tls_session())
+    }
+
+    fn unix_peer(&self) -> Option<UnixPeer> {
+        self.io().and_then(|io| io.unix_peer())
+    }
+
     fn enable_nodelay(&self) -> io::Result<()> {
         match self.io() {
             Some(io) => io.enable_nodelay(),
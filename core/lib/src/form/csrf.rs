@@ -0,0 +1,126 @@
+use std::ops::{Deref, DerefMut};
+
+use crate::Request;
+use crate::outcome::try_outcome;
+use crate::data::{Data, FromData, Outcome};
+use crate::http::Status;
+use crate::request::CsrfToken;
+use crate::form::parser::Parser;
+use crate::form::prelude::*;
+
+/// A data guard for [`FromForm`] types that checks the form's CSRF token.
+///
+/// `CsrfForm<T>` parses a url-encoded or multipart form into a `T` exactly as
+/// [`Form<T>`](Form) does, with the same limits, but first sets aside the
+/// [`CsrfToken::FIELD`] field, which `T` never sees. That field's value, or the
+/// [`CsrfToken::HEADER`] header if present, must match the session's
+/// [`CsrfToken`]. Otherwise, the guard fails with `403 Forbidden` and a
+/// [`Missing`](ErrorKind::Missing) or [validation](Error::validation()) error
+/// named for the field.
+///
+/// As with the [`CsrfToken`] guard, the session's token is created when the
+/// request has no valid session cookie, so such requests always fail.
+///
+/// This guard is only available when the `secrets` feature is enabled.
+///
+/// # Example
+///
+/// ```rust
+/// # #[macro_use] extern crate rocket;
+/// use rocket::form::CsrfForm;
+/// use rocket::request::CsrfToken;
+/// use rocket::response::content::RawHtml;
+///
+/// #[derive(FromForm)]
+/// struct Transfer {
+///     amount: u64,
+/// }
+///
+/// #[get("/transfer")]
+/// fn form(csrf: CsrfToken) -> RawHtml<String> {
+///     RawHtml(format!(r#"<form method="post" action="/transfer">
+///         <input type="hidden" name="{}" value="{}">
+///         <input type="text" name="amount">
+///     </form>"#, CsrfToken::FIELD, csrf))
+/// }
+///
+/// #[post("/transfer", data = "<transfer>")]
+/// fn transfer(transfer: CsrfForm<Transfer>) -> String {
+///     format!("transferred {}", transfer.amount)
+/// }
+/// ```
+#[cfg_attr(nightly, doc(cfg(feature = "secrets")))]
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct CsrfForm<T>(T);
+
+impl<T> CsrfForm<T> {
+    /// Consumes `self` and returns the inner value.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # #[macro_use] extern crate rocket;
+    /// use rocket::form::CsrfForm;
+    ///
+    /// #[post("/name", data = "<name>")]
+    /// fn name(name: CsrfForm<String>) -> String {
+    ///     name.into_inner()
+    /// }
+    /// ```
+    #[inline(always)]
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+#[crate::async_trait]
+impl<'r, T: FromForm<'r>> FromData<'r> for CsrfForm<T> {
+    type Error = Errors<'r>;
+
+    async fn from_data(req: &'r Request<'_>, data: Data<'r>) -> Outcome<'r, Self> {
+        use either::Either;
+
+        let mut parser = try_outcome!(Parser::new(req, data).await);
+        let mut context = T::init(Options::Lenient);
+        let mut field_token = None;
+        while let Some(field) = parser.next().await {
+            match field {
+                Ok(Either::Left(value)) if value.name == CsrfToken::FIELD => {
+                    field_token = Some(value.value);
+                }
+                Ok(Either::Left(value)) => T::push_value(&mut context, value),
+                Ok(Either::Right(data)) => T::push_data(&mut context, data).await,
+                Err(e) => T::push_error(&mut context, e),
+            }
+        }
+
+        let submitted = req.headers().get_one(CsrfToken::HEADER).or(field_token);
+        let error = match submitted {
+            Some(token) if CsrfToken::session(req).matches(token) => {
+                return match T::finalize(context) {
+                    Ok(value) => Outcome::Success(CsrfForm(value)),
+                    Err(e) => Outcome::Failure((e.status(), e)),
+                };
+            }
+            Some(_) => Error::validation("CSRF token mismatch"),
+            None => Error::from(ErrorKind::Missing),
+        };
+
+        let errors = Errors::from(error.with_name(CsrfToken::FIELD));
+        Outcome::Failure((Status::Forbidden, errors))
+    }
+}
+
+impl<T> Deref for CsrfForm<T> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<T> DerefMut for CsrfForm<T> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
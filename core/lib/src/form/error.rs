@@ -190,6 +190,9 @@ pub enum ErrorKind<'v> {
     Missing,
     /// An unexpected entity was received.
     Unexpected,
+    /// A field was used both as a value and as the parent of nested fields,
+    /// as in `a=1&a.b=2`.
+    Conflict,
     /// An unknown entity was received.
     Unknown,
     /// A custom error occurred.
@@ -845,6 +848,7 @@ impl fmt::Display for ErrorKind<'_> {
             ErrorKind::Duplicate => "duplicate".fmt(f)?,
             ErrorKind::Missing => "missing".fmt(f)?,
             ErrorKind::Unexpected => "unexpected".fmt(f)?,
+            ErrorKind::Conflict => "conflicts with nested fields".fmt(f)?,
             ErrorKind::Unknown => "unknown internal error".fmt(f)?,
             ErrorKind::Custom(e) => e.fmt(f)?,
             ErrorKind::Multipart(e) => write!(f, "invalid multipart: {}", e)?,
@@ -873,6 +877,7 @@ impl crate::http::ext::IntoOwned for ErrorKind<'_> {
             Duplicate => Duplicate,
             Missing => Missing,
             Unexpected => Unexpected,
+            Conflict => Conflict,
             Unknown => Unknown,
             Custom(e) => Custom(e),
             Multipart(e) => Multipart(e),
@@ -904,6 +909,7 @@ impl<'a, 'b> PartialEq<ErrorKind<'b>> for ErrorKind<'a> {
             (Duplicate, Duplicate) => true,
             (Missing, Missing) => true,
             (Unexpected, Unexpected) => true,
+            (Conflict, Conflict) => true,
             (Custom(_), Custom(_)) => true,
             (Multipart(a), Multipart(b)) => a == b,
             (Utf8(a), Utf8(b)) => a == b,
@@ -998,7 +1004,8 @@ impl fmt::Display for Entity {
 impl Entity {
     /// The default entity for an [`Error`] created for `ErrorKind`.
     ///
-    ///  * **[`Field`]** if `Duplicate`, `Missing`, `Unexpected`, or `Unknown`
+    ///  * **[`Field`]** if `Duplicate`, `Missing`, `Unexpected`, `Conflict`,
+    ///    or `Unknown`
     ///  * **[`Form`]** if `Multipart` or `Io`
     ///  * **[`Value`]** otherwise
     ///
@@ -1021,7 +1028,8 @@ impl Entity {
             | ErrorKind::Duplicate
             | ErrorKind::Missing
             | ErrorKind::Unknown
-            | ErrorKind::Unexpected => Entity::Field,
+            | ErrorKind::Unexpected
+            | ErrorKind::Conflict => Entity::Field,
 
             | ErrorKind::Multipart(_)
             | ErrorKind::Io(_) => Entity::Form,
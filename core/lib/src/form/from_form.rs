@@ -110,7 +110,7 @@ use crate::http::uncased::AsUncased;
 /// | `Vec<T>`           | _inherit_   | `vec![]`          | if `T` | if `T` | `T: FromForm`                                      |
 /// | [`HashMap<K, V>`]  | _inherit_   | `HashMap::new()`  | if `V` | if `V` | `K: FromForm + Eq + Hash`, `V: FromForm`           |
 /// | [`BTreeMap<K, V>`] | _inherit_   | `BTreeMap::new()` | if `V` | if `V` | `K: FromForm + Ord`, `V: FromForm`                 |
-/// | `bool`             | _inherit_   | `false`           | No     | Yes    | `"yes"/"on"/"true"`, `"no"/"off"/"false"`          |
+/// | `bool`             | _inherit_   | `false`           | No     | Yes    | `on`/`yes`/`true`/`1`, `off`/`no`/`false`/`0`      |
 /// | (un)signed int     | _inherit_   | **no default**    | No     | Yes    | `{u,i}{size,8,16,32,64,128}`                       |
 /// | _nonzero_ int      | _inherit_   | **no default**    | No     | Yes    | `NonZero{I,U}{size,8,16,32,64,128}`                |
 /// | float              | _inherit_   | **no default**    | No     | Yes    | `f{32,64}`                                         |
@@ -123,6 +123,10 @@ use crate::http::uncased::AsUncased;
 /// | [`time::Date`]     | _inherit_   | **no default**    | No     | Yes    | `%F` (`YYYY-MM-DD`). HTML "date" input.            |
 /// | [`time::DateTime`] | _inherit_   | **no default**    | No     | Yes    | `%FT%R` or `%FT%T` (`YYYY-MM-DDTHH:MM[:SS]`)       |
 /// | [`time::Time`]     | _inherit_   | **no default**    | No     | Yes    | `%R` or `%T` (`HH:MM[:SS]`)                        |
+/// | [`HumanDuration`]  | _inherit_   | **no default**    | No     | Yes    | `30s`, `1h30m`. See [`HumanDuration`].             |
+/// | [`NaiveDate`]      | _inherit_   | **no default**    | No     | Yes    | `YYYY-MM-DD`. Requires the `chrono` feature.       |
+/// | [`NaiveDateTime`]  | _inherit_   | **no default**    | No     | Yes    | `YYYY-MM-DDTHH:MM[:SS[.f]]`. Requires `chrono`.    |
+/// | [`DateTime<Utc>`]  | _inherit_   | **no default**    | No     | Yes    | RFC 3339 with an offset. Requires `chrono`.        |
 ///
 /// [`Result<T>`]: crate::form::Result
 /// [`Strict<T>`]: crate::form::Strict
@@ -132,6 +136,10 @@ use crate::http::uncased::AsUncased;
 /// [`TempFile`]: crate::fs::TempFile
 /// [`Capped<C>`]: crate::data::Capped
 /// [`time::DateTime`]: time::PrimitiveDateTime
+/// [`HumanDuration`]: crate::request::HumanDuration
+/// [`NaiveDate`]: https://docs.rs/chrono/0.4/chrono/naive/struct.NaiveDate.html
+/// [`NaiveDateTime`]: https://docs.rs/chrono/0.4/chrono/naive/struct.NaiveDateTime.html
+/// [`DateTime<Utc>`]: https://docs.rs/chrono/0.4/chrono/struct.DateTime.html
 /// [`IpAddr`]: std::net::IpAddr
 /// [`Ipv4Addr`]: std::net::Ipv4Addr
 /// [`Ipv6Addr`]: std::net::Ipv6Addr
@@ -150,6 +158,14 @@ use crate::http::uncased::AsUncased;
 ///     are returned at finalization, if any, or the successfully created vector
 ///     is returned.
 ///
+///     As such, repeated fields with the same name, as submitted by a group
+///     of checkboxes (`tag=a&tag=b&tag=c`), are collected in submission
+///     order. A field with no values, even when parsing strictly, is an empty
+///     vector. Errors for an element are named with the element's index, so
+///     an invalid second value in `n=1&n=x` is reported for `n.1`. Likewise,
+///     indexed fields (`items[0].id=1&items[0].qty=2&items[1].id=3`) are
+///     grouped into one element per index, in the order they're submitted.
+///
 ///   * **`HashMap<K, V>` where `K: FromForm + Eq + Hash`, `V: FromForm`**
 ///
 ///     **`BTreeMap<K, V>` where `K: FromForm + Ord`, `V: FromForm`**
@@ -174,8 +190,10 @@ use crate::http::uncased::AsUncased;
 ///   * **`bool`**
 ///
 ///     Parses as `false` for missing values (when lenient) and case-insensitive
-///     values of `off`, `false`, and `no`. Parses as `true` for values of `on`,
-///     `true`, `yes`, and the empty value. Failed to parse otherwise.
+///     values of `off`, `false`, `no`, and `0`. Parses as `true` for values
+///     of `on`, `true`, `yes`, `1`, and the empty value. Failed to parse
+///     otherwise. This mirrors HTML checkboxes, which submit `on` when checked
+///     and nothing at all when unchecked.
 ///
 ///   * **[`time::DateTime`]**
 ///
@@ -189,6 +207,22 @@ use crate::http::uncased::AsUncased;
 ///     This is the `"time"` HTML input type without support for the millisecond
 ///     variant.
 ///
+///   * **[`NaiveDate`]**, **[`NaiveDateTime`]**, **[`DateTime<Utc>`]**
+///
+///     _Requires the `chrono` feature._
+///
+///     `NaiveDate` parses a date as `YYYY-MM-DD`, the `"date"` HTML input type
+///     and an RFC 3339 full date. `NaiveDateTime` parses `YYYY-MM-DDTHH:MM` or
+///     `YYYY-MM-DDTHH:MM:SS` with optional fractional seconds, which includes
+///     every variant of the `"datetime-local"` HTML input type. `DateTime<Utc>`
+///     parses an RFC 3339 date and time, which must include a timezone offset,
+///     and converts it to UTC.
+///
+///     Values with a timezone offset are rejected as a `NaiveDateTime`, and
+///     values without one are rejected as a `DateTime<Utc>`: the two are never
+///     silently converted. Dates that don't exist, such as `2023-02-30`, and
+///     empty values fail to parse.
+///
 /// # Push Parsing
 ///
 /// `FromForm` describes a push-based parser for Rocket's [field wire format].
@@ -558,6 +592,11 @@ pub struct VecContext<'v, T: FromForm<'v>> {
     opts: Options,
     last_key: Option<&'v Key>,
     current: Option<T::Context>,
+    /// The name of `current`, the name of the vector, and the index of
+    /// `current` within it. Used to name errors by their index.
+    name: Option<&'v Name>,
+    parent: Option<&'v Name>,
+    index: usize,
     errors: Errors<'v>,
     items: Vec<T>
 }
@@ -568,6 +607,9 @@ impl<'v, T: FromForm<'v>> VecContext<'v, T> {
             opts,
             last_key: None,
             current: None,
+            name: None,
+            parent: None,
+            index: 0,
             items: vec![],
             errors: Errors::new(),
         }
@@ -577,8 +619,27 @@ impl<'v, T: FromForm<'v>> VecContext<'v, T> {
         if let Some(current) = self.current.take() {
             match T::finalize(current) {
                 Ok(v) => self.items.push(v),
-                Err(e) => self.errors.extend(e)
+                Err(mut errors) => {
+                    let name = NameBuf::from((self.parent, self.index.to_string()));
+                    for error in errors.iter_mut() {
+                        // Rename errors for the element itself, but not those
+                        // named for a field nested within it.
+                        let own = match (&error.name, self.name) {
+                            (Some(e), Some(n)) => e == n,
+                            (Some(_), None) => false,
+                            (None, _) => true,
+                        };
+
+                        if own {
+                            error.name = Some(name.clone());
+                        }
+                    }
+
+                    self.errors.extend(errors);
+                }
             }
+
+            self.index += 1;
         }
     }
 
@@ -591,6 +652,8 @@ impl<'v, T: FromForm<'v>> VecContext<'v, T> {
 
         if !keys_match {
             self.shift();
+            self.name = Some(name.as_name());
+            self.parent = name.parent();
             self.current = Some(T::init(self.opts));
         }
 
@@ -619,8 +682,6 @@ impl<'v, T: FromForm<'v> + 'v> FromForm<'v> for Vec<T> {
         this.shift();
         if !this.errors.is_empty() {
             Err(this.errors)
-        } else if this.opts.strict && this.items.is_empty() {
-            Err(Errors::from(ErrorKind::Missing))
         } else {
             Ok(this.items)
         }
@@ -192,11 +192,20 @@ pub struct FromFieldContext<'v, T: FromFormField<'v>> {
     field_value: Option<&'v str>,
     opts: Options,
     value: Option<Result<'v, T>>,
-    pushes: usize
+    pushes: usize,
+    /// The name of the first field pushed without a remaining key, if any,
+    /// and whether a field with a remaining key, a nested field, was pushed.
+    scalar: Option<&'v Name>,
+    nested: bool,
 }
 
 impl<'v, T: FromFormField<'v>> FromFieldContext<'v, T> {
-    fn should_push(&mut self) -> bool {
+    fn should_push(&mut self, name: &NameView<'v>) -> bool {
+        match name.key() {
+            Some(_) => self.nested = true,
+            None => { self.scalar.get_or_insert(name.as_name()); },
+        }
+
         self.pushes += 1;
         self.value.is_none()
     }
@@ -225,23 +234,29 @@ impl<'v, T: FromFormField<'v>> FromForm<'v> for T {
             field_value: None,
             value: None,
             pushes: 0,
+            scalar: None,
+            nested: false,
         }
     }
 
     fn push_value(ctxt: &mut Self::Context, field: ValueField<'v>) {
-        if ctxt.should_push() {
+        if ctxt.should_push(&field.name) {
             ctxt.field_value = Some(field.value);
             ctxt.push(field.name, Self::from_value(field))
         }
     }
 
     async fn push_data(ctxt: &mut FromFieldContext<'v, T>, field: DataField<'v, '_>) {
-        if ctxt.should_push() {
+        if ctxt.should_push(&field.name) {
             ctxt.push(field.name, Self::from_data(field).await);
         }
     }
 
     fn finalize(ctxt: Self::Context) -> Result<'v, Self> {
+        if let (Some(name), true) = (ctxt.scalar, ctxt.nested) {
+            return Err(Error::from(ErrorKind::Conflict).with_name(name).into());
+        }
+
         let mut errors = match ctxt.value {
             Some(Ok(val)) if !ctxt.opts.strict || ctxt.pushes <= 1 => return Ok(val),
             Some(Ok(_)) => Errors::from(ErrorKind::Duplicate),
@@ -314,8 +329,8 @@ impl<'v> FromFormField<'v> for bool {
 
     fn from_value(field: ValueField<'v>) -> Result<'v, Self> {
         match field.value.as_uncased() {
-            v if v == "off" || v == "no" || v == "false" => Ok(false),
-            v if v.is_empty() || v == "on" || v == "yes" || v == "true" => Ok(true),
+            v if v == "off" || v == "no" || v == "false" || v == "0" => Ok(false),
+            v if v.is_empty() || v == "on" || v == "yes" || v == "true" || v == "1" => Ok(true),
             // force a `ParseBoolError`
             _ => Ok("".parse()?),
         }
@@ -394,3 +409,61 @@ impl<'v> FromFormField<'v> for PrimitiveDateTime {
         Ok(dt)
     }
 }
+
+// Keep formats in sync with the `FromForm` documentation.
+#[cfg(feature = "chrono")]
+static CHRONO_DATE_FMT: &str = "%Y-%m-%d";
+#[cfg(feature = "chrono")]
+static CHRONO_DATE_TIME_FMTS: [&str; 2] = ["%Y-%m-%dT%H:%M:%S%.f", "%Y-%m-%dT%H:%M"];
+
+/// Parses `value` with `parse`, naming the value `what` in error messages.
+#[cfg(feature = "chrono")]
+fn parse_chrono<'v, T, F>(value: &str, what: &str, parse: F) -> Result<'v, T>
+    where F: Fn(&str) -> chrono_::ParseResult<T>
+{
+    if value.is_empty() {
+        return Err(Error::validation(format!("empty {}", what)).into());
+    }
+
+    parse(value).map_err(|e| Error::validation(format!("invalid {}: {}", what, e)).into())
+}
+
+#[cfg(feature = "chrono")]
+#[cfg_attr(nightly, doc(cfg(feature = "chrono")))]
+impl<'v> FromFormField<'v> for chrono_::NaiveDate {
+    fn from_value(field: ValueField<'v>) -> Result<'v, Self> {
+        parse_chrono(field.value, "date", |v| Self::parse_from_str(v, CHRONO_DATE_FMT))
+    }
+}
+
+#[cfg(feature = "chrono")]
+#[cfg_attr(nightly, doc(cfg(feature = "chrono")))]
+impl<'v> FromFormField<'v> for chrono_::NaiveDateTime {
+    fn from_value(field: ValueField<'v>) -> Result<'v, Self> {
+        let value = field.value;
+        if chrono_::DateTime::parse_from_rfc3339(value).is_ok() {
+            let msg = "unexpected timezone offset in date and time without a timezone";
+            return Err(Error::validation(msg).into());
+        }
+
+        parse_chrono(value, "date and time", |v| {
+            Self::parse_from_str(v, CHRONO_DATE_TIME_FMTS[0])
+                .or_else(|_| Self::parse_from_str(v, CHRONO_DATE_TIME_FMTS[1]))
+        })
+    }
+}
+
+#[cfg(feature = "chrono")]
+#[cfg_attr(nightly, doc(cfg(feature = "chrono")))]
+impl<'v> FromFormField<'v> for chrono_::DateTime<chrono_::Utc> {
+    fn from_value(field: ValueField<'v>) -> Result<'v, Self> {
+        let value = field.value;
+        if <chrono_::NaiveDateTime as FromFormField>::from_value(field).is_ok() {
+            return Err(Error::validation("missing timezone offset in date and time").into());
+        }
+
+        parse_chrono(value, "date and time", |v| {
+            chrono_::DateTime::parse_from_rfc3339(v).map(|dt| dt.with_timezone(&chrono_::Utc))
+        })
+    }
+}
@@ -45,6 +45,12 @@
 //!   * `map[k:1]=Bob`
 //!   * `people[bob]nickname=Stan`
 //!
+//! Since a name is a path into a nested structure, a name can't be used both
+//! for a value and as the parent of other fields: parsing
+//! `user.address=x&user.address.city=Oulu` into a structure fails with an
+//! [`ErrorKind::Conflict`](error::ErrorKind::Conflict) error, even when
+//! parsing leniently.
+//!
 //! See [`FromForm`] for full details on push-parsing and complete examples.
 
 // ## Maps w/named Fields (`struct`)
@@ -367,8 +373,12 @@ mod form;
 mod context;
 mod strict;
 mod lenient;
+mod validated;
 mod parser;
 mod buffer;
+mod multipart;
+#[cfg(feature = "secrets")]
+mod csrf;
 pub mod validate;
 pub mod name;
 pub mod error;
@@ -396,6 +406,10 @@ pub use form::*;
 pub use context::*;
 pub use strict::*;
 pub use lenient::*;
+pub use validated::*;
+pub use multipart::*;
+#[cfg(feature = "secrets")]
+pub use csrf::*;
 
 #[doc(hidden)]
 pub mod prelude {
@@ -0,0 +1,478 @@
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+use tokio::io::AsyncRead;
+use tokio_util::io::ReaderStream;
+use futures::stream::Stream;
+
+use crate::Request;
+use crate::request::local_cache_once;
+use crate::data::{Data, FromData, Limits, Outcome};
+use crate::http::ContentType;
+use crate::form::{SharedStack, prelude::*};
+use crate::fs::FileName;
+
+/// A data guard that streams a `multipart/form-data` body field-by-field.
+///
+/// Unlike [`Form`], which parses an entire form into a [`FromForm`] type,
+/// `Multipart` hands fields to the handler one at a time, in the order they
+/// appear in the body, via [`Multipart::next()`]. Nothing beyond the current
+/// field is read from the network until the handler asks for it.
+///
+/// Fields are yielded as a [`MultipartField`]:
+///
+///   * Fields _without_ a `Content-Type` are [`MultipartField::Value`]s. Their
+///     name and value are read into memory and stored in request-local
+///     storage, exactly as with [`Form`].
+///   * Fields _with_ a `Content-Type`, typically file uploads, are
+///     [`MultipartField::Data`]s. Their contents are _not_ read into memory;
+///     instead, [`MultipartData::data`] streams the part directly from the
+///     body. Opening it yields a [`DataStream`](crate::data::DataStream),
+///     which implements [`AsyncRead`] and can be piped straight to disk.
+///
+/// Reading is pull-based: the underlying body is only read as quickly as the
+/// handler consumes the current part. A slow consumer thus slows the upstream
+/// read. Unread data in a part is skipped when the next field is requested,
+/// but the previous [`MultipartData`] must be dropped first.
+///
+/// # Data Limits
+///
+/// The total amount of data read is limited by the `data-form` limit, which
+/// defaults to 2MiB. Each [`MultipartData`] part is additionally limited by
+/// the limit passed to [`Data::open()`].
+///
+/// # Example
+///
+/// ```rust
+/// # #[macro_use] extern crate rocket;
+/// use rocket::form::{Multipart, MultipartField};
+/// use rocket::data::ToByteUnit;
+///
+/// #[post("/upload", data = "<form>")]
+/// async fn upload(mut form: Multipart<'_>) -> std::io::Result<String> {
+///     let mut summary = String::new();
+///     while let Some(field) = form.next().await {
+///         match field {
+///             Ok(MultipartField::Value(v)) => summary += &format!("{}={} ", v.name, v.value),
+///             Ok(MultipartField::Data(d)) => {
+///                 let mut sink = tokio::io::sink();
+///                 let n = d.data.open(64.mebibytes()).stream_to(&mut sink).await?;
+///                 summary += &format!("{}:{} ", d.name, n);
+///             }
+///             Err(e) => summary += &format!("error: {} ", e),
+///         }
+///     }
+///
+///     Ok(summary)
+/// }
+/// ```
+pub struct Multipart<'r> {
+    buffer: &'r SharedStack<String>,
+    source: multer::Multipart<'r>,
+    done: bool,
+}
+
+/// A field yielded by [`Multipart::next()`].
+pub enum MultipartField<'r> {
+    /// A field without a `Content-Type`, buffered in memory.
+    Value(ValueField<'r>),
+    /// A field with a `Content-Type`, streamed from the body.
+    Data(MultipartData<'r>),
+}
+
+/// A streamed multipart field with a `Content-Type`.
+///
+/// This is the request-independent counterpart to [`DataField`].
+pub struct MultipartData<'r> {
+    /// The (decoded) name of the form field.
+    pub name: NameView<'r>,
+    /// The form field's file name.
+    pub file_name: Option<&'r FileName>,
+    /// The form field's Content-Type, as submitted, which may or may not
+    /// reflect on `data`.
+    pub content_type: ContentType,
+    /// The raw data stream of the part.
+    pub data: Data<'r>,
+}
+
+impl<'r> Multipart<'r> {
+    /// Creates a parser reading a multipart body delimited by `boundary` from
+    /// `reader`, storing small field names and values in `buffer`.
+    pub(crate) fn with_reader<R>(buffer: &'r SharedStack<String>, reader: R, boundary: &str) -> Self
+        where R: AsyncRead + Unpin + Send + 'r
+    {
+        let stream = Paced { stream: ReaderStream::new(reader), budget: Paced::<R>::BUDGET };
+        let source = multer::Multipart::new(stream, boundary);
+        Multipart { buffer, source, done: false }
+    }
+
+    /// Creates a parser for the multipart body `data` of `req`. The body is
+    /// read up to the `data-form` limit.
+    pub(crate) fn new(req: &'r Request<'_>, data: Data<'r>) -> std::result::Result<Self, Error<'r>> {
+        let boundary = req.content_type()
+            .ok_or(multer::Error::NoMultipart)?
+            .param("boundary")
+            .ok_or(multer::Error::NoBoundary)?;
+
+        let form_limit = req.limits()
+            .get("data-form")
+            .unwrap_or(Limits::DATA_FORM);
+
+        let buffer = local_cache_once!(req, SharedStack::new());
+        Ok(Multipart::with_reader(buffer, data.open(form_limit), boundary))
+    }
+
+    /// Returns `None` when there are no further fields. Otherwise, reads the
+    /// next field from the body and returns it or the error encountered.
+    ///
+    /// Once an error is returned, all subsequent calls return `None`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::form::{Multipart, MultipartField};
+    ///
+    /// async fn names(mut form: Multipart<'_>) -> Vec<String> {
+    ///     let mut names = vec![];
+    ///     while let Some(Ok(field)) = form.next().await {
+    ///         match field {
+    ///             MultipartField::Value(v) => names.push(v.name.to_string()),
+    ///             MultipartField::Data(d) => names.push(d.name.to_string()),
+    ///         }
+    ///     }
+    ///
+    ///     names
+    /// }
+    /// ```
+    pub async fn next(&mut self) -> Option<std::result::Result<MultipartField<'r>, Error<'r>>> {
+        if self.done {
+            return None;
+        }
+
+        let field = match self.source.next_field().await {
+            Ok(Some(field)) => field,
+            Ok(None) => return None,
+            Err(e) => {
+                self.done = true;
+                return Some(Err(e.into()));
+            }
+        };
+
+        // A field with a content-type is data; one without is "value".
+        trace_!("multipart field: {:?}", field);
+        let content_type = field.content_type().and_then(|m| m.as_ref().parse().ok());
+        let field = if let Some(content_type) = content_type {
+            let (name, file_name) = match (field.name(), field.file_name()) {
+                (None, None) => ("", None),
+                (None, Some(file_name)) => ("", Some(self.buffer.push(file_name))),
+                (Some(name), None) => (self.buffer.push(name), None),
+                (Some(a), Some(b)) => {
+                    let (field_name, file_name) = self.buffer.push_two(a, b);
+                    (field_name, Some(file_name))
+                }
+            };
+
+            MultipartField::Data(MultipartData {
+                content_type,
+                name: NameView::new(name),
+                file_name: file_name.map(FileName::new),
+                data: Data::from(field),
+            })
+        } else {
+            let (mut buf, len) = match field.name() {
+                Some(s) => (s.to_string(), s.len()),
+                None => (String::new(), 0)
+            };
+
+            match field.text().await {
+                Ok(text) => buf.push_str(&text),
+                Err(e) => return Some(Err(e.into())),
+            };
+
+            let name_val = self.buffer.push_split(buf, len);
+            MultipartField::Value(ValueField::from(name_val))
+        };
+
+        Some(Ok(field))
+    }
+}
+
+/// A stream adapter that returns `Pending`, immediately rescheduling the task,
+/// each time it has yielded [`Paced::BUDGET`] bytes.
+///
+/// `multer` moves every item its source has ready into an internal buffer each
+/// time it is polled. Without pacing, a body that arrives faster than a handler
+/// consumes it would be buffered in its entirety, defeating backpressure. With
+/// it, at most about one budget's worth is buffered ahead of the handler.
+struct Paced<S> {
+    stream: S,
+    budget: usize,
+}
+
+impl<S> Paced<S> {
+    /// The number of bytes yielded between forced yields to the scheduler.
+    const BUDGET: usize = 64 * 1024;
+}
+
+impl<S, B, E> Stream for Paced<S>
+    where S: Stream<Item = std::result::Result<B, E>> + Unpin, B: AsRef<[u8]>
+{
+    type Item = S::Item;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<S::Item>> {
+        if self.budget == 0 {
+            self.budget = Self::BUDGET;
+            cx.waker().wake_by_ref();
+            return Poll::Pending;
+        }
+
+        let item = futures::ready!(Pin::new(&mut self.stream).poll_next(cx));
+        if let Some(Ok(bytes)) = &item {
+            self.budget = self.budget.saturating_sub(bytes.as_ref().len());
+        }
+
+        Poll::Ready(item)
+    }
+}
+
+impl<'r> MultipartData<'r> {
+    /// Attaches `request` to `self`, converting it into a [`DataField`].
+    pub(crate) fn with_request<'i>(self, request: &'r Request<'i>) -> DataField<'r, 'i> {
+        DataField {
+            name: self.name,
+            file_name: self.file_name,
+            content_type: self.content_type,
+            request,
+            data: self.data,
+        }
+    }
+}
+
+#[crate::async_trait]
+impl<'r> FromData<'r> for Multipart<'r> {
+    type Error = Errors<'r>;
+
+    async fn from_data(req: &'r Request<'_>, data: Data<'r>) -> Outcome<'r, Self> {
+        if !req.content_type().map_or(false, |c| c.is_form_data()) {
+            return Outcome::Forward(data);
+        }
+
+        match Multipart::new(req, data) {
+            Ok(multipart) => Outcome::Success(multipart),
+            Err(e) => Outcome::Failure((e.status(), e.into())),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::pin::Pin;
+    use std::sync::Arc;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::task::{Context, Poll};
+
+    use futures::stream::Stream;
+    use tokio::io::{AsyncRead, AsyncReadExt, ReadBuf};
+
+    use super::{Multipart, MultipartField, Paced, SharedStack};
+    use crate::data::ToByteUnit;
+
+    const BOUNDARY: &str = "X-BOUNDARY-1234";
+
+    /// A reader that yields `body` at most `chunk` bytes at a time, ending
+    /// one read exactly at `split`, if set, and counting the number of bytes
+    /// handed out so far in `pulled`.
+    struct Chunked {
+        body: Vec<u8>,
+        pos: usize,
+        chunk: usize,
+        split: Option<usize>,
+        pulled: Arc<AtomicUsize>,
+    }
+
+    impl Chunked {
+        fn new(body: Vec<u8>, chunk: usize) -> Self {
+            let pulled = Arc::new(AtomicUsize::new(0));
+            Chunked { body, pos: 0, chunk, split: None, pulled }
+        }
+
+        fn split_at(body: Vec<u8>, chunk: usize, split: usize) -> Self {
+            Chunked { split: Some(split), ..Chunked::new(body, chunk) }
+        }
+    }
+
+    impl AsyncRead for Chunked {
+        fn poll_read(
+            mut self: Pin<&mut Self>,
+            _: &mut Context<'_>,
+            buf: &mut ReadBuf<'_>
+        ) -> Poll<std::io::Result<()>> {
+            let mut end = std::cmp::min(self.body.len(), self.pos + self.chunk);
+            if let Some(split) = self.split.filter(|&s| s > self.pos && s < end) {
+                end = split;
+            }
+
+            let n = std::cmp::min(end - self.pos, buf.remaining());
+            buf.put_slice(&self.body[self.pos..(self.pos + n)]);
+            self.pos += n;
+            self.pulled.fetch_add(n, Ordering::SeqCst);
+            Poll::Ready(Ok(()))
+        }
+    }
+
+    fn multipart_body(parts: &[(&str, Option<&str>, &[u8])]) -> Vec<u8> {
+        let mut body = Vec::new();
+        for (name, content_type, data) in parts {
+            body.extend_from_slice(format!("--{}\r\n", BOUNDARY).as_bytes());
+            let disposition = match content_type {
+                Some(_) => format!("form-data; name=\"{}\"; filename=\"{}.bin\"", name, name),
+                None => format!("form-data; name=\"{}\"", name),
+            };
+
+            body.extend_from_slice(format!("Content-Disposition: {}\r\n", disposition).as_bytes());
+            if let Some(ct) = content_type {
+                body.extend_from_slice(format!("Content-Type: {}\r\n", ct).as_bytes());
+            }
+
+            body.extend_from_slice(b"\r\n");
+            body.extend_from_slice(data);
+            body.extend_from_slice(b"\r\n");
+        }
+
+        body.extend_from_slice(format!("--{}--\r\n", BOUNDARY).as_bytes());
+        body
+    }
+
+    fn pattern(len: usize) -> Vec<u8> {
+        (0..len).map(|i| (i % 251) as u8).collect()
+    }
+
+    async fn collect(reader: Chunked) -> Vec<(String, Vec<u8>)> {
+        let buffer = SharedStack::new();
+        let mut multipart = Multipart::with_reader(&buffer, reader, BOUNDARY);
+        let mut fields = vec![];
+        while let Some(field) = multipart.next().await {
+            match field.expect("valid multipart field") {
+                MultipartField::Value(v) => {
+                    fields.push((v.name.to_string(), v.value.as_bytes().to_vec()));
+                }
+                MultipartField::Data(d) => {
+                    let name = d.name.to_string();
+                    let mut bytes = vec![];
+                    let n = d.data.open(64.mebibytes()).stream_to(&mut bytes).await.unwrap();
+                    assert!(n.complete);
+                    fields.push((name, bytes));
+                }
+            }
+        }
+
+        fields
+    }
+
+    #[test]
+    fn test_multi_megabyte_parts() {
+        crate::async_test(async {
+            let big = pattern(3 * 1024 * 1024 + 17);
+            let bigger = pattern(5 * 1024 * 1024 + 3);
+            let body = multipart_body(&[
+                ("note", None, b"hello"),
+                ("a", Some("application/octet-stream"), &big),
+                ("b", Some("application/octet-stream"), &bigger),
+            ]);
+
+            let fields = collect(Chunked::new(body, 8192)).await;
+            assert_eq!(fields.len(), 3);
+            assert_eq!(fields[0], ("note".into(), b"hello".to_vec()));
+            assert_eq!(fields[1].0, "a");
+            assert!(fields[1].1 == big);
+            assert_eq!(fields[2].0, "b");
+            assert!(fields[2].1 == bigger);
+        })
+    }
+
+    #[test]
+    fn test_boundary_straddles_chunks() {
+        crate::async_test(async {
+            let data = pattern(1000);
+            let body = multipart_body(&[
+                ("first", Some("application/octet-stream"), &data),
+                ("value", None, b"a value"),
+                ("second", Some("text/plain"), b"short"),
+            ]);
+
+            // Find the delimiter after the first part, then end a read at every
+            // offset within it so that it's seen in two pieces.
+            let delimiter = format!("\r\n--{}", BOUNDARY);
+            let start = body.windows(delimiter.len())
+                .position(|w| w == delimiter.as_bytes())
+                .unwrap();
+
+            for split in start..=(start + delimiter.len()) {
+                for chunk in [3, 64, 1 << 16] {
+                    let fields = collect(Chunked::split_at(body.clone(), chunk, split)).await;
+                    assert_eq!(fields.len(), 3, "split = {}, chunk = {}", split, chunk);
+                    assert!(fields[0].1 == data, "split = {}, chunk = {}", split, chunk);
+                    assert_eq!(fields[1], ("value".into(), b"a value".to_vec()));
+                    assert_eq!(fields[2], ("second".into(), b"short".to_vec()));
+                }
+            }
+
+            // And a large part read in chunks that don't divide the part evenly.
+            let data = pattern(2 * 1024 * 1024 + 1);
+            let body = multipart_body(&[("file", Some("application/octet-stream"), &data)]);
+            for chunk in [7, 4093, 4097, 65521] {
+                let fields = collect(Chunked::new(body.clone(), chunk)).await;
+                assert_eq!(fields.len(), 1);
+                assert!(fields[0].1 == data, "chunk = {}", chunk);
+            }
+        })
+    }
+
+    #[test]
+    fn test_slow_consumer_applies_backpressure() {
+        crate::async_test(async {
+            let data = pattern(8 * 1024 * 1024);
+            let body = multipart_body(&[("file", Some("application/octet-stream"), &data)]);
+            let reader = Chunked::new(body, 4096);
+            let pulled = reader.pulled.clone();
+
+            let buffer = SharedStack::new();
+            let mut multipart = Multipart::with_reader(&buffer, reader, BOUNDARY);
+            let field = match multipart.next().await {
+                Some(Ok(MultipartField::Data(d))) => d,
+                _ => panic!("expected a data field"),
+            };
+
+            let mut stream = field.data.open(64.mebibytes());
+            let mut buf = vec![0; 64 * 1024];
+            stream.read_exact(&mut buf).await.unwrap();
+            assert_eq!(&buf[..], &data[..buf.len()]);
+
+            // Only a small window past what was consumed may have been read.
+            let pulled = pulled.load(Ordering::SeqCst);
+            assert!(pulled >= buf.len());
+            assert!(pulled < buf.len() + 256 * 1024, "read {} bytes upstream", pulled);
+        })
+    }
+
+    #[test]
+    fn paced_yields_once_per_budget() {
+        let chunks = (0..64).map(|_| Ok::<_, ()>(vec![0u8; 4096]));
+        let stream = futures::stream::iter(chunks);
+        let mut paced = Paced { stream, budget: Paced::<()>::BUDGET };
+        let mut cx = Context::from_waker(futures::task::noop_waker_ref());
+
+        let (mut items, mut pending) = (0, 0);
+        loop {
+            match Pin::new(&mut paced).poll_next(&mut cx) {
+                Poll::Ready(Some(_)) => items += 1,
+                Poll::Ready(None) => break,
+                Poll::Pending => pending += 1,
+            }
+        }
+
+        // 256KiB in 4KiB chunks, pausing after each 64KiB.
+        assert_eq!(items, 64);
+        assert_eq!(pending, 4);
+    }
+}
@@ -1,4 +1,3 @@
-use multer::Multipart;
 use either::Either;
 
 use crate::request::{Request, local_cache_once};
@@ -12,9 +11,7 @@ type Field<'r, 'i> = Either<ValueField<'r>, DataField<'r, 'i>>;
 
 pub struct MultipartParser<'r, 'i> {
     request: &'r Request<'i>,
-    buffer: &'r SharedStack<String>,
     source: Multipart<'r>,
-    done: bool,
 }
 
 pub struct RawStrParser<'r> {
@@ -58,20 +55,9 @@ impl<'r, 'i> Parser<'r, 'i> {
     }
 
     async fn from_multipart(req: &'r Request<'i>, data: Data<'r>) -> Result<'r, Parser<'r, 'i>> {
-        let boundary = req.content_type()
-            .ok_or(multer::Error::NoMultipart)?
-            .param("boundary")
-            .ok_or(multer::Error::NoBoundary)?;
-
-        let form_limit = req.limits()
-            .get("data-form")
-            .unwrap_or(Limits::DATA_FORM);
-
         Ok(Parser::Multipart(MultipartParser {
             request: req,
-            buffer: local_cache_once!(req, SharedStack::new()),
-            source: Multipart::with_reader(data.open(form_limit), boundary),
-            done: false,
+            source: Multipart::new(req, data)?,
         }))
     }
 
@@ -147,53 +133,10 @@ impl<'r, 'i> MultipartParser<'r, 'i> {
     /// Returns `None` when there are no further fields. Otherwise tries to
     /// parse the next multipart form field and returns the result.
     async fn next(&mut self) -> Option<Result<'r, Field<'r, 'i>>> {
-        if self.done {
-            return None;
-        }
-
-        let field = match self.source.next_field().await {
-            Ok(Some(field)) => field,
-            Ok(None) => return None,
-            Err(e) => {
-                self.done = true;
-                return Some(Err(e.into()));
-            }
-        };
-
-        // A field with a content-type is data; one without is "value".
-        trace_!("multipart field: {:?}", field);
-        let content_type = field.content_type().and_then(|m| m.as_ref().parse().ok());
-        let field = if let Some(content_type) = content_type {
-            let (name, file_name) = match (field.name(), field.file_name()) {
-                (None, None) => ("", None),
-                (None, Some(file_name)) => ("", Some(self.buffer.push(file_name))),
-                (Some(name), None) => (self.buffer.push(name), None),
-                (Some(a), Some(b)) => {
-                    let (field_name, file_name) = self.buffer.push_two(a, b);
-                    (field_name, Some(file_name))
-                }
-            };
-
-            Either::Right(DataField {
-                content_type,
-                request: self.request,
-                name: NameView::new(name),
-                file_name: file_name.map(crate::fs::FileName::new),
-                data: Data::from(field),
-            })
-        } else {
-            let (mut buf, len) = match field.name() {
-                Some(s) => (s.to_string(), s.len()),
-                None => (String::new(), 0)
-            };
-
-            match field.text().await {
-                Ok(text) => buf.push_str(&text),
-                Err(e) => return Some(Err(e.into())),
-            };
-
-            let name_val = self.buffer.push_split(buf, len);
-            Either::Left(ValueField::from(name_val))
+        let field = match self.source.next().await? {
+            Ok(MultipartField::Value(field)) => Either::Left(field),
+            Ok(MultipartField::Data(field)) => Either::Right(field.with_request(self.request)),
+            Err(e) => return Some(Err(e)),
         };
 
         Some(Ok(field))
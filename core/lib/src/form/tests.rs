@@ -83,11 +83,17 @@ fn bool() {
         &["=false", "=no", "=off"] => Vec<bool> = vec![false, false, false],
         &["=tRuE", "=YES", "=On"] => Vec<bool> = vec![true, true, true],
         &["=fAlSE", "=NO", "=OFF"] => Vec<bool> = vec![false, false, false],
+        &["=1", "=0"] => Vec<bool> = vec![true, false],
+        &["=on"] => bool = true,
+        &["=off"] => bool = false,
+        &[] => bool = false,
     }
 
     assert_parses_fail! {
         &[] => Strict<bool>,
         &["=unknown"] => bool,
+        &["=2"] => bool,
+        &["=checked"] => bool,
         &["=unknown", "=please"] => Vec<bool>,
     }
 }
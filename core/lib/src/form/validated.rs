@@ -0,0 +1,184 @@
+use std::fmt;
+use std::ops::Deref;
+use std::marker::PhantomData;
+
+use crate::form::prelude::*;
+use crate::http::uri::fmt::{Query, FromUriParam};
+
+/// A form field of type `T` validated by `V` after it parses.
+///
+/// This type implements the [`FromFormField`] trait and can thus be used as
+/// the type of a field in a [`FromForm`] derive or as a form guard directly.
+/// It first parses a `T` exactly as `T` would; if that succeeds, the parsed
+/// value is validated with [`V::validate()`](Validate::validate()). `V` is
+/// typically a unit struct naming the validation, so that validation is
+/// declared by the field's type and reused wherever the type is used.
+///
+/// # Errors
+///
+/// Errors returned by `V`, like parse errors, are scoped to the field: Rocket
+/// sets their name to the name of the field and their value to the field's
+/// value, so they can be shown next to the field when a form is re-rendered,
+/// as with [`Contextual`]. A `FromForm` derive collects the errors of every
+/// field, so when several `Validated` fields fail, all of their errors are
+/// reported, not only the first.
+///
+/// When the field is missing and `T` has a [default](FromFormField::default()),
+/// the default is used only if it passes validation.
+///
+/// # Example
+///
+/// ```rust
+/// # #[macro_use] extern crate rocket;
+/// use rocket::form::{self, Form, Error, Validate, Validated};
+///
+/// /// Accepts only addresses at `example.com`.
+/// struct AllowedDomain;
+///
+/// impl<'a> Validate<&'a str> for AllowedDomain {
+///     fn validate<'v>(email: &&'a str) -> form::Result<'v, ()> {
+///         match email.rsplit_once('@') {
+///             Some((user, "example.com")) if !user.is_empty() => Ok(()),
+///             _ => Err(Error::validation("email must be at example.com"))?,
+///         }
+///     }
+/// }
+///
+/// #[derive(FromForm)]
+/// struct Signup<'r> {
+///     email: Validated<&'r str, AllowedDomain>,
+/// }
+///
+/// #[post("/signup", data = "<form>")]
+/// fn signup(form: Form<Signup<'_>>) -> String {
+///     format!("welcome, {}", *form.email)
+/// }
+/// ```
+pub struct Validated<T, V>(T, PhantomData<fn() -> V>);
+
+/// A validation of a parsed `T`, for use with [`Validated`].
+///
+/// # Example
+///
+/// A validation may report several errors for one value:
+///
+/// ```rust
+/// use rocket::form::{self, Error, Errors, Validate};
+///
+/// struct StrongPassword;
+///
+/// impl Validate<String> for StrongPassword {
+///     fn validate<'v>(password: &String) -> form::Result<'v, ()> {
+///         let mut errors = Errors::new();
+///         if password.len() < 12 {
+///             errors.push(Error::validation("must be at least 12 characters"));
+///         }
+///
+///         if !password.chars().any(|c| c.is_ascii_digit()) {
+///             errors.push(Error::validation("must contain a digit"));
+///         }
+///
+///         match errors.is_empty() {
+///             true => Ok(()),
+///             false => Err(errors),
+///         }
+///     }
+/// }
+///
+/// assert_eq!(StrongPassword::validate(&"short".to_string()).unwrap_err().len(), 2);
+/// assert!(StrongPassword::validate(&"correct horse 1".to_string()).is_ok());
+/// ```
+pub trait Validate<T> {
+    /// Validates `value`, returning the reasons it is invalid, typically as
+    /// errors created with [`Error::validation()`], if it is.
+    fn validate<'v>(value: &T) -> Result<'v, ()>;
+}
+
+impl<T, V> Validated<T, V> {
+    /// Consumes `self` and returns the inner, validated value.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # #[macro_use] extern crate rocket;
+    /// use rocket::form::{self, Form, Validate, Validated};
+    ///
+    /// struct NonEmpty;
+    ///
+    /// impl Validate<String> for NonEmpty {
+    ///     fn validate<'v>(value: &String) -> form::Result<'v, ()> {
+    ///         form::validate::len(value, 1..)
+    ///     }
+    /// }
+    ///
+    /// #[post("/name", data = "<name>")]
+    /// fn name(name: Form<Validated<String, NonEmpty>>) -> String {
+    ///     name.into_inner().into_inner()
+    /// }
+    /// ```
+    #[inline(always)]
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+#[crate::async_trait]
+impl<'v, T: FromFormField<'v>, V: Validate<T>> FromFormField<'v> for Validated<T, V> {
+    fn from_value(field: ValueField<'v>) -> Result<'v, Self> {
+        let value = T::from_value(field)?;
+        V::validate(&value)?;
+        Ok(Validated(value, PhantomData))
+    }
+
+    async fn from_data(field: DataField<'v, '_>) -> Result<'v, Self> {
+        let value = T::from_data(field).await?;
+        V::validate(&value)?;
+        Ok(Validated(value, PhantomData))
+    }
+
+    fn default() -> Option<Self> {
+        T::default()
+            .filter(|value| V::validate(value).is_ok())
+            .map(|value| Validated(value, PhantomData))
+    }
+}
+
+impl<T, V> Deref for Validated<T, V> {
+    type Target = T;
+
+    #[inline(always)]
+    fn deref(&self) -> &T {
+        &self.0
+    }
+}
+
+impl<T: fmt::Debug, V> fmt::Debug for Validated<T, V> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("Validated").field(&self.0).finish()
+    }
+}
+
+impl<T: Clone, V> Clone for Validated<T, V> {
+    fn clone(&self) -> Self {
+        Validated(self.0.clone(), PhantomData)
+    }
+}
+
+impl<T: PartialEq, V> PartialEq for Validated<T, V> {
+    fn eq(&self, other: &Self) -> bool {
+        self.0 == other.0
+    }
+}
+
+impl<T: Eq, V> Eq for Validated<T, V> { }
+
+impl<'f, A, T, V> FromUriParam<Query, A> for Validated<T, V>
+    where T: FromUriParam<Query, A> + FromFormField<'f>, V: Validate<T>
+{
+    type Target = T::Target;
+
+    #[inline(always)]
+    fn from_uri_param(param: A) -> Self::Target {
+        T::from_uri_param(param)
+    }
+}
@@ -0,0 +1,605 @@
+//! Safe retries of unsafe requests via an `Idempotency-Key` header.
+//!
+//! A client that may retry a request, like a `POST` that creates a payment,
+//! sends a unique key with it in an `Idempotency-Key` header. The first
+//! request with a given key from a given client for a given route runs as
+//! usual; its response is recorded by the [`Idempotency`] fairing. A repeat of
+//! the request with the same key and the same body doesn't run the handler
+//! again: the recorded status, headers, and body are replayed instead, with an
+//! additional `Idempotent-Replayed: true` header. A repeat with the same key
+//! but a different body fails with `409 Conflict`.
+//!
+//! Requests with the same key that arrive while the first is still in flight
+//! wait for it to complete and are then replayed. Recorded responses expire
+//! after a configurable time to live. Server errors, with a `5xx` status, are
+//! never recorded, so a request that failed that way can be retried with the
+//! same key.
+//!
+//! At most [`max_keys`](Idempotency::max_keys()) keys are recorded at once.
+//! When there is no room for a new key, expired recordings are dropped; if
+//! there's still no room, the request fails with `503 Service Unavailable`.
+//! Live recordings are never dropped early, as a retry would then run the
+//! handler again.
+//!
+//! Routes opt in by using the [`IdempotentBody`] data guard, which requires
+//! the [`Idempotency`] fairing to be attached. Other routes are unaffected,
+//! even for requests with an `Idempotency-Key` header.
+//!
+//! # Client Scope
+//!
+//! Keys are chosen by clients, so one client's keys must not match another's
+//! recordings. Keys are scoped to the client's
+//! [real IP address](Request::real_client_ip()) by default. A different scope,
+//! like an account or API token, can be used with [`Idempotency::scope()`].
+//!
+//! # Body
+//!
+//! [`IdempotentBody`] reads the body as [`RawBody`] does, up to the `raw-body`
+//! [limit](crate::data::Limits), and compares its SHA-256 digest against the
+//! recorded one. The handler receives the bytes to parse as it sees fit.
+//!
+//! # Example
+//!
+//! ```rust
+//! # #[macro_use] extern crate rocket;
+//! use std::time::Duration;
+//!
+//! use rocket::idempotency::{Idempotency, IdempotentBody};
+//!
+//! #[post("/payments", data = "<amount>")]
+//! fn pay(amount: IdempotentBody<'_>) -> String {
+//!     format!("charged {}", String::from_utf8_lossy(&amount))
+//! }
+//!
+//! #[launch]
+//! fn rocket() -> _ {
+//!     rocket::build()
+//!         .attach(Idempotency::new(Duration::from_secs(24 * 60 * 60)))
+//!         .mount("/", routes![pay])
+//! }
+//! ```
+
+use std::{fmt, io};
+use std::io::Cursor;
+use std::ops::Deref;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use std::collections::HashMap;
+use std::collections::hash_map::Entry;
+
+use parking_lot::Mutex;
+use sha2::{Digest, Sha256};
+use tokio::sync::{Mutex as AsyncMutex, OwnedMutexGuard};
+
+use crate::{Rocket, Build, Ignite, Sentinel, Response};
+use crate::data::{Data, FromData, Outcome, RawBody};
+use crate::fairing::{self, Fairing, Info, Kind};
+use crate::http::{Header, Status, StatusClass};
+use crate::outcome::Outcome::*;
+use crate::request::Request;
+
+type ScopeFn = dyn Fn(&Request<'_>) -> Option<String> + Send + Sync;
+
+/// The key of a recorded response: the client's scope, the idempotency key,
+/// and the route.
+type SlotKey = (String, String, String);
+
+/// The SHA-256 digest of a request's body.
+type Fingerprint = [u8; 32];
+
+/// The response recorded for a slot, if any, behind a lock held by the request
+/// that is producing it.
+type Recorded = Arc<AsyncMutex<Option<Arc<Stored>>>>;
+
+/// The lock on a slot's recording, held by the request producing it.
+type Producing = OwnedMutexGuard<Option<Arc<Stored>>>;
+
+/// The default maximum number of keys recorded at once.
+const DEFAULT_MAX_KEYS: usize = 10_000;
+
+/// A fairing that records and replays responses for [`IdempotentBody`].
+///
+/// An `Idempotency` is cheap to clone: clones share the same recordings.
+///
+/// See the [module level docs](self) for an example.
+#[derive(Clone)]
+pub struct Idempotency {
+    ttl: Duration,
+    max_keys: usize,
+    scope: Arc<ScopeFn>,
+    slots: Arc<Mutex<Slots>>,
+}
+
+/// The slots of all known keys and when expired slots were last dropped.
+struct Slots {
+    map: HashMap<SlotKey, Slot>,
+    swept: Instant,
+}
+
+/// A key's use on one route.
+struct Slot {
+    created: Instant,
+    fingerprint: Fingerprint,
+    response: Recorded,
+}
+
+/// A recorded response.
+struct Stored {
+    status: Status,
+    headers: Vec<Header<'static>>,
+    body: Vec<u8>,
+}
+
+/// The slot a request is producing the response for, in its local cache. Holds
+/// the slot's lock until the response is recorded.
+struct Pending(SlotKey, Mutex<Option<Producing>>);
+
+/// The response to replay for a request, in its local cache.
+struct Replay(Arc<Stored>);
+
+impl Idempotency {
+    /// The name of the header the key is read from.
+    pub const HEADER: &'static str = "Idempotency-Key";
+
+    /// The name of the header added to replayed responses.
+    pub const REPLAYED_HEADER: &'static str = "Idempotent-Replayed";
+
+    /// The maximum length, in bytes, of a key.
+    pub const MAX_KEY_LEN: usize = 255;
+
+    /// Returns a fairing that records responses for `ttl`, scoping keys to the
+    /// client's [real IP address](Request::real_client_ip()).
+    ///
+    /// # Panics
+    ///
+    /// Panics if `ttl` is zero.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use std::time::Duration;
+    /// use rocket::idempotency::Idempotency;
+    ///
+    /// let idempotency = Idempotency::new(Duration::from_secs(60 * 60));
+    /// ```
+    pub fn new(ttl: Duration) -> Idempotency {
+        assert!(!ttl.is_zero(), "idempotency ttl must be non-zero");
+        Idempotency {
+            ttl,
+            max_keys: DEFAULT_MAX_KEYS,
+            scope: Arc::new(|req| req.real_client_ip().map(|ip| ip.to_string())),
+            slots: Arc::new(Mutex::new(Slots {
+                map: HashMap::new(),
+                swept: Instant::now(),
+            })),
+        }
+    }
+
+    /// Sets the maximum number of keys recorded at once. **(default:
+    /// `10_000`)**
+    ///
+    /// When a new key arrives and the limit is reached even after dropping
+    /// expired recordings, the request fails with `503 Service Unavailable`
+    /// and [`IdempotencyError::TooManyKeys`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if `max` is zero.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use std::time::Duration;
+    /// use rocket::idempotency::Idempotency;
+    ///
+    /// let idempotency = Idempotency::new(Duration::from_secs(60)).max_keys(1_000);
+    /// ```
+    pub fn max_keys(mut self, max: usize) -> Self {
+        assert!(max > 0, "idempotency key maximum must be non-zero");
+        self.max_keys = max;
+        self
+    }
+
+    /// Sets the function that returns the scope of a request's key: only
+    /// requests with the same scope share recordings.
+    ///
+    /// Requests for which `scope` returns `None` fail with `400 Bad Request`
+    /// and [`IdempotencyError::UnknownClient`].
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use std::time::Duration;
+    /// use rocket::idempotency::Idempotency;
+    ///
+    /// let idempotency = Idempotency::new(Duration::from_secs(60 * 60))
+    ///     .scope(|req| req.headers().get_one("X-Api-Token").map(|t| t.to_string()));
+    /// ```
+    pub fn scope<F>(mut self, scope: F) -> Self
+        where F: Fn(&Request<'_>) -> Option<String> + Send + Sync + 'static
+    {
+        self.scope = Arc::new(scope);
+        self
+    }
+
+    /// Finds or creates the slot for `key` with a body of `fingerprint`, at
+    /// `now`. Returns the slot's recording if it exists and an owned lock on a
+    /// new recording otherwise.
+    fn slot(
+        &self,
+        key: SlotKey,
+        fingerprint: Fingerprint,
+        now: Instant,
+    ) -> Result<Result<Recorded, Producing>, IdempotencyError> {
+        let mut guard = self.slots.lock();
+        let slots = &mut *guard;
+
+        // Sweeping once per `ttl` bounds how long expired slots are kept.
+        if now.saturating_duration_since(slots.swept) >= self.ttl {
+            self.sweep(slots, now);
+        }
+
+        if slots.map.get(&key).map_or(false, |slot| self.is_expired(slot, now)) {
+            slots.map.remove(&key);
+        }
+
+        if slots.map.len() >= self.max_keys && !slots.map.contains_key(&key) {
+            self.sweep(slots, now);
+            if slots.map.len() >= self.max_keys {
+                return Err(IdempotencyError::TooManyKeys);
+            }
+        }
+
+        match slots.map.entry(key) {
+            Entry::Occupied(e) if e.get().fingerprint != fingerprint => {
+                Err(IdempotencyError::Conflict)
+            }
+            Entry::Occupied(e) => Ok(Ok(e.get().response.clone())),
+            Entry::Vacant(e) => {
+                let response: Recorded = Arc::new(AsyncMutex::new(None));
+                let guard = response.clone().try_lock_owned().expect("new lock is unlocked");
+                e.insert(Slot { created: now, fingerprint, response });
+                Ok(Err(guard))
+            }
+        }
+    }
+
+    /// Returns `true` if `slot` has expired at `now`.
+    fn is_expired(&self, slot: &Slot, now: Instant) -> bool {
+        now.saturating_duration_since(slot.created) >= self.ttl
+    }
+
+    /// Drops the slots that have expired at `now`.
+    fn sweep(&self, slots: &mut Slots, now: Instant) {
+        slots.map.retain(|_, slot| !self.is_expired(slot, now));
+        slots.swept = now;
+    }
+
+    /// Returns `true` if `recorded` is the recording of the slot for `key`.
+    fn is_current(&self, key: &SlotKey, recorded: &Recorded) -> bool {
+        let slots = self.slots.lock();
+        slots.map.get(key).map_or(false, |slot| Arc::ptr_eq(&slot.response, recorded))
+    }
+
+    /// Forgets the slot for `key`, so that the key can be reused.
+    fn forget(&self, key: &SlotKey) {
+        self.slots.lock().map.remove(key);
+    }
+}
+
+/// Returns the recorded response to replay for `req`, if its
+/// [`IdempotentBody`] guard found one. The response is sent as is: no catcher
+/// is invoked for it.
+pub(crate) fn replayed<'r>(req: &'r Request<'_>) -> Option<Response<'r>> {
+    let Replay(stored) = req.local_cache_get::<Replay>()?;
+    info_!("Replaying recorded response for idempotency key.");
+
+    let mut replay = Response::build()
+        .status(stored.status)
+        .sized_body(stored.body.len(), Cursor::new(stored.body.clone()))
+        .finalize();
+
+    for header in &stored.headers {
+        replay.adjoin_header(header.clone());
+    }
+
+    // As with catchers, cookies set while routing aren't sent.
+    req.cookies().reset_delta();
+    replay.set_header(Header::new(Idempotency::REPLAYED_HEADER, "true"));
+    Some(replay)
+}
+
+#[crate::async_trait]
+impl Fairing for Idempotency {
+    fn info(&self) -> Info {
+        Info {
+            name: "Idempotency",
+            kind: Kind::Ignite | Kind::Response | Kind::Singleton,
+        }
+    }
+
+    async fn on_ignite(&self, rocket: Rocket<Build>) -> fairing::Result {
+        Ok(rocket.manage(self.clone()))
+    }
+
+    async fn on_response<'r>(&self, req: &'r Request<'_>, res: &mut Response<'r>) {
+        let Pending(key, pending) = match req.local_cache_get::<Pending>() {
+            Some(pending) => pending,
+            None => return,
+        };
+
+        let mut guard = match pending.lock().take() {
+            Some(guard) => guard,
+            None => return,
+        };
+
+        if res.status().class() == StatusClass::ServerError {
+            self.forget(key);
+            return;
+        }
+
+        match res.body_mut().to_bytes().await {
+            Ok(body) => {
+                res.set_sized_body(body.len(), Cursor::new(body.clone()));
+                let headers = res.headers().iter()
+                    .map(|h| Header::new(h.name().to_string(), h.value().to_string()))
+                    .collect();
+
+                *guard = Some(Arc::new(Stored { status: res.status(), headers, body }));
+            }
+            Err(e) => {
+                warn_!("Failed to record response for idempotency: {}", e);
+                self.forget(key);
+            }
+        }
+    }
+}
+
+impl fmt::Debug for Idempotency {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Idempotency")
+            .field("ttl", &self.ttl)
+            .field("max_keys", &self.max_keys)
+            .finish_non_exhaustive()
+    }
+}
+
+/// A request's idempotency key.
+///
+/// A key is between 1 and [`Idempotency::MAX_KEY_LEN`] bytes of visible ASCII,
+/// read from the [`Idempotency-Key`](Idempotency::HEADER) header. It is
+/// available from [`IdempotentBody::key()`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct IdempotencyKey(String);
+
+impl IdempotencyKey {
+    /// Returns the key as a string slice.
+    #[inline(always)]
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl Deref for IdempotencyKey {
+    type Target = str;
+
+    #[inline(always)]
+    fn deref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl fmt::Display for IdempotencyKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+/// A data guard for the body of a request with an idempotency key.
+///
+/// The guard reads the key from the [`Idempotency-Key`](Idempotency::HEADER)
+/// header and the body as [`RawBody`] does. Keys are scoped to the client, as
+/// set by [`Idempotency::scope()`], and to the matched route. The guard:
+///
+///   * succeeds on the first use of a key, and the response is recorded;
+///   * fails with [`IdempotencyError::Replayed`] on a repeat with the same
+///     body, after which the recorded response is sent in place of running
+///     the handler, without invoking a catcher;
+///   * fails with `409 Conflict` and [`IdempotencyError::Conflict`] on a
+///     repeat with a different body;
+///   * fails with `503 Service Unavailable` and
+///     [`IdempotencyError::TooManyKeys`] on the first use of a key when
+///     [`max_keys`](Idempotency::max_keys()) keys are already recorded;
+///   * fails with `400 Bad Request` if the header is missing or invalid or if
+///     the client has no scope, and with the status of [`RawBody`] and
+///     [`IdempotencyError::Io`] if the body can't be read, for instance
+///     because it exceeds the `raw-body` limit.
+///
+/// Without an attached [`Idempotency`] fairing, it fails with `500 Internal
+/// Server Error`; as a [`Sentinel`], it aborts launch instead.
+/// `IdempotentBody` dereferences to `[u8]`.
+///
+/// See the [module level docs](self) for an example.
+pub struct IdempotentBody<'r> {
+    key: IdempotencyKey,
+    body: &'r [u8],
+}
+
+impl<'r> IdempotentBody<'r> {
+    /// Returns the request's idempotency key.
+    #[inline(always)]
+    pub fn key(&self) -> &IdempotencyKey {
+        &self.key
+    }
+
+    /// Returns the body bytes.
+    #[inline(always)]
+    pub fn as_bytes(&self) -> &'r [u8] {
+        self.body
+    }
+}
+
+impl Deref for IdempotentBody<'_> {
+    type Target = [u8];
+
+    fn deref(&self) -> &[u8] {
+        self.body
+    }
+}
+
+impl AsRef<[u8]> for IdempotentBody<'_> {
+    fn as_ref(&self) -> &[u8] {
+        self.body
+    }
+}
+
+impl fmt::Debug for IdempotentBody<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("IdempotentBody")
+            .field("key", &self.key)
+            .field("body", &self.body)
+            .finish()
+    }
+}
+
+/// Error returned by the [`IdempotentBody`] data guard.
+#[derive(Debug)]
+pub enum IdempotencyError {
+    /// The `Idempotency-Key` header is missing.
+    MissingKey,
+    /// The key is empty, too long, or contains characters other than visible
+    /// ASCII.
+    InvalidKey,
+    /// The request has no [scope](Idempotency::scope()).
+    UnknownClient,
+    /// The body couldn't be read.
+    Io(io::Error),
+    /// The key was used by this client for this route with a different body.
+    Conflict,
+    /// The key was used by this client for this route with the same body, and
+    /// the recorded response is replayed.
+    Replayed,
+    /// The key is new, but the [maximum](Idempotency::max_keys()) number of
+    /// keys are already recorded.
+    TooManyKeys,
+    /// There is no attached [`Idempotency`] fairing.
+    MissingFairing,
+}
+
+impl fmt::Display for IdempotencyError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            IdempotencyError::MissingKey => write!(f, "missing idempotency key"),
+            IdempotencyError::InvalidKey => write!(f, "invalid idempotency key"),
+            IdempotencyError::UnknownClient => write!(f, "unknown idempotency scope"),
+            IdempotencyError::Io(e) => write!(f, "i/o error: {}", e),
+            IdempotencyError::Conflict => write!(f, "idempotency key reused with another body"),
+            IdempotencyError::Replayed => write!(f, "replaying recorded response"),
+            IdempotencyError::TooManyKeys => write!(f, "too many idempotency keys"),
+            IdempotencyError::MissingFairing => write!(f, "no attached idempotency fairing"),
+        }
+    }
+}
+
+impl std::error::Error for IdempotencyError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            IdempotencyError::Io(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+#[crate::async_trait]
+impl<'r> FromData<'r> for IdempotentBody<'r> {
+    type Error = IdempotencyError;
+
+    async fn from_data(req: &'r Request<'_>, data: Data<'r>) -> Outcome<'r, Self> {
+        use IdempotencyError::*;
+
+        let idempotency = match req.rocket().state::<Idempotency>() {
+            Some(idempotency) => idempotency,
+            None => {
+                error_!("Attempted to use an idempotent body without `Idempotency`!");
+                return Failure((Status::InternalServerError, MissingFairing));
+            }
+        };
+
+        let key = match req.headers().get_one(Idempotency::HEADER) {
+            Some(key) => key,
+            None => return Failure((Status::BadRequest, MissingKey)),
+        };
+
+        let valid = (1..=Idempotency::MAX_KEY_LEN).contains(&key.len())
+            && key.bytes().all(|b| b.is_ascii_graphic());
+
+        if !valid {
+            return Failure((Status::BadRequest, InvalidKey));
+        }
+
+        let scope = match (idempotency.scope)(req) {
+            Some(scope) => scope,
+            None => return Failure((Status::BadRequest, UnknownClient)),
+        };
+
+        let body = match RawBody::from_data(req, data).await {
+            Success(body) => body.as_bytes(),
+            Failure((status, e)) => return Failure((status, Io(e))),
+            Forward(data) => return Forward(data),
+        };
+
+        // The guard may run more than once per request; it must not wait on
+        // the lock this request itself holds.
+        let key = IdempotencyKey(key.into());
+        if req.local_cache_get::<Pending>().is_some() {
+            return Success(IdempotentBody { key, body });
+        } else if let Some(Replay(stored)) = req.local_cache_get::<Replay>() {
+            return Failure((stored.status, Replayed));
+        }
+
+        let route = match req.route() {
+            Some(route) => format!("{} {}", route.method, route.uri),
+            None => String::new(),
+        };
+
+        let fingerprint: Fingerprint = Sha256::digest(body).into();
+        let slot_key = (scope, key.to_string(), route);
+        let guard = loop {
+            let recorded = match idempotency.slot(slot_key.clone(), fingerprint, Instant::now()) {
+                Ok(Ok(recorded)) => recorded,
+                Ok(Err(guard)) => break guard,
+                Err(e @ TooManyKeys) => return Failure((Status::ServiceUnavailable, e)),
+                Err(e) => return Failure((Status::Conflict, e)),
+            };
+
+            // Wait for the request producing the response, then replay it. If
+            // it didn't record one, either take over its slot or, if it was
+            // forgotten, start anew.
+            let guard = recorded.clone().lock_owned().await;
+            if let Some(stored) = &*guard {
+                let status = stored.status;
+                req.local_cache(|| Replay(stored.clone()));
+                return Failure((status, Replayed));
+            }
+
+            if idempotency.is_current(&slot_key, &recorded) {
+                break guard;
+            }
+        };
+
+        req.local_cache(|| Pending(slot_key, Mutex::new(Some(guard))));
+        Success(IdempotentBody { key, body })
+    }
+}
+
+impl Sentinel for IdempotentBody<'_> {
+    fn abort(rocket: &Rocket<Ignite>) -> bool {
+        if rocket.state::<Idempotency>().is_none() {
+            error!("launching without an attached `Idempotency` fairing.");
+            info_!("Using `IdempotentBody` requires attaching `Idempotency`.");
+            return true;
+        }
+
+        false
+    }
+}
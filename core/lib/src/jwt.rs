@@ -0,0 +1,485 @@
+//! Support for JSON Web Token (JWT) authentication.
+//!
+//! The [`Jwt`] request guard retrieves a bearer token from a request's
+//! `Authorization` header, verifies it with the managed [`JwtKey`], validates
+//! its time claims, and deserializes its claims. Only compact JWS tokens are
+//! supported; encrypted (JWE) tokens are not.
+//!
+//! # Example
+//!
+//! ```rust
+//! # #[macro_use] extern crate rocket;
+//! use rocket::serde::Deserialize;
+//! use rocket::jwt::{Algorithm, Jwt, JwtKey};
+//!
+//! #[derive(Deserialize)]
+//! # #[serde(crate = "rocket::serde")]
+//! struct Claims {
+//!     sub: String,
+//! }
+//!
+//! #[get("/me")]
+//! fn me(token: Jwt<Claims>) -> String {
+//!     format!("hello, {}", token.sub)
+//! }
+//!
+//! #[launch]
+//! fn rocket() -> _ {
+//!     let key = JwtKey::new(Algorithm::HS256, "a very secret key");
+//!     rocket::build().manage(key).mount("/", routes![me])
+//! }
+//! ```
+
+use std::fmt;
+use std::ops::Deref;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use ring::{hmac, signature};
+use serde::{Serialize, de::DeserializeOwned};
+use serde_json::{Map, Value};
+
+use crate::{Rocket, Ignite, Sentinel};
+use crate::http::Status;
+use crate::outcome::Outcome::*;
+use crate::request::{self, Request, FromRequest, FromParam, BearerToken, Base64, base64};
+
+/// A JWS signature algorithm, as named in a token's `alg` header.
+///
+/// The algorithm of a [`JwtKey`] is fixed when it is created: a token is only
+/// accepted if its `alg` is exactly the key's algorithm.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[allow(clippy::upper_case_acronyms)]
+pub enum Algorithm {
+    /// HMAC using SHA-256.
+    HS256,
+    /// HMAC using SHA-384.
+    HS384,
+    /// HMAC using SHA-512.
+    HS512,
+    /// RSASSA-PKCS1-v1_5 using SHA-256.
+    RS256,
+    /// RSASSA-PKCS1-v1_5 using SHA-384.
+    RS384,
+    /// RSASSA-PKCS1-v1_5 using SHA-512.
+    RS512,
+    /// ECDSA using P-256 and SHA-256.
+    ES256,
+    /// ECDSA using P-384 and SHA-384.
+    ES384,
+}
+
+/// The key with which [`Jwt`] guards verify tokens, and the policy with which
+/// they validate claims.
+///
+/// A `JwtKey` must be managed for [`Jwt`] to be used; Rocket aborts launch
+/// otherwise. The key's format depends on its [`Algorithm`]:
+///
+///   * `HS256`, `HS384`, `HS512`: the shared secret.
+///   * `RS256`, `RS384`, `RS512`: the DER-encoded PKCS#1 `RSAPublicKey`, as
+///     output by `openssl rsa -RSAPublicKey_out -outform DER`. The key must be
+///     at least 2048 bits.
+///   * `ES256`, `ES384`: the public key as an uncompressed point.
+///
+/// Time claims are checked with a leeway, 60 seconds by default, to allow for
+/// clock skew. By default, tokens must have an `exp` claim.
+///
+/// # Example
+///
+/// ```rust
+/// use std::time::Duration;
+/// use rocket::jwt::{Algorithm, JwtKey};
+///
+/// let key = JwtKey::new(Algorithm::HS256, "a very secret key")
+///     .leeway(Duration::from_secs(5))
+///     .require_exp(false);
+///
+/// # let _ = key;
+/// ```
+#[derive(Clone)]
+pub struct JwtKey {
+    algorithm: Algorithm,
+    key: Vec<u8>,
+    leeway: Duration,
+    require_exp: bool,
+}
+
+/// A request guard for a verified JSON Web Token with claims `C`.
+///
+/// The token is read from an `Authorization: Bearer` header and verified with
+/// the managed [`JwtKey`]. The guard:
+///
+///   * forwards if there is no `Authorization` header or it doesn't use the
+///     `Bearer` scheme;
+///   * fails with `401 Unauthorized` and a [`JwtError`] if the token is
+///     malformed, its `alg` isn't the key's algorithm, its signature doesn't
+///     verify, its `exp` has passed, its `nbf` or `iat` is in the future, or
+///     its claims don't deserialize into `C`;
+///   * fails with `500 Internal Server Error` if no `JwtKey` is managed.
+///
+/// Otherwise, the guard succeeds with the claims. `Jwt` dereferences to `C`,
+/// which can also be retrieved with [`Jwt::into_inner()`].
+///
+/// # Example
+///
+/// ```rust
+/// # #[macro_use] extern crate rocket;
+/// use rocket::serde::Deserialize;
+/// use rocket::jwt::{Jwt, JwtError};
+///
+/// #[derive(Deserialize)]
+/// # #[serde(crate = "rocket::serde")]
+/// struct Claims {
+///     sub: String,
+///     admin: bool,
+/// }
+///
+/// #[get("/admin")]
+/// fn admin(token: Result<Jwt<Claims>, JwtError>) -> String {
+///     match token {
+///         Ok(token) if token.admin => format!("welcome, {}", token.sub),
+///         Ok(_) => "not an admin".into(),
+///         Err(JwtError::Expired) => "please log in again".into(),
+///         Err(e) => format!("invalid token: {}", e),
+///     }
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Jwt<C>(C);
+
+/// Error returned by the [`Jwt`] guard and [`JwtKey::decode()`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum JwtError {
+    /// The token isn't three `.`-separated base64url parts, its header or
+    /// claims aren't JSON objects, a time claim isn't a number, or its header
+    /// has a `crit` parameter.
+    Malformed,
+    /// The token's `alg` isn't the key's algorithm. Tokens with an `alg` of
+    /// `none` always fail with this error.
+    InvalidAlgorithm,
+    /// The token's signature doesn't verify with the key.
+    InvalidSignature,
+    /// The token's `exp` has passed.
+    Expired,
+    /// The token's `nbf` or `iat` is in the future.
+    NotYetValid,
+    /// The token has no `exp` but one is required, or its claims don't
+    /// deserialize into the requested type. Contains a description.
+    InvalidClaims(String),
+    /// No [`JwtKey`] is managed.
+    MissingKey,
+}
+
+impl Algorithm {
+    /// Returns the name of the algorithm as used in a token's `alg` header.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::jwt::Algorithm;
+    ///
+    /// assert_eq!(Algorithm::HS256.as_str(), "HS256");
+    /// assert_eq!(Algorithm::ES384.as_str(), "ES384");
+    /// ```
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Algorithm::HS256 => "HS256",
+            Algorithm::HS384 => "HS384",
+            Algorithm::HS512 => "HS512",
+            Algorithm::RS256 => "RS256",
+            Algorithm::RS384 => "RS384",
+            Algorithm::RS512 => "RS512",
+            Algorithm::ES256 => "ES256",
+            Algorithm::ES384 => "ES384",
+        }
+    }
+
+    fn hmac(&self) -> Option<hmac::Algorithm> {
+        match self {
+            Algorithm::HS256 => Some(hmac::HMAC_SHA256),
+            Algorithm::HS384 => Some(hmac::HMAC_SHA384),
+            Algorithm::HS512 => Some(hmac::HMAC_SHA512),
+            _ => None,
+        }
+    }
+
+    fn public_key(&self) -> Option<&'static dyn signature::VerificationAlgorithm> {
+        match self {
+            Algorithm::RS256 => Some(&signature::RSA_PKCS1_2048_8192_SHA256),
+            Algorithm::RS384 => Some(&signature::RSA_PKCS1_2048_8192_SHA384),
+            Algorithm::RS512 => Some(&signature::RSA_PKCS1_2048_8192_SHA512),
+            Algorithm::ES256 => Some(&signature::ECDSA_P256_SHA256_FIXED),
+            Algorithm::ES384 => Some(&signature::ECDSA_P384_SHA384_FIXED),
+            _ => None,
+        }
+    }
+}
+
+impl JwtKey {
+    /// Creates a key for `algorithm` from `key`, in the format described in
+    /// the [type-level docs](JwtKey).
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::jwt::{Algorithm, JwtKey};
+    ///
+    /// let key = JwtKey::new(Algorithm::HS512, "a very secret key");
+    /// assert_eq!(key.algorithm(), Algorithm::HS512);
+    /// ```
+    pub fn new<K: Into<Vec<u8>>>(algorithm: Algorithm, key: K) -> JwtKey {
+        JwtKey {
+            algorithm,
+            key: key.into(),
+            leeway: Duration::from_secs(60),
+            require_exp: true,
+        }
+    }
+
+    /// Sets the leeway allowed when checking time claims.
+    pub fn leeway(mut self, leeway: Duration) -> Self {
+        self.leeway = leeway;
+        self
+    }
+
+    /// Sets whether tokens must have an `exp` claim.
+    pub fn require_exp(mut self, require: bool) -> Self {
+        self.require_exp = require;
+        self
+    }
+
+    /// Returns the key's algorithm.
+    pub fn algorithm(&self) -> Algorithm {
+        self.algorithm
+    }
+
+    /// Verifies `token` and validates and deserializes its claims, exactly
+    /// as the [`Jwt`] guard does.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use std::collections::HashMap;
+    /// use rocket::jwt::{Algorithm, JwtKey, JwtError};
+    ///
+    /// let key = JwtKey::new(Algorithm::HS256, "secret").require_exp(false);
+    /// let token = key.encode(&HashMap::from([("sub", "alice")])).unwrap();
+    ///
+    /// let claims: HashMap<String, String> = key.decode(&token).unwrap();
+    /// assert_eq!(claims["sub"], "alice");
+    ///
+    /// let other = JwtKey::new(Algorithm::HS256, "other secret");
+    /// let result = other.decode::<HashMap<String, String>>(&token);
+    /// assert_eq!(result.unwrap_err(), JwtError::InvalidSignature);
+    /// ```
+    pub fn decode<C: DeserializeOwned>(&self, token: &str) -> Result<C, JwtError> {
+        let (message, signature) = token.rsplit_once('.').ok_or(JwtError::Malformed)?;
+        let (header, claims) = message.split_once('.').ok_or(JwtError::Malformed)?;
+        if claims.contains('.') {
+            return Err(JwtError::Malformed);
+        }
+
+        let header = json_object(header)?;
+        if header.get("alg").and_then(Value::as_str) != Some(self.algorithm.as_str()) {
+            return Err(JwtError::InvalidAlgorithm);
+        }
+
+        if header.contains_key("crit") {
+            return Err(JwtError::Malformed);
+        }
+
+        let signature = base64url(signature)?;
+        if !self.verify(message.as_bytes(), &signature) {
+            return Err(JwtError::InvalidSignature);
+        }
+
+        let claims = json_object(claims)?;
+        self.validate_times(&claims)?;
+        serde_json::from_value(Value::Object(claims))
+            .map_err(|e| JwtError::InvalidClaims(e.to_string()))
+    }
+
+    /// Encodes `claims` into a token signed with `self`.
+    ///
+    /// Returns `None` if `self` is an RSA or ECDSA public key, which can't
+    /// sign, or if `claims` doesn't serialize into a JSON object.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use std::collections::HashMap;
+    /// use rocket::jwt::{Algorithm, JwtKey};
+    ///
+    /// let key = JwtKey::new(Algorithm::HS256, "secret");
+    /// let token = key.encode(&HashMap::from([("sub", "alice")])).unwrap();
+    /// assert!(token.starts_with("eyJhbGciOiJIUzI1NiIsInR5cCI6IkpXVCJ9."));
+    ///
+    /// assert!(key.encode(&"not an object").is_none());
+    /// ```
+    pub fn encode<C: Serialize>(&self, claims: &C) -> Option<String> {
+        let algorithm = self.algorithm.hmac()?;
+        let claims = serde_json::to_value(claims).ok().filter(Value::is_object)?;
+        let header = serde_json::json!({ "alg": self.algorithm.as_str(), "typ": "JWT" });
+
+        let mut token = to_base64url(header.to_string().as_bytes());
+        token.push('.');
+        token.push_str(&to_base64url(claims.to_string().as_bytes()));
+
+        let tag = hmac::sign(&hmac::Key::new(algorithm, &self.key), token.as_bytes());
+        token.push('.');
+        token.push_str(&to_base64url(tag.as_ref()));
+        Some(token)
+    }
+
+    fn verify(&self, message: &[u8], signature: &[u8]) -> bool {
+        if let Some(algorithm) = self.algorithm.hmac() {
+            let key = hmac::Key::new(algorithm, &self.key);
+            return hmac::verify(&key, message, signature).is_ok();
+        }
+
+        match self.algorithm.public_key() {
+            Some(algorithm) => signature::UnparsedPublicKey::new(algorithm, &self.key)
+                .verify(message, signature)
+                .is_ok(),
+            None => false,
+        }
+    }
+
+    fn validate_times(&self, claims: &Map<String, Value>) -> Result<(), JwtError> {
+        let time = |name: &str| match claims.get(name) {
+            Some(value) => value.as_f64().map(Some).ok_or(JwtError::Malformed),
+            None => Ok(None),
+        };
+
+        let now = SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default();
+        let (now, leeway) = (now.as_secs_f64(), self.leeway.as_secs_f64());
+        match time("exp")? {
+            Some(exp) if now - leeway >= exp => return Err(JwtError::Expired),
+            None if self.require_exp => {
+                return Err(JwtError::InvalidClaims("missing `exp` claim".into()));
+            }
+            _ => {}
+        }
+
+        for name in ["nbf", "iat"] {
+            if matches!(time(name)?, Some(t) if now + leeway < t) {
+                return Err(JwtError::NotYetValid);
+            }
+        }
+
+        Ok(())
+    }
+}
+
+fn base64url(part: &str) -> Result<Vec<u8>, JwtError> {
+    <Base64<base64::UrlSafe>>::from_param(part)
+        .map(Base64::into_inner)
+        .map_err(|_| JwtError::Malformed)
+}
+
+fn json_object(part: &str) -> Result<Map<String, Value>, JwtError> {
+    serde_json::from_slice(&base64url(part)?).map_err(|_| JwtError::Malformed)
+}
+
+fn to_base64url(data: &[u8]) -> String {
+    const ALPHABET: &[u8; 64] =
+        b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789-_";
+
+    let mut string = String::with_capacity((data.len() * 4 + 2) / 3);
+    for chunk in data.chunks(3) {
+        let bytes = [chunk[0], *chunk.get(1).unwrap_or(&0), *chunk.get(2).unwrap_or(&0)];
+        let bits = u32::from(bytes[0]) << 16 | u32::from(bytes[1]) << 8 | u32::from(bytes[2]);
+        for i in 0..=chunk.len() {
+            string.push(ALPHABET[(bits >> (18 - 6 * i) & 0x3f) as usize] as char);
+        }
+    }
+
+    string
+}
+
+impl<C> Jwt<C> {
+    /// Consumes `self` and returns the claims.
+    #[inline(always)]
+    pub fn into_inner(self) -> C {
+        self.0
+    }
+}
+
+impl<C> Deref for Jwt<C> {
+    type Target = C;
+
+    #[inline(always)]
+    fn deref(&self) -> &C {
+        &self.0
+    }
+}
+
+#[crate::async_trait]
+impl<'r, C: DeserializeOwned + Send> FromRequest<'r> for Jwt<C> {
+    type Error = JwtError;
+
+    async fn from_request(req: &'r Request<'_>) -> request::Outcome<Self, Self::Error> {
+        let key = match req.rocket().state::<JwtKey>() {
+            Some(key) => key,
+            None => {
+                error_!("Attempted to verify a JWT without a managed `JwtKey`!");
+                return Failure((Status::InternalServerError, JwtError::MissingKey));
+            }
+        };
+
+        let token = match req.guard::<BearerToken>().await {
+            Success(token) => token.0,
+            Failure(_) => return Failure((Status::Unauthorized, JwtError::Malformed)),
+            Forward(()) => return Forward(()),
+        };
+
+        match key.decode(&token) {
+            Ok(claims) => Success(Jwt(claims)),
+            Err(e) => Failure((Status::Unauthorized, e)),
+        }
+    }
+}
+
+impl<C> Sentinel for Jwt<C> {
+    fn abort(rocket: &Rocket<Ignite>) -> bool {
+        if rocket.state::<JwtKey>().is_none() {
+            error!("launching with unmanaged `JwtKey` state.");
+            info_!("Using `Jwt` requires managing a `JwtKey`.");
+            return true;
+        }
+
+        false
+    }
+}
+
+impl fmt::Debug for JwtKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("JwtKey")
+            .field("algorithm", &self.algorithm)
+            .field("key", &"<redacted>")
+            .field("leeway", &self.leeway)
+            .field("require_exp", &self.require_exp)
+            .finish()
+    }
+}
+
+impl fmt::Display for Algorithm {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl fmt::Display for JwtError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            JwtError::Malformed => f.write_str("malformed token"),
+            JwtError::InvalidAlgorithm => f.write_str("token algorithm is not accepted"),
+            JwtError::InvalidSignature => f.write_str("token signature is invalid"),
+            JwtError::Expired => f.write_str("token has expired"),
+            JwtError::NotYetValid => f.write_str("token is not yet valid"),
+            JwtError::InvalidClaims(e) => write!(f, "invalid token claims: {}", e),
+            JwtError::MissingKey => f.write_str("no JWT key is configured"),
+        }
+    }
+}
+
+impl std::error::Error for JwtError { }
@@ -59,15 +59,21 @@
 //! To avoid compiling unused dependencies, Rocket gates certain features. With
 //! the exception of `http2`, all are disabled by default:
 //!
-//! | Feature   | Description                                             |
-//! |-----------|---------------------------------------------------------|
-//! | `secrets` | Support for authenticated, encrypted [private cookies]. |
-//! | `tls`     | Support for [TLS] encrypted connections.                |
-//! | `mtls`    | Support for verified clients via [mutual TLS].          |
-//! | `http2`   | Support for HTTP/2 (enabled by default).                |
-//! | `json`    | Support for [JSON (de)serialization].                   |
-//! | `msgpack` | Support for [MessagePack (de)serialization].            |
-//! | `uuid`    | Support for [UUID value parsing and (de)serialization]. |
+//! | Feature        | Description                                             |
+//! |----------------|---------------------------------------------------------|
+//! | `secrets`      | Support for authenticated, encrypted [private cookies]. |
+//! | `tls`          | Support for [TLS] encrypted connections.                |
+//! | `mtls`         | Support for verified clients via [mutual TLS].          |
+//! | `http2`        | Support for HTTP/2 (enabled by default).                |
+//! | `json`         | Support for [JSON (de)serialization].                   |
+//! | `msgpack`      | Support for [MessagePack (de)serialization].            |
+//! | `uuid`         | Support for [UUID value parsing and (de)serialization]. |
+//! | `semver`       | Support for [semantic version] path parameters.         |
+//! | `chrono`       | Support for [chrono date and time] form fields.         |
+//! | `rust_decimal` | Support for exact [decimal number] path parameters.     |
+//! | `jwt`          | Support for [JSON Web Token] authentication.            |
+//! | `compress`     | Support for [response compression].                     |
+//! | `webhook`      | Support for [HMAC-signed webhook] bodies.               |
 //!
 //! Disabled features can be selectively enabled in `Cargo.toml`:
 //!
@@ -86,6 +92,12 @@
 //! [JSON (de)serialization]: crate::serde::json
 //! [MessagePack (de)serialization]: crate::serde::msgpack
 //! [UUID value parsing and (de)serialization]: crate::serde::uuid
+//! [semantic version]: crate::semver
+//! [chrono date and time]: crate::chrono
+//! [decimal number]: crate::rust_decimal
+//! [JSON Web Token]: crate::jwt
+//! [response compression]: crate::compress
+//! [HMAC-signed webhook]: crate::webhook
 //! [private cookies]: https://rocket.rs/v0.5-rc/guide/requests/#private-cookies
 //! [TLS]: https://rocket.rs/v0.5-rc/guide/configuration/#tls
 //! [mutual TLS]: crate::mtls
@@ -115,6 +127,15 @@ pub use futures;
 pub use tokio;
 pub use figment;
 pub use time;
+#[cfg(feature = "semver")]
+#[cfg_attr(nightly, doc(cfg(feature = "semver")))]
+pub use semver_ as semver;
+#[cfg(feature = "chrono")]
+#[cfg_attr(nightly, doc(cfg(feature = "chrono")))]
+pub use chrono_ as chrono;
+#[cfg(feature = "rust_decimal")]
+#[cfg_attr(nightly, doc(cfg(feature = "rust_decimal")))]
+pub use rust_decimal_ as rust_decimal;
 
 #[doc(hidden)]
 #[macro_use] pub mod log;
@@ -133,6 +154,8 @@ pub mod route;
 pub mod serde;
 pub mod shield;
 pub mod fs;
+pub mod rate_limit;
+pub mod idempotency;
 
 // Reexport of HTTP everything.
 pub mod http {
@@ -164,6 +187,18 @@ pub mod http {
 #[cfg_attr(nightly, doc(cfg(feature = "mtls")))]
 pub mod mtls;
 
+#[cfg(feature = "jwt")]
+#[cfg_attr(nightly, doc(cfg(feature = "jwt")))]
+pub mod jwt;
+
+#[cfg(feature = "compress")]
+#[cfg_attr(nightly, doc(cfg(feature = "compress")))]
+pub mod compress;
+
+#[cfg(feature = "webhook")]
+#[cfg_attr(nightly, doc(cfg(feature = "webhook")))]
+pub mod webhook;
+
 /// TODO: We need a futures mod or something.
 mod trip_wire;
 mod shutdown;
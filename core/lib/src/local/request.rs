@@ -118,6 +118,50 @@ macro_rules! pub_request_impl {
         self
     }
 
+    /// Mark this request as received over a Unix domain socket from `peer`.
+    ///
+    /// # Examples
+    ///
+    /// Set the peer to a process owned by user and group `1000`:
+    ///
+    /// ```rust
+    #[doc = $import]
+    /// use rocket::http::{UnixPeer, PeerCredentials};
+    ///
+    /// # Client::_test(|_, request, _| {
+    /// let request: LocalRequest = request;
+    /// let credentials = PeerCredentials { uid: 1000, gid: 1000, pid: None };
+    /// let req = request.unix_peer(UnixPeer { path: None, credentials: Some(credentials) });
+    /// # });
+    /// ```
+    #[inline]
+    pub fn unix_peer(mut self, peer: crate::http::UnixPeer) -> Self {
+        self._request_mut().set_unix_peer(peer);
+        self
+    }
+
+    /// Set the HTTP version of this request. Local requests are `HTTP/1.1`
+    /// by default.
+    ///
+    /// # Examples
+    ///
+    /// Set the version to `HTTP/2`:
+    ///
+    /// ```rust
+    #[doc = $import]
+    /// use rocket::http::HttpVersion;
+    ///
+    /// # Client::_test(|_, request, _| {
+    /// let request: LocalRequest = request;
+    /// let req = request.http_version(HttpVersion::Http2);
+    /// # });
+    /// ```
+    #[inline]
+    pub fn http_version(mut self, version: crate::http::HttpVersion) -> Self {
+        self._request_mut().set_http_version(version);
+        self
+    }
+
     /// Add a cookie to this request.
     ///
     /// # Examples
@@ -148,6 +148,53 @@ impl<S, E, F> IntoOutcome<S, E, F> for Option<S> {
     }
 }
 
+/// Converts `Ok(S)` into `Success(S)` and `Err(E)` into `Failure(E)`.
+///
+/// For request guards, where the failure value is a `(Status, E)` pair, see
+/// [`IntoOutcome::into_outcome()`] to choose the status from a plain `Result`.
+///
+/// # Example
+///
+/// ```rust
+/// # use rocket::outcome::Outcome::{self, *};
+/// let x: Outcome<i32, &str, ()> = Ok(10).into();
+/// assert_eq!(x, Success(10));
+///
+/// let x: Outcome<i32, &str, ()> = Err("bad").into();
+/// assert_eq!(x, Failure("bad"));
+/// ```
+impl<S, E, F> From<Result<S, E>> for Outcome<S, E, F> {
+    #[inline]
+    fn from(result: Result<S, E>) -> Self {
+        match result {
+            Ok(val) => Success(val),
+            Err(e) => Failure(e),
+        }
+    }
+}
+
+/// Converts `Some(S)` into `Success(S)` and `None` into `Forward(())`.
+///
+/// # Example
+///
+/// ```rust
+/// # use rocket::outcome::Outcome::{self, *};
+/// let x: Outcome<i32, &str, ()> = Some(10).into();
+/// assert_eq!(x, Success(10));
+///
+/// let x: Outcome<i32, &str, ()> = None.into();
+/// assert_eq!(x, Forward(()));
+/// ```
+impl<S, E> From<Option<S>> for Outcome<S, E, ()> {
+    #[inline]
+    fn from(option: Option<S>) -> Self {
+        match option {
+            Some(val) => Success(val),
+            None => Forward(()),
+        }
+    }
+}
+
 impl<S, E, F> Outcome<S, E, F> {
     /// Unwraps the Outcome, yielding the contents of a Success.
     ///
@@ -528,6 +575,40 @@ impl<S, E, F> Outcome<S, E, F> {
     ///
     /// assert_eq!(mapped, Success("10"));
     /// ```
+    ///
+    /// Combinators like `and_then()` and [`map()`](Outcome::map()) are
+    /// particularly useful when a request guard builds on another guard. Here,
+    /// an `Admin` guard extracts a `User` and refines it into an `Admin` only
+    /// if the user is an administrator, forwarding otherwise:
+    ///
+    /// ```rust
+    /// use rocket::request::{self, Request, FromRequest};
+    /// use rocket::outcome::Outcome::*;
+    /// # struct User { name: String, is_admin: bool }
+    /// # #[rocket::async_trait]
+    /// # impl<'r> FromRequest<'r> for User {
+    /// #     type Error = ();
+    /// #     async fn from_request(_: &'r Request<'_>) -> request::Outcome<User, ()> {
+    /// #         Success(User { name: "Bob".into(), is_admin: true })
+    /// #     }
+    /// # }
+    ///
+    /// struct Admin { name: String }
+    ///
+    /// #[rocket::async_trait]
+    /// impl<'r> FromRequest<'r> for Admin {
+    ///     type Error = ();
+    ///
+    ///     async fn from_request(req: &'r Request<'_>) -> request::Outcome<Self, ()> {
+    ///         req.guard::<User>().await
+    ///             .and_then(|user| match user.is_admin {
+    ///                 true => Success(user),
+    ///                 false => Forward(()),
+    ///             })
+    ///             .map(|user| Admin { name: user.name })
+    ///     }
+    /// }
+    /// ```
     #[inline]
     pub fn and_then<T, M: FnOnce(S) -> Outcome<T, E, F>>(self, f: M) -> Outcome<T, E, F> {
         match self {
@@ -597,6 +678,39 @@ impl<S, E, F> Outcome<S, E, F> {
         }
     }
 
+    /// Returns `self` if `self` is a `Success`. Otherwise, calls `f` and
+    /// returns the result.
+    ///
+    /// This is the `Outcome` analog of [`Option::or_else()`]. To inspect the
+    /// `Failure` or `Forward` value, use [`failure_then()`] or
+    /// [`forward_then()`] instead.
+    ///
+    /// [`failure_then()`]: Outcome::failure_then()
+    /// [`forward_then()`]: Outcome::forward_then()
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use rocket::outcome::Outcome;
+    /// # use rocket::outcome::Outcome::*;
+    /// #
+    /// let x: Outcome<i32, &str, usize> = Success(10);
+    /// assert_eq!(x.or_else(|| Success(20)), Success(10));
+    ///
+    /// let x: Outcome<i32, &str, usize> = Failure("hi");
+    /// assert_eq!(x.or_else(|| Success(20)), Success(20));
+    ///
+    /// let x: Outcome<i32, &str, usize> = Forward(5);
+    /// assert_eq!(x.or_else(|| Forward(7)), Forward(7));
+    /// ```
+    #[inline]
+    pub fn or_else<M: FnOnce() -> Outcome<S, E, F>>(self, f: M) -> Outcome<S, E, F> {
+        match self {
+            Success(val) => Success(val),
+            Failure(_) | Forward(_) => f(),
+        }
+    }
+
     /// Converts `Outcome<S, E, F>` to `Result<S, E>` by identity mapping
     /// `Success(S)` and `Failure(E)` to `Result<T, E>` and mapping `Forward(F)`
     /// to `Result<T, E>` using `f`.
@@ -697,7 +811,15 @@ crate::export! {
     /// immediately returned. Because of the early return, `try_outcome!` can
     /// only be used in methods that return [`Outcome`].
     ///
+    /// A second argument, `try_outcome!(value, failure)`, first converts
+    /// `value`, any [`IntoOutcome`] such as a `Result` or `Option`, into an
+    /// `Outcome` via [`IntoOutcome::into_outcome()`] with `failure`. In a
+    /// request guard, `failure` is a [`Status`](crate::http::Status) for a
+    /// `Result` and a `(Status, Error)` pair for an `Option`.
+    ///
     /// [`Outcome`]: crate::outcome::Outcome
+    /// [`IntoOutcome`]: crate::outcome::IntoOutcome
+    /// [`IntoOutcome::into_outcome()`]: crate::outcome::IntoOutcome::into_outcome()
     ///
     /// ## Example
     ///
@@ -743,6 +865,36 @@ crate::export! {
     ///     }
     /// }
     /// ```
+    ///
+    /// Converting a `Result` or `Option` with a chosen failure:
+    ///
+    /// ```rust,no_run
+    /// # #[macro_use] extern crate rocket;
+    /// use rocket::http::Status;
+    /// use rocket::request::{self, Request, FromRequest};
+    /// use rocket::outcome::{try_outcome, Outcome::*};
+    ///
+    /// struct Page(u32);
+    ///
+    /// #[derive(Debug)]
+    /// enum PageError {
+    ///     Missing,
+    ///     Invalid(std::num::ParseIntError),
+    /// }
+    ///
+    /// #[rocket::async_trait]
+    /// impl<'r> FromRequest<'r> for Page {
+    ///     type Error = PageError;
+    ///
+    ///     async fn from_request(req: &'r Request<'_>) -> request::Outcome<Self, PageError> {
+    ///         let page = req.headers().get_one("X-Page");
+    ///         let page = try_outcome!(page, (Status::BadRequest, PageError::Missing));
+    ///         let page = page.parse::<u32>().map_err(PageError::Invalid);
+    ///         let page = try_outcome!(page, Status::BadRequest);
+    ///         Success(Page(page))
+    ///     }
+    /// }
+    /// ```
     macro_rules! try_outcome {
         ($expr:expr $(,)?) => (match $expr {
             $crate::outcome::Outcome::Success(val) => val,
@@ -753,6 +905,9 @@ crate::export! {
                 return $crate::outcome::Outcome::Forward(::std::convert::From::from(f))
             },
         });
+        ($expr:expr, $failure:expr $(,)?) => ($crate::outcome::try_outcome!(
+            $crate::outcome::IntoOutcome::into_outcome($expr, $failure)
+        ));
     }
 }
 
@@ -768,3 +923,82 @@ impl<S, E, F> fmt::Display for Outcome<S, E, F> {
         write!(f, "{}", Paint::default(string).fg(color))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::Outcome::{self, *};
+
+    type Test = Outcome<i32, &'static str, u8>;
+
+    const SUCCESS: Test = Success(10);
+    const FAILURE: Test = Failure("bad");
+    const FORWARD: Test = Forward(3);
+
+    #[test]
+    fn map() {
+        assert_eq!(SUCCESS.map(|v| v * 2), Success(20));
+        assert_eq!(FAILURE.map(|v| v * 2), Failure("bad"));
+        assert_eq!(FORWARD.map(|v| v * 2), Forward(3));
+    }
+
+    #[test]
+    fn map_failure() {
+        assert_eq!(SUCCESS.map_failure(str::len), Success(10));
+        assert_eq!(FAILURE.map_failure(str::len), Failure(3));
+        assert_eq!(FORWARD.map_failure(str::len), Forward(3));
+    }
+
+    #[test]
+    fn map_forward() {
+        assert_eq!(SUCCESS.map_forward(|f| f + 1), Success(10));
+        assert_eq!(FAILURE.map_forward(|f| f + 1), Failure("bad"));
+        assert_eq!(FORWARD.map_forward(|f| f + 1), Forward(4));
+    }
+
+    #[test]
+    fn and_then() {
+        assert_eq!(SUCCESS.and_then(|v| Success(v + 1)), Success(11));
+        assert_eq!(SUCCESS.and_then(|_| Test::Failure("no")), Failure("no"));
+        assert_eq!(SUCCESS.and_then(|_| Test::Forward(9)), Forward(9));
+        assert_eq!(FAILURE.and_then(|_| -> Test { unreachable!() }), Failure("bad"));
+        assert_eq!(FORWARD.and_then(|_| -> Test { unreachable!() }), Forward(3));
+    }
+
+    #[test]
+    fn failure_then() {
+        assert_eq!(SUCCESS.failure_then(|_| -> Test { unreachable!() }), Success(10));
+        assert_eq!(FAILURE.failure_then(|e| Test::Failure(&e[1..])), Failure("ad"));
+        assert_eq!(FAILURE.failure_then(|_| Test::Success(1)), Success(1));
+        assert_eq!(FORWARD.failure_then(|_| -> Test { unreachable!() }), Forward(3));
+    }
+
+    #[test]
+    fn forward_then() {
+        assert_eq!(SUCCESS.forward_then(|_| -> Test { unreachable!() }), Success(10));
+        assert_eq!(FAILURE.forward_then(|_| -> Test { unreachable!() }), Failure("bad"));
+        assert_eq!(FORWARD.forward_then(|f| Test::Forward(f * 2)), Forward(6));
+        assert_eq!(FORWARD.forward_then(|_| Test::Failure("no")), Failure("no"));
+    }
+
+    #[test]
+    fn from_result() {
+        assert_eq!(Test::from(Ok(10)), SUCCESS);
+        assert_eq!(Test::from(Err("bad")), FAILURE);
+    }
+
+    #[test]
+    fn from_option() {
+        type Unit = Outcome<i32, &'static str, ()>;
+        assert_eq!(Unit::from(Some(10)), Success(10));
+        assert_eq!(Unit::from(None), Forward(()));
+    }
+
+    #[test]
+    fn or_else() {
+        assert_eq!(SUCCESS.or_else(|| unreachable!()), Success(10));
+        assert_eq!(FAILURE.or_else(|| Success(1)), Success(1));
+        assert_eq!(FAILURE.or_else(|| Forward(1)), Forward(1));
+        assert_eq!(FORWARD.or_else(|| Success(2)), Success(2));
+        assert_eq!(FORWARD.or_else(|| Failure("no")), Failure("no"));
+    }
+}
@@ -0,0 +1,463 @@
+//! Token-bucket rate limiting keyed by client identity.
+//!
+//! The [`RateLimiter`] fairing keeps one token bucket per client key. Each
+//! bucket holds up to `capacity` tokens and regains one token every `interval`.
+//! The [`RateLimit`] request guard takes one token from the bucket of the
+//! requesting client. When the bucket is empty, the guard fails with a status
+//! of `429 Too Many Requests`, and the fairing adds a `Retry-After` header to
+//! the response with the number of seconds until a token is available.
+//!
+//! By default, clients are keyed by their
+//! [IP address](Request::real_client_ip()), which only honors forwarding
+//! headers set by trusted proxies, so clients can't pick their own key. A
+//! custom key, like an API token, can be used instead with
+//! [`RateLimiter::key()`].
+//!
+//! A bucket that has refilled completely is indistinguishable from a new one,
+//! so the limiter forgets such buckets. At most
+//! [`max_clients`](RateLimiter::max_clients()) buckets are kept at once: when
+//! a new client arrives and there is no room, the bucket closest to being full
+//! is dropped.
+//!
+//! # Example
+//!
+//! ```rust
+//! # #[macro_use] extern crate rocket;
+//! use std::time::Duration;
+//!
+//! use rocket::rate_limit::{RateLimit, RateLimiter};
+//!
+//! #[get("/search")]
+//! fn search(_limit: RateLimit) -> &'static str {
+//!     "results"
+//! }
+//!
+//! #[launch]
+//! fn rocket() -> _ {
+//!     // Bursts of up to 10 requests, refilled at one request per 6 seconds.
+//!     let limiter = RateLimiter::new(10, Duration::from_secs(6))
+//!         .key(|req| req.headers().get_one("X-Api-Token").map(|t| t.to_string()));
+//!
+//!     rocket::build().attach(limiter).mount("/", routes![search])
+//! }
+//! ```
+
+use std::fmt;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use std::collections::HashMap;
+
+use parking_lot::Mutex;
+
+use crate::{Rocket, Build, Ignite, Sentinel, Response};
+use crate::fairing::{self, Fairing, Info, Kind};
+use crate::http::{Header, Status};
+use crate::outcome::Outcome::*;
+use crate::request::{self, Request, FromRequest};
+
+type KeyFn = dyn Fn(&Request<'_>) -> Option<String> + Send + Sync;
+
+/// The default maximum number of client buckets kept at once.
+const DEFAULT_MAX_CLIENTS: usize = 10_000;
+
+/// A fairing that manages the token buckets used by [`RateLimit`].
+///
+/// A `RateLimiter` is cheap to clone: clones share the same buckets.
+///
+/// See the [module level docs](self) for an example.
+#[derive(Clone)]
+pub struct RateLimiter {
+    capacity: u32,
+    interval: Duration,
+    max_clients: usize,
+    key: Arc<KeyFn>,
+    buckets: Arc<Mutex<Buckets>>,
+}
+
+/// The buckets of all known clients and when full buckets were last dropped.
+#[derive(Debug)]
+struct Buckets {
+    map: HashMap<String, Bucket>,
+    swept: Instant,
+}
+
+/// The state of one client's bucket.
+///
+/// `refilled` is the point at which the bucket last gained a token. It only
+/// ever advances by whole `interval`s, so partial progress toward the next
+/// token is never lost.
+#[derive(Debug)]
+struct Bucket {
+    tokens: u32,
+    refilled: Instant,
+}
+
+/// The outcome of charging a request, in its local cache. A request is only
+/// charged once, even if it's forwarded to other routes that use the guard.
+struct Charge(Result<u32, Duration>);
+
+impl RateLimiter {
+    /// Returns a limiter allowing bursts of up to `capacity` requests per
+    /// client and one additional request every `interval`, keyed by client IP.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `capacity` or `interval` is zero.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use std::time::Duration;
+    /// use rocket::rate_limit::RateLimiter;
+    ///
+    /// // 100 requests per minute, in bursts of up to 100.
+    /// let limiter = RateLimiter::new(100, Duration::from_millis(600));
+    /// ```
+    pub fn new(capacity: u32, interval: Duration) -> RateLimiter {
+        assert!(capacity > 0, "rate limit capacity must be non-zero");
+        assert!(!interval.is_zero(), "rate limit interval must be non-zero");
+
+        RateLimiter {
+            capacity,
+            interval,
+            max_clients: DEFAULT_MAX_CLIENTS,
+            key: Arc::new(|req| req.real_client_ip().map(|ip| ip.to_string())),
+            buckets: Arc::new(Mutex::new(Buckets {
+                map: HashMap::new(),
+                swept: Instant::now(),
+            })),
+        }
+    }
+
+    /// Sets the maximum number of client buckets kept at once. **(default:
+    /// `10_000`)**
+    ///
+    /// When a new client arrives and the limit is reached, the bucket closest
+    /// to being full is dropped, granting that client a full bucket early.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `max` is zero.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use std::time::Duration;
+    /// use rocket::rate_limit::RateLimiter;
+    ///
+    /// let limiter = RateLimiter::new(10, Duration::from_secs(1)).max_clients(1_000);
+    /// ```
+    pub fn max_clients(mut self, max: usize) -> Self {
+        assert!(max > 0, "rate limit client maximum must be non-zero");
+        self.max_clients = max;
+        self
+    }
+
+    /// Sets the function that extracts a client's key from a request.
+    ///
+    /// Requests for which `key` returns `None` are not limited: the
+    /// [`RateLimit`] guard forwards them.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use std::time::Duration;
+    /// use rocket::rate_limit::RateLimiter;
+    ///
+    /// let limiter = RateLimiter::new(10, Duration::from_secs(1))
+    ///     .key(|req| req.headers().get_one("X-Api-Token").map(|t| t.to_string()));
+    /// ```
+    pub fn key<F>(mut self, key: F) -> Self
+        where F: Fn(&Request<'_>) -> Option<String> + Send + Sync + 'static
+    {
+        self.key = Arc::new(key);
+        self
+    }
+
+    /// Takes a token from the bucket for `key` at `now`. Returns the number of
+    /// remaining tokens or, if the bucket is empty, how long until it isn't.
+    fn acquire(&self, key: String, now: Instant) -> Result<u32, Duration> {
+        let mut guard = self.buckets.lock();
+        let buckets = &mut *guard;
+
+        // An empty bucket refills completely in `capacity * interval`, so
+        // sweeping that often bounds how long full buckets are kept.
+        let period = self.interval.checked_mul(self.capacity).unwrap_or(Duration::MAX);
+        if now.saturating_duration_since(buckets.swept) >= period {
+            self.sweep(buckets, now);
+        }
+
+        if buckets.map.len() >= self.max_clients && !buckets.map.contains_key(&key) {
+            self.sweep(buckets, now);
+            if buckets.map.len() >= self.max_clients {
+                let soonest_full = buckets.map.iter()
+                    .min_by_key(|(_, bucket)| {
+                        let full_at = self.full_at(bucket);
+                        (full_at.is_none(), full_at)
+                    })
+                    .map(|(key, _)| key.clone());
+
+                if let Some(evicted) = soonest_full {
+                    buckets.map.remove(&evicted);
+                }
+            }
+        }
+
+        let bucket = buckets.map.entry(key).or_insert(Bucket {
+            tokens: self.capacity,
+            refilled: now,
+        });
+
+        let elapsed = now.saturating_duration_since(bucket.refilled);
+        let refills = elapsed.as_nanos() / self.interval.as_nanos();
+        if refills > 0 {
+            let missing = self.capacity - bucket.tokens;
+            if refills >= u128::from(missing) {
+                bucket.tokens = self.capacity;
+                bucket.refilled = now;
+            } else {
+                bucket.tokens += refills as u32;
+                bucket.refilled += self.interval * refills as u32;
+            }
+        }
+
+        if bucket.tokens == self.capacity {
+            bucket.refilled = now;
+        }
+
+        match bucket.tokens {
+            0 => Err(self.interval - now.saturating_duration_since(bucket.refilled)),
+            _ => {
+                bucket.tokens -= 1;
+                Ok(bucket.tokens)
+            }
+        }
+    }
+
+    /// Returns when `bucket` will have refilled completely or `None` if that
+    /// point isn't representable.
+    fn full_at(&self, bucket: &Bucket) -> Option<Instant> {
+        let missing = self.interval.checked_mul(self.capacity - bucket.tokens)?;
+        bucket.refilled.checked_add(missing)
+    }
+
+    /// Drops the buckets that are full at `now`.
+    fn sweep(&self, buckets: &mut Buckets, now: Instant) {
+        buckets.map.retain(|_, bucket| self.full_at(bucket).map_or(true, |at| at > now));
+        buckets.swept = now;
+    }
+}
+
+#[crate::async_trait]
+impl Fairing for RateLimiter {
+    fn info(&self) -> Info {
+        Info {
+            name: "Rate Limiter",
+            kind: Kind::Ignite | Kind::Response | Kind::Singleton,
+        }
+    }
+
+    async fn on_ignite(&self, rocket: Rocket<Build>) -> fairing::Result {
+        Ok(rocket.manage(self.clone()))
+    }
+
+    async fn on_response<'r>(&self, req: &'r Request<'_>, res: &mut Response<'r>) {
+        if res.status() != Status::TooManyRequests {
+            return;
+        }
+
+        if let Some(Charge(Err(delay))) = req.local_cache_get::<Charge>() {
+            let secs = delay.as_secs() + u64::from(delay.subsec_nanos() > 0);
+            res.set_header(Header::new("Retry-After", secs.to_string()));
+        }
+    }
+}
+
+impl fmt::Debug for RateLimiter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RateLimiter")
+            .field("capacity", &self.capacity)
+            .field("interval", &self.interval)
+            .field("max_clients", &self.max_clients)
+            .finish_non_exhaustive()
+    }
+}
+
+/// A request guard that takes one token from the requesting client's bucket.
+///
+/// The guard succeeds if the bucket has a token, fails with `429 Too Many
+/// Requests` and [`RateLimitError::Exhausted`] if it doesn't, and forwards if
+/// the request has no key. A request is charged at most once: if it's
+/// forwarded to another route using the guard, the guard there sees the same
+/// outcome. Without a managed [`RateLimiter`], it fails with
+/// `500 Internal Server Error`; as a [`Sentinel`], it aborts launch instead.
+///
+/// See the [module level docs](self) for an example.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RateLimit {
+    remaining: u32,
+}
+
+impl RateLimit {
+    /// Returns the number of tokens left in the client's bucket after this
+    /// request.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # #[macro_use] extern crate rocket;
+    /// use rocket::rate_limit::RateLimit;
+    ///
+    /// #[get("/")]
+    /// fn index(limit: RateLimit) -> String {
+    ///     format!("{} requests left", limit.remaining())
+    /// }
+    /// ```
+    #[inline(always)]
+    pub fn remaining(&self) -> u32 {
+        self.remaining
+    }
+}
+
+/// Error returned by the [`RateLimit`] request guard.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RateLimitError {
+    /// The client's bucket is empty. A token will be available after the
+    /// contained delay.
+    Exhausted(Duration),
+    /// There is no managed [`RateLimiter`].
+    MissingLimiter,
+}
+
+impl fmt::Display for RateLimitError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RateLimitError::Exhausted(delay) => {
+                write!(f, "rate limit exceeded; retry after {:?}", delay)
+            }
+            RateLimitError::MissingLimiter => write!(f, "no managed rate limiter"),
+        }
+    }
+}
+
+impl std::error::Error for RateLimitError { }
+
+#[crate::async_trait]
+impl<'r> FromRequest<'r> for RateLimit {
+    type Error = RateLimitError;
+
+    async fn from_request(req: &'r Request<'_>) -> request::Outcome<Self, Self::Error> {
+        let limiter = match req.rocket().state::<RateLimiter>() {
+            Some(limiter) => limiter,
+            None => {
+                error_!("Attempted to rate limit without an attached `RateLimiter`!");
+                return Failure((Status::InternalServerError, RateLimitError::MissingLimiter));
+            }
+        };
+
+        let key = match (limiter.key)(req) {
+            Some(key) => key,
+            None => return Forward(()),
+        };
+
+        match req.local_cache(|| Charge(limiter.acquire(key, Instant::now()))) {
+            Charge(Ok(remaining)) => Success(RateLimit { remaining: *remaining }),
+            Charge(Err(delay)) => {
+                Failure((Status::TooManyRequests, RateLimitError::Exhausted(*delay)))
+            }
+        }
+    }
+}
+
+impl Sentinel for RateLimit {
+    fn abort(rocket: &Rocket<Ignite>) -> bool {
+        if rocket.state::<RateLimiter>().is_none() {
+            error!("launching without an attached `RateLimiter`.");
+            info_!("Using `RateLimit` requires attaching a `RateLimiter`.");
+            return true;
+        }
+
+        false
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn refill_does_not_drift() {
+        let limiter = RateLimiter::new(2, Duration::from_millis(100));
+        let start = Instant::now();
+        let at = |ms| start + Duration::from_millis(ms);
+
+        assert_eq!(limiter.acquire("a".into(), at(0)), Ok(1));
+        assert_eq!(limiter.acquire("a".into(), at(0)), Ok(0));
+        assert_eq!(limiter.acquire("a".into(), at(30)), Err(Duration::from_millis(70)));
+
+        // A token at 100ms; the 50ms of progress toward the next one is kept.
+        assert_eq!(limiter.acquire("a".into(), at(150)), Ok(0));
+        assert_eq!(limiter.acquire("a".into(), at(160)), Err(Duration::from_millis(40)));
+        assert_eq!(limiter.acquire("a".into(), at(200)), Ok(0));
+
+        // Refills never exceed the capacity.
+        assert_eq!(limiter.acquire("a".into(), at(10_000)), Ok(1));
+        assert_eq!(limiter.acquire("a".into(), at(10_000)), Ok(0));
+        assert!(limiter.acquire("a".into(), at(10_000)).is_err());
+
+        // Buckets are per key.
+        assert_eq!(limiter.acquire("b".into(), at(10_000)), Ok(1));
+    }
+
+    #[test]
+    fn time_going_backwards_is_harmless() {
+        let limiter = RateLimiter::new(1, Duration::from_secs(1));
+        let now = Instant::now() + Duration::from_secs(5);
+
+        assert_eq!(limiter.acquire("a".into(), now), Ok(0));
+        let delay = limiter.acquire("a".into(), now - Duration::from_secs(2)).unwrap_err();
+        assert_eq!(delay, Duration::from_secs(1));
+    }
+
+    #[test]
+    fn full_buckets_are_dropped() {
+        let limiter = RateLimiter::new(2, Duration::from_millis(100));
+        let start = limiter.buckets.lock().swept;
+        let at = |ms| start + Duration::from_millis(ms);
+        let clients = || limiter.buckets.lock().map.len();
+
+        assert_eq!(limiter.acquire("a".into(), at(0)), Ok(1));
+        assert_eq!(limiter.acquire("b".into(), at(100)), Ok(1));
+        assert_eq!(limiter.acquire("b".into(), at(100)), Ok(0));
+        assert_eq!(clients(), 2);
+
+        // At 200ms, `a` is full and swept; `b` and `c` aren't and are kept.
+        assert_eq!(limiter.acquire("c".into(), at(150)), Ok(1));
+        assert_eq!(clients(), 3);
+        assert_eq!(limiter.acquire("c".into(), at(200)), Ok(0));
+        assert_eq!(clients(), 2);
+        assert!(!limiter.buckets.lock().map.contains_key("a"));
+
+        // Dropping a full bucket doesn't change the outcome for its client.
+        assert_eq!(limiter.acquire("a".into(), at(200)), Ok(1));
+    }
+
+    #[test]
+    fn client_count_is_capped() {
+        let limiter = RateLimiter::new(2, Duration::from_secs(1)).max_clients(2);
+        let now = Instant::now();
+
+        assert_eq!(limiter.acquire("a".into(), now), Ok(1));
+        assert_eq!(limiter.acquire("b".into(), now), Ok(1));
+        assert_eq!(limiter.acquire("b".into(), now), Ok(0));
+
+        // `a` is closest to full, so it makes room for `c`.
+        assert_eq!(limiter.acquire("c".into(), now), Ok(1));
+        let buckets = limiter.buckets.lock();
+        assert_eq!(buckets.map.len(), 2);
+        assert!(buckets.map.contains_key("b") && buckets.map.contains_key("c"));
+        drop(buckets);
+
+        assert!(limiter.acquire("b".into(), now).is_err());
+    }
+}
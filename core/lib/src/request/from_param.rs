@@ -1,6 +1,9 @@
+use std::fmt;
+use std::borrow::Cow;
 use std::str::FromStr;
 use std::path::PathBuf;
 
+use crate::http::RawStr;
 use crate::http::uri::{Segments, error::PathError, fmt::Path};
 
 /// Trait to convert a dynamic path segment string to a concrete value.
@@ -72,20 +75,109 @@ use crate::http::uri::{Segments, error::PathError, fmt::Path};
 ///         usize, u8, u16, u32, u64, u128, bool**
 ///       * `IpAddr` and `SocketAddr` types: **IpAddr, Ipv4Addr, Ipv6Addr,
 ///         SocketAddrV4, SocketAddrV6, SocketAddr**
-///       * `NonZero*` types: **NonZeroI8, NonZeroI16, NonZeroI32, NonZeroI64,
-///         NonZeroI128, NonZeroIsize, NonZeroU8, NonZeroU16, NonZeroU32,
-///         NonZeroU64, NonZeroU128, NonZeroUsize**
 ///
 ///     A value is parsed successfully if the `from_str` method from the given
 ///     type returns successfully. Otherwise, the raw path segment is returned
 ///     in the `Err` value.
 ///
+///   * **NonZeroI8, NonZeroI16, NonZeroI32, NonZeroI64, NonZeroI128,
+///     NonZeroIsize, NonZeroU8, NonZeroU16, NonZeroU32, NonZeroU64,
+///     NonZeroU128, NonZeroUsize**
+///
+///     A value is parsed successfully if it parses as a nonzero integer of the
+///     given width. Otherwise, a [`NonZeroParamError`] is returned in the `Err`
+///     value, indicating whether the value was zero, out of range, or not an
+///     integer at all.
+///
+///   * **Bounded&lt;T, MIN, MAX>, Radix&lt;T, R>** _where_ **T** _is an integer_
+///
+///     A value is parsed successfully if it parses as a `T` in decimal and is
+///     within `MIN..=MAX` for [`Bounded`], or if it parses as a `T` in radix
+///     `R` for [`Radix`]. Otherwise, an [`IntParamError`] is returned in the
+///     `Err` value.
+///
+///   * **CommaSep&lt;T>, CommaSepLoose&lt;T>** _where_ **T: FromParam**
+///
+///     The path segment is split on `,` and each element is parsed by `T`'s
+///     `FromParam` implementation. If any element fails to parse, or, for
+///     [`CommaSep`], if any element is empty, a [`CommaSepError`] is returned
+///     in the `Err` value. [`CommaSepLoose`] skips empty elements instead.
+///
+///   * **semver::Version, semver::VersionReq** _with the `semver` feature_
+///
+///     A value is parsed successfully if the `parse` method of the given type
+///     returns successfully. Otherwise, the `semver::Error` describing the
+///     failure is returned in the `Err` value. A version may carry pre-release
+///     and build metadata, as in `1.2.3-rc.1+build.5`.
+///
+///   * **rust_decimal::Decimal** _with the `rust_decimal` feature_
+///
+///     A value is parsed successfully if it is a plain decimal number, with an
+///     optional leading `-`, that can be represented exactly. Exponents, such
+///     as `1e3`, are rejected. Otherwise, a [`DecimalParamError`] is returned
+///     in the `Err` value. To accept only non-negative amounts with at most two
+///     decimal places, use [`Money`].
+///
+///   * **SafeSegment**
+///
+///     The path segment is percent-decoded. If the decoded segment contains a
+///     path-significant character, such as `/` or NUL, or is `.` or `..`, a
+///     [`SafeSegmentError`] is returned in the `Err` value.
+///
 ///   * **&str, String**
 ///
 ///     _This implementation always returns successfully._
 ///
 ///     Returns the percent-decoded path segment with invalid UTF-8 byte
-///     sequences replaced by � U+FFFD.
+///     sequences replaced by � U+FFFD. A malformed percent-escape, such as
+///     `%2G`, is kept as is. To reject segments containing one, use
+///     [`Strict`].
+///
+///   * **Cow&lt;str>**
+///
+///     Returns the path segment, borrowed from the request if it contains no
+///     percent-escapes, so that only a segment that needs decoding allocates.
+///     Unlike `&str` and `String`, a segment that doesn't decode to valid
+///     UTF-8 isn't repaired: a [`Utf8Error`](std::str::Utf8Error) is returned
+///     in the `Err` value instead.
+///
+///   * **RawBytes**
+///
+///     _This implementation always returns successfully._
+///
+///     Returns the bytes of the percent-decoded path segment, which needn't be
+///     valid UTF-8. See [`RawBytes`].
+///
+///   * **Trimmed&lt;T>** _where_ **T: FromParam**
+///
+///     The percent-decoded path segment, with leading and trailing ASCII
+///     whitespace removed, is parsed by `T`. If parsing fails, `T`'s error is
+///     returned in the `Err` value. See [`Trimmed`].
+///
+///   * **WithRaw&lt;T>** _where_ **T: FromParam**
+///
+///     The path segment is parsed by `T`. The parsed value is returned along
+///     with the raw, undecoded segment. If parsing fails, `T`'s error is
+///     returned in the `Err` value along with the raw segment. See
+///     [`WithRaw`].
+///
+///   * **Base64&lt;C>**
+///
+///     The percent-decoded path segment is decoded as base64 with the
+///     alphabet and padding policy of `C`. If decoding fails, a
+///     [`Base64Error`] is returned in the `Err` value. See [`Base64`].
+///
+///   * **HumanDuration**
+///
+///     The percent-decoded path segment is parsed as a duration in human
+///     form, such as `1h30m`. If parsing fails, a [`DurationError`] is
+///     returned in the `Err` value. See [`HumanDuration`].
+///
+///   * **Glob**
+///
+///     The percent-decoded path segment is compiled as a glob pattern. If
+///     compiling fails, a [`GlobError`] is returned in the `Err` value. See
+///     [`Glob`].
 ///
 ///   * **Option&lt;T>** _where_ **T: FromParam**
 ///
@@ -102,6 +194,12 @@ use crate::http::uri::{Segments, error::PathError, fmt::Path};
 ///     The path segment is parsed by `T`'s `FromParam` implementation. The
 ///     returned `Result` value is returned.
 ///
+/// # Deriving
+///
+/// `FromParam` can be derived for enums with nullary variants, matching the
+/// segment case-insensitively against each variant's name. See the
+/// [`FromParam` derive](macro@crate::FromParam) for details.
+///
 /// # Example
 ///
 /// Say you want to parse a segment of the form:
@@ -174,6 +272,25 @@ use crate::http::uri::{Segments, error::PathError, fmt::Path};
 /// }
 /// # fn main() {  }
 /// ```
+///
+/// [`Bounded`]: crate::request::Bounded
+/// [`Radix`]: crate::request::Radix
+/// [`IntParamError`]: crate::request::IntParamError
+/// [`CommaSep`]: crate::request::CommaSep
+/// [`CommaSepLoose`]: crate::request::CommaSepLoose
+/// [`CommaSepError`]: crate::request::CommaSepError
+/// [`SafeSegmentError`]: crate::request::SafeSegmentError
+/// [`RawBytes`]: crate::request::RawBytes
+/// [`Trimmed`]: crate::request::Trimmed
+/// [`WithRaw`]: crate::request::WithRaw
+/// [`Base64`]: crate::request::Base64
+/// [`Base64Error`]: crate::request::Base64Error
+/// [`HumanDuration`]: crate::request::HumanDuration
+/// [`DurationError`]: crate::request::DurationError
+/// [`Glob`]: crate::request::Glob
+/// [`GlobError`]: crate::request::GlobError
+/// [`Money`]: crate::request::Money
+/// [`DecimalParamError`]: crate::request::DecimalParamError
 pub trait FromParam<'a>: Sized {
     /// The associated error to be returned if parsing/validation fails.
     type Error: std::fmt::Debug;
@@ -181,6 +298,20 @@ pub trait FromParam<'a>: Sized {
     /// Parses and validates an instance of `Self` from a path parameter string
     /// or returns an `Error` if parsing or validation fails.
     fn from_param(param: &'a str) -> Result<Self, Self::Error>;
+
+    /// Parses and validates an instance of `Self` from a path parameter given
+    /// both as the `raw`, undecoded segment and as the percent-decoded `param`.
+    ///
+    /// This is the method Rocket's code generation calls. The default
+    /// implementation ignores `raw` and calls [`FromParam::from_param()`] with
+    /// `param`. Implementations that need the exact bytes sent by the client,
+    /// like [`RawBytes`](crate::request::RawBytes), override it; most
+    /// implementations should not.
+    #[inline(always)]
+    fn from_raw_param(raw: &'a RawStr, param: &'a str) -> Result<Self, Self::Error> {
+        let _ = raw;
+        Self::from_param(param)
+    }
 }
 
 impl<'a> FromParam<'a> for &'a str {
@@ -202,6 +333,33 @@ impl<'a> FromParam<'a> for String {
     }
 }
 
+impl<'a> FromParam<'a> for Cow<'a, str> {
+    type Error = std::str::Utf8Error;
+
+    /// Borrows the already-decoded `param`.
+    #[inline(always)]
+    fn from_param(param: &'a str) -> Result<Self, Self::Error> {
+        Ok(Cow::Borrowed(param))
+    }
+
+    /// Borrows `raw` if it has no percent-escapes. Otherwise, returns an owned
+    /// copy of the decoded segment, failing if it isn't valid UTF-8.
+    #[inline]
+    fn from_raw_param(raw: &'a RawStr, param: &'a str) -> Result<Self, Self::Error> {
+        if memchr::memchr(b'%', raw.as_bytes()).is_none() {
+            return Ok(Cow::Borrowed(raw.as_str()));
+        }
+
+        // `param` was decoded lossily, so it's exact unless it contains
+        // replacement characters. Only then is `raw` decoded again to check.
+        if !param.contains(char::REPLACEMENT_CHARACTER) {
+            return Ok(Cow::Owned(param.into()));
+        }
+
+        raw.percent_decode()
+    }
+}
+
 macro_rules! impl_with_fromstr {
     ($($T:ty),+) => ($(
         impl<'a> FromParam<'a> for $T {
@@ -223,11 +381,92 @@ use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddrV4, SocketAddrV6, SocketAdd
 
 impl_with_fromstr! {
     i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize, f32, f64,
-    NonZeroI8, NonZeroI16, NonZeroI32, NonZeroI64, NonZeroI128, NonZeroIsize,
-    NonZeroU8, NonZeroU16, NonZeroU32, NonZeroU64, NonZeroU128, NonZeroUsize,
     bool, IpAddr, Ipv4Addr, Ipv6Addr, SocketAddrV4, SocketAddrV6, SocketAddr
 }
 
+/// Error returned by the `FromParam` implementations for the `NonZero*`
+/// integer types.
+///
+/// # Example
+///
+/// ```rust
+/// use std::num::NonZeroU8;
+/// use rocket::request::{FromParam, NonZeroParamError};
+///
+/// assert_eq!(NonZeroU8::from_param("7").unwrap().get(), 7);
+/// assert_eq!(NonZeroU8::from_param("0"), Err(NonZeroParamError::Zero("0")));
+/// assert_eq!(NonZeroU8::from_param("256"), Err(NonZeroParamError::Overflow("256")));
+/// assert!(matches!(NonZeroU8::from_param("one"), Err(NonZeroParamError::NotANumber(..))));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum NonZeroParamError<'a> {
+    /// The path segment is an integer equal to zero.
+    Zero(&'a str),
+    /// The path segment is an integer that doesn't fit in the target type.
+    Overflow(&'a str),
+    /// The path segment isn't an integer. Contains the segment and the parse
+    /// error.
+    NotANumber(&'a str, std::num::ParseIntError),
+}
+
+impl fmt::Display for NonZeroParamError<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            NonZeroParamError::Zero(s) => write!(f, "integer `{}` is zero", s),
+            NonZeroParamError::Overflow(s) => write!(f, "integer `{}` is out of range", s),
+            NonZeroParamError::NotANumber(s, e) => write!(f, "invalid integer `{}`: {}", s, e),
+        }
+    }
+}
+
+impl std::error::Error for NonZeroParamError<'_> { }
+
+macro_rules! impl_nonzero {
+    ($($T:ty),+) => ($(
+        impl<'a> FromParam<'a> for $T {
+            type Error = NonZeroParamError<'a>;
+
+            #[inline]
+            fn from_param(param: &'a str) -> Result<Self, Self::Error> {
+                use std::num::IntErrorKind::*;
+
+                param.parse::<$T>().map_err(|e| match e.kind() {
+                    Zero => NonZeroParamError::Zero(param),
+                    PosOverflow | NegOverflow => NonZeroParamError::Overflow(param),
+                    _ => NonZeroParamError::NotANumber(param, e),
+                })
+            }
+        }
+    )+)
+}
+
+impl_nonzero! {
+    NonZeroI8, NonZeroI16, NonZeroI32, NonZeroI64, NonZeroI128, NonZeroIsize,
+    NonZeroU8, NonZeroU16, NonZeroU32, NonZeroU64, NonZeroU128, NonZeroUsize
+}
+
+#[cfg(feature = "semver")]
+#[cfg_attr(nightly, doc(cfg(feature = "semver")))]
+impl<'a> FromParam<'a> for semver_::Version {
+    type Error = semver_::Error;
+
+    #[inline]
+    fn from_param(param: &'a str) -> Result<Self, Self::Error> {
+        semver_::Version::parse(param)
+    }
+}
+
+#[cfg(feature = "semver")]
+#[cfg_attr(nightly, doc(cfg(feature = "semver")))]
+impl<'a> FromParam<'a> for semver_::VersionReq {
+    type Error = semver_::Error;
+
+    #[inline]
+    fn from_param(param: &'a str) -> Result<Self, Self::Error> {
+        semver_::VersionReq::parse(param)
+    }
+}
+
 impl<'a> FromParam<'a> for PathBuf {
     type Error = PathError;
 
@@ -240,6 +479,300 @@ impl<'a> FromParam<'a> for PathBuf {
     }
 }
 
+/// Error returned by the [`FromParam`](macro@crate::FromParam) derive when a
+/// path segment matches none of an enum's variants.
+///
+/// The error records the segment that failed to match as well as the values
+/// that would have been accepted.
+///
+/// # Example
+///
+/// ```rust
+/// # #[macro_use] extern crate rocket;
+/// use rocket::request::{FromParam, InvalidOption};
+///
+/// #[derive(Debug, PartialEq, FromParam)]
+/// enum SortOrder {
+///     Asc,
+///     Desc,
+/// }
+///
+/// assert_eq!(SortOrder::from_param("ASC"), Ok(SortOrder::Asc));
+///
+/// let error = SortOrder::from_param("up").unwrap_err();
+/// assert_eq!(error.value, "up");
+/// assert_eq!(error.options, &["Asc", "Desc"]);
+/// assert_eq!(error.to_string(), "unexpected value `up`, expected one of: Asc, Desc");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct InvalidOption<'a> {
+    /// The path segment that failed to match.
+    pub value: &'a str,
+    /// The values accepted by the parameter, in declaration order.
+    pub options: &'static [&'static str],
+}
+
+impl<'a> InvalidOption<'a> {
+    #[doc(hidden)]
+    pub fn new(value: &'a str, options: &'static [&'static str]) -> Self {
+        InvalidOption { value, options }
+    }
+}
+
+impl fmt::Display for InvalidOption<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "unexpected value `{}`, expected one of: ", self.value)?;
+        for (i, option) in self.options.iter().enumerate() {
+            if i != 0 { f.write_str(", ")?; }
+            f.write_str(option)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl std::error::Error for InvalidOption<'_> { }
+
+/// Error returned by the [`FromParam`](macro@crate::FromParam) derive for a
+/// `#[param(numeric)]` enum.
+///
+/// A numeric enum is matched by its integer discriminant rather than by its
+/// variant names. The segment must first parse as the enum's `#[repr]` type
+/// and must then equal the discriminant of one of the variants.
+///
+/// # Example
+///
+/// ```rust
+/// # #[macro_use] extern crate rocket;
+/// use rocket::request::{FromParam, DiscriminantError};
+///
+/// #[derive(Debug, PartialEq, FromParam)]
+/// #[param(numeric)]
+/// #[repr(u16)]
+/// enum Code {
+///     Ok = 200,
+///     NotFound = 404,
+/// }
+///
+/// assert_eq!(Code::from_param("404"), Ok(Code::NotFound));
+/// assert_eq!(Code::from_param("500"), Err(DiscriminantError::Unknown("500")));
+/// assert!(matches!(Code::from_param("ok"), Err(DiscriminantError::Parse("ok", _))));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DiscriminantError<'a> {
+    /// The path segment could not be parsed as the enum's representation.
+    /// Contains the segment and the parse error.
+    Parse(&'a str, std::num::ParseIntError),
+    /// The path segment parsed as an integer, but no variant has that
+    /// discriminant. Contains the segment.
+    Unknown(&'a str),
+}
+
+impl fmt::Display for DiscriminantError<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DiscriminantError::Parse(s, e) => write!(f, "invalid integer `{}`: {}", s, e),
+            DiscriminantError::Unknown(s) => write!(f, "no variant has discriminant `{}`", s),
+        }
+    }
+}
+
+impl std::error::Error for DiscriminantError<'_> { }
+
+/// Error returned when a path segment contains a malformed percent-escape.
+///
+/// A percent-escape is a `%` followed by two hexadecimal digits. Parameters
+/// are decoded leniently by default: a malformed escape is kept as is. The
+/// [`Strict`] and [`RawBytes`](crate::request::RawBytes) parameters instead
+/// check the raw, _undecoded_ segment for malformed escapes and fail with
+/// this error if one is found.
+///
+/// The [`offset()`](PercentDecodeError::offset()) is the byte index of the
+/// offending `%` in the original, encoded segment, not in the decoded output.
+/// The [`bytes()`](PercentDecodeError::bytes()) are the `%` and the (up to
+/// two) characters following it.
+///
+/// # Example
+///
+/// ```rust
+/// use rocket::request::PercentDecodeError;
+///
+/// assert!(PercentDecodeError::check("a%20b").is_ok());
+///
+/// let error = PercentDecodeError::check("a%20b%2Gc").unwrap_err();
+/// assert_eq!(error.offset(), 5);
+/// assert_eq!(error.bytes(), "%2G");
+/// assert_eq!(error.to_string(), "invalid percent-encoding at byte 5: '%2G'");
+/// ```
+///
+/// A catcher for requests forwarded by a [`Strict`] parameter can log the
+/// error by re-checking the request's raw segments:
+///
+/// ```rust
+/// # #[macro_use] extern crate rocket;
+/// use rocket::Request;
+/// use rocket::request::PercentDecodeError;
+///
+/// #[catch(404)]
+/// fn not_found(req: &Request<'_>) -> String {
+///     let bad_segment = req.uri().path().raw_segments()
+///         .find_map(|s| PercentDecodeError::check(s.as_str()).err());
+///
+///     match bad_segment {
+///         Some(e) => format!("bad request path `{}`: {}", e.segment(), e),
+///         None => format!("no route for `{}`", req.uri()),
+///     }
+/// }
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PercentDecodeError<'a> {
+    segment: &'a str,
+    offset: usize,
+}
+
+impl<'a> PercentDecodeError<'a> {
+    /// Checks that every `%` in the raw, undecoded `segment` begins a
+    /// well-formed percent-escape. Returns an error for the first malformed
+    /// escape otherwise.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::request::PercentDecodeError;
+    ///
+    /// assert!(PercentDecodeError::check("100%25").is_ok());
+    /// assert_eq!(PercentDecodeError::check("100%").unwrap_err().bytes(), "%");
+    /// assert_eq!(PercentDecodeError::check("%a").unwrap_err().bytes(), "%a");
+    /// ```
+    pub fn check(segment: &'a str) -> Result<(), Self> {
+        let bytes = segment.as_bytes();
+        for offset in memchr::memchr_iter(b'%', bytes) {
+            let escape = bytes.get((offset + 1)..(offset + 3));
+            if !escape.map_or(false, |e| e.iter().all(u8::is_ascii_hexdigit)) {
+                return Err(PercentDecodeError { segment, offset });
+            }
+        }
+
+        Ok(())
+    }
+
+    /// The raw, undecoded segment containing the malformed escape.
+    pub fn segment(&self) -> &'a str {
+        self.segment
+    }
+
+    /// The byte offset of the malformed escape's `%` in
+    /// [`segment()`](PercentDecodeError::segment()).
+    pub fn offset(&self) -> usize {
+        self.offset
+    }
+
+    /// The malformed escape: the `%` and up to two characters following it.
+    pub fn bytes(&self) -> &'a str {
+        let rest = &self.segment[self.offset..];
+        let end = rest.char_indices().nth(3).map_or(rest.len(), |(i, _)| i);
+        &rest[..end]
+    }
+}
+
+impl fmt::Display for PercentDecodeError<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "invalid percent-encoding at byte {}: '{}'", self.offset, self.bytes())
+    }
+}
+
+impl std::error::Error for PercentDecodeError<'_> { }
+
+/// A `T` parsed from a path segment without malformed percent-escapes.
+///
+/// The raw, undecoded segment is first checked with
+/// [`PercentDecodeError::check()`]. If it contains a malformed escape, such as
+/// `%2G`, the guard fails with [`StrictError::Escape`]. Otherwise, the segment
+/// is parsed with `T`'s [`FromParam`] implementation, and if that fails, the
+/// guard fails with [`StrictError::Param`]. `Strict` dereferences to the inner
+/// `T`, which can also be retrieved with [`Strict::into_inner()`].
+///
+/// # Example
+///
+/// ```rust
+/// # #[macro_use] extern crate rocket;
+/// use rocket::request::{Strict, StrictError};
+///
+/// #[get("/name/<name>")]
+/// fn name(name: Result<Strict<String>, StrictError<'_, std::convert::Infallible>>) -> String {
+///     match name {
+///         Ok(name) => name.into_inner(),
+///         Err(e) => e.to_string(),
+///     }
+/// }
+///
+/// # use rocket::local::blocking::Client;
+/// # let client = Client::debug_with(routes![name]).unwrap();
+/// # let get = |uri| client.get(uri).dispatch().into_string().unwrap();
+/// # assert_eq!(get("/name/caf%C3%A9"), "café");
+/// # assert_eq!(get("/name/a%2Gb"), "invalid percent-encoding at byte 1: '%2G'");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Strict<T>(T);
+
+/// Error returned by the [`Strict`] parameter.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StrictError<'a, E> {
+    /// The raw segment contains a malformed percent-escape.
+    Escape(PercentDecodeError<'a>),
+    /// The inner `T` failed to parse.
+    Param(E),
+}
+
+impl<T> Strict<T> {
+    /// Consumes `self` and returns the inner value.
+    #[inline(always)]
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+impl<'a, T: FromParam<'a>> FromParam<'a> for Strict<T> {
+    type Error = StrictError<'a, T::Error>;
+
+    #[inline]
+    fn from_param(param: &'a str) -> Result<Self, Self::Error> {
+        Self::from_raw_param(param.into(), param)
+    }
+
+    #[inline]
+    fn from_raw_param(raw: &'a RawStr, param: &'a str) -> Result<Self, Self::Error> {
+        PercentDecodeError::check(raw.as_str()).map_err(StrictError::Escape)?;
+        T::from_raw_param(raw, param).map(Strict).map_err(StrictError::Param)
+    }
+}
+
+impl<T> std::ops::Deref for Strict<T> {
+    type Target = T;
+
+    #[inline(always)]
+    fn deref(&self) -> &T {
+        &self.0
+    }
+}
+
+impl<T: fmt::Display> fmt::Display for Strict<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl<E: fmt::Display> fmt::Display for StrictError<'_, E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            StrictError::Escape(e) => e.fmt(f),
+            StrictError::Param(e) => e.fmt(f),
+        }
+    }
+}
+
+impl<E: std::error::Error> std::error::Error for StrictError<'_, E> { }
+
 impl<'a, T: FromParam<'a>> FromParam<'a> for Result<T, T::Error> {
     type Error = std::convert::Infallible;
 
@@ -250,6 +783,14 @@ impl<'a, T: FromParam<'a>> FromParam<'a> for Result<T, T::Error> {
             Err(e) => Ok(Err(e)),
         }
     }
+
+    #[inline]
+    fn from_raw_param(raw: &'a RawStr, param: &'a str) -> Result<Self, Self::Error> {
+        match T::from_raw_param(raw, param) {
+            Ok(val) => Ok(Ok(val)),
+            Err(e) => Ok(Err(e)),
+        }
+    }
 }
 
 impl<'a, T: FromParam<'a>> FromParam<'a> for Option<T> {
@@ -262,6 +803,14 @@ impl<'a, T: FromParam<'a>> FromParam<'a> for Option<T> {
             Err(_) => Ok(None)
         }
     }
+
+    #[inline]
+    fn from_raw_param(raw: &'a RawStr, param: &'a str) -> Result<Self, Self::Error> {
+        match T::from_raw_param(raw, param) {
+            Ok(val) => Ok(Some(val)),
+            Err(_) => Ok(None)
+        }
+    }
 }
 
 /// Trait to convert _many_ dynamic path segment strings to a concrete value.
@@ -282,6 +831,32 @@ impl<'a, T: FromParam<'a>> FromParam<'a> for Option<T> {
 /// any other segments that begin with "*" or "." are ignored.  If a
 /// percent-decoded segment results in invalid UTF8, an `Err` is returned with
 /// the `Utf8Error`.
+///
+/// **[`LimitedSegments`]**
+///
+/// The `LimitedSegments<N>` implementation returns an `Err` if there are more
+/// than `N` segments or if any segment is `..`. Otherwise, it behaves like the
+/// `PathBuf` implementation.
+///
+/// **[`RawSegments`]**
+///
+/// The `RawSegments` implementation collects the segments exactly as they
+/// appear in the request URI, without percent-decoding them. A segment that
+/// decodes to a string containing a NUL byte results in an `Err`. No other
+/// checks are performed; see [`RawSegments`] for details.
+///
+/// **`Vec<String>`**
+///
+/// _This implementation always returns successfully._
+///
+/// The `Vec<String>` implementation collects each percent-decoded segment
+/// verbatim. **Unlike `PathBuf`, it has no file system semantics:** a `..`
+/// segment is kept as the literal string `".."` rather than removing the
+/// previous segment, no segment is rejected, and empty segments between two
+/// non-empty segments, as in `a//b`, are kept as empty strings. Leading and
+/// trailing slashes do not produce empty segments. Use it when the handler
+/// applies its own interpretation to the path; _never_ use it to construct a
+/// file system path.
 pub trait FromSegments<'r>: Sized {
     /// The associated error to be returned when parsing fails.
     type Error: std::fmt::Debug;
@@ -324,6 +899,247 @@ impl FromSegments<'_> for PathBuf {
     }
 }
 
+/// A `PathBuf` matched by a `<param..>` route parameter with at most `N`
+/// segments.
+///
+/// A request whose `<param..>` matches more than `N` segments is forwarded
+/// before any segment is inspected, bounding the work done on behalf of a
+/// client that sends a path with thousands of segments. The limit counts
+/// segments, not bytes; empty segments, as in `a//b`, are not counted.
+///
+/// Otherwise, the segments are converted exactly as the `PathBuf`
+/// implementation of [`FromSegments`] does, with the same security checks,
+/// save for one difference: where `PathBuf` treats a `..` segment as a request
+/// to drop the previous segment, `LimitedSegments` rejects it outright with
+/// [`SegmentsError::Traversal`]. This is true regardless of the number of
+/// segments. `LimitedSegments` dereferences to the inner `PathBuf`, which can
+/// also be retrieved with [`LimitedSegments::into_inner()`].
+///
+/// # Example
+///
+/// ```rust
+/// # #[macro_use] extern crate rocket;
+/// use rocket::request::LimitedSegments;
+///
+/// #[get("/files/<path..>")]
+/// fn files(path: LimitedSegments<8>) -> String {
+///     format!("serving {}", path.display())
+/// }
+/// ```
+///
+/// Inspect the error by requesting a `Result`:
+///
+/// ```rust
+/// # use rocket::http::uri::Origin;
+/// use rocket::request::{FromSegments, LimitedSegments, SegmentsError};
+///
+/// let uri = Origin::parse("/a/b/c").unwrap();
+/// let path = LimitedSegments::<3>::from_segments(uri.path().segments()).unwrap();
+/// assert_eq!(path.to_str(), Some("a/b/c"));
+///
+/// let error = LimitedSegments::<2>::from_segments(uri.path().segments()).unwrap_err();
+/// assert_eq!(error, SegmentsError::TooDeep { depth: 3, limit: 2 });
+///
+/// let uri = Origin::parse("/a/../b").unwrap();
+/// let error = LimitedSegments::<3>::from_segments(uri.path().segments()).unwrap_err();
+/// assert_eq!(error, SegmentsError::Traversal);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct LimitedSegments<const N: usize>(PathBuf);
+
+/// Error returned by the [`LimitedSegments`] segments guard.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SegmentsError {
+    /// There were more segments than the limit allows.
+    TooDeep {
+        /// The number of segments.
+        depth: usize,
+        /// The maximum number of segments.
+        limit: usize,
+    },
+    /// A segment was equal to `..`, before or after decoding.
+    Traversal,
+    /// A segment failed one of `PathBuf`'s security checks.
+    Path(PathError),
+}
+
+impl<const N: usize> LimitedSegments<N> {
+    /// Consumes `self` and returns the inner `PathBuf`.
+    #[inline(always)]
+    pub fn into_inner(self) -> PathBuf {
+        self.0
+    }
+}
+
+impl<const N: usize> std::ops::Deref for LimitedSegments<N> {
+    type Target = PathBuf;
+
+    #[inline(always)]
+    fn deref(&self) -> &PathBuf {
+        &self.0
+    }
+}
+
+impl<const N: usize> FromSegments<'_> for LimitedSegments<N> {
+    type Error = SegmentsError;
+
+    fn from_segments(segments: Segments<'_, Path>) -> Result<Self, Self::Error> {
+        if segments.len() > N {
+            return Err(SegmentsError::TooDeep { depth: segments.len(), limit: N });
+        }
+
+        if segments.clone().any(|s| s == "..") {
+            return Err(SegmentsError::Traversal);
+        }
+
+        segments.to_path_buf(false)
+            .map(LimitedSegments)
+            .map_err(SegmentsError::Path)
+    }
+}
+
+impl fmt::Display for SegmentsError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SegmentsError::TooDeep { depth, limit } => {
+                write!(f, "{} segments exceed the limit of {}", depth, limit)
+            }
+            SegmentsError::Traversal => write!(f, "path contains a `..` segment"),
+            SegmentsError::Path(e) => write!(f, "invalid path segment: {:?}", e),
+        }
+    }
+}
+
+impl std::error::Error for SegmentsError { }
+
+/// The raw, _undecoded_ segments matched by a `<param..>` route parameter.
+///
+/// Unlike [`PathBuf`] and [`Segments`], whose segments are percent-decoded,
+/// `RawSegments` preserves each segment exactly as it was sent by the client.
+/// This is useful when segments must be forwarded verbatim, as in a proxy,
+/// where decoding and re-encoding would turn a `%2F` into a `/`. Empty
+/// segments are skipped.
+///
+/// Individual segments can be retrieved via [`RawSegments::iter()`] while the
+/// [`Display`](fmt::Display) implementation writes the segments joined by
+/// `/`.
+///
+/// # ⚠️ DANGER ⚠️
+///
+/// `RawSegments` performs _none_ of the path traversal checks performed by the
+/// `PathBuf` implementation of [`FromSegments`]. In particular, segments like
+/// `..` or `%2E%2E` are passed through unchanged. The only segments rejected,
+/// with a [`PathError::BadChar`] error, are those that decode to a string
+/// containing a NUL byte. _Never_ use `RawSegments` to construct file system
+/// paths; use [`PathBuf`] instead.
+///
+/// # Example
+///
+/// ```rust
+/// # #[macro_use] extern crate rocket;
+/// use rocket::request::RawSegments;
+///
+/// #[get("/proxy/<path..>")]
+/// fn proxy(path: RawSegments<'_>) -> String {
+///     format!("https://upstream.example.com/{}", path)
+/// }
+///
+/// # use rocket::local::blocking::Client;
+/// # let client = Client::debug_with(routes![proxy]).unwrap();
+/// # let response = client.get("/proxy/a%2Fb/c%20d").dispatch();
+/// # assert_eq!(response.into_string().unwrap(),
+/// #     "https://upstream.example.com/a%2Fb/c%20d");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RawSegments<'r>(Vec<&'r RawStr>);
+
+impl<'r> RawSegments<'r> {
+    /// Returns an iterator over the individual raw, undecoded segments.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use rocket::http::uri::Origin;
+    /// use rocket::request::{FromSegments, RawSegments};
+    ///
+    /// let uri = Origin::parse("/a%2Fb//%C3%BC").unwrap();
+    /// let segments = RawSegments::from_segments(uri.path().segments()).unwrap();
+    /// let raw: Vec<_> = segments.iter().collect();
+    /// assert_eq!(raw, &["a%2Fb", "%C3%BC"]);
+    /// ```
+    #[inline]
+    pub fn iter(&self) -> impl Iterator<Item = &'r RawStr> + '_ {
+        self.0.iter().copied()
+    }
+
+    /// Returns the number of segments.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use rocket::http::uri::Origin;
+    /// use rocket::request::{FromSegments, RawSegments};
+    ///
+    /// let uri = Origin::parse("/a/b%20c").unwrap();
+    /// let segments = RawSegments::from_segments(uri.path().segments()).unwrap();
+    /// assert_eq!(segments.len(), 2);
+    /// ```
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Returns `true` if there are no segments.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use rocket::http::uri::Origin;
+    /// use rocket::request::{FromSegments, RawSegments};
+    ///
+    /// let uri = Origin::parse("/").unwrap();
+    /// let segments = RawSegments::from_segments(uri.path().segments()).unwrap();
+    /// assert!(segments.is_empty());
+    /// ```
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+impl<'r> FromSegments<'r> for RawSegments<'r> {
+    type Error = PathError;
+
+    fn from_segments(segments: Segments<'r, Path>) -> Result<Self, Self::Error> {
+        if segments.clone().any(|s| s.contains('\0')) {
+            return Err(PathError::BadChar('\0'));
+        }
+
+        Ok(RawSegments(segments.raw().collect()))
+    }
+}
+
+impl fmt::Display for RawSegments<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, segment) in self.0.iter().enumerate() {
+            if i != 0 { f.write_str("/")?; }
+            f.write_str(segment.as_str())?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Collects the percent-decoded segments verbatim. See [`FromSegments`] for
+/// how this differs from the `PathBuf` implementation.
+impl FromSegments<'_> for Vec<String> {
+    type Error = std::convert::Infallible;
+
+    fn from_segments(segments: Segments<'_, Path>) -> Result<Self, Self::Error> {
+        Ok(segments.with_empty().map(String::from).collect())
+    }
+}
+
 impl<'r, T: FromSegments<'r>> FromSegments<'r> for Result<T, T::Error> {
     type Error = std::convert::Infallible;
 
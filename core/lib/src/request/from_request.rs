@@ -1,12 +1,20 @@
-use std::fmt::Debug;
+use std::fmt::{self, Debug};
+use std::convert::Infallible;
 use std::net::{IpAddr, SocketAddr};
+use std::str::FromStr;
+use std::borrow::Cow;
 
-use crate::{Request, Route};
+use crate::{Request, Route, Rocket, Ignite, Sentinel};
 use crate::outcome::{self, IntoOutcome};
 use crate::outcome::Outcome::*;
+use crate::request::PeerAddr;
 
-use crate::http::{Status, ContentType, Accept, Method, CookieJar};
+use crate::http::{Status, ContentType, MediaType, Accept, Method, CookieJar};
 use crate::http::uri::{Host, Origin};
+use crate::http::uncased::UncasedStr;
+
+use time::{OffsetDateTime, PrimitiveDateTime};
+use time::{macros::format_description, format_description::FormatItem};
 
 /// Type alias for the `Outcome` of a `FromRequest` conversion.
 pub type Outcome<S, E> = outcome::Outcome<S, (Status, E), ()>;
@@ -32,6 +40,121 @@ impl<S, E> IntoOutcome<S, (Status, E), ()> for Result<S, E> {
     }
 }
 
+impl<S, E> Outcome<S, E> {
+    /// Converts `result` into an `Outcome`: `Ok(val)` into `Success(val)` and
+    /// `Err(e)` into `Failure((status, e))`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::http::Status;
+    /// use rocket::request::Outcome;
+    /// use rocket::outcome::Outcome::*;
+    ///
+    /// let outcome = Outcome::from_result_or(Status::BadRequest, "7".parse::<u8>());
+    /// assert_eq!(outcome.succeeded(), Some(7));
+    ///
+    /// let outcome = Outcome::from_result_or(Status::BadRequest, "x".parse::<u8>());
+    /// assert!(matches!(outcome, Failure((status, _)) if status == Status::BadRequest));
+    /// ```
+    #[inline]
+    pub fn from_result_or(status: Status, result: Result<S, E>) -> Self {
+        result.into_outcome(status)
+    }
+
+    /// Converts `result` into an `Outcome`: `Ok(val)` into `Success(val)` and
+    /// `Err(_)` into `Forward(())`.
+    #[inline]
+    pub fn from_result_or_forward(result: Result<S, E>) -> Self {
+        result.or_forward(())
+    }
+
+    /// Converts `option` into an `Outcome`: `Some(val)` into `Success(val)`
+    /// and `None` into `Failure((status, error))`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::http::Status;
+    /// use rocket::request::Outcome;
+    /// use rocket::outcome::Outcome::*;
+    ///
+    /// let outcome = Outcome::from_option_or(Status::Unauthorized, "missing", Some(1));
+    /// assert_eq!(outcome, Success(1));
+    ///
+    /// let outcome = Outcome::<u8, _>::from_option_or(Status::Unauthorized, "missing", None);
+    /// assert_eq!(outcome, Failure((Status::Unauthorized, "missing")));
+    /// ```
+    #[inline]
+    pub fn from_option_or(status: Status, error: E, option: Option<S>) -> Self {
+        option.into_outcome((status, error))
+    }
+
+    /// Converts `option` into an `Outcome`: `Some(val)` into `Success(val)`
+    /// and `None` into `Forward(())`.
+    #[inline]
+    pub fn from_option_or_forward(option: Option<S>) -> Self {
+        option.or_forward(())
+    }
+}
+
+impl<S, E: Clone + Send + Sync + 'static> Outcome<S, E> {
+    /// Returns a `Failure` outcome of `status` and `payload` after storing a
+    /// copy of `payload` in `request`'s local cache, keyed by its type `E`.
+    ///
+    /// The error value in a guard's `Failure` only reaches handlers that ask
+    /// for a `Result` or `Option` of the guard; a catcher invoked for the
+    /// failing `status` sees only the status. `error_with()` makes the payload
+    /// available to the catcher as well: it can be read back with
+    /// [`Request::local_cache_get::<E>()`](Request::local_cache_get()).
+    ///
+    /// # Lifetime
+    ///
+    /// The cached copy is owned by the request, not by the guard invocation,
+    /// so it lives until the request, including any catcher run on its behalf,
+    /// has been fully processed. Because `E` must be `'static`, a payload
+    /// cannot borrow from the request itself; use owned data instead.
+    ///
+    /// As with [`Request::local_cache()`], the first value stored for a given
+    /// type wins: if several guards fail with the same payload type, the
+    /// catcher sees the first one. Use distinct payload types to tell them
+    /// apart.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # #[macro_use] extern crate rocket;
+    /// use rocket::request::{self, Request, FromRequest};
+    /// use rocket::http::Status;
+    ///
+    /// #[derive(Debug, Clone)]
+    /// struct MissingScope(&'static str);
+    ///
+    /// struct Admin;
+    ///
+    /// #[rocket::async_trait]
+    /// impl<'r> FromRequest<'r> for Admin {
+    ///     type Error = MissingScope;
+    ///
+    ///     async fn from_request(req: &'r Request<'_>) -> request::Outcome<Self, Self::Error> {
+    ///         request::Outcome::error_with(req, Status::Forbidden, MissingScope("admin"))
+    ///     }
+    /// }
+    ///
+    /// #[catch(403)]
+    /// fn forbidden(req: &Request<'_>) -> String {
+    ///     match req.local_cache_get::<MissingScope>() {
+    ///         Some(MissingScope(scope)) => format!("missing scope `{}`", scope),
+    ///         None => "forbidden".into(),
+    ///     }
+    /// }
+    /// ```
+    pub fn error_with(request: &Request<'_>, status: Status, payload: E) -> Self {
+        request.local_cache(|| payload.clone());
+        Failure((status, payload))
+    }
+}
+
 /// Trait implemented by request guards to derive a value from incoming
 /// requests.
 ///
@@ -88,6 +211,12 @@ impl<S, E> IntoOutcome<S, (Status, E), ()> for Result<S, E> {
 /// example above, the order is `a` followed by `b` followed by `c`. Failure is
 /// short-circuiting; if one guard fails, the remaining are not attempted.
 ///
+/// Guards are evaluated sequentially, even when they are `async`: each guard's
+/// `from_request` future is awaited to completion before the next guard's is
+/// created, and guards are never polled concurrently. As a result, a guard can
+/// rely on values an earlier guard stored in the [request-local
+/// cache](crate::Request::local_cache()).
+///
 /// # Outcomes
 ///
 /// The returned [`Outcome`] of a `from_request` call determines how the
@@ -174,6 +303,27 @@ impl<S, E> IntoOutcome<S, (Status, E), ()> for Result<S, E> {
 ///
 ///     _This implementation always returns successfully._
 ///
+///   * **PeerAddr**
+///
+///     Extracts the peer of the incoming request, be it a TCP address or a
+///     Unix domain socket peer, as a [`PeerAddr`]. If the peer is not known,
+///     the request is forwarded. See [`Request::peer_addr()`] for details.
+///
+///   * **PreferredLanguage**
+///
+///     Negotiates the best language from the request's `Accept-Language`
+///     header among those in the managed [`SupportedLanguages`]. If no
+///     supported language is acceptable, the request is forwarded. See
+///     [`PreferredLanguage`] for details.
+///
+///   * **ConditionalRequest**
+///
+///     Parses the request's `If-Match`, `If-None-Match`, `If-Modified-Since`,
+///     `If-Unmodified-Since`, and `If-Range` headers. See
+///     [`ConditionalRequest`] for details.
+///
+///     _This implementation always returns successfully._
+///
 ///   * **Option&lt;T>** _where_ **T: FromRequest**
 ///
 ///     The type `T` is derived from the incoming request using `T`'s
@@ -488,6 +638,18 @@ impl<'r> FromRequest<'r> for SocketAddr {
     }
 }
 
+#[crate::async_trait]
+impl<'r> FromRequest<'r> for PeerAddr<'r> {
+    type Error = std::convert::Infallible;
+
+    async fn from_request(request: &'r Request<'_>) -> Outcome<Self, Self::Error> {
+        match request.peer_addr() {
+            Some(peer) => Success(peer),
+            None => Forward(())
+        }
+    }
+}
+
 #[crate::async_trait]
 impl<'r, T: FromRequest<'r>> FromRequest<'r> for Result<T, T::Error> {
     type Error = std::convert::Infallible;
@@ -512,3 +674,3394 @@ impl<'r, T: FromRequest<'r>> FromRequest<'r> for Option<T> {
         }
     }
 }
+
+/// A request guard that requires all of several request guards to succeed.
+///
+/// `AllOf<(A, B, ..)>` runs the guards `A`, `B`, and so on, in order, each to
+/// completion before the next begins. If every guard succeeds, the guard
+/// succeeds with a tuple of their outputs, in the same order, which can be
+/// retrieved with [`AllOf::into_inner()`] or by destructuring. Tuples of one
+/// to eight guards are supported.
+///
+/// Evaluation short-circuits: as soon as one guard fails or forwards, no
+/// further guards are run and `AllOf` fails or forwards in the same way. On
+/// failure, the failing guard's status is preserved and its error is returned
+/// in an [`AllOfError`].
+///
+/// # Example
+///
+/// ```rust
+/// # #[macro_use] extern crate rocket;
+/// use std::net::IpAddr;
+/// use rocket::request::AllOf;
+/// use rocket::http::{Method, uri::Host};
+///
+/// #[get("/")]
+/// fn index(guards: AllOf<(Method, &Host<'_>, IpAddr)>) -> String {
+///     let (method, host, ip) = guards.into_inner();
+///     format!("{} {} from {}", method, host, ip)
+/// }
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct AllOf<T>(pub T);
+
+impl<T> AllOf<T> {
+    /// Consumes `self` and returns the tuple of guard outputs.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::request::AllOf;
+    ///
+    /// let guards = AllOf((1, "two"));
+    /// assert_eq!(guards.into_inner(), (1, "two"));
+    /// ```
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+impl<T> std::ops::Deref for AllOf<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.0
+    }
+}
+
+/// The error returned by [`AllOf`] when one of its guards fails.
+pub struct AllOfError<'r> {
+    /// The 0-based position of the failing guard in the tuple.
+    pub index: usize,
+    /// The name of the failing guard's type.
+    pub guard: &'static str,
+    /// The error returned by the failing guard.
+    pub error: Box<dyn Debug + Send + 'r>,
+}
+
+impl Debug for AllOfError<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("AllOfError")
+            .field("index", &self.index)
+            .field("guard", &self.guard)
+            .field("error", &self.error)
+            .finish()
+    }
+}
+
+impl fmt::Display for AllOfError<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "guard {} (`{}`) failed: {:?}", self.index, self.guard, self.error)
+    }
+}
+
+macro_rules! impl_all_of {
+    ($($T:ident : $i:tt),+) => (
+        #[crate::async_trait]
+        impl<'r, $($T),+> FromRequest<'r> for AllOf<($($T,)+)>
+            where $($T: FromRequest<'r> + Send, $T::Error: Send + 'r),+
+        {
+            type Error = AllOfError<'r>;
+
+            #[allow(non_snake_case)]
+            async fn from_request(request: &'r Request<'_>) -> Outcome<Self, Self::Error> {
+                $(
+                    let $T = match $T::from_request(request).await {
+                        Success(value) => value,
+                        Forward(()) => return Forward(()),
+                        Failure((status, error)) => return Failure((status, AllOfError {
+                            index: $i,
+                            guard: std::any::type_name::<$T>(),
+                            error: Box::new(error),
+                        })),
+                    };
+                )+
+
+                Success(AllOf(($($T,)+)))
+            }
+        }
+    )
+}
+
+impl_all_of!(A: 0);
+impl_all_of!(A: 0, B: 1);
+impl_all_of!(A: 0, B: 1, C: 2);
+impl_all_of!(A: 0, B: 1, C: 2, D: 3);
+impl_all_of!(A: 0, B: 1, C: 2, D: 3, E: 4);
+impl_all_of!(A: 0, B: 1, C: 2, D: 3, E: 4, F: 5);
+impl_all_of!(A: 0, B: 1, C: 2, D: 3, E: 4, F: 5, G: 6);
+impl_all_of!(A: 0, B: 1, C: 2, D: 3, E: 4, F: 5, G: 6, H: 7);
+
+/// A request guard that succeeds with the first of several request guards to
+/// succeed.
+///
+/// `OneOf<(A, B, ..)>` runs the guards `A`, `B`, and so on, in order, each to
+/// completion before the next begins. The first guard to succeed stops the
+/// chain, and `OneOf` succeeds with a [`Matched`] value tagging which guard it
+/// was: `Matched::A` for the first, `Matched::B` for the second, and so on.
+/// Tuples of one to eight guards are supported.
+///
+/// A guard that fails or forwards doesn't stop the chain. If no guard
+/// succeeds and at least one failed, `OneOf` fails with the status of the
+/// _last_ failing guard and its error in a [`OneOfError`]. If every guard
+/// forwarded, `OneOf` forwards.
+///
+/// # Example
+///
+/// ```rust
+/// # #[macro_use] extern crate rocket;
+/// use rocket::request::{OneOf, Matched, BearerToken, BasicAuth};
+///
+/// #[get("/account")]
+/// fn account(auth: OneOf<(BearerToken, BasicAuth)>) -> String {
+///     match auth.into_inner() {
+///         Matched::A(token) => format!("token {}", token.0),
+///         Matched::B(basic) => format!("user {}", basic.username),
+///     }
+/// }
+/// ```
+pub struct OneOf<T: Alternatives>(pub T::Matched);
+
+/// A tuple of request guards usable with [`OneOf`].
+///
+/// Implemented for tuples of one to eight types. `Matched` is the [`Matched`]
+/// enum with one inhabited variant per tuple element.
+pub trait Alternatives {
+    /// The value produced when one of the guards succeeds.
+    type Matched;
+}
+
+/// The output of a [`OneOf`] guard: the output of the guard that succeeded,
+/// tagged by its position in the tuple.
+///
+/// Variant `A` holds the output of the first guard, `B` of the second, and so
+/// on. Variants beyond the number of guards hold [`Infallible`] and so can
+/// never be constructed; since Rust 1.82, `match` arms for them can be
+/// omitted.
+///
+/// [`Infallible`]: std::convert::Infallible
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Matched<A, B = Infallible, C = Infallible, D = Infallible,
+    E = Infallible, F = Infallible, G = Infallible, H = Infallible>
+{
+    /// The first guard succeeded.
+    A(A),
+    /// The second guard succeeded.
+    B(B),
+    /// The third guard succeeded.
+    C(C),
+    /// The fourth guard succeeded.
+    D(D),
+    /// The fifth guard succeeded.
+    E(E),
+    /// The sixth guard succeeded.
+    F(F),
+    /// The seventh guard succeeded.
+    G(G),
+    /// The eighth guard succeeded.
+    H(H),
+}
+
+impl<A, B, C, D, E, F, G, H> Matched<A, B, C, D, E, F, G, H> {
+    /// Returns the 0-based position of the guard that succeeded.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::request::Matched;
+    ///
+    /// let matched: Matched<u8, &str> = Matched::B("session");
+    /// assert_eq!(matched.index(), 1);
+    /// ```
+    pub fn index(&self) -> usize {
+        match self {
+            Matched::A(_) => 0,
+            Matched::B(_) => 1,
+            Matched::C(_) => 2,
+            Matched::D(_) => 3,
+            Matched::E(_) => 4,
+            Matched::F(_) => 5,
+            Matched::G(_) => 6,
+            Matched::H(_) => 7,
+        }
+    }
+}
+
+impl<T: Alternatives> OneOf<T> {
+    /// Consumes `self` and returns the tagged output of the guard that
+    /// succeeded.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::request::{OneOf, Matched};
+    ///
+    /// let guard: OneOf<(u8, &str)> = OneOf(Matched::A(7));
+    /// assert_eq!(guard.into_inner(), Matched::A(7));
+    /// ```
+    #[inline(always)]
+    pub fn into_inner(self) -> T::Matched {
+        self.0
+    }
+}
+
+impl<T: Alternatives> std::ops::Deref for OneOf<T> {
+    type Target = T::Matched;
+
+    fn deref(&self) -> &T::Matched {
+        &self.0
+    }
+}
+
+impl<T: Alternatives> Debug for OneOf<T> where T::Matched: Debug {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("OneOf").field(&self.0).finish()
+    }
+}
+
+/// The error returned by [`OneOf`] when none of its guards succeed and at
+/// least one fails.
+pub struct OneOfError<'r> {
+    /// The 0-based position of the last failing guard in the tuple.
+    pub index: usize,
+    /// The name of the last failing guard's type.
+    pub guard: &'static str,
+    /// The error returned by the last failing guard.
+    pub error: Box<dyn Debug + Send + 'r>,
+}
+
+impl Debug for OneOfError<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("OneOfError")
+            .field("index", &self.index)
+            .field("guard", &self.guard)
+            .field("error", &self.error)
+            .finish()
+    }
+}
+
+impl fmt::Display for OneOfError<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "guard {} (`{}`) failed: {:?}", self.index, self.guard, self.error)
+    }
+}
+
+macro_rules! impl_one_of {
+    ($($T:ident : $i:tt),+) => (
+        impl<$($T),+> Alternatives for ($($T,)+) {
+            type Matched = Matched<$($T),+>;
+        }
+
+        #[crate::async_trait]
+        impl<'r, $($T),+> FromRequest<'r> for OneOf<($($T,)+)>
+            where $($T: FromRequest<'r> + Send, $T::Error: Send + 'r),+
+        {
+            type Error = OneOfError<'r>;
+
+            async fn from_request(request: &'r Request<'_>) -> Outcome<Self, Self::Error> {
+                let mut failure = None;
+                $(
+                    match $T::from_request(request).await {
+                        Success(value) => return Success(OneOf(Matched::$T(value))),
+                        Forward(()) => {},
+                        Failure((status, error)) => failure = Some((status, OneOfError {
+                            index: $i,
+                            guard: std::any::type_name::<$T>(),
+                            error: Box::new(error),
+                        })),
+                    }
+                )+
+
+                match failure {
+                    Some(failure) => Failure(failure),
+                    None => Forward(()),
+                }
+            }
+        }
+    )
+}
+
+impl_one_of!(A: 0);
+impl_one_of!(A: 0, B: 1);
+impl_one_of!(A: 0, B: 1, C: 2);
+impl_one_of!(A: 0, B: 1, C: 2, D: 3);
+impl_one_of!(A: 0, B: 1, C: 2, D: 3, E: 4);
+impl_one_of!(A: 0, B: 1, C: 2, D: 3, E: 4, F: 5);
+impl_one_of!(A: 0, B: 1, C: 2, D: 3, E: 4, F: 5, G: 6);
+impl_one_of!(A: 0, B: 1, C: 2, D: 3, E: 4, F: 5, G: 6, H: 7);
+
+/// A language tag or, in an `Accept-Language` header, a language range.
+///
+/// A `LanguageTag` is a sequence of `-` separated subtags such as `en`,
+/// `en-US`, or `zh-Hant-TW`, or the wildcard range `*`. The first subtag must
+/// consist of 1 to 8 ASCII letters while the remaining subtags must consist of
+/// 1 to 8 ASCII letters or digits. Tags are compared case-insensitively.
+///
+/// # Example
+///
+/// ```rust
+/// use rocket::request::LanguageTag;
+///
+/// let tag: LanguageTag = "en-US".parse().unwrap();
+/// assert_eq!(tag, "EN-us".parse::<LanguageTag>().unwrap());
+/// assert_eq!(tag.as_str(), "en-US");
+/// assert_eq!(tag.primary(), "en");
+///
+/// assert!("en_US".parse::<LanguageTag>().is_err());
+/// assert!("".parse::<LanguageTag>().is_err());
+/// ```
+#[derive(Debug, Clone)]
+pub struct LanguageTag(String);
+
+impl LanguageTag {
+    /// Returns the tag as a string, as it was written.
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    /// Returns the primary, first subtag of `self`, or `*` for the wildcard.
+    pub fn primary(&self) -> &str {
+        self.0.split('-').next().unwrap_or(&self.0)
+    }
+
+    /// Returns `true` if `self` is the wildcard range `*`.
+    pub fn is_wildcard(&self) -> bool {
+        self.0 == "*"
+    }
+
+    /// Returns `true` if the language range `self` matches the language tag
+    /// `tag` per RFC 4647 basic filtering. That is, if `self` is `*`, is
+    /// case-insensitively equal to `tag`, or is a case-insensitive prefix of
+    /// `tag` followed by a `-`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::request::LanguageTag;
+    ///
+    /// let tag = |s: &str| s.parse::<LanguageTag>().unwrap();
+    /// assert!(tag("en").matches(&tag("en-US")));
+    /// assert!(tag("EN-us").matches(&tag("en-US")));
+    /// assert!(tag("*").matches(&tag("fr")));
+    /// assert!(!tag("en-US").matches(&tag("en")));
+    /// assert!(!tag("e").matches(&tag("en")));
+    /// ```
+    pub fn matches(&self, tag: &LanguageTag) -> bool {
+        if self.is_wildcard() {
+            return true;
+        }
+
+        let range = UncasedStr::new(&self.0);
+        match tag.0.get(..self.0.len()) {
+            Some(prefix) if range == prefix => {
+                tag.0.len() == self.0.len() || tag.0[self.0.len()..].starts_with('-')
+            }
+            _ => false,
+        }
+    }
+}
+
+impl FromStr for LanguageTag {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        fn valid(i: usize, subtag: &str) -> bool {
+            (1..=8).contains(&subtag.len()) && match i {
+                0 => subtag.chars().all(|c| c.is_ascii_alphabetic()),
+                _ => subtag.chars().all(|c| c.is_ascii_alphanumeric()),
+            }
+        }
+
+        if s == "*" || s.split('-').enumerate().all(|(i, subtag)| valid(i, subtag)) {
+            Ok(LanguageTag(s.into()))
+        } else {
+            Err(s.into())
+        }
+    }
+}
+
+impl PartialEq for LanguageTag {
+    fn eq(&self, other: &LanguageTag) -> bool {
+        UncasedStr::new(&self.0) == UncasedStr::new(&other.0)
+    }
+}
+
+impl Eq for LanguageTag {}
+
+impl std::hash::Hash for LanguageTag {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        UncasedStr::new(&self.0).hash(state)
+    }
+}
+
+impl fmt::Display for LanguageTag {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+/// The languages an application supports, in order of preference, for use
+/// with the [`PreferredLanguage`] request guard.
+///
+/// The first language is the default, used when a request expresses no
+/// preference. `SupportedLanguages` must be managed for `PreferredLanguage`
+/// to be used; Rocket aborts launch otherwise.
+///
+/// # Example
+///
+/// ```rust
+/// # #[macro_use] extern crate rocket;
+/// use rocket::request::SupportedLanguages;
+///
+/// #[launch]
+/// fn rocket() -> _ {
+///     let languages = SupportedLanguages::new(["en-US", "fr", "de"]).unwrap();
+///     rocket::build().manage(languages)
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct SupportedLanguages(Vec<LanguageTag>);
+
+impl SupportedLanguages {
+    /// Parses every string in `languages` as a [`LanguageTag`], returning the
+    /// set of supported languages in the given order. Returns the first string
+    /// that isn't a valid tag for a specific language, that is, isn't a valid
+    /// tag or is `*`, as an `Err`. Also returns an empty `Err` if `languages`
+    /// is empty.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::request::SupportedLanguages;
+    ///
+    /// let languages = SupportedLanguages::new(["en-US", "fr"]).unwrap();
+    /// assert_eq!(languages.default_language().as_str(), "en-US");
+    ///
+    /// assert_eq!(SupportedLanguages::new(["en", "*"]), Err("*".into()));
+    /// assert_eq!(SupportedLanguages::new(["en", "en US"]), Err("en US".into()));
+    /// assert_eq!(SupportedLanguages::new(Vec::<&str>::new()), Err("".into()));
+    /// ```
+    pub fn new<I, S>(languages: I) -> Result<Self, String>
+        where I: IntoIterator<Item = S>, S: AsRef<str>
+    {
+        let tags = languages.into_iter()
+            .map(|tag| match tag.as_ref().parse::<LanguageTag>() {
+                Ok(tag) if !tag.is_wildcard() => Ok(tag),
+                _ => Err(tag.as_ref().to_string()),
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        if tags.is_empty() {
+            return Err(String::new());
+        }
+
+        Ok(SupportedLanguages(tags))
+    }
+
+    /// Returns the default language: the first in the set.
+    pub fn default_language(&self) -> &LanguageTag {
+        &self.0[0]
+    }
+
+    /// Returns an iterator over the supported languages in order.
+    pub fn iter(&self) -> impl Iterator<Item = &LanguageTag> {
+        self.0.iter()
+    }
+
+    /// Returns the supported language that best matches the `Accept-Language`
+    /// header value `accept_language`, if any. See [`PreferredLanguage`] for
+    /// the negotiation rules.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::request::SupportedLanguages;
+    ///
+    /// let languages = SupportedLanguages::new(["en-US", "fr", "de"]).unwrap();
+    /// let best = |header| languages.negotiate(header).map(|tag| tag.as_str());
+    ///
+    /// assert_eq!(best("fr-CH, fr;q=0.9, en;q=0.8, de;q=0.7, *;q=0.5"), Some("fr"));
+    /// assert_eq!(best("de-DE,de;q=0.9,en-US;q=0.8,en;q=0.7"), Some("de"));
+    /// assert_eq!(best("*;q=0.5, en;q=0"), Some("fr"));
+    /// assert_eq!(best(""), Some("en-US"));
+    /// assert_eq!(best("ja, zh;q=0.5"), None);
+    /// ```
+    pub fn negotiate(&self, accept_language: &str) -> Option<&LanguageTag> {
+        let ranges = PreferredLanguage::parse(accept_language);
+        if ranges.is_empty() {
+            return Some(self.default_language());
+        }
+
+        // For each supported language, its quality is that of the most
+        // specific range that matches it. Ranges excluded with `q=0` thus
+        // override less specific ranges, like `*`, that would include them.
+        let quality = |tag: &LanguageTag| ranges.iter()
+            .enumerate()
+            .filter(|(_, (range, _))| range.matches(tag))
+            .max_by_key(|(i, (range, _))| {
+                let len = if range.is_wildcard() { 0 } else { range.as_str().len() };
+                (len, std::cmp::Reverse(*i))
+            })
+            .map(|(i, (_, q))| (*q, i));
+
+        // Among acceptable languages, pick the one with the highest quality,
+        // breaking ties by header order and then by the supported order.
+        self.iter()
+            .enumerate()
+            .filter_map(|(j, tag)| quality(tag).map(|(q, i)| (tag, q, i, j)))
+            .filter(|(_, q, ..)| *q > 0.0)
+            .min_by(|(_, q1, i1, j1), (_, q2, i2, j2)| {
+                q2.partial_cmp(q1).unwrap_or(std::cmp::Ordering::Equal)
+                    .then(i1.cmp(i2))
+                    .then(j1.cmp(j2))
+            })
+            .map(|(tag, ..)| tag)
+    }
+}
+
+/// A request guard for the language negotiated from the `Accept-Language`
+/// header against the application's managed [`SupportedLanguages`].
+///
+/// # Negotiation
+///
+/// The header is parsed into a list of language ranges and their quality
+/// values, as returned by [`PreferredLanguage::parse()`]. Then:
+///
+///   * If the header is missing or contains no valid ranges, the default,
+///     first supported language is chosen.
+///   * Each supported language is matched against the ranges per RFC 4647
+///     basic filtering, so `en` matches `en` and `en-US` but `en-US` does not
+///     match `en`. Comparisons are case-insensitive. The wildcard `*` matches
+///     every language.
+///   * A supported language takes the quality of the most specific range
+///     matching it. A quality of `0` excludes the language, so `*, fr;q=0`
+///     accepts any language but `fr`.
+///   * The acceptable language with the highest quality is chosen. Ties are
+///     broken by the order of the matching ranges in the header and then by
+///     the order of the supported languages.
+///
+/// If no supported language is acceptable, the request is forwarded. If
+/// `SupportedLanguages` is not managed, the request fails with status `500`,
+/// though Rocket aborts launch before this can occur for routes using the
+/// guard.
+///
+/// # Example
+///
+/// ```rust
+/// # #[macro_use] extern crate rocket;
+/// use rocket::request::{PreferredLanguage, SupportedLanguages};
+///
+/// #[get("/")]
+/// fn index(lang: PreferredLanguage) -> &'static str {
+///     match lang.primary() {
+///         "fr" => "Bonjour!",
+///         "de" => "Hallo!",
+///         _ => "Hello!",
+///     }
+/// }
+///
+/// #[launch]
+/// fn rocket() -> _ {
+///     let languages = SupportedLanguages::new(["en", "fr", "de"]).unwrap();
+///     rocket::build()
+///         .manage(languages)
+///         .mount("/", routes![index])
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PreferredLanguage(pub LanguageTag);
+
+impl PreferredLanguage {
+    /// Parses the `Accept-Language` header value `header` into a list of
+    /// language ranges and their quality values, ordered from most to least
+    /// preferred. Ranges of equal quality keep their order in the header.
+    ///
+    /// Entries with a malformed range or quality value are ignored. Only the
+    /// first occurrence of a duplicated range is kept. Ranges without a
+    /// quality value have a quality of `1`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::request::PreferredLanguage;
+    ///
+    /// let ranges = PreferredLanguage::parse("en;q=0.5, fr-CH, de;q=x, en;q=1, *;q=0.1");
+    /// let ranges: Vec<_> = ranges.iter().map(|(t, q)| (t.as_str(), *q)).collect();
+    /// assert_eq!(ranges, [("fr-CH", 1.0), ("en", 0.5), ("*", 0.1)]);
+    /// ```
+    pub fn parse(header: &str) -> Vec<(LanguageTag, f32)> {
+        fn quality(param: &str) -> Option<f32> {
+            let (key, value) = param.split_once('=')?;
+            if !key.trim().eq_ignore_ascii_case("q") {
+                return None;
+            }
+
+            // qvalue = ( "0" [ "." 0*3DIGIT ] ) / ( "1" [ "." 0*3("0") ] )
+            let value = value.trim();
+            let (int, frac) = value.split_once('.').unwrap_or((value, ""));
+            let valid = frac.len() <= 3 && match int {
+                "0" => frac.chars().all(|c| c.is_ascii_digit()),
+                "1" => frac.chars().all(|c| c == '0'),
+                _ => false,
+            };
+
+            if !valid {
+                return None;
+            }
+
+            value.parse().ok()
+        }
+
+        let mut ranges: Vec<(LanguageTag, f32)> = vec![];
+        for entry in header.split(',').map(|e| e.trim()).filter(|e| !e.is_empty()) {
+            let mut parts = entry.split(';');
+            let tag = match parts.next().and_then(|t| t.trim().parse::<LanguageTag>().ok()) {
+                Some(tag) => tag,
+                None => continue,
+            };
+
+            let q = match parts.next() {
+                Some(param) => match (quality(param), parts.next()) {
+                    (Some(q), None) => q,
+                    _ => continue,
+                },
+                None => 1.0,
+            };
+
+            if !ranges.iter().any(|(existing, _)| *existing == tag) {
+                ranges.push((tag, q));
+            }
+        }
+
+        ranges.sort_by(|(_, a), (_, b)| b.partial_cmp(a).unwrap_or(std::cmp::Ordering::Equal));
+        ranges
+    }
+}
+
+impl std::ops::Deref for PreferredLanguage {
+    type Target = LanguageTag;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+#[crate::async_trait]
+impl<'r> FromRequest<'r> for PreferredLanguage {
+    type Error = ();
+
+    async fn from_request(request: &'r Request<'_>) -> Outcome<Self, Self::Error> {
+        let languages = match request.rocket().state::<SupportedLanguages>() {
+            Some(languages) => languages,
+            None => {
+                error_!("Attempted to negotiate a language without `SupportedLanguages`!");
+                return Failure((Status::InternalServerError, ()));
+            }
+        };
+
+        let header = request.headers().get("Accept-Language").collect::<Vec<_>>().join(",");
+        match languages.negotiate(&header) {
+            Some(tag) => Success(PreferredLanguage(tag.clone())),
+            None => Forward(())
+        }
+    }
+}
+
+impl Sentinel for PreferredLanguage {
+    fn abort(rocket: &Rocket<Ignite>) -> bool {
+        if rocket.state::<SupportedLanguages>().is_none() {
+            error!("launching with unmanaged `SupportedLanguages` state.");
+            info_!("Using `PreferredLanguage` requires managing `SupportedLanguages`.");
+            return true;
+        }
+
+        false
+    }
+}
+
+/// A set of media types an application can produce, for use with the
+/// [`Negotiated`] request guard.
+///
+/// Typically implemented by a field-less `enum` with one variant per format.
+///
+/// # Example
+///
+/// ```rust
+/// use rocket::http::MediaType;
+/// use rocket::request::Negotiate;
+///
+/// #[derive(Debug, Clone, Copy, PartialEq)]
+/// enum Format {
+///     Json,
+///     Html,
+///     Csv,
+/// }
+///
+/// impl Negotiate for Format {
+///     const SUPPORTED: &'static [Self] = &[Format::Json, Format::Html, Format::Csv];
+///
+///     fn media_type(&self) -> MediaType {
+///         match self {
+///             Format::Json => MediaType::JSON,
+///             Format::Html => MediaType::HTML,
+///             Format::Csv => MediaType::CSV,
+///         }
+///     }
+/// }
+/// ```
+pub trait Negotiate: Clone + Send + 'static {
+    /// The supported values, in order of preference. The first is the
+    /// default, used when a request expresses no preference.
+    const SUPPORTED: &'static [Self];
+
+    /// The media type of `self`.
+    fn media_type(&self) -> MediaType;
+}
+
+/// A request guard for the media type negotiated from the `Accept` header
+/// against the values of a [`Negotiate`] implementation.
+///
+/// # Negotiation
+///
+///   * If the header is missing or malformed, the first supported value is
+///     chosen.
+///   * Each supported media type is matched against the ranges in the header.
+///     `*/*` matches every media type, `text/*` every `text` media type, and
+///     `text/html` only itself. Comparisons are case-insensitive, and
+///     parameters other than `q`, like `charset`, are ignored.
+///   * A supported media type takes the quality, `q`, of the most specific
+///     range matching it, or `1` if the range has none. A quality of `0`
+///     excludes the media type, so `*/*, text/csv;q=0` accepts anything but
+///     CSV.
+///   * The acceptable media type with the highest quality is chosen. Ties are
+///     broken by the order of [`Negotiate::SUPPORTED`].
+///
+/// If no supported media type is acceptable, the request fails with status
+/// `406 Not Acceptable`. Use `Option<Negotiated<T>>` to fall back to a
+/// default instead.
+///
+/// # Example
+///
+/// ```rust
+/// # #[macro_use] extern crate rocket;
+/// # use rocket::http::MediaType;
+/// use rocket::request::{Negotiate, Negotiated};
+///
+/// # #[derive(Clone, Copy)]
+/// # enum Format { Json, Html }
+/// # impl Negotiate for Format {
+/// #     const SUPPORTED: &'static [Self] = &[Format::Json, Format::Html];
+/// #     fn media_type(&self) -> MediaType {
+/// #         match self { Format::Json => MediaType::JSON, Format::Html => MediaType::HTML }
+/// #     }
+/// # }
+/// #[get("/report")]
+/// fn report(format: Negotiated<Format>) -> &'static str {
+///     match *format {
+///         Format::Json => "{ \"total\": 3 }",
+///         Format::Html => "<p>Total: 3</p>",
+///     }
+/// }
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Negotiated<T>(pub T);
+
+impl<T: Negotiate> Negotiated<T> {
+    /// Returns the supported value that best matches `accept`, if any. See
+    /// [`Negotiated`] for the negotiation rules.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use rocket::http::MediaType;
+    /// use rocket::http::Accept;
+    /// use rocket::request::{Negotiate, Negotiated};
+    ///
+    /// # #[derive(Debug, Clone, Copy, PartialEq)]
+    /// # enum Format { Json, Html }
+    /// # impl Negotiate for Format {
+    /// #     const SUPPORTED: &'static [Self] = &[Format::Json, Format::Html];
+    /// #     fn media_type(&self) -> MediaType {
+    /// #         match self { Format::Json => MediaType::JSON, Format::Html => MediaType::HTML }
+    /// #     }
+    /// # }
+    /// let best = |header: &str| {
+    ///     let accept: Accept = header.parse().unwrap();
+    ///     Negotiated::<Format>::negotiate(Some(&accept)).map(|f| f.0)
+    /// };
+    ///
+    /// assert_eq!(best("text/html"), Some(Format::Html));
+    /// assert_eq!(best("text/*, application/json;q=0.5"), Some(Format::Html));
+    /// assert_eq!(best("*/*"), Some(Format::Json));
+    /// assert_eq!(best("text/plain"), None);
+    /// assert_eq!(Negotiated::<Format>::negotiate(None), Some(Negotiated(Format::Json)));
+    /// ```
+    pub fn negotiate(accept: Option<&Accept>) -> Option<Self> {
+        let accept = match accept {
+            Some(accept) => accept,
+            None => return T::SUPPORTED.first().cloned().map(Negotiated),
+        };
+
+        // The specificity of `range` if it matches `media_type`.
+        let specificity = |range: &MediaType, media_type: &MediaType| {
+            let (any_top, any_sub) = (range.top() == "*", range.sub() == "*");
+            let top = any_top || range.top() == media_type.top();
+            let sub = any_sub || range.sub() == media_type.sub();
+            match (top && sub, any_top, any_sub) {
+                (false, ..) => None,
+                (true, true, _) => Some(0),
+                (true, false, true) => Some(1),
+                (true, false, false) => Some(2),
+            }
+        };
+
+        // The `q` parameter may follow others, like `charset`.
+        let quality = |range: &MediaType| range.params()
+            .find(|(key, _)| key == "q")
+            .and_then(|(_, value)| value.parse::<f32>().ok())
+            .unwrap_or(1.0);
+
+        // Each supported media type takes the quality of the most specific
+        // range matching it; earlier ranges win among equally specific ones.
+        let mut best: Option<(&T, f32)> = None;
+        for value in T::SUPPORTED {
+            let media_type = value.media_type();
+            let q = accept.iter()
+                .enumerate()
+                .filter_map(|(i, range)| {
+                    let range = range.media_type();
+                    specificity(range, &media_type).map(|s| (s, std::cmp::Reverse(i), range))
+                })
+                .max_by_key(|(s, i, _)| (*s, *i))
+                .map(|(.., range)| quality(range));
+
+            match (q, best) {
+                (Some(q), Some((_, best_q))) if q > best_q => best = Some((value, q)),
+                (Some(q), None) if q > 0.0 => best = Some((value, q)),
+                _ => continue,
+            }
+        }
+
+        best.map(|(value, _)| Negotiated(value.clone()))
+    }
+
+    /// Consumes `self` and returns the negotiated value.
+    #[inline(always)]
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+impl<T> std::ops::Deref for Negotiated<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.0
+    }
+}
+
+#[crate::async_trait]
+impl<'r, T: Negotiate> FromRequest<'r> for Negotiated<T> {
+    type Error = ();
+
+    async fn from_request(request: &'r Request<'_>) -> Outcome<Self, Self::Error> {
+        match Negotiated::negotiate(request.accept()) {
+            Some(negotiated) => Success(negotiated),
+            None => Failure((Status::NotAcceptable, ()))
+        }
+    }
+}
+
+/// A media type required of request bodies, for use with the
+/// [`RequireContentType`] request guard.
+///
+/// Typically implemented by a unit `struct` named after the media type.
+///
+/// # Example
+///
+/// ```rust
+/// use rocket::http::MediaType;
+/// use rocket::request::RequiredMediaType;
+///
+/// struct Json;
+///
+/// impl RequiredMediaType for Json {
+///     fn media_type() -> MediaType {
+///         MediaType::JSON
+///     }
+/// }
+/// ```
+pub trait RequiredMediaType: Send + 'static {
+    /// The required media type. Its parameters, if any, are ignored.
+    fn media_type() -> MediaType;
+}
+
+/// A request guard that requires the request's `Content-Type` to be
+/// `T::media_type()`.
+///
+/// The guard checks the header alone and never reads the body, so it can
+/// reject a request before a data guard parses it. The top-level type and
+/// subtype are compared case-insensitively, and parameters such as `charset`
+/// are ignored, so `Application/JSON; charset=utf-8` satisfies a requirement
+/// of `application/json`. The guard:
+///
+///   * succeeds with the request's [`ContentType`] if it matches.
+///   * fails with `415 Unsupported Media Type` and a [`ContentTypeError`] if
+///     the header is missing, malformed, or is another media type.
+///
+/// Unlike the `format` route attribute, which forwards, the guard fails so
+/// that a client is told that the media type it sent is the problem. Use
+/// `Option<RequireContentType<T>>` or `Result` to handle the error instead.
+///
+/// # Example
+///
+/// ```rust
+/// # #[macro_use] extern crate rocket;
+/// # use rocket::http::MediaType;
+/// use rocket::request::{RequireContentType, RequiredMediaType};
+///
+/// # struct Json;
+/// # impl RequiredMediaType for Json {
+/// #     fn media_type() -> MediaType { MediaType::JSON }
+/// # }
+/// #[post("/items", data = "<item>")]
+/// fn create(_json: RequireContentType<Json>, item: &str) -> String {
+///     format!("created {}", item)
+/// }
+/// ```
+pub struct RequireContentType<T> {
+    content_type: ContentType,
+    _required: std::marker::PhantomData<fn() -> T>,
+}
+
+/// Error returned by the [`RequireContentType`] guard.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ContentTypeError {
+    /// The request has no `Content-Type` header.
+    Missing,
+    /// The request's `Content-Type` header isn't a valid media type.
+    Malformed,
+    /// The request's `Content-Type` isn't the required media type.
+    Mismatch(ContentType),
+}
+
+impl<T: RequiredMediaType> RequireContentType<T> {
+    /// Checks `content_type`, the value of a request's `Content-Type` header,
+    /// if any, against `T::media_type()`. See [`RequireContentType`] for the
+    /// matching rules.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use rocket::http::MediaType;
+    /// use rocket::http::ContentType;
+    /// use rocket::request::{RequireContentType, RequiredMediaType, ContentTypeError};
+    ///
+    /// # struct Json;
+    /// # impl RequiredMediaType for Json {
+    /// #     fn media_type() -> MediaType { MediaType::JSON }
+    /// # }
+    /// let check = |ct: Option<&ContentType>| RequireContentType::<Json>::check(ct).is_ok();
+    /// assert!(check(Some(&ContentType::JSON)));
+    /// assert!(check(Some(&"APPLICATION/Json; charset=utf-8".parse().unwrap())));
+    /// assert!(!check(Some(&ContentType::Form)));
+    ///
+    /// let missing = RequireContentType::<Json>::check(None);
+    /// assert_eq!(missing.err(), Some(ContentTypeError::Missing));
+    /// ```
+    pub fn check(content_type: Option<&ContentType>) -> Result<Self, ContentTypeError> {
+        let content_type = content_type.ok_or(ContentTypeError::Missing)?;
+        let required = T::media_type();
+        if content_type.top() != required.top() || content_type.sub() != required.sub() {
+            return Err(ContentTypeError::Mismatch(content_type.clone()));
+        }
+
+        Ok(RequireContentType {
+            content_type: content_type.clone(),
+            _required: std::marker::PhantomData,
+        })
+    }
+}
+
+impl<T> RequireContentType<T> {
+    /// The request's `Content-Type`, including any parameters.
+    pub fn content_type(&self) -> &ContentType {
+        &self.content_type
+    }
+}
+
+impl<T> fmt::Debug for RequireContentType<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("RequireContentType").field(&self.content_type).finish()
+    }
+}
+
+impl fmt::Display for ContentTypeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ContentTypeError::Missing => f.write_str("missing `Content-Type` header"),
+            ContentTypeError::Malformed => f.write_str("malformed `Content-Type` header"),
+            ContentTypeError::Mismatch(ct) => write!(f, "unsupported media type `{}`", ct),
+        }
+    }
+}
+
+impl std::error::Error for ContentTypeError { }
+
+#[crate::async_trait]
+impl<'r, T: RequiredMediaType> FromRequest<'r> for RequireContentType<T> {
+    type Error = ContentTypeError;
+
+    async fn from_request(request: &'r Request<'_>) -> Outcome<Self, Self::Error> {
+        let result = match request.content_type() {
+            None if request.headers().contains("Content-Type") => {
+                Err(ContentTypeError::Malformed)
+            }
+            content_type => RequireContentType::check(content_type),
+        };
+
+        result.into_outcome(Status::UnsupportedMediaType)
+    }
+}
+
+/// An entity tag, as found in `ETag`, `If-Match`, and `If-None-Match` headers.
+///
+/// An entity tag is an opaque, double-quoted string such as `"v1"`, optionally
+/// prefixed with `W/` to mark it as _weak_, as in `W/"v1"`. Two entity tags
+/// match _strongly_ if neither is weak and their opaque tags are identical.
+/// They match _weakly_ if their opaque tags are identical, regardless of
+/// either being weak.
+///
+/// # Example
+///
+/// ```rust
+/// use rocket::request::EntityTag;
+///
+/// let strong = EntityTag::strong("v1").unwrap();
+/// let weak: EntityTag = "W/\"v1\"".parse().unwrap();
+/// assert_eq!(weak.tag(), "v1");
+/// assert_eq!(weak.to_string(), "W/\"v1\"");
+///
+/// assert!(strong.weak_eq(&weak));
+/// assert!(!strong.strong_eq(&weak));
+/// assert!(strong.strong_eq(&strong));
+///
+/// assert!(EntityTag::strong("a\"b").is_none());
+/// assert!("v1".parse::<EntityTag>().is_err());
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct EntityTag {
+    weak: bool,
+    tag: String,
+}
+
+impl EntityTag {
+    /// Creates a strong entity tag with the opaque tag `tag`, which must not
+    /// include the surrounding quotes. Returns `None` if `tag` contains a
+    /// character that isn't allowed in an entity tag, such as `"`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::request::EntityTag;
+    ///
+    /// let etag = EntityTag::strong("abc123").unwrap();
+    /// assert_eq!(etag.to_string(), "\"abc123\"");
+    /// ```
+    pub fn strong<T: Into<String>>(tag: T) -> Option<EntityTag> {
+        EntityTag::new(tag.into(), false)
+    }
+
+    /// Creates a weak entity tag with the opaque tag `tag`, which must not
+    /// include the surrounding quotes. Returns `None` if `tag` contains a
+    /// character that isn't allowed in an entity tag, such as `"`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::request::EntityTag;
+    ///
+    /// let etag = EntityTag::weak("abc123").unwrap();
+    /// assert_eq!(etag.to_string(), "W/\"abc123\"");
+    /// ```
+    pub fn weak<T: Into<String>>(tag: T) -> Option<EntityTag> {
+        EntityTag::new(tag.into(), true)
+    }
+
+    fn new(tag: String, weak: bool) -> Option<EntityTag> {
+        // etagc = %x21 / %x23-7E / obs-text
+        let valid = |b: u8| b == 0x21 || (0x23..=0x7E).contains(&b) || b >= 0x80;
+        tag.bytes().all(valid).then(|| EntityTag { weak, tag })
+    }
+
+    /// Parses the entity tag at the start of `s`, returning it and the
+    /// remainder of `s`.
+    fn parse_prefix(s: &str) -> Option<(EntityTag, &str)> {
+        let (weak, s) = match s.strip_prefix("W/") {
+            Some(rest) => (true, rest),
+            None => (false, s),
+        };
+
+        let s = s.strip_prefix('"')?;
+        let end = s.find('"')?;
+        let etag = EntityTag::new(s[..end].into(), weak)?;
+        Some((etag, &s[end + 1..]))
+    }
+
+    /// Returns the opaque tag, without the surrounding quotes or `W/` prefix.
+    pub fn tag(&self) -> &str {
+        &self.tag
+    }
+
+    /// Returns `true` if `self` is a weak entity tag.
+    pub fn is_weak(&self) -> bool {
+        self.weak
+    }
+
+    /// Returns `true` if `self` and `other` match using the _strong_
+    /// comparison function: neither is weak and their opaque tags are
+    /// identical.
+    pub fn strong_eq(&self, other: &EntityTag) -> bool {
+        !self.weak && !other.weak && self.tag == other.tag
+    }
+
+    /// Returns `true` if `self` and `other` match using the _weak_ comparison
+    /// function: their opaque tags are identical. The `W/` prefix is ignored.
+    pub fn weak_eq(&self, other: &EntityTag) -> bool {
+        self.tag == other.tag
+    }
+}
+
+impl FromStr for EntityTag {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match EntityTag::parse_prefix(s) {
+            Some((etag, "")) => Ok(etag),
+            _ => Err(s.into()),
+        }
+    }
+}
+
+impl fmt::Display for EntityTag {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.weak {
+            f.write_str("W/")?;
+        }
+
+        write!(f, "\"{}\"", self.tag)
+    }
+}
+
+/// The value of an `If-Match` or `If-None-Match` header: either the wildcard
+/// `*` or a list of entity tags.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum EntityTags {
+    /// The wildcard `*`, which matches any current representation.
+    Any,
+    /// A non-empty list of entity tags.
+    List(Vec<EntityTag>),
+}
+
+impl EntityTags {
+    /// Parses an `If-Match` or `If-None-Match` header value. Returns `None` if
+    /// `value` is empty or malformed.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::request::{EntityTag, EntityTags};
+    ///
+    /// assert_eq!(EntityTags::parse(" * "), Some(EntityTags::Any));
+    ///
+    /// let tags = EntityTags::parse(r#""a", W/"b,c" ,"d""#).unwrap();
+    /// assert!(tags.strong_match(&EntityTag::strong("d").unwrap()));
+    /// assert!(tags.weak_match(&EntityTag::strong("b,c").unwrap()));
+    /// assert!(!tags.strong_match(&EntityTag::strong("b,c").unwrap()));
+    ///
+    /// assert_eq!(EntityTags::parse(r#""a", b"#), None);
+    /// assert_eq!(EntityTags::parse("*, \"a\""), None);
+    /// assert_eq!(EntityTags::parse(""), None);
+    /// ```
+    pub fn parse(value: &str) -> Option<EntityTags> {
+        const OWS: &[char] = &[' ', '\t'];
+
+        if value.trim_matches(OWS) == "*" {
+            return Some(EntityTags::Any);
+        }
+
+        let mut tags = vec![];
+        let mut rest = value;
+        loop {
+            rest = rest.trim_start_matches(|c| OWS.contains(&c) || c == ',');
+            if rest.is_empty() {
+                break;
+            }
+
+            let (etag, remaining) = EntityTag::parse_prefix(rest)?;
+            rest = remaining.trim_start_matches(OWS);
+            if !rest.is_empty() && !rest.starts_with(',') {
+                return None;
+            }
+
+            tags.push(etag);
+        }
+
+        (!tags.is_empty()).then(|| EntityTags::List(tags))
+    }
+
+    /// Returns `true` if `etag` is matched by `self` using the strong
+    /// comparison function. The wildcard matches every entity tag.
+    pub fn strong_match(&self, etag: &EntityTag) -> bool {
+        match self {
+            EntityTags::Any => true,
+            EntityTags::List(tags) => tags.iter().any(|tag| tag.strong_eq(etag)),
+        }
+    }
+
+    /// Returns `true` if `etag` is matched by `self` using the weak comparison
+    /// function. The wildcard matches every entity tag.
+    pub fn weak_match(&self, etag: &EntityTag) -> bool {
+        match self {
+            EntityTags::Any => true,
+            EntityTags::List(tags) => tags.iter().any(|tag| tag.weak_eq(etag)),
+        }
+    }
+}
+
+/// Parses an HTTP-date in any of the three formats recipients must accept:
+/// IMF-fixdate, the obsolete RFC 850 format, and ANSI C's `asctime()` format.
+fn parse_http_date(value: &str) -> Option<OffsetDateTime> {
+    const IMF_FIXDATE: &[FormatItem<'_>] = format_description!(
+        "[weekday repr:short], [day] [month repr:short] [year] [hour]:[minute]:[second] GMT");
+
+    const ASCTIME: &[FormatItem<'_>] = format_description!(
+        "[weekday repr:short] [month repr:short] [day padding:space] \
+        [hour]:[minute]:[second] [year]");
+
+    const RFC_850_REST: &[FormatItem<'_>] = format_description!(
+        "[day] [month repr:short] [year] [hour]:[minute]:[second] GMT");
+
+    const WEEKDAYS: &[&str] = &[
+        "Monday", "Tuesday", "Wednesday", "Thursday", "Friday", "Saturday", "Sunday"
+    ];
+
+    // For example, `Sunday, 06-Nov-94 08:49:37 GMT`. Two-digit years below 70
+    // are taken to be in the 21st century.
+    let rfc_850 = || {
+        let (weekday, rest) = value.split_once(", ")?;
+        let (date, time) = rest.split_once(' ')?;
+        let mut parts = date.split('-');
+        let (day, month, year) = (parts.next()?, parts.next()?, parts.next()?);
+        if !WEEKDAYS.contains(&weekday) || parts.next().is_some() || year.len() != 2 {
+            return None;
+        }
+
+        let year = year.parse::<u8>().ok()?;
+        let year = if year < 70 { 2000 + year as u16 } else { 1900 + year as u16 };
+        let rest = format!("{} {} {} {}", day, month, year, time);
+        PrimitiveDateTime::parse(&rest, &RFC_850_REST).ok()
+    };
+
+    PrimitiveDateTime::parse(value, &IMF_FIXDATE).ok()
+        .or_else(rfc_850)
+        .or_else(|| PrimitiveDateTime::parse(value, &ASCTIME).ok())
+        .map(|date| date.assume_utc())
+}
+
+/// The result of evaluating a [`ConditionalRequest`]'s preconditions.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum Precondition {
+    /// All preconditions passed, or there were none: the request should be
+    /// handled normally. Corresponds to `200 OK`.
+    Proceed,
+    /// The client's cached representation is current. Corresponds to `304 Not
+    /// Modified`.
+    NotModified,
+    /// A precondition failed. Corresponds to `412 Precondition Failed`.
+    Failed,
+}
+
+impl Precondition {
+    /// Returns the status the response should have for this outcome.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::http::Status;
+    /// use rocket::request::Precondition;
+    ///
+    /// assert_eq!(Precondition::Proceed.status(), Status::Ok);
+    /// assert_eq!(Precondition::NotModified.status(), Status::NotModified);
+    /// assert_eq!(Precondition::Failed.status(), Status::PreconditionFailed);
+    /// ```
+    pub fn status(self) -> Status {
+        match self {
+            Precondition::Proceed => Status::Ok,
+            Precondition::NotModified => Status::NotModified,
+            Precondition::Failed => Status::PreconditionFailed,
+        }
+    }
+}
+
+/// The validator in an `If-Range` header: either an entity tag or a date.
+///
+/// The two are told apart by syntax: an entity tag starts with `"` or `W/`,
+/// while anything else is parsed as an HTTP-date.
+///
+/// # Example
+///
+/// ```rust
+/// use rocket::request::{EntityTag, IfRange};
+///
+/// let tag: IfRange = "\"v1\"".parse().unwrap();
+/// assert_eq!(tag, IfRange::Tag(EntityTag::strong("v1").unwrap()));
+///
+/// let date: IfRange = "Sun, 06 Nov 1994 08:49:37 GMT".parse().unwrap();
+/// assert!(matches!(date, IfRange::Date(_)));
+///
+/// assert!("v1".parse::<IfRange>().is_err());
+/// assert!("\"v1\", \"v2\"".parse::<IfRange>().is_err());
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum IfRange {
+    /// An entity tag, compared using the strong comparison function.
+    Tag(EntityTag),
+    /// A last modification date, which must match exactly.
+    Date(OffsetDateTime),
+}
+
+impl IfRange {
+    /// Returns `true` if the validator matches the current state of the
+    /// requested resource: its entity tag `etag` and its last modification
+    /// date `last_modified`.
+    ///
+    /// As required by [RFC 7233 § 3.2], entity tags are compared with the
+    /// strong comparison function, so a weak tag never matches, and a date
+    /// matches only if it is identical to `last_modified`, to the second.
+    ///
+    /// [RFC 7233 § 3.2]: https://datatracker.ietf.org/doc/html/rfc7233#section-3.2
+    pub fn matches(&self, etag: Option<&EntityTag>, last_modified: Option<OffsetDateTime>) -> bool {
+        match self {
+            IfRange::Tag(tag) => etag.map_or(false, |etag| tag.strong_eq(etag)),
+            IfRange::Date(date) => last_modified
+                .map_or(false, |modified| modified.unix_timestamp() == date.unix_timestamp()),
+        }
+    }
+}
+
+impl FromStr for IfRange {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let value = s.trim_matches(&[' ', '\t'][..]);
+        if value.starts_with('"') || value.starts_with("W/") {
+            value.parse().map(IfRange::Tag).map_err(|_| s.into())
+        } else {
+            parse_http_date(value).map(IfRange::Date).ok_or_else(|| s.into())
+        }
+    }
+}
+
+/// Whether to honor a request's `Range` header, as decided by its `If-Range`
+/// header. Returned by [`ConditionalRequest::range()`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum RangeCondition {
+    /// There is no `If-Range` header, or its validator matches: the requested
+    /// range can be served. Corresponds to `206 Partial Content`.
+    Partial,
+    /// The `If-Range` validator doesn't match or is malformed: the range must
+    /// be ignored and the full resource served. Corresponds to `200 OK`.
+    Full,
+}
+
+impl RangeCondition {
+    /// Returns the status the response should have for this outcome, assuming
+    /// the request has a satisfiable `Range` header.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::http::Status;
+    /// use rocket::request::RangeCondition;
+    ///
+    /// assert_eq!(RangeCondition::Partial.status(), Status::PartialContent);
+    /// assert_eq!(RangeCondition::Full.status(), Status::Ok);
+    /// ```
+    pub fn status(self) -> Status {
+        match self {
+            RangeCondition::Partial => Status::PartialContent,
+            RangeCondition::Full => Status::Ok,
+        }
+    }
+}
+
+/// A request guard for conditional requests.
+///
+/// `ConditionalRequest` parses a request's `If-Match`, `If-None-Match`,
+/// `If-Modified-Since`, `If-Unmodified-Since`, and `If-Range` headers. Given
+/// the current entity tag and last modification date of the requested
+/// resource, [`ConditionalRequest::matches()`] evaluates the preconditions as
+/// described in [RFC 7232 § 6] and returns whether the handler should respond
+/// normally, with `304 Not Modified`, or with `412 Precondition Failed`.
+/// [`ConditionalRequest::range()`] separately evaluates `If-Range` to decide
+/// whether a [`RangeHeader`] should be honored.
+///
+/// Malformed headers are ignored as if they were not present, as are date
+/// headers that appear more than once. Multiple `If-Match` or `If-None-Match`
+/// headers are combined into a single list. The exception is `If-Range`: a
+/// malformed or repeated `If-Range` header never matches, so that a client
+/// can't be sent a range of a representation it doesn't have.
+///
+/// [RFC 7232 § 6]: https://datatracker.ietf.org/doc/html/rfc7232#section-6
+///
+/// # Example
+///
+/// ```rust
+/// # #[macro_use] extern crate rocket;
+/// use rocket::http::Status;
+/// use rocket::request::{ConditionalRequest, EntityTag, Precondition};
+///
+/// #[get("/resource")]
+/// fn resource(conditions: ConditionalRequest) -> (Status, &'static str) {
+///     let etag = EntityTag::strong("v2").unwrap();
+///     match conditions.matches(Some(&etag), None) {
+///         Precondition::Proceed => (Status::Ok, "the resource"),
+///         outcome => (outcome.status(), ""),
+///     }
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConditionalRequest {
+    method: Method,
+    if_match: Option<EntityTags>,
+    if_none_match: Option<EntityTags>,
+    if_modified_since: Option<OffsetDateTime>,
+    if_unmodified_since: Option<OffsetDateTime>,
+    if_range: Option<Result<IfRange, String>>,
+}
+
+impl ConditionalRequest {
+    fn new(request: &Request<'_>) -> ConditionalRequest {
+        let headers = request.headers();
+        let etags = |name| EntityTags::parse(&headers.get(name).collect::<Vec<_>>().join(","));
+        let date = |name| {
+            let mut values = headers.get(name);
+            match (values.next(), values.next()) {
+                (Some(value), None) => parse_http_date(value.trim()),
+                _ => None,
+            }
+        };
+
+        let mut if_ranges = headers.get("If-Range");
+        let if_range = match (if_ranges.next(), if_ranges.next()) {
+            (Some(value), None) => Some(value.parse()),
+            (Some(value), Some(_)) => Some(Err(value.into())),
+            (None, _) => None,
+        };
+
+        ConditionalRequest {
+            method: request.method(),
+            if_match: etags("If-Match"),
+            if_none_match: etags("If-None-Match"),
+            if_modified_since: date("If-Modified-Since"),
+            if_unmodified_since: date("If-Unmodified-Since"),
+            if_range,
+        }
+    }
+
+    /// Returns the parsed `If-Match` header, if there is a valid one.
+    pub fn if_match(&self) -> Option<&EntityTags> {
+        self.if_match.as_ref()
+    }
+
+    /// Returns the parsed `If-None-Match` header, if there is a valid one.
+    pub fn if_none_match(&self) -> Option<&EntityTags> {
+        self.if_none_match.as_ref()
+    }
+
+    /// Returns the parsed `If-Modified-Since` header, if there is a valid one.
+    pub fn if_modified_since(&self) -> Option<OffsetDateTime> {
+        self.if_modified_since
+    }
+
+    /// Returns the parsed `If-Unmodified-Since` header, if there is a valid
+    /// one.
+    pub fn if_unmodified_since(&self) -> Option<OffsetDateTime> {
+        self.if_unmodified_since
+    }
+
+    /// Returns the parsed `If-Range` header, if there is a single valid one.
+    pub fn if_range(&self) -> Option<&IfRange> {
+        self.if_range.as_ref().and_then(|if_range| if_range.as_ref().ok())
+    }
+
+    /// Returns `true` if the request has at least one valid precondition.
+    pub fn is_conditional(&self) -> bool {
+        self.if_match.is_some()
+            || self.if_none_match.is_some()
+            || self.if_modified_since.is_some()
+            || self.if_unmodified_since.is_some()
+    }
+
+    /// Evaluates the request's preconditions against the current state of the
+    /// requested resource: its entity tag `etag` and its last modification
+    /// date `last_modified`, if it has either. The resource is assumed to
+    /// exist, so a wildcard `*` always matches.
+    ///
+    /// Preconditions are evaluated in the order defined by RFC 7232:
+    ///
+    ///   1. If `If-Match` is present and doesn't strongly match `etag`,
+    ///      returns [`Precondition::Failed`].
+    ///   2. Otherwise, if `If-Match` is absent, `If-Unmodified-Since` is
+    ///      present, and the resource was modified after that date, returns
+    ///      [`Precondition::Failed`].
+    ///   3. If `If-None-Match` is present and weakly matches `etag`, returns
+    ///      [`Precondition::NotModified`] for `GET` and `HEAD` requests, and
+    ///      [`Precondition::Failed`] otherwise.
+    ///   4. Otherwise, if `If-None-Match` is absent, the request is a `GET` or
+    ///      `HEAD` request, `If-Modified-Since` is present, and the resource
+    ///      was not modified after that date, returns
+    ///      [`Precondition::NotModified`].
+    ///   5. Otherwise, returns [`Precondition::Proceed`].
+    ///
+    /// Dates are compared with a resolution of one second. Date preconditions
+    /// are ignored when `last_modified` is `None`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # #[macro_use] extern crate rocket;
+    /// use std::time::SystemTime;
+    ///
+    /// use rocket::http::Status;
+    /// use rocket::request::{ConditionalRequest, EntityTag, Precondition};
+    ///
+    /// #[put("/document")]
+    /// fn update(conditions: ConditionalRequest) -> (Status, &'static str) {
+    ///     let etag = EntityTag::strong("v7").unwrap();
+    ///     let modified = SystemTime::UNIX_EPOCH.into();
+    ///     match conditions.matches(Some(&etag), Some(modified)) {
+    ///         Precondition::Proceed => (Status::Ok, "updated"),
+    ///         outcome => (outcome.status(), ""),
+    ///     }
+    /// }
+    /// ```
+    pub fn matches(
+        &self,
+        etag: Option<&EntityTag>,
+        last_modified: Option<OffsetDateTime>
+    ) -> Precondition {
+        let any = |tags: &EntityTags| *tags == EntityTags::Any;
+        let strong_match = |tags| etag.map_or(any(tags), |etag| tags.strong_match(etag));
+        let weak_match = |tags| etag.map_or(any(tags), |etag| tags.weak_match(etag));
+
+        let modified_after = |date: OffsetDateTime| {
+            last_modified.map(|modified| modified.unix_timestamp() > date.unix_timestamp())
+        };
+
+        match (&self.if_match, self.if_unmodified_since) {
+            (Some(tags), _) if !strong_match(tags) => return Precondition::Failed,
+            (None, Some(date)) if modified_after(date) == Some(true) => {
+                return Precondition::Failed;
+            }
+            _ => {}
+        }
+
+        let safe = matches!(self.method, Method::Get | Method::Head);
+        match (&self.if_none_match, self.if_modified_since) {
+            (Some(tags), _) if weak_match(tags) && safe => Precondition::NotModified,
+            (Some(tags), _) if weak_match(tags) => Precondition::Failed,
+            (None, Some(date)) if safe && modified_after(date) == Some(false) => {
+                Precondition::NotModified
+            }
+            _ => Precondition::Proceed,
+        }
+    }
+
+    /// Evaluates the request's `If-Range` header against the current state of
+    /// the requested resource: its entity tag `etag` and its last modification
+    /// date `last_modified`, if it has either.
+    ///
+    /// Returns [`RangeCondition::Partial`] if there is no `If-Range` header or
+    /// if its validator matches as described in [`IfRange::matches()`].
+    /// Otherwise, including when the header is malformed or repeated, returns
+    /// [`RangeCondition::Full`], and the handler should ignore the `Range`
+    /// header and respond with the full resource. `If-Range` is only
+    /// meaningful alongside a `Range` header; this method doesn't check for
+    /// one.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # #[macro_use] extern crate rocket;
+    /// use rocket::request::{ConditionalRequest, EntityTag, RangeCondition, RangeHeader};
+    /// use rocket::http::Status;
+    ///
+    /// const FILE: &[u8] = b"0123456789";
+    ///
+    /// #[get("/file")]
+    /// fn file(range: Option<RangeHeader>, conditions: ConditionalRequest) -> (Status, Vec<u8>) {
+    ///     let etag = EntityTag::strong("v3").unwrap();
+    ///     let honored = conditions.range(Some(&etag), None) == RangeCondition::Partial;
+    ///     let range = range.filter(|_| honored);
+    ///     match range.map(|r| r.resolve(FILE.len() as u64)) {
+    ///         Some(Ok(ranges)) => {
+    ///             let (start, end) = ranges[0];
+    ///             (Status::PartialContent, FILE[start as usize..=end as usize].to_vec())
+    ///         }
+    ///         Some(Err(_)) => (Status::RangeNotSatisfiable, vec![]),
+    ///         None => (Status::Ok, FILE.to_vec()),
+    ///     }
+    /// }
+    /// ```
+    pub fn range(
+        &self,
+        etag: Option<&EntityTag>,
+        last_modified: Option<OffsetDateTime>
+    ) -> RangeCondition {
+        match &self.if_range {
+            None => RangeCondition::Partial,
+            Some(Ok(if_range)) if if_range.matches(etag, last_modified) => RangeCondition::Partial,
+            Some(_) => RangeCondition::Full,
+        }
+    }
+}
+
+#[crate::async_trait]
+impl<'r> FromRequest<'r> for ConditionalRequest {
+    type Error = std::convert::Infallible;
+
+    async fn from_request(request: &'r Request<'_>) -> Outcome<Self, Self::Error> {
+        Success(ConditionalRequest::new(request))
+    }
+}
+
+/// A single `byte-range-spec` or `suffix-byte-range-spec` in a `Range` header.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ByteRangeSpec {
+    /// `first-last`: the bytes from `first` to `last`, inclusive.
+    FromTo(u64, u64),
+    /// `first-`: the bytes from `first` to the end of the resource.
+    From(u64),
+    /// `-length`: the last `length` bytes of the resource.
+    Suffix(u64),
+}
+
+/// A request guard for the `Range` header with the `bytes` unit.
+///
+/// The guard forwards if the request has no `Range` header. As [RFC 7233]
+/// requires that a server ignore a `Range` header it doesn't understand, the
+/// guard also forwards if the header is malformed or uses a unit other than
+/// `bytes`. Use `Option<RangeHeader>` to serve the full resource in either
+/// case.
+///
+/// The range specifiers are retained exactly as requested and can be
+/// inspected with [`RangeHeader::specs()`]. Because the guard doesn't know the
+/// length of the resource being requested, the handler converts the
+/// specifiers into concrete byte ranges with [`RangeHeader::resolve()`].
+/// Resolution either returns the normalized, satisfiable ranges or an
+/// [`Unsatisfiable`] error, in which case the handler should respond with
+/// `416 Range Not Satisfiable`. When more than one range is returned, the
+/// handler decides whether to respond with a `multipart/byteranges` body or,
+/// for instance, only the first range.
+///
+/// [RFC 7233]: https://datatracker.ietf.org/doc/html/rfc7233#section-3.1
+///
+/// # Example
+///
+/// ```rust
+/// # #[macro_use] extern crate rocket;
+/// use rocket::request::RangeHeader;
+/// use rocket::http::{Status, Header};
+///
+/// const VIDEO: &[u8] = b"0123456789";
+///
+/// #[derive(Responder)]
+/// enum Partial {
+///     #[response(status = 206)]
+///     Content(&'static [u8], Header<'static>),
+///     #[response(status = 416)]
+///     Unsatisfiable((), Header<'static>),
+///     #[response(status = 200)]
+///     Full(&'static [u8]),
+/// }
+///
+/// #[get("/video")]
+/// fn video(range: Option<RangeHeader>) -> Partial {
+///     let len = VIDEO.len() as u64;
+///     let ranges = match range.map(|r| r.resolve(len)) {
+///         Some(Ok(ranges)) => ranges,
+///         Some(Err(e)) => {
+///             return Partial::Unsatisfiable((), Header::new("Content-Range", e.content_range()));
+///         }
+///         None => return Partial::Full(VIDEO),
+///     };
+///
+///     // Only send the first range.
+///     let (start, end) = ranges[0];
+///     let content_range = format!("bytes {}-{}/{}", start, end, len);
+///     Partial::Content(&VIDEO[start as usize..=end as usize],
+///         Header::new("Content-Range", content_range))
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RangeHeader(Vec<ByteRangeSpec>);
+
+/// Error returned by [`RangeHeader::resolve()`] when none of the requested
+/// ranges overlap the resource.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Unsatisfiable {
+    /// The length of the resource.
+    pub len: u64,
+}
+
+impl RangeHeader {
+    /// Parses the value of a `Range` header. Returns `None` if the value is
+    /// malformed or its unit isn't `bytes`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::request::{RangeHeader, ByteRangeSpec::*};
+    ///
+    /// let range = RangeHeader::parse("bytes=0-99, 500-, -20").unwrap();
+    /// assert_eq!(range.specs(), &[FromTo(0, 99), From(500), Suffix(20)]);
+    ///
+    /// assert!(RangeHeader::parse("bytes=10-5").is_none());
+    /// assert!(RangeHeader::parse("items=0-5").is_none());
+    /// assert!(RangeHeader::parse("bytes=").is_none());
+    /// ```
+    pub fn parse(value: &str) -> Option<RangeHeader> {
+        fn number(s: &str) -> Option<u64> {
+            match s.bytes().all(|b| b.is_ascii_digit()) {
+                true => s.parse().ok(),
+                false => None,
+            }
+        }
+
+        let (unit, ranges) = value.trim().split_once('=')?;
+        if !unit.eq_ignore_ascii_case("bytes") {
+            return None;
+        }
+
+        let mut specs = vec![];
+        for spec in ranges.split(',').map(|s| s.trim()).filter(|s| !s.is_empty()) {
+            let spec = match spec.split_once('-')? {
+                ("", suffix) => ByteRangeSpec::Suffix(number(suffix)?),
+                (first, "") => ByteRangeSpec::From(number(first)?),
+                (first, last) => match (number(first)?, number(last)?) {
+                    (first, last) if first <= last => ByteRangeSpec::FromTo(first, last),
+                    _ => return None,
+                }
+            };
+
+            specs.push(spec);
+        }
+
+        (!specs.is_empty()).then(|| RangeHeader(specs))
+    }
+
+    /// Returns the range specifiers in the order they were requested.
+    pub fn specs(&self) -> &[ByteRangeSpec] {
+        &self.0
+    }
+
+    /// Resolves the requested ranges against a resource that is `len` bytes
+    /// long, returning a list of inclusive `(start, end)` byte ranges.
+    ///
+    /// Ranges are clamped to the end of the resource, and suffix ranges
+    /// longer than the resource select the entire resource. Ranges that begin
+    /// at or after the end of the resource are dropped. The remaining ranges
+    /// are sorted by starting byte, and overlapping or adjacent ranges are
+    /// coalesced, so the returned ranges are disjoint.
+    ///
+    /// Returns [`Unsatisfiable`] if no range remains, which is always the
+    /// case when `len` is `0`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::request::{RangeHeader, Unsatisfiable};
+    ///
+    /// let range = RangeHeader::parse("bytes=-500").unwrap();
+    /// assert_eq!(range.resolve(10000), Ok(vec![(9500, 9999)]));
+    /// assert_eq!(range.resolve(100), Ok(vec![(0, 99)]));
+    ///
+    /// let range = RangeHeader::parse("bytes=50-99,0-9,90-120,9000-").unwrap();
+    /// assert_eq!(range.resolve(1000), Ok(vec![(0, 9), (50, 120)]));
+    ///
+    /// let range = RangeHeader::parse("bytes=1000-").unwrap();
+    /// assert_eq!(range.resolve(1000), Err(Unsatisfiable { len: 1000 }));
+    /// ```
+    pub fn resolve(&self, len: u64) -> Result<Vec<(u64, u64)>, Unsatisfiable> {
+        let mut ranges: Vec<(u64, u64)> = self.0.iter()
+            .filter_map(|spec| match *spec {
+                ByteRangeSpec::FromTo(first, last) if first < len => {
+                    Some((first, last.min(len - 1)))
+                }
+                ByteRangeSpec::From(first) if first < len => Some((first, len - 1)),
+                ByteRangeSpec::Suffix(n) if n > 0 && len > 0 => {
+                    Some((len.saturating_sub(n), len - 1))
+                }
+                _ => None,
+            })
+            .collect();
+
+        ranges.sort_unstable();
+        let mut coalesced: Vec<(u64, u64)> = Vec::with_capacity(ranges.len());
+        for (start, end) in ranges {
+            match coalesced.last_mut() {
+                Some(last) if start <= last.1.saturating_add(1) => last.1 = last.1.max(end),
+                _ => coalesced.push((start, end)),
+            }
+        }
+
+        match coalesced.is_empty() {
+            true => Err(Unsatisfiable { len }),
+            false => Ok(coalesced),
+        }
+    }
+}
+
+impl Unsatisfiable {
+    /// Returns the value of the `Content-Range` header to send with a `416
+    /// Range Not Satisfiable` response: `bytes */{len}`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::request::Unsatisfiable;
+    ///
+    /// assert_eq!(Unsatisfiable { len: 1000 }.content_range(), "bytes */1000");
+    /// ```
+    pub fn content_range(&self) -> String {
+        format!("bytes */{}", self.len)
+    }
+}
+
+impl fmt::Display for Unsatisfiable {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "no requested range overlaps the {} byte resource", self.len)
+    }
+}
+
+impl std::error::Error for Unsatisfiable { }
+
+#[crate::async_trait]
+impl<'r> FromRequest<'r> for RangeHeader {
+    type Error = std::convert::Infallible;
+
+    async fn from_request(request: &'r Request<'_>) -> Outcome<Self, Self::Error> {
+        match request.headers().get_one("Range").and_then(RangeHeader::parse) {
+            Some(range) => Success(range),
+            None => Forward(())
+        }
+    }
+}
+
+/// A request guard for HTTP Basic authentication credentials, as sent in an
+/// `Authorization: Basic ...` header.
+///
+/// The credentials are base64-decoded and split on the _first_ `:`, so a
+/// username can't contain a `:` but a password can. The guard:
+///
+///   * forwards if there is no `Authorization` header or if the header's
+///     scheme isn't `Basic`, compared case-insensitively, so that routes for
+///     anonymous users or other schemes can coexist;
+///   * fails with `400 Bad Request` and an [`AuthError`] if the credentials
+///     aren't valid base64, aren't valid UTF-8, or contain no `:`;
+///   * succeeds with the username and password otherwise.
+///
+/// The `Debug` implementation doesn't display the password.
+///
+/// # Example
+///
+/// ```rust
+/// # #[macro_use] extern crate rocket;
+/// use rocket::request::BasicAuth;
+///
+/// #[get("/")]
+/// fn index(auth: BasicAuth) -> String {
+///     format!("Hello, {}!", auth.username)
+/// }
+///
+/// #[get("/", rank = 2)]
+/// fn anonymous() -> &'static str {
+///     "Hello, stranger!"
+/// }
+/// ```
+#[derive(Clone, PartialEq, Eq)]
+pub struct BasicAuth {
+    /// The username: the credentials before the first `:`.
+    pub username: String,
+    /// The password: the credentials after the first `:`.
+    pub password: String,
+}
+
+/// A request guard for a bearer token, as sent in an `Authorization: Bearer
+/// ...` header.
+///
+/// The scheme is matched case-insensitively and must be followed by one or
+/// more spaces and a `token68`: a non-empty sequence of ASCII letters, digits,
+/// and any of `-._~+/`, optionally followed by `=` padding. The guard:
+///
+///   * forwards if there is no `Authorization` header or if the header's
+///     scheme isn't `Bearer`;
+///   * fails with `400 Bad Request` and [`AuthError::InvalidToken`] if the
+///     token is missing or malformed;
+///   * succeeds with the token otherwise.
+///
+/// # Example
+///
+/// ```rust
+/// # #[macro_use] extern crate rocket;
+/// use rocket::request::BearerToken;
+///
+/// #[get("/api")]
+/// fn api(token: BearerToken) -> String {
+///     format!("token: {}", token.0)
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BearerToken(pub String);
+
+/// Error returned by the [`BasicAuth`] and [`BearerToken`] guards when the
+/// `Authorization` header uses the guard's scheme but is malformed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AuthError {
+    /// The Basic credentials aren't valid base64.
+    InvalidBase64,
+    /// The decoded Basic credentials aren't valid UTF-8.
+    InvalidUtf8,
+    /// The decoded Basic credentials don't contain a `:`.
+    MissingColon,
+    /// The bearer token is missing or isn't a valid `token68`.
+    InvalidToken,
+}
+
+/// Returns the credentials in `request`'s `Authorization` header if the
+/// header's scheme is `scheme`, compared case-insensitively.
+fn authorization<'r>(request: &'r Request<'_>, scheme: &str) -> Option<&'r str> {
+    let value = request.headers().get_one("Authorization")?.trim();
+    let (header_scheme, credentials) = value.split_once(' ').unwrap_or((value, ""));
+    match header_scheme.eq_ignore_ascii_case(scheme) {
+        true => Some(credentials.trim_start_matches(' ')),
+        false => None,
+    }
+}
+
+impl BasicAuth {
+    /// Parses base64-encoded Basic `credentials`, without the `Basic ` scheme
+    /// prefix.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::request::{BasicAuth, AuthError};
+    ///
+    /// let auth = BasicAuth::parse("QWxhZGRpbjpvcGVuIHNlc2FtZQ==").unwrap();
+    /// assert_eq!(auth.username, "Aladdin");
+    /// assert_eq!(auth.password, "open sesame");
+    ///
+    /// assert_eq!(BasicAuth::parse("QWxhZGRpbg=="), Err(AuthError::MissingColon));
+    /// assert_eq!(BasicAuth::parse("not base64!"), Err(AuthError::InvalidBase64));
+    /// ```
+    pub fn parse(credentials: &str) -> Result<BasicAuth, AuthError> {
+        let mut buf = vec![0; credentials.len() / 4 * 3];
+        let decoded = binascii::b64decode(credentials.as_bytes(), &mut buf)
+            .map_err(|_| AuthError::InvalidBase64)?;
+
+        let decoded = std::str::from_utf8(decoded).map_err(|_| AuthError::InvalidUtf8)?;
+        let (username, password) = decoded.split_once(':').ok_or(AuthError::MissingColon)?;
+        Ok(BasicAuth { username: username.into(), password: password.into() })
+    }
+}
+
+impl Debug for BasicAuth {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("BasicAuth")
+            .field("username", &self.username)
+            .field("password", &"<redacted>")
+            .finish()
+    }
+}
+
+impl BearerToken {
+    /// Parses a bearer `token`, without the `Bearer ` scheme prefix.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::request::{BearerToken, AuthError};
+    ///
+    /// assert_eq!(BearerToken::parse("mF_9.B5f-4.1JqM"), Ok(BearerToken("mF_9.B5f-4.1JqM".into())));
+    /// assert_eq!(BearerToken::parse("abc=="), Ok(BearerToken("abc==".into())));
+    ///
+    /// assert_eq!(BearerToken::parse(""), Err(AuthError::InvalidToken));
+    /// assert_eq!(BearerToken::parse("a b"), Err(AuthError::InvalidToken));
+    /// assert_eq!(BearerToken::parse("a=b"), Err(AuthError::InvalidToken));
+    /// ```
+    pub fn parse(token: &str) -> Result<BearerToken, AuthError> {
+        let valid = |b: u8| b.is_ascii_alphanumeric() || b"-._~+/".contains(&b);
+        let body = token.trim_end_matches('=');
+        match !body.is_empty() && body.bytes().all(valid) {
+            true => Ok(BearerToken(token.into())),
+            false => Err(AuthError::InvalidToken),
+        }
+    }
+}
+
+impl fmt::Display for AuthError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AuthError::InvalidBase64 => f.write_str("credentials are not valid base64"),
+            AuthError::InvalidUtf8 => f.write_str("credentials are not valid UTF-8"),
+            AuthError::MissingColon => f.write_str("credentials are missing a `:`"),
+            AuthError::InvalidToken => f.write_str("bearer token is missing or malformed"),
+        }
+    }
+}
+
+impl std::error::Error for AuthError { }
+
+#[crate::async_trait]
+impl<'r> FromRequest<'r> for BasicAuth {
+    type Error = AuthError;
+
+    async fn from_request(request: &'r Request<'_>) -> Outcome<Self, Self::Error> {
+        match authorization(request, "Basic").map(BasicAuth::parse) {
+            Some(Ok(auth)) => Success(auth),
+            Some(Err(e)) => Failure((Status::BadRequest, e)),
+            None => Forward(())
+        }
+    }
+}
+
+#[crate::async_trait]
+impl<'r> FromRequest<'r> for BearerToken {
+    type Error = AuthError;
+
+    async fn from_request(request: &'r Request<'_>) -> Outcome<Self, Self::Error> {
+        match authorization(request, "Bearer").map(BearerToken::parse) {
+            Some(Ok(token)) => Success(token),
+            Some(Err(e)) => Failure((Status::BadRequest, e)),
+            None => Forward(())
+        }
+    }
+}
+
+/// A request guard for the request's query, parsed into key/value pairs and
+/// sorted into a canonical order.
+///
+/// Keys and values are percent-decoded with `+` decoded as a space. Pairs are
+/// sorted by key, then by value, and repeated keys are kept, so `SortedQuery`
+/// is a multimap. Empty segments, as in `?a&&b`, are skipped. A key with no
+/// `=` has no value, which is distinct from a key with an empty value: `?flag`
+/// has the pair `("flag", None)` while `?flag=` has `("flag", Some(""))`.
+///
+/// The canonical form, rendered by the `Display` implementation and by
+/// [`SortedQuery::to_canonical_string()`], percent-encodes each key and value
+/// and joins the sorted pairs with `&`. Two queries with the same pairs in any
+/// order or encoding have the same canonical form, and parsing a canonical
+/// form yields the same `SortedQuery`, making it suitable for cache keys and
+/// signatures.
+///
+/// This guard always succeeds. A request without a query yields an empty
+/// `SortedQuery`.
+///
+/// # Example
+///
+/// ```rust
+/// # #[macro_use] extern crate rocket;
+/// use rocket::request::SortedQuery;
+///
+/// #[get("/search")]
+/// fn search(query: SortedQuery) -> String {
+///     format!("cache key: /search?{}", query)
+/// }
+///
+/// # use rocket::local::blocking::Client;
+/// # let client = Client::debug_with(routes![search]).unwrap();
+/// # let response = client.get("/search?q=a+b&lang=en&q=c").dispatch();
+/// # assert_eq!(response.into_string().unwrap(), "cache key: /search?lang=en&q=a%20b&q=c");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+pub struct SortedQuery(Vec<(String, Option<String>)>);
+
+impl SortedQuery {
+    /// Parses and sorts the raw, undecoded `query`, without the leading `?`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::request::SortedQuery;
+    ///
+    /// let query = SortedQuery::parse("b=2&a=&flag&b=1&c=x+y%21");
+    /// let pairs: Vec<_> = query.iter().collect();
+    /// assert_eq!(pairs, [
+    ///     ("a", Some("")),
+    ///     ("b", Some("1")),
+    ///     ("b", Some("2")),
+    ///     ("c", Some("x y!")),
+    ///     ("flag", None),
+    /// ]);
+    /// ```
+    pub fn parse(query: &str) -> SortedQuery {
+        let decode = |s: &str| crate::http::RawStr::new(s).url_decode_lossy().into_owned();
+        let mut pairs: Vec<_> = query.split('&')
+            .filter(|segment| !segment.is_empty())
+            .map(|segment| match segment.split_once('=') {
+                Some((key, value)) => (decode(key), Some(decode(value))),
+                None => (decode(segment), None),
+            })
+            .collect();
+
+        pairs.sort();
+        SortedQuery(pairs)
+    }
+
+    /// Returns an iterator over the sorted key/value pairs.
+    #[inline]
+    pub fn iter(&self) -> impl Iterator<Item = (&str, Option<&str>)> {
+        self.0.iter().map(|(k, v)| (k.as_str(), v.as_deref()))
+    }
+
+    /// Returns an iterator over the values for `key`, in sorted order. A key
+    /// without a value yields `None`, ahead of any values.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::request::SortedQuery;
+    ///
+    /// let query = SortedQuery::parse("tag=b&tag&tag=a&other=1");
+    /// let tags: Vec<_> = query.get_all("tag").collect();
+    /// assert_eq!(tags, [None, Some("a"), Some("b")]);
+    /// assert_eq!(query.get_all("missing").count(), 0);
+    /// ```
+    pub fn get_all<'a>(&'a self, key: &str) -> impl Iterator<Item = Option<&'a str>> + 'a {
+        let start = self.0.partition_point(|(k, _)| k.as_str() < key);
+        let end = start + self.0[start..].partition_point(|(k, _)| k.as_str() == key);
+        self.0[start..end].iter().map(|(_, v)| v.as_deref())
+    }
+
+    /// Returns `true` if there is at least one pair with `key`.
+    #[inline]
+    pub fn contains_key(&self, key: &str) -> bool {
+        self.get_all(key).next().is_some()
+    }
+
+    /// Returns the number of pairs.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Returns `true` if there are no pairs.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Renders the canonical form of the query, without a leading `?`. This is
+    /// identical to the `Display` implementation.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::request::SortedQuery;
+    ///
+    /// let query = SortedQuery::parse("z=1&a=b+c&&flag&a=%26");
+    /// assert_eq!(query.to_canonical_string(), "a=%26&a=b%20c&flag&z=1");
+    /// assert_eq!(SortedQuery::parse(&query.to_canonical_string()), query);
+    /// ```
+    #[inline]
+    pub fn to_canonical_string(&self) -> String {
+        self.to_string()
+    }
+}
+
+impl fmt::Display for SortedQuery {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        use crate::http::RawStr;
+
+        for (i, (key, value)) in self.0.iter().enumerate() {
+            if i != 0 { f.write_str("&")?; }
+            write!(f, "{}", RawStr::new(key).percent_encode())?;
+            if let Some(value) = value {
+                write!(f, "={}", RawStr::new(value).percent_encode())?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[crate::async_trait]
+impl<'r> FromRequest<'r> for SortedQuery {
+    type Error = std::convert::Infallible;
+
+    async fn from_request(request: &'r Request<'_>) -> Outcome<Self, Self::Error> {
+        let query = request.uri().query().map(|q| q.as_str()).unwrap_or("");
+        Success(SortedQuery::parse(query))
+    }
+}
+
+/// A request guard for the request's query exactly as received.
+///
+/// `RawQuery` yields the query of the request URI verbatim: the substring
+/// after the `?` and before any `#`, without percent-decoding, `+` decoding,
+/// or reordering. It is intended for verifying signatures computed over the
+/// literal query string, as in some OAuth callbacks. For a canonical form that
+/// is independent of the order and encoding of the pairs, use
+/// [`SortedQuery`].
+///
+/// This guard always succeeds. A request without a query yields an empty
+/// string, as does a request with an empty query, like `/callback?`; the two
+/// can be distinguished with [`RawQuery::is_present()`].
+///
+/// # Example
+///
+/// ```rust
+/// # #[macro_use] extern crate rocket;
+/// use rocket::request::RawQuery;
+///
+/// # fn hmac(s: &str) -> String { s.into() }
+/// #[get("/callback")]
+/// fn callback(query: RawQuery<'_>) -> String {
+///     hmac(query.as_str())
+/// }
+///
+/// # use rocket::local::blocking::Client;
+/// # let client = Client::debug_with(routes![callback]).unwrap();
+/// # let response = client.get("/callback?b=2&a=x+y%21").dispatch();
+/// # assert_eq!(response.into_string().unwrap(), "b=2&a=x+y%21");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct RawQuery<'r>(Option<&'r str>);
+
+impl<'r> RawQuery<'r> {
+    /// Returns the raw query, without the leading `?`. Returns an empty string
+    /// if there is no query.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::request::{RawQuery, FromRequest};
+    ///
+    /// # let client = rocket::local::blocking::Client::debug_with(vec![]).unwrap();
+    /// # let raw_query = |uri: &'static str| {
+    /// #     let req = client.get(uri);
+    /// #     let query = rocket::async_test(RawQuery::from_request(&req)).unwrap();
+    /// #     (query.as_str().to_string(), query.is_present())
+    /// # };
+    /// assert_eq!(raw_query("/?a=1&b=%20"), ("a=1&b=%20".into(), true));
+    /// assert_eq!(raw_query("/?"), ("".into(), true));
+    /// assert_eq!(raw_query("/"), ("".into(), false));
+    /// ```
+    #[inline(always)]
+    pub fn as_str(&self) -> &'r str {
+        self.0.unwrap_or("")
+    }
+
+    /// Returns `true` if the request URI has a query, even an empty one.
+    #[inline(always)]
+    pub fn is_present(&self) -> bool {
+        self.0.is_some()
+    }
+}
+
+impl std::ops::Deref for RawQuery<'_> {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl fmt::Display for RawQuery<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+#[crate::async_trait]
+impl<'r> FromRequest<'r> for RawQuery<'r> {
+    type Error = std::convert::Infallible;
+
+    async fn from_request(request: &'r Request<'_>) -> Outcome<Self, Self::Error> {
+        let query = request.uri().query().map(|q| q.as_str());
+        Success(RawQuery(query.map(|q| q.split('#').next().unwrap_or(q))))
+    }
+}
+
+/// A request guard that runs the request guard `G` with a deadline of `MS`
+/// milliseconds.
+///
+/// If `G` completes in time, its outcome is returned as-is, with a successful
+/// value wrapped in `WithTimeout` and an error wrapped in
+/// [`TimeoutError::Guard`]. Otherwise, `G`'s future is dropped, cancelling it
+/// at its current `.await` point, and the guard fails with status `504
+/// Gateway Timeout` and [`TimeoutError::Elapsed`]. To forward instead, or to
+/// handle a timeout in the route, request an `Option` or a `Result`.
+///
+/// The deadline is driven by the async runtime's timer, so `G` must yield to
+/// the runtime while waiting for a slow dependency, as any well-behaved async
+/// code does. Cancellation is clean with respect to the request-local cache:
+/// a value computed by [`Request::local_cache_async()`] is only stored once
+/// its future completes, so a cancelled computation leaves nothing behind and
+/// is simply recomputed by the next guard that requests it.
+///
+/// `WithTimeout` dereferences to the inner guard, which can also be retrieved
+/// with [`WithTimeout::into_inner()`].
+///
+/// # Example
+///
+/// ```rust
+/// # #[macro_use] extern crate rocket;
+/// # use rocket::request::{self, FromRequest, Request};
+/// # struct User;
+/// # #[rocket::async_trait]
+/// # impl<'r> FromRequest<'r> for User {
+/// #     type Error = ();
+/// #     async fn from_request(_: &'r Request<'_>) -> request::Outcome<Self, ()> {
+/// #         request::Outcome::Success(User)
+/// #     }
+/// # }
+/// use rocket::request::WithTimeout;
+///
+/// /// `User` consults a remote session store; give up after 250ms.
+/// #[get("/account")]
+/// fn account(user: WithTimeout<User, 250>) -> &'static str {
+///     let _user: &User = &user;
+///     "account"
+/// }
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct WithTimeout<G, const MS: u64>(pub G);
+
+/// Error returned by the [`WithTimeout`] request guard.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TimeoutError<E> {
+    /// The guard didn't complete within the contained deadline.
+    Elapsed(std::time::Duration),
+    /// The guard completed in time but failed with the contained error.
+    Guard(E),
+}
+
+impl<G, const MS: u64> WithTimeout<G, MS> {
+    /// The deadline for the inner guard.
+    pub const TIMEOUT: std::time::Duration = std::time::Duration::from_millis(MS);
+
+    /// Consumes `self` and returns the inner guard.
+    #[inline(always)]
+    pub fn into_inner(self) -> G {
+        self.0
+    }
+}
+
+impl<G, const MS: u64> std::ops::Deref for WithTimeout<G, MS> {
+    type Target = G;
+
+    #[inline(always)]
+    fn deref(&self) -> &G {
+        &self.0
+    }
+}
+
+impl<E: Debug> fmt::Display for TimeoutError<E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TimeoutError::Elapsed(d) => write!(f, "guard timed out after {}ms", d.as_millis()),
+            TimeoutError::Guard(e) => write!(f, "guard failed: {:?}", e),
+        }
+    }
+}
+
+impl<E: Debug> std::error::Error for TimeoutError<E> { }
+
+#[crate::async_trait]
+impl<'r, G: FromRequest<'r>, const MS: u64> FromRequest<'r> for WithTimeout<G, MS> {
+    type Error = TimeoutError<G::Error>;
+
+    async fn from_request(request: &'r Request<'_>) -> Outcome<Self, Self::Error> {
+        match tokio::time::timeout(Self::TIMEOUT, G::from_request(request)).await {
+            Ok(Success(guard)) => Success(WithTimeout(guard)),
+            Ok(Failure((status, e))) => Failure((status, TimeoutError::Guard(e))),
+            Ok(Forward(())) => Forward(()),
+            Err(_) => {
+                warn_!("`{}` timed out after {}ms.", std::any::type_name::<G>(), MS);
+                Failure((Status::GatewayTimeout, TimeoutError::Elapsed(Self::TIMEOUT)))
+            }
+        }
+    }
+}
+
+/// A request guard for the request's correlation ID.
+///
+/// The ID is taken from the request's `X-Request-Id` header if it is a sane
+/// token: between 1 and [`RequestId::MAX_LEN`] bytes of visible ASCII, that
+/// is, any of `!` through `~`. Otherwise, including when the header is absent,
+/// a fresh ID of 32 random hex digits is generated. Either way, the ID is
+/// stored in the request-local cache, so every use of the guard during the
+/// request, whether by a route, another guard, or a fairing, sees the same ID.
+///
+/// The guard always succeeds. The ID can be retrieved as a `&str` via
+/// [`RequestId::as_str()`] or by dereferencing, and a `&RequestId` converts
+/// into an `X-Request-Id` [`Header`](crate::http::Header), so echoing it back
+/// is a single call.
+///
+/// # Example
+///
+/// Echo the ID in every response with a fairing:
+///
+/// ```rust
+/// # #[macro_use] extern crate rocket;
+/// use rocket::fairing::AdHoc;
+/// use rocket::request::RequestId;
+///
+/// #[get("/")]
+/// fn index(id: &RequestId) -> String {
+///     format!("handling request {}", id)
+/// }
+///
+/// #[launch]
+/// fn rocket() -> _ {
+///     rocket::build()
+///         .mount("/", routes![index])
+///         .attach(AdHoc::on_response("Request ID", |req, res| Box::pin(async move {
+///             let id = req.guard::<&RequestId>().await.unwrap();
+///             res.set_header(id);
+///         })))
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct RequestId(String);
+
+impl RequestId {
+    /// The name of the header the ID is read from and written to.
+    pub const HEADER: &'static str = "X-Request-Id";
+
+    /// The maximum length, in bytes, of an ID accepted from a request.
+    pub const MAX_LEN: usize = 128;
+
+    /// Returns `id` as a `RequestId` if it is a sane token and `None`
+    /// otherwise.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::request::RequestId;
+    ///
+    /// assert_eq!(RequestId::parse("req-42").unwrap().as_str(), "req-42");
+    /// assert!(RequestId::parse("").is_none());
+    /// assert!(RequestId::parse("has space").is_none());
+    /// assert!(RequestId::parse(&"a".repeat(RequestId::MAX_LEN + 1)).is_none());
+    /// ```
+    pub fn parse(id: &str) -> Option<RequestId> {
+        let valid = (1..=Self::MAX_LEN).contains(&id.len())
+            && id.bytes().all(|b| b.is_ascii_graphic());
+
+        valid.then(|| RequestId(id.into()))
+    }
+
+    /// Returns a new, random `RequestId`.
+    pub fn generate() -> RequestId {
+        RequestId(format!("{:032x}", rand::random::<u128>()))
+    }
+
+    /// Returns the ID as a string slice.
+    #[inline(always)]
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl std::ops::Deref for RequestId {
+    type Target = str;
+
+    #[inline(always)]
+    fn deref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl fmt::Display for RequestId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl From<&RequestId> for crate::http::Header<'static> {
+    fn from(id: &RequestId) -> Self {
+        crate::http::Header::new(RequestId::HEADER, id.0.clone())
+    }
+}
+
+impl From<RequestId> for crate::http::Header<'static> {
+    fn from(id: RequestId) -> Self {
+        crate::http::Header::new(RequestId::HEADER, id.0)
+    }
+}
+
+#[crate::async_trait]
+impl<'r> FromRequest<'r> for &'r RequestId {
+    type Error = std::convert::Infallible;
+
+    async fn from_request(request: &'r Request<'_>) -> Outcome<Self, Self::Error> {
+        Success(request.local_cache(|| {
+            request.headers().get_one(RequestId::HEADER)
+                .and_then(RequestId::parse)
+                .unwrap_or_else(RequestId::generate)
+        }))
+    }
+}
+
+/// A request guard for the request's `User-Agent`, with best-effort parsing.
+///
+/// The raw header value is always available via [`UserAgent::raw()`]. In
+/// addition, a handful of common patterns are recognized to extract the
+/// product, its version, and the operating system:
+///
+///   * crawlers whose product token contains `bot`, `spider`, or `crawler`,
+///     like `Googlebot/2.1`, as that product;
+///   * Edge, Opera, Firefox, Chrome, and Safari browsers;
+///   * a bare `product/version` token, like `curl/7.68.0`;
+///   * Windows, Android, iOS, macOS, ChromeOS, and Linux in the first
+///     parenthesized comment.
+///
+/// Parsing is deliberately shallow. When a component can't be determined with
+/// confidence, it is `None` rather than a guess, so exotic user agents simply
+/// yield their raw string with empty structured fields.
+///
+/// The guard always succeeds. A request without a `User-Agent` header yields an
+/// empty raw string.
+///
+/// # Example
+///
+/// ```rust
+/// # #[macro_use] extern crate rocket;
+/// use rocket::request::UserAgent;
+///
+/// #[get("/")]
+/// fn index(ua: UserAgent) -> String {
+///     match (ua.product(), ua.os()) {
+///         (Some(product), Some(os)) => format!("{} on {}", product, os),
+///         _ => format!("unknown agent: {}", ua.raw()),
+///     }
+/// }
+/// ```
+///
+/// Parse a value directly:
+///
+/// ```rust
+/// use rocket::request::UserAgent;
+///
+/// let raw = "Mozilla/5.0 (X11; Linux x86_64; rv:93.0) Gecko/20100101 Firefox/93.0";
+/// let ua = UserAgent::parse(raw);
+/// assert_eq!(ua.product(), Some("Firefox"));
+/// assert_eq!(ua.version(), Some("93.0"));
+/// assert_eq!(ua.os(), Some("Linux"));
+///
+/// let ua = UserAgent::parse("SomethingExotic");
+/// assert_eq!(ua.raw(), "SomethingExotic");
+/// assert_eq!((ua.product(), ua.version(), ua.os()), (None, None, None));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+pub struct UserAgent {
+    raw: String,
+    product: Option<String>,
+    version: Option<String>,
+    os: Option<String>,
+}
+
+impl UserAgent {
+    /// Browser product tokens, in the order they're checked, and the name each
+    /// is reported as. Order matters: Edge and Opera also send `Chrome/`.
+    const BROWSERS: &'static [(&'static str, &'static str)] = &[
+        ("Edg", "Edge"),
+        ("OPR", "Opera"),
+        ("Firefox", "Firefox"),
+        ("Chrome", "Chrome"),
+    ];
+
+    /// Substrings of the first comment, in the order they're checked, and the
+    /// operating system each indicates. Order matters: Android reports
+    /// `Linux`, and iOS reports `like Mac OS X`.
+    const SYSTEMS: &'static [(&'static str, &'static str)] = &[
+        ("Windows", "Windows"),
+        ("Android", "Android"),
+        ("iPhone", "iOS"),
+        ("iPad", "iOS"),
+        ("Mac OS X", "macOS"),
+        ("CrOS", "ChromeOS"),
+        ("Linux", "Linux"),
+    ];
+
+    /// Parses the raw `User-Agent` value `raw`. Never fails.
+    pub fn parse(raw: &str) -> UserAgent {
+        let (product, version) = match Self::product_of(raw) {
+            Some((product, version)) => (Some(product.into()), Some(version.into())),
+            None => (None, None),
+        };
+
+        let comment = raw.split_once('(').and_then(|(_, rest)| rest.split_once(')'));
+        let os = comment.and_then(|(comment, _)| {
+            Self::SYSTEMS.iter().find(|(needle, _)| comment.contains(needle))
+        });
+
+        UserAgent { raw: raw.into(), product, version, os: os.map(|(_, os)| os.to_string()) }
+    }
+
+    /// The product and version from `raw`, if one can be identified.
+    fn product_of(raw: &str) -> Option<(&str, &str)> {
+        let is_delim = |c: char| matches!(c, ' ' | ';' | '(' | ')');
+        let tokens = || raw.split(is_delim)
+            .filter_map(|t| t.split_once('/'))
+            .filter(|(name, version)| !name.is_empty() && !version.is_empty());
+
+        let bot = tokens().find(|(name, _)| {
+            let name = name.to_ascii_lowercase();
+            ["bot", "spider", "crawler"].iter().any(|n| name.contains(n))
+        });
+
+        if bot.is_some() {
+            return bot;
+        }
+
+        let version_of = |name: &str| tokens().find(|(n, _)| *n == name).map(|(_, v)| v);
+        for &(token, product) in Self::BROWSERS {
+            if let Some(version) = version_of(token) {
+                return Some((product, version));
+            }
+        }
+
+        if version_of("Safari").is_some() {
+            return version_of("Version").map(|v| ("Safari", v));
+        }
+
+        match raw.trim().split_once('/') {
+            Some((name, version)) if name != "Mozilla" && !raw.trim().contains(is_delim) => {
+                (!name.is_empty() && !version.is_empty()).then(|| (name, version))
+            }
+            _ => None,
+        }
+    }
+
+    /// Returns the raw `User-Agent` value, or an empty string if there was
+    /// none.
+    #[inline(always)]
+    pub fn raw(&self) -> &str {
+        &self.raw
+    }
+
+    /// Returns the identified product, like `Chrome` or `Googlebot`, if any.
+    #[inline(always)]
+    pub fn product(&self) -> Option<&str> {
+        self.product.as_deref()
+    }
+
+    /// Returns the version of the identified product, if any.
+    #[inline(always)]
+    pub fn version(&self) -> Option<&str> {
+        self.version.as_deref()
+    }
+
+    /// Returns the identified operating system, like `Windows` or `iOS`, if
+    /// any.
+    #[inline(always)]
+    pub fn os(&self) -> Option<&str> {
+        self.os.as_deref()
+    }
+}
+
+impl fmt::Display for UserAgent {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.raw)
+    }
+}
+
+#[crate::async_trait]
+impl<'r> FromRequest<'r> for UserAgent {
+    type Error = std::convert::Infallible;
+
+    async fn from_request(request: &'r Request<'_>) -> Outcome<Self, Self::Error> {
+        Success(UserAgent::parse(request.headers().get_one("User-Agent").unwrap_or("")))
+    }
+}
+
+/// A request guard that protects against cross-site request forgery (CSRF).
+///
+/// Each client session is assigned a random token, stored in a cookie named
+/// [`CsrfToken::COOKIE`] and signed with the configured `secret_key` so that
+/// it can't be forged. The cookie is created the first time the guard runs
+/// for a client without one. The guard's value, available via
+/// [`CsrfToken::as_str()`], is the session's token, to be embedded in pages
+/// that submit requests back to the application.
+///
+/// For requests with a safe method, `GET`, `HEAD`, or `OPTIONS`, the guard
+/// always succeeds. For all other methods, the request must echo the token
+/// back in the [`CsrfToken::HEADER`] header. The submitted token is compared
+/// to the session's in constant time. The guard fails with `403 Forbidden`
+/// and:
+///
+///   * [`CsrfError::Missing`] if no token was submitted;
+///   * [`CsrfError::Mismatch`] if the submitted token doesn't match the
+///     session's, including when the request has no valid session cookie.
+///
+/// Because request guards run before the body is read, HTML forms, which
+/// can't set headers, can't be checked by this guard. Submit the token in the
+/// form's [`CsrfToken::FIELD`] field instead and check it with the
+/// [`CsrfForm`](crate::form::CsrfForm) data guard.
+///
+/// This guard is only available when the `secrets` feature is enabled.
+///
+/// # Example
+///
+/// ```rust
+/// # #[macro_use] extern crate rocket;
+/// use rocket::request::CsrfToken;
+/// use rocket::response::content::RawHtml;
+///
+/// #[get("/token")]
+/// fn token(csrf: CsrfToken) -> String {
+///     csrf.to_string()
+/// }
+///
+/// // Scripts send the token from `/token` in the `X-CSRF-Token` header.
+/// #[delete("/account")]
+/// fn delete(_csrf: CsrfToken) -> &'static str {
+///     "deleted"
+/// }
+/// ```
+#[cfg(feature = "secrets")]
+#[cfg_attr(nightly, doc(cfg(feature = "secrets")))]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CsrfToken(String);
+
+/// Error returned by the [`CsrfToken`] guard.
+#[cfg(feature = "secrets")]
+#[cfg_attr(nightly, doc(cfg(feature = "secrets")))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CsrfError {
+    /// The request didn't submit a token.
+    Missing,
+    /// The submitted token doesn't match the session's.
+    Mismatch,
+}
+
+#[cfg(feature = "secrets")]
+impl CsrfToken {
+    /// The name of the signed cookie that stores the session's token.
+    pub const COOKIE: &'static str = "_csrf";
+
+    /// The name of the header a token is read from.
+    pub const HEADER: &'static str = "X-CSRF-Token";
+
+    /// The name of the form field a token is read from by
+    /// [`CsrfForm`](crate::form::CsrfForm).
+    pub const FIELD: &'static str = "csrf_token";
+
+    /// Returns the session's token, creating and storing a new one if the
+    /// request has no valid session cookie.
+    pub(crate) fn session<'r>(request: &'r Request<'_>) -> &'r CsrfToken {
+        request.local_cache(|| {
+            let jar = request.cookies();
+            if let Some(cookie) = jar.get_signed(Self::COOKIE) {
+                return CsrfToken(cookie.value().into());
+            }
+
+            let token = format!("{:032x}", rand::random::<u128>());
+            jar.add_signed(crate::http::Cookie::build(Self::COOKIE, token.clone())
+                .same_site(crate::http::SameSite::Lax)
+                .http_only(true)
+                .finish());
+
+            CsrfToken(token)
+        })
+    }
+
+    /// Returns `true` if `submitted` matches this token. The comparison takes
+    /// the same time wherever the first difference occurs.
+    pub(crate) fn matches(&self, submitted: &str) -> bool {
+        let (a, b) = (self.0.as_bytes(), submitted.as_bytes());
+        a.len() == b.len() && a.iter().zip(b).fold(0, |diff, (x, y)| diff | (x ^ y)) == 0
+    }
+
+    /// Returns the token as a string slice.
+    #[inline(always)]
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+#[cfg(feature = "secrets")]
+impl fmt::Display for CsrfToken {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+#[cfg(feature = "secrets")]
+impl fmt::Display for CsrfError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CsrfError::Missing => f.write_str("missing CSRF token"),
+            CsrfError::Mismatch => f.write_str("CSRF token mismatch"),
+        }
+    }
+}
+
+#[cfg(feature = "secrets")]
+impl std::error::Error for CsrfError { }
+
+#[cfg(feature = "secrets")]
+#[crate::async_trait]
+impl<'r> FromRequest<'r> for CsrfToken {
+    type Error = CsrfError;
+
+    async fn from_request(request: &'r Request<'_>) -> Outcome<Self, Self::Error> {
+        let token = CsrfToken::session(request);
+        if matches!(request.method(), Method::Get | Method::Head | Method::Options) {
+            return Success(token.clone());
+        }
+
+        match request.headers().get_one(CsrfToken::HEADER) {
+            Some(submitted) if token.matches(submitted) => Success(token.clone()),
+            Some(_) => Failure((Status::Forbidden, CsrfError::Mismatch)),
+            None => Failure((Status::Forbidden, CsrfError::Missing)),
+        }
+    }
+}
+
+/// A request guard for the hops reported in the request's `Forwarded`
+/// headers, as defined in [RFC 7239].
+///
+/// [RFC 7239]: https://tools.ietf.org/html/rfc7239
+///
+/// Each header is a comma-separated list of elements, one per proxy, in the
+/// order the proxies were traversed. Each element is a semicolon-separated
+/// list of `name=value` pairs, where a value may be a token or a quoted
+/// string. The `for`, `by`, `host`, and `proto` parameters, matched
+/// case-insensitively, are parsed into a [`ForwardedHop`]; other parameters
+/// are ignored. The `for` and `by` parameters are parsed as a
+/// [`ForwardedNode`]: an IPv4 address or a bracketed IPv6 address, each with
+/// an optional port, or one of the obfuscated identifiers `unknown` and
+/// `_name`.
+///
+/// A malformed element, such as one with a node that can't be parsed, an
+/// unterminated quoted string, or a repeated parameter, is skipped, as are
+/// empty elements. The remaining hops are available, in order, via `Deref` or
+/// [`Forwarded::hops()`]. This guard always succeeds; a request without a
+/// `Forwarded` header yields no hops.
+///
+/// Note that any client can send a `Forwarded` header. Only hops appended by
+/// trusted proxies should be believed. [`Request::real_client_ip()`] and
+/// [`Request::scheme()`] consult this header only when the request comes from
+/// a [trusted proxy](crate::Config::trusted_proxies) and
+/// [`Config::forwarded_header`](crate::Config::forwarded_header) is
+/// [`Forwarded`](crate::config::ForwardedHeader::Forwarded).
+///
+/// # Example
+///
+/// ```rust
+/// # #[macro_use] extern crate rocket;
+/// use rocket::request::Forwarded;
+///
+/// #[get("/")]
+/// fn index(forwarded: Forwarded) -> String {
+///     format!("{} proxies", forwarded.len())
+/// }
+/// ```
+///
+/// Parsing a header value directly:
+///
+/// ```rust
+/// use rocket::request::{Forwarded, ForwardedNode};
+///
+/// let value = r#"for="[2001:db8::1]:8080";proto=https, for=_gateway;by=203.0.113.43"#;
+/// let forwarded = Forwarded::parse(value);
+/// assert_eq!(forwarded.len(), 2);
+///
+/// let ip = "2001:db8::1".parse().unwrap();
+/// assert_eq!(forwarded[0].for_node, Some(ForwardedNode::Addr(ip, Some(8080))));
+/// assert_eq!(forwarded[0].proto.as_deref(), Some("https"));
+///
+/// assert_eq!(forwarded[1].for_node, Some(ForwardedNode::Obfuscated("_gateway".into())));
+/// assert_eq!(forwarded[1].by_node.as_ref().and_then(|n| n.ip()), "203.0.113.43".parse().ok());
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct Forwarded(Vec<ForwardedHop>);
+
+/// A single hop in a [`Forwarded`] header: the parameters added by one proxy.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct ForwardedHop {
+    /// The `for` parameter: the client that made the request to the proxy.
+    pub for_node: Option<ForwardedNode>,
+    /// The `by` parameter: the interface on which the proxy received the
+    /// request.
+    pub by_node: Option<ForwardedNode>,
+    /// The `host` parameter: the `Host` header the proxy received.
+    pub host: Option<String>,
+    /// The `proto` parameter: the scheme the proxy received the request over.
+    pub proto: Option<String>,
+}
+
+/// A node identifier in the `for` or `by` parameter of a [`Forwarded`] hop.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum ForwardedNode {
+    /// An IP address, with a port unless none was given or it was obfuscated.
+    Addr(IpAddr, Option<u16>),
+    /// The identifier `unknown`: the proxy doesn't know or won't reveal the
+    /// node.
+    Unknown,
+    /// An obfuscated identifier such as `_hidden`, including the leading `_`.
+    Obfuscated(String),
+}
+
+impl Forwarded {
+    /// Parses the value of a single `Forwarded` header, skipping malformed
+    /// and empty elements.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::request::{Forwarded, ForwardedNode};
+    ///
+    /// let forwarded = Forwarded::parse("for=192.0.2.60, for=nonsense, , for=unknown");
+    /// assert_eq!(forwarded.len(), 2);
+    /// assert_eq!(forwarded[0].for_node.as_ref().unwrap().ip(), "192.0.2.60".parse().ok());
+    /// assert_eq!(forwarded[1].for_node, Some(ForwardedNode::Unknown));
+    /// ```
+    pub fn parse(value: &str) -> Forwarded {
+        let hops = split_unquoted(value, ',')
+            .filter(|element| !element.trim().is_empty())
+            .filter_map(|element| match ForwardedHop::parse(element) {
+                Some(hop) => Some(hop),
+                None => {
+                    debug_!("Ignoring malformed 'Forwarded' element: {}", element.trim());
+                    None
+                }
+            })
+            .collect();
+
+        Forwarded(hops)
+    }
+
+    /// Parses the values of all of `request`'s `Forwarded` headers, in order.
+    pub(crate) fn from_headers(request: &Request<'_>) -> Forwarded {
+        let hops = request.headers().get("Forwarded")
+            .flat_map(|value| Forwarded::parse(value).0)
+            .collect();
+
+        Forwarded(hops)
+    }
+
+    /// Parses every element of all of `request`'s `Forwarded` headers, in
+    /// order. Unlike [`Forwarded::from_headers()`], malformed and empty
+    /// elements are kept, as `None`.
+    pub(crate) fn elements(request: &Request<'_>) -> Vec<Option<ForwardedHop>> {
+        request.headers().get("Forwarded")
+            .flat_map(|value| split_unquoted(value, ','))
+            .map(|element| match element.trim().is_empty() {
+                true => None,
+                false => ForwardedHop::parse(element),
+            })
+            .collect()
+    }
+
+    /// Returns the parsed hops, in the order the proxies were traversed.
+    #[inline(always)]
+    pub fn hops(&self) -> &[ForwardedHop] {
+        &self.0
+    }
+
+    /// Consumes `self` and returns the parsed hops.
+    #[inline(always)]
+    pub fn into_inner(self) -> Vec<ForwardedHop> {
+        self.0
+    }
+}
+
+impl ForwardedHop {
+    fn parse(element: &str) -> Option<ForwardedHop> {
+        let mut hop = ForwardedHop::default();
+        for pair in split_unquoted(element, ';').filter(|pair| !pair.trim().is_empty()) {
+            let (name, value) = pair.split_once('=')?;
+            let (name, value) = (name.trim().to_ascii_lowercase(), unquote(value.trim())?);
+            match name.as_str() {
+                "for" if hop.for_node.is_none() => {
+                    hop.for_node = Some(ForwardedNode::parse(&value)?);
+                }
+                "by" if hop.by_node.is_none() => {
+                    hop.by_node = Some(ForwardedNode::parse(&value)?);
+                }
+                "host" if hop.host.is_none() => hop.host = Some(value.into_owned()),
+                "proto" if hop.proto.is_none() => hop.proto = Some(value.into_owned()),
+                "for" | "by" | "host" | "proto" => return None,
+                _ => continue,
+            }
+        }
+
+        Some(hop)
+    }
+}
+
+impl ForwardedNode {
+    /// Parses a `node`, with any quotes already removed.
+    fn parse(node: &str) -> Option<ForwardedNode> {
+        let obfuscated = |s: &str| {
+            s.len() > 1 && s.starts_with('_')
+                && s.bytes().all(|b| b.is_ascii_alphanumeric() || b"._-".contains(&b))
+        };
+
+        let (name, port) = match node.strip_prefix('[') {
+            Some(rest) => {
+                let (ip, port) = rest.split_once(']')?;
+                (ip, port.strip_prefix(':').or_else(|| port.is_empty().then(|| ""))?)
+            }
+            None => node.split_once(':').unwrap_or((node, "")),
+        };
+
+        let port = match port {
+            "" => None,
+            p if obfuscated(p) => None,
+            p if p.bytes().all(|b| b.is_ascii_digit()) => Some(p.parse().ok()?),
+            _ => return None,
+        };
+
+        if node.starts_with('[') {
+            return name.parse::<std::net::Ipv6Addr>().ok().map(|ip| Self::Addr(ip.into(), port));
+        }
+
+        match name {
+            _ if name.eq_ignore_ascii_case("unknown") => Some(ForwardedNode::Unknown),
+            _ if obfuscated(name) => Some(ForwardedNode::Obfuscated(name.into())),
+            _ => name.parse::<std::net::Ipv4Addr>().ok().map(|ip| Self::Addr(ip.into(), port)),
+        }
+    }
+
+    /// Returns the node's IP address, if it isn't obfuscated.
+    pub fn ip(&self) -> Option<IpAddr> {
+        match self {
+            ForwardedNode::Addr(ip, _) => Some(*ip),
+            _ => None,
+        }
+    }
+}
+
+/// Splits `value` at every `delim` that isn't inside a quoted string.
+fn split_unquoted(value: &str, delim: char) -> impl Iterator<Item = &str> {
+    let (mut quoted, mut escaped) = (false, false);
+    value.split(move |c: char| {
+        match (quoted, escaped, c) {
+            (true, true, _) => escaped = false,
+            (true, false, '\\') => escaped = true,
+            (_, false, '"') => quoted = !quoted,
+            (false, _, c) if c == delim => return true,
+            _ => {}
+        }
+
+        false
+    })
+}
+
+/// Returns `value` as a token or, if it is quoted, the unescaped contents of
+/// the quoted string. Returns `None` if `value` is empty or malformed.
+fn unquote(value: &str) -> Option<Cow<'_, str>> {
+    let quoted = match value.strip_prefix('"') {
+        Some(rest) => rest.strip_suffix('"')?,
+        None if value.is_empty() => return None,
+        None => match value.bytes().all(|b| b.is_ascii_graphic() && b != b'"') {
+            true => return Some(Cow::Borrowed(value)),
+            false => return None,
+        },
+    };
+
+    if !quoted.contains(&['"', '\\'][..]) {
+        return Some(Cow::Borrowed(quoted));
+    }
+
+    let (mut unescaped, mut chars) = (String::with_capacity(quoted.len()), quoted.chars());
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => unescaped.push(chars.next()?),
+            '"' => return None,
+            c => unescaped.push(c),
+        }
+    }
+
+    Some(Cow::Owned(unescaped))
+}
+
+impl std::ops::Deref for Forwarded {
+    type Target = [ForwardedHop];
+
+    #[inline(always)]
+    fn deref(&self) -> &[ForwardedHop] {
+        &self.0
+    }
+}
+
+impl fmt::Display for ForwardedNode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ForwardedNode::Addr(IpAddr::V4(ip), None) => write!(f, "{}", ip),
+            ForwardedNode::Addr(IpAddr::V6(ip), None) => write!(f, "[{}]", ip),
+            ForwardedNode::Addr(ip, Some(port)) => write!(f, "{}", SocketAddr::new(*ip, *port)),
+            ForwardedNode::Unknown => f.write_str("unknown"),
+            ForwardedNode::Obfuscated(id) => f.write_str(id),
+        }
+    }
+}
+
+#[crate::async_trait]
+impl<'r> FromRequest<'r> for Forwarded {
+    type Error = std::convert::Infallible;
+
+    async fn from_request(request: &'r Request<'_>) -> Outcome<Self, Self::Error> {
+        Success(Forwarded::from_headers(request))
+    }
+}
+
+/// A request guard for the preferences in the request's `Prefer` headers, as
+/// defined in [RFC 7240].
+///
+/// [RFC 7240]: https://tools.ietf.org/html/rfc7240
+///
+/// Each header is a comma-separated list of preferences. A preference is a
+/// name, matched case-insensitively, with an optional value, followed by any
+/// number of semicolon-separated parameters, each with an optional value.
+/// Values may be tokens or quoted strings. When a preference is given more
+/// than once, only the first instance is kept. Malformed preferences are
+/// skipped.
+///
+/// The preferences defined by RFC 7240 have typed getters:
+/// [`Prefer::return_minimal()`], [`Prefer::return_representation()`],
+/// [`Prefer::wait()`], [`Prefer::respond_async()`],
+/// [`Prefer::handling_strict()`], and [`Prefer::handling_lenient()`]. Every preference,
+/// known or not, is preserved and can be retrieved with [`Prefer::get()`],
+/// iterated over via `Deref`, or, for those without a typed getter, via
+/// [`Prefer::unknown()`]. A [`Preference`] displays as its name and value, the
+/// form expected in a `Preference-Applied` response header.
+///
+/// This guard always succeeds; a request without a `Prefer` header yields no
+/// preferences.
+///
+/// # Example
+///
+/// ```rust
+/// # #[macro_use] extern crate rocket;
+/// use rocket::request::Prefer;
+///
+/// #[get("/")]
+/// fn index(prefer: Prefer) -> String {
+///     match prefer.wait() {
+///         Some(wait) => format!("waiting at most {:?}", wait),
+///         None => "not waiting".into(),
+///     }
+/// }
+/// ```
+///
+/// Parsing a header value directly:
+///
+/// ```rust
+/// use std::time::Duration;
+/// use rocket::request::Prefer;
+///
+/// let value = r#"return=minimal, wait=10, foo="a b"; bar, RETURN=representation"#;
+/// let prefer = Prefer::parse(value);
+/// assert!(prefer.return_minimal());
+/// assert!(!prefer.return_representation());
+/// assert_eq!(prefer.wait(), Some(Duration::from_secs(10)));
+///
+/// let foo = prefer.get("Foo").unwrap();
+/// assert_eq!(foo.value(), Some("a b"));
+/// assert_eq!(foo.param("bar"), Some(None));
+/// assert_eq!(foo.to_string(), r#"foo="a b""#);
+/// assert_eq!(prefer.unknown().count(), 1);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct Prefer(Vec<Preference>);
+
+/// A single preference in a [`Prefer`] header.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Preference {
+    name: String,
+    value: Option<String>,
+    params: Vec<(String, Option<String>)>,
+}
+
+impl Prefer {
+    /// The names of the preferences with typed getters.
+    const KNOWN: &'static [&'static str] = &["return", "wait", "respond-async", "handling"];
+
+    /// Parses the value of a single `Prefer` header, skipping malformed
+    /// preferences and all but the first instance of each preference.
+    pub fn parse(value: &str) -> Prefer {
+        let mut prefer = Prefer::default();
+        prefer.extend(value);
+        prefer
+    }
+
+    /// Parses the values of all of `request`'s `Prefer` headers, in order.
+    fn from_headers(request: &Request<'_>) -> Prefer {
+        let mut prefer = Prefer::default();
+        request.headers().get("Prefer").for_each(|value| prefer.extend(value));
+        prefer
+    }
+
+    fn extend(&mut self, value: &str) {
+        let preferences = split_unquoted(value, ',').filter(|p| !p.trim().is_empty());
+        for preference in preferences {
+            match Preference::parse(preference) {
+                Some(p) if self.get(&p.name).is_none() => self.0.push(p),
+                Some(_) => continue,
+                None => debug_!("Ignoring malformed 'Prefer' preference: {}", preference.trim()),
+            }
+        }
+    }
+
+    /// Returns the first preference named `name`, compared case-insensitively,
+    /// if there is one.
+    pub fn get(&self, name: &str) -> Option<&Preference> {
+        self.0.iter().find(|p| p.name.eq_ignore_ascii_case(name))
+    }
+
+    /// Returns `true` if a preference named `name`, compared
+    /// case-insensitively, is present.
+    pub fn contains(&self, name: &str) -> bool {
+        self.get(name).is_some()
+    }
+
+    /// Returns `true` if `return=minimal` is preferred.
+    pub fn return_minimal(&self) -> bool {
+        self.value_is("return", "minimal")
+    }
+
+    /// Returns `true` if `return=representation` is preferred.
+    pub fn return_representation(&self) -> bool {
+        self.value_is("return", "representation")
+    }
+
+    /// Returns `true` if `respond-async` is preferred.
+    pub fn respond_async(&self) -> bool {
+        self.contains("respond-async")
+    }
+
+    /// Returns `true` if `handling=strict` is preferred.
+    pub fn handling_strict(&self) -> bool {
+        self.value_is("handling", "strict")
+    }
+
+    /// Returns `true` if `handling=lenient` is preferred.
+    pub fn handling_lenient(&self) -> bool {
+        self.value_is("handling", "lenient")
+    }
+
+    /// Returns the duration of the `wait` preference, if present and a valid
+    /// number of seconds.
+    pub fn wait(&self) -> Option<std::time::Duration> {
+        let secs = self.get("wait")?.value()?.parse().ok()?;
+        Some(std::time::Duration::from_secs(secs))
+    }
+
+    /// Returns an iterator over the preferences without a typed getter, in
+    /// order.
+    pub fn unknown(&self) -> impl Iterator<Item = &Preference> {
+        self.0.iter().filter(|p| !Self::KNOWN.iter().any(|k| p.name.eq_ignore_ascii_case(k)))
+    }
+
+    /// Consumes `self` and returns the parsed preferences.
+    #[inline(always)]
+    pub fn into_inner(self) -> Vec<Preference> {
+        self.0
+    }
+
+    fn value_is(&self, name: &str, value: &str) -> bool {
+        let value_of = self.get(name).and_then(|p| p.value());
+        value_of.map_or(false, |v| v.eq_ignore_ascii_case(value))
+    }
+}
+
+impl Preference {
+    fn parse(preference: &str) -> Option<Preference> {
+        let mut pairs = split_unquoted(preference, ';').map(str::trim);
+        let (name, value) = Self::parse_pair(pairs.next()?)?;
+        let params = pairs.filter(|pair| !pair.is_empty()).map(Self::parse_pair);
+        Some(Preference { name, value, params: params.collect::<Option<_>>()? })
+    }
+
+    /// Parses a `name` or `name=value` pair, where `name` is a token.
+    fn parse_pair(pair: &str) -> Option<(String, Option<String>)> {
+        let (name, value) = match pair.split_once('=') {
+            Some((name, value)) => (name.trim_end(), Some(unquote(value.trim_start())?)),
+            None => (pair, None),
+        };
+
+        let token = !name.is_empty() && name.bytes().all(|b| is_tchar(&b));
+        token.then(|| (name.to_string(), value.map(|v| v.into_owned())))
+    }
+
+    /// Returns the preference's name, as it was sent.
+    #[inline(always)]
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Returns the preference's value, unquoted, if it has one.
+    #[inline(always)]
+    pub fn value(&self) -> Option<&str> {
+        self.value.as_deref()
+    }
+
+    /// Returns the preference's parameters, in order, with their unquoted
+    /// values.
+    #[inline(always)]
+    pub fn params(&self) -> &[(String, Option<String>)] {
+        &self.params
+    }
+
+    /// Returns `Some(value)` if a parameter named `name`, compared
+    /// case-insensitively, is present, where `value` is its value, if any.
+    pub fn param(&self, name: &str) -> Option<Option<&str>> {
+        self.params.iter()
+            .find(|(n, _)| n.eq_ignore_ascii_case(name))
+            .map(|(_, v)| v.as_deref())
+    }
+}
+
+/// Returns `true` if `b` is a `tchar` as defined in RFC 7230.
+fn is_tchar(b: &u8) -> bool {
+    b.is_ascii_alphanumeric() || b"!#$%&'*+-.^_`|~".contains(b)
+}
+
+impl std::ops::Deref for Prefer {
+    type Target = [Preference];
+
+    #[inline(always)]
+    fn deref(&self) -> &[Preference] {
+        &self.0
+    }
+}
+
+impl fmt::Display for Preference {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.name)?;
+        match self.value.as_deref() {
+            Some(v) if !v.is_empty() && v.bytes().all(|b| is_tchar(&b)) => write!(f, "={}", v),
+            Some(v) => write!(f, "=\"{}\"", v.replace('\\', "\\\\").replace('"', "\\\"")),
+            None => Ok(()),
+        }
+    }
+}
+
+#[crate::async_trait]
+impl<'r> FromRequest<'r> for Prefer {
+    type Error = std::convert::Infallible;
+
+    async fn from_request(request: &'r Request<'_>) -> Outcome<Self, Self::Error> {
+        Success(Prefer::from_headers(request))
+    }
+}
@@ -3,16 +3,54 @@
 mod request;
 mod from_param;
 mod from_request;
+mod params;
 
 #[cfg(test)]
 mod tests;
 
-pub use self::request::Request;
-pub use self::from_request::{FromRequest, Outcome};
-pub use self::from_param::{FromParam, FromSegments};
+pub use self::request::{Request, TlsInfo, PeerAddr, NormalizedHost, HostError};
+#[cfg(debug_assertions)]
+pub use self::request::CachedType;
+pub use self::from_request::{FromRequest, Outcome, AllOf, AllOfError};
+pub use self::from_request::{OneOf, OneOfError, Matched, Alternatives};
+pub use self::from_request::{PreferredLanguage, SupportedLanguages, LanguageTag};
+pub use self::from_request::{ConditionalRequest, Precondition, EntityTag, EntityTags};
+pub use self::from_request::{IfRange, RangeCondition};
+pub use self::from_request::{RangeHeader, ByteRangeSpec, Unsatisfiable};
+pub use self::from_request::{BasicAuth, BearerToken, AuthError};
+pub use self::from_request::{SortedQuery, RawQuery, WithTimeout, TimeoutError};
+pub use self::from_request::{RequestId, UserAgent};
+pub use self::from_request::{Forwarded, ForwardedHop, ForwardedNode};
+pub use self::from_request::{Prefer, Preference};
+#[cfg(feature = "secrets")]
+pub use self::from_request::{CsrfToken, CsrfError};
+pub use self::from_request::{Negotiate, Negotiated};
+pub use self::from_request::{RequireContentType, RequiredMediaType, ContentTypeError};
+pub use self::from_param::{FromParam, FromSegments, RawSegments, InvalidOption};
+pub use self::from_param::DiscriminantError;
+pub use self::from_param::{LimitedSegments, SegmentsError};
+pub use self::from_param::{PercentDecodeError, Strict, StrictError};
+pub use self::from_param::NonZeroParamError;
+pub use self::params::{RawBytes, base64, Base64, Base64Error};
+pub use self::params::{HumanDuration, DurationError};
+pub use self::params::{Glob, GlobError};
+pub use self::params::{Color, ColorError};
+pub use self::params::{CountryCode, LanguageCode, IsoCodeError};
+pub use self::params::{Slug, SlugError};
+pub use self::params::{Latitude, Longitude, LatLng, CoordinateError};
+pub use self::params::{Bounded, Radix, IntParamError};
+pub use self::params::{CommaSep, CommaSepLoose, CommaSepError};
+pub use self::params::{SafeSegment, SafeSegmentError};
+pub use self::params::Trimmed;
+pub use self::params::{WithRaw, WithRawError};
+#[cfg(feature = "rust_decimal")]
+pub use self::params::{Money, DecimalParamError};
+
+#[doc(hidden)]
+pub use rocket_codegen::FromParam;
 
 #[doc(inline)]
-pub use crate::response::flash::FlashMessage;
+pub use crate::response::flash::{FlashMessage, FlashMessages};
 
 pub(crate) use self::request::ConnectionMeta;
 
@@ -111,3 +149,49 @@ crate::export! {
         })
     }
 }
+
+crate::export! {
+    /// Await a future `$fut` and store and retrieve its output in `$request`'s
+    /// local cache using a locally generated anonymous type to avoid type
+    /// conflicts.
+    ///
+    /// This is the asynchronous analog of [`local_cache_once!`] and must be
+    /// used in an `async` context. `$fut` is only awaited if the invocation
+    /// has not previously stored a value for the request. As with
+    /// `local_cache_once!`, the anonymous type is generated per _macro_
+    /// invocation, so for a given request, an invocation always returns the
+    /// reference to the value stored first.
+    ///
+    /// See [`Request::local_cache_async()`](crate::Request::local_cache_async)
+    /// for details on concurrent use.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::request::local_cache_async;
+    /// # rocket::async_test(async move {
+    /// # let c = rocket::local::asynchronous::Client::debug_with(vec![]).await.unwrap();
+    /// # let request = c.get("/");
+    ///
+    /// async fn lookup(id: usize) -> String {
+    ///     // query a database, remote service, etc.
+    ///     format!("user #{}", id)
+    /// }
+    ///
+    /// // Each invocation uses its own type, so values of the same type coexist.
+    /// assert_eq!(local_cache_async!(request, lookup(1)), "user #1");
+    /// assert_eq!(local_cache_async!(request, lookup(2)), "user #2");
+    ///
+    /// // But a given invocation always resolves to its first stored value.
+    /// for i in 3..6 {
+    ///     assert_eq!(local_cache_async!(request, lookup(i)), "user #3");
+    /// }
+    /// # })
+    /// ```
+    macro_rules! local_cache_async {
+        ($request:expr, $fut:expr $(,)?) => ({
+            struct Local<T>(T);
+            &$request.local_cache_async(async { Local($fut.await) }).await.0
+        })
+    }
+}
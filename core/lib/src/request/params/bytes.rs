@@ -0,0 +1,360 @@
+use std::fmt;
+use std::borrow::Cow;
+use std::marker::PhantomData;
+
+use crate::http::RawStr;
+use crate::request::{FromParam, PercentDecodeError};
+
+/// The percent-decoded bytes of a path segment, which need not be valid UTF-8.
+///
+/// The `&str` and `String` implementations of [`FromParam`] replace invalid
+/// UTF-8 byte sequences in the decoded segment with � U+FFFD. `RawBytes`
+/// instead decodes the raw segment to exactly the bytes that were encoded,
+/// making it suitable for binary identifiers embedded in a URL. When the
+/// segment contains no percent-escapes, the bytes are borrowed from the
+/// request.
+///
+/// Malformed percent-escapes, like `%2G` or a truncated `%4`, are structural
+/// errors and are still rejected: the request is forwarded before `RawBytes`
+/// sees the segment, as described in [`PercentDecodeError`], which is also
+/// the guard's error type when [`FromParam::from_raw_param()`] is called
+/// directly.
+///
+/// # Example
+///
+/// ```rust
+/// # #[macro_use] extern crate rocket;
+/// use rocket::request::RawBytes;
+///
+/// #[get("/object/<id>")]
+/// fn object(id: RawBytes<'_>) -> String {
+///     id.iter().map(|b| format!("{:02x}", b)).collect()
+/// }
+///
+/// # use rocket::local::blocking::Client;
+/// # let client = Client::debug_with(routes![object]).unwrap();
+/// # let response = client.get("/object/%FF%00a").dispatch();
+/// # assert_eq!(response.into_string().unwrap(), "ff0061");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct RawBytes<'a>(Cow<'a, [u8]>);
+
+impl<'a> RawBytes<'a> {
+    /// Consumes `self` and returns the decoded bytes.
+    #[inline(always)]
+    pub fn into_inner(self) -> Cow<'a, [u8]> {
+        self.0
+    }
+}
+
+impl<'a> FromParam<'a> for RawBytes<'a> {
+    type Error = PercentDecodeError<'a>;
+
+    /// Returns the bytes of the already-decoded `param`.
+    #[inline]
+    fn from_param(param: &'a str) -> Result<Self, Self::Error> {
+        Ok(RawBytes(Cow::Borrowed(param.as_bytes())))
+    }
+
+    fn from_raw_param(raw: &'a RawStr, _: &'a str) -> Result<Self, Self::Error> {
+        PercentDecodeError::check(raw.as_str())?;
+
+        let raw = raw.as_bytes();
+        if memchr::memchr(b'%', raw).is_none() {
+            return Ok(RawBytes(Cow::Borrowed(raw)));
+        }
+
+        let hex = |b: u8| (b as char).to_digit(16).expect("checked hex digit") as u8;
+        let mut bytes = Vec::with_capacity(raw.len());
+        let mut i = 0;
+        while i < raw.len() {
+            if raw[i] == b'%' {
+                bytes.push(hex(raw[i + 1]) << 4 | hex(raw[i + 2]));
+                i += 3;
+            } else {
+                bytes.push(raw[i]);
+                i += 1;
+            }
+        }
+
+        Ok(RawBytes(Cow::Owned(bytes)))
+    }
+}
+
+impl std::ops::Deref for RawBytes<'_> {
+    type Target = [u8];
+
+    #[inline(always)]
+    fn deref(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+impl AsRef<[u8]> for RawBytes<'_> {
+    #[inline(always)]
+    fn as_ref(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+/// Base64 decoded bytes of a path segment, such as an opaque token.
+///
+/// The configuration `C` determines the alphabet and whether padding is
+/// required, optional, or forbidden. It defaults to
+/// [`base64::UrlSafe`], the URL-safe alphabet without padding, the usual
+/// choice for tokens embedded in URLs. The other provided configurations are
+/// [`base64::UrlSafePadded`], [`base64::Standard`], and
+/// [`base64::StandardNoPad`]; custom configurations implement
+/// [`base64::Config`].
+///
+/// Decoding is strict: a character outside of the alphabet, padding that the
+/// configuration doesn't allow, or a final character with bits set beyond the
+/// end of the data results in a [`Base64Error`], forwarding the request. Note
+/// that the `/` of the standard alphabet must be percent-encoded in a path
+/// segment, as `%2F`.
+///
+/// The decoded bytes are available via `Deref` and [`Base64::into_inner()`].
+///
+/// # Example
+///
+/// ```rust
+/// # #[macro_use] extern crate rocket;
+/// use rocket::request::{base64, Base64};
+///
+/// #[get("/verify/<token>")]
+/// fn verify(token: Base64) -> String {
+///     format!("{} byte token", token.len())
+/// }
+///
+/// #[get("/legacy/<token>")]
+/// fn legacy(token: Base64<base64::Standard>) -> Vec<u8> {
+///     token.into_inner()
+/// }
+/// ```
+///
+/// Inspect the error by requesting a `Result`:
+///
+/// ```rust
+/// use rocket::request::{base64, Base64, Base64Error, FromParam};
+///
+/// let token = Base64::<base64::UrlSafe>::from_param("aGk_Pw").unwrap();
+/// assert_eq!(&*token, b"hi??");
+///
+/// let result = Base64::<base64::UrlSafe>::from_param("aGk/Pw");
+/// assert_eq!(result.unwrap_err(), Base64Error::InvalidChar(3, '/'));
+///
+/// let result = Base64::<base64::Standard>::from_param("aGk/Pw");
+/// assert_eq!(result.unwrap_err(), Base64Error::InvalidPadding);
+/// ```
+pub struct Base64<C: base64::Config = base64::UrlSafe>(Vec<u8>, PhantomData<fn() -> C>);
+
+/// Configurations for the [`Base64`](crate::request::Base64) parameter.
+pub mod base64 {
+    /// Whether a base64 [`Config`] requires, allows, or forbids padding.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum Padding {
+        /// Input must be padded with `=` to a multiple of four characters.
+        Required,
+        /// Input may or may not be padded. If it is, the padding must be
+        /// correct.
+        Optional,
+        /// Input must not contain any padding.
+        Forbidden,
+    }
+
+    /// A base64 alphabet and padding policy.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::request::{base64, Base64, FromParam};
+    ///
+    /// /// URL-safe base64, with or without padding.
+    /// struct Lenient;
+    ///
+    /// impl base64::Config for Lenient {
+    ///     const URL_SAFE: bool = true;
+    ///     const PADDING: base64::Padding = base64::Padding::Optional;
+    /// }
+    ///
+    /// assert_eq!(&*Base64::<Lenient>::from_param("aGk").unwrap(), b"hi");
+    /// assert_eq!(&*Base64::<Lenient>::from_param("aGk=").unwrap(), b"hi");
+    /// ```
+    pub trait Config {
+        /// Whether the alphabet is the URL-safe one, with `-` and `_`, or the
+        /// standard one, with `+` and `/`.
+        const URL_SAFE: bool;
+
+        /// The padding policy.
+        const PADDING: Padding;
+    }
+
+    macro_rules! config {
+        ($(#[$attr:meta])* $name:ident: $url_safe:expr, $padding:ident) => (
+            $(#[$attr])*
+            #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+            pub struct $name;
+
+            impl Config for $name {
+                const URL_SAFE: bool = $url_safe;
+                const PADDING: Padding = Padding::$padding;
+            }
+        )
+    }
+
+    config! {
+        /// The URL-safe alphabet without padding. The default.
+        UrlSafe: true, Forbidden
+    }
+
+    config! {
+        /// The URL-safe alphabet with required padding.
+        UrlSafePadded: true, Required
+    }
+
+    config! {
+        /// The standard alphabet with required padding.
+        Standard: false, Required
+    }
+
+    config! {
+        /// The standard alphabet without padding.
+        StandardNoPad: false, Forbidden
+    }
+}
+
+/// Error returned by the [`Base64`] parameter when a segment isn't valid
+/// base64 for the configuration.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Base64Error {
+    /// The character at the given byte index isn't in the alphabet or, for
+    /// the last character, has bits set beyond the end of the data.
+    InvalidChar(usize, char),
+    /// The number of characters, excluding padding, is one more than a
+    /// multiple of four, which no data encodes to.
+    InvalidLength(usize),
+    /// Padding is missing, malformed, or not allowed by the configuration.
+    InvalidPadding,
+}
+
+impl<C: base64::Config> Base64<C> {
+    fn decode(input: &str) -> Result<Vec<u8>, Base64Error> {
+        use base64::Padding;
+
+        let data = input.trim_end_matches('=');
+        let padding = input.len() - data.len();
+        let valid_padding = match (padding, C::PADDING) {
+            (0, Padding::Required) => data.len() % 4 == 0,
+            (0, _) => true,
+            (_, Padding::Forbidden) => false,
+            (n, _) => n <= 2 && data.len() % 4 > 1 && (data.len() + n) % 4 == 0,
+        };
+
+        if !valid_padding {
+            return Err(Base64Error::InvalidPadding);
+        }
+
+        if data.len() % 4 == 1 {
+            return Err(Base64Error::InvalidLength(data.len()));
+        }
+
+        let sextet = |c: u8| match c {
+            b'A'..=b'Z' => Some(c - b'A'),
+            b'a'..=b'z' => Some(c - b'a' + 26),
+            b'0'..=b'9' => Some(c - b'0' + 52),
+            b'-' if C::URL_SAFE => Some(62),
+            b'_' if C::URL_SAFE => Some(63),
+            b'+' if !C::URL_SAFE => Some(62),
+            b'/' if !C::URL_SAFE => Some(63),
+            _ => None,
+        };
+
+        let invalid = |i: usize| {
+            let c = data[i..].chars().next().expect("index in bounds");
+            Base64Error::InvalidChar(i, c)
+        };
+
+        let mut bytes = Vec::with_capacity(data.len() * 3 / 4);
+        let (mut buffer, mut bits) = (0u32, 0);
+        for (i, c) in data.bytes().enumerate() {
+            buffer = buffer << 6 | sextet(c).ok_or_else(|| invalid(i))? as u32;
+            bits += 6;
+            if bits >= 8 {
+                bits -= 8;
+                bytes.push((buffer >> bits) as u8);
+                buffer &= (1 << bits) - 1;
+            }
+        }
+
+        if buffer != 0 {
+            return Err(invalid(data.len() - 1));
+        }
+
+        Ok(bytes)
+    }
+
+    /// Consumes `self` and returns the decoded bytes.
+    #[inline(always)]
+    pub fn into_inner(self) -> Vec<u8> {
+        self.0
+    }
+}
+
+impl<'a, C: base64::Config> FromParam<'a> for Base64<C> {
+    type Error = Base64Error;
+
+    #[inline]
+    fn from_param(param: &'a str) -> Result<Self, Self::Error> {
+        Base64::<C>::decode(param).map(|bytes| Base64(bytes, PhantomData))
+    }
+}
+
+impl<C: base64::Config> std::ops::Deref for Base64<C> {
+    type Target = [u8];
+
+    #[inline(always)]
+    fn deref(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+impl<C: base64::Config> AsRef<[u8]> for Base64<C> {
+    #[inline(always)]
+    fn as_ref(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+impl<C: base64::Config> fmt::Debug for Base64<C> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("Base64").field(&self.0).finish()
+    }
+}
+
+impl<C: base64::Config> Clone for Base64<C> {
+    fn clone(&self) -> Self {
+        Base64(self.0.clone(), PhantomData)
+    }
+}
+
+impl<C: base64::Config> PartialEq for Base64<C> {
+    fn eq(&self, other: &Self) -> bool {
+        self.0 == other.0
+    }
+}
+
+impl<C: base64::Config> Eq for Base64<C> { }
+
+impl fmt::Display for Base64Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Base64Error::InvalidChar(i, c) => {
+                write!(f, "invalid base64 character {:?} at index {}", c, i)
+            }
+            Base64Error::InvalidLength(n) => write!(f, "invalid base64 length {}", n),
+            Base64Error::InvalidPadding => f.write_str("invalid base64 padding"),
+        }
+    }
+}
+
+impl std::error::Error for Base64Error { }
@@ -0,0 +1,239 @@
+use std::fmt;
+use std::str::FromStr;
+
+use crate::form::{self, FromFormField, ValueField};
+use crate::request::FromParam;
+
+/// A color with 8-bit red, green, blue, and alpha channels.
+///
+/// A path segment is parsed as a color in one of two forms:
+///
+///   * Hexadecimal, with 3, 4, 6, or 8 digits and an optional leading `#`:
+///     `rgb`, `rgba`, `rrggbb`, or `rrggbbaa`. In the short forms, each digit
+///     is repeated, so `f80` is `ff8800`. Without an alpha component, the
+///     color is opaque. Digits are case-insensitive.
+///   * One of the 148 [CSS named colors], like `red` or `rebeccapurple`, or
+///     `transparent`. Names are case-insensitive.
+///
+/// A segment consisting only of hexadecimal digits is always parsed as hex. A
+/// `#` must be percent-encoded as `%23` to appear in a path segment.
+///
+/// If the segment is neither, parsing fails with a [`ColorError`]. `Color`
+/// implements [`FromParam`], [`FromFormField`], and [`FromStr`]. It displays
+/// as lowercase `#rrggbb`, or as `#rrggbbaa` if it isn't opaque.
+///
+/// [CSS named colors]: https://www.w3.org/TR/css-color-4/#named-colors
+///
+/// # Example
+///
+/// ```rust
+/// # #[macro_use] extern crate rocket;
+/// use rocket::request::Color;
+///
+/// #[get("/swatch/<color>")]
+/// fn swatch(color: Color) -> String {
+///     format!("r={} g={} b={} a={}", color.r, color.g, color.b, color.a)
+/// }
+/// ```
+///
+/// Inspect the error by requesting a `Result`:
+///
+/// ```rust
+/// use rocket::request::{Color, ColorError};
+///
+/// assert_eq!("ff8800".parse(), Ok(Color::rgb(0xff, 0x88, 0x00)));
+/// assert_eq!("#f808".parse(), Ok(Color::rgba(0xff, 0x88, 0x00, 0x88)));
+/// assert_eq!("Orange".parse(), Ok(Color::rgb(0xff, 0xa5, 0x00)));
+///
+/// assert_eq!("ff88".parse::<Color>().unwrap().to_string(), "#ffff8888");
+/// assert_eq!("#ff880".parse::<Color>(), Err(ColorError::InvalidLength(5)));
+/// assert_eq!("redd".parse::<Color>(), Err(ColorError::UnknownName("redd".into())));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Color {
+    /// The red channel.
+    pub r: u8,
+    /// The green channel.
+    pub g: u8,
+    /// The blue channel.
+    pub b: u8,
+    /// The alpha channel, where `0` is fully transparent and `255` is opaque.
+    pub a: u8,
+}
+
+/// Error returned when parsing a [`Color`] fails.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ColorError {
+    /// The value is empty.
+    Empty,
+    /// A hexadecimal color doesn't have 3, 4, 6, or 8 digits. Contains the
+    /// number of characters after the `#`, if any.
+    InvalidLength(usize),
+    /// A `#`-prefixed color contains a character that isn't a hexadecimal
+    /// digit. Contains the character.
+    InvalidDigit(char),
+    /// The value isn't hexadecimal and isn't a known color name. Contains the
+    /// value.
+    UnknownName(String),
+}
+
+/// The CSS named colors, sorted by name, as `0xrrggbb`.
+const NAMED_COLORS: &[(&str, u32)] = &[
+    ("aliceblue", 0xf0f8ff), ("antiquewhite", 0xfaebd7), ("aqua", 0x00ffff),
+    ("aquamarine", 0x7fffd4), ("azure", 0xf0ffff), ("beige", 0xf5f5dc), ("bisque", 0xffe4c4),
+    ("black", 0x000000), ("blanchedalmond", 0xffebcd), ("blue", 0x0000ff), ("blueviolet", 0x8a2be2),
+    ("brown", 0xa52a2a), ("burlywood", 0xdeb887), ("cadetblue", 0x5f9ea0), ("chartreuse", 0x7fff00),
+    ("chocolate", 0xd2691e), ("coral", 0xff7f50), ("cornflowerblue", 0x6495ed),
+    ("cornsilk", 0xfff8dc), ("crimson", 0xdc143c), ("cyan", 0x00ffff), ("darkblue", 0x00008b),
+    ("darkcyan", 0x008b8b), ("darkgoldenrod", 0xb8860b), ("darkgray", 0xa9a9a9),
+    ("darkgreen", 0x006400), ("darkgrey", 0xa9a9a9), ("darkkhaki", 0xbdb76b),
+    ("darkmagenta", 0x8b008b), ("darkolivegreen", 0x556b2f), ("darkorange", 0xff8c00),
+    ("darkorchid", 0x9932cc), ("darkred", 0x8b0000), ("darksalmon", 0xe9967a),
+    ("darkseagreen", 0x8fbc8f), ("darkslateblue", 0x483d8b), ("darkslategray", 0x2f4f4f),
+    ("darkslategrey", 0x2f4f4f), ("darkturquoise", 0x00ced1), ("darkviolet", 0x9400d3),
+    ("deeppink", 0xff1493), ("deepskyblue", 0x00bfff), ("dimgray", 0x696969), ("dimgrey", 0x696969),
+    ("dodgerblue", 0x1e90ff), ("firebrick", 0xb22222), ("floralwhite", 0xfffaf0),
+    ("forestgreen", 0x228b22), ("fuchsia", 0xff00ff), ("gainsboro", 0xdcdcdc),
+    ("ghostwhite", 0xf8f8ff), ("gold", 0xffd700), ("goldenrod", 0xdaa520), ("gray", 0x808080),
+    ("green", 0x008000), ("greenyellow", 0xadff2f), ("grey", 0x808080), ("honeydew", 0xf0fff0),
+    ("hotpink", 0xff69b4), ("indianred", 0xcd5c5c), ("indigo", 0x4b0082), ("ivory", 0xfffff0),
+    ("khaki", 0xf0e68c), ("lavender", 0xe6e6fa), ("lavenderblush", 0xfff0f5),
+    ("lawngreen", 0x7cfc00), ("lemonchiffon", 0xfffacd), ("lightblue", 0xadd8e6),
+    ("lightcoral", 0xf08080), ("lightcyan", 0xe0ffff), ("lightgoldenrodyellow", 0xfafad2),
+    ("lightgray", 0xd3d3d3), ("lightgreen", 0x90ee90), ("lightgrey", 0xd3d3d3),
+    ("lightpink", 0xffb6c1), ("lightsalmon", 0xffa07a), ("lightseagreen", 0x20b2aa),
+    ("lightskyblue", 0x87cefa), ("lightslategray", 0x778899), ("lightslategrey", 0x778899),
+    ("lightsteelblue", 0xb0c4de), ("lightyellow", 0xffffe0), ("lime", 0x00ff00),
+    ("limegreen", 0x32cd32), ("linen", 0xfaf0e6), ("magenta", 0xff00ff), ("maroon", 0x800000),
+    ("mediumaquamarine", 0x66cdaa), ("mediumblue", 0x0000cd), ("mediumorchid", 0xba55d3),
+    ("mediumpurple", 0x9370db), ("mediumseagreen", 0x3cb371), ("mediumslateblue", 0x7b68ee),
+    ("mediumspringgreen", 0x00fa9a), ("mediumturquoise", 0x48d1cc), ("mediumvioletred", 0xc71585),
+    ("midnightblue", 0x191970), ("mintcream", 0xf5fffa), ("mistyrose", 0xffe4e1),
+    ("moccasin", 0xffe4b5), ("navajowhite", 0xffdead), ("navy", 0x000080), ("oldlace", 0xfdf5e6),
+    ("olive", 0x808000), ("olivedrab", 0x6b8e23), ("orange", 0xffa500), ("orangered", 0xff4500),
+    ("orchid", 0xda70d6), ("palegoldenrod", 0xeee8aa), ("palegreen", 0x98fb98),
+    ("paleturquoise", 0xafeeee), ("palevioletred", 0xdb7093), ("papayawhip", 0xffefd5),
+    ("peachpuff", 0xffdab9), ("peru", 0xcd853f), ("pink", 0xffc0cb), ("plum", 0xdda0dd),
+    ("powderblue", 0xb0e0e6), ("purple", 0x800080), ("rebeccapurple", 0x663399), ("red", 0xff0000),
+    ("rosybrown", 0xbc8f8f), ("royalblue", 0x4169e1), ("saddlebrown", 0x8b4513),
+    ("salmon", 0xfa8072), ("sandybrown", 0xf4a460), ("seagreen", 0x2e8b57), ("seashell", 0xfff5ee),
+    ("sienna", 0xa0522d), ("silver", 0xc0c0c0), ("skyblue", 0x87ceeb), ("slateblue", 0x6a5acd),
+    ("slategray", 0x708090), ("slategrey", 0x708090), ("snow", 0xfffafa), ("springgreen", 0x00ff7f),
+    ("steelblue", 0x4682b4), ("tan", 0xd2b48c), ("teal", 0x008080), ("thistle", 0xd8bfd8),
+    ("tomato", 0xff6347), ("turquoise", 0x40e0d0), ("violet", 0xee82ee), ("wheat", 0xf5deb3),
+    ("white", 0xffffff), ("whitesmoke", 0xf5f5f5), ("yellow", 0xffff00), ("yellowgreen", 0x9acd32),
+];
+
+impl Color {
+    /// Returns the opaque color with channels `r`, `g`, and `b`.
+    #[inline(always)]
+    pub const fn rgb(r: u8, g: u8, b: u8) -> Color {
+        Color { r, g, b, a: 255 }
+    }
+
+    /// Returns the color with channels `r`, `g`, `b`, and `a`.
+    #[inline(always)]
+    pub const fn rgba(r: u8, g: u8, b: u8, a: u8) -> Color {
+        Color { r, g, b, a }
+    }
+
+    /// Returns the CSS named color `name`, ignoring case, if there is one.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::request::Color;
+    ///
+    /// assert_eq!(Color::named("RebeccaPurple"), Some(Color::rgb(0x66, 0x33, 0x99)));
+    /// assert_eq!(Color::named("transparent"), Some(Color::rgba(0, 0, 0, 0)));
+    /// assert_eq!(Color::named("ff0000"), None);
+    /// ```
+    pub fn named(name: &str) -> Option<Color> {
+        let name = name.to_ascii_lowercase();
+        if name == "transparent" {
+            return Some(Color::rgba(0, 0, 0, 0));
+        }
+
+        let i = NAMED_COLORS.binary_search_by(|(n, _)| n.cmp(&name.as_str())).ok()?;
+        let [_, r, g, b] = NAMED_COLORS[i].1.to_be_bytes();
+        Some(Color::rgb(r, g, b))
+    }
+
+    /// Parses `digits`, without a leading `#`, as a hexadecimal color.
+    fn from_hex(digits: &str) -> Result<Color, ColorError> {
+        if let Some(c) = digits.chars().find(|c| !c.is_ascii_hexdigit()) {
+            return Err(ColorError::InvalidDigit(c));
+        }
+
+        let nibble = |i: usize| u8::from_str_radix(&digits[i..(i + 1)], 16).unwrap() * 17;
+        let byte = |i: usize| u8::from_str_radix(&digits[i..(i + 2)], 16).unwrap();
+        match digits.len() {
+            3 => Ok(Color::rgb(nibble(0), nibble(1), nibble(2))),
+            4 => Ok(Color::rgba(nibble(0), nibble(1), nibble(2), nibble(3))),
+            6 => Ok(Color::rgb(byte(0), byte(2), byte(4))),
+            8 => Ok(Color::rgba(byte(0), byte(2), byte(4), byte(6))),
+            n => Err(ColorError::InvalidLength(n)),
+        }
+    }
+}
+
+impl FromStr for Color {
+    type Err = ColorError;
+
+    fn from_str(string: &str) -> Result<Self, Self::Err> {
+        if string.is_empty() {
+            return Err(ColorError::Empty);
+        }
+
+        if let Some(digits) = string.strip_prefix('#') {
+            return Color::from_hex(digits);
+        }
+
+        if string.bytes().all(|b| b.is_ascii_hexdigit()) {
+            return Color::from_hex(string);
+        }
+
+        Color::named(string).ok_or_else(|| ColorError::UnknownName(string.into()))
+    }
+}
+
+impl<'a> FromParam<'a> for Color {
+    type Error = ColorError;
+
+    #[inline]
+    fn from_param(param: &'a str) -> Result<Self, Self::Error> {
+        param.parse()
+    }
+}
+
+impl<'v> FromFormField<'v> for Color {
+    #[inline]
+    fn from_value(field: ValueField<'v>) -> form::Result<'v, Self> {
+        Ok(field.value.parse().map_err(form::Error::custom)?)
+    }
+}
+
+impl fmt::Display for Color {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "#{:02x}{:02x}{:02x}", self.r, self.g, self.b)?;
+        match self.a {
+            255 => Ok(()),
+            a => write!(f, "{:02x}", a),
+        }
+    }
+}
+
+impl fmt::Display for ColorError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ColorError::Empty => f.write_str("empty color"),
+            ColorError::InvalidLength(n) => {
+                write!(f, "hex color has {} digits; expected 3, 4, 6, or 8", n)
+            }
+            ColorError::InvalidDigit(c) => write!(f, "invalid hex digit {:?} in color", c),
+            ColorError::UnknownName(name) => write!(f, "unknown color name `{}`", name),
+        }
+    }
+}
+
+impl std::error::Error for ColorError { }
@@ -0,0 +1,144 @@
+use std::fmt;
+
+use crate::request::FromParam;
+
+/// A comma-separated list of `T`s.
+///
+/// The path segment is split on `,` and each element is parsed with `T`'s
+/// [`FromParam`] implementation. An empty segment yields an empty list. Every
+/// other element must be non-empty: a leading, trailing, or repeated comma
+/// results in a [`CommaSepError::Empty`] error rather than a silently dropped
+/// or misparsed element. Use [`CommaSepLoose`] to skip empty elements instead.
+/// If any element fails to parse, the guard fails with a
+/// [`CommaSepError::Element`] identifying the first such element.
+///
+/// Note that the segment is percent-decoded before it is split, so `%2C` is
+/// treated as a separator. `CommaSep` dereferences to the inner `Vec<T>`,
+/// which can also be retrieved with [`CommaSep::into_inner()`].
+///
+/// # Example
+///
+/// ```rust
+/// # #[macro_use] extern crate rocket;
+/// use rocket::request::{CommaSep, FromParam, CommaSepError};
+///
+/// #[get("/items/<ids>")]
+/// fn items(ids: CommaSep<u32>) -> String {
+///     format!("{} items: {:?}", ids.len(), *ids)
+/// }
+///
+/// assert_eq!(*CommaSep::<u32>::from_param("1,2,3").unwrap(), [1, 2, 3]);
+/// assert!(CommaSep::<u32>::from_param("").unwrap().is_empty());
+/// assert_eq!(CommaSep::<u32>::from_param("1,2,"), Err(CommaSepError::Empty { index: 2 }));
+/// assert!(matches!(CommaSep::<u32>::from_param("1,x"),
+///     Err(CommaSepError::Element { index: 1, element: "x", .. })));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct CommaSep<T>(Vec<T>);
+
+/// A comma-separated list of `T`s that skips empty elements.
+///
+/// Identical to [`CommaSep`] except that empty elements, such as those
+/// introduced by a leading, trailing, or repeated comma, are skipped rather
+/// than rejected. As such, the only error is [`CommaSepError::Element`].
+///
+/// # Example
+///
+/// ```rust
+/// use rocket::request::{CommaSepLoose, FromParam};
+///
+/// assert_eq!(*CommaSepLoose::<u32>::from_param(",1,,2,").unwrap(), [1, 2]);
+/// assert!(CommaSepLoose::<u32>::from_param(",,").unwrap().is_empty());
+/// assert!(CommaSepLoose::<u32>::from_param("1,-2").is_err());
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct CommaSepLoose<T>(Vec<T>);
+
+/// Error returned by the [`CommaSep`] and [`CommaSepLoose`] parameter guards.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CommaSepError<'a, E> {
+    /// The element at `index` is empty. Only returned by [`CommaSep`].
+    Empty {
+        /// The 0-based index of the empty element.
+        index: usize,
+    },
+    /// The element at `index` failed to parse.
+    Element {
+        /// The 0-based index of the element, counting empty elements.
+        index: usize,
+        /// The element that failed to parse.
+        element: &'a str,
+        /// The error returned by the element's `FromParam` implementation.
+        error: E,
+    },
+}
+
+impl<E: fmt::Debug> fmt::Display for CommaSepError<'_, E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CommaSepError::Empty { index } => write!(f, "list element {} is empty", index),
+            CommaSepError::Element { index, element, error } => {
+                write!(f, "invalid list element {} `{}`: {:?}", index, element, error)
+            }
+        }
+    }
+}
+
+impl<E: fmt::Debug> std::error::Error for CommaSepError<'_, E> { }
+
+fn parse_comma_sep<'a, T>(param: &'a str, loose: bool) -> Result<Vec<T>, CommaSepError<'a, T::Error>>
+    where T: FromParam<'a>
+{
+    if param.is_empty() {
+        return Ok(vec![]);
+    }
+
+    let mut values = vec![];
+    for (index, element) in param.split(',').enumerate() {
+        if element.is_empty() {
+            match loose {
+                true => continue,
+                false => return Err(CommaSepError::Empty { index }),
+            }
+        }
+
+        let value = T::from_param(element)
+            .map_err(|error| CommaSepError::Element { index, element, error })?;
+
+        values.push(value);
+    }
+
+    Ok(values)
+}
+
+macro_rules! impl_comma_sep {
+    ($($Type:ident: $loose:expr),+) => ($(
+        impl<T> $Type<T> {
+            /// Consumes `self` and returns the inner `Vec`.
+            #[inline(always)]
+            pub fn into_inner(self) -> Vec<T> {
+                self.0
+            }
+        }
+
+        impl<T> std::ops::Deref for $Type<T> {
+            type Target = Vec<T>;
+
+            #[inline(always)]
+            fn deref(&self) -> &Vec<T> {
+                &self.0
+            }
+        }
+
+        impl<'a, T: FromParam<'a>> FromParam<'a> for $Type<T> {
+            type Error = CommaSepError<'a, T::Error>;
+
+            #[inline]
+            fn from_param(param: &'a str) -> Result<Self, Self::Error> {
+                parse_comma_sep(param, $loose).map($Type)
+            }
+        }
+    )+)
+}
+
+impl_comma_sep!(CommaSep: false, CommaSepLoose: true);
@@ -0,0 +1,195 @@
+use std::fmt;
+
+use crate::request::FromParam;
+
+/// A latitude in degrees, between `-90` and `90` inclusive.
+///
+/// A path segment is parsed as a latitude if it parses as an `f64` that is
+/// finite and within range. `NaN`, infinities, and out-of-range values fail to
+/// parse with a [`CoordinateError`]. The [`LatLng`] type parses a latitude and
+/// a [`Longitude`] from a single segment.
+///
+/// # Example
+///
+/// ```rust
+/// # #[macro_use] extern crate rocket;
+/// use rocket::request::{Latitude, Longitude};
+///
+/// #[get("/at/<lat>/<lng>")]
+/// fn at(lat: Latitude, lng: Longitude) -> String {
+///     format!("{}, {}", lat, lng)
+/// }
+/// ```
+///
+/// Inspect the error by requesting a `Result`:
+///
+/// ```rust
+/// use rocket::request::{Latitude, FromParam, CoordinateError};
+///
+/// assert_eq!(*Latitude::from_param("60.17").unwrap(), 60.17);
+/// assert_eq!(Latitude::from_param("90.5"), Err(CoordinateError::OutOfRange(90.5)));
+/// assert_eq!(Latitude::from_param("NaN"), Err(CoordinateError::NotFinite("NaN")));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
+pub struct Latitude(f64);
+
+/// A longitude in degrees, between `-180` and `180` inclusive.
+///
+/// A path segment is parsed as a longitude if it parses as an `f64` that is
+/// finite and within range. `NaN`, infinities, and out-of-range values fail to
+/// parse with a [`CoordinateError`]. See [`Latitude`] for an example.
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
+pub struct Longitude(f64);
+
+/// A pair of a latitude and a longitude, parsed from a single `lat,lng`
+/// segment.
+///
+/// The segment is split at its only `,`, and each side is parsed as a
+/// [`Latitude`] and [`Longitude`], respectively. Whitespace isn't allowed.
+///
+/// # Example
+///
+/// ```rust
+/// # #[macro_use] extern crate rocket;
+/// use rocket::request::{LatLng, FromParam, CoordinateError};
+///
+/// #[get("/near/<point>")]
+/// fn near(point: LatLng) -> String {
+///     format!("near {}", point)
+/// }
+///
+/// let point = LatLng::from_param("60.17,24.94").unwrap();
+/// assert_eq!((*point.lat, *point.lng), (60.17, 24.94));
+/// assert_eq!(LatLng::from_param("60.17"), Err(CoordinateError::Malformed("60.17")));
+/// assert_eq!(LatLng::from_param("0,181"), Err(CoordinateError::OutOfRange(181.0)));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LatLng {
+    /// The latitude.
+    pub lat: Latitude,
+    /// The longitude.
+    pub lng: Longitude,
+}
+
+/// Error returned when parsing a [`Latitude`], [`Longitude`], or [`LatLng`]
+/// fails.
+#[derive(Debug, Clone, PartialEq)]
+pub enum CoordinateError<'a> {
+    /// The value isn't a number. Contains the value.
+    Invalid(&'a str),
+    /// The value is `NaN` or infinite. Contains the value.
+    NotFinite(&'a str),
+    /// The value is a number outside of the valid range. Contains the number.
+    OutOfRange(f64),
+    /// A [`LatLng`] isn't of the form `lat,lng`. Contains the value.
+    Malformed(&'a str),
+}
+
+/// Parses `string` as a finite `f64` no greater than `max` in magnitude.
+fn coordinate(string: &str, max: f64) -> Result<f64, CoordinateError<'_>> {
+    let value: f64 = string.parse().map_err(|_| CoordinateError::Invalid(string))?;
+    if !value.is_finite() {
+        return Err(CoordinateError::NotFinite(string));
+    } else if !(-max..=max).contains(&value) {
+        return Err(CoordinateError::OutOfRange(value));
+    }
+
+    Ok(value)
+}
+
+impl Latitude {
+    /// Consumes `self` and returns the latitude in degrees.
+    #[inline(always)]
+    pub fn into_inner(self) -> f64 {
+        self.0
+    }
+}
+
+impl Longitude {
+    /// Consumes `self` and returns the longitude in degrees.
+    #[inline(always)]
+    pub fn into_inner(self) -> f64 {
+        self.0
+    }
+}
+
+impl<'a> FromParam<'a> for Latitude {
+    type Error = CoordinateError<'a>;
+
+    #[inline]
+    fn from_param(param: &'a str) -> Result<Self, Self::Error> {
+        coordinate(param, 90.0).map(Latitude)
+    }
+}
+
+impl<'a> FromParam<'a> for Longitude {
+    type Error = CoordinateError<'a>;
+
+    #[inline]
+    fn from_param(param: &'a str) -> Result<Self, Self::Error> {
+        coordinate(param, 180.0).map(Longitude)
+    }
+}
+
+impl<'a> FromParam<'a> for LatLng {
+    type Error = CoordinateError<'a>;
+
+    fn from_param(param: &'a str) -> Result<Self, Self::Error> {
+        match param.split_once(',') {
+            Some((lat, lng)) if !lng.contains(',') => Ok(LatLng {
+                lat: Latitude::from_param(lat)?,
+                lng: Longitude::from_param(lng)?,
+            }),
+            _ => Err(CoordinateError::Malformed(param)),
+        }
+    }
+}
+
+impl std::ops::Deref for Latitude {
+    type Target = f64;
+
+    #[inline(always)]
+    fn deref(&self) -> &f64 {
+        &self.0
+    }
+}
+
+impl std::ops::Deref for Longitude {
+    type Target = f64;
+
+    #[inline(always)]
+    fn deref(&self) -> &f64 {
+        &self.0
+    }
+}
+
+impl fmt::Display for Latitude {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl fmt::Display for Longitude {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl fmt::Display for LatLng {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{},{}", self.lat, self.lng)
+    }
+}
+
+impl fmt::Display for CoordinateError<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CoordinateError::Invalid(s) => write!(f, "coordinate `{}` is not a number", s),
+            CoordinateError::NotFinite(s) => write!(f, "coordinate `{}` is not finite", s),
+            CoordinateError::OutOfRange(v) => write!(f, "coordinate {} is out of range", v),
+            CoordinateError::Malformed(s) => write!(f, "`{}` is not of the form `lat,lng`", s),
+        }
+    }
+}
+
+impl std::error::Error for CoordinateError<'_> { }
@@ -0,0 +1,188 @@
+use std::fmt;
+use std::str::FromStr;
+use std::time::Duration;
+
+use crate::form::{self, FromFormField, ValueField};
+use crate::request::FromParam;
+
+/// A [`Duration`] written in human form, as in `30s`, `5m`, or `1h30m`.
+///
+/// A value is one or more components, each a non-negative integer followed
+/// immediately by a unit:
+///
+/// | unit | meaning      |
+/// |------|--------------|
+/// | `ns` | nanoseconds  |
+/// | `us` | microseconds |
+/// | `ms` | milliseconds |
+/// | `s`  | seconds      |
+/// | `m`  | minutes      |
+/// | `h`  | hours        |
+/// | `d`  | days         |
+///
+/// The components are summed, so `1h30m` is 90 minutes. Whitespace, signs,
+/// and fractions aren't allowed. A number without a unit, including a bare
+/// number like `30`, is rejected as ambiguous, and a value that doesn't fit in
+/// a `Duration` is rejected rather than wrapped. Either way, parsing fails
+/// with a [`DurationError`].
+///
+/// `HumanDuration` implements [`FromParam`], [`FromFormField`], and
+/// [`FromStr`]. It dereferences to the `Duration`, which can also be
+/// retrieved with [`HumanDuration::into_inner()`] or via `From`.
+///
+/// # Example
+///
+/// ```rust
+/// # #[macro_use] extern crate rocket;
+/// use rocket::form::Form;
+/// use rocket::request::HumanDuration;
+///
+/// #[get("/cache/<ttl>")]
+/// fn cache(ttl: HumanDuration) -> String {
+///     format!("caching for {} seconds", ttl.as_secs())
+/// }
+///
+/// #[derive(FromForm)]
+/// struct Timeouts {
+///     connect: HumanDuration,
+///     idle: HumanDuration,
+/// }
+///
+/// #[post("/timeouts", data = "<form>")]
+/// fn timeouts(form: Form<Timeouts>) -> String {
+///     format!("connect {:?}, idle {:?}", *form.connect, *form.idle)
+/// }
+/// ```
+///
+/// Inspect the error by requesting a `Result`:
+///
+/// ```rust
+/// use std::time::Duration;
+/// use rocket::request::{HumanDuration, DurationError};
+///
+/// let duration: HumanDuration = "1h30m".parse().unwrap();
+/// assert_eq!(*duration, Duration::from_secs(90 * 60));
+///
+/// assert_eq!("30".parse::<HumanDuration>(), Err(DurationError::MissingUnit));
+/// assert_eq!("3w".parse::<HumanDuration>(), Err(DurationError::UnknownUnit("w".into())));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct HumanDuration(Duration);
+
+/// Error returned when parsing a [`HumanDuration`] fails.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DurationError {
+    /// The value is empty.
+    Empty,
+    /// A unit isn't preceded by a number.
+    MissingNumber,
+    /// A number isn't followed by a unit.
+    MissingUnit,
+    /// A unit isn't one of the supported units. Contains the unit.
+    UnknownUnit(String),
+    /// The value contains a character that is neither an ASCII digit nor an
+    /// ASCII letter. Contains the character.
+    UnexpectedChar(char),
+    /// The duration is too large to be represented.
+    Overflow,
+}
+
+impl HumanDuration {
+    /// Consumes `self` and returns the inner `Duration`.
+    #[inline(always)]
+    pub fn into_inner(self) -> Duration {
+        self.0
+    }
+}
+
+impl FromStr for HumanDuration {
+    type Err = DurationError;
+
+    fn from_str(string: &str) -> Result<Self, Self::Err> {
+        if string.is_empty() {
+            return Err(DurationError::Empty);
+        }
+
+        let mut total = Duration::ZERO;
+        let mut rest = string;
+        while !rest.is_empty() {
+            let digits = rest.find(|c: char| !c.is_ascii_digit()).unwrap_or(rest.len());
+            let (number, tail) = rest.split_at(digits);
+            let letters = tail.find(|c: char| !c.is_ascii_alphabetic()).unwrap_or(tail.len());
+            let (unit, tail) = tail.split_at(letters);
+            match (number.is_empty(), unit.is_empty(), rest.chars().next()) {
+                (true, true, Some(c)) => return Err(DurationError::UnexpectedChar(c)),
+                (true, false, _) => return Err(DurationError::MissingNumber),
+                (false, true, _) => match tail.chars().next() {
+                    Some(c) => return Err(DurationError::UnexpectedChar(c)),
+                    None => return Err(DurationError::MissingUnit),
+                },
+                _ => rest = tail,
+            }
+
+            let n: u64 = number.parse().map_err(|_| DurationError::Overflow)?;
+            let secs = |factor: u64| n.checked_mul(factor).map(Duration::from_secs);
+            let component = match unit {
+                "ns" => Some(Duration::from_nanos(n)),
+                "us" => Some(Duration::from_micros(n)),
+                "ms" => Some(Duration::from_millis(n)),
+                "s" => Some(Duration::from_secs(n)),
+                "m" => secs(60),
+                "h" => secs(60 * 60),
+                "d" => secs(24 * 60 * 60),
+                _ => return Err(DurationError::UnknownUnit(unit.into())),
+            };
+
+            total = component.and_then(|c| total.checked_add(c)).ok_or(DurationError::Overflow)?;
+        }
+
+        Ok(HumanDuration(total))
+    }
+}
+
+impl<'a> FromParam<'a> for HumanDuration {
+    type Error = DurationError;
+
+    #[inline]
+    fn from_param(param: &'a str) -> Result<Self, Self::Error> {
+        param.parse()
+    }
+}
+
+impl<'v> FromFormField<'v> for HumanDuration {
+    #[inline]
+    fn from_value(field: ValueField<'v>) -> form::Result<'v, Self> {
+        Ok(field.value.parse().map_err(form::Error::custom)?)
+    }
+}
+
+impl std::ops::Deref for HumanDuration {
+    type Target = Duration;
+
+    #[inline(always)]
+    fn deref(&self) -> &Duration {
+        &self.0
+    }
+}
+
+impl From<HumanDuration> for Duration {
+    #[inline(always)]
+    fn from(duration: HumanDuration) -> Duration {
+        duration.0
+    }
+}
+
+impl fmt::Display for DurationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DurationError::Empty => f.write_str("empty duration"),
+            DurationError::MissingNumber => f.write_str("duration unit without a number"),
+            DurationError::MissingUnit => f.write_str("duration number without a unit"),
+            DurationError::UnknownUnit(u) => write!(f, "unknown duration unit `{}`", u),
+            DurationError::UnexpectedChar(c) => write!(f, "unexpected {:?} in duration", c),
+            DurationError::Overflow => f.write_str("duration is too large"),
+        }
+    }
+}
+
+impl std::error::Error for DurationError { }
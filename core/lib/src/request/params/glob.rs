@@ -0,0 +1,263 @@
+use std::fmt;
+use std::str::FromStr;
+
+use crate::request::FromParam;
+
+/// A parameter compiled into a glob pattern matcher.
+///
+/// The percent-decoded path segment is compiled as a glob pattern with the
+/// following syntax:
+///
+/// | pattern   | matches                                                  |
+/// |-----------|----------------------------------------------------------|
+/// | `*`       | any sequence of characters, including none               |
+/// | `?`       | exactly one character                                    |
+/// | `[abc]`   | one of the characters in the brackets                    |
+/// | `[a-z]`   | one character in the inclusive range                     |
+/// | `[!a-z]`  | one character _not_ in the class; `[^a-z]` is the same   |
+///
+/// Every other character matches itself. Within a class, a `]` immediately
+/// following the opening `[` or negation, and a `-` that begins or ends the
+/// class, are literal, so a literal `*`, `?`, or `[` can be matched with
+/// `[*]`, `[?]`, or `[[]`. Patterns match whole strings, character by
+/// character, and are case-sensitive.
+///
+/// A class without a closing `]` or with a reversed range such as `[z-a]`
+/// fails to compile with a [`GlobError`].
+///
+/// A `Glob` is compiled once, when the parameter is parsed, so matching it
+/// against many candidates with [`Glob::matches()`] doesn't reparse the
+/// pattern or allocate. Matching takes at worst time proportional to the
+/// product of the pattern's and the candidate's lengths.
+///
+/// # Example
+///
+/// ```rust
+/// # #[macro_use] extern crate rocket;
+/// use rocket::request::Glob;
+///
+/// #[get("/files/<pattern>")]
+/// fn files(pattern: Glob) -> String {
+///     let files = ["notes.txt", "todo.txt", "photo.png"];
+///     let matches: Vec<_> = files.iter().filter(|f| pattern.matches(f)).collect();
+///     format!("{:?}", matches)
+/// }
+/// ```
+///
+/// A `Glob` can also be compiled directly:
+///
+/// ```rust
+/// use rocket::request::{Glob, GlobError};
+///
+/// let glob = Glob::new("*.tx?").unwrap();
+/// assert!(glob.matches("notes.txt"));
+/// assert!(!glob.matches("notes.md"));
+///
+/// let glob: Glob = "report-[0-9][!a-z]".parse().unwrap();
+/// assert!(glob.matches("report-1A"));
+/// assert!(!glob.matches("report-1a"));
+///
+/// assert_eq!(Glob::new("[a-z").unwrap_err(), GlobError::UnclosedClass(0));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Glob {
+    pattern: String,
+    tokens: Vec<GlobToken>,
+}
+
+/// A single compiled element of a [`Glob`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+enum GlobToken {
+    Char(char),
+    Any,
+    Star,
+    Class { negated: bool, ranges: Vec<(char, char)> },
+}
+
+/// Error returned when compiling a [`Glob`] fails.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GlobError {
+    /// A `[` isn't followed by a closing `]`. Contains the byte offset of the
+    /// `[` in the pattern.
+    UnclosedClass(usize),
+    /// A range in a class ends before it starts, as in `[z-a]`. Contains the
+    /// start and end of the range.
+    InvalidRange(char, char),
+}
+
+impl GlobToken {
+    fn matches(&self, c: char) -> bool {
+        match self {
+            GlobToken::Char(expected) => *expected == c,
+            GlobToken::Any | GlobToken::Star => true,
+            GlobToken::Class { negated, ranges } => {
+                ranges.iter().any(|&(start, end)| start <= c && c <= end) != *negated
+            }
+        }
+    }
+}
+
+impl Glob {
+    /// Compiles `pattern` into a `Glob`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::request::{Glob, GlobError};
+    ///
+    /// assert!(Glob::new("img-??.png").is_ok());
+    /// assert_eq!(Glob::new("[z-a]").unwrap_err(), GlobError::InvalidRange('z', 'a'));
+    /// ```
+    pub fn new(pattern: &str) -> Result<Glob, GlobError> {
+        let mut tokens = vec![];
+        let mut chars = pattern.char_indices().peekable();
+        while let Some((i, c)) = chars.next() {
+            let token = match c {
+                '*' if tokens.last() == Some(&GlobToken::Star) => continue,
+                '*' => GlobToken::Star,
+                '?' => GlobToken::Any,
+                '[' => {
+                    let negated = chars.next_if(|&(_, c)| c == '!' || c == '^').is_some();
+                    let mut ranges = vec![];
+                    let mut closed = false;
+                    while let Some((_, c)) = chars.next() {
+                        if c == ']' && !ranges.is_empty() {
+                            closed = true;
+                            break;
+                        }
+
+                        let end = match chars.peek() {
+                            Some(&(_, '-')) => {
+                                let mut lookahead = chars.clone();
+                                lookahead.next();
+                                match lookahead.next() {
+                                    Some((_, end)) if end != ']' => {
+                                        chars = lookahead;
+                                        end
+                                    }
+                                    _ => c,
+                                }
+                            }
+                            _ => c,
+                        };
+
+                        if end < c {
+                            return Err(GlobError::InvalidRange(c, end));
+                        }
+
+                        ranges.push((c, end));
+                    }
+
+                    if !closed {
+                        return Err(GlobError::UnclosedClass(i));
+                    }
+
+                    GlobToken::Class { negated, ranges }
+                }
+                c => GlobToken::Char(c),
+            };
+
+            tokens.push(token);
+        }
+
+        Ok(Glob { pattern: pattern.into(), tokens })
+    }
+
+    /// Returns `true` if `string`, in its entirety, matches `self`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::request::Glob;
+    ///
+    /// let glob = Glob::new("*.txt").unwrap();
+    /// assert!(glob.matches("a.txt"));
+    /// assert!(glob.matches(".txt"));
+    /// assert!(!glob.matches("a.txt.bak"));
+    /// ```
+    pub fn matches(&self, string: &str) -> bool {
+        // Greedy matching that, on a mismatch, retries from the most recent
+        // `*` with it consuming one more character.
+        let (mut t, mut s) = (0, 0);
+        let mut retry: Option<(usize, usize)> = None;
+        loop {
+            match self.tokens.get(t) {
+                Some(GlobToken::Star) => {
+                    retry = Some((t, s));
+                    t += 1;
+                    continue;
+                }
+                Some(token) => match string[s..].chars().next() {
+                    Some(c) if token.matches(c) => {
+                        t += 1;
+                        s += c.len_utf8();
+                        continue;
+                    }
+                    _ => {}
+                },
+                None if s == string.len() => return true,
+                None => {}
+            }
+
+            match retry.and_then(|(t, s)| Some((t, s, string[s..].chars().next()?))) {
+                Some((star, from, c)) => {
+                    retry = Some((star, from + c.len_utf8()));
+                    t = star + 1;
+                    s = from + c.len_utf8();
+                }
+                None => return false,
+            }
+        }
+    }
+
+    /// Returns the uncompiled pattern.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::request::Glob;
+    ///
+    /// assert_eq!(Glob::new("*.rs").unwrap().as_str(), "*.rs");
+    /// ```
+    #[inline(always)]
+    pub fn as_str(&self) -> &str {
+        &self.pattern
+    }
+}
+
+impl FromStr for Glob {
+    type Err = GlobError;
+
+    #[inline]
+    fn from_str(string: &str) -> Result<Self, Self::Err> {
+        Glob::new(string)
+    }
+}
+
+impl<'a> FromParam<'a> for Glob {
+    type Error = GlobError;
+
+    #[inline]
+    fn from_param(param: &'a str) -> Result<Self, Self::Error> {
+        Glob::new(param)
+    }
+}
+
+impl fmt::Display for Glob {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.pattern)
+    }
+}
+
+impl fmt::Display for GlobError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GlobError::UnclosedClass(i) => write!(f, "unclosed character class at byte {}", i),
+            GlobError::InvalidRange(start, end) => {
+                write!(f, "invalid character range `{}-{}`", start, end)
+            }
+        }
+    }
+}
+
+impl std::error::Error for GlobError { }
@@ -0,0 +1,189 @@
+use std::fmt;
+
+use crate::request::FromParam;
+
+/// An integer parameter constrained to the inclusive range `MIN..=MAX`.
+///
+/// The path segment is parsed as a decimal `T`. If parsing fails, or if the
+/// parsed value is outside of `MIN..=MAX`, the guard fails with an
+/// [`IntParamError`] indicating which. `Bounded` dereferences to the inner
+/// integer, which can also be retrieved with [`Bounded::into_inner()`].
+///
+/// `T` may be any integer type but `u128`. Instantiating `Bounded` with
+/// `MIN > MAX` results in a compile-time error.
+///
+/// # Example
+///
+/// ```rust
+/// # #[macro_use] extern crate rocket;
+/// use rocket::request::Bounded;
+///
+/// #[get("/page/<n>")]
+/// fn page(n: Bounded<u32, 1, 100>) -> String {
+///     format!("page {} of 100, offset {}", n, (*n - 1) * 10)
+/// }
+/// ```
+///
+/// Inspect the error by requesting a `Result`:
+///
+/// ```rust
+/// use rocket::request::{Bounded, FromParam, IntParamError};
+///
+/// type Page = Bounded<u32, 1, 100>;
+///
+/// assert_eq!(*Page::from_param("100").unwrap(), 100);
+/// assert_eq!(Page::from_param("0"), Err(IntParamError::TooSmall { value: 0, min: 1 }));
+/// assert_eq!(Page::from_param("101").unwrap_err().to_string(),
+///     "value 101 is greater than the maximum of 100");
+///
+/// assert!(matches!(Page::from_param("-1"), Err(IntParamError::Parse(..))));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Bounded<T, const MIN: i128, const MAX: i128>(T);
+
+/// An integer parameter parsed in radix `R`.
+///
+/// The path segment is parsed with `T::from_str_radix(segment, R)`. No prefix
+/// such as `0x` is accepted, though a leading `+` is and, for signed types, a
+/// leading `-`. If parsing fails, the guard fails with an
+/// [`IntParamError::Parse`]. `Radix` dereferences to the inner integer, which
+/// can also be retrieved with [`Radix::into_inner()`].
+///
+/// `R` must be in `2..=36`; other values result in a compile-time error.
+///
+/// # Example
+///
+/// ```rust
+/// # #[macro_use] extern crate rocket;
+/// use rocket::request::{Radix, FromParam};
+///
+/// #[get("/color/<rgb>")]
+/// fn color(rgb: Radix<u32, 16>) -> String {
+///     let (r, g, b) = (*rgb >> 16, (*rgb >> 8) & 0xFF, *rgb & 0xFF);
+///     format!("rgb({}, {}, {})", r, g, b)
+/// }
+///
+/// assert_eq!(*Radix::<u32, 16>::from_param("ff00aa").unwrap(), 0xff00aa);
+/// assert_eq!(*Radix::<u16, 8>::from_param("755").unwrap(), 0o755);
+/// assert!(Radix::<u32, 16>::from_param("0xff").is_err());
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct Radix<T, const R: u32>(T);
+
+/// Error returned by the [`Bounded`] and [`Radix`] parameter guards.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum IntParamError<'a> {
+    /// The path segment could not be parsed as an integer. Contains the
+    /// segment and the parse error.
+    Parse(&'a str, std::num::ParseIntError),
+    /// The parsed value is less than the minimum bound.
+    TooSmall {
+        /// The parsed value.
+        value: i128,
+        /// The minimum bound.
+        min: i128,
+    },
+    /// The parsed value is greater than the maximum bound.
+    TooLarge {
+        /// The parsed value.
+        value: i128,
+        /// The maximum bound.
+        max: i128,
+    },
+}
+
+impl fmt::Display for IntParamError<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            IntParamError::Parse(s, e) => write!(f, "invalid integer `{}`: {}", s, e),
+            IntParamError::TooSmall { value, min } => {
+                write!(f, "value {} is less than the minimum of {}", value, min)
+            }
+            IntParamError::TooLarge { value, max } => {
+                write!(f, "value {} is greater than the maximum of {}", value, max)
+            }
+        }
+    }
+}
+
+impl std::error::Error for IntParamError<'_> { }
+
+impl<T, const MIN: i128, const MAX: i128> Bounded<T, MIN, MAX> {
+    const VALID: () = assert!(MIN <= MAX, "`Bounded` requires `MIN <= MAX`");
+
+    /// Consumes `self` and returns the inner integer.
+    #[inline(always)]
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+impl<T, const R: u32> Radix<T, R> {
+    const VALID: () = assert!(2 <= R && R <= 36, "`Radix` requires `2 <= R <= 36`");
+
+    /// Consumes `self` and returns the inner integer.
+    #[inline(always)]
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+macro_rules! impl_int_wrappers {
+    ($($T:ty),+) => ($(
+        impl<'a, const MIN: i128, const MAX: i128> FromParam<'a> for Bounded<$T, MIN, MAX> {
+            type Error = IntParamError<'a>;
+
+            #[inline]
+            fn from_param(param: &'a str) -> Result<Self, Self::Error> {
+                let _ = Self::VALID;
+
+                let n = param.parse::<$T>().map_err(|e| IntParamError::Parse(param, e))?;
+                let value = n as i128;
+                if value < MIN {
+                    return Err(IntParamError::TooSmall { value, min: MIN });
+                } else if value > MAX {
+                    return Err(IntParamError::TooLarge { value, max: MAX });
+                }
+
+                Ok(Bounded(n))
+            }
+        }
+
+        impl<'a, const R: u32> FromParam<'a> for Radix<$T, R> {
+            type Error = IntParamError<'a>;
+
+            #[inline]
+            fn from_param(param: &'a str) -> Result<Self, Self::Error> {
+                let _ = Self::VALID;
+
+                <$T>::from_str_radix(param, R)
+                    .map(Radix)
+                    .map_err(|e| IntParamError::Parse(param, e))
+            }
+        }
+    )+)
+}
+
+impl_int_wrappers!(i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, usize);
+
+macro_rules! impl_int_wrapper_traits {
+    ($Type:ident < $T:ident, $(const $P:ident: $PT:ty),+ >) => (
+        impl<$T, $(const $P: $PT),+> std::ops::Deref for $Type<$T, $($P),+> {
+            type Target = $T;
+
+            #[inline(always)]
+            fn deref(&self) -> &$T {
+                &self.0
+            }
+        }
+
+        impl<$T: fmt::Display, $(const $P: $PT),+> fmt::Display for $Type<$T, $($P),+> {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                self.0.fmt(f)
+            }
+        }
+    )
+}
+
+impl_int_wrapper_traits!(Bounded<T, const MIN: i128, const MAX: i128>);
+impl_int_wrapper_traits!(Radix<T, const R: u32>);
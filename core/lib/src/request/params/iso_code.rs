@@ -0,0 +1,196 @@
+use std::fmt;
+
+use crate::request::FromParam;
+
+/// An [ISO 3166-1 alpha-2] country code, like `US` or `FI`.
+///
+/// A path segment is parsed as a country code if it is two letters that,
+/// ignoring case, are an officially assigned ISO 3166-1 alpha-2 code. The
+/// stored code is always uppercase, so `fi`, `Fi`, and `FI` all parse to `FI`.
+/// Any other segment fails to parse with an [`IsoCodeError`].
+///
+/// [ISO 3166-1 alpha-2]: https://www.iso.org/iso-3166-country-codes.html
+///
+/// # Example
+///
+/// ```rust
+/// # #[macro_use] extern crate rocket;
+/// use rocket::request::{CountryCode, LanguageCode};
+///
+/// #[get("/c/<country>/<lang>")]
+/// fn locale(country: CountryCode, lang: LanguageCode) -> String {
+///     format!("{}-{}", lang, country)
+/// }
+/// ```
+///
+/// Inspect the error by requesting a `Result`:
+///
+/// ```rust
+/// use rocket::request::{CountryCode, FromParam, IsoCodeError};
+///
+/// assert_eq!(CountryCode::from_param("fi").unwrap().as_str(), "FI");
+/// assert_eq!(CountryCode::from_param("ZZ"), Err(IsoCodeError::Unknown("ZZ")));
+/// assert_eq!(CountryCode::from_param("FIN"), Err(IsoCodeError::InvalidLength("FIN")));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct CountryCode(&'static str);
+
+/// An [ISO 639-1] language code, like `en` or `fi`.
+///
+/// A path segment is parsed as a language code if it is two letters that,
+/// ignoring case, are an ISO 639-1 code. The stored code is always lowercase,
+/// so `FI`, `Fi`, and `fi` all parse to `fi`. Any other segment fails to parse
+/// with an [`IsoCodeError`].
+///
+/// [ISO 639-1]: https://www.loc.gov/standards/iso639-2/php/code_list.php
+///
+/// # Example
+///
+/// ```rust
+/// use rocket::request::{LanguageCode, FromParam, IsoCodeError};
+///
+/// assert_eq!(LanguageCode::from_param("EN").unwrap().as_str(), "en");
+/// assert_eq!(LanguageCode::from_param("xx"), Err(IsoCodeError::Unknown("xx")));
+/// assert_eq!(LanguageCode::from_param("e"), Err(IsoCodeError::InvalidLength("e")));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct LanguageCode(&'static str);
+
+/// Error returned when parsing a [`CountryCode`] or [`LanguageCode`] fails.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum IsoCodeError<'a> {
+    /// The value isn't exactly two characters long. Contains the value.
+    InvalidLength(&'a str),
+    /// The value isn't a known code. Contains the value.
+    Unknown(&'a str),
+}
+
+/// The officially assigned ISO 3166-1 alpha-2 codes, sorted.
+const COUNTRY_CODES: &[&str] = &[
+    "AD", "AE", "AF", "AG", "AI", "AL", "AM", "AO", "AQ", "AR", "AS", "AT", "AU", "AW", "AX",
+    "AZ", "BA", "BB", "BD", "BE", "BF", "BG", "BH", "BI", "BJ", "BL", "BM", "BN", "BO", "BQ",
+    "BR", "BS", "BT", "BV", "BW", "BY", "BZ", "CA", "CC", "CD", "CF", "CG", "CH", "CI", "CK",
+    "CL", "CM", "CN", "CO", "CR", "CU", "CV", "CW", "CX", "CY", "CZ", "DE", "DJ", "DK", "DM",
+    "DO", "DZ", "EC", "EE", "EG", "EH", "ER", "ES", "ET", "FI", "FJ", "FK", "FM", "FO", "FR",
+    "GA", "GB", "GD", "GE", "GF", "GG", "GH", "GI", "GL", "GM", "GN", "GP", "GQ", "GR", "GS",
+    "GT", "GU", "GW", "GY", "HK", "HM", "HN", "HR", "HT", "HU", "ID", "IE", "IL", "IM", "IN",
+    "IO", "IQ", "IR", "IS", "IT", "JE", "JM", "JO", "JP", "KE", "KG", "KH", "KI", "KM", "KN",
+    "KP", "KR", "KW", "KY", "KZ", "LA", "LB", "LC", "LI", "LK", "LR", "LS", "LT", "LU", "LV",
+    "LY", "MA", "MC", "MD", "ME", "MF", "MG", "MH", "MK", "ML", "MM", "MN", "MO", "MP", "MQ",
+    "MR", "MS", "MT", "MU", "MV", "MW", "MX", "MY", "MZ", "NA", "NC", "NE", "NF", "NG", "NI",
+    "NL", "NO", "NP", "NR", "NU", "NZ", "OM", "PA", "PE", "PF", "PG", "PH", "PK", "PL", "PM",
+    "PN", "PR", "PS", "PT", "PW", "PY", "QA", "RE", "RO", "RS", "RU", "RW", "SA", "SB", "SC",
+    "SD", "SE", "SG", "SH", "SI", "SJ", "SK", "SL", "SM", "SN", "SO", "SR", "SS", "ST", "SV",
+    "SX", "SY", "SZ", "TC", "TD", "TF", "TG", "TH", "TJ", "TK", "TL", "TM", "TN", "TO", "TR",
+    "TT", "TV", "TW", "TZ", "UA", "UG", "UM", "US", "UY", "UZ", "VA", "VC", "VE", "VG", "VI",
+    "VN", "VU", "WF", "WS", "YE", "YT", "ZA", "ZM", "ZW",
+];
+
+/// The ISO 639-1 codes, sorted.
+const LANGUAGE_CODES: &[&str] = &[
+    "aa", "ab", "ae", "af", "ak", "am", "an", "ar", "as", "av", "ay", "az", "ba", "be", "bg",
+    "bi", "bm", "bn", "bo", "br", "bs", "ca", "ce", "ch", "co", "cr", "cs", "cu", "cv", "cy",
+    "da", "de", "dv", "dz", "ee", "el", "en", "eo", "es", "et", "eu", "fa", "ff", "fi", "fj",
+    "fo", "fr", "fy", "ga", "gd", "gl", "gn", "gu", "gv", "ha", "he", "hi", "ho", "hr", "ht",
+    "hu", "hy", "hz", "ia", "id", "ie", "ig", "ii", "ik", "io", "is", "it", "iu", "ja", "jv",
+    "ka", "kg", "ki", "kj", "kk", "kl", "km", "kn", "ko", "kr", "ks", "ku", "kv", "kw", "ky",
+    "la", "lb", "lg", "li", "ln", "lo", "lt", "lu", "lv", "mg", "mh", "mi", "mk", "ml", "mn",
+    "mr", "ms", "mt", "my", "na", "nb", "nd", "ne", "ng", "nl", "nn", "no", "nr", "nv", "ny",
+    "oc", "oj", "om", "or", "os", "pa", "pi", "pl", "ps", "pt", "qu", "rm", "rn", "ro", "ru",
+    "rw", "sa", "sc", "sd", "se", "sg", "si", "sk", "sl", "sm", "sn", "so", "sq", "sr", "ss",
+    "st", "su", "sv", "sw", "ta", "te", "tg", "th", "ti", "tk", "tl", "tn", "to", "tr", "ts",
+    "tt", "tw", "ty", "ug", "uk", "ur", "uz", "ve", "vi", "vo", "wa", "wo", "xh", "yi", "yo",
+    "za", "zh", "zu",
+];
+
+/// Looks up `code`, after normalizing its case with `normalize`, in `table`.
+fn iso_code<'a>(
+    code: &'a str,
+    table: &'static [&'static str],
+    normalize: fn(&str) -> String,
+) -> Result<&'static str, IsoCodeError<'a>> {
+    if code.chars().count() != 2 {
+        return Err(IsoCodeError::InvalidLength(code));
+    }
+
+    let normalized = normalize(code);
+    match table.binary_search(&normalized.as_str()) {
+        Ok(i) => Ok(table[i]),
+        Err(_) => Err(IsoCodeError::Unknown(code)),
+    }
+}
+
+impl CountryCode {
+    /// Returns the uppercase code.
+    #[inline(always)]
+    pub fn as_str(&self) -> &'static str {
+        self.0
+    }
+}
+
+impl LanguageCode {
+    /// Returns the lowercase code.
+    #[inline(always)]
+    pub fn as_str(&self) -> &'static str {
+        self.0
+    }
+}
+
+impl<'a> FromParam<'a> for CountryCode {
+    type Error = IsoCodeError<'a>;
+
+    #[inline]
+    fn from_param(param: &'a str) -> Result<Self, Self::Error> {
+        iso_code(param, COUNTRY_CODES, str::to_ascii_uppercase).map(CountryCode)
+    }
+}
+
+impl<'a> FromParam<'a> for LanguageCode {
+    type Error = IsoCodeError<'a>;
+
+    #[inline]
+    fn from_param(param: &'a str) -> Result<Self, Self::Error> {
+        iso_code(param, LANGUAGE_CODES, str::to_ascii_lowercase).map(LanguageCode)
+    }
+}
+
+impl std::ops::Deref for CountryCode {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        self.0
+    }
+}
+
+impl std::ops::Deref for LanguageCode {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        self.0
+    }
+}
+
+impl fmt::Display for CountryCode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl fmt::Display for LanguageCode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl fmt::Display for IsoCodeError<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            IsoCodeError::InvalidLength(code) => {
+                write!(f, "code `{}` is not two characters long", code)
+            }
+            IsoCodeError::Unknown(code) => write!(f, "unknown code `{}`", code),
+        }
+    }
+}
+
+impl std::error::Error for IsoCodeError<'_> { }
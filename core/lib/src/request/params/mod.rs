@@ -0,0 +1,31 @@
+//! Parameter types: values and wrappers parsed from a single path segment.
+
+mod int;
+mod comma_sep;
+mod safe_segment;
+mod bytes;
+mod trimmed;
+mod with_raw;
+mod duration;
+mod glob;
+mod color;
+mod iso_code;
+mod slug;
+mod coordinate;
+#[cfg(feature = "rust_decimal")]
+mod money;
+
+pub use self::int::{Bounded, Radix, IntParamError};
+pub use self::comma_sep::{CommaSep, CommaSepLoose, CommaSepError};
+pub use self::safe_segment::{SafeSegment, SafeSegmentError};
+pub use self::bytes::{RawBytes, base64, Base64, Base64Error};
+pub use self::trimmed::Trimmed;
+pub use self::with_raw::{WithRaw, WithRawError};
+pub use self::duration::{HumanDuration, DurationError};
+pub use self::glob::{Glob, GlobError};
+pub use self::color::{Color, ColorError};
+pub use self::iso_code::{CountryCode, LanguageCode, IsoCodeError};
+pub use self::slug::{Slug, SlugError};
+pub use self::coordinate::{Latitude, Longitude, LatLng, CoordinateError};
+#[cfg(feature = "rust_decimal")]
+pub use self::money::{Money, DecimalParamError};
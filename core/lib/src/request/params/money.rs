@@ -0,0 +1,160 @@
+use std::fmt;
+
+use crate::request::FromParam;
+
+use rust_decimal_::Decimal;
+
+/// A non-negative amount of money with at most two decimal places.
+///
+/// The path segment must be a plain decimal number: ASCII digits, optionally
+/// followed by a `.` and at least one more digit. Signs, exponents such as
+/// `1e3`, digit separators, and surrounding whitespace are all rejected, so
+/// that a segment is only accepted if it reads unambiguously as an amount. The
+/// amount is parsed exactly as a [`Decimal`], never passing through a float.
+///
+/// If the segment is negative, has more than two decimal places, or isn't a
+/// number at all, the guard fails with the corresponding
+/// [`DecimalParamError`]. The amount is stored with exactly two decimal
+/// places, so `5` and `5.0` display as `5.00`. `Money` dereferences to the
+/// inner `Decimal`, which can also be retrieved with [`Money::into_inner()`].
+///
+/// To accept decimal numbers with any number of decimal places, or negative
+/// ones, use `Decimal` itself as a parameter.
+///
+/// # Example
+///
+/// ```rust
+/// # #[macro_use] extern crate rocket;
+/// use rocket::request::Money;
+///
+/// #[post("/charge/<amount>")]
+/// fn charge(amount: Money) -> String {
+///     format!("charging ${}", amount)
+/// }
+/// ```
+///
+/// Inspect the error by requesting a `Result`:
+///
+/// ```rust
+/// use rocket::request::{Money, FromParam, DecimalParamError};
+///
+/// assert_eq!(Money::from_param("19.99").unwrap().to_string(), "19.99");
+/// assert_eq!(Money::from_param("5").unwrap().to_string(), "5.00");
+///
+/// assert_eq!(Money::from_param("-5"), Err(DecimalParamError::Negative("-5")));
+/// assert_eq!(Money::from_param("1e3"), Err(DecimalParamError::NotANumber("1e3")));
+/// assert_eq!(Money::from_param("0.125").unwrap_err().to_string(),
+///     "`0.125` has 3 decimal places, more than the maximum of 2");
+/// ```
+#[cfg_attr(nightly, doc(cfg(feature = "rust_decimal")))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct Money(Decimal);
+
+/// Error returned by the [`Money`] and `Decimal` parameter guards.
+#[cfg_attr(nightly, doc(cfg(feature = "rust_decimal")))]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DecimalParamError<'a> {
+    /// The path segment isn't a plain decimal number. This includes numbers
+    /// in scientific notation. Contains the segment.
+    NotANumber(&'a str),
+    /// The number has more decimal places than allowed.
+    TooManyDecimalPlaces {
+        /// The path segment.
+        value: &'a str,
+        /// The number of decimal places in the segment.
+        places: usize,
+        /// The maximum number of decimal places allowed.
+        max: usize,
+    },
+    /// The number is too large to be represented exactly. Contains the
+    /// segment.
+    OutOfRange(&'a str),
+    /// The number is negative, but only non-negative numbers are allowed.
+    /// Contains the segment.
+    Negative(&'a str),
+}
+
+/// Parses `param` as a plain decimal number with at most `max` places.
+fn parse_decimal(param: &str, max: usize) -> Result<Decimal, DecimalParamError<'_>> {
+    let is_digits = |s: &str| !s.is_empty() && s.bytes().all(|b| b.is_ascii_digit());
+    let unsigned = param.strip_prefix('-').unwrap_or(param);
+    let (integer, fraction) = match unsigned.split_once('.') {
+        Some((integer, fraction)) if is_digits(fraction) => (integer, fraction),
+        Some(_) => return Err(DecimalParamError::NotANumber(param)),
+        None => (unsigned, ""),
+    };
+
+    if !is_digits(integer) {
+        return Err(DecimalParamError::NotANumber(param));
+    }
+
+    if fraction.len() > max {
+        let places = fraction.len();
+        return Err(DecimalParamError::TooManyDecimalPlaces { value: param, places, max });
+    }
+
+    Decimal::from_str_exact(param).map_err(|_| DecimalParamError::OutOfRange(param))
+}
+
+impl Money {
+    /// Consumes `self` and returns the inner `Decimal`.
+    #[inline(always)]
+    pub fn into_inner(self) -> Decimal {
+        self.0
+    }
+}
+
+#[cfg_attr(nightly, doc(cfg(feature = "rust_decimal")))]
+impl<'a> FromParam<'a> for Decimal {
+    type Error = DecimalParamError<'a>;
+
+    #[inline]
+    fn from_param(param: &'a str) -> Result<Self, Self::Error> {
+        parse_decimal(param, Decimal::MAX_SCALE as usize)
+    }
+}
+
+impl<'a> FromParam<'a> for Money {
+    type Error = DecimalParamError<'a>;
+
+    fn from_param(param: &'a str) -> Result<Self, Self::Error> {
+        let mut amount = parse_decimal(param, 2)?;
+        if param.starts_with('-') {
+            return Err(DecimalParamError::Negative(param));
+        }
+
+        amount.rescale(2);
+        Ok(Money(amount))
+    }
+}
+
+impl std::ops::Deref for Money {
+    type Target = Decimal;
+
+    #[inline(always)]
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl fmt::Display for Money {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl fmt::Display for DecimalParamError<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DecimalParamError::NotANumber(s) => write!(f, "invalid decimal number `{}`", s),
+            DecimalParamError::TooManyDecimalPlaces { value, places, max } => {
+                write!(f, "`{}` has {} decimal places, more than the maximum of {}",
+                    value, places, max)
+            }
+            DecimalParamError::OutOfRange(s) => write!(f, "decimal number `{}` is out of range", s),
+            DecimalParamError::Negative(s) => write!(f, "negative amount `{}`", s),
+        }
+    }
+}
+
+impl std::error::Error for DecimalParamError<'_> { }
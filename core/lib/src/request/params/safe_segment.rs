@@ -0,0 +1,119 @@
+use std::fmt;
+
+use crate::request::FromParam;
+
+/// A percent-decoded path segment that is safe to use as a single file name
+/// component.
+///
+/// Like the `&str` implementation of [`FromParam`], `SafeSegment` receives the
+/// percent-decoded segment. Unlike it, the guard fails, and the request is
+/// forwarded, if the decoded segment could be interpreted as more or less than
+/// a single path component. In particular, a segment of `a%2Fb` decodes to
+/// `a/b` and is rejected, as is `%00`. The guard fails with a
+/// [`SafeSegmentError`] when the decoded segment:
+///
+///   * contains a `/` or `\`, or an ASCII control character, including NUL,
+///     as [`SafeSegmentError::BadChar`];
+///   * is exactly `.` or `..`, as [`SafeSegmentError::DotSegment`];
+///   * did not decode to valid UTF-8, as [`SafeSegmentError::Decode`].
+///
+/// Rocket replaces invalid UTF-8 in decoded segments with `U+FFFD`, so the
+/// last condition is detected by the presence of `U+FFFD` and a segment
+/// containing a legitimately encoded `U+FFFD` is rejected as well.
+///
+/// `SafeSegment` dereferences to `str`.
+///
+/// # Example
+///
+/// ```rust
+/// # #[macro_use] extern crate rocket;
+/// use std::path::Path;
+/// use rocket::request::{FromParam, SafeSegment, SafeSegmentError};
+///
+/// #[get("/avatars/<name>")]
+/// fn avatar(name: SafeSegment<'_>) -> String {
+///     Path::new("/srv/avatars").join(name).display().to_string()
+/// }
+///
+/// assert_eq!(SafeSegment::from_param("día.png").unwrap().as_str(), "día.png");
+/// assert_eq!(SafeSegment::from_param("a/b"), Err(SafeSegmentError::BadChar("a/b", '/')));
+/// assert_eq!(SafeSegment::from_param(".."), Err(SafeSegmentError::DotSegment("..")));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct SafeSegment<'a>(&'a str);
+
+/// Error returned by the [`SafeSegment`] parameter guard.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SafeSegmentError<'a> {
+    /// The decoded segment contains a path-significant character. Contains
+    /// the segment and the first such character.
+    BadChar(&'a str, char),
+    /// The decoded segment is `.` or `..`.
+    DotSegment(&'a str),
+    /// The segment did not percent-decode to valid UTF-8.
+    Decode(&'a str),
+}
+
+impl<'a> SafeSegment<'a> {
+    /// Returns the decoded segment.
+    #[inline(always)]
+    pub fn as_str(&self) -> &'a str {
+        self.0
+    }
+}
+
+impl<'a> FromParam<'a> for SafeSegment<'a> {
+    type Error = SafeSegmentError<'a>;
+
+    fn from_param(param: &'a str) -> Result<Self, Self::Error> {
+        if param.contains(char::REPLACEMENT_CHARACTER) {
+            return Err(SafeSegmentError::Decode(param));
+        }
+
+        if param == "." || param == ".." {
+            return Err(SafeSegmentError::DotSegment(param));
+        }
+
+        let bad_char = |c: &char| matches!(c, '/' | '\\') || c.is_ascii_control();
+        match param.chars().find(bad_char) {
+            Some(c) => Err(SafeSegmentError::BadChar(param, c)),
+            None => Ok(SafeSegment(param)),
+        }
+    }
+}
+
+impl std::ops::Deref for SafeSegment<'_> {
+    type Target = str;
+
+    #[inline(always)]
+    fn deref(&self) -> &str {
+        self.0
+    }
+}
+
+impl AsRef<std::path::Path> for SafeSegment<'_> {
+    #[inline(always)]
+    fn as_ref(&self) -> &std::path::Path {
+        self.0.as_ref()
+    }
+}
+
+impl fmt::Display for SafeSegment<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl fmt::Display for SafeSegmentError<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SafeSegmentError::BadChar(s, c) => {
+                write!(f, "segment `{}` contains forbidden character {:?}", s.escape_debug(), c)
+            }
+            SafeSegmentError::DotSegment(s) => write!(f, "segment `{}` is a dot-segment", s),
+            SafeSegmentError::Decode(s) => write!(f, "segment `{}` is not valid UTF-8", s),
+        }
+    }
+}
+
+impl std::error::Error for SafeSegmentError<'_> { }
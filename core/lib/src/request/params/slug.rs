@@ -0,0 +1,126 @@
+use std::fmt;
+use std::str::FromStr;
+
+use crate::request::FromParam;
+
+/// A URL slug, like `my-first-post`, normalized for stable comparison.
+///
+/// A path segment is parsed as a slug if, ignoring case, it consists only of
+/// ASCII letters, digits, and `-`, and neither begins nor ends with `-`. The
+/// stored slug is normalized: uppercase letters are lowercased and runs of
+/// `-` are collapsed into one, so `My-Slug`, `my--slug`, and `my-slug` all
+/// parse to `my-slug`. Any other segment fails to parse with a [`SlugError`].
+///
+/// # Example
+///
+/// ```rust
+/// # #[macro_use] extern crate rocket;
+/// use rocket::request::Slug;
+///
+/// #[get("/posts/<slug>")]
+/// fn post(slug: Slug) -> String {
+///     format!("post {}", slug)
+/// }
+/// ```
+///
+/// Inspect the error by requesting a `Result`:
+///
+/// ```rust
+/// use rocket::request::{Slug, FromParam, SlugError};
+///
+/// assert_eq!(Slug::from_param("Hello--World").unwrap().as_str(), "hello-world");
+/// assert_eq!(Slug::from_param("-hello"), Err(SlugError::LeadingHyphen));
+/// assert_eq!(Slug::from_param("a_b"), Err(SlugError::InvalidChar('_')));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Slug(String);
+
+/// Error returned when parsing a [`Slug`] fails.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SlugError {
+    /// The value is empty.
+    Empty,
+    /// The value contains a character that is neither an ASCII letter, an
+    /// ASCII digit, nor `-`. Contains the character.
+    InvalidChar(char),
+    /// The value begins with `-`.
+    LeadingHyphen,
+    /// The value ends with `-`.
+    TrailingHyphen,
+}
+
+impl Slug {
+    /// Returns the normalized slug.
+    #[inline(always)]
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    /// Consumes `self` and returns the normalized slug.
+    #[inline(always)]
+    pub fn into_inner(self) -> String {
+        self.0
+    }
+}
+
+impl FromStr for Slug {
+    type Err = SlugError;
+
+    fn from_str(string: &str) -> Result<Self, Self::Err> {
+        if string.is_empty() {
+            return Err(SlugError::Empty);
+        } else if string.starts_with('-') {
+            return Err(SlugError::LeadingHyphen);
+        } else if string.ends_with('-') {
+            return Err(SlugError::TrailingHyphen);
+        }
+
+        let mut slug = String::with_capacity(string.len());
+        for c in string.chars() {
+            match c {
+                '-' if slug.ends_with('-') => continue,
+                'a'..='z' | '0'..='9' | '-' => slug.push(c),
+                'A'..='Z' => slug.push(c.to_ascii_lowercase()),
+                c => return Err(SlugError::InvalidChar(c)),
+            }
+        }
+
+        Ok(Slug(slug))
+    }
+}
+
+impl<'a> FromParam<'a> for Slug {
+    type Error = SlugError;
+
+    #[inline]
+    fn from_param(param: &'a str) -> Result<Self, Self::Error> {
+        param.parse()
+    }
+}
+
+impl std::ops::Deref for Slug {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl fmt::Display for Slug {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl fmt::Display for SlugError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SlugError::Empty => f.write_str("empty slug"),
+            SlugError::InvalidChar(c) => write!(f, "unexpected {:?} in slug", c),
+            SlugError::LeadingHyphen => f.write_str("slug begins with `-`"),
+            SlugError::TrailingHyphen => f.write_str("slug ends with `-`"),
+        }
+    }
+}
+
+impl std::error::Error for SlugError { }
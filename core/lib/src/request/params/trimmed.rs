@@ -0,0 +1,64 @@
+use std::fmt;
+
+use crate::request::FromParam;
+
+/// A `T` parsed from a path segment with surrounding whitespace trimmed.
+///
+/// The percent-decoded segment is stripped of leading and trailing ASCII
+/// whitespace, then parsed with `T`'s [`FromParam`] implementation. Whitespace
+/// inside the segment is preserved. If parsing fails, the guard fails with
+/// `T`'s error. A segment of only whitespace is trimmed to an empty string,
+/// which `T` may or may not accept: `&str` and `String` do, while integers do
+/// not. `Trimmed` dereferences to the inner `T`, which can also be retrieved
+/// with [`Trimmed::into_inner()`].
+///
+/// # Example
+///
+/// ```rust
+/// # #[macro_use] extern crate rocket;
+/// use rocket::request::{Trimmed, FromParam};
+///
+/// #[get("/n/<n>")]
+/// fn number(n: Trimmed<u32>) -> String {
+///     format!("n = {}", n)
+/// }
+///
+/// assert_eq!(*Trimmed::<u32>::from_param(" 42\t").unwrap(), 42);
+/// assert_eq!(*Trimmed::<&str>::from_param(" a b ").unwrap(), "a b");
+/// assert_eq!(*Trimmed::<&str>::from_param("  ").unwrap(), "");
+/// assert!(Trimmed::<u32>::from_param("  ").is_err());
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct Trimmed<T>(T);
+
+impl<T> Trimmed<T> {
+    /// Consumes `self` and returns the inner value.
+    #[inline(always)]
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+impl<'a, T: FromParam<'a>> FromParam<'a> for Trimmed<T> {
+    type Error = T::Error;
+
+    #[inline]
+    fn from_param(param: &'a str) -> Result<Self, Self::Error> {
+        T::from_param(param.trim_matches(|c: char| c.is_ascii_whitespace())).map(Trimmed)
+    }
+}
+
+impl<T> std::ops::Deref for Trimmed<T> {
+    type Target = T;
+
+    #[inline(always)]
+    fn deref(&self) -> &T {
+        &self.0
+    }
+}
+
+impl<T: fmt::Display> fmt::Display for Trimmed<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
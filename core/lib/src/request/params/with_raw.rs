@@ -0,0 +1,102 @@
+use std::fmt;
+
+use crate::http::RawStr;
+use crate::request::FromParam;
+
+/// A `T` parsed from a path segment along with the raw segment itself.
+///
+/// The segment is parsed with `T`'s [`FromParam`] implementation. On success,
+/// [`value`](WithRaw::value) is the parsed `T` and [`raw`](WithRaw::raw) is the
+/// segment exactly as the client sent it: undecoded, with percent-escapes and
+/// their case intact. On failure, the guard fails with a [`WithRawError`] that
+/// carries the raw segment alongside `T`'s error. This makes `WithRaw` useful
+/// for logs, audit trails, and error messages that need to echo the client's
+/// input verbatim. `WithRaw` dereferences to the inner `T`.
+///
+/// When [`FromParam::from_param()`] is called directly, there is no separate
+/// raw segment: `raw` is the `param` that was passed in.
+///
+/// # Example
+///
+/// ```rust
+/// # #[macro_use] extern crate rocket;
+/// use rocket::request::{Color, ColorError, WithRaw, WithRawError};
+///
+/// #[get("/swatch/<color>")]
+/// fn swatch(color: Result<WithRaw<'_, Color>, WithRawError<'_, ColorError>>) -> String {
+///     match color {
+///         Ok(color) => format!("{} (sent as `{}`)", color.value, color.raw),
+///         Err(e) => format!("bad color {}", e),
+///     }
+/// }
+///
+/// # use rocket::local::blocking::Client;
+/// # let client = Client::debug_with(routes![swatch]).unwrap();
+/// # let get = |uri| client.get(uri).dispatch().into_string().unwrap();
+/// # assert_eq!(get("/swatch/%23F80"), "#ff8800 (sent as `%23F80`)");
+/// # assert_eq!(get("/swatch/no%20pe"), "bad color `no%20pe`: unknown color name `no pe`");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct WithRaw<'a, T> {
+    /// The raw, undecoded segment.
+    pub raw: &'a RawStr,
+    /// The value parsed from the segment.
+    pub value: T,
+}
+
+/// Error returned by [`WithRaw`] when parsing the inner `T` fails.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WithRawError<'a, E> {
+    /// The raw, undecoded segment.
+    pub raw: &'a RawStr,
+    /// The error returned by the inner `T`.
+    pub error: E,
+}
+
+impl<'a, T> WithRaw<'a, T> {
+    /// Consumes `self` and returns the parsed value.
+    #[inline(always)]
+    pub fn into_inner(self) -> T {
+        self.value
+    }
+}
+
+impl<'a, T: FromParam<'a>> FromParam<'a> for WithRaw<'a, T> {
+    type Error = WithRawError<'a, T::Error>;
+
+    #[inline]
+    fn from_param(param: &'a str) -> Result<Self, Self::Error> {
+        Self::from_raw_param(param.into(), param)
+    }
+
+    #[inline]
+    fn from_raw_param(raw: &'a RawStr, param: &'a str) -> Result<Self, Self::Error> {
+        match T::from_raw_param(raw, param) {
+            Ok(value) => Ok(WithRaw { raw, value }),
+            Err(error) => Err(WithRawError { raw, error }),
+        }
+    }
+}
+
+impl<T> std::ops::Deref for WithRaw<'_, T> {
+    type Target = T;
+
+    #[inline(always)]
+    fn deref(&self) -> &T {
+        &self.value
+    }
+}
+
+impl<T: fmt::Display> fmt::Display for WithRaw<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.value.fmt(f)
+    }
+}
+
+impl<E: fmt::Display> fmt::Display for WithRawError<'_, E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "`{}`: {}", self.raw, self.error)
+    }
+}
+
+impl<E: std::error::Error> std::error::Error for WithRawError<'_, E> { }
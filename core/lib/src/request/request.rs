@@ -1,22 +1,30 @@
 use std::fmt;
+use std::marker::PhantomData;
 use std::ops::RangeFrom;
 use std::{future::Future, borrow::Cow, sync::Arc};
 use std::net::{IpAddr, SocketAddr};
+use std::any::{Any, TypeId};
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
 
 use yansi::Paint;
 use state::{Container, Storage};
 use futures::future::BoxFuture;
 use atomic::{Atomic, Ordering};
+use parking_lot::Mutex;
 
 use crate::{Rocket, Route, Orbit};
 use crate::request::{FromParam, FromSegments, FromRequest, Outcome};
+use crate::request::Forwarded;
 use crate::form::{self, ValueField, FromForm};
 use crate::data::Limits;
+use crate::config::{IpCidr, HeaderLimits, ForwardedHeader};
 
-use crate::http::{hyper, Method, Header, HeaderMap};
-use crate::http::{ContentType, Accept, MediaType, CookieJar, Cookie};
+use crate::http::{hyper, Method, Header, HeaderMap, RawStr, Status, HttpVersion};
+use crate::http::{UnixPeer, PeerCredentials};
+use crate::http::{ContentType, Accept, MediaType, CookieJar, Cookie, PendingCookie};
 use crate::http::uncased::UncasedStr;
-use crate::http::private::Certificates;
+use crate::http::private::{Certificates, TlsSession};
 use crate::http::uri::{fmt::Path, Origin, Segments, Host, Authority};
 
 /// The type of an incoming web request.
@@ -39,19 +47,213 @@ pub(crate) struct ConnectionMeta {
     pub remote: Option<SocketAddr>,
     #[cfg_attr(not(feature = "mtls"), allow(dead_code))]
     pub client_certificates: Option<Certificates>,
+    pub tls_session: Option<TlsSession>,
+    pub trusted_proxies: Arc<[IpCidr]>,
+    pub header_limits: HeaderLimits,
+    pub http_version: HttpVersion,
+    pub unix_peer: Option<UnixPeer>,
+    pub received_at: Instant,
+}
+
+impl ConnectionMeta {
+    /// The server name sent by the client via SNI during the TLS handshake.
+    pub fn sni_hostname(&self) -> Option<&str> {
+        self.tls_session.as_ref()?.parameters()?.server_name.as_deref()
+    }
+}
+
+/// The peer that initiated a request, whatever the transport it connected
+/// over.
+///
+/// Returned by [`Request::peer_addr()`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PeerAddr<'a> {
+    /// The peer connected over TCP, possibly with TLS, from this address.
+    Tcp(SocketAddr),
+    /// The peer connected over a Unix domain socket.
+    Unix(&'a UnixPeer),
+}
+
+impl PeerAddr<'_> {
+    /// Returns `true` if the peer connected over a Unix domain socket.
+    pub fn is_unix(&self) -> bool {
+        matches!(self, PeerAddr::Unix(_))
+    }
+
+    /// Returns the peer process's credentials if the peer connected over a
+    /// Unix domain socket and the platform reported them.
+    pub fn credentials(&self) -> Option<PeerCredentials> {
+        match self {
+            PeerAddr::Unix(peer) => peer.credentials,
+            PeerAddr::Tcp(_) => None,
+        }
+    }
+}
+
+/// Details of the TLS session over which a request was received.
+///
+/// Returned by [`Request::tls_info()`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TlsInfo {
+    /// The negotiated protocol version, e.g. `TLSv1_3`.
+    pub protocol: String,
+    /// The negotiated cipher suite, e.g. `TLS13_AES_128_GCM_SHA256`.
+    pub cipher_suite: String,
+    /// The subject of the client's certificate, if the client presented a
+    /// valid one via mutual TLS.
+    pub peer_subject: Option<String>,
+    /// The issuer of the client's certificate, if the client presented a
+    /// valid one via mutual TLS.
+    pub peer_issuer: Option<String>,
+}
+
+/// A validated, normalized host, as returned by
+/// [`Request::host_normalized()`].
+///
+/// The `name` is lowercase, and `port` is `None` when the request didn't name
+/// a port or named the default port of the request's scheme.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct NormalizedHost {
+    /// The lowercase domain name or IP address. An IPv6 address is in its
+    /// canonical form and enclosed in brackets, e.g. `[2001:db8::1]`.
+    pub name: String,
+    /// The port, if there is one and it isn't the scheme's default.
+    pub port: Option<u16>,
+}
+
+/// Error returned by [`Request::host_normalized()`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HostError {
+    /// The request doesn't identify a host.
+    Missing,
+    /// The request has more than one `Host` header.
+    Duplicate,
+    /// The host's name is empty.
+    Empty,
+    /// The host's name contains a character that isn't an ASCII letter, digit,
+    /// `-`, `.`, or `_`. Contains the character.
+    InvalidChar(char),
+    /// The host is bracketed but isn't a valid IPv6 address.
+    InvalidIpv6,
+    /// The port isn't a valid port number.
+    InvalidPort,
+}
+
+impl NormalizedHost {
+    /// Parses and normalizes the raw host `string` for a request with the
+    /// scheme `scheme`.
+    fn parse(string: &str, scheme: &str) -> Result<NormalizedHost, HostError> {
+        let (name, port) = match string.strip_prefix('[') {
+            Some(rest) => {
+                let (addr, rest) = rest.split_once(']').ok_or(HostError::InvalidIpv6)?;
+                let addr: std::net::Ipv6Addr = addr.parse().map_err(|_| HostError::InvalidIpv6)?;
+                let port = match rest.chars().next() {
+                    Some(':') => Some(&rest[1..]),
+                    Some(c) => return Err(HostError::InvalidChar(c)),
+                    None => None,
+                };
+
+                (format!("[{}]", addr), port)
+            }
+            None => {
+                let (name, port) = match string.rsplit_once(':') {
+                    Some((name, port)) => (name, Some(port)),
+                    None => (string, None),
+                };
+
+                let valid = |c: char| c.is_ascii_alphanumeric() || matches!(c, '-' | '.' | '_');
+                if let Some(c) = name.chars().find(|&c| !valid(c)) {
+                    return Err(HostError::InvalidChar(c));
+                }
+
+                (name.to_ascii_lowercase(), port)
+            }
+        };
+
+        if name.is_empty() {
+            return Err(HostError::Empty);
+        }
+
+        let port = match port {
+            Some(p) if !p.is_empty() && p.bytes().all(|b| b.is_ascii_digit()) => {
+                Some(p.parse::<u16>().map_err(|_| HostError::InvalidPort)?)
+            }
+            Some(_) => return Err(HostError::InvalidPort),
+            None => None,
+        };
+
+        let default_port = match scheme {
+            "https" => 443,
+            _ => 80,
+        };
+
+        Ok(NormalizedHost { name, port: port.filter(|&p| p != default_port) })
+    }
+}
+
+impl fmt::Display for NormalizedHost {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.name)?;
+        if let Some(port) = self.port {
+            write!(f, ":{}", port)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl fmt::Display for HostError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            HostError::Missing => f.write_str("missing host"),
+            HostError::Duplicate => f.write_str("multiple `Host` headers"),
+            HostError::Empty => f.write_str("empty host name"),
+            HostError::InvalidChar(c) => write!(f, "invalid character {:?} in host", c),
+            HostError::InvalidIpv6 => f.write_str("invalid IPv6 host"),
+            HostError::InvalidPort => f.write_str("invalid host port"),
+        }
+    }
+}
+
+impl std::error::Error for HostError { }
+
+/// A type with a value in a request's local cache.
+///
+/// Returned by [`Request::local_cache_debug()`], which is only available when
+/// compiled with `debug_assertions`.
+#[cfg(debug_assertions)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CachedType {
+    /// The `TypeId` of the cached value.
+    pub type_id: TypeId,
+    /// The name of the cached value's type, as returned by
+    /// [`std::any::type_name()`].
+    pub type_name: &'static str,
 }
 
 /// Information derived from the request.
 pub(crate) struct RequestState<'r> {
     pub rocket: &'r Rocket<Orbit>,
     pub route: Atomic<Option<&'r Route>>,
+    pub original_method: Method,
+    pub rerouted_head: Atomic<bool>,
+    pub in_catcher: Atomic<bool>,
+    pub forward_count: Atomic<usize>,
     pub cookies: CookieJar<'r>,
     pub accept: Storage<Option<Accept>>,
     pub content_type: Storage<Option<ContentType>>,
     pub cache: Arc<Container![Send + Sync]>,
+    #[cfg(debug_assertions)]
+    pub cached_types: Arc<Mutex<Vec<CachedType>>>,
+    pub replaced: HashMap<TypeId, Arc<dyn Any + Send + Sync>>,
     pub host: Option<Host<'r>>,
+    pub log_fields: Arc<Mutex<Vec<(String, String)>>>,
+    pub keyed_cache: Arc<Mutex<KeyedCache>>,
 }
 
+/// Values in the keyed request-local cache, by type and key.
+type KeyedCache = HashMap<(TypeId, String), Box<dyn Any + Send + Sync>>;
+
 impl Request<'_> {
     pub(crate) fn clone(&self) -> Self {
         Request {
@@ -69,11 +271,20 @@ impl RequestState<'_> {
         RequestState {
             rocket: self.rocket,
             route: Atomic::new(self.route.load(Ordering::Acquire)),
+            original_method: self.original_method,
+            rerouted_head: Atomic::new(self.rerouted_head.load(Ordering::Acquire)),
+            in_catcher: Atomic::new(self.in_catcher.load(Ordering::Acquire)),
+            forward_count: Atomic::new(self.forward_count.load(Ordering::Acquire)),
             cookies: self.cookies.clone(),
             accept: self.accept.clone(),
             content_type: self.content_type.clone(),
             cache: self.cache.clone(),
+            #[cfg(debug_assertions)]
+            cached_types: self.cached_types.clone(),
+            replaced: self.replaced.clone(),
             host: self.host.clone(),
+            log_fields: self.log_fields.clone(),
+            keyed_cache: self.keyed_cache.clone(),
         }
     }
 }
@@ -93,15 +304,30 @@ impl<'r> Request<'r> {
             connection: ConnectionMeta {
                 remote: None,
                 client_certificates: None,
+                tls_session: None,
+                trusted_proxies: rocket.config().trusted_proxies.as_slice().into(),
+                header_limits: rocket.config().header_limits,
+                http_version: HttpVersion::Http11,
+                unix_peer: None,
+                received_at: Instant::now(),
             },
             state: RequestState {
                 rocket,
                 route: Atomic::new(None),
+                original_method: method,
+                rerouted_head: Atomic::new(false),
+                in_catcher: Atomic::new(false),
+                forward_count: Atomic::new(0),
                 cookies: CookieJar::new(rocket.config()),
                 accept: Storage::new(),
                 content_type: Storage::new(),
                 cache: Arc::new(<Container![Send + Sync]>::new()),
+                #[cfg(debug_assertions)]
+                cached_types: Arc::default(),
+                replaced: HashMap::new(),
                 host: None,
+                log_fields: Arc::default(),
+                keyed_cache: Arc::default(),
             }
         }
     }
@@ -124,6 +350,35 @@ impl<'r> Request<'r> {
         self.method.load(Ordering::Acquire)
     }
 
+    /// Retrieve the method of `self` as it was received, before any rewriting.
+    ///
+    /// Rocket may route a request with a method other than the one it was
+    /// received with: a `POST` form with a `_method` field or, if
+    /// [`Config::method_override`](crate::Config::method_override) is enabled,
+    /// a `POST` request with an `X-HTTP-Method-Override` header is routed
+    /// with the overriding method, and a `HEAD` request may be routed to `GET`
+    /// routes. [`Request::method()`] returns the method used for routing while
+    /// this method returns the received method, for instance, for logging.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::http::Method;
+    /// # let c = rocket::local::blocking::Client::debug_with(vec![]).unwrap();
+    /// # let mut req = c.post("/");
+    /// # let request = req.inner_mut();
+    ///
+    /// assert_eq!(request.original_method(), Method::Post);
+    ///
+    /// request.set_method(Method::Delete);
+    /// assert_eq!(request.method(), Method::Delete);
+    /// assert_eq!(request.original_method(), Method::Post);
+    /// ```
+    #[inline(always)]
+    pub fn original_method(&self) -> Method {
+        self.state.original_method
+    }
+
     /// Set the method of `self` to `method`.
     ///
     /// # Example
@@ -144,6 +399,107 @@ impl<'r> Request<'r> {
         self._set_method(method);
     }
 
+    /// Returns `true` if Rocket will strip the body of the response to this
+    /// request. This is the case for `HEAD` requests, including while they are
+    /// handled by a `GET` route, in which case [`Request::method()`] returns
+    /// `GET`.
+    ///
+    /// A stripped response keeps its status and headers, and its body keeps
+    /// its size, so the `Content-Length` is exactly that of the equivalent
+    /// `GET` response. A responder that is expensive to run can use this
+    /// method to avoid generating a body that would be discarded, responding
+    /// with a sized body of the right length but no data instead.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::http::Method;
+    ///
+    /// # let c = rocket::local::blocking::Client::debug_with(vec![]).unwrap();
+    /// # let get = |uri| c.get(uri);
+    /// # let head = |uri| c.head(uri);
+    /// assert!(!get("/").strips_body());
+    /// assert!(head("/").strips_body());
+    /// ```
+    #[inline]
+    pub fn strips_body(&self) -> bool {
+        self.method() == Method::Head || self.state.rerouted_head.load(Ordering::Acquire)
+    }
+
+    /// Returns `true` if this request is being handled by an error catcher.
+    ///
+    /// A catcher is invoked with the same request that failed to route or
+    /// whose handler failed, so a request guard the catcher runs, via
+    /// [`Request::guard()`], may already have run for the same request. A
+    /// guard with side effects can use this method to avoid repeating them.
+    /// Values in the [request-local cache](Request::local_cache()) are shared
+    /// by both runs.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # #[macro_use] extern crate rocket;
+    /// use rocket::Request;
+    ///
+    /// #[catch(404)]
+    /// fn not_found(req: &Request) -> String {
+    ///     assert!(req.is_in_catcher());
+    ///     format!("{} not found", req.uri())
+    /// }
+    ///
+    /// # let c = rocket::local::blocking::Client::debug_with(vec![]).unwrap();
+    /// # let get = |uri| c.get(uri);
+    /// assert!(!get("/").is_in_catcher());
+    /// ```
+    #[inline]
+    pub fn is_in_catcher(&self) -> bool {
+        self.state.in_catcher.load(Ordering::Acquire)
+    }
+
+    /// Returns the number of times this request has been forwarded to another
+    /// route so far.
+    ///
+    /// The count is `0` while the first matching route's guards and handler
+    /// run. Each time a route forwards, because a guard or handler returned
+    /// `Forward`, the count is incremented before the next matching route is
+    /// tried, including the `GET` routes tried when a `HEAD` request is
+    /// automatically handled. Since every route runs its guards anew, a guard
+    /// with side effects that mustn't be repeated, such as incrementing a rate
+    /// limit, can use a nonzero count to detect that it is being run again.
+    /// This has nothing to do with redirects, which the client follows with a
+    /// new request.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # #[macro_use] extern crate rocket;
+    /// use rocket::Request;
+    /// use rocket::request::{self, FromRequest};
+    ///
+    /// struct RateLimited;
+    ///
+    /// #[rocket::async_trait]
+    /// impl<'r> FromRequest<'r> for RateLimited {
+    ///     type Error = ();
+    ///
+    ///     async fn from_request(req: &'r Request<'_>) -> request::Outcome<Self, ()> {
+    ///         if req.forward_count() == 0 {
+    ///             // Count the request against the client's limit only once.
+    ///         }
+    ///
+    ///         request::Outcome::Success(RateLimited)
+    ///     }
+    /// }
+    ///
+    /// # let c = rocket::local::blocking::Client::debug_with(vec![]).unwrap();
+    /// # let get = |uri| c.get(uri);
+    /// assert_eq!(get("/").forward_count(), 0);
+    /// ```
+    #[inline]
+    pub fn forward_count(&self) -> usize {
+        self.state.forward_count.load(Ordering::Acquire)
+    }
+
     /// Borrow the [`Origin`] URI from `self`.
     ///
     /// # Example
@@ -184,6 +540,92 @@ impl<'r> Request<'r> {
         self.uri = uri;
     }
 
+    /// Returns the canonical form of the request's URI path, suitable for use
+    /// as a cache key.
+    ///
+    /// Empty segments, including a trailing empty segment, are removed, `.`
+    /// segments are removed, and each `..` segment removes the segment before
+    /// it. A `..` segment at the root is removed without effect, so the result
+    /// never escapes the root. Segments are not percent-decoded: `%2E%2E` is a
+    /// regular segment, not a `..` segment. The query is not part of the
+    /// returned path and is left untouched; see [`Request::uri()`].
+    ///
+    /// The path is borrowed if it is already canonical.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # let c = rocket::local::blocking::Client::debug_with(vec![]).unwrap();
+    /// # let get = |uri| c.get(uri);
+    /// assert_eq!(get("/a//b/../c").normalized_path(), "/a/c");
+    /// assert_eq!(get("/a/./c/?x=/../y").normalized_path(), "/a/c");
+    /// assert_eq!(get("/../../a").normalized_path(), "/a");
+    /// assert_eq!(get("/a/..").normalized_path(), "/");
+    /// ```
+    pub fn normalized_path(&self) -> Cow<'_, str> {
+        let path = self.uri.path().as_str();
+        let is_canonical = match path.strip_prefix('/') {
+            Some("") => true,
+            Some(rest) => rest.split('/').all(|s| !matches!(s, "" | "." | "..")),
+            None => false,
+        };
+
+        if is_canonical {
+            return Cow::Borrowed(path);
+        }
+
+        let mut segments = vec![];
+        for segment in path.split('/') {
+            match segment {
+                "" | "." => continue,
+                ".." => { segments.pop(); }
+                segment => segments.push(segment),
+            }
+        }
+
+        Cow::Owned(format!("/{}", segments.join("/")))
+    }
+
+    /// Returns an iterator over the percent-decoded segments of the request's
+    /// path, including any mount point, or an error if a decoded segment is not
+    /// valid UTF-8.
+    ///
+    /// The segments are those the router matches against: they are decoded
+    /// once, cached in the request's URI, and borrowed here. Where routing
+    /// replaces invalid UTF-8 with `U+FFFD`, this method fails instead, so a
+    /// yielded segment is always exactly what the client sent.
+    ///
+    /// Empty segments, as in `/a//b`, are skipped: `/a//b` yields `a` and `b`,
+    /// just as `/a/b` does. If the path ends in a `/` (and is not `/` itself),
+    /// however, a single final empty segment is yielded so that `/a/` can be
+    /// distinguished from `/a`. `/a//` thus yields `a` and `""`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # let c = rocket::local::blocking::Client::debug_with(vec![]).unwrap();
+    /// # let get = |uri| c.get(uri);
+    /// let segments = |uri| get(uri).path_segments().unwrap()
+    ///     .map(String::from)
+    ///     .collect::<Vec<_>>();
+    ///
+    /// assert_eq!(segments("/a/b"), ["a", "b"]);
+    /// assert_eq!(segments("/a/b/"), ["a", "b", ""]);
+    /// assert_eq!(segments("/a%20b//c%2Fd"), ["a b", "c/d"]);
+    /// assert!(segments("/").is_empty());
+    ///
+    /// assert!(get("/a/%FF").path_segments().is_err());
+    /// ```
+    pub fn path_segments(&self) -> Result<impl Iterator<Item = &str>, std::str::Utf8Error> {
+        let path = self.uri.path();
+        for raw in path.raw_segments().filter(|raw| raw.as_str().contains('%')) {
+            raw.percent_decode()?;
+        }
+
+        let trailing = path.as_str().len() > 1 && path.as_str().ends_with('/');
+        Ok(path.segments().chain(trailing.then(|| "")))
+    }
+
     /// Returns the [`Host`] identified in the request, if any.
     ///
     /// If the request is made via HTTP/1.1 (or earlier), this method returns
@@ -301,6 +743,69 @@ impl<'r> Request<'r> {
         self.state.host = Some(host);
     }
 
+    /// Returns the validated host of `self`, normalized for constructing
+    /// absolute URLs.
+    ///
+    /// The host is read from the `Host` header or, if there is none, from
+    /// [`Request::host()`], which is set from the `:authority` of HTTP/2
+    /// requests. It is then normalized:
+    ///
+    ///   * The name is lowercased. IPv6 addresses, which must be enclosed in
+    ///     brackets, are rewritten in their canonical form, brackets intact.
+    ///   * A port equal to the default port of the request's
+    ///     [`scheme()`](Request::scheme()), `80` for `http` or `443` for
+    ///     `https`, is removed.
+    ///
+    /// A request with more than one `Host` header is rejected with
+    /// [`HostError::Duplicate`]: proxies and servers that disagree on which
+    /// one to use are a source of request smuggling. A name containing any
+    /// character other than an ASCII letter, digit, `-`, `.`, or `_` is
+    /// rejected with [`HostError::InvalidChar`]. In particular,
+    /// internationalized domain names must be sent in their ASCII
+    /// (`xn--`-prefixed) form, as HTTP requires.
+    ///
+    /// Like [`Request::host()`], the result is client-controlled: validation
+    /// ensures it is well-formed, not that it names this server.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::http::Header;
+    /// use rocket::request::HostError;
+    ///
+    /// # let c = rocket::local::blocking::Client::debug_with(vec![]).unwrap();
+    /// # let mut req = c.get("/");
+    /// # let request = req.inner_mut();
+    /// assert_eq!(request.host_normalized(), Err(HostError::Missing));
+    ///
+    /// request.add_header(Header::new("Host", "Rocket.RS:80"));
+    /// let host = request.host_normalized().unwrap();
+    /// assert_eq!(host.name, "rocket.rs");
+    /// assert_eq!(host.port, None);
+    ///
+    /// request.replace_header(Header::new("Host", "[2001:DB8:0::1]:8000"));
+    /// let host = request.host_normalized().unwrap();
+    /// assert_eq!(host.to_string(), "[2001:db8::1]:8000");
+    ///
+    /// request.add_header(Header::new("Host", "evil.com"));
+    /// assert_eq!(request.host_normalized(), Err(HostError::Duplicate));
+    /// ```
+    pub fn host_normalized(&self) -> Result<NormalizedHost, HostError> {
+        let mut headers = self.headers().get("Host");
+        let header = headers.next();
+        if headers.next().is_some() {
+            return Err(HostError::Duplicate);
+        }
+
+        let host: Cow<'_, str> = match (header, self.host()) {
+            (Some(header), _) => header.into(),
+            (None, Some(host)) => host.to_string().into(),
+            (None, None) => return Err(HostError::Missing),
+        };
+
+        NormalizedHost::parse(&host, self.scheme())
+    }
+
     /// Returns the raw address of the remote connection that initiated this
     /// request if the address is known. If the address is not known, `None` is
     /// returned.
@@ -356,6 +861,221 @@ impl<'r> Request<'r> {
         self.connection.remote = Some(address);
     }
 
+    /// Returns `true` if the request was received over a Unix domain socket.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::http::UnixPeer;
+    /// # let c = rocket::local::blocking::Client::debug_with(vec![]).unwrap();
+    /// # let mut req = c.get("/");
+    /// # let request = req.inner_mut();
+    ///
+    /// assert!(!request.is_unix_socket());
+    ///
+    /// request.set_unix_peer(UnixPeer::default());
+    /// assert!(request.is_unix_socket());
+    /// ```
+    #[inline(always)]
+    pub fn is_unix_socket(&self) -> bool {
+        self.connection.unix_peer.is_some()
+    }
+
+    /// Returns the peer that initiated this request, whatever the transport it
+    /// connected over, if the peer is known.
+    ///
+    /// For a request received over a Unix domain socket, this is
+    /// [`PeerAddr::Unix`], which carries the peer process's credentials when
+    /// the platform reports them. Otherwise, it is [`PeerAddr::Tcp`] with the
+    /// [`remote()`](Request::remote()) address, or `None` if the address isn't
+    /// known. Unlike [`client_ip()`](Request::client_ip()), this method never
+    /// consults proxy headers.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::request::PeerAddr;
+    /// use rocket::http::{UnixPeer, PeerCredentials};
+    /// # let c = rocket::local::blocking::Client::debug_with(vec![]).unwrap();
+    /// # let mut req = c.get("/");
+    /// # let request = req.inner_mut();
+    ///
+    /// assert_eq!(request.peer_addr(), None);
+    ///
+    /// let address = "127.0.0.1:8000".parse().unwrap();
+    /// request.set_remote(address);
+    /// assert_eq!(request.peer_addr(), Some(PeerAddr::Tcp(address)));
+    ///
+    /// let credentials = PeerCredentials { uid: 1000, gid: 1000, pid: Some(42) };
+    /// request.set_unix_peer(UnixPeer { path: None, credentials: Some(credentials) });
+    /// assert_eq!(request.peer_addr().unwrap().credentials(), Some(credentials));
+    /// ```
+    pub fn peer_addr(&self) -> Option<PeerAddr<'_>> {
+        match self.connection.unix_peer {
+            Some(ref peer) => Some(PeerAddr::Unix(peer)),
+            None => self.remote().map(PeerAddr::Tcp),
+        }
+    }
+
+    /// Marks `self` as received over a Unix domain socket from `peer`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::http::UnixPeer;
+    /// # let c = rocket::local::blocking::Client::debug_with(vec![]).unwrap();
+    /// # let mut req = c.get("/");
+    /// # let request = req.inner_mut();
+    ///
+    /// request.set_unix_peer(UnixPeer::default());
+    /// assert!(request.peer_addr().unwrap().is_unix());
+    /// ```
+    #[inline(always)]
+    pub fn set_unix_peer(&mut self, peer: UnixPeer) {
+        self.connection.unix_peer = Some(peer);
+    }
+
+    /// Returns the version of HTTP the request was received over.
+    ///
+    /// For a local request, this is `HTTP/1.1` unless changed with
+    /// [`Request::set_http_version()`].
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::http::HttpVersion;
+    /// # let c = rocket::local::blocking::Client::debug_with(vec![]).unwrap();
+    /// # let req = c.get("/");
+    /// # let request = req.inner();
+    ///
+    /// assert_eq!(request.http_version(), HttpVersion::Http11);
+    /// ```
+    #[inline(always)]
+    pub fn http_version(&self) -> HttpVersion {
+        self.connection.http_version
+    }
+
+    /// Sets the HTTP version of `self` to `version`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::http::HttpVersion;
+    /// # let c = rocket::local::blocking::Client::debug_with(vec![]).unwrap();
+    /// # let mut req = c.get("/");
+    /// # let request = req.inner_mut();
+    ///
+    /// request.set_http_version(HttpVersion::Http2);
+    /// assert_eq!(request.http_version(), HttpVersion::Http2);
+    /// ```
+    #[inline(always)]
+    pub fn set_http_version(&mut self, version: HttpVersion) {
+        self.connection.http_version = version;
+    }
+
+    /// Returns the instant at which the request was received: when the server
+    /// finished reading its head, before any fairing, guard, or handler ran.
+    /// For a local request, it is the instant the request was created.
+    ///
+    /// The instant is fixed for the lifetime of the request, making it a
+    /// consistent starting point for measuring latency.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # let c = rocket::local::blocking::Client::debug_with(vec![]).unwrap();
+    /// # let request = c.get("/");
+    /// let elapsed = request.received_at().elapsed();
+    /// assert_eq!(request.received_at(), request.received_at());
+    /// ```
+    #[inline(always)]
+    pub fn received_at(&self) -> Instant {
+        self.connection.received_at
+    }
+
+    /// Returns the instant by which the request should be handled, if it has
+    /// a deadline.
+    ///
+    /// The deadline is [`Request::received_at()`] plus a timeout read from
+    /// the header named by [`Deadline::header`] or, if the header is absent
+    /// or isn't a positive integer, [`Deadline::default`]. The timeout is
+    /// clamped to [`Deadline::max`]. See [`Deadline`] for details.
+    ///
+    /// Rocket doesn't enforce the deadline. Handlers and guards can consult
+    /// it, or [`Request::time_remaining()`], to give up on work that can't
+    /// finish in time, responding with `504 Gateway Timeout`.
+    ///
+    /// [`Deadline`]: crate::config::Deadline
+    /// [`Deadline::header`]: crate::config::Deadline::header
+    /// [`Deadline::default`]: crate::config::Deadline::default
+    /// [`Deadline::max`]: crate::config::Deadline::max
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use std::time::Duration;
+    /// use rocket::http::Header;
+    ///
+    /// # let c = rocket::local::blocking::Client::debug_with(vec![]).unwrap();
+    /// # let mut request = c.get("/");
+    /// // By default, requests without a timeout header have no deadline.
+    /// assert!(request.deadline().is_none());
+    ///
+    /// request.add_header(Header::new("X-Timeout-Ms", "250"));
+    /// let deadline = request.deadline().unwrap();
+    /// assert_eq!(deadline, request.received_at() + Duration::from_millis(250));
+    /// ```
+    pub fn deadline(&self) -> Option<Instant> {
+        let config = &self.rocket().config().deadline;
+        let header = match config.header.as_str() {
+            "" => None,
+            name => self.headers().get_one(name),
+        };
+
+        let timeout = Duration::from_millis(config.timeout_ms(header)?);
+        self.received_at().checked_add(timeout)
+    }
+
+    /// Returns the time remaining until the request's
+    /// [deadline](Request::deadline()), if it has one. Once the deadline has
+    /// passed, returns `Some(Duration::ZERO)`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # #[macro_use] extern crate rocket;
+    /// use std::time::Duration;
+    /// use rocket::Request;
+    /// use rocket::http::Status;
+    /// use rocket::request::{self, FromRequest};
+    ///
+    /// /// Succeeds if there's at least a second left to handle the request.
+    /// struct TimeForReport;
+    ///
+    /// #[rocket::async_trait]
+    /// impl<'r> FromRequest<'r> for TimeForReport {
+    ///     type Error = ();
+    ///
+    ///     async fn from_request(req: &'r Request<'_>) -> request::Outcome<Self, ()> {
+    ///         match req.time_remaining() {
+    ///             Some(left) if left < Duration::from_secs(1) => {
+    ///                 request::Outcome::Failure((Status::GatewayTimeout, ()))
+    ///             }
+    ///             _ => request::Outcome::Success(TimeForReport),
+    ///         }
+    ///     }
+    /// }
+    ///
+    /// #[get("/report")]
+    /// fn report(_time: TimeForReport) -> &'static str {
+    ///     "an expensive report"
+    /// }
+    /// ```
+    pub fn time_remaining(&self) -> Option<Duration> {
+        let deadline = self.deadline()?;
+        Some(deadline.saturating_duration_since(Instant::now()))
+    }
+
     /// Returns the IP address in the "X-Real-IP" header of the request if such
     /// a header exists and contains a valid IP address.
     ///
@@ -414,6 +1134,284 @@ impl<'r> Request<'r> {
         self.real_ip().or_else(|| self.remote().map(|r| r.ip()))
     }
 
+    /// Returns the client's IP address as reported by the chain of trusted
+    /// proxies in the "Forwarded" or "X-Forwarded-For" header, falling back to
+    /// the remote connection's IP address.
+    ///
+    /// Proxies are trusted if their IP address is in one of the blocks in
+    /// [`Config::trusted_proxies`](crate::Config::trusted_proxies). If the
+    /// remote connection is not from a trusted proxy, the headers are ignored
+    /// entirely and the remote IP address is returned. Otherwise, the addresses
+    /// reported in the header selected by
+    /// [`Config::forwarded_header`](crate::Config::forwarded_header) are
+    /// consulted: the values of "X-Forwarded-For" or the `for` parameters of
+    /// the elements of "Forwarded", parsed as by the
+    /// [`Forwarded`](crate::request::Forwarded) guard. The other header is
+    /// never read. The addresses are walked from right to left, and the first
+    /// address not in a trusted block is returned. If every address is trusted,
+    /// the leftmost is returned. If an element is malformed, obfuscated, empty,
+    /// or, for "Forwarded", lacks a `for` parameter, the walk stops and the
+    /// address immediately to its right is returned, as that is the last one
+    /// reported by a trusted proxy.
+    ///
+    /// Unlike [`Request::client_ip()`], this method never consults the
+    /// "X-Real-IP" header. Returns `None` only if the remote address is
+    /// unknown.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use rocket::http::Header;
+    /// # use std::net::IpAddr;
+    /// let config = rocket::Config {
+    ///     trusted_proxies: vec!["10.0.0.0/8".parse().unwrap()],
+    ///     ..rocket::Config::debug_default()
+    /// };
+    ///
+    /// # let rocket = rocket::custom(config);
+    /// # let c = rocket::local::blocking::Client::debug(rocket).unwrap();
+    /// # let mut req = c.get("/");
+    /// # let request = req.inner_mut();
+    /// let ip = |s: &str| s.parse::<IpAddr>().unwrap();
+    /// request.add_header(Header::new("X-Forwarded-For", "1.1.1.1, 8.8.8.8, 10.0.0.2"));
+    ///
+    /// // the remote connection is not from a trusted proxy: ignore the header
+    /// request.set_remote("4.4.4.4:8000".parse().unwrap());
+    /// assert_eq!(request.real_client_ip(), Some(ip("4.4.4.4")));
+    ///
+    /// // the remote connection is from a trusted proxy: walk the header
+    /// request.set_remote("10.0.0.1:8000".parse().unwrap());
+    /// assert_eq!(request.real_client_ip(), Some(ip("8.8.8.8")));
+    /// ```
+    pub fn real_client_ip(&self) -> Option<IpAddr> {
+        self.forwarded_client().map(|(client, _)| client)
+    }
+
+    /// Returns the scheme, `"http"` or `"https"`, that the client used to make
+    /// the request.
+    ///
+    /// If the remote connection is from a trusted proxy, the chain of trusted
+    /// proxies is walked exactly as in [`Request::real_client_ip()`], and the
+    /// scheme reported for the hop that ends the walk is returned. Each hop's
+    /// scheme is the `proto` parameter of its "Forwarded" element or, when
+    /// [`Config::forwarded_header`](crate::Config::forwarded_header) selects
+    /// the "X-Forwarded" headers, the "X-Forwarded-Proto" value at the same
+    /// position, counting from the right, as its "X-Forwarded-For" value. If
+    /// that hop reports no scheme, the scheme reported for the nearest hop to
+    /// its right is used instead. Values other than `http` and `https`,
+    /// compared case-insensitively, are ignored.
+    ///
+    /// Otherwise, including when the remote connection is not from a trusted
+    /// proxy or no trusted proxy reported a scheme, the scheme of the
+    /// connection itself is returned: `"https"` if TLS is enabled and `"http"`
+    /// if not. Schemes are only read from elements appended by trusted
+    /// proxies, so a client cannot claim a scheme it didn't use.
+    ///
+    /// The scheme also determines whether cookies added via
+    /// [`CookieJar::add_private()`] and [`Flash`](crate::response::Flash) are
+    /// marked `Secure` by default.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use rocket::http::Header;
+    /// let config = rocket::Config {
+    ///     trusted_proxies: vec!["10.0.0.0/8".parse().unwrap()],
+    ///     ..rocket::Config::debug_default()
+    /// };
+    ///
+    /// # let rocket = rocket::custom(config);
+    /// # let c = rocket::local::blocking::Client::debug(rocket).unwrap();
+    /// # let mut req = c.get("/");
+    /// # let request = req.inner_mut();
+    /// assert_eq!(request.scheme(), "http");
+    ///
+    /// // the remote connection is not from a trusted proxy: ignore the header
+    /// request.add_header(Header::new("X-Forwarded-Proto", "https"));
+    /// request.set_remote("4.4.4.4:8000".parse().unwrap());
+    /// assert_eq!(request.scheme(), "http");
+    ///
+    /// // the remote connection is from a trusted proxy: use the header
+    /// request.set_remote("10.0.0.1:8000".parse().unwrap());
+    /// assert_eq!(request.scheme(), "https");
+    /// ```
+    pub fn scheme(&self) -> &'static str {
+        let connection = if self.rocket().config().tls_enabled() { "https" } else { "http" };
+        let proto = self.forwarded_client().and_then(|(_, proto)| proto);
+        match proto.as_deref() {
+            Some(p) if p.eq_ignore_ascii_case("https") => "https",
+            Some(p) if p.eq_ignore_ascii_case("http") => "http",
+            Some(p) => {
+                warn_!("Ignoring unknown forwarded protocol: {}", p);
+                connection
+            }
+            None => connection,
+        }
+    }
+
+    /// Walks the chain of trusted proxies, returning the client address that
+    /// ends the walk and the last scheme reported along the way, if any. See
+    /// [`Request::real_client_ip()`] and [`Request::scheme()`].
+    fn forwarded_client(&self) -> Option<(IpAddr, Option<String>)> {
+        let mut client = self.remote()?.ip();
+        let mut proto = None;
+        if !self.is_trusted_proxy(client) {
+            return Some((client, proto));
+        }
+
+        for (ip, hop_proto) in self.forwarded_hops().into_iter().rev() {
+            // `client` is trusted, so this element was appended by a proxy.
+            proto = hop_proto.or(proto);
+            match ip {
+                Some(ip) => client = ip,
+                None => {
+                    debug_!("Malformed forwarded address; stopping at {}.", client);
+                    break;
+                }
+            }
+
+            if !self.is_trusted_proxy(client) {
+                break;
+            }
+        }
+
+        Some((client, proto))
+    }
+
+    /// Returns the address and scheme reported for each hop in the configured
+    /// forwarding headers, in order, with `None` for any element that doesn't
+    /// hold an address or scheme. "X-Forwarded-For" and "X-Forwarded-Proto"
+    /// values are paired from the right.
+    fn forwarded_hops(&self) -> Vec<(Option<IpAddr>, Option<String>)> {
+        let values = |name| self.headers().get(name)
+            .flat_map(|value| value.split(','))
+            .map(|value| value.trim())
+            .collect::<Vec<_>>();
+
+        match self.rocket().config().forwarded_header {
+            ForwardedHeader::Forwarded => Forwarded::elements(self).into_iter()
+                .map(|hop| match hop {
+                    Some(hop) => (hop.for_node.and_then(|node| node.ip()), hop.proto),
+                    None => (None, None),
+                })
+                .collect(),
+            ForwardedHeader::XForwarded => {
+                let (ips, protos) = (values("X-Forwarded-For"), values("X-Forwarded-Proto"));
+                let len = ips.len().max(protos.len());
+                (0..len)
+                    .map(|i| {
+                        let ip = i.checked_sub(len - ips.len()).and_then(|i| ips[i].parse().ok());
+                        let proto = i.checked_sub(len - protos.len())
+                            .map(|i| protos[i])
+                            .filter(|proto| !proto.is_empty())
+                            .map(String::from);
+
+                        (ip, proto)
+                    })
+                    .collect()
+            }
+        }
+    }
+
+    /// Returns `true` if `ip` is in one of the blocks in
+    /// [`Config::trusted_proxies`](crate::Config::trusted_proxies).
+    fn is_trusted_proxy(&self, ip: IpAddr) -> bool {
+        self.connection.trusted_proxies.iter().any(|block| block.contains(ip))
+    }
+
+    /// Returns details of the TLS session over which the request was received
+    /// or `None` if the connection is not a TLS connection.
+    ///
+    /// The protocol version and cipher suite are those negotiated during the
+    /// TLS handshake. When the `mtls` feature is enabled and the client
+    /// presented a certificate, the certificate's subject and issuer are also
+    /// reported; otherwise, they are `None`.
+    ///
+    /// Local requests, made via [`local`](crate::local), are never TLS
+    /// requests.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::Request;
+    ///
+    /// fn audit(request: &Request<'_>) -> String {
+    ///     match request.tls_info() {
+    ///         Some(tls) => format!("{} with {}", tls.protocol, tls.cipher_suite),
+    ///         None => "plaintext".into(),
+    ///     }
+    /// }
+    ///
+    /// # let c = rocket::local::blocking::Client::debug_with(vec![]).unwrap();
+    /// # let req = c.get("/");
+    /// # let request = req.inner();
+    /// assert_eq!(audit(request), "plaintext");
+    /// ```
+    pub fn tls_info(&self) -> Option<TlsInfo> {
+        let session = self.connection.tls_session.as_ref()?;
+        let parameters = session.parameters()?;
+
+        #[allow(unused_mut)]
+        let (mut peer_subject, mut peer_issuer) = (None, None);
+
+        #[cfg(feature = "mtls")]
+        if let Some(chain) = self.connection.client_certificates.as_ref()
+            .and_then(|certs| certs.chain_data())
+        {
+            if let Ok(cert) = crate::mtls::Certificate::parse(chain) {
+                peer_subject = Some(cert.subject().to_string());
+                peer_issuer = Some(cert.issuer().to_string());
+            }
+        }
+
+        Some(TlsInfo {
+            protocol: parameters.protocol.clone(),
+            cipher_suite: parameters.cipher_suite.clone(),
+            peer_subject,
+            peer_issuer,
+        })
+    }
+
+    /// Returns the server name the client requested via SNI (Server Name
+    /// Indication) during the TLS handshake, or `None` if the connection is
+    /// not a TLS connection or the client didn't send a server name.
+    ///
+    /// Unlike the `Host` header, which is sent with every request, the server
+    /// name is fixed for the lifetime of the connection. Comparing the two
+    /// allows rejecting requests for a host other than the one the connection
+    /// was established for, as when a client reuses a connection for several
+    /// virtual hosts. Clients don't send a server name when connecting to an
+    /// IP address.
+    ///
+    /// Local requests, made via [`local`](crate::local), are never TLS
+    /// requests.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::Request;
+    ///
+    /// /// Whether the `Host` of `request` is the host the TLS connection was
+    /// /// established for. Plaintext requests are always considered matching.
+    /// fn host_matches_sni(request: &Request<'_>) -> bool {
+    ///     match (request.sni_hostname(), request.host()) {
+    ///         (Some(sni), Some(host)) => host.domain() == sni,
+    ///         (Some(_), None) => false,
+    ///         (None, _) => request.tls_info().is_none(),
+    ///     }
+    /// }
+    ///
+    /// # let c = rocket::local::blocking::Client::debug_with(vec![]).unwrap();
+    /// # let req = c.get("/");
+    /// # let request = req.inner();
+    /// assert_eq!(request.sni_hostname(), None);
+    /// assert!(host_matches_sni(request));
+    /// ```
+    #[inline]
+    pub fn sni_hostname(&self) -> Option<&str> {
+        self.connection.sni_hostname()
+    }
+
     /// Returns a wrapped borrow to the cookies in `self`.
     ///
     /// [`CookieJar`] implements internal mutability, so this method allows you
@@ -440,6 +1438,75 @@ impl<'r> Request<'r> {
         &self.state.cookies
     }
 
+    /// Returns an iterator over all of the cookies named `name` sent by the
+    /// client, in the order they were received. This is equivalent to
+    /// [`CookieJar::get_all()`] on [`Request::cookies()`].
+    ///
+    /// A client may send several cookies with the same name, as a browser does
+    /// when cookies with one name are set for different paths. Of these,
+    /// [`CookieJar::get()`] returns the first. Since anyone controlling the
+    /// client can append a cookie, checking for duplicates is worthwhile before
+    /// basing a security decision on a cookie.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # #[macro_use] extern crate rocket;
+    /// use rocket::Request;
+    /// use rocket::http::Status;
+    /// use rocket::request::{self, FromRequest};
+    ///
+    /// struct Session<'r>(&'r str);
+    ///
+    /// #[rocket::async_trait]
+    /// impl<'r> FromRequest<'r> for Session<'r> {
+    ///     type Error = ();
+    ///
+    ///     async fn from_request(req: &'r Request<'_>) -> request::Outcome<Self, ()> {
+    ///         let mut sessions = req.cookies_all("session");
+    ///         match (sessions.next(), sessions.next()) {
+    ///             (Some(session), None) => request::Outcome::Success(Session(session.value())),
+    ///             (Some(_), Some(_)) => request::Outcome::Failure((Status::BadRequest, ())),
+    ///             (None, _) => request::Outcome::Forward(()),
+    ///         }
+    ///     }
+    /// }
+    /// ```
+    #[inline]
+    pub fn cookies_all(&self, name: &str) -> impl Iterator<Item = &Cookie<'static>> {
+        self.state.cookies.get_all(name)
+    }
+
+    /// Returns the cookie additions and removals queued during this request,
+    /// in the order they were made. Changing a cookie's attributes by adding
+    /// it again appears as another addition.
+    ///
+    /// The queue is the same one observed by [`CookieJar::get_pending()`]. It
+    /// is sent with the response and remains readable from response fairings,
+    /// which can use it to audit the cookies set by a request. If a catcher
+    /// handles the request, changes made before the catcher ran are discarded.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::http::{Cookie, PendingCookie};
+    ///
+    /// # let c = rocket::local::blocking::Client::debug_with(vec![]).unwrap();
+    /// # let request = c.get("/");
+    /// # let req = request.inner();
+    /// req.cookies().add(Cookie::new("theme", "dark"));
+    /// req.cookies().remove(Cookie::named("session"));
+    ///
+    /// let pending = req.pending_cookies();
+    /// assert_eq!(pending.len(), 2);
+    /// assert!(matches!(&pending[0], PendingCookie::Add(c) if c.value() == "dark"));
+    /// assert!(matches!(&pending[1], PendingCookie::Remove(c) if c.name() == "session"));
+    /// ```
+    #[inline]
+    pub fn pending_cookies(&self) -> Vec<PendingCookie> {
+        self.state.cookies.pending()
+    }
+
     /// Returns a [`HeaderMap`] of all of the headers in `self`.
     ///
     /// # Example
@@ -459,6 +1526,34 @@ impl<'r> Request<'r> {
         &self.headers
     }
 
+    /// Returns an iterator over all of the values of the header named `name`
+    /// in the order in which they arrived. The name is matched
+    /// case-insensitively. If there is no such header, the iterator is empty.
+    ///
+    /// This is useful for headers whose values are semantically ordered, like
+    /// `Via` and `Forwarded`, where each proxy appends its own value.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::http::Header;
+    ///
+    /// # let c = rocket::local::blocking::Client::debug_with(vec![]).unwrap();
+    /// # let get = |uri| c.get(uri);
+    /// let req = get("/")
+    ///     .header(Header::new("Via", "1.0 fred"))
+    ///     .header(Header::new("X-Other", "other"))
+    ///     .header(Header::new("via", "1.1 nowhere.com"));
+    ///
+    /// let via: Vec<_> = req.headers_get_all("VIA").collect();
+    /// assert_eq!(via, ["1.0 fred", "1.1 nowhere.com"]);
+    /// assert_eq!(req.headers_get_all("Forwarded").count(), 0);
+    /// ```
+    #[inline(always)]
+    pub fn headers_get_all<'a>(&'a self, name: &str) -> impl Iterator<Item = &'a str> {
+        self.headers.get(name)
+    }
+
     /// Add `header` to `self`'s headers. The type of `header` can be any type
     /// that implements the `Into<Header>` trait. This includes common types
     /// such as [`ContentType`] and [`Accept`].
@@ -535,6 +1630,54 @@ impl<'r> Request<'r> {
         }).as_ref()
     }
 
+    /// Returns the length of the body in bytes as declared by the
+    /// Content-Length header of `self`, if it is present and valid.
+    ///
+    /// Surrounding whitespace is ignored. A header may be repeated, or contain
+    /// a comma-separated list of values, as long as every value is identical.
+    /// Returns `None` if the header is not present, if any value is not a
+    /// non-negative decimal integer, if values conflict, or if the value
+    /// doesn't fit in a `u64`.
+    ///
+    /// Note that the declared length is provided by the client and is not
+    /// validated against the request's actual body.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::http::Header;
+    ///
+    /// # let c = rocket::local::blocking::Client::debug_with(vec![]).unwrap();
+    /// # let get = |uri| c.get(uri);
+    /// assert_eq!(get("/").content_length(), None);
+    ///
+    /// let req = get("/").header(Header::new("Content-Length", " 1024 "));
+    /// assert_eq!(req.content_length(), Some(1024));
+    ///
+    /// let req = req.header(Header::new("Content-Length", "1024"));
+    /// assert_eq!(req.content_length(), Some(1024));
+    ///
+    /// let req = req.header(Header::new("Content-Length", "512"));
+    /// assert_eq!(req.content_length(), None);
+    /// ```
+    pub fn content_length(&self) -> Option<u64> {
+        let mut length = None;
+        for value in self.headers().get("Content-Length").flat_map(|v| v.split(',')) {
+            let value = value.trim();
+            if value.is_empty() || !value.bytes().all(|b| b.is_ascii_digit()) {
+                return None;
+            }
+
+            let value = value.parse::<u64>().ok()?;
+            match length {
+                Some(length) if length != value => return None,
+                _ => length = Some(value),
+            }
+        }
+
+        length
+    }
+
     /// Returns the Accept header of `self`. If the header is not present,
     /// returns `None`.
     ///
@@ -555,6 +1698,34 @@ impl<'r> Request<'r> {
         }).as_ref()
     }
 
+    /// Returns `true` if a response of the concrete `media_type` is acceptable
+    /// to the client according to the Accept header of `self`.
+    ///
+    /// Wildcards like `*/*` and `text/*` accept any media type they cover
+    /// unless a more specific media range excludes it with `q=0`. See
+    /// [`Accept::accepts()`] for details. If the Accept header is missing, or
+    /// can't be parsed, every media type is acceptable.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::http::{Accept, Header, MediaType};
+    ///
+    /// # let c = rocket::local::blocking::Client::debug_with(vec![]).unwrap();
+    /// # let get = |uri| c.get(uri);
+    /// assert!(get("/").accepts(&MediaType::JSON));
+    /// assert!(get("/").header(Accept::JSON).accepts(&MediaType::JSON));
+    /// assert!(!get("/").header(Accept::HTML).accepts(&MediaType::JSON));
+    ///
+    /// let req = get("/").header(Header::new("Accept", "*/*, application/json; q=0"));
+    /// assert!(req.accepts(&MediaType::HTML));
+    /// assert!(!req.accepts(&MediaType::JSON));
+    /// ```
+    #[inline]
+    pub fn accepts(&self, media_type: &MediaType) -> bool {
+        self.accept().map_or(true, |accept| accept.accepts(media_type))
+    }
+
     /// Returns the media type "format" of the request.
     ///
     /// The "format" of a request is either the Content-Type, if the request
@@ -660,7 +1831,15 @@ impl<'r> Request<'r> {
     ///
     /// This method returns `Some` any time a handler or its guards are being
     /// invoked. This method returns `None` _before_ routing has commenced; this
-    /// includes during request fairing callbacks.
+    /// includes during request fairing callbacks. It also returns `None` in a
+    /// catcher if no route matched the request at all.
+    ///
+    /// When a route forwards, the next matching route replaces it before that
+    /// route's guards run, so a guard always sees the route it is guarding.
+    /// The route's [`uri`](Route::uri) is the mounted URI _pattern_, such as
+    /// `/api/users/<id>`, not the request's concrete path, making it a suitable
+    /// low-cardinality label alongside [`name`](Route::name),
+    /// [`method`](Route::method), and [`rank`](Route::rank).
     ///
     /// # Example
     ///
@@ -669,6 +1848,34 @@ impl<'r> Request<'r> {
     /// # let request = c.get("/");
     /// let route = request.route();
     /// ```
+    ///
+    /// Label metrics by route from a request guard:
+    ///
+    /// ```rust
+    /// # #[macro_use] extern crate rocket;
+    /// use rocket::request::{self, Request, FromRequest};
+    ///
+    /// struct MetricsLabel(String);
+    ///
+    /// #[rocket::async_trait]
+    /// impl<'r> FromRequest<'r> for MetricsLabel {
+    ///     type Error = ();
+    ///
+    ///     async fn from_request(req: &'r Request<'_>) -> request::Outcome<Self, ()> {
+    ///         let label = match req.route() {
+    ///             Some(route) => format!("{} {}", route.method, route.uri),
+    ///             None => "unrouted".into(),
+    ///         };
+    ///
+    ///         request::Outcome::Success(MetricsLabel(label))
+    ///     }
+    /// }
+    ///
+    /// #[get("/users/<id>")]
+    /// fn user(id: usize, label: MetricsLabel) -> String {
+    ///     label.0 // "GET /users/<id>"
+    /// }
+    /// ```
     #[inline(always)]
     pub fn route(&self) -> Option<&'r Route> {
         self.state.route.load(Ordering::Acquire)
@@ -703,11 +1910,16 @@ impl<'r> Request<'r> {
     /// Different values of the same type _cannot_ be cached without using a
     /// proxy, wrapper type. To avoid the need to write these manually, or for
     /// libraries wishing to store values of public types, use the
-    /// [`local_cache!`](crate::request::local_cache) or
-    /// [`local_cache_once!`](crate::request::local_cache_once) macros to
+    /// [`local_cache!`](crate::request::local_cache),
+    /// [`local_cache_once!`](crate::request::local_cache_once), or
+    /// [`local_cache_async!`](crate::request::local_cache_async) macros to
     /// generate a locally anonymous wrapper type, store, and retrieve the
     /// wrapped value from request-local cache.
     ///
+    /// The cache lives as long as the request. In particular, if the request
+    /// is handled by an error catcher, values cached by guards or handlers
+    /// that ran earlier remain available to the catcher.
+    ///
     /// # Example
     ///
     /// ```rust
@@ -725,11 +1937,8 @@ impl<'r> Request<'r> {
         where F: FnOnce() -> T,
               T: Send + Sync + 'static
     {
-        self.state.cache.try_get()
-            .unwrap_or_else(|| {
-                self.state.cache.set(f());
-                self.state.cache.get()
-            })
+        self.local_cache_get()
+            .unwrap_or_else(|| self.local_cache_set(f()))
     }
 
     /// Retrieves the cached value for type `T` from the request-local cached
@@ -737,6 +1946,19 @@ impl<'r> Request<'r> {
     /// request, `fut` is `await`ed to produce the value which is subsequently
     /// returned.
     ///
+    /// `fut` is only polled if no value of type `T` is cached, so an `async`
+    /// block passed to this method acts as a lazy initializer. The cache is
+    /// shared with [`Request::local_cache()`]: once a value is stored by either
+    /// method, both return it for the remainder of the request. To cache
+    /// values of public types without conflicts, use the
+    /// [`local_cache_async!`](crate::request::local_cache_async) macro.
+    ///
+    /// As with `local_cache()`, the first store for a given type wins. If two
+    /// calls for the same type are awaited concurrently, as with `join!`, both
+    /// futures may run to completion, but only the first to complete is stored
+    /// and both calls return that value. Sequential calls never run more than
+    /// one future.
+    ///
     /// # Example
     ///
     /// ```rust
@@ -754,18 +1976,374 @@ impl<'r> Request<'r> {
     /// }).await;
     /// # })
     /// ```
+    ///
+    /// Values stored asynchronously are visible to synchronous reads:
+    ///
+    /// ```rust
+    /// # rocket::async_test(async move {
+    /// # let c = rocket::local::asynchronous::Client::debug_with(vec![]).await.unwrap();
+    /// # let request = c.get("/");
+    /// let value = request.local_cache_async(async { "hello" }).await;
+    /// assert_eq!(*value, "hello");
+    ///
+    /// // The cached value is returned; the new initializers never run.
+    /// assert_eq!(*request.local_cache(|| "goodbye"), "hello");
+    /// assert_eq!(*request.local_cache_async(async { "goodbye" }).await, "hello");
+    /// # })
+    /// ```
     #[inline]
     pub async fn local_cache_async<'a, T, F>(&'a self, fut: F) -> &'a T
         where F: Future<Output = T>,
               T: Send + Sync + 'static
     {
-        match self.state.cache.try_get() {
+        match self.local_cache_get() {
             Some(s) => s,
-            None => {
-                self.state.cache.set(fut.await);
-                self.state.cache.get()
+            None => self.local_cache_set(fut.await)
+        }
+    }
+
+    /// Retrieves the value of type `T` derived from the managed state of type
+    /// `S` for this request, calling `f` with the managed state to compute and
+    /// cache the value if it hasn't already been derived. Returns `None` if no
+    /// state of type `S` is managed.
+    ///
+    /// This is shorthand for a [`Request::local_cache()`] lookup through a
+    /// private wrapper keyed by both `S` and `T`, so values derived this way
+    /// never conflict with values of type `T` cached directly or derived from
+    /// another state type. As with `local_cache()`, the first store wins: at
+    /// most one `T` is derived from an `S` per request, no matter how many
+    /// guards ask for it, and `f` is not called again even if it would produce
+    /// a different value. Use a wrapper type for `T` to derive several values
+    /// of the same type from the same state.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use std::collections::HashMap;
+    ///
+    /// struct Tenants(HashMap<&'static str, &'static str>);
+    ///
+    /// /// The name of the tenant the request is for, if any.
+    /// struct TenantName(Option<&'static str>);
+    ///
+    /// # let tenants = Tenants(HashMap::from([("acme", "ACME Corp.")]));
+    /// # let rocket = rocket::build().manage(tenants);
+    /// # let c = rocket::local::blocking::Client::debug(rocket).unwrap();
+    /// # let request = c.get("/").header(rocket::http::Header::new("X-Tenant", "acme"));
+    /// let tenant = request.local_derive(|tenants: &Tenants| {
+    ///     let id = request.headers().get_one("X-Tenant");
+    ///     TenantName(id.and_then(|id| tenants.0.get(id).copied()))
+    /// });
+    ///
+    /// assert_eq!(tenant.unwrap().0, Some("ACME Corp."));
+    ///
+    /// // The derived value is cached: `f` isn't called again.
+    /// let tenant = request.local_derive(|_: &Tenants| -> TenantName { unreachable!() });
+    /// assert_eq!(tenant.unwrap().0, Some("ACME Corp."));
+    ///
+    /// // Deriving from unmanaged state returns `None`.
+    /// assert!(request.local_derive(|n: &usize| *n + 1).is_none());
+    /// ```
+    #[inline]
+    pub fn local_derive<S, T, F>(&self, f: F) -> Option<&T>
+        where F: FnOnce(&S) -> T,
+              S: Send + Sync + 'static,
+              T: Send + Sync + 'static
+    {
+        struct Derived<S, T>(T, PhantomData<fn() -> S>);
+
+        if let Some(derived) = self.local_cache_get::<Derived<S, T>>() {
+            return Some(&derived.0);
+        }
+
+        let state = self.rocket().state::<S>()?;
+        Some(&self.local_cache_set(Derived::<S, T>(f(state), PhantomData)).0)
+    }
+
+    /// Retrieves the cached value for type `T` from the request-local cached
+    /// state of `self`, if any, without storing a value if there is none.
+    ///
+    /// Catchers receive the same request the failing guards saw, so this is
+    /// also how a catcher recovers a payload stored by
+    /// [`Outcome::error_with()`](crate::request::Outcome::error_with()).
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # let c = rocket::local::blocking::Client::debug_with(vec![]).unwrap();
+    /// # let request = c.get("/");
+    /// assert_eq!(request.local_cache_get::<&str>(), None);
+    ///
+    /// request.local_cache(|| "hello");
+    /// assert_eq!(request.local_cache_get::<&str>(), Some(&"hello"));
+    /// ```
+    #[inline]
+    pub fn local_cache_get<T: Send + Sync + 'static>(&self) -> Option<&T> {
+        if !self.state.replaced.is_empty() {
+            if let Some(value) = self.state.replaced.get(&TypeId::of::<T>()) {
+                return value.downcast_ref();
             }
         }
+
+        self.state.cache.try_get()
+    }
+
+    /// Stores `value` in the request-local cached state of `self`, replacing
+    /// any value of type `T` previously cached, and returns a reference to the
+    /// newly cached value. Subsequent reads via [`Request::local_cache()`],
+    /// [`Request::local_cache_async()`], and [`Request::local_cache_get()`]
+    /// return the new value.
+    ///
+    /// # Aliasing
+    ///
+    /// Values stored with `local_cache()` are never moved or dropped while the
+    /// request lives, which is why references to them can be handed out from a
+    /// shared `&Request`. Replacing a value would invalidate such references,
+    /// so this method requires `&mut self`: the borrow checker guarantees that
+    /// no reference to a previously cached value is alive during replacement.
+    /// In practice, this means values can be replaced in places where Rocket
+    /// hands out a `&mut Request`, such as [`Fairing::on_request()`], but not
+    /// from request guards, which receive a `&Request`.
+    ///
+    /// # Lifetime and Clones
+    ///
+    /// A value cached by `local_cache()` is hidden, not dropped, when it is
+    /// replaced: it lives until the request and all of its clones are
+    /// dropped. A value stored by this method, on the other hand, is dropped
+    /// as soon as it is itself replaced, unless a clone of the request still
+    /// holds it, and otherwise when the request is dropped.
+    ///
+    /// Unlike values cached by `local_cache()`, replacements are not shared
+    /// between a request and its clones. A clone made before a replacement
+    /// keeps reading the value it saw when it was made, and a replacement in a
+    /// clone is not seen by the original.
+    ///
+    /// [`Fairing::on_request()`]: crate::fairing::Fairing::on_request()
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # let c = rocket::local::blocking::Client::debug_with(vec![]).unwrap();
+    /// # let mut local_request = c.get("/");
+    /// # let request = local_request.inner_mut();
+    /// #[derive(Debug, PartialEq)]
+    /// struct Token(&'static str);
+    ///
+    /// assert_eq!(request.local_cache(|| Token("stale")), &Token("stale"));
+    ///
+    /// // Replace the token; the old reference can no longer be used.
+    /// assert_eq!(request.local_cache_replace(Token("fresh")), &Token("fresh"));
+    /// assert_eq!(request.local_cache(|| Token("unused")), &Token("fresh"));
+    /// assert_eq!(request.local_cache_get::<Token>(), Some(&Token("fresh")));
+    /// ```
+    #[inline]
+    pub fn local_cache_replace<T: Send + Sync + 'static>(&mut self, value: T) -> &T {
+        let key = TypeId::of::<T>();
+        self.state.replaced.insert(key, Arc::new(value));
+
+        #[cfg(debug_assertions)]
+        self.record_cached_type::<T>();
+
+        self.state.replaced[&key].downcast_ref().expect("replaced value has type `T`")
+    }
+
+    /// Stores `value` in the local cache unless a value of type `T` is already
+    /// stored, then returns the stored value.
+    fn local_cache_set<T: Send + Sync + 'static>(&self, value: T) -> &T {
+        #[cfg(debug_assertions)]
+        if self.state.cache.set(value) {
+            self.record_cached_type::<T>();
+        }
+
+        #[cfg(not(debug_assertions))]
+        self.state.cache.set(value);
+
+        self.state.cache.get()
+    }
+
+    #[cfg(debug_assertions)]
+    fn record_cached_type<T: 'static>(&self) {
+        let mut types = self.state.cached_types.lock();
+        if !types.iter().any(|t| t.type_id == TypeId::of::<T>()) {
+            types.push(CachedType {
+                type_id: TypeId::of::<T>(),
+                type_name: std::any::type_name::<T>(),
+            });
+        }
+    }
+
+    /// Returns the types of all values currently in the request-local cache,
+    /// in the order they were first stored. Intended for diagnosing guard
+    /// ordering issues, for instance from a logging fairing.
+    ///
+    /// Only types are listed: the cached values themselves can't be rendered
+    /// since there is no way to tell whether an arbitrary cached type
+    /// implements `Debug`. Use [`Request::local_cache_get()`] to inspect a
+    /// value of a known type. Listing the cache does not affect its contents.
+    ///
+    /// This method is only available when compiled with `debug_assertions`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # let c = rocket::local::blocking::Client::debug_with(vec![]).unwrap();
+    /// # let request = c.get("/");
+    /// request.local_cache(|| "hello");
+    /// request.local_cache(|| 42usize);
+    ///
+    /// let names: Vec<_> = request.local_cache_debug().iter().map(|t| t.type_name).collect();
+    /// assert_eq!(names, ["&str", "usize"]);
+    /// ```
+    #[cfg(debug_assertions)]
+    pub fn local_cache_debug(&self) -> Vec<CachedType> {
+        self.state.cached_types.lock().clone()
+    }
+
+    /// Retrieves the value of type `T` cached under `key` in the keyed
+    /// request-local cache of `self`, computing and storing it with `f` if
+    /// there is none.
+    ///
+    /// Unlike [`Request::local_cache()`], which holds at most one value per
+    /// type, this cache holds one value per type _and_ key, so several values
+    /// of the same type can be memoized independently. The two caches are
+    /// separate: a value stored in one is never visible through the other.
+    ///
+    /// As with `local_cache()`, the first store for a given type and key wins,
+    /// and values are never moved or dropped while the request lives. `f` is
+    /// called without holding any lock, so it may itself use the cache.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # let c = rocket::local::blocking::Client::debug_with(vec![]).unwrap();
+    /// # let request = c.get("/");
+    /// let a = request.local_map_get_or_insert("a", || String::from("first"));
+    /// let b = request.local_map_get_or_insert("b", || String::from("second"));
+    /// assert_eq!((a.as_str(), b.as_str()), ("first", "second"));
+    ///
+    /// // The first store for a key wins.
+    /// let a = request.local_map_get_or_insert("a", || String::from("unused"));
+    /// assert_eq!(a, "first");
+    ///
+    /// // The same key holds an independent value for each type.
+    /// assert_eq!(*request.local_map_get_or_insert("a", || 7usize), 7);
+    /// ```
+    pub fn local_map_get_or_insert<T, F>(&self, key: &str, f: F) -> &T
+        where F: FnOnce() -> T, T: Send + Sync + 'static
+    {
+        if let Some(value) = self.local_map_get(key) {
+            return value;
+        }
+
+        let value = f();
+        let mut cache = self.state.keyed_cache.lock();
+        let value = cache.entry((TypeId::of::<T>(), key.into()))
+            .or_insert_with(|| Box::new(value))
+            .downcast_ref::<T>()
+            .expect("keyed cache value has type `T`");
+
+        // SAFETY: See `local_map_get()`.
+        unsafe { &*(value as *const T) }
+    }
+
+    /// Retrieves the value of type `T` cached under `key` in the keyed
+    /// request-local cache of `self`, if any. See
+    /// [`Request::local_map_get_or_insert()`].
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # let c = rocket::local::blocking::Client::debug_with(vec![]).unwrap();
+    /// # let request = c.get("/");
+    /// assert_eq!(request.local_map_get::<u8>("x"), None);
+    ///
+    /// request.local_map_get_or_insert("x", || 1u8);
+    /// assert_eq!(request.local_map_get::<u8>("x"), Some(&1));
+    /// assert_eq!(request.local_map_get::<u16>("x"), None);
+    /// assert_eq!(request.local_map_get::<u8>("y"), None);
+    /// ```
+    pub fn local_map_get<T: Send + Sync + 'static>(&self, key: &str) -> Option<&T> {
+        let cache = self.state.keyed_cache.lock();
+        let value = cache.get(&(TypeId::of::<T>(), key.to_string()))?.downcast_ref::<T>()?;
+
+        // SAFETY: The returned reference is to a boxed value in the keyed
+        // cache. Entries are only ever inserted into the cache, never removed
+        // or replaced, and the cache is only dropped when the last clone of
+        // the request is, so the boxed value lives and never moves as long as
+        // `self` does, even as the map itself reallocates. The reference is
+        // immutable and no mutable reference to the value is ever created, so
+        // it doesn't alias. `T: Sync`, so sharing it across threads is sound.
+        Some(unsafe { &*(value as *const T) })
+    }
+
+    /// Appends the field `key` with value `value` to this request's logging
+    /// context. Intended for guards and handlers to annotate the request with
+    /// structured data that a response fairing then emits as a single log line
+    /// via [`Request::take_log_fields()`].
+    ///
+    /// Fields are kept in insertion order. Adding a field whose `key` is
+    /// already present does not overwrite the existing field: both are kept,
+    /// in the order they were added.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # let c = rocket::local::blocking::Client::debug_with(vec![]).unwrap();
+    /// # let request = c.get("/");
+    /// request.log_field("user", "bob");
+    /// request.log_field("attempt", 2);
+    ///
+    /// let fields = request.log_fields();
+    /// assert_eq!(fields[0], ("user".to_string(), "bob".to_string()));
+    /// assert_eq!(fields[1], ("attempt".to_string(), "2".to_string()));
+    /// ```
+    pub fn log_field<K: Into<String>, V: fmt::Display>(&self, key: K, value: V) {
+        let field = (key.into(), value.to_string());
+        self.state.log_fields.lock().push(field);
+    }
+
+    /// Returns a copy of the fields in this request's logging context, in the
+    /// order they were added with [`Request::log_field()`]. The context is
+    /// left unchanged.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # let c = rocket::local::blocking::Client::debug_with(vec![]).unwrap();
+    /// # let request = c.get("/");
+    /// assert!(request.log_fields().is_empty());
+    ///
+    /// request.log_field("user", "bob");
+    /// assert_eq!(request.log_fields().len(), 1);
+    /// assert_eq!(request.log_fields().len(), 1);
+    /// ```
+    pub fn log_fields(&self) -> Vec<(String, String)> {
+        self.state.log_fields.lock().clone()
+    }
+
+    /// Removes and returns the fields in this request's logging context, in
+    /// the order they were added with [`Request::log_field()`]. Subsequent
+    /// calls return only fields added after this call.
+    ///
+    /// This is intended to be called once, from a response fairing, to emit
+    /// the context as a structured log line.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::fairing::AdHoc;
+    ///
+    /// let fairing = AdHoc::on_response("Log Context", |req, _| Box::pin(async move {
+    ///     let fields: Vec<_> = req.take_log_fields()
+    ///         .into_iter()
+    ///         .map(|(k, v)| format!("{}={:?}", k, v))
+    ///         .collect();
+    ///
+    ///     rocket::info!("{} {}", req.uri(), fields.join(" "));
+    /// }));
+    /// ```
+    pub fn take_log_fields(&self) -> Vec<(String, String)> {
+        std::mem::take(&mut *self.state.log_fields.lock())
     }
 
     /// Retrieves and parses into `T` the 0-indexed `n`th non-empty segment from
@@ -800,7 +2378,7 @@ impl<'r> Request<'r> {
     pub fn param<'a, T>(&'a self, n: usize) -> Option<Result<T, T::Error>>
         where T: FromParam<'a>
     {
-        self.routed_segment(n).map(T::from_param)
+        self.routed_raw_segment(n).map(|(raw, param)| T::from_raw_param(raw, param))
     }
 
     /// Retrieves and parses into `T` all of the path segments in the request
@@ -841,6 +2419,132 @@ impl<'r> Request<'r> {
         T::from_segments(self.routed_segments(n))
     }
 
+    /// Returns an iterator over the names and raw, undecoded values of every
+    /// named dynamic path parameter in the matched route, in route order.
+    ///
+    /// For a single-segment parameter `<name>`, the value is the matched
+    /// segment. For a trailing parameter `<name..>`, the value is the matched
+    /// remainder of the path, with segments separated by `/`, and may be
+    /// empty. As a `/` in a segment is always percent-encoded, only a trailing
+    /// parameter's value ever contains a `/`. Use [`Request::param_named()`]
+    /// and [`Request::segments_named()`] to parse values. Ignored parameters,
+    /// `<_>` and `<_..>`, are skipped.
+    ///
+    /// If the request has not been routed, the iterator is empty.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::Request;
+    ///
+    /// // For a route `/<user>/<_>/files/<path..>` and a request to
+    /// // `/bob/x/files/a%20b/c`, returns `[("user", "bob"), ("path", "a%20b/c")]`.
+    /// fn describe(request: &Request<'_>) -> Vec<(String, String)> {
+    ///     request.params()
+    ///         .map(|(name, value)| (name.to_string(), value.to_string()))
+    ///         .collect()
+    /// }
+    /// ```
+    pub fn params(&self) -> impl Iterator<Item = (&str, &RawStr)> {
+        let route_segs = self.route().map_or(&[][..], |r| &r.uri.metadata.path_segs[..]);
+        let path = self.uri().path().raw();
+        let spans = path.as_str().split('/')
+            .scan(0, |start, seg| {
+                let span = (*start, *start + seg.len());
+                *start = span.1 + 1;
+                Some(span)
+            })
+            .filter(|(start, end)| start != end)
+            .collect::<Vec<_>>();
+
+        route_segs.iter()
+            .enumerate()
+            .filter(|(_, seg)| seg.dynamic && seg.value != "_")
+            .map(move |(i, seg)| {
+                let value = match (seg.trailing, spans.get(i)) {
+                    (false, Some(&(start, end))) => &path[start..end],
+                    (true, Some(&(start, _))) => &path[start..spans[spans.len() - 1].1],
+                    (_, None) => RawStr::new(""),
+                };
+
+                (seg.value.as_str(), value)
+            })
+    }
+
+    /// Retrieves and parses into `T` the single-segment path parameter named
+    /// `name` in the matched route. The segment is parsed exactly as by
+    /// [`Request::param()`], via [`FromParam::from_raw_param()`], so types
+    /// that inspect the raw, undecoded segment see it here too.
+    ///
+    /// Returns `None` if the request has not been routed or if the route has
+    /// no single-segment parameter `<name>`. In particular, a trailing
+    /// parameter `<name..>` is _not_ returned; use
+    /// [`Request::segments_named()`] instead. Returns `Some(Err(T::Error))` if
+    /// `T` failed to parse the segment.
+    ///
+    /// This method is intended for request guards which need route parameters
+    /// but don't know the route's layout.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # #[macro_use] extern crate rocket;
+    /// use rocket::request::{self, Request, FromRequest};
+    ///
+    /// struct Tenant(u32);
+    ///
+    /// #[rocket::async_trait]
+    /// impl<'r> FromRequest<'r> for Tenant {
+    ///     type Error = ();
+    ///
+    ///     async fn from_request(req: &'r Request<'_>) -> request::Outcome<Self, ()> {
+    ///         match req.param_named::<u32>("tenant") {
+    ///             Some(Ok(id)) => request::Outcome::Success(Tenant(id)),
+    ///             _ => request::Outcome::Forward(()),
+    ///         }
+    ///     }
+    /// }
+    ///
+    /// #[get("/<tenant>/dashboard")]
+    /// fn dashboard(tenant: &str, guard: Tenant) -> String {
+    ///     format!("tenant {} (#{})", tenant, guard.0)
+    /// }
+    /// ```
+    pub fn param_named<'a, T>(&'a self, name: &str) -> Option<Result<T, T::Error>>
+        where T: FromParam<'a>
+    {
+        let i = self.route_param_index(name, false)?;
+        let mount_segments = self.route()?.uri.metadata.base_segs.len();
+        self.routed_raw_segment(i.checked_sub(mount_segments)?)
+            .map(|(raw, param)| T::from_raw_param(raw, param))
+    }
+
+    /// Retrieves and parses into `T` the segments matched by the trailing path
+    /// parameter named `name`, that is, `<name..>`, in the matched route.
+    ///
+    /// Returns `None` if the request has not been routed or if the route has
+    /// no trailing parameter `<name..>`. A single-segment parameter `<name>`
+    /// is _not_ returned; use [`Request::param_named()`] instead. Otherwise,
+    /// returns the result of `T`'s [`FromSegments`] implementation.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # #[macro_use] extern crate rocket;
+    /// use std::path::PathBuf;
+    /// use rocket::Request;
+    ///
+    /// fn file_path(request: &Request<'_>) -> Option<PathBuf> {
+    ///     request.segments_named::<PathBuf>("path")?.ok()
+    /// }
+    /// ```
+    pub fn segments_named<'a, T>(&'a self, name: &str) -> Option<Result<T, T::Error>>
+        where T: FromSegments<'a>
+    {
+        let i = self.route_param_index(name, true)?;
+        Some(T::from_segments(self.uri().path().segments().skip(i)))
+    }
+
     /// Retrieves and parses into `T` the query value with field name `name`.
     /// `T` must implement [`FromForm`], which is used to parse the query's
     /// value. Key matching is performed case-sensitively.
@@ -918,6 +2622,17 @@ impl<'r> Request<'r> {
         }
     }
 
+    /// Returns the index, including the mount point, of the dynamic path
+    /// segment named `name`, if it is `trailing` or not, in the matched route.
+    fn route_param_index(&self, name: &str, trailing: bool) -> Option<usize> {
+        if name == "_" {
+            return None;
+        }
+
+        self.route()?.uri.metadata.path_segs.iter()
+            .position(|seg| seg.dynamic && seg.trailing == trailing && seg.value == name)
+    }
+
     /// Get the `n`th path segment, 0-indexed, after the mount point for the
     /// currently matched route, as a string, if it exists. Used by codegen.
     #[inline]
@@ -925,6 +2640,22 @@ impl<'r> Request<'r> {
         self.routed_segments(0..).get(n)
     }
 
+    /// Like [`Request::routed_segment()`], but returns the raw, undecoded
+    /// segment alongside the decoded one. Used by codegen.
+    #[doc(hidden)]
+    #[inline]
+    pub fn routed_raw_segment(&self, n: usize) -> Option<(&RawStr, &str)> {
+        let mount_segments = self.route()
+            .map(|r| r.uri.metadata.base_segs.len())
+            .unwrap_or(0);
+
+        let raw = self.uri().path().raw_segments()
+            .filter(|s| !s.is_empty())
+            .nth(mount_segments + n)?;
+
+        self.routed_segment(n).map(|s| (raw, s))
+    }
+
     /// Get the segments beginning at the `n`th, 0-indexed, after the mount
     /// point for the currently matched route, if they exist. Used by codegen.
     #[inline]
@@ -952,6 +2683,25 @@ impl<'r> Request<'r> {
         self.state.route.store(Some(route), Ordering::Release)
     }
 
+    /// Reroutes a `HEAD` request to `GET` routes, remembering that the body of
+    /// the response must still be stripped.
+    pub(crate) fn _reroute_head(&self) {
+        self.state.rerouted_head.store(true, Ordering::Release);
+        self._set_method(Method::Get);
+    }
+
+    /// Marks `self` as being handled by an error catcher.
+    #[inline(always)]
+    pub(crate) fn _set_in_catcher(&self, in_catcher: bool) {
+        self.state.in_catcher.store(in_catcher, Ordering::Release);
+    }
+
+    /// Records that the request was forwarded by a route.
+    #[inline(always)]
+    pub(crate) fn _record_forward(&self) {
+        self.state.forward_count.fetch_add(1, Ordering::AcqRel);
+    }
+
     /// Set the method of `self`, even when `self` is a shared reference. Used
     /// during routing to override methods for re-routing.
     #[inline(always)]
@@ -976,6 +2726,19 @@ impl<'r> Request<'r> {
         // TODO: Keep around not just the path/query, but the rest, if there?
         let uri = hyper.uri.path_and_query().ok_or(Error::InvalidUri(&hyper.uri))?;
 
+        // Reject oversized headers before doing any work on them. Note that
+        // hyper has already read and parsed them: this bounds what we process,
+        // not what was read.
+        let limits = connection.as_ref()
+            .map(|c| c.header_limits)
+            .unwrap_or(rocket.config().header_limits);
+
+        let count = hyper.headers.len();
+        let size = hyper.headers.iter().map(|(k, v)| k.as_str().len() + v.len()).sum();
+        if !limits.allows(count, size) {
+            return Err(Error::HeadersTooLarge { count, size });
+        }
+
         // In debug, make sure we agree with Hyper that the URI is valid. If we
         // disagree, print a warning but continue anyway seeing as if this is a
         // security issue with Hyper, there isn't much we can do.
@@ -994,6 +2757,10 @@ impl<'r> Request<'r> {
             request.connection = connection;
         }
 
+        if let Some(version) = HttpVersion::from_hyp(&hyper.version) {
+            request.connection.http_version = version;
+        }
+
         // Determine the host. On HTTP < 2, use the `HOST` header. Otherwise,
         // use the `:authority` pseudo-header which hyper makes part of the URI.
         request.state.host = if hyper.version < hyper::Version::HTTP_2 {
@@ -1011,7 +2778,7 @@ impl<'r> Request<'r> {
 
             for cookie_str in raw_str.split(';').map(|s| s.trim()) {
                 if let Ok(cookie) = Cookie::parse_encoded(cookie_str) {
-                    request.state.cookies.add_original(cookie.into_owned());
+                    request.state.cookies.add_received(cookie.into_owned());
                 }
             }
         }
@@ -1040,6 +2807,17 @@ pub(crate) enum Error<'r> {
     InvalidUri(&'r hyper::Uri),
     UriParse(crate::http::uri::Error<'r>),
     BadMethod(&'r hyper::Method),
+    HeadersTooLarge { count: usize, size: usize },
+}
+
+impl Error<'_> {
+    /// The status with which to reject the request.
+    pub(crate) fn status(&self) -> Status {
+        match self {
+            Error::HeadersTooLarge { .. } => Status::RequestHeaderFieldsTooLarge,
+            _ => Status::BadRequest,
+        }
+    }
 }
 
 impl fmt::Display for Error<'_> {
@@ -1050,6 +2828,9 @@ impl fmt::Display for Error<'_> {
             Error::InvalidUri(u) => write!(f, "invalid origin URI: {}", u),
             Error::UriParse(u) => write!(f, "URI `{}` failed to parse as origin", u),
             Error::BadMethod(m) => write!(f, "invalid or unrecognized method: {}", m),
+            Error::HeadersTooLarge { count, size } => {
+                write!(f, "{} headers totalling {} bytes exceed the limits", count, size)
+            }
         }
     }
 }
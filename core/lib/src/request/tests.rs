@@ -1,8 +1,12 @@
 use std::collections::HashMap;
+use std::net::IpAddr;
 
-use crate::Request;
+use crate::{Request, Config};
+use crate::config::ForwardedHeader;
+use crate::request::{ConnectionMeta, TlsInfo, PeerAddr};
 use crate::local::blocking::Client;
-use crate::http::hyper;
+use crate::http::{hyper, MediaType, HttpVersion, UnixPeer, PeerCredentials};
+use crate::http::private::{Certificates, TlsSession, TlsParameters};
 
 macro_rules! assert_headers {
     ($($key:expr => [$($value:expr),+]),+) => ({
@@ -44,3 +48,728 @@ fn test_multiple_headers_merge_into_one_from_hyp() {
     assert_headers!("friend" => ["alice"], "friend" => ["bob"], "friend" => ["carol"]);
     assert_headers!("friend" => ["alice"], "friend" => ["bob"], "enemy" => ["carol"]);
 }
+
+fn real_client_ip(remote: &str, proxies: &[&str], forwarded: &[&str]) -> Option<IpAddr> {
+    let headers: Vec<_> = forwarded.iter().map(|v| ("X-Forwarded-For", *v)).collect();
+    real_client_ip_from(ForwardedHeader::XForwarded, remote, proxies, &headers)
+}
+
+fn forwarded_client(source: ForwardedHeader) -> Client {
+    let config = Config { forwarded_header: source, ..Config::debug_default() };
+    Client::debug(crate::custom(config)).unwrap()
+}
+
+fn real_client_ip_from(
+    source: ForwardedHeader,
+    remote: &str,
+    proxies: &[&str],
+    headers: &[(&'static str, &str)]
+) -> Option<IpAddr> {
+    let mut req = hyper::Request::get("/test").body(()).unwrap();
+    for (name, value) in headers {
+        let value = hyper::HeaderValue::from_str(value).unwrap();
+        req.headers_mut().append(*name, value);
+    }
+
+    let client = forwarded_client(source);
+    let hyper = req.into_parts().0;
+    let connection = ConnectionMeta {
+        remote: Some(remote.parse().unwrap()),
+        client_certificates: None,
+        tls_session: None,
+        trusted_proxies: proxies.iter().map(|p| p.parse().unwrap()).collect(),
+        header_limits: Default::default(),
+        http_version: HttpVersion::Http11,
+        unix_peer: None,
+        received_at: std::time::Instant::now(),
+    };
+
+    let req = Request::from_hyp(client.rocket(), &hyper, Some(connection)).unwrap();
+    req.real_client_ip()
+}
+
+fn ip(string: &str) -> Option<IpAddr> {
+    Some(string.parse().unwrap())
+}
+
+#[test]
+fn test_real_client_ip_untrusted_peer() {
+    // Without any trusted proxies, the header is always ignored.
+    assert_eq!(real_client_ip("1.2.3.4:80", &[], &[]), ip("1.2.3.4"));
+    assert_eq!(real_client_ip("1.2.3.4:80", &[], &["9.9.9.9"]), ip("1.2.3.4"));
+
+    // A spoofed header from a peer that isn't trusted is ignored.
+    let proxies = &["10.0.0.0/8"];
+    assert_eq!(real_client_ip("1.2.3.4:80", proxies, &["9.9.9.9"]), ip("1.2.3.4"));
+    assert_eq!(real_client_ip("1.2.3.4:80", proxies, &["10.0.0.1"]), ip("1.2.3.4"));
+    assert_eq!(real_client_ip("[::1]:80", proxies, &["9.9.9.9"]), ip("::1"));
+    assert_eq!(real_client_ip("11.0.0.1:80", proxies, &["9.9.9.9"]), ip("11.0.0.1"));
+}
+
+#[test]
+fn test_real_client_ip_trusted_chain() {
+    let proxies = &["10.0.0.0/8", "192.168.1.1", "fd00::/8"];
+
+    // Trusted peer without a header.
+    assert_eq!(real_client_ip("10.0.0.1:80", proxies, &[]), ip("10.0.0.1"));
+
+    // A single trusted hop.
+    assert_eq!(real_client_ip("10.0.0.1:80", proxies, &["9.9.9.9"]), ip("9.9.9.9"));
+    assert_eq!(real_client_ip("[::ffff:10.0.0.1]:80", proxies, &["9.9.9.9"]), ip("9.9.9.9"));
+
+    // Multiple trusted hops, in one header or across several.
+    let chain = &["9.9.9.9, 192.168.1.1, fd00::2"];
+    assert_eq!(real_client_ip("10.0.0.1:80", proxies, chain), ip("9.9.9.9"));
+    let chain = &["9.9.9.9", "192.168.1.1", "fd00::2"];
+    assert_eq!(real_client_ip("10.0.0.1:80", proxies, chain), ip("9.9.9.9"));
+
+    // Anything left of the first untrusted address may be spoofed.
+    let chain = &["6.6.6.6,9.9.9.9 ,  10.1.1.1"];
+    assert_eq!(real_client_ip("10.0.0.1:80", proxies, chain), ip("9.9.9.9"));
+    let chain = &["10.6.6.6, 192.168.1.2, 10.1.1.1"];
+    assert_eq!(real_client_ip("10.0.0.1:80", proxies, chain), ip("192.168.1.2"));
+
+    // If every hop is trusted, the leftmost is the client.
+    let chain = &["10.6.6.6, 192.168.1.1, 10.1.1.1"];
+    assert_eq!(real_client_ip("10.0.0.1:80", proxies, chain), ip("10.6.6.6"));
+}
+
+#[test]
+fn test_real_client_ip_malformed() {
+    let proxies = &["10.0.0.0/8"];
+
+    // A malformed address stops the walk at the last good one to its right.
+    let chain = &["9.9.9.9, nonsense"];
+    assert_eq!(real_client_ip("10.0.0.1:80", proxies, chain), ip("10.0.0.1"));
+    let chain = &["9.9.9.9, nonsense, 10.0.0.2"];
+    assert_eq!(real_client_ip("10.0.0.1:80", proxies, chain), ip("10.0.0.2"));
+    let chain = &["nonsense, 9.9.9.9, 10.0.0.2"];
+    assert_eq!(real_client_ip("10.0.0.1:80", proxies, chain), ip("9.9.9.9"));
+
+    // Empty entries, ports, and garbage are all malformed.
+    for value in &["", ",", "9.9.9.9,", "9.9.9.9:80", "[::1]", "unknown", "1.2.3"] {
+        let chain = &[*value];
+        let actual = real_client_ip("10.0.0.1:80", proxies, chain);
+        assert_eq!(actual, ip("10.0.0.1"), "{:?}", value);
+    }
+}
+
+#[test]
+fn test_real_client_ip_forwarded() {
+    let proxies = &["10.0.0.0/8", "fd00::/8"];
+    let via = |remote: &str, values: &[&str]| {
+        let headers: Vec<_> = values.iter().map(|v| ("Forwarded", *v)).collect();
+        real_client_ip_from(ForwardedHeader::Forwarded, remote, proxies, &headers)
+    };
+
+    // Untrusted peers are ignored.
+    assert_eq!(via("1.2.3.4:80", &["for=9.9.9.9"]), ip("1.2.3.4"));
+
+    // Quoted, bracketed IPv6 addresses with ports, and multiple hops.
+    assert_eq!(via("10.0.0.1:80", &[r#"for="[2001:db8::1]:8080""#]), ip("2001:db8::1"));
+    let chain = &[r#"for=9.9.9.9;proto=https, for="[fd00::2]";by=10.0.0.1"#];
+    assert_eq!(via("10.0.0.1:80", chain), ip("9.9.9.9"));
+    let chain = &["for=6.6.6.6, for=9.9.9.9", "for=\"10.1.1.1:443\""];
+    assert_eq!(via("10.0.0.1:80", chain), ip("9.9.9.9"));
+
+    // Malformed hops and hops without a `for` parameter stop the walk.
+    let chain = &["for=9.9.9.9, for=not-an-ip, for=10.1.1.1"];
+    assert_eq!(via("10.0.0.1:80", chain), ip("10.1.1.1"));
+    let chain = &["for=9.9.9.9, proto=https, for=10.1.1.1"];
+    assert_eq!(via("10.0.0.1:80", chain), ip("10.1.1.1"));
+    assert_eq!(via("10.0.0.1:80", &["for=9.9.9.9, , for=10.1.1.1"]), ip("10.1.1.1"));
+    assert_eq!(via("10.0.0.1:80", &["for=9.9.9.9", "garbage"]), ip("10.0.0.1"));
+
+    // Obfuscated identifiers stop the walk.
+    assert_eq!(via("10.0.0.1:80", &["for=9.9.9.9, for=_hidden"]), ip("10.0.0.1"));
+    assert_eq!(via("10.0.0.1:80", &["for=9.9.9.9, for=unknown, for=10.1.1.1"]), ip("10.1.1.1"));
+}
+
+#[test]
+fn test_real_client_ip_header_source() {
+    let proxies = &["10.0.0.0/8"];
+    let headers = &[("Forwarded", "for=6.6.6.6"), ("X-Forwarded-For", "9.9.9.9")];
+
+    // Only the configured header is read; the other may be client-supplied.
+    let source = ForwardedHeader::XForwarded;
+    assert_eq!(real_client_ip_from(source, "10.0.0.1:80", proxies, headers), ip("9.9.9.9"));
+    let source = ForwardedHeader::Forwarded;
+    assert_eq!(real_client_ip_from(source, "10.0.0.1:80", proxies, headers), ip("6.6.6.6"));
+
+    let headers = &[("Forwarded", "for=6.6.6.6")];
+    let source = ForwardedHeader::XForwarded;
+    assert_eq!(real_client_ip_from(source, "10.0.0.1:80", proxies, headers), ip("10.0.0.1"));
+    let headers = &[("X-Forwarded-For", "9.9.9.9")];
+    let source = ForwardedHeader::Forwarded;
+    assert_eq!(real_client_ip_from(source, "10.0.0.1:80", proxies, headers), ip("10.0.0.1"));
+}
+
+fn content_length(values: &[&str]) -> Option<u64> {
+    let mut req = hyper::Request::get("/test").body(()).unwrap();
+    for value in values {
+        let value = hyper::HeaderValue::from_str(value).unwrap();
+        req.headers_mut().append("Content-Length", value);
+    }
+
+    let client = Client::debug_with(vec![]).unwrap();
+    let hyper = req.into_parts().0;
+    let req = Request::from_hyp(client.rocket(), &hyper, None).unwrap();
+    req.content_length()
+}
+
+#[test]
+fn test_content_length() {
+    // Absent.
+    assert_eq!(content_length(&[]), None);
+
+    // Valid, with and without surrounding whitespace.
+    assert_eq!(content_length(&["0"]), Some(0));
+    assert_eq!(content_length(&["1024"]), Some(1024));
+    assert_eq!(content_length(&["007"]), Some(7));
+    assert_eq!(content_length(&[" 42"]), Some(42));
+    assert_eq!(content_length(&["42\t "]), Some(42));
+    assert_eq!(content_length(&["18446744073709551615"]), Some(u64::MAX));
+
+    // Overflowing.
+    assert_eq!(content_length(&["18446744073709551616"]), None);
+    assert_eq!(content_length(&["99999999999999999999999999"]), None);
+
+    // Malformed.
+    for value in &["", " ", "-1", "+1", "1.0", "1e3", "0x10", "4 2", "ten", "42,"] {
+        assert_eq!(content_length(&[value]), None, "{:?}", value);
+    }
+
+    // Duplicated, agreeing and conflicting.
+    assert_eq!(content_length(&["42", "42"]), Some(42));
+    assert_eq!(content_length(&["42", " 42 "]), Some(42));
+    assert_eq!(content_length(&["42, 42", "42"]), Some(42));
+    assert_eq!(content_length(&["42", "43"]), None);
+    assert_eq!(content_length(&["42, 43"]), None);
+    assert_eq!(content_length(&["42", "nope"]), None);
+    assert_eq!(content_length(&["42", ""]), None);
+}
+
+fn accepts(accept: Option<&str>, media_type: MediaType) -> bool {
+    let mut req = hyper::Request::get("/test").body(()).unwrap();
+    if let Some(accept) = accept {
+        let value = hyper::HeaderValue::from_str(accept).unwrap();
+        req.headers_mut().append("Accept", value);
+    }
+
+    let client = Client::debug_with(vec![]).unwrap();
+    let hyper = req.into_parts().0;
+    let req = Request::from_hyp(client.rocket(), &hyper, None).unwrap();
+    req.accepts(&media_type)
+}
+
+#[test]
+fn test_accepts() {
+    // Absent or unparseable: everything is acceptable.
+    assert!(accepts(None, MediaType::JSON));
+    assert!(accepts(None, MediaType::PNG));
+    assert!(accepts(Some("not a media type"), MediaType::JSON));
+
+    // Explicit.
+    assert!(accepts(Some("application/json"), MediaType::JSON));
+    assert!(accepts(Some("text/html, application/json;q=0.1"), MediaType::JSON));
+    assert!(!accepts(Some("application/json"), MediaType::HTML));
+    assert!(!accepts(Some("text/html, image/png"), MediaType::JSON));
+
+    // Wildcards.
+    assert!(accepts(Some("*/*"), MediaType::JSON));
+    assert!(accepts(Some("text/html, */*;q=0.8"), MediaType::Binary));
+    assert!(accepts(Some("application/*"), MediaType::JSON));
+    assert!(!accepts(Some("text/*"), MediaType::JSON));
+
+    // `q=0` exclusions.
+    assert!(!accepts(Some("application/json;q=0"), MediaType::JSON));
+    assert!(!accepts(Some("*/*, application/json;q=0"), MediaType::JSON));
+    assert!(accepts(Some("*/*, application/json;q=0"), MediaType::HTML));
+    assert!(!accepts(Some("text/html, */*;q=0"), MediaType::JSON));
+    assert!(!accepts(Some("*/*, application/*;q=0"), MediaType::JSON));
+    assert!(accepts(Some("application/*;q=0, application/json"), MediaType::JSON));
+}
+
+fn scheme(remote: &str, proxies: &[&str], headers: &[(&'static str, &str)]) -> &'static str {
+    scheme_from(ForwardedHeader::XForwarded, remote, proxies, headers)
+}
+
+fn scheme_from(
+    source: ForwardedHeader,
+    remote: &str,
+    proxies: &[&str],
+    headers: &[(&'static str, &str)]
+) -> &'static str {
+    let mut req = hyper::Request::get("/test").body(()).unwrap();
+    for (name, value) in headers {
+        let value = hyper::HeaderValue::from_str(value).unwrap();
+        req.headers_mut().append(*name, value);
+    }
+
+    let client = forwarded_client(source);
+    let hyper = req.into_parts().0;
+    let connection = ConnectionMeta {
+        remote: Some(remote.parse().unwrap()),
+        client_certificates: None,
+        tls_session: None,
+        trusted_proxies: proxies.iter().map(|p| p.parse().unwrap()).collect(),
+        header_limits: Default::default(),
+        http_version: HttpVersion::Http11,
+        unix_peer: None,
+        received_at: std::time::Instant::now(),
+    };
+
+    let req = Request::from_hyp(client.rocket(), &hyper, Some(connection)).unwrap();
+    req.scheme()
+}
+
+#[test]
+fn test_scheme_trusted_proxy() {
+    let proxies = &["10.0.0.0/8"];
+
+    assert_eq!(scheme("10.0.0.1:80", proxies, &[("X-Forwarded-Proto", "https")]), "https");
+    assert_eq!(scheme("10.0.0.1:80", proxies, &[("X-Forwarded-Proto", "HTTPS")]), "https");
+    assert_eq!(scheme("10.0.0.1:80", proxies, &[("X-Forwarded-Proto", "http")]), "http");
+
+    // The value added by the nearest proxy, the last one, wins.
+    assert_eq!(scheme("10.0.0.1:80", proxies, &[("X-Forwarded-Proto", "http, https")]), "https");
+    assert_eq!(scheme("10.0.0.1:80", proxies, &[("X-Forwarded-Proto", "https"),
+        ("X-Forwarded-Proto", "http")]), "http");
+
+    // Unknown protocols are ignored.
+    assert_eq!(scheme("10.0.0.1:80", proxies, &[("X-Forwarded-Proto", "gopher")]), "http");
+}
+
+#[test]
+fn test_scheme_trusted_chain() {
+    let proxies = &["10.0.0.0/8"];
+
+    // The scheme reported for the client that ends the walk is used, not one
+    // the client itself sent.
+    let headers = &[
+        ("X-Forwarded-For", "6.6.6.6, 1.1.1.1"),
+        ("X-Forwarded-Proto", "https, http"),
+    ];
+    assert_eq!(scheme("10.0.0.1:80", proxies, headers), "http");
+
+    let headers = &[
+        ("X-Forwarded-For", "1.1.1.1, 10.0.0.2"),
+        ("X-Forwarded-Proto", "https, http"),
+    ];
+    assert_eq!(scheme("10.0.0.1:80", proxies, headers), "https");
+
+    // Values are paired from the right; an inner proxy's scheme is used if the
+    // outermost trusted proxy didn't report one.
+    let headers = &[("X-Forwarded-For", "1.1.1.1, 10.0.0.2"), ("X-Forwarded-Proto", "https")];
+    assert_eq!(scheme("10.0.0.1:80", proxies, headers), "https");
+
+    // A malformed address stops the walk at the last trusted hop.
+    let headers = &[
+        ("X-Forwarded-For", "1.1.1.1, bogus, 10.0.0.2"),
+        ("X-Forwarded-Proto", "http, http, https"),
+    ];
+    assert_eq!(scheme("10.0.0.1:80", proxies, headers), "http");
+}
+
+#[test]
+fn test_scheme_forwarded() {
+    let proxies = &["10.0.0.0/8"];
+    let via = |headers: &[(&'static str, &str)]| {
+        scheme_from(ForwardedHeader::Forwarded, "10.0.0.1:80", proxies, headers)
+    };
+
+    assert_eq!(via(&[("Forwarded", "proto=https")]), "https");
+    assert_eq!(via(&[("Forwarded", "For=\"[::1]\";Proto=\"https\"")]), "https");
+    assert_eq!(via(&[("Forwarded", "proto=http, for=1.1.1.1;proto=https")]), "https");
+    assert_eq!(via(&[("Forwarded", "for=1.1.1.1;proto=https, for=10.0.0.2")]), "https");
+    assert_eq!(via(&[("Forwarded", "for=1.1.1.1")]), "http");
+
+    // A `proto` sent by the client is never used.
+    assert_eq!(via(&[("Forwarded", "for=6.6.6.6;proto=https, for=1.1.1.1")]), "http");
+    let headers = &[("Forwarded", "for=6.6.6.6;proto=https, for=1.1.1.1;proto=http")];
+    assert_eq!(via(headers), "http");
+
+    // Only the configured header is read.
+    let headers = &[("X-Forwarded-Proto", "http"), ("Forwarded", "proto=https")];
+    assert_eq!(via(headers), "https");
+    assert_eq!(scheme("10.0.0.1:80", proxies, headers), "http");
+    assert_eq!(scheme("10.0.0.1:80", proxies, &[("Forwarded", "proto=https")]), "http");
+    assert_eq!(via(&[("X-Forwarded-Proto", "https")]), "http");
+}
+
+#[test]
+fn test_scheme_untrusted_peer() {
+    use ForwardedHeader::*;
+
+    // Without any trusted proxies, the headers are always ignored.
+    assert_eq!(scheme("10.0.0.1:80", &[], &[("X-Forwarded-Proto", "https")]), "http");
+    assert_eq!(scheme_from(Forwarded, "10.0.0.1:80", &[], &[("Forwarded", "proto=https")]),
+        "http");
+
+    // A header from a peer that isn't trusted is ignored.
+    let proxies = &["10.0.0.0/8"];
+    assert_eq!(scheme("1.2.3.4:80", proxies, &[("X-Forwarded-Proto", "https")]), "http");
+    assert_eq!(scheme_from(Forwarded, "1.2.3.4:80", proxies, &[("Forwarded", "proto=https")]),
+        "http");
+}
+
+#[test]
+fn test_scheme_no_header() {
+    assert_eq!(scheme("1.2.3.4:80", &[], &[]), "http");
+    assert_eq!(scheme("10.0.0.1:80", &["10.0.0.0/8"], &[]), "http");
+}
+
+fn tls_connection(session: Option<TlsSession>) -> ConnectionMeta {
+    ConnectionMeta {
+        remote: Some("1.2.3.4:443".parse().unwrap()),
+        client_certificates: Some(Certificates::default()),
+        tls_session: session,
+        trusted_proxies: Vec::new().into(),
+        header_limits: Default::default(),
+        http_version: HttpVersion::Http11,
+        unix_peer: None,
+        received_at: std::time::Instant::now(),
+    }
+}
+
+#[test]
+fn test_tls_info() {
+    let client = Client::debug_with(vec![]).unwrap();
+    let hyper = hyper::Request::get("/test").body(()).unwrap().into_parts().0;
+
+    let connection = tls_connection(Some(tls_session(None)));
+    let req = Request::from_hyp(client.rocket(), &hyper, Some(connection)).unwrap();
+    assert_eq!(req.tls_info(), Some(TlsInfo {
+        protocol: "TLSv1_3".into(),
+        cipher_suite: "TLS13_AES_128_GCM_SHA256".into(),
+        peer_subject: None,
+        peer_issuer: None,
+    }));
+}
+
+#[test]
+fn test_tls_info_plaintext() {
+    let client = Client::debug_with(vec![]).unwrap();
+    let hyper = hyper::Request::get("/test").body(()).unwrap().into_parts().0;
+
+    // No connection details at all, as with local requests.
+    let req = Request::from_hyp(client.rocket(), &hyper, None).unwrap();
+    assert_eq!(req.tls_info(), None);
+
+    // A plaintext connection.
+    let req = Request::from_hyp(client.rocket(), &hyper, Some(tls_connection(None))).unwrap();
+    assert_eq!(req.tls_info(), None);
+
+    // A TLS connection whose handshake hasn't completed.
+    let connection = tls_connection(Some(TlsSession::default()));
+    let req = Request::from_hyp(client.rocket(), &hyper, Some(connection)).unwrap();
+    assert_eq!(req.tls_info(), None);
+}
+
+fn tls_session(server_name: Option<&str>) -> TlsSession {
+    let session = TlsSession::default();
+    session.set(TlsParameters {
+        protocol: "TLSv1_3".into(),
+        cipher_suite: "TLS13_AES_128_GCM_SHA256".into(),
+        server_name: server_name.map(String::from),
+    });
+
+    session
+}
+
+#[test]
+fn test_sni_hostname() {
+    let client = Client::debug_with(vec![]).unwrap();
+    let mut hyper = hyper::Request::get("/test").body(()).unwrap();
+    hyper.headers_mut().insert("Host", hyper::HeaderValue::from_static("other.example"));
+    let hyper = hyper.into_parts().0;
+
+    let connection = tls_connection(Some(tls_session(Some("site.example"))));
+    let req = Request::from_hyp(client.rocket(), &hyper, Some(connection)).unwrap();
+    assert_eq!(req.sni_hostname(), Some("site.example"));
+
+    // The server name is independent of the `Host` header.
+    assert_eq!(req.host().unwrap().domain(), "other.example");
+
+    // A TLS connection without a server name.
+    let connection = tls_connection(Some(tls_session(None)));
+    let req = Request::from_hyp(client.rocket(), &hyper, Some(connection)).unwrap();
+    assert_eq!(req.sni_hostname(), None);
+    assert!(req.tls_info().is_some());
+}
+
+#[test]
+fn test_sni_hostname_plaintext() {
+    let client = Client::debug_with(vec![]).unwrap();
+    let hyper = hyper::Request::get("/test").body(()).unwrap().into_parts().0;
+
+    let req = Request::from_hyp(client.rocket(), &hyper, None).unwrap();
+    assert_eq!(req.sni_hostname(), None);
+
+    let req = Request::from_hyp(client.rocket(), &hyper, Some(tls_connection(None))).unwrap();
+    assert_eq!(req.sni_hostname(), None);
+
+    // A TLS connection whose handshake hasn't completed.
+    let connection = tls_connection(Some(TlsSession::default()));
+    let req = Request::from_hyp(client.rocket(), &hyper, Some(connection)).unwrap();
+    assert_eq!(req.sni_hostname(), None);
+
+    let req = client.get("/");
+    assert_eq!(req.inner().sni_hostname(), None);
+}
+
+/// Returns the value of the cookie `name` and the values of all cookies named
+/// `name` in a request with the `Cookie` headers `headers`.
+fn cookies(headers: &[&str], name: &str) -> (Option<String>, Vec<String>) {
+    let mut req = hyper::Request::get("/test").body(()).unwrap();
+    for value in headers {
+        let value = hyper::HeaderValue::from_str(value).unwrap();
+        req.headers_mut().append("Cookie", value);
+    }
+
+    let client = Client::debug_with(vec![]).unwrap();
+    let hyper = req.into_parts().0;
+    let req = Request::from_hyp(client.rocket(), &hyper, None).unwrap();
+    let first = req.cookies().get(name).map(|c| c.value().to_string());
+    let all = req.cookies_all(name).map(|c| c.value().to_string()).collect();
+    (first, all)
+}
+
+#[test]
+fn test_duplicate_cookies() {
+    let (first, all) = cookies(&["a=1; b=2; a=3"], "a");
+    assert_eq!(first.as_deref(), Some("1"));
+    assert_eq!(all, ["1", "3"]);
+
+    // Duplicates across headers, in the order received.
+    let (first, all) = cookies(&["a=1; b=2", "b=3", "a=4; a=5"], "a");
+    assert_eq!(first.as_deref(), Some("1"));
+    assert_eq!(all, ["1", "4", "5"]);
+
+    let (first, all) = cookies(&["a=1; b=2", "b=3", "a=4; a=5"], "b");
+    assert_eq!(first.as_deref(), Some("2"));
+    assert_eq!(all, ["2", "3"]);
+
+    // Identical duplicates are kept.
+    let (_, all) = cookies(&["a=1; a=1", "a=1"], "a");
+    assert_eq!(all.len(), 3);
+
+    // Unique and missing cookies.
+    assert_eq!(cookies(&["a=1; b=2"], "b"), (Some("2".into()), vec!["2".into()]));
+    assert_eq!(cookies(&["a=1; b=2"], "c"), (None, vec![]));
+    assert_eq!(cookies(&[], "a"), (None, vec![]));
+}
+
+#[test]
+fn test_cookie_values_with_equals_and_empty() {
+    let (first, all) = cookies(&["token=abc=; a=b=c=d; empty=; a="], "a");
+    assert_eq!(first.as_deref(), Some("b=c=d"));
+    assert_eq!(all, ["b=c=d", ""]);
+
+    assert_eq!(cookies(&["token=abc=="], "token").0.as_deref(), Some("abc=="));
+    assert_eq!(cookies(&["empty=; a=1"], "empty").1, [""]);
+
+    // Percent-encoded values are decoded; malformed pairs are skipped.
+    let (_, all) = cookies(&["a=%3D; noequals; =nameless; a=x%20y;;  a = z "], "a");
+    assert_eq!(all, ["=", "x y", "z"]);
+}
+
+#[test]
+fn test_local_cookies_replace_originals() {
+    use crate::http::Cookie;
+
+    let client = Client::debug_with(vec![]).unwrap();
+    let request = client.get("/")
+        .cookie(Cookie::new("a", "1"))
+        .cookie(Cookie::new("a", "2"));
+
+    let request = request.inner();
+    assert_eq!(request.cookies().get("a").map(|c| c.value()), Some("2"));
+    assert_eq!(request.cookies_all("a").count(), 1);
+}
+
+fn from_hyp_with_limits(
+    count: usize,
+    size: usize,
+    headers: &[(&'static str, &str)]
+) -> Result<(), String> {
+    use crate::config::HeaderLimits;
+    use crate::data::ToByteUnit;
+
+    let mut req = hyper::Request::get("/test").body(()).unwrap();
+    for (name, value) in headers {
+        let value = hyper::HeaderValue::from_str(value).unwrap();
+        req.headers_mut().append(*name, value);
+    }
+
+    let client = Client::debug_with(vec![]).unwrap();
+    let hyper = req.into_parts().0;
+    let connection = ConnectionMeta {
+        remote: None,
+        client_certificates: None,
+        tls_session: None,
+        trusted_proxies: Vec::new().into(),
+        header_limits: HeaderLimits { count, size: size.bytes(), ..Default::default() },
+        http_version: HttpVersion::Http11,
+        unix_peer: None,
+        received_at: std::time::Instant::now(),
+    };
+
+    match Request::from_hyp(client.rocket(), &hyper, Some(connection)) {
+        Ok(_) => Ok(()),
+        Err(e) => {
+            assert_eq!(e.status(), crate::http::Status::RequestHeaderFieldsTooLarge);
+            Err(e.to_string())
+        }
+    }
+}
+
+#[test]
+fn test_header_count_limit() {
+    let headers = &[("a", "1"), ("b", "2"), ("a", "3")];
+    assert!(from_hyp_with_limits(3, 1024, headers).is_ok());
+    assert_eq!(from_hyp_with_limits(2, 1024, headers).unwrap_err(),
+        "3 headers totalling 6 bytes exceed the limits");
+    assert!(from_hyp_with_limits(0, 1024, &[]).is_ok());
+    assert!(from_hyp_with_limits(0, 1024, &[("a", "")]).is_err());
+}
+
+#[test]
+fn test_header_size_limit() {
+    // Names and values both count: "name" + "value" is 9 bytes.
+    assert!(from_hyp_with_limits(10, 9, &[("name", "value")]).is_ok());
+    assert!(from_hyp_with_limits(10, 8, &[("name", "value")]).is_err());
+
+    // Sizes are totalled across headers.
+    let headers = &[("name", "value"), ("name", "value")];
+    assert!(from_hyp_with_limits(10, 18, headers).is_ok());
+    assert_eq!(from_hyp_with_limits(10, 17, headers).unwrap_err(),
+        "2 headers totalling 18 bytes exceed the limits");
+
+    // A single oversized value trips the limit.
+    let huge = "x".repeat(4096);
+    assert!(from_hyp_with_limits(10, 4096, &[("a", &huge)]).is_err());
+    assert!(from_hyp_with_limits(10, 4097, &[("a", &huge)]).is_ok());
+}
+
+#[test]
+fn test_header_limits_default_from_config() {
+    let client = Client::debug_with(vec![]).unwrap();
+    let mut req = hyper::Request::get("/test").body(()).unwrap();
+    for _ in 0..=client.rocket().config().header_limits.count {
+        req.headers_mut().append("x-repeated", hyper::HeaderValue::from_static("v"));
+    }
+
+    let hyper = req.into_parts().0;
+    assert!(Request::from_hyp(client.rocket(), &hyper, None).is_err());
+}
+
+fn http_version_from_hyp(version: hyper::Version) -> HttpVersion {
+    let client = Client::debug_with(vec![]).unwrap();
+    let mut req = hyper::Request::get("/test").body(()).unwrap();
+    *req.version_mut() = version;
+
+    let hyper = req.into_parts().0;
+    let connection = tls_connection(None);
+    let with_connection = Request::from_hyp(client.rocket(), &hyper, Some(connection)).unwrap();
+    let without_connection = Request::from_hyp(client.rocket(), &hyper, None).unwrap();
+    assert_eq!(with_connection.http_version(), without_connection.http_version());
+    with_connection.http_version()
+}
+
+#[test]
+fn test_http_version_from_hyp() {
+    assert_eq!(http_version_from_hyp(hyper::Version::HTTP_10), HttpVersion::Http10);
+    assert_eq!(http_version_from_hyp(hyper::Version::HTTP_11), HttpVersion::Http11);
+    assert_eq!(http_version_from_hyp(hyper::Version::HTTP_2), HttpVersion::Http2);
+    assert_eq!(http_version_from_hyp(hyper::Version::HTTP_3), HttpVersion::Http3);
+
+    // Unsupported versions keep the connection's default.
+    assert_eq!(http_version_from_hyp(hyper::Version::HTTP_09), HttpVersion::Http11);
+}
+
+#[test]
+fn test_http_version_local() {
+    let client = Client::debug_with(vec![]).unwrap();
+    assert_eq!(client.get("/").inner().http_version(), HttpVersion::Http11);
+
+    let request = client.get("/").http_version(HttpVersion::Http2);
+    assert_eq!(request.inner().http_version(), HttpVersion::Http2);
+    assert!(request.inner().http_version().is_multiplexed());
+}
+
+fn peer_connection(remote: Option<&str>, unix_peer: Option<UnixPeer>) -> ConnectionMeta {
+    ConnectionMeta {
+        remote: remote.map(|r| r.parse().unwrap()),
+        client_certificates: None,
+        tls_session: None,
+        trusted_proxies: Vec::new().into(),
+        header_limits: Default::default(),
+        http_version: HttpVersion::Http11,
+        unix_peer,
+        received_at: std::time::Instant::now(),
+    }
+}
+
+#[test]
+fn test_peer_addr_tcp() {
+    let client = Client::debug_with(vec![]).unwrap();
+    let hyper = hyper::Request::get("/test").body(()).unwrap().into_parts().0;
+
+    let connection = peer_connection(Some("10.0.0.1:9000"), None);
+    let req = Request::from_hyp(client.rocket(), &hyper, Some(connection)).unwrap();
+    assert!(!req.is_unix_socket());
+
+    let peer = req.peer_addr().unwrap();
+    assert_eq!(peer, PeerAddr::Tcp("10.0.0.1:9000".parse().unwrap()));
+    assert!(!peer.is_unix());
+    assert_eq!(peer.credentials(), None);
+
+    let req = Request::from_hyp(client.rocket(), &hyper, None).unwrap();
+    assert!(!req.is_unix_socket());
+    assert_eq!(req.peer_addr(), None);
+}
+
+#[test]
+fn test_peer_addr_unix() {
+    let client = Client::debug_with(vec![]).unwrap();
+    let mut req = hyper::Request::get("/test").body(()).unwrap();
+    req.headers_mut().append("X-Real-IP", hyper::HeaderValue::from_static("8.8.8.8"));
+    let hyper = req.into_parts().0;
+
+    let credentials = PeerCredentials { uid: 1000, gid: 100, pid: Some(4242) };
+    let unix_peer = UnixPeer {
+        path: Some("/run/sidecar.sock".into()),
+        credentials: Some(credentials),
+    };
+
+    let connection = peer_connection(None, Some(unix_peer.clone()));
+    let req = Request::from_hyp(client.rocket(), &hyper, Some(connection)).unwrap();
+    assert!(req.is_unix_socket());
+    assert_eq!(req.remote(), None);
+
+    // Proxy headers never stand in for a Unix socket peer.
+    let peer = req.peer_addr().unwrap();
+    assert_eq!(peer, PeerAddr::Unix(&unix_peer));
+    assert!(peer.is_unix());
+    assert_eq!(peer.credentials(), Some(credentials));
+
+    // Credentials are optional: not every platform reports them.
+    let connection = peer_connection(None, Some(UnixPeer::default()));
+    let req = Request::from_hyp(client.rocket(), &hyper, Some(connection)).unwrap();
+    assert!(req.is_unix_socket());
+    assert_eq!(req.peer_addr().unwrap().credentials(), None);
+}
+
+#[test]
+fn test_peer_addr_local() {
+    let client = Client::debug_with(vec![]).unwrap();
+    let request = client.get("/").remote("127.0.0.1:8000".parse().unwrap());
+    assert!(!request.inner().is_unix_socket());
+
+    let credentials = PeerCredentials { uid: 0, gid: 0, pid: None };
+    let unix_peer = UnixPeer { path: None, credentials: Some(credentials) };
+    let request = request.unix_peer(unix_peer);
+    assert!(request.inner().is_unix_socket());
+    assert_eq!(request.inner().peer_addr().unwrap().credentials(), Some(credentials));
+}
@@ -131,6 +131,8 @@ impl<'r> Body<'r> {
         self.max_chunk = max_chunk;
     }
 
+    /// Drops the body's data but keeps its size, or lack thereof, so that the
+    /// response is framed exactly as it would have been with the data.
     pub(crate) fn strip(&mut self) {
         let body = std::mem::take(self);
         *self = match body.inner {
@@ -139,7 +141,12 @@ impl<'r> Body<'r> {
                 inner: Inner::Phantom(b),
                 max_chunk: body.max_chunk,
             },
-            Inner::Unsized(_) | Inner::None => Body::default()
+            Inner::Unsized(_) => Body {
+                size: None,
+                inner: Inner::None,
+                max_chunk: body.max_chunk,
+            },
+            Inner::None => Body::default()
         };
     }
 
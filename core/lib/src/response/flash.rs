@@ -1,10 +1,12 @@
+use std::borrow::Cow;
+
 use time::Duration;
 use serde::ser::{Serialize, Serializer, SerializeStruct};
 
 use crate::outcome::IntoOutcome;
 use crate::response::{self, Responder};
 use crate::request::{self, Request, FromRequest};
-use crate::http::{Status, Cookie, CookieJar};
+use crate::http::{Status, Cookie, CookieJar, SameSite};
 use std::sync::atomic::{AtomicBool, Ordering};
 
 // The name of the actual flash cookie.
@@ -13,6 +15,10 @@ const FLASH_COOKIE_NAME: &str = "_flash";
 // Character to use as a delimiter after the cookie's name's length.
 const FLASH_COOKIE_DELIM: char = ':';
 
+// Character prefixing a cookie holding any number of messages. Single-message
+// cookies always begin with a digit, so the two encodings never overlap.
+const FLASH_COOKIE_MULTI: char = '~';
+
 /// Sets a "flash" cookie that will be removed when it is accessed. The
 /// analogous request type is [`FlashMessage`].
 ///
@@ -34,6 +40,8 @@ const FLASH_COOKIE_DELIM: char = ':';
 /// Messages can be retrieved on the request side via the [`FlashMessage`] type
 /// and the [kind](#method.kind) and [message](#method.message) methods.
 ///
+/// To send several messages at once, use [`Flashes`] instead.
+///
 /// # Response
 ///
 /// The `Responder` implementation for `Flash` sets the message cookie and then
@@ -41,6 +49,32 @@ const FLASH_COOKIE_DELIM: char = ':';
 /// it simply sets a cookie and delegates the rest of the response handling to
 /// the wrapped responder.
 ///
+/// # Cookie Attributes
+///
+/// By default, the flash cookie is set with `SameSite=Lax`, `HttpOnly`, a
+/// `Path` of `/`, and a `Max-Age` of 5 minutes. It is marked `Secure` when the
+/// request's [scheme](Request::scheme()) is `https`. `Lax`, rather than the
+/// stricter default of [`CookieJar::add()`], lets the message survive a
+/// redirect back from another site. Each attribute can be overridden with the
+/// builder methods [`same_site()`](Flash::same_site()),
+/// [`secure()`](Flash::secure()), [`http_only()`](Flash::http_only()),
+/// [`path()`](Flash::path()), and [`max_age()`](Flash::max_age()):
+///
+/// ```rust
+/// use rocket::http::SameSite;
+/// use rocket::response::{Flash, Redirect};
+/// use rocket::time::Duration;
+///
+/// let flash = Flash::success(Redirect::to("/"), "Saved.")
+///     .same_site(SameSite::Strict)
+///     .secure(true)
+///     .max_age(Duration::seconds(30));
+/// ```
+///
+/// When a [`FlashMessage`] is read, the cookie is removed with a `Path` of
+/// `/`. A flash cookie set with a different path is thus not removed on
+/// access; it expires at the end of its `Max-Age` instead.
+///
 /// # Example
 ///
 /// The following routes illustrate the use of a `Flash` message on both the
@@ -73,11 +107,23 @@ const FLASH_COOKIE_DELIM: char = ':';
 /// one and otherwise emits a standard welcome message. Note that if the user
 /// were to refresh the index page after viewing a flash message, the user would
 /// receive the standard welcome message.
+///
+/// # Signing
+///
+/// When the `secrets` feature is enabled, the flash cookie is signed with the
+/// application's [`secret_key`](crate::Config::secret_key), the same key used
+/// for private cookies. The message remains readable by the client, but a
+/// flash cookie that was modified or forged by the client fails verification
+/// and is removed and ignored as if it were never sent. To send and accept
+/// unsigned flash cookies, as Rocket did previously, set
+/// [`unsigned_flash`](crate::Config::unsigned_flash) to `true`. Without the
+/// `secrets` feature, flash cookies are never signed.
 #[derive(Debug)]
 pub struct Flash<R> {
     kind: String,
     message: String,
     consumed: AtomicBool,
+    attrs: CookieAttrs,
     inner: R,
 }
 
@@ -86,8 +132,9 @@ pub struct Flash<R> {
 /// # Flash Cookie
 ///
 /// A `FlashMessage` holds the parsed contents of the flash cookie. As long as
-/// there is a flash cookie present (set by the `Flash` `Responder`), a
-/// `FlashMessage` request guard will succeed.
+/// there is a valid flash cookie present (set by the `Flash` `Responder`), a
+/// `FlashMessage` request guard will succeed. See [signing](Flash#signing) for
+/// what makes a flash cookie valid.
 ///
 /// The flash cookie is cleared if either the [`kind()`] or [`message()`] method is
 /// called. If neither method is called, the flash cookie is not cleared.
@@ -96,6 +143,154 @@ pub struct Flash<R> {
 /// [`message()`]: Flash::message()
 pub type FlashMessage<'a> = crate::response::Flash<&'a CookieJar<'a>>;
 
+/// Sets a "flash" cookie holding any number of messages that will be removed
+/// when accessed. The analogous request type is [`FlashMessages`].
+///
+/// `Flashes` is like [`Flash`] but queues several messages, each with its own
+/// kind, across a single redirect. Messages are added with the
+/// [`message()`](Flashes::message()), [`success()`](Flashes::success()),
+/// [`warning()`](Flashes::warning()), and [`error()`](Flashes::error())
+/// builder methods or all at once via [`messages()`](Flashes::messages()). If
+/// no messages are queued, no cookie is set.
+///
+/// Both `Flash` and `Flashes` write the same cookie, so the last to respond
+/// wins. On the request side, [`FlashMessages`] retrieves every message while
+/// [`FlashMessage`] retrieves only the first. The cookie's attributes are set
+/// with the same builder methods and defaults as for `Flash`; see [cookie
+/// attributes](Flash#cookie-attributes).
+///
+/// # Example
+///
+/// ```rust
+/// # #[macro_use] extern crate rocket;
+/// use rocket::response::{Flashes, Redirect};
+/// use rocket::request::FlashMessages;
+///
+/// #[post("/save")]
+/// fn save() -> Flashes<Redirect> {
+///     Flashes::new(Redirect::to(uri!(index)))
+///         .success("Saved.")
+///         .warning("Your password expires soon.")
+///         .warning("Your profile is incomplete.")
+/// }
+///
+/// #[get("/")]
+/// fn index(flashes: FlashMessages<'_>) -> String {
+///     flashes.iter()
+///         .map(|flash| format!("{}: {}", flash.kind(), flash.message()))
+///         .collect::<Vec<_>>()
+///         .join("\n")
+/// }
+/// ```
+#[derive(Debug)]
+pub struct Flashes<R> {
+    messages: Vec<(String, String)>,
+    attrs: CookieAttrs,
+    inner: R,
+}
+
+/// The attributes of the flash cookie set by a `Flash` or `Flashes`.
+#[derive(Debug, Clone)]
+struct CookieAttrs {
+    same_site: SameSite,
+    secure: Option<bool>,
+    http_only: bool,
+    path: Cow<'static, str>,
+    max_age: Duration,
+}
+
+impl Default for CookieAttrs {
+    fn default() -> Self {
+        CookieAttrs {
+            same_site: SameSite::Lax,
+            secure: None,
+            http_only: true,
+            path: Cow::Borrowed("/"),
+            max_age: Duration::minutes(5),
+        }
+    }
+}
+
+// Builder methods for the flash cookie's attributes, shared by `Flash` and
+// `Flashes`.
+macro_rules! cookie_attr_methods {
+    () => (
+        /// Sets the `SameSite` attribute of the flash cookie. The default is
+        /// `Lax`.
+        pub fn same_site(mut self, value: SameSite) -> Self {
+            self.attrs.same_site = value;
+            self
+        }
+
+        /// Sets whether the flash cookie has the `Secure` attribute. By
+        /// default, it does if and only if the request's scheme is `https`.
+        pub fn secure(mut self, value: bool) -> Self {
+            self.attrs.secure = Some(value);
+            self
+        }
+
+        /// Sets whether the flash cookie has the `HttpOnly` attribute. The
+        /// default is `true`.
+        pub fn http_only(mut self, value: bool) -> Self {
+            self.attrs.http_only = value;
+            self
+        }
+
+        /// Sets the `Path` attribute of the flash cookie. The default is `/`.
+        /// See [cookie attributes](Flash#cookie-attributes) for how this
+        /// affects removal.
+        pub fn path<P: Into<Cow<'static, str>>>(mut self, path: P) -> Self {
+            self.attrs.path = path.into();
+            self
+        }
+
+        /// Sets the `Max-Age` attribute of the flash cookie. The default is 5
+        /// minutes.
+        pub fn max_age(mut self, value: Duration) -> Self {
+            self.attrs.max_age = value;
+            self
+        }
+    )
+}
+
+/// Request guard to retrieve all [`Flashes`] (or [`Flash`]) messages from a
+/// request.
+///
+/// # Flash Cookie
+///
+/// A `FlashMessages` holds the parsed contents of the flash cookie, which may
+/// contain zero, one, or several messages. The guard always succeeds unless
+/// the cookie is malformed, in which case it fails with `BadRequest`. A cookie
+/// set by [`Flash`] is read as a single message.
+///
+/// As with [`FlashMessage`], the flash cookie is cleared only once the
+/// [`kind()`](Flash::kind()) or [`message()`](Flash::message()) of any of the
+/// messages is accessed. Iterating alone does not clear the cookie.
+///
+/// # Example
+///
+/// ```rust
+/// # #[macro_use] extern crate rocket;
+/// use rocket::request::FlashMessages;
+///
+/// #[get("/")]
+/// fn index(flashes: FlashMessages<'_>) -> String {
+///     if flashes.is_empty() {
+///         return "Welcome!".into();
+///     }
+///
+///     flashes.into_iter()
+///         .map(|flash| flash.into_inner())
+///         .map(|(kind, message)| format!("{}: {}", kind, message))
+///         .collect::<Vec<_>>()
+///         .join("\n")
+/// }
+/// ```
+#[derive(Debug)]
+pub struct FlashMessages<'a> {
+    messages: Vec<FlashMessage<'a>>,
+}
+
 impl<R> Flash<R> {
     /// Constructs a new `Flash` message with the given `kind`, `message`, and
     /// underlying `responder`.
@@ -116,6 +311,7 @@ impl<R> Flash<R> {
             kind: kind.into(),
             message: message.into(),
             consumed: AtomicBool::default(),
+            attrs: CookieAttrs::default(),
             inner: res,
         }
     }
@@ -174,14 +370,241 @@ impl<R> Flash<R> {
         Flash::new(responder, "error", message.into())
     }
 
-    fn cookie(&self) -> Cookie<'static> {
+    cookie_attr_methods!();
+
+    fn cookie(&self, req: &Request<'_>) -> Cookie<'static> {
         let content = format!("{}{}{}{}",
             self.kind.len(), FLASH_COOKIE_DELIM, self.kind, self.message);
 
-        Cookie::build(FLASH_COOKIE_NAME, content)
-            .max_age(Duration::minutes(5))
-            .finish()
+        flash_cookie(content, &self.attrs, req)
+    }
+}
+
+impl<R> Flashes<R> {
+    /// Constructs a new `Flashes` with no messages and the given underlying
+    /// `responder`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::response::{Redirect, Flashes};
+    ///
+    /// let flashes = Flashes::new(Redirect::to("/"));
+    /// assert!(flashes.is_empty());
+    /// ```
+    pub fn new(responder: R) -> Flashes<R> {
+        Flashes { messages: vec![], attrs: CookieAttrs::default(), inner: responder }
+    }
+
+    /// Adds every `(kind, message)` pair in `messages` to `self`.
+    ///
+    /// # Example
+    ///
+    /// Queue two messages that redirect to "/".
+    ///
+    /// ```rust
+    /// use rocket::response::{Redirect, Flashes};
+    ///
+    /// let flashes = Flashes::new(Redirect::to("/")).messages(vec![
+    ///     ("success", "Saved."),
+    ///     ("suggestion", "Try this out!"),
+    /// ]);
+    ///
+    /// assert_eq!(flashes.len(), 2);
+    /// ```
+    pub fn messages<I, K, M>(mut self, messages: I) -> Self
+        where I: IntoIterator<Item = (K, M)>, K: Into<String>, M: Into<String>
+    {
+        let messages = messages.into_iter().map(|(k, m)| (k.into(), m.into()));
+        self.messages.extend(messages);
+        self
+    }
+
+    /// Adds a message with the given `kind` and `message` to `self`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::response::{Redirect, Flashes};
+    ///
+    /// let flashes = Flashes::new(Redirect::to("/"))
+    ///     .message("suggestion", "Try this out!")
+    ///     .message("suggestion", "Or this!");
+    ///
+    /// assert_eq!(flashes.len(), 2);
+    /// ```
+    pub fn message<K: Into<String>, M: Into<String>>(mut self, kind: K, message: M) -> Self {
+        self.messages.push((kind.into(), message.into()));
+        self
+    }
+
+    /// Adds a "success" message with the given `message` to `self`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::response::{Redirect, Flashes};
+    ///
+    /// # #[allow(unused_variables)]
+    /// let flashes = Flashes::new(Redirect::to("/"))
+    ///     .success("It worked!");
+    /// ```
+    pub fn success<S: Into<String>>(self, message: S) -> Self {
+        self.message("success", message)
+    }
+
+    /// Adds a "warning" message with the given `message` to `self`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::response::{Redirect, Flashes};
+    ///
+    /// # #[allow(unused_variables)]
+    /// let flashes = Flashes::new(Redirect::to("/"))
+    ///     .warning("Watch out!");
+    /// ```
+    pub fn warning<S: Into<String>>(self, message: S) -> Self {
+        self.message("warning", message)
+    }
+
+    /// Adds an "error" message with the given `message` to `self`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::response::{Redirect, Flashes};
+    ///
+    /// # #[allow(unused_variables)]
+    /// let flashes = Flashes::new(Redirect::to("/"))
+    ///     .error("Whoops!");
+    /// ```
+    pub fn error<S: Into<String>>(self, message: S) -> Self {
+        self.message("error", message)
+    }
+
+    /// Returns the number of queued messages.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::response::{Redirect, Flashes};
+    ///
+    /// let flashes = Flashes::new(Redirect::to("/"));
+    /// assert_eq!(flashes.len(), 0);
+    ///
+    /// let flashes = flashes.success("Yes!").error("No!");
+    /// assert_eq!(flashes.len(), 2);
+    /// ```
+    pub fn len(&self) -> usize {
+        self.messages.len()
+    }
+
+    /// Returns `true` if no messages are queued.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::response::{Redirect, Flashes};
+    ///
+    /// let flashes = Flashes::new(Redirect::to("/"));
+    /// assert!(flashes.is_empty());
+    /// assert!(!flashes.success("Yes!").is_empty());
+    /// ```
+    pub fn is_empty(&self) -> bool {
+        self.messages.is_empty()
+    }
+
+    cookie_attr_methods!();
+
+    fn cookie(&self, req: &Request<'_>) -> Cookie<'static> {
+        let mut content = String::from(FLASH_COOKIE_MULTI);
+        for (kind, message) in &self.messages {
+            content.push_str(&format!("{}{}{}{}{}{}", kind.len(), FLASH_COOKIE_DELIM,
+                message.len(), FLASH_COOKIE_DELIM, kind, message));
+        }
+
+        flash_cookie(content, &self.attrs, req)
+    }
+}
+
+fn flash_cookie(content: String, attrs: &CookieAttrs, req: &Request<'_>) -> Cookie<'static> {
+    let secure = attrs.secure.unwrap_or_else(|| req.scheme() == "https");
+    Cookie::build(FLASH_COOKIE_NAME, content)
+        .same_site(attrs.same_site)
+        .secure(secure)
+        .http_only(attrs.http_only)
+        .path(attrs.path.clone())
+        .max_age(attrs.max_age)
+        .finish()
+}
+
+// Adds the flash `cookie` to `req`'s jar, signed unless `unsigned_flash` is set.
+fn set_flash_cookie(req: &Request<'_>, cookie: Cookie<'static>) {
+    #[cfg(feature = "secrets")]
+    if !req.rocket().config().unsigned_flash {
+        req.cookies().add_signed(cookie);
+        return;
+    }
+
+    req.cookies().add(cookie);
+}
+
+// Returns the flash cookie in `req`, if any. Unless `unsigned_flash` is set, a
+// cookie without a valid signature is treated as absent and removed.
+fn get_flash_cookie(req: &Request<'_>) -> Option<Cookie<'static>> {
+    #[cfg(feature = "secrets")]
+    if !req.rocket().config().unsigned_flash {
+        let cookie = req.cookies().get_signed(FLASH_COOKIE_NAME);
+        if cookie.is_none() && req.cookies().get(FLASH_COOKIE_NAME).is_some() {
+            warn_!("Flash: dropping cookie with a missing or invalid signature.");
+            req.cookies().remove(Cookie::named(FLASH_COOKIE_NAME));
+        }
+
+        return cookie;
+    }
+
+    req.cookies().get(FLASH_COOKIE_NAME).cloned()
+}
+
+/// Parses the contents of a flash cookie into `(kind, message)` pairs.
+///
+/// A cookie set by `Flash` is of the form `{kind_len}:{kind}{message}` while a
+/// cookie set by `Flashes` is `~` followed by zero or more messages of the form
+/// `{kind_len}:{message_len}:{kind}{message}`. All lengths are in bytes.
+fn parse_flash_cookie(content: &str) -> Option<Vec<(&str, &str)>> {
+    fn take_len(string: &str) -> Option<(usize, &str)> {
+        let (len, rest) = string.split_once(FLASH_COOKIE_DELIM)?;
+        if len.is_empty() || !len.bytes().all(|b| b.is_ascii_digit()) {
+            return None;
+        }
+
+        Some((len.parse().ok()?, rest))
     }
+
+    fn take(string: &str, len: usize) -> Option<(&str, &str)> {
+        Some((string.get(..len)?, string.get(len..)?))
+    }
+
+    let mut rest = match content.strip_prefix(FLASH_COOKIE_MULTI) {
+        Some(rest) => rest,
+        None => {
+            let (kind_len, rest) = take_len(content)?;
+            return Some(vec![take(rest, kind_len)?]);
+        }
+    };
+
+    let mut messages = vec![];
+    while !rest.is_empty() {
+        let (kind_len, tail) = take_len(rest)?;
+        let (message_len, tail) = take_len(tail)?;
+        let (kind, tail) = take(tail, kind_len)?;
+        let (message, tail) = take(tail, message_len)?;
+        messages.push((kind, message));
+        rest = tail;
+    }
+
+    Some(messages)
 }
 
 /// Sets the message cookie and then uses the wrapped responder to complete the
@@ -190,7 +613,20 @@ impl<R> Flash<R> {
 /// the response is the `Outcome` of the wrapped `Responder`.
 impl<'r, 'o: 'r, R: Responder<'r, 'o>> Responder<'r, 'o> for Flash<R> {
     fn respond_to(self, req: &'r Request<'_>) -> response::Result<'o> {
-        req.cookies().add(self.cookie());
+        set_flash_cookie(req, self.cookie(req));
+        self.inner.respond_to(req)
+    }
+}
+
+/// Sets the message cookie, if there are any messages, and then uses the
+/// wrapped responder to complete the response. As a result, the `Outcome` of
+/// the response is the `Outcome` of the wrapped `Responder`.
+impl<'r, 'o: 'r, R: Responder<'r, 'o>> Responder<'r, 'o> for Flashes<R> {
+    fn respond_to(self, req: &'r Request<'_>) -> response::Result<'o> {
+        if !self.messages.is_empty() {
+            set_flash_cookie(req, self.cookie(req));
+        }
+
         self.inner.respond_to(req)
     }
 }
@@ -203,6 +639,7 @@ impl<'r> FlashMessage<'r> {
             kind: kind.into(),
             message: message.into(),
             consumed: AtomicBool::new(false),
+            attrs: CookieAttrs::default(),
             inner: req.cookies(),
         }
     }
@@ -234,8 +671,45 @@ impl<'r> FlashMessage<'r> {
     }
 }
 
+impl<'r> FlashMessages<'r> {
+    /// Returns an iterator over the messages in `self`.
+    pub fn iter(&self) -> std::slice::Iter<'_, FlashMessage<'r>> {
+        self.messages.iter()
+    }
+
+    /// Returns the number of messages in `self`.
+    pub fn len(&self) -> usize {
+        self.messages.len()
+    }
+
+    /// Returns `true` if `self` contains no messages.
+    pub fn is_empty(&self) -> bool {
+        self.messages.is_empty()
+    }
+}
+
+impl<'r> IntoIterator for FlashMessages<'r> {
+    type Item = FlashMessage<'r>;
+    type IntoIter = std::vec::IntoIter<FlashMessage<'r>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.messages.into_iter()
+    }
+}
+
+impl<'a, 'r> IntoIterator for &'a FlashMessages<'r> {
+    type Item = &'a FlashMessage<'r>;
+    type IntoIter = std::slice::Iter<'a, FlashMessage<'r>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
 /// Retrieves a flash message from a flash cookie. If there is no flash cookie,
-/// or if the flash cookie is malformed, an empty `Err` is returned.
+/// if the flash cookie is malformed, or if it contains no messages, an empty
+/// `Err` is returned. If the cookie contains several messages, as set by
+/// [`Flashes`], the first is returned.
 ///
 /// The suggested use is through an `Option` and the `FlashMessage` type alias
 /// in `request`: `Option<FlashMessage>`.
@@ -245,24 +719,39 @@ impl<'r> FromRequest<'r> for FlashMessage<'r> {
 
     async fn from_request(req: &'r Request<'_>) -> request::Outcome<Self, Self::Error> {
         trace_!("Flash: attempting to retrieve message.");
-        req.cookies().get(FLASH_COOKIE_NAME).ok_or(()).and_then(|cookie| {
+        get_flash_cookie(req).ok_or(()).and_then(|cookie| {
             trace_!("Flash: retrieving message: {:?}", cookie);
-
-            // Parse the flash message.
-            let content = cookie.value();
-            let (len_str, kv) = match content.find(FLASH_COOKIE_DELIM) {
-                Some(i) => (&content[..i], &content[(i + 1)..]),
-                None => return Err(()),
-            };
-
-            match len_str.parse::<usize>() {
-                Ok(i) if i <= kv.len() => Ok(Flash::named(&kv[..i], &kv[i..], req)),
-                _ => Err(())
-            }
+            let messages = parse_flash_cookie(cookie.value()).ok_or(())?;
+            let (kind, message) = messages.into_iter().next().ok_or(())?;
+            Ok(Flash::named(kind, message, req))
         }).into_outcome(Status::BadRequest)
     }
 }
 
+/// Retrieves all flash messages from a flash cookie. If there is no flash
+/// cookie, an empty set of messages is returned. If the flash cookie is
+/// malformed, an empty `Err` is returned.
+#[crate::async_trait]
+impl<'r> FromRequest<'r> for FlashMessages<'r> {
+    type Error = ();
+
+    async fn from_request(req: &'r Request<'_>) -> request::Outcome<Self, Self::Error> {
+        trace_!("Flash: attempting to retrieve messages.");
+        let cookie = match get_flash_cookie(req) {
+            Some(cookie) => cookie,
+            None => return request::Outcome::Success(FlashMessages { messages: vec![] }),
+        };
+
+        trace_!("Flash: retrieving messages: {:?}", cookie);
+        parse_flash_cookie(cookie.value())
+            .map(|messages| messages.into_iter()
+                .map(|(kind, message)| Flash::named(kind, message, req))
+                .collect())
+            .map(|messages| FlashMessages { messages })
+            .into_outcome((Status::BadRequest, ()))
+    }
+}
+
 impl Serialize for FlashMessage<'_> {
     fn serialize<S: Serializer>(&self, ser: S) -> Result<S::Ok, S::Error> {
         let mut flash = ser.serialize_struct("Flash", 2)?;
@@ -271,3 +760,9 @@ impl Serialize for FlashMessage<'_> {
         flash.end()
     }
 }
+
+impl Serialize for FlashMessages<'_> {
+    fn serialize<S: Serializer>(&self, ser: S) -> Result<S::Ok, S::Error> {
+        ser.collect_seq(self.iter())
+    }
+}
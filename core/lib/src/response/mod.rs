@@ -19,6 +19,7 @@ mod redirect;
 mod response;
 mod debug;
 mod body;
+mod sized_stream;
 
 pub(crate) mod flash;
 
@@ -33,8 +34,9 @@ pub use self::response::{Response, Builder};
 pub use self::body::Body;
 pub use self::responder::Responder;
 pub use self::redirect::Redirect;
-pub use self::flash::Flash;
+pub use self::flash::{Flash, Flashes};
 pub use self::debug::Debug;
+pub use self::sized_stream::SizedStream;
 
 /// Type alias for the `Result` of a [`Responder::respond_to()`] call.
 pub type Result<'r> = std::result::Result<Response<'r>, crate::http::Status>;
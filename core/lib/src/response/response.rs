@@ -746,7 +746,8 @@ impl<'r> Response<'r> {
     }
 
     // Makes the `AsyncRead`er in the body empty but leaves the size of the body
-    // if it exists. Meant to be used during HEAD handling.
+    // if it exists. Meant to be used during HEAD handling. See
+    // `Request::strips_body()`.
     #[inline(always)]
     pub(crate) fn strip_body(&mut self) {
         self.body.strip();
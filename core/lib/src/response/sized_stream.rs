@@ -0,0 +1,218 @@
+use std::io;
+use std::convert::TryFrom;
+use std::task::{Context, Poll};
+use std::pin::Pin;
+
+use futures::ready;
+use tokio::io::{AsyncRead, AsyncSeek, ReadBuf};
+use pin_project_lite::pin_project;
+
+use crate::http::Status;
+use crate::request::{Request, RangeHeader};
+use crate::response::{self, Response, Responder};
+
+/// A responder that streams the contents of an [`AsyncRead`] with an
+/// optional, known length.
+///
+/// `SizedStream` is intended for large bodies that shouldn't be read into
+/// memory but whose length is known ahead of time, such as a file or an object
+/// fetched from a remote store. Bytes are read from the reader only as fast as
+/// they are written to the network.
+///
+/// # Responder
+///
+/// No `Content-Type` is set. If a length is provided, the body is
+/// [sized](crate::response::Body#sized) and is sent with a `Content-Length`
+/// equal to the length. Otherwise, the body is
+/// [unsized](crate::response::Body#unsized) and is chunk-encoded.
+///
+/// The reader is trusted to produce exactly as many bytes as declared. Bytes
+/// beyond the declared length are never sent. If the reader ends before
+/// producing the declared number of bytes, reading the body fails with an
+/// [`UnexpectedEof`](io::ErrorKind::UnexpectedEof) error and the connection
+/// is aborted so that the client can't mistake a truncated body for a
+/// complete one.
+///
+/// ## Ranges
+///
+/// When a length is known, a [`RangeHeader`] set via
+/// [`SizedStream::range()`] is honored:
+///
+///   * If the header resolves to a single range, the response is a `206
+///     Partial Content` with a `Content-Range` header and a body consisting
+///     only of the bytes in the range. The bytes preceding the range are read
+///     and discarded.
+///   * If none of the requested ranges are satisfiable, the response is a
+///     `416 Range Not Satisfiable` with a `Content-Range` header and no body.
+///   * If the header resolves to multiple disjoint ranges, or the length is
+///     unknown, the header is ignored and the full body is sent.
+///
+/// # Example
+///
+/// ```rust
+/// # #[macro_use] extern crate rocket;
+/// use rocket::request::RangeHeader;
+/// use rocket::response::SizedStream;
+/// use rocket::tokio::fs::File;
+///
+/// #[get("/video")]
+/// async fn video(range: Option<RangeHeader>) -> std::io::Result<SizedStream<File>> {
+///     let file = File::open("videos/big.mp4").await?;
+///     let len = file.metadata().await?.len();
+///     Ok(SizedStream::new(file, len).range(range))
+/// }
+/// ```
+#[derive(Debug)]
+pub struct SizedStream<R> {
+    reader: R,
+    len: Option<u64>,
+    range: Option<RangeHeader>,
+}
+
+impl<R: AsyncRead + Send> SizedStream<R> {
+    /// Creates a new `SizedStream` that reads from `reader`, which is expected
+    /// to produce exactly `len` bytes if `len` is `Some`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use std::io::Cursor;
+    /// use rocket::response::SizedStream;
+    ///
+    /// let known = SizedStream::new(Cursor::new("Hello!"), 6);
+    /// let unknown = SizedStream::new(Cursor::new("Hello!"), None);
+    /// ```
+    pub fn new<L: Into<Option<u64>>>(reader: R, len: L) -> Self {
+        SizedStream { reader, len: len.into(), range: None }
+    }
+
+    /// Sets the `Range` header to honor when responding. Passing `None`, the
+    /// default, always sends the full body. See [ranges](#ranges) for details.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use std::io::Cursor;
+    /// use rocket::request::RangeHeader;
+    /// use rocket::response::SizedStream;
+    ///
+    /// let range = RangeHeader::parse("bytes=0-1");
+    /// let stream = SizedStream::new(Cursor::new("Hello!"), 6).range(range);
+    /// ```
+    pub fn range<H: Into<Option<RangeHeader>>>(mut self, range: H) -> Self {
+        self.range = range.into();
+        self
+    }
+}
+
+impl<'r, 'o: 'r, R: AsyncRead + Send + 'o> Responder<'r, 'o> for SizedStream<R> {
+    fn respond_to(self, _: &'r Request<'_>) -> response::Result<'o> {
+        let len = match self.len {
+            Some(len) => len,
+            None => return Response::build().streamed_body(self.reader).ok(),
+        };
+
+        let mut response = Response::build();
+        let (start, end) = match self.range.map(|range| range.resolve(len)) {
+            Some(Ok(ranges)) if ranges.len() == 1 => {
+                let (start, end) = ranges[0];
+                let content_range = format!("bytes {}-{}/{}", start, end, len);
+                response.status(Status::PartialContent)
+                    .raw_header("Content-Range", content_range);
+
+                (start, end + 1)
+            }
+            Some(Err(e)) => {
+                return Response::build()
+                    .status(Status::RangeNotSatisfiable)
+                    .raw_header("Content-Range", e.content_range())
+                    .ok();
+            }
+            _ => (0, len),
+        };
+
+        let pump = Pump { reader: self.reader, skip: start, remaining: end - start, pos: 0 };
+        match usize::try_from(end - start) {
+            Ok(size) => response.sized_body(size, pump).ok(),
+            Err(_) => response.streamed_body(pump).ok(),
+        }
+    }
+}
+
+pin_project! {
+    /// Skips the first `skip` bytes of `reader`, then yields exactly
+    /// `remaining` bytes or fails.
+    struct Pump<R> {
+        #[pin]
+        reader: R,
+        skip: u64,
+        remaining: u64,
+        pos: u64,
+    }
+}
+
+impl<R> Pump<R> {
+    fn short_read(&self) -> io::Error {
+        let msg = format!("stream ended {} bytes short of its declared length",
+            self.skip + self.remaining);
+
+        io::Error::new(io::ErrorKind::UnexpectedEof, msg)
+    }
+}
+
+impl<R: AsyncRead> AsyncRead for Pump<R> {
+    fn poll_read(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut ReadBuf<'_>
+    ) -> Poll<io::Result<()>> {
+        while self.skip > 0 {
+            let mut scratch = [0u8; 4096];
+            let cap = self.skip.min(scratch.len() as u64) as usize;
+            let mut discard = ReadBuf::new(&mut scratch[..cap]);
+            ready!(self.as_mut().project().reader.poll_read(cx, &mut discard))?;
+            match discard.filled().len() {
+                0 => return Poll::Ready(Err(self.short_read())),
+                n => *self.as_mut().project().skip -= n as u64,
+            }
+        }
+
+        if self.remaining == 0 {
+            return Poll::Ready(Ok(()));
+        }
+
+        let init = buf.filled().len();
+        ready!(self.as_mut().project().reader.poll_read(cx, buf))?;
+        let read = (buf.filled().len() - init) as u64;
+        if read == 0 && buf.remaining() > 0 {
+            return Poll::Ready(Err(self.short_read()));
+        }
+
+        // Anything past the declared length is dropped.
+        let read = read.min(self.remaining);
+        buf.set_filled(init + read as usize);
+
+        let me = self.project();
+        *me.remaining -= read;
+        *me.pos += read;
+        Poll::Ready(Ok(()))
+    }
+}
+
+/// A `Pump` is only "seekable" so that it can be used as a sized body with a
+/// preset size; it can report but not change its position.
+impl<R: AsyncRead> AsyncSeek for Pump<R> {
+    fn start_seek(self: Pin<&mut Self>, position: io::SeekFrom) -> io::Result<()> {
+        let end = self.pos + self.remaining;
+        match position {
+            io::SeekFrom::Current(0) => Ok(()),
+            io::SeekFrom::Start(n) if n == self.pos => Ok(()),
+            io::SeekFrom::End(0) if end == self.pos => Ok(()),
+            _ => Err(io::Error::new(io::ErrorKind::Other, "stream is not seekable")),
+        }
+    }
+
+    fn poll_complete(self: Pin<&mut Self>, _: &mut Context<'_>) -> Poll<io::Result<u64>> {
+        Poll::Ready(Ok(self.pos))
+    }
+}
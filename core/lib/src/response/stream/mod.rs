@@ -201,7 +201,7 @@ pub use self::one::One;
 pub use self::text::TextStream;
 pub use self::bytes::ByteStream;
 pub use self::reader::ReaderStream;
-pub use self::sse::{Event, EventStream};
+pub use self::sse::{Event, EventStream, LastEventId};
 
 crate::export! {
     /// Retrofitted support for [`Stream`]s with `yield`, `for await` syntax.
@@ -5,9 +5,10 @@ use tokio::time::Duration;
 use futures::stream::{self, Stream, StreamExt};
 use futures::future::ready;
 
-use crate::request::Request;
+use crate::request::{self, Request, FromRequest};
 use crate::response::{self, Response, Responder, stream::{ReaderStream, RawLinedEvent}};
 use crate::http::ContentType;
+use crate::outcome::Outcome::*;
 
 /// A Server-Sent `Event` (SSE) in a Server-Sent [`struct@EventStream`].
 ///
@@ -355,6 +356,11 @@ impl Event {
 /// is [unsized](crate::response::Body#unsized), and values are sent as soon as
 /// they are yielded by the internal iterator.
 ///
+/// So that events reach the client as they are sent, the response also sets
+/// `Cache-Control: no-cache, no-transform`, which prohibits intermediaries from
+/// caching or compressing the stream, and `X-Accel-Buffering: no`, which
+/// disables response buffering in reverse proxies like NGINX.
+///
 /// ## Heartbeat
 ///
 /// A heartbeat comment is injected into the internal stream and sent at a fixed
@@ -568,13 +574,74 @@ impl<'r, S: Stream<Item = Event> + Send + 'r> Responder<'r, 'r> for EventStream<
     fn respond_to(self, _: &'r Request<'_>) -> response::Result<'r> {
         Response::build()
             .header(ContentType::EventStream)
-            .raw_header("Cache-Control", "no-cache")
+            .raw_header("Cache-Control", "no-cache, no-transform")
             .raw_header("Expires", "0")
+            .raw_header("X-Accel-Buffering", "no")
             .streamed_body(self.into_reader())
             .ok()
     }
 }
 
+/// A request guard for the `Last-Event-ID` header sent by a reconnecting
+/// Server-Sent Events client.
+///
+/// When a connection to an [`struct@EventStream`] is lost, clients like
+/// JavaScript's `EventSource` reconnect and send the [id](Event::id()) of the
+/// last event they received in the `Last-Event-ID` header. A handler can use
+/// this guard to resume the stream after that event.
+///
+/// The guard forwards if the header is missing, so a handler that serves both
+/// new and reconnecting clients should request an `Option<LastEventId>`. The
+/// header's value is not otherwise validated; an empty value, which resets the
+/// last event id at the client, is passed through as is.
+///
+/// # Example
+///
+/// ```rust
+/// # use rocket::get;
+/// use rocket::response::stream::{Event, EventStream, LastEventId};
+///
+/// #[get("/events")]
+/// fn events(last: Option<LastEventId<'_>>) -> EventStream![] {
+///     let start = last.and_then(|id| id.parse::<usize>().ok()).map_or(0, |id| id + 1);
+///     EventStream! {
+///         for i in start..10 {
+///             yield Event::data(format!("event {}", i)).id(i.to_string());
+///         }
+///     }
+/// }
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct LastEventId<'r>(&'r str);
+
+impl<'r> LastEventId<'r> {
+    /// Returns the id of the last event the client received.
+    #[inline(always)]
+    pub fn as_str(&self) -> &'r str {
+        self.0
+    }
+}
+
+impl std::ops::Deref for LastEventId<'_> {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        self.0
+    }
+}
+
+#[crate::async_trait]
+impl<'r> FromRequest<'r> for LastEventId<'r> {
+    type Error = std::convert::Infallible;
+
+    async fn from_request(req: &'r Request<'_>) -> request::Outcome<Self, Self::Error> {
+        match req.headers().get_one("Last-Event-ID") {
+            Some(id) => Success(LastEventId(id)),
+            None => Forward(()),
+        }
+    }
+}
+
 crate::export! {
     /// Type and stream expression macro for [`struct@EventStream`].
     ///
@@ -655,8 +655,14 @@ impl Rocket<Ignite> {
                 rkt.fairings.handle_liftoff(&rkt).await;
 
                 let proto = rkt.config.tls_enabled().then(|| "https").unwrap_or("http");
-                let socket_addr = SocketAddr::new(rkt.config.address, rkt.config.port);
-                let addr = format!("{}://{}", proto, socket_addr);
+                let addr = match &rkt.config.unix_socket {
+                    Some(path) => format!("unix:{}", path.display()),
+                    None => {
+                        let socket_addr = SocketAddr::new(rkt.config.address, rkt.config.port);
+                        format!("{}://{}", proto, socket_addr)
+                    }
+                };
+
                 launch_info!("{}{} {}",
                     Paint::emoji("🚀 "),
                     Paint::default("Rocket has launched from").bold(),
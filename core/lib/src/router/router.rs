@@ -55,6 +55,37 @@ impl Router {
             .flat_map(move |routes| routes.iter().filter(move |r| r.matches(req)))
     }
 
+    /// Returns the highest-ranked route for `method` whose path and query
+    /// match `req`, irrespective of the request's method and format.
+    pub fn first_uri_match(&self, method: Method, req: &Request<'_>) -> Option<&Route> {
+        self.routes.get(&method)?.iter().find(|r| r.matches_uri(req))
+    }
+
+    /// Returns the methods of all routes whose path and query match `req`,
+    /// along with `OPTIONS`, in a canonical order. `HEAD` is included whenever
+    /// `GET` is, as `HEAD` requests are rerouted to `GET` routes. Returns an
+    /// empty vector if there are no such routes.
+    pub fn allowed_methods(&self, req: &Request<'_>) -> Vec<Method> {
+        use Method::*;
+
+        let routed = |method: &Method| self.routes.get(method)
+            .map_or(false, |routes| routes.iter().any(|r| r.matches_uri(req)));
+
+        let matches = |method: &Method| routed(method) || (*method == Head && routed(&Get));
+
+        let mut allowed: Vec<_> = [Get, Head, Post, Put, Patch, Delete, Trace, Connect, Options]
+            .iter()
+            .copied()
+            .filter(matches)
+            .collect();
+
+        if !allowed.is_empty() && !allowed.contains(&Options) {
+            allowed.push(Options);
+        }
+
+        allowed
+    }
+
     // For many catchers, using aho-corasick or similar should be much faster.
     pub fn catch<'r>(&self, status: Status, req: &'r Request<'r>) -> Option<&Catcher> {
         // Note that catchers are presorted by descending base length.
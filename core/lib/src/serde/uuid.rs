@@ -76,23 +76,92 @@
 //! features = ["v1", "v4"]
 //! ```
 
+use std::fmt as std_fmt;
+
 use crate::request::FromParam;
 use crate::form::{self, FromFormField, ValueField};
 
-/// Error returned on [`FromParam`] or [`FromFormField`] failure.
+/// Error returned on [`FromFormField`] failure.
 ///
 pub use uuid_::Error;
 
 pub use uuid_::{Uuid, Builder, Variant, Version, Bytes, uuid, fmt};
 
+/// Error returned on [`FromParam`] failure.
+///
+/// A path segment parses as a [`Uuid`] only if it is in either the hyphenated
+/// form, as in `550e8400-e29b-41d4-a716-446655440000`, or the 32-character
+/// simple form, as in `550e8400e29b41d4a716446655440000`. Hex digits may be
+/// upper or lowercase. Other forms accepted by [`Uuid::parse_str()`], such as
+/// `urn:uuid:`-prefixed or braced UUIDs, are rejected.
+///
+/// # Example
+///
+/// ```rust
+/// use rocket::request::FromParam;
+/// use rocket::serde::uuid::{Uuid, ParamError};
+///
+/// let id = "550e8400-e29b-41d4-a716-446655440000";
+/// assert_eq!(Uuid::from_param(id), Uuid::from_param("550E8400E29B41D4A716446655440000"));
+///
+/// let urn = "urn:uuid:550e8400-e29b-41d4-a716-446655440000";
+/// assert_eq!(Uuid::from_param(urn), Err(ParamError::InvalidLength(urn)));
+///
+/// let bad = "550e8400-e29b-41d4-a716-44665544000g";
+/// assert_eq!(Uuid::from_param(bad), Err(ParamError::InvalidCharacter(bad, 35)));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ParamError<'a> {
+    /// The segment is neither 36 (hyphenated) nor 32 (simple) bytes long.
+    /// Contains the segment.
+    InvalidLength(&'a str),
+    /// The segment has a valid length but contains a character that isn't a
+    /// hex digit, or a hyphen at the wrong position. Contains the segment and
+    /// the byte index of the first such character.
+    InvalidCharacter(&'a str, usize),
+}
+
+impl std_fmt::Display for ParamError<'_> {
+    fn fmt(&self, f: &mut std_fmt::Formatter<'_>) -> std_fmt::Result {
+        match self {
+            ParamError::InvalidLength(s) => {
+                write!(f, "invalid UUID length {} in `{}`: expected 32 or 36", s.len(), s)
+            }
+            ParamError::InvalidCharacter(s, i) => {
+                write!(f, "invalid character in UUID `{}` at byte {}", s, i)
+            }
+        }
+    }
+}
+
+impl std::error::Error for ParamError<'_> { }
+
 impl<'a> FromParam<'a> for Uuid {
-    type Error = Error;
+    type Error = ParamError<'a>;
 
-    /// A value is successfully parsed if `param` is a properly formatted Uuid.
-    /// Otherwise, an error is returned.
-    #[inline(always)]
+    /// A value is successfully parsed if `param` is a properly formatted Uuid
+    /// in either hyphenated or simple form. Otherwise, an error is returned.
+    #[inline]
     fn from_param(param: &'a str) -> Result<Uuid, Self::Error> {
-        param.parse()
+        const HYPHENS: [usize; 4] = [8, 13, 18, 23];
+
+        let hyphenated = match param.len() {
+            32 => false,
+            36 => true,
+            _ => return Err(ParamError::InvalidLength(param)),
+        };
+
+        let invalid = param.bytes().enumerate().position(|(i, b)| {
+            match hyphenated && HYPHENS.contains(&i) {
+                true => b != b'-',
+                false => !b.is_ascii_hexdigit(),
+            }
+        });
+
+        match invalid {
+            Some(i) => Err(ParamError::InvalidCharacter(param, i)),
+            None => Ok(Uuid::try_parse(param).expect("validated hyphenated or simple UUID")),
+        }
     }
 }
 
@@ -119,4 +188,49 @@ mod test {
         let uuid_str = "c1aa1e3b-9614-4895-9ebd-705255fa5bc2p";
         assert!(Uuid::from_param(uuid_str).is_err());
     }
+
+    #[test]
+    fn test_from_param_simple() {
+        let uuid = Uuid::from_param("c1aa1e3b96144895-9ebd-705255fa5bc2");
+        assert!(uuid.is_err());
+
+        let uuid = Uuid::from_param("c1aa1e3b961448959ebd705255fa5bc2").unwrap();
+        assert_eq!(uuid.to_string(), "c1aa1e3b-9614-4895-9ebd-705255fa5bc2");
+    }
+
+    #[test]
+    fn test_from_param_uppercase() {
+        let lower = Uuid::from_param("c1aa1e3b-9614-4895-9ebd-705255fa5bc2").unwrap();
+        let upper = Uuid::from_param("C1AA1E3B-9614-4895-9EBD-705255FA5BC2").unwrap();
+        let simple = Uuid::from_param("C1AA1E3B961448959EBD705255fa5bc2").unwrap();
+        assert_eq!(lower, upper);
+        assert_eq!(lower, simple);
+    }
+
+    #[test]
+    fn test_from_param_errors() {
+        use super::ParamError::*;
+
+        for s in &[
+            "", "c1aa1e3b", "c1aa1e3b961448959ebd705255fa5bc", "c1aa1e3b961448959ebd705255fa5bc2a",
+            "urn:uuid:c1aa1e3b-9614-4895-9ebd-705255fa5bc2",
+            "{c1aa1e3b-9614-4895-9ebd-705255fa5bc2}",
+            "c1aa1e3b-9614-4895-9ebd705255fa5bc2",
+        ] {
+            assert_eq!(Uuid::from_param(s), Err(InvalidLength(s)));
+        }
+
+        for (s, i) in &[
+            ("g1aa1e3b961448959ebd705255fa5bc2", 0),
+            ("c1aa1e3b961448959ebd705255fa5bcz", 31),
+            ("c1aa1e3b-9614-4895-9ebd-705255fa5bc ", 35),
+            ("c1aa1e3b_9614-4895-9ebd-705255fa5bc2", 8),
+            ("c1aa1e3b-9614-48959-ebd-705255fa5bc2", 18),
+            ("c1aa1e3b-961-44895-9ebd-705255fa5bc2", 12),
+            ("c1aa1e3b-9614-4895-9ebd-705255\u{e9}5bc2", 30),
+            ("c1aa1e3b9614--4895-9ebd-705255fa5bc2", 8),
+        ] {
+            assert_eq!(Uuid::from_param(s), Err(InvalidCharacter(s, *i)), "{:?}", s);
+        }
+    }
 }
@@ -1,6 +1,7 @@
 use std::io;
 use std::sync::Arc;
-use std::time::Duration;
+use std::path::PathBuf;
+use std::time::{Duration, Instant};
 
 use yansi::Paint;
 use tokio::sync::oneshot;
@@ -57,6 +58,12 @@ async fn handle<Fut, T, F>(name: Option<&str>, run: F) -> Option<T>
         .ok()
 }
 
+/// Returns an `Allow` header listing `methods`.
+fn allow_header(methods: &[Method]) -> Header<'static> {
+    let methods: Vec<_> = methods.iter().map(|m| m.as_str()).collect();
+    Header::new("Allow", methods.join(", "))
+}
+
 // This function tries to hide all of the Hyper-ness from Rocket. It essentially
 // converts Hyper types into Rocket types, then calls the `dispatch` function,
 // which knows nothing about Hyper. Because responding depends on the
@@ -66,6 +73,9 @@ async fn hyper_service_fn(
     conn: ConnectionMeta,
     hyp_req: hyper::Request<hyper::Body>,
 ) -> Result<hyper::Response<hyper::Body>, io::Error> {
+    // The connection is shared by all of its requests; stamp this one.
+    let conn = ConnectionMeta { received_at: Instant::now(), ..conn };
+
     // This future must return a hyper::Response, but the response body might
     // borrow from the request. Instead, write the body in another future that
     // sends the response metadata (and a body channel) prior.
@@ -88,7 +98,7 @@ async fn hyper_service_fn(
                 // to parse a request (a special handler?).
                 error!("Bad incoming request: {}", e);
                 let dummy = Request::new(&rocket, Method::Get, Origin::ROOT);
-                let response = rocket.handle_error(Status::BadRequest, &dummy).await;
+                let response = rocket.handle_error(e.status(), &dummy).await;
                 rocket.send_response(response, tx).await;
             }
         }
@@ -153,7 +163,17 @@ impl Rocket<Orbit> {
         let max_chunk_size = body.max_chunk_size();
         let mut stream = body.into_bytes_stream(max_chunk_size);
         while let Some(next) = stream.next().await {
-            sender.send_data(next?).await
+            // Abort rather than end the body so a failed read isn't mistaken
+            // for a complete, if short, response.
+            let chunk = match next {
+                Ok(chunk) => chunk,
+                Err(e) => {
+                    sender.abort();
+                    return Err(e);
+                }
+            };
+
+            sender.send_data(chunk).await
                 .map_err(|e| io::Error::new(io::ErrorKind::BrokenPipe, e))?;
         }
 
@@ -163,6 +183,7 @@ impl Rocket<Orbit> {
     /// Preprocess the request for Rocket things. Currently, this means:
     ///
     ///   * Rewriting the method in the request if _method form field exists.
+    ///   * Rewriting the method if enabled and `X-HTTP-Method-Override` is set.
     ///   * Run the request fairings.
     ///
     /// Keep this in-sync with derive_form when preprocessing form fields.
@@ -188,9 +209,26 @@ impl Rocket<Orbit> {
             }
         }
 
+        // If enabled, let the override header turn a `POST` into an unsafe
+        // method. Anything else, including a `GET` override, is ignored.
+        if self.config.method_override && req.method() == Method::Post {
+            let method = req.headers().get_one("X-HTTP-Method-Override")
+                .and_then(|value| value.trim().parse().ok())
+                .filter(|m| matches!(m, Method::Put | Method::Patch | Method::Delete));
+
+            if let Some(method) = method {
+                req._set_method(method);
+            }
+        }
+
         // Run request fairings.
         self.fairings.handle_request(req, data).await;
 
+        // Fairings may have changed the headers or remote, so only now decide
+        // whether cookies should default to `Secure`.
+        let secure = req.scheme() == "https";
+        req.cookies_mut().set_secure(secure);
+
         RequestToken
     }
 
@@ -203,9 +241,6 @@ impl Rocket<Orbit> {
     ) -> Response<'r> {
         info!("{}:", request);
 
-        // Remember if the request is `HEAD` for later body stripping.
-        let was_head_request = request.method() == Method::Head;
-
         // Route the request and run the user's handlers.
         let mut response = self.route_and_process(request, data).await;
 
@@ -221,7 +256,7 @@ impl Rocket<Orbit> {
         self.fairings.handle_response(request, &mut response).await;
 
         // Strip the body if this is a `HEAD` request.
-        if was_head_request {
+        if request.strips_body() {
             response.strip_body();
         }
 
@@ -233,26 +268,38 @@ impl Rocket<Orbit> {
         request: &'r Request<'s>,
         data: Data<'r>
     ) -> Response<'r> {
-        let mut response = match self.route(request, data).await {
-            Outcome::Success(response) => response,
-            Outcome::Forward(data) if request.method() == Method::Head => {
-                info_!("Autohandling {} request.", Paint::default("HEAD").bold());
-
-                // Dispatch the request again with Method `GET`.
-                request._set_method(Method::Get);
-                match self.route(request, data).await {
-                    Outcome::Success(response) => response,
-                    Outcome::Failure(status) => self.handle_error(status, request).await,
-                    Outcome::Forward(_) => self.handle_error(Status::NotFound, request).await,
+        let mut response = match self.trailing_slash_redirect(request) {
+            Some(redirect) => redirect,
+            None => match self.route(request, data).await {
+                Outcome::Success(response) => response,
+                Outcome::Forward(data) if request.method() == Method::Head => {
+                    info_!("Autohandling {} request.", Paint::default("HEAD").bold());
+
+                    // Dispatch the request again with Method `GET`.
+                    request._reroute_head();
+                    match self.route(request, data).await {
+                        Outcome::Success(response) => response,
+                        Outcome::Failure(status) => self.handle_error(status, request).await,
+                        Outcome::Forward(_) => self.handle_unrouted(request).await,
+                    }
                 }
+                Outcome::Forward(_) => self.handle_unrouted(request).await,
+                Outcome::Failure(status) => self.handle_error(status, request).await,
             }
-            Outcome::Forward(_) => self.handle_error(Status::NotFound, request).await,
-            Outcome::Failure(status) => self.handle_error(status, request).await,
         };
 
+        // If enabled, tell the client which methods a `405` would accept.
+        let status = response.status();
+        if self.config.auto_options && status == Status::MethodNotAllowed {
+            let allowed = self.router.allowed_methods(request);
+            if !allowed.is_empty() && !response.headers().contains("Allow") {
+                response.set_header(allow_header(&allowed));
+            }
+        }
+
         // Set the cookies. Note that error responses will only include cookies
         // set by the error handler. See `handle_error` for more.
-        let delta_jar = request.cookies().take_delta_jar();
+        let delta_jar = request.cookies().delta_jar();
         for cookie in delta_jar.delta() {
             response.adjoin_header(cookie);
         }
@@ -260,6 +307,67 @@ impl Rocket<Orbit> {
         response
     }
 
+    /// Returns a permanent redirect to the canonical form of `request`'s path,
+    /// per the configured trailing slash policy, if the path isn't already in
+    /// that form and a route other than a `<path..>` route would handle it.
+    ///
+    /// The router ignores trailing slashes, so a route handles the canonical
+    /// path exactly when it handles the request's. Since the canonical form of
+    /// a canonical path is itself, a redirected request is never redirected
+    /// again.
+    fn trailing_slash_redirect<'r>(&self, request: &Request<'_>) -> Option<Response<'r>> {
+        let path = request.uri().path();
+        let canonical = self.config.trailing_slash.canonicalize(path.as_str())?;
+
+        // `HEAD` requests are handled by `GET` routes if there's no `HEAD` route.
+        let method = request.method();
+        let route = self.router.first_uri_match(method, request)
+            .or_else(|| match method {
+                Method::Head => self.router.first_uri_match(Method::Get, request),
+                _ => None
+            })?;
+
+        if route.uri.metadata.trailing_path {
+            return None;
+        }
+
+        let location = request.uri().map_path(|_| canonical)?;
+        let status = match method {
+            Method::Get | Method::Head => Status::MovedPermanently,
+            _ => Status::PermanentRedirect,
+        };
+
+        info_!("Redirecting to canonical path {}.", Paint::default(&location).bold());
+        let response = Response::build()
+            .status(status)
+            .raw_header("Location", location.to_string())
+            .finalize();
+
+        Some(response)
+    }
+
+    /// Responds to a `request` that no route handled. If `auto_options` is
+    /// enabled and routes exist for the request's path, an `OPTIONS` request
+    /// is answered with the allowed methods, and a request with a method that
+    /// isn't allowed fails with a `405`. Otherwise, the request fails with a
+    /// `404`.
+    async fn handle_unrouted<'s, 'r: 's>(&'s self, request: &'r Request<'s>) -> Response<'r> {
+        if self.config.auto_options {
+            let allowed = self.router.allowed_methods(request);
+            if request.method() == Method::Options && !allowed.is_empty() {
+                info_!("Autohandling {} request.", Paint::default("OPTIONS").bold());
+                return Response::build()
+                    .status(Status::NoContent)
+                    .header(allow_header(&allowed))
+                    .finalize();
+            } else if !allowed.is_empty() && !allowed.contains(&request.method()) {
+                return self.handle_error(Status::MethodNotAllowed, request).await;
+            }
+        }
+
+        self.handle_error(Status::NotFound, request).await
+    }
+
     /// Tries to find a `Responder` for a given `request`. It does this by
     /// routing the request and calling the handler for each matching route
     /// until one of the handlers returns success or failure, or there are no
@@ -287,7 +395,10 @@ impl Rocket<Orbit> {
             info_!("{} {}", Paint::default("Outcome:").bold(), outcome);
             match outcome {
                 o@Outcome::Success(_) | o@Outcome::Failure(_) => return o,
-                Outcome::Forward(unused_data) => data = unused_data,
+                Outcome::Forward(unused_data) => {
+                    request._record_forward();
+                    data = unused_data;
+                }
             }
         }
 
@@ -314,6 +425,7 @@ impl Rocket<Orbit> {
         // from earlier, unsuccessful paths from being reflected in error
         // response. We may wish to relax this in the future.
         req.cookies().reset_delta();
+        req._set_in_catcher(true);
 
         if let Some(catcher) = self.router.catch(status, req) {
             warn_!("Responding with registered {} catcher.", catcher);
@@ -337,6 +449,11 @@ impl Rocket<Orbit> {
         mut status: Status,
         req: &'r Request<'s>
     ) -> Response<'r> {
+        // A replayed idempotent request already has its response.
+        if let Some(response) = crate::idempotency::replayed(req) {
+            return response;
+        }
+
         // Dispatch to the `status` catcher.
         if let Ok(r) = self.invoke_catcher(status, req).await {
             return r;
@@ -361,6 +478,10 @@ impl Rocket<Orbit> {
     {
         use std::net::ToSocketAddrs;
 
+        if let Some(path) = self.config.unix_socket.clone() {
+            return self.default_unix_http_server(path, ready).await;
+        }
+
         // Determine the address we're going to serve on.
         let addr = format!("{}:{}", self.config.address, self.config.port);
         let mut addr = addr.to_socket_addrs()
@@ -390,6 +511,47 @@ impl Rocket<Orbit> {
         self.http_server(l).await
     }
 
+    #[cfg(unix)]
+    async fn default_unix_http_server<C>(mut self, path: PathBuf, ready: C) -> Result<Self, Error>
+        where C: for<'a> Fn(&'a Self) -> BoxFuture<'a, ()>
+    {
+        use std::os::unix::fs::FileTypeExt;
+        use crate::http::private::UnixListener;
+
+        if self.config.tls_enabled() {
+            let kind = io::ErrorKind::InvalidInput;
+            let e = io::Error::new(kind, "TLS is not supported on a Unix domain socket");
+            return Err(Error::new(ErrorKind::Io(e)));
+        }
+
+        // Remove a socket left by a previous run, but only if nothing is
+        // listening on it and it is, in fact, a socket.
+        let is_socket = std::fs::symlink_metadata(&path)
+            .map_or(false, |m| m.file_type().is_socket());
+
+        if is_socket && std::os::unix::net::UnixStream::connect(&path).is_err() {
+            std::fs::remove_file(&path).map_err(ErrorKind::Bind)?;
+        }
+
+        let l = UnixListener::bind(&path).map_err(ErrorKind::Bind)?;
+        ready(&mut self).await;
+        let result = self.http_server(l).await;
+        if let Err(e) = std::fs::remove_file(&path) {
+            warn!("Failed to remove Unix domain socket {}: {}", path.display(), e);
+        }
+
+        result
+    }
+
+    #[cfg(not(unix))]
+    async fn default_unix_http_server<C>(self, _: PathBuf, _: C) -> Result<Self, Error>
+        where C: for<'a> Fn(&'a Self) -> BoxFuture<'a, ()>
+    {
+        let kind = io::ErrorKind::Unsupported;
+        let e = io::Error::new(kind, "Unix domain sockets are not supported on this platform");
+        Err(Error::new(ErrorKind::Io(e)))
+    }
+
     // TODO.async: Solidify the Listener APIs and make this function public
     pub(crate) async fn http_server<L>(self, listener: L) -> Result<Self, Error>
         where L: Listener + Send, <L as Listener>::Connection: Send + Unpin + 'static
@@ -431,8 +593,10 @@ impl Rocket<Orbit> {
             });
         }
 
-        // Save the keep-alive value for later use; we're about to move `self`.
+        // Save the keep-alive, proxy, and header limit values; we're about to move `self`.
         let keep_alive = self.config.keep_alive;
+        let trusted_proxies: Arc<[_]> = self.config.trusted_proxies.as_slice().into();
+        let header_limits = self.config.header_limits;
 
         // Create the Hyper `Service`.
         let rocket = Arc::new(self);
@@ -441,6 +605,12 @@ impl Rocket<Orbit> {
             let connection = ConnectionMeta {
                 remote: conn.peer_address(),
                 client_certificates: conn.peer_certificates(),
+                tls_session: conn.tls_session(),
+                trusted_proxies: trusted_proxies.clone(),
+                header_limits,
+                http_version: crate::http::HttpVersion::Http11,
+                unix_peer: conn.unix_peer(),
+                received_at: Instant::now(),
             };
 
             async move {
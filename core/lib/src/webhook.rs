@@ -0,0 +1,415 @@
+//! Verification of HMAC-signed webhook bodies.
+//!
+//! Webhook senders commonly sign each request body with a secret shared with
+//! the receiver and send the signature in a header, like GitHub's
+//! `X-Hub-Signature-256: sha256=<hex>`. The [`SignedBody`] data guard reads
+//! the raw body, computes its HMAC with the managed [`WebhookKey`], and
+//! compares it, in constant time, against the signature in the header. Only
+//! if they match does the guard succeed with the verified bytes, which can
+//! then be parsed.
+//!
+//! Optionally, a [`WebhookKey`] can also require a timestamp header, rejecting
+//! requests whose timestamp is too far from the current time so that captured
+//! requests can't be replayed later. The timestamp is then part of the signed
+//! message; see [`WebhookKey::timestamp()`].
+//!
+//! # Example
+//!
+//! ```rust
+//! # #[macro_use] extern crate rocket;
+//! use rocket::webhook::{Algorithm, SignedBody, WebhookKey};
+//!
+//! /// Marker type for the key verifying GitHub's webhooks.
+//! struct GitHub;
+//!
+//! #[post("/hooks/github", data = "<body>")]
+//! fn github(body: SignedBody<'_, GitHub>) -> String {
+//!     format!("verified {} bytes", body.len())
+//! }
+//!
+//! #[launch]
+//! fn rocket() -> _ {
+//!     let key = WebhookKey::<GitHub>::new(Algorithm::Sha256, "a shared secret")
+//!         .header("X-Hub-Signature-256");
+//!
+//!     rocket::build().manage(key).mount("/", routes![github])
+//! }
+//! ```
+
+use std::{fmt, io};
+use std::borrow::Cow;
+use std::marker::PhantomData;
+use std::ops::Deref;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use ring::hmac;
+
+use crate::http::Status;
+use crate::outcome::Outcome::*;
+use crate::request::Request;
+use crate::data::{Data, FromData, Outcome, RawBody};
+
+/// An HMAC algorithm with which webhook bodies are signed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Algorithm {
+    /// HMAC using SHA-1. Only for senders that offer nothing better.
+    Sha1,
+    /// HMAC using SHA-256.
+    Sha256,
+    /// HMAC using SHA-384.
+    Sha384,
+    /// HMAC using SHA-512.
+    Sha512,
+}
+
+/// The secret and policy with which [`SignedBody`] guards verify signatures.
+///
+/// A `WebhookKey<V>` must be managed for [`SignedBody<V>`](SignedBody) to be
+/// used; requests fail otherwise. The type `V`, `()` by default, lets
+/// an application manage several keys at once, one for each sender.
+///
+/// By default, the signature is read from the `X-Signature` header and must
+/// be prefixed with the lowercase name of the algorithm and a `=`, as in
+/// `sha256=`, followed by the HMAC of the body in hexadecimal. The header and
+/// prefix are set with [`WebhookKey::header()`] and [`WebhookKey::prefix()`].
+///
+/// # Example
+///
+/// ```rust
+/// use std::time::Duration;
+/// use rocket::webhook::{Algorithm, WebhookKey};
+///
+/// let key = WebhookKey::<()>::new(Algorithm::Sha256, "a shared secret")
+///     .header("X-Webhook-Signature")
+///     .prefix("v1=")
+///     .timestamp("X-Webhook-Timestamp", Duration::from_secs(300));
+///
+/// # let _ = key;
+/// ```
+pub struct WebhookKey<V = ()> {
+    algorithm: Algorithm,
+    key: hmac::Key,
+    header: Cow<'static, str>,
+    prefix: Option<Cow<'static, str>>,
+    timestamp: Option<(Cow<'static, str>, Duration)>,
+    _verifier: PhantomData<fn() -> V>,
+}
+
+/// A data guard for a request body with a verified HMAC signature.
+///
+/// The guard reads the signature from the header configured in the managed
+/// [`WebhookKey<V>`](WebhookKey) and the body as [`RawBody`] does, caching it
+/// for the rest of the request. It then:
+///
+///   * fails with `401 Unauthorized` and a [`SignatureError`] if the header
+///     is missing, lacks the expected prefix, isn't hexadecimal, or doesn't
+///     match the body's HMAC, or if a required timestamp is missing, invalid,
+///     or outside the allowed tolerance;
+///   * fails with the status of [`RawBody`] and [`SignatureError::Io`] if the
+///     body can't be read, for instance because it exceeds the `raw-body`
+///     limit;
+///   * fails with `500 Internal Server Error` if no `WebhookKey<V>` is
+///     managed.
+///
+/// Otherwise, the guard succeeds with the verified bytes. `SignedBody`
+/// dereferences to `[u8]`.
+///
+/// See the [module level docs](self) for an example.
+pub struct SignedBody<'r, V = ()> {
+    body: &'r [u8],
+    _verifier: PhantomData<fn() -> V>,
+}
+
+/// Error returned by the [`SignedBody`] guard.
+#[derive(Debug)]
+pub enum SignatureError {
+    /// The signature header is missing.
+    MissingSignature,
+    /// The signature doesn't start with the expected prefix.
+    InvalidPrefix,
+    /// The signature isn't hexadecimal or has the wrong length.
+    Malformed,
+    /// The signature doesn't match the body.
+    Mismatch,
+    /// The timestamp header is required but missing.
+    MissingTimestamp,
+    /// The timestamp isn't a number of seconds since the Unix epoch.
+    InvalidTimestamp,
+    /// The timestamp is further from the current time than the tolerance.
+    StaleTimestamp,
+    /// The body couldn't be read.
+    Io(io::Error),
+    /// No [`WebhookKey`] is managed.
+    MissingKey,
+}
+
+impl Algorithm {
+    /// Returns the lowercase name of the algorithm, as used in the default
+    /// signature prefix.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::webhook::Algorithm;
+    ///
+    /// assert_eq!(Algorithm::Sha256.as_str(), "sha256");
+    /// ```
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Algorithm::Sha1 => "sha1",
+            Algorithm::Sha256 => "sha256",
+            Algorithm::Sha384 => "sha384",
+            Algorithm::Sha512 => "sha512",
+        }
+    }
+
+    fn hmac(&self) -> hmac::Algorithm {
+        match self {
+            Algorithm::Sha1 => hmac::HMAC_SHA1_FOR_LEGACY_USE_ONLY,
+            Algorithm::Sha256 => hmac::HMAC_SHA256,
+            Algorithm::Sha384 => hmac::HMAC_SHA384,
+            Algorithm::Sha512 => hmac::HMAC_SHA512,
+        }
+    }
+}
+
+impl<V> WebhookKey<V> {
+    /// Creates a key for `algorithm` with the shared `secret`, reading
+    /// signatures from `X-Signature` with the algorithm's default prefix.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::webhook::{Algorithm, WebhookKey};
+    ///
+    /// let key = WebhookKey::<()>::new(Algorithm::Sha512, "a shared secret");
+    /// assert_eq!(key.algorithm(), Algorithm::Sha512);
+    /// ```
+    pub fn new<S: AsRef<[u8]>>(algorithm: Algorithm, secret: S) -> Self {
+        WebhookKey {
+            algorithm,
+            key: hmac::Key::new(algorithm.hmac(), secret.as_ref()),
+            header: "X-Signature".into(),
+            prefix: Some(format!("{}=", algorithm.as_str()).into()),
+            timestamp: None,
+            _verifier: PhantomData,
+        }
+    }
+
+    /// Sets the name of the header the signature is read from.
+    pub fn header<H: Into<Cow<'static, str>>>(mut self, name: H) -> Self {
+        self.header = name.into();
+        self
+    }
+
+    /// Sets the prefix that must precede the hexadecimal signature. The
+    /// prefix is matched case-insensitively. An empty prefix requires none.
+    pub fn prefix<P: Into<Cow<'static, str>>>(mut self, prefix: P) -> Self {
+        let prefix = prefix.into();
+        self.prefix = Some(prefix).filter(|prefix| !prefix.is_empty());
+        self
+    }
+
+    /// Requires a timestamp, in seconds since the Unix epoch, in the header
+    /// `name`, no further than `tolerance` from the current time.
+    ///
+    /// The timestamp is covered by the signature: the signed message is the
+    /// timestamp exactly as it appears in the header, followed by a `.`,
+    /// followed by the body.
+    pub fn timestamp<H>(mut self, name: H, tolerance: Duration) -> Self
+        where H: Into<Cow<'static, str>>
+    {
+        self.timestamp = Some((name.into(), tolerance));
+        self
+    }
+
+    /// Returns the key's algorithm.
+    pub fn algorithm(&self) -> Algorithm {
+        self.algorithm
+    }
+
+    /// Returns the value of the signature header for `body` with the
+    /// timestamp `timestamp`, which must be `Some` exactly when a timestamp
+    /// is required. This is what a sender computes.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::webhook::{Algorithm, WebhookKey};
+    ///
+    /// let key = WebhookKey::<()>::new(Algorithm::Sha256, "It's a Secret to Everybody");
+    /// assert_eq!(key.sign(b"Hello, World!", None),
+    ///     "sha256=757107ea0eb2509fc211221cce984b8a37570b6d7586c22c46f4379c8b043e17");
+    /// ```
+    pub fn sign(&self, body: &[u8], timestamp: Option<&str>) -> String {
+        let tag = hmac::sign(&self.key, &self.message(body, timestamp));
+        let hex: String = tag.as_ref().iter().map(|b| format!("{:02x}", b)).collect();
+        format!("{}{}", self.prefix.as_deref().unwrap_or(""), hex)
+    }
+
+    /// Returns the signed message for `body` and `timestamp`.
+    fn message<'a>(&self, body: &'a [u8], timestamp: Option<&str>) -> Cow<'a, [u8]> {
+        match timestamp {
+            Some(timestamp) => {
+                let mut message = Vec::with_capacity(timestamp.len() + 1 + body.len());
+                message.extend_from_slice(timestamp.as_bytes());
+                message.push(b'.');
+                message.extend_from_slice(body);
+                Cow::Owned(message)
+            }
+            None => Cow::Borrowed(body),
+        }
+    }
+
+    /// Returns the decoded signature from the signature header `value`.
+    fn signature(&self, value: &str) -> Result<Vec<u8>, SignatureError> {
+        let value = value.trim();
+        let hex = match self.prefix.as_deref() {
+            Some(prefix) => match value.get(..prefix.len()) {
+                Some(p) if p.eq_ignore_ascii_case(prefix) => &value[prefix.len()..],
+                _ => return Err(SignatureError::InvalidPrefix),
+            },
+            None => value,
+        };
+
+        let len = self.algorithm.hmac().digest_algorithm().output_len;
+        if hex.len() != 2 * len || !hex.bytes().all(|b| b.is_ascii_hexdigit()) {
+            return Err(SignatureError::Malformed);
+        }
+
+        let signature = (0..len)
+            .map(|i| u8::from_str_radix(&hex[(2 * i)..(2 * i + 2)], 16))
+            .collect::<Result<_, _>>()
+            .map_err(|_| SignatureError::Malformed)?;
+
+        Ok(signature)
+    }
+
+    /// Checks the timestamp header of `req`, if one is required, and returns
+    /// it.
+    fn check_timestamp<'r>(&self, req: &'r Request<'_>) -> Result<Option<&'r str>, SignatureError> {
+        let (name, tolerance) = match &self.timestamp {
+            Some((name, tolerance)) => (name, *tolerance),
+            None => return Ok(None),
+        };
+
+        let value = req.headers().get_one(name).ok_or(SignatureError::MissingTimestamp)?;
+        let secs: u64 = value.trim().parse().map_err(|_| SignatureError::InvalidTimestamp)?;
+        let now = SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default();
+        let then = Duration::from_secs(secs);
+        let skew = if now > then { now - then } else { then - now };
+        if skew > tolerance {
+            return Err(SignatureError::StaleTimestamp);
+        }
+
+        Ok(Some(value))
+    }
+}
+
+impl<'r, V> SignedBody<'r, V> {
+    /// Returns the verified body bytes.
+    #[inline(always)]
+    pub fn as_bytes(&self) -> &'r [u8] {
+        self.body
+    }
+}
+
+impl<V> Deref for SignedBody<'_, V> {
+    type Target = [u8];
+
+    fn deref(&self) -> &[u8] {
+        self.body
+    }
+}
+
+impl<V> AsRef<[u8]> for SignedBody<'_, V> {
+    fn as_ref(&self) -> &[u8] {
+        self.body
+    }
+}
+
+#[crate::async_trait]
+impl<'r, V: Send + Sync + 'static> FromData<'r> for SignedBody<'r, V> {
+    type Error = SignatureError;
+
+    async fn from_data(req: &'r Request<'_>, data: Data<'r>) -> Outcome<'r, Self> {
+        let key = match req.rocket().state::<WebhookKey<V>>() {
+            Some(key) => key,
+            None => {
+                error_!("Attempted to verify a webhook body without a managed `WebhookKey`!");
+                return Failure((Status::InternalServerError, SignatureError::MissingKey));
+            }
+        };
+
+        let header = req.headers().get_one(&key.header);
+        let signature = match header.ok_or(SignatureError::MissingSignature) {
+            Ok(value) => key.signature(value),
+            Err(e) => Err(e),
+        };
+
+        let (signature, timestamp) = match (signature, key.check_timestamp(req)) {
+            (Ok(signature), Ok(timestamp)) => (signature, timestamp),
+            (Err(e), _) | (_, Err(e)) => return Failure((Status::Unauthorized, e)),
+        };
+
+        let body = match RawBody::from_data(req, data).await {
+            Success(body) => body.as_bytes(),
+            Failure((status, e)) => return Failure((status, SignatureError::Io(e))),
+            Forward(data) => return Forward(data),
+        };
+
+        match hmac::verify(&key.key, &key.message(body, timestamp), &signature) {
+            Ok(()) => Success(SignedBody { body, _verifier: PhantomData }),
+            Err(_) => Failure((Status::Unauthorized, SignatureError::Mismatch)),
+        }
+    }
+}
+
+impl<V> fmt::Debug for WebhookKey<V> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("WebhookKey")
+            .field("algorithm", &self.algorithm)
+            .field("key", &"<redacted>")
+            .field("header", &self.header)
+            .field("prefix", &self.prefix)
+            .field("timestamp", &self.timestamp)
+            .finish()
+    }
+}
+
+impl<V> fmt::Debug for SignedBody<'_, V> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("SignedBody").field(&self.body).finish()
+    }
+}
+
+impl fmt::Display for Algorithm {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl fmt::Display for SignatureError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SignatureError::MissingSignature => f.write_str("missing signature header"),
+            SignatureError::InvalidPrefix => f.write_str("signature has an unexpected prefix"),
+            SignatureError::Malformed => f.write_str("malformed signature"),
+            SignatureError::Mismatch => f.write_str("signature does not match body"),
+            SignatureError::MissingTimestamp => f.write_str("missing timestamp header"),
+            SignatureError::InvalidTimestamp => f.write_str("invalid timestamp"),
+            SignatureError::StaleTimestamp => f.write_str("timestamp is outside the tolerance"),
+            SignatureError::Io(e) => write!(f, "i/o error: {}", e),
+            SignatureError::MissingKey => f.write_str("no webhook key is configured"),
+        }
+    }
+}
+
+impl std::error::Error for SignatureError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            SignatureError::Io(e) => Some(e),
+            _ => None,
+        }
+    }
+}
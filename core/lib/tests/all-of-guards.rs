@@ -0,0 +1,121 @@
+#[macro_use] extern crate rocket;
+
+use std::sync::Mutex;
+
+use rocket::Request;
+use rocket::http::Status;
+use rocket::request::{self, AllOf, AllOfError, FromRequest};
+
+/// Records the order in which guards ran.
+#[derive(Default)]
+struct Log(Mutex<Vec<&'static str>>);
+
+impl Log {
+    fn take(&self) -> Vec<&'static str> {
+        std::mem::take(&mut *self.0.lock().unwrap())
+    }
+}
+
+macro_rules! guard {
+    ($name:ident => $outcome:expr) => (
+        #[derive(Debug, PartialEq)]
+        struct $name(&'static str);
+
+        #[rocket::async_trait]
+        impl<'r> FromRequest<'r> for $name {
+            type Error = &'static str;
+
+            async fn from_request(req: &'r Request<'_>) -> request::Outcome<Self, Self::Error> {
+                req.rocket().state::<Log>().unwrap().0.lock().unwrap().push(stringify!($name));
+                rocket::tokio::task::yield_now().await;
+                $outcome
+            }
+        }
+    )
+}
+
+guard!(A => request::Outcome::Success(A("a")));
+guard!(B => request::Outcome::Success(B("b")));
+guard!(C => request::Outcome::Success(C("c")));
+guard!(Fails => request::Outcome::Failure((Status::Unauthorized, "no way")));
+guard!(Forwards => request::Outcome::Forward(()));
+
+#[get("/ok")]
+fn ok(guards: AllOf<(A, B, C)>) -> String {
+    let (a, b, c) = guards.into_inner();
+    format!("{}{}{}", a.0, b.0, c.0)
+}
+
+#[get("/fail")]
+fn fail(_guards: AllOf<(A, Fails, B)>) { }
+
+#[get("/forward")]
+fn forward(_guards: AllOf<(A, B, Forwards, C)>) { }
+
+#[get("/forward", rank = 2)]
+fn forward_fallback() -> &'static str {
+    "fallback"
+}
+
+#[get("/error")]
+fn error(guards: Result<AllOf<(A, Fails, C)>, AllOfError<'_>>) -> String {
+    match guards {
+        Ok(_) => "unreachable".into(),
+        Err(e) => format!("{} {:?}", e.index, e.error),
+    }
+}
+
+mod all_of_tests {
+    use super::*;
+    use rocket::local::blocking::Client;
+
+    fn client() -> Client {
+        let rocket = rocket::build()
+            .manage(Log::default())
+            .mount("/", routes![ok, fail, forward, forward_fallback, error]);
+
+        Client::debug(rocket).unwrap()
+    }
+
+    fn ran(client: &Client) -> Vec<&'static str> {
+        client.rocket().state::<Log>().unwrap().take()
+    }
+
+    #[test]
+    fn success_assembles_tuple_in_order() {
+        let client = client();
+        let response = client.get("/ok").dispatch();
+        assert_eq!(response.into_string().unwrap(), "abc");
+        assert_eq!(ran(&client), ["A", "B", "C"]);
+    }
+
+    #[test]
+    fn failure_short_circuits_and_keeps_status() {
+        let client = client();
+        let response = client.get("/fail").dispatch();
+        assert_eq!(response.status(), Status::Unauthorized);
+        assert_eq!(ran(&client), ["A", "Fails"]);
+    }
+
+    #[test]
+    fn forward_short_circuits() {
+        let client = client();
+        let response = client.get("/forward").dispatch();
+        assert_eq!(response.into_string().unwrap(), "fallback");
+        assert_eq!(ran(&client), ["A", "B", "Forwards"]);
+    }
+
+    #[test]
+    fn error_identifies_failing_guard() {
+        let client = client();
+        let response = client.get("/error").dispatch();
+        assert_eq!(response.into_string().unwrap(), "1 \"no way\"");
+        assert_eq!(ran(&client), ["A", "Fails"]);
+    }
+
+    #[test]
+    fn error_display() {
+        let error = AllOfError { index: 2, guard: "my::Guard", error: Box::new("bad") };
+        assert_eq!(error.to_string(), "guard 2 (`my::Guard`) failed: \"bad\"");
+    }
+}
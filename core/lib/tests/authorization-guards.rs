@@ -0,0 +1,133 @@
+#[macro_use] extern crate rocket;
+
+use rocket::request::{BasicAuth, BearerToken, AuthError};
+
+#[get("/basic")]
+fn basic(auth: BasicAuth) -> String {
+    format!("{}|{}", auth.username, auth.password)
+}
+
+#[get("/basic", rank = 2)]
+fn basic_anonymous() -> &'static str {
+    "anonymous"
+}
+
+#[get("/bearer")]
+fn bearer(token: BearerToken) -> String {
+    token.0
+}
+
+#[get("/bearer", rank = 2)]
+fn bearer_anonymous() -> &'static str {
+    "anonymous"
+}
+
+#[get("/result")]
+fn result(auth: Result<BasicAuth, AuthError>) -> String {
+    match auth {
+        Ok(auth) => auth.username,
+        Err(e) => e.to_string(),
+    }
+}
+
+mod authorization_tests {
+    use super::*;
+    use rocket::http::{Header, Status};
+    use rocket::local::blocking::Client;
+
+    fn client() -> Client {
+        Client::debug_with(routes![basic, basic_anonymous, bearer, bearer_anonymous, result])
+            .unwrap()
+    }
+
+    fn get(client: &Client, uri: &'static str, auth: Option<&str>) -> (Status, String) {
+        let mut request = client.get(uri);
+        if let Some(auth) = auth {
+            request.add_header(Header::new("Authorization", auth.to_string()));
+        }
+
+        let response = request.dispatch();
+        (response.status(), response.into_string().unwrap_or_default())
+    }
+
+    #[test]
+    fn basic_credentials() {
+        let client = client();
+        let ok = |v: &str| (Status::Ok, v.to_string());
+
+        assert_eq!(get(&client, "/basic", Some("Basic YWxpY2U6b3BlbiBzZXNhbWU=")),
+            ok("alice|open sesame"));
+        assert_eq!(get(&client, "/basic", Some("basic YWxpY2U6b3BlbiBzZXNhbWU=")),
+            ok("alice|open sesame"));
+        assert_eq!(get(&client, "/basic", Some("BASIC   YWxpY2U6b3BlbiBzZXNhbWU=")),
+            ok("alice|open sesame"));
+
+        // Only the first colon separates the username and password.
+        assert_eq!(get(&client, "/basic", Some("Basic Ym9iOnBhOnNzOndvcmQ=")), ok("bob|pa:ss:word"));
+        assert_eq!(get(&client, "/basic", Some("Basic Og==")), ok("|"));
+        assert_eq!(get(&client, "/basic", Some("Basic dXNlcjo=")), ok("user|"));
+        assert_eq!(get(&client, "/basic", Some("Basic dTrDqXTDqQ==")), ok("u|été"));
+    }
+
+    #[test]
+    fn basic_invalid_credentials() {
+        assert_eq!(BasicAuth::parse("bm9jb2xvbg=="), Err(AuthError::MissingColon));
+        assert_eq!(BasicAuth::parse(""), Err(AuthError::MissingColon));
+        assert_eq!(BasicAuth::parse("//46cHc="), Err(AuthError::InvalidUtf8));
+        assert_eq!(BasicAuth::parse("YWxpY2U6b3BlbiBzZXNhbWU"), Err(AuthError::InvalidBase64));
+        assert_eq!(BasicAuth::parse("YWxp*2U6"), Err(AuthError::InvalidBase64));
+
+        let client = client();
+        assert_eq!(get(&client, "/basic", Some("Basic bm9jb2xvbg==")).0, Status::BadRequest);
+        assert_eq!(get(&client, "/basic", Some("Basic //46cHc=")).0, Status::BadRequest);
+        assert_eq!(get(&client, "/basic", Some("Basic %%%")).0, Status::BadRequest);
+        assert_eq!(get(&client, "/result", Some("Basic //46cHc=")).1,
+            "credentials are not valid UTF-8");
+        assert_eq!(get(&client, "/result", Some("Basic bm9jb2xvbg==")).1,
+            "credentials are missing a `:`");
+    }
+
+    #[test]
+    fn basic_debug_redacts_password() {
+        let auth = BasicAuth::parse("YWxpY2U6b3BlbiBzZXNhbWU=").unwrap();
+        let debug = format!("{:?}", auth);
+        assert!(debug.contains("alice"));
+        assert!(!debug.contains("sesame"));
+    }
+
+    #[test]
+    fn bearer_tokens() {
+        let client = client();
+        let ok = |v: &str| (Status::Ok, v.to_string());
+
+        assert_eq!(get(&client, "/bearer", Some("Bearer mF_9.B5f-4.1JqM")), ok("mF_9.B5f-4.1JqM"));
+        assert_eq!(get(&client, "/bearer", Some("bearer abc")), ok("abc"));
+        assert_eq!(get(&client, "/bearer", Some("BEARER  a+b/c~d==")), ok("a+b/c~d=="));
+
+        assert_eq!(get(&client, "/bearer", Some("Bearer")).0, Status::BadRequest);
+        assert_eq!(get(&client, "/bearer", Some("Bearer a b")).0, Status::BadRequest);
+        assert_eq!(get(&client, "/bearer", Some("Bearer ==")).0, Status::BadRequest);
+        assert_eq!(get(&client, "/bearer", Some("Bearer tok=en")).0, Status::BadRequest);
+    }
+
+    #[test]
+    fn wrong_scheme_forwards() {
+        let client = client();
+        let anonymous = (Status::Ok, "anonymous".to_string());
+
+        assert_eq!(get(&client, "/basic", Some("Bearer abc")), anonymous);
+        assert_eq!(get(&client, "/basic", Some("Digest username=\"a\"")), anonymous);
+        assert_eq!(get(&client, "/basic", Some("Basically YWxpY2U6")), anonymous);
+        assert_eq!(get(&client, "/bearer", Some("Basic YWxpY2U6b3BlbiBzZXNhbWU=")), anonymous);
+        assert_eq!(get(&client, "/bearer", Some("Bearerabc")), anonymous);
+    }
+
+    #[test]
+    fn missing_header_forwards() {
+        let client = client();
+        let anonymous = (Status::Ok, "anonymous".to_string());
+        assert_eq!(get(&client, "/basic", None), anonymous);
+        assert_eq!(get(&client, "/bearer", None), anonymous);
+        assert_eq!(get(&client, "/result", None).0, Status::NotFound);
+    }
+}
@@ -0,0 +1,106 @@
+#[macro_use] extern crate rocket;
+
+use rocket::http::Status;
+
+#[get("/items")]
+fn list() -> &'static str { "items" }
+
+#[post("/items")]
+fn create() -> &'static str { "created" }
+
+#[delete("/items/<id>")]
+fn remove(id: usize) -> String { id.to_string() }
+
+#[options("/items/<_id>")]
+fn custom_options(_id: usize) -> &'static str { "custom" }
+
+#[get("/teapot")]
+fn teapot() -> Status { Status::MethodNotAllowed }
+
+#[put("/forwards")]
+fn forwards() -> Option<&'static str> { None }
+
+mod auto_options_tests {
+    use super::*;
+    use rocket::local::blocking::{Client, LocalResponse};
+
+    fn client(auto_options: bool) -> Client {
+        let config = rocket::Config { auto_options, ..rocket::Config::debug_default() };
+        let routes = routes![list, create, remove, custom_options, teapot, forwards];
+        Client::debug(rocket::custom(config).mount("/", routes)).unwrap()
+    }
+
+    fn allow(response: &LocalResponse<'_>) -> Option<String> {
+        response.headers().get_one("Allow").map(String::from)
+    }
+
+    #[test]
+    fn options_lists_allowed_methods() {
+        let client = client(true);
+        let response = client.options("/items").dispatch();
+        assert_eq!(response.status(), Status::NoContent);
+        assert_eq!(allow(&response).as_deref(), Some("GET, HEAD, POST, OPTIONS"));
+        assert!(response.into_bytes().unwrap_or_default().is_empty());
+
+        // Routes with dynamic segments are considered too.
+        let response = client.options("/items/7").dispatch();
+        assert_eq!(response.into_string().unwrap(), "custom");
+    }
+
+    #[test]
+    fn options_without_routes_is_404() {
+        let client = client(true);
+        let response = client.options("/missing").dispatch();
+        assert_eq!(response.status(), Status::NotFound);
+        assert_eq!(allow(&response), None);
+    }
+
+    #[test]
+    fn method_not_allowed_carries_allow() {
+        let client = client(true);
+        let response = client.put("/items").dispatch();
+        assert_eq!(response.status(), Status::MethodNotAllowed);
+        assert_eq!(allow(&response).as_deref(), Some("GET, HEAD, POST, OPTIONS"));
+
+        let response = client.get("/items/7").dispatch();
+        assert_eq!(response.status(), Status::MethodNotAllowed);
+        assert_eq!(allow(&response).as_deref(), Some("DELETE, OPTIONS"));
+
+        // `HEAD` is rerouted to `GET`, so it's only allowed where `GET` is.
+        let response = client.head("/items").dispatch();
+        assert_eq!(response.status(), Status::Ok);
+        let response = client.head("/items/7").dispatch();
+        assert_eq!(response.status(), Status::MethodNotAllowed);
+    }
+
+    #[test]
+    fn handler_405_carries_allow() {
+        let client = client(true);
+        let response = client.get("/teapot").dispatch();
+        assert_eq!(response.status(), Status::MethodNotAllowed);
+        assert_eq!(allow(&response).as_deref(), Some("GET, HEAD, OPTIONS"));
+    }
+
+    #[test]
+    fn forwarded_allowed_method_is_404() {
+        let client = client(true);
+        let response = client.put("/forwards").dispatch();
+        assert_eq!(response.status(), Status::NotFound);
+        assert_eq!(allow(&response), None);
+    }
+
+    #[test]
+    fn disabled_by_default() {
+        let client = client(false);
+        let response = client.options("/items").dispatch();
+        assert_eq!(response.status(), Status::NotFound);
+        assert_eq!(allow(&response), None);
+
+        let response = client.put("/items").dispatch();
+        assert_eq!(response.status(), Status::NotFound);
+
+        let response = client.get("/teapot").dispatch();
+        assert_eq!(response.status(), Status::MethodNotAllowed);
+        assert_eq!(allow(&response), None);
+    }
+}
@@ -0,0 +1,127 @@
+#[macro_use] extern crate rocket;
+
+use rocket::request::{base64, Base64, Base64Error};
+
+#[get("/url/<token>")]
+fn url(token: Base64) -> Vec<u8> {
+    token.into_inner()
+}
+
+#[get("/url-padded/<token>")]
+fn url_padded(token: Base64<base64::UrlSafePadded>) -> Vec<u8> {
+    token.into_inner()
+}
+
+#[get("/std/<token>")]
+fn standard(token: Base64<base64::Standard>) -> Vec<u8> {
+    token.into_inner()
+}
+
+#[get("/std-nopad/<token>")]
+fn standard_no_pad(token: Base64<base64::StandardNoPad>) -> Vec<u8> {
+    token.into_inner()
+}
+
+#[get("/error/<token>")]
+fn error(token: Result<Base64, Base64Error>) -> String {
+    match token {
+        Ok(token) => format!("{:?}", &*token),
+        Err(e) => e.to_string(),
+    }
+}
+
+mod base64_param_tests {
+    use super::*;
+    use rocket::http::Status;
+    use rocket::request::FromParam;
+    use rocket::local::blocking::Client;
+
+    fn client() -> Client {
+        Client::debug_with(routes![url, url_padded, standard, standard_no_pad, error]).unwrap()
+    }
+
+    fn get(client: &Client, uri: &str) -> Option<Vec<u8>> {
+        let response = client.get(uri.to_string()).dispatch();
+        if response.status() != Status::Ok {
+            return None;
+        }
+
+        response.into_bytes()
+    }
+
+    fn decode<C: base64::Config>(input: &str) -> Result<Vec<u8>, Base64Error> {
+        Base64::<C>::from_param(input).map(|b| b.into_inner())
+    }
+
+    #[test]
+    fn unpadded() {
+        let client = client();
+        assert_eq!(get(&client, "/url/"), None);
+        assert_eq!(get(&client, "/url/aA").unwrap(), b"h");
+        assert_eq!(get(&client, "/url/aGk").unwrap(), b"hi");
+        assert_eq!(get(&client, "/url/aGV5").unwrap(), b"hey");
+        assert_eq!(get(&client, "/std-nopad/aGk").unwrap(), b"hi");
+
+        // Padding is forbidden in both configurations.
+        assert_eq!(get(&client, "/url/aGk="), None);
+        assert_eq!(get(&client, "/std-nopad/aA=="), None);
+
+        assert_eq!(decode::<base64::UrlSafe>("aGk="), Err(Base64Error::InvalidPadding));
+    }
+
+    #[test]
+    fn padded() {
+        let client = client();
+        assert_eq!(get(&client, "/std/aA==").unwrap(), b"h");
+        assert_eq!(get(&client, "/std/aGk=").unwrap(), b"hi");
+        assert_eq!(get(&client, "/std/aGV5").unwrap(), b"hey");
+        assert_eq!(get(&client, "/url-padded/aGk=").unwrap(), b"hi");
+
+        // Padding is required in both configurations.
+        assert_eq!(get(&client, "/std/aGk"), None);
+        assert_eq!(get(&client, "/url-padded/aA"), None);
+
+        // Padding must bring the length to a multiple of four, exactly.
+        assert_eq!(decode::<base64::Standard>("aGk=="), Err(Base64Error::InvalidPadding));
+        assert_eq!(decode::<base64::Standard>("aA="), Err(Base64Error::InvalidPadding));
+        assert_eq!(decode::<base64::Standard>("aGV5===="), Err(Base64Error::InvalidPadding));
+        assert_eq!(decode::<base64::Standard>("a==="), Err(Base64Error::InvalidPadding));
+        assert_eq!(decode::<base64::Standard>("aA=a"), Err(Base64Error::InvalidChar(2, '=')));
+    }
+
+    #[test]
+    fn alphabets() {
+        let client = client();
+        assert_eq!(get(&client, "/url/-_8").unwrap(), [0xfb, 0xff]);
+        assert_eq!(get(&client, "/std/+%2F8=").unwrap(), [0xfb, 0xff]);
+        assert_eq!(get(&client, "/std-nopad/+%2F8").unwrap(), [0xfb, 0xff]);
+
+        // An unencoded `/` splits the segment.
+        assert_eq!(get(&client, "/std/+/8="), None);
+
+        // Neither alphabet accepts the other's characters.
+        assert_eq!(get(&client, "/url/+%2F8"), None);
+        assert_eq!(get(&client, "/std/-_8="), None);
+
+        assert_eq!(decode::<base64::UrlSafe>("+_8"), Err(Base64Error::InvalidChar(0, '+')));
+        assert_eq!(decode::<base64::StandardNoPad>("+_8"), Err(Base64Error::InvalidChar(1, '_')));
+    }
+
+    #[test]
+    fn invalid_characters() {
+        let client = client();
+        assert_eq!(get(&client, "/url/aG%20k"), None);
+        assert_eq!(get(&client, "/error/aG%20k").unwrap(),
+            b"invalid base64 character ' ' at index 2");
+
+        assert_eq!(get(&client, "/error/a%C3%A9k").unwrap(),
+            b"invalid base64 character '\xc3\xa9' at index 1");
+
+        // Bits beyond the end of the data must be zero.
+        assert_eq!(decode::<base64::UrlSafe>("aGl"), Err(Base64Error::InvalidChar(2, 'l')));
+        assert_eq!(get(&client, "/error/aB").unwrap(), b"invalid base64 character 'B' at index 1");
+
+        // No data encodes to a length one more than a multiple of four.
+        assert_eq!(get(&client, "/error/aGV5a").unwrap(), b"invalid base64 length 5");
+    }
+}
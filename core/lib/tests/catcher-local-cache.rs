@@ -0,0 +1,124 @@
+#[macro_use] extern crate rocket;
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use rocket::{Request, State};
+use rocket::http::Status;
+use rocket::request::{self, FromRequest, local_cache_once};
+
+/// Counts how many times the guard did its work outside of a catcher.
+#[derive(Default)]
+struct Lookups(AtomicUsize);
+
+/// A guard that caches the user it "looks up" and fails for missing users or
+/// guests.
+struct User(String);
+
+#[rocket::async_trait]
+impl<'r> FromRequest<'r> for User {
+    type Error = ();
+
+    async fn from_request(req: &'r Request<'_>) -> request::Outcome<Self, ()> {
+        let name = req.local_cache(|| {
+            if !req.is_in_catcher() {
+                req.rocket().state::<Lookups>().unwrap().0.fetch_add(1, Ordering::SeqCst);
+            }
+
+            req.query_value::<&str>("user").and_then(|r| r.ok()).map(String::from)
+        });
+
+        match name {
+            Some(name) if name != "guest" => request::Outcome::Success(User(name.clone())),
+            _ => request::Outcome::Failure((Status::Unauthorized, ())),
+        }
+    }
+}
+
+/// Stores `path` as the first path seen by `req`, returning the stored path.
+/// The `local_cache_once!` type is shared by every call to this function.
+fn first_path<'r>(req: &'r Request<'_>, path: String) -> &'r String {
+    local_cache_once!(req, path)
+}
+
+/// A guard that records where it was run, then forwards.
+struct Trail;
+
+#[rocket::async_trait]
+impl<'r> FromRequest<'r> for Trail {
+    type Error = ();
+
+    async fn from_request(req: &'r Request<'_>) -> request::Outcome<Self, ()> {
+        first_path(req, req.uri().path().to_string());
+        request::Outcome::Forward(())
+    }
+}
+
+#[get("/private")]
+fn private(user: User) -> String {
+    user.0
+}
+
+#[get("/trail")]
+fn trail(_trail: Trail) { }
+
+#[get("/lookups")]
+fn lookups(lookups: &State<Lookups>) -> String {
+    lookups.0.load(Ordering::SeqCst).to_string()
+}
+
+#[catch(401)]
+async fn unauthorized(req: &Request<'_>) -> String {
+    let cached = req.local_cache(|| None::<String>);
+    let rerun = req.guard::<User>().await.succeeded().map(|u| u.0);
+    format!("{} {:?} {:?}", req.is_in_catcher(), cached, rerun)
+}
+
+#[catch(404)]
+fn not_found(req: &Request<'_>) -> String {
+    let path = first_path(req, String::from("<none>"));
+    format!("{} {}", req.is_in_catcher(), path)
+}
+
+mod catcher_local_cache_tests {
+    use super::*;
+    use rocket::local::blocking::Client;
+
+    fn client() -> Client {
+        let rocket = rocket::build()
+            .manage(Lookups::default())
+            .mount("/", routes![private, trail, lookups])
+            .register("/", catchers![unauthorized, not_found]);
+
+        Client::debug(rocket).unwrap()
+    }
+
+    #[test]
+    fn guard_value_is_visible_in_catcher() {
+        let client = client();
+        let response = client.get("/private").dispatch();
+        assert_eq!(response.status(), Status::Unauthorized);
+        assert_eq!(response.into_string().unwrap(), "true None None");
+
+        let response = client.get("/private?user=guest").dispatch();
+        assert_eq!(response.status(), Status::Unauthorized);
+        assert_eq!(response.into_string().unwrap(), "true Some(\"guest\") None");
+
+        let response = client.get("/private?user=bob").dispatch();
+        assert_eq!(response.into_string().unwrap(), "bob");
+
+        // The guard's lookup ran once per request, never in the catcher.
+        assert_eq!(client.get("/lookups").dispatch().into_string().unwrap(), "3");
+    }
+
+    #[test]
+    fn local_cache_once_value_is_visible_in_catcher() {
+        let client = client();
+        let response = client.get("/trail").dispatch();
+        assert_eq!(response.status(), Status::NotFound);
+        assert_eq!(response.into_string().unwrap(), "true /trail");
+
+        // A request that never ran the guard has nothing cached.
+        let response = client.get("/nowhere").dispatch();
+        assert_eq!(response.into_string().unwrap(), "true <none>");
+    }
+}
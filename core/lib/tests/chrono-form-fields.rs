@@ -0,0 +1,123 @@
+#![cfg(feature = "chrono")]
+
+#[macro_use] extern crate rocket;
+
+use rocket::chrono::{DateTime, NaiveDate, NaiveDateTime, Utc};
+use rocket::form::{Form, Errors};
+
+#[derive(Debug, FromForm)]
+struct Event {
+    day: NaiveDate,
+    local: NaiveDateTime,
+    at: DateTime<Utc>,
+}
+
+#[post("/", data = "<form>")]
+fn event(form: Result<Form<Event>, Errors<'_>>) -> String {
+    match form {
+        Ok(event) => format!("{} {} {:?}", event.day, event.local, event.at),
+        Err(errors) => errors.iter()
+            .map(|e| format!("{}: {}", e.name.as_ref().unwrap(), e))
+            .collect::<Vec<_>>()
+            .join("; "),
+    }
+}
+
+mod chrono_form_fields_tests {
+    use super::*;
+    use rocket::form::{self, FromFormField, ValueField};
+    use rocket::http::ContentType;
+    use rocket::local::blocking::Client;
+
+    fn parse<'v, T: FromFormField<'v>>(value: &'v str) -> Result<T, String> {
+        T::from_value(ValueField::from_value(value))
+            .map_err(|errors| errors.iter().map(|e| e.to_string()).collect::<Vec<_>>().join("; "))
+    }
+
+    fn date(y: i32, m: u32, d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(y, m, d).unwrap()
+    }
+
+    #[test]
+    fn naive_date() {
+        assert_eq!(parse::<NaiveDate>("2023-02-28"), Ok(date(2023, 2, 28)));
+        assert_eq!(parse::<NaiveDate>("2024-02-29"), Ok(date(2024, 2, 29)));
+
+        assert_eq!(parse::<NaiveDate>("2023-02-30").unwrap_err(),
+            "invalid date: input is out of range");
+        assert_eq!(parse::<NaiveDate>("2023-13-01").unwrap_err(),
+            "invalid date: input is out of range");
+        assert!(parse::<NaiveDate>("2023-02-28T10:00").is_err());
+        assert!(parse::<NaiveDate>("02/28/2023").is_err());
+        assert_eq!(parse::<NaiveDate>("").unwrap_err(), "empty date");
+    }
+
+    #[test]
+    fn naive_date_time() {
+        let at = |h, m, s, ms| date(2023, 6, 1).and_hms_milli_opt(h, m, s, ms).unwrap();
+        assert_eq!(parse::<NaiveDateTime>("2023-06-01T10:30"), Ok(at(10, 30, 0, 0)));
+        assert_eq!(parse::<NaiveDateTime>("2023-06-01T10:30:15"), Ok(at(10, 30, 15, 0)));
+        assert_eq!(parse::<NaiveDateTime>("2023-06-01T10:30:15.250"), Ok(at(10, 30, 15, 250)));
+
+        assert_eq!(parse::<NaiveDateTime>("2023-06-01T10:30Z").unwrap_err(),
+            "invalid date and time: trailing input");
+        assert_eq!(parse::<NaiveDateTime>("2023-06-01T10:30:00+02:00").unwrap_err(),
+            "unexpected timezone offset in date and time without a timezone");
+        assert!(parse::<NaiveDateTime>("2023-06-01T25:00").is_err());
+        assert!(parse::<NaiveDateTime>("2023-06-31T10:00").is_err());
+        assert!(parse::<NaiveDateTime>("2023-06-01").is_err());
+        assert_eq!(parse::<NaiveDateTime>("").unwrap_err(), "empty date and time");
+    }
+
+    #[test]
+    fn utc_date_time() {
+        let utc = |h, m| date(2023, 6, 1).and_hms_opt(h, m, 0).unwrap().and_utc();
+        assert_eq!(parse::<DateTime<Utc>>("2023-06-01T10:30:00Z"), Ok(utc(10, 30)));
+        assert_eq!(parse::<DateTime<Utc>>("2023-06-01T12:30:00+02:00"), Ok(utc(10, 30)));
+        assert_eq!(parse::<DateTime<Utc>>("2023-06-01 10:30:00z"), Ok(utc(10, 30)));
+
+        assert_eq!(parse::<DateTime<Utc>>("2023-06-01T10:30:00").unwrap_err(),
+            "missing timezone offset in date and time");
+        assert_eq!(parse::<DateTime<Utc>>("2023-06-01T10:30").unwrap_err(),
+            "missing timezone offset in date and time");
+        assert_eq!(parse::<DateTime<Utc>>("2023-02-30T10:30:00Z").unwrap_err(),
+            "invalid date and time: input is out of range");
+        assert_eq!(parse::<DateTime<Utc>>("").unwrap_err(), "empty date and time");
+    }
+
+    #[test]
+    fn form_errors_are_named() {
+        let client = Client::debug_with(routes![event]).unwrap();
+        let post = |body: &str| client.post("/")
+            .header(ContentType::Form)
+            .body(body)
+            .dispatch()
+            .into_string()
+            .unwrap();
+
+        assert_eq!(post("day=2023-06-01&local=2023-06-01T10:30&at=2023-06-01T10:30:00%2B02:00"),
+            "2023-06-01 2023-06-01 10:30:00 2023-06-01T08:30:00Z");
+
+        assert_eq!(post("day=2023-02-30&local=2023-06-01T10:30&at=2023-06-01T10:30:00Z"),
+            "day: invalid date: input is out of range");
+
+        assert_eq!(post("day=&local=2023-06-01T10:30:00Z&at=2023-06-01T10:30"),
+            "day: empty date; local: unexpected timezone offset in date and time without a \
+            timezone; \
+            at: missing timezone offset in date and time");
+    }
+
+    #[test]
+    fn optional_fields_are_none_when_invalid() {
+        #[derive(FromForm)]
+        struct Optional {
+            day: Option<NaiveDate>,
+        }
+
+        let form: Optional = Form::parse("day=2023-02-30").unwrap();
+        assert_eq!(form.day, None);
+
+        let form: form::Result<'_, Optional> = Form::parse("day=2023-02-03");
+        assert_eq!(form.unwrap().day, Some(date(2023, 2, 3)));
+    }
+}
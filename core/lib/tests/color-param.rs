@@ -0,0 +1,87 @@
+#[macro_use] extern crate rocket;
+
+use rocket::request::{Color, ColorError};
+
+#[get("/swatch/<color>")]
+fn swatch(color: Result<Color, ColorError>) -> String {
+    match color {
+        Ok(color) => color.to_string(),
+        Err(e) => e.to_string(),
+    }
+}
+
+mod color_param_tests {
+    use super::*;
+    use rocket::request::FromParam;
+    use rocket::local::blocking::Client;
+
+    #[test]
+    fn hex_lengths() {
+        assert_eq!(Color::from_param("f80"), Ok(Color::rgb(0xff, 0x88, 0x00)));
+        assert_eq!(Color::from_param("F80C"), Ok(Color::rgba(0xff, 0x88, 0x00, 0xcc)));
+        assert_eq!(Color::from_param("ff8800"), Ok(Color::rgb(0xff, 0x88, 0x00)));
+        assert_eq!(Color::from_param("12345678"), Ok(Color::rgba(0x12, 0x34, 0x56, 0x78)));
+        assert_eq!(Color::from_param("#1a2B3c"), Ok(Color::rgb(0x1a, 0x2b, 0x3c)));
+        assert_eq!(Color::from_param("#abc"), Ok(Color::rgb(0xaa, 0xbb, 0xcc)));
+    }
+
+    #[test]
+    fn alpha() {
+        assert_eq!(Color::from_param("fff").unwrap().a, 255);
+        assert_eq!(Color::from_param("ffffff").unwrap().a, 255);
+        assert_eq!(Color::from_param("fff0").unwrap().a, 0x00);
+        assert_eq!(Color::from_param("fff8").unwrap().a, 0x88);
+        assert_eq!(Color::from_param("ffff").unwrap().a, 0xff);
+        assert_eq!(Color::from_param("ffffff00").unwrap().a, 0x00);
+        assert_eq!(Color::from_param("ffffff80").unwrap().a, 0x80);
+        assert_eq!(Color::from_param("ffffffff").unwrap().a, 0xff);
+
+        // Translucent colors display their alpha; opaque ones don't.
+        assert_eq!(Color::from_param("f808").unwrap().to_string(), "#ff880088");
+        assert_eq!(Color::from_param("f80f").unwrap().to_string(), "#ff8800");
+    }
+
+    #[test]
+    fn named_colors() {
+        assert_eq!(Color::from_param("red"), Ok(Color::rgb(0xff, 0, 0)));
+        assert_eq!(Color::from_param("CornflowerBlue"), Ok(Color::rgb(0x64, 0x95, 0xed)));
+        assert_eq!(Color::from_param("aliceblue"), Ok(Color::rgb(0xf0, 0xf8, 0xff)));
+        assert_eq!(Color::from_param("yellowgreen"), Ok(Color::rgb(0x9a, 0xcd, 0x32)));
+        assert_eq!(Color::from_param("grey"), Color::from_param("gray"));
+        assert_eq!(Color::from_param("transparent"), Ok(Color::rgba(0, 0, 0, 0)));
+    }
+
+    #[test]
+    fn bad_names() {
+        for name in ["redd", "rouge", "light-blue", "light blue", "12g", "#red"] {
+            assert!(Color::from_param(name).is_err(), "{}", name);
+        }
+
+        assert_eq!(Color::from_param("rouge"), Err(ColorError::UnknownName("rouge".into())));
+        assert_eq!(Color::from_param("#red"), Err(ColorError::InvalidDigit('r')));
+        assert_eq!(Color::from_param(""), Err(ColorError::Empty));
+    }
+
+    #[test]
+    fn wrong_hex_lengths() {
+        for (hex, len) in [("f", 1), ("ff", 2), ("fffff", 5), ("fffffff", 7), ("fffffffff", 9)] {
+            assert_eq!(Color::from_param(hex), Err(ColorError::InvalidLength(len)));
+        }
+
+        assert_eq!(Color::from_param("#"), Err(ColorError::InvalidLength(0)));
+        assert_eq!(Color::from_param("#12345"), Err(ColorError::InvalidLength(5)));
+    }
+
+    #[test]
+    fn routes() {
+        let client = Client::debug_with(routes![swatch]).unwrap();
+        let get = |uri: &str| client.get(uri.to_string()).dispatch().into_string().unwrap();
+
+        assert_eq!(get("/swatch/ff8800"), "#ff8800");
+        assert_eq!(get("/swatch/%23F80"), "#ff8800");
+        assert_eq!(get("/swatch/red"), "#ff0000");
+        assert_eq!(get("/swatch/ff880080"), "#ff880080");
+        assert_eq!(get("/swatch/blurple"), "unknown color name `blurple`");
+        assert_eq!(get("/swatch/ff88000"), "hex color has 7 digits; expected 3, 4, 6, or 8");
+    }
+}
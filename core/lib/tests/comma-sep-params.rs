@@ -0,0 +1,104 @@
+#[macro_use] extern crate rocket;
+
+use rocket::request::{CommaSep, CommaSepLoose, CommaSepError};
+
+#[get("/items/<ids>")]
+fn items(ids: CommaSep<u32>) -> String {
+    format!("{:?}", *ids)
+}
+
+#[get("/items/<_ids>", rank = 2)]
+fn items_fallback(_ids: &str) -> &'static str {
+    "fallback"
+}
+
+#[get("/loose/<ids>")]
+fn loose(ids: CommaSepLoose<u32>) -> String {
+    format!("{:?}", ids.into_inner())
+}
+
+#[get("/names/<names>")]
+fn names(names: CommaSep<&str>) -> String {
+    names.join("|")
+}
+
+mod comma_sep_tests {
+    use super::*;
+    use rocket::request::FromParam;
+    use rocket::local::blocking::Client;
+
+    fn strict(param: &str) -> Result<Vec<i32>, CommaSepError<'_, &str>> {
+        CommaSep::<i32>::from_param(param).map(|v| v.into_inner())
+    }
+
+    fn lenient(param: &str) -> Result<Vec<i32>, CommaSepError<'_, &str>> {
+        CommaSepLoose::<i32>::from_param(param).map(|v| v.into_inner())
+    }
+
+    #[test]
+    fn ints() {
+        assert_eq!(strict("1,2,3"), Ok(vec![1, 2, 3]));
+        assert_eq!(strict("-7"), Ok(vec![-7]));
+        assert_eq!(strict("3,3,1"), Ok(vec![3, 3, 1]));
+        assert_eq!(lenient("1,2,3"), Ok(vec![1, 2, 3]));
+    }
+
+    #[test]
+    fn empty_string() {
+        assert_eq!(strict(""), Ok(vec![]));
+        assert_eq!(lenient(""), Ok(vec![]));
+    }
+
+    #[test]
+    fn trailing_comma() {
+        assert_eq!(strict("1,2,"), Err(CommaSepError::Empty { index: 2 }));
+        assert_eq!(strict(","), Err(CommaSepError::Empty { index: 0 }));
+        assert_eq!(strict(",1"), Err(CommaSepError::Empty { index: 0 }));
+        assert_eq!(strict("1,,2"), Err(CommaSepError::Empty { index: 1 }));
+
+        assert_eq!(lenient("1,2,"), Ok(vec![1, 2]));
+        assert_eq!(lenient(",1,,2"), Ok(vec![1, 2]));
+        assert_eq!(lenient(",,,"), Ok(vec![]));
+    }
+
+    #[test]
+    fn mixed_valid_and_invalid() {
+        assert_eq!(strict("1,two,3"),
+            Err(CommaSepError::Element { index: 1, element: "two", error: "two" }));
+        assert_eq!(strict("1,2,x,y"),
+            Err(CommaSepError::Element { index: 2, element: "x", error: "x" }));
+        assert_eq!(strict("1, 2"),
+            Err(CommaSepError::Element { index: 1, element: " 2", error: " 2" }));
+
+        // Indices count empty elements, even when they're skipped.
+        assert_eq!(lenient(",1,,bad"),
+            Err(CommaSepError::Element { index: 3, element: "bad", error: "bad" }));
+
+        let error = strict("1,two").unwrap_err();
+        assert_eq!(error.to_string(), "invalid list element 1 `two`: \"two\"");
+        assert_eq!(strict("1,").unwrap_err().to_string(), "list element 1 is empty");
+    }
+
+    #[test]
+    fn routes() {
+        let client = Client::debug_with(routes![items, items_fallback, loose, names]).unwrap();
+
+        let response = client.get("/items/1,2,3").dispatch();
+        assert_eq!(response.into_string().unwrap(), "[1, 2, 3]");
+
+        let response = client.get("/items/1%2C2").dispatch();
+        assert_eq!(response.into_string().unwrap(), "[1, 2]");
+
+        let response = client.get("/items/1,2,").dispatch();
+        assert_eq!(response.into_string().unwrap(), "fallback");
+
+        let response = client.get("/items/1,x").dispatch();
+        assert_eq!(response.into_string().unwrap(), "fallback");
+
+        let response = client.get("/loose/1,,2,").dispatch();
+        assert_eq!(response.into_string().unwrap(), "[1, 2]");
+
+        let response = client.get("/names/a%20b,c").dispatch();
+        assert_eq!(response.into_string().unwrap(), "a b|c");
+    }
+}
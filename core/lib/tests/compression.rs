@@ -0,0 +1,148 @@
+#![cfg(feature = "compress")]
+
+#[macro_use] extern crate rocket;
+
+use std::io::Cursor;
+
+use rocket::http::ContentType;
+use rocket::response::stream::ReaderStream;
+
+const TEXT: &str = "It was the best of times, it was the worst of times. ";
+
+#[get("/text")]
+fn text() -> String {
+    TEXT.repeat(100)
+}
+
+#[get("/small")]
+fn small() -> &'static str {
+    TEXT
+}
+
+#[get("/png")]
+fn png() -> (ContentType, Vec<u8>) {
+    (ContentType::PNG, TEXT.repeat(100).into_bytes())
+}
+
+#[get("/stream")]
+fn stream() -> ReaderStream![Cursor<String>] {
+    ReaderStream::one(Cursor::new(TEXT.to_string()))
+}
+
+mod compression_tests {
+    use super::*;
+    use async_compression::tokio::bufread::{BrotliDecoder, GzipDecoder};
+    use rocket::compress::Compression;
+    use rocket::http::{Header, Status};
+    use rocket::local::asynchronous::{Client, LocalResponse};
+    use rocket::tokio::io::{AsyncRead, AsyncReadExt};
+
+    async fn client() -> Client {
+        let rocket = rocket::build()
+            .attach(Compression::new())
+            .mount("/", routes![text, small, png, stream]);
+
+        Client::tracked(rocket).await.unwrap()
+    }
+
+    async fn get<'c>(client: &'c Client, uri: &'static str, accept: &str) -> LocalResponse<'c> {
+        client.get(uri)
+            .header(Header::new("Accept-Encoding", accept.to_string()))
+            .dispatch()
+            .await
+    }
+
+    async fn decode<R: AsyncRead + Unpin>(mut decoder: R) -> String {
+        let mut string = String::new();
+        decoder.read_to_string(&mut string).await.unwrap();
+        string
+    }
+
+    #[rocket::async_test]
+    async fn gzip_selection() {
+        let client = client().await;
+        let accepts = ["gzip", "GZIP, deflate", "deflate, x-gzip", "br;q=0.2, gzip, identity;q=0"];
+        for accept in accepts {
+            let response = get(&client, "/text", accept).await;
+            assert_eq!(response.headers().get_one("Content-Encoding"), Some("gzip"), "{}", accept);
+            assert_eq!(response.headers().get_one("Vary"), Some("Accept-Encoding"));
+            assert_eq!(response.content_type(), Some(ContentType::Plain));
+
+            let bytes = response.into_bytes().await.unwrap();
+            assert!(bytes.len() < TEXT.len() * 100);
+            assert_eq!(decode(GzipDecoder::new(&bytes[..])).await, TEXT.repeat(100));
+        }
+    }
+
+    #[rocket::async_test]
+    async fn br_preference_by_q_value() {
+        let client = client().await;
+        let encoding = |accept: &'static str| {
+            let client = &client;
+            async move {
+                let response = get(client, "/text", accept).await;
+                response.headers().get_one("Content-Encoding").map(|e| e.to_string())
+            }
+        };
+
+        assert_eq!(encoding("gzip;q=0.5, br").await.as_deref(), Some("br"));
+        assert_eq!(encoding("gzip, br").await.as_deref(), Some("br"));
+        assert_eq!(encoding("gzip;q=1.0, br;q=0.9").await.as_deref(), Some("gzip"));
+        assert_eq!(encoding("br;q=0, gzip;q=0.1, identity;q=0").await.as_deref(), Some("gzip"));
+
+        // `identity` is acceptable with a q-value of 1 unless stated otherwise.
+        assert_eq!(encoding("br;q=0.9, gzip;q=0.8").await.as_deref(), None);
+        assert_eq!(encoding("br;q=0.4, identity;q=0.5").await.as_deref(), None);
+        assert_eq!(encoding("deflate").await.as_deref(), None);
+        assert_eq!(encoding("").await.as_deref(), None);
+
+        let response = get(&client, "/text", "br;q=0.9, gzip;q=0.8, identity;q=0.1").await;
+        let bytes = response.into_bytes().await.unwrap();
+        assert_eq!(decode(BrotliDecoder::new(&bytes[..])).await, TEXT.repeat(100));
+    }
+
+    #[rocket::async_test]
+    async fn small_bodies_are_not_compressed() {
+        let client = client().await;
+        let response = get(&client, "/small", "gzip, br").await;
+        assert!(response.headers().get_one("Content-Encoding").is_none());
+        assert_eq!(response.into_string().await.unwrap(), TEXT);
+
+        // Unless the client refuses them.
+        let response = get(&client, "/small", "gzip, identity;q=0").await;
+        assert_eq!(response.headers().get_one("Content-Encoding"), Some("gzip"));
+        let bytes = response.into_bytes().await.unwrap();
+        assert_eq!(decode(GzipDecoder::new(&bytes[..])).await, TEXT);
+
+        // Bodies of unknown size are compressed as they're streamed.
+        let response = get(&client, "/stream", "gzip").await;
+        assert_eq!(response.headers().get_one("Content-Encoding"), Some("gzip"));
+        let bytes = response.into_bytes().await.unwrap();
+        assert_eq!(decode(GzipDecoder::new(&bytes[..])).await, TEXT);
+    }
+
+    #[rocket::async_test]
+    async fn already_compressed_content_passes_through() {
+        let client = client().await;
+        let response = get(&client, "/png", "gzip, br").await;
+        assert_eq!(response.status(), Status::Ok);
+        assert_eq!(response.content_type(), Some(ContentType::PNG));
+        assert!(response.headers().get_one("Content-Encoding").is_none());
+        assert_eq!(response.into_bytes().await.unwrap(), TEXT.repeat(100).into_bytes());
+    }
+
+    #[rocket::async_test]
+    async fn refused_identity_is_honored() {
+        let client = client().await;
+        for accept in ["identity;q=0", "deflate, *;q=0", "br;q=0, gzip;q=0, identity;q=0"] {
+            let response = get(&client, "/text", accept).await;
+            assert_eq!(response.status(), Status::NotAcceptable, "{}", accept);
+            assert!(response.headers().get_one("Content-Encoding").is_none());
+            assert!(response.into_bytes().await.unwrap_or_default().is_empty());
+        }
+
+        // `*` covers the supported encodings, too.
+        let response = get(&client, "/png", "*, identity;q=0").await;
+        assert_eq!(response.headers().get_one("Content-Encoding"), Some("br"));
+    }
+}
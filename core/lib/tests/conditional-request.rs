@@ -0,0 +1,325 @@
+#[macro_use] extern crate rocket;
+
+use rocket::http::Status;
+use rocket::time::{Date, Month, OffsetDateTime};
+use rocket::request::{ConditionalRequest, EntityTag, Precondition};
+use rocket::request::{IfRange, RangeCondition, RangeHeader};
+
+/// `Sun, 06 Nov 1994 08:49:37 GMT`
+fn modified() -> OffsetDateTime {
+    Date::from_calendar_date(1994, Month::November, 6).unwrap()
+        .with_hms(8, 49, 37).unwrap()
+        .assume_utc()
+}
+
+fn evaluate(conditions: ConditionalRequest) -> (Status, ()) {
+    let etag = EntityTag::strong("v2").unwrap();
+    (conditions.matches(Some(&etag), Some(modified())).status(), ())
+}
+
+#[get("/")]
+fn get(conditions: ConditionalRequest) -> (Status, ()) {
+    evaluate(conditions)
+}
+
+#[put("/")]
+fn put(conditions: ConditionalRequest) -> (Status, ()) {
+    evaluate(conditions)
+}
+
+#[get("/weak")]
+fn weak(conditions: ConditionalRequest) -> (Status, ()) {
+    let etag = EntityTag::weak("v2").unwrap();
+    (conditions.matches(Some(&etag), None).status(), ())
+}
+
+#[get("/untagged")]
+fn untagged(conditions: ConditionalRequest) -> (Status, ()) {
+    (conditions.matches(None, None).status(), ())
+}
+
+#[get("/file")]
+fn file(range: Option<RangeHeader>, conditions: ConditionalRequest) -> (Status, &'static str) {
+    const FILE: &str = "0123456789";
+
+    let etag = EntityTag::strong("v2").unwrap();
+    match conditions.range(Some(&etag), Some(modified())) {
+        RangeCondition::Partial => match range.map(|r| r.resolve(FILE.len() as u64)) {
+            Some(Ok(ranges)) => {
+                let (start, end) = ranges[0];
+                (Status::PartialContent, &FILE[start as usize..=end as usize])
+            }
+            Some(Err(_)) => (Status::RangeNotSatisfiable, ""),
+            None => (Status::Ok, FILE),
+        },
+        RangeCondition::Full => (Status::Ok, FILE),
+    }
+}
+
+mod conditional_request_tests {
+    use super::*;
+    use rocket::http::{Header, Method};
+    use rocket::local::blocking::Client;
+    use rocket::request::{EntityTags, FromRequest};
+
+    fn client() -> Client {
+        Client::debug_with(routes![get, put, weak, untagged, file]).unwrap()
+    }
+
+    fn status(client: &Client, method: Method, uri: &str, headers: &[(&str, &str)]) -> Status {
+        let mut request = client.req(method, uri);
+        for (name, value) in headers {
+            request.add_header(Header::new(name.to_string(), value.to_string()));
+        }
+
+        request.dispatch().status()
+    }
+
+    fn conditions(client: &Client, headers: &[(&'static str, &'static str)]) -> ConditionalRequest {
+        let mut request = client.get("/");
+        for (name, value) in headers {
+            request.add_header(Header::new(*name, *value));
+        }
+
+        let outcome = rocket::async_test(ConditionalRequest::from_request(request.inner()));
+        outcome.succeeded().unwrap()
+    }
+
+    #[test]
+    fn multiple_etags() {
+        let client = client();
+        let none_match = |value| status(&client, Method::Get, "/", &[("If-None-Match", value)]);
+        assert_eq!(none_match(r#""v1", "v2", "v3""#), Status::NotModified);
+        assert_eq!(none_match(r#""v1","v3""#), Status::Ok);
+        assert_eq!(none_match(r#"W/"v1", W/"v2""#), Status::NotModified);
+        assert_eq!(none_match(r#""v2,v3""#), Status::Ok);
+        assert_eq!(none_match(r#""v1" , "v2" ,"#), Status::NotModified);
+
+        let any_match = |value| status(&client, Method::Put, "/", &[("If-Match", value)]);
+        assert_eq!(any_match(r#""v1", "v2""#), Status::Ok);
+        assert_eq!(any_match(r#""v1", "v3""#), Status::PreconditionFailed);
+
+        // Values from several headers are combined.
+        let headers = [("If-None-Match", r#""v1""#), ("If-None-Match", r#""v2""#)];
+        assert_eq!(status(&client, Method::Get, "/", &headers), Status::NotModified);
+
+        let tags = conditions(&client, &[("If-None-Match", r#""a", W/"b""#)]);
+        assert_eq!(tags.if_none_match(), Some(&EntityTags::List(vec![
+            EntityTag::strong("a").unwrap(),
+            EntityTag::weak("b").unwrap(),
+        ])));
+    }
+
+    #[test]
+    fn weak_and_strong_comparison() {
+        let client = client();
+
+        // `If-None-Match` uses the weak comparison function...
+        let none_match = |uri, value| status(&client, Method::Get, uri, &[("If-None-Match", value)]);
+        assert_eq!(none_match("/", r#"W/"v2""#), Status::NotModified);
+        assert_eq!(none_match("/weak", r#""v2""#), Status::NotModified);
+        assert_eq!(none_match("/weak", r#"W/"v2""#), Status::NotModified);
+
+        // ...while `If-Match` uses the strong comparison function.
+        let any_match = |uri, value| status(&client, Method::Get, uri, &[("If-Match", value)]);
+        assert_eq!(any_match("/", r#""v2""#), Status::Ok);
+        assert_eq!(any_match("/", r#"W/"v2""#), Status::PreconditionFailed);
+        assert_eq!(any_match("/weak", r#""v2""#), Status::PreconditionFailed);
+        assert_eq!(any_match("/weak", r#"W/"v2""#), Status::PreconditionFailed);
+
+        // A tag is case-sensitive.
+        assert_eq!(any_match("/", r#""V2""#), Status::PreconditionFailed);
+
+        // Unsafe methods fail instead of responding with `304`.
+        let headers = [("If-None-Match", r#"W/"v2""#)];
+        assert_eq!(status(&client, Method::Put, "/", &headers), Status::PreconditionFailed);
+    }
+
+    #[test]
+    fn wildcard() {
+        let client = client();
+        let get = |uri, headers: &[_]| status(&client, Method::Get, uri, headers);
+        assert_eq!(get("/", &[("If-None-Match", "*")]), Status::NotModified);
+        assert_eq!(get("/untagged", &[("If-None-Match", "*")]), Status::NotModified);
+        assert_eq!(get("/", &[("If-Match", " * ")]), Status::Ok);
+        assert_eq!(get("/untagged", &[("If-Match", "*")]), Status::Ok);
+
+        // Without an entity tag, only the wildcard matches.
+        assert_eq!(get("/untagged", &[("If-Match", r#""v2""#)]), Status::PreconditionFailed);
+        assert_eq!(get("/untagged", &[("If-None-Match", r#""v2""#)]), Status::Ok);
+
+        let headers = [("If-None-Match", "*")];
+        assert_eq!(status(&client, Method::Put, "/", &headers), Status::PreconditionFailed);
+
+        // The wildcard can't be combined with a list of tags.
+        assert_eq!(get("/", &[("If-None-Match", r#"*, "v1""#)]), Status::Ok);
+        assert_eq!(conditions(&client, &[("If-Match", "*")]).if_match(), Some(&EntityTags::Any));
+    }
+
+    #[test]
+    fn dates() {
+        let client = client();
+        let since = |value| status(&client, Method::Get, "/", &[("If-Modified-Since", value)]);
+        assert_eq!(since("Sun, 06 Nov 1994 08:49:37 GMT"), Status::NotModified);
+        assert_eq!(since("Sun, 06 Nov 1994 08:49:36 GMT"), Status::Ok);
+        assert_eq!(since("Mon, 07 Nov 1994 00:00:00 GMT"), Status::NotModified);
+
+        // The obsolete RFC 850 and `asctime()` formats.
+        assert_eq!(since("Sunday, 06-Nov-94 08:49:37 GMT"), Status::NotModified);
+        assert_eq!(since("Sunday, 06-Nov-94 08:49:36 GMT"), Status::Ok);
+        assert_eq!(since("Sun Nov  6 08:49:37 1994"), Status::NotModified);
+        assert_eq!(since("Sun Nov  6 08:49:36 1994"), Status::Ok);
+
+        let unmodified = |value| status(&client, Method::Put, "/", &[("If-Unmodified-Since", value)]);
+        assert_eq!(unmodified("Sun, 06 Nov 1994 08:49:37 GMT"), Status::Ok);
+        assert_eq!(unmodified("Sun, 06 Nov 1994 08:49:36 GMT"), Status::PreconditionFailed);
+
+        // `If-Modified-Since` only applies to `GET` and `HEAD`.
+        let headers = [("If-Modified-Since", "Sun, 06 Nov 1994 08:49:37 GMT")];
+        assert_eq!(status(&client, Method::Put, "/", &headers), Status::Ok);
+        assert_eq!(status(&client, Method::Head, "/", &headers), Status::NotModified);
+
+        // Dates are ignored without a last modification date.
+        assert_eq!(status(&client, Method::Get, "/weak", &headers), Status::Ok);
+
+        let date = conditions(&client, &[("If-Modified-Since", "Sun, 06 Nov 2044 08:49:37 GMT")]);
+        assert_eq!(date.if_modified_since().unwrap().year(), 2044);
+        let date = conditions(&client, &[("If-Modified-Since", "Sunday, 06-Nov-44 08:49:37 GMT")]);
+        assert_eq!(date.if_modified_since().unwrap().year(), 2044);
+    }
+
+    #[test]
+    fn malformed_dates_ignored() {
+        let client = client();
+        let malformed = [
+            "", "yesterday", "1994-11-06T08:49:37Z", "Sun, 06 Nov 1994 08:49:37",
+            "Sun, 06 Nov 1994 08:49:37 UTC", "Sun, 6 Nov 1994 08:49:37 GMT",
+            "Sun, 06 nov 1994 08:49:37 GMT", "Sun, 31 Feb 1994 08:49:37 GMT",
+            "Sun, 06 Nov 1994 25:49:37 GMT", "Sun, 06-Nov-1994 08:49:37 GMT",
+            "Sun, 06-Nov-94 08:49:37 GMT", "Sunday, 06 Nov 1994 08:49:37 GMT",
+            "Sun Nov 06 08:49:37", "Sun, 06 Nov 1994 08:49:37 GMT, extra",
+        ];
+
+        for value in &malformed {
+            let conditions = conditions(&client, &[("If-Modified-Since", value)]);
+            assert!(!conditions.is_conditional(), "{:?}", value);
+            let headers = [("If-Unmodified-Since", *value)];
+            assert_eq!(status(&client, Method::Put, "/", &headers), Status::Ok, "{:?}", value);
+        }
+
+        // A duplicated date header is ignored.
+        let headers = [
+            ("If-Unmodified-Since", "Sun, 06 Nov 1994 08:49:36 GMT"),
+            ("If-Unmodified-Since", "Sun, 06 Nov 1994 08:49:36 GMT"),
+        ];
+
+        assert_eq!(status(&client, Method::Put, "/", &headers), Status::Ok);
+    }
+
+    #[test]
+    fn malformed_etags_ignored() {
+        let client = client();
+        for value in &["v2", r#""v2"#, r#"w/"v2""#, r#""v2" "v3""#, r#""v"2""#, "", " , "] {
+            let headers = [("If-Match", *value)];
+            assert_eq!(status(&client, Method::Put, "/", &headers), Status::Ok, "{:?}", value);
+            assert!(!conditions(&client, &[("If-None-Match", value)]).is_conditional());
+        }
+    }
+
+    #[test]
+    fn evaluation_order() {
+        let client = client();
+        let get = |headers: &[_]| status(&client, Method::Get, "/", headers);
+
+        // `If-Match` takes precedence over `If-Unmodified-Since`.
+        assert_eq!(get(&[
+            ("If-Match", r#""v2""#),
+            ("If-Unmodified-Since", "Sun, 06 Nov 1994 08:49:36 GMT"),
+        ]), Status::Ok);
+
+        // `If-None-Match` takes precedence over `If-Modified-Since`.
+        assert_eq!(get(&[
+            ("If-None-Match", r#""v1""#),
+            ("If-Modified-Since", "Sun, 06 Nov 1994 08:49:37 GMT"),
+        ]), Status::Ok);
+
+        // A failed precondition takes precedence over `304`.
+        assert_eq!(get(&[
+            ("If-Match", r#""v1""#),
+            ("If-None-Match", r#""v2""#),
+        ]), Status::PreconditionFailed);
+
+        assert_eq!(get(&[]), Status::Ok);
+        assert!(!conditions(&client, &[]).is_conditional());
+        assert_eq!(conditions(&client, &[]).matches(None, None), Precondition::Proceed);
+    }
+
+    fn ranged(client: &Client, if_range: &[&str]) -> (Status, String) {
+        let mut request = client.get("/file").header(Header::new("Range", "bytes=2-4"));
+        for value in if_range {
+            request.add_header(Header::new("If-Range", value.to_string()));
+        }
+
+        let response = request.dispatch();
+        (response.status(), response.into_string().unwrap())
+    }
+
+    #[test]
+    fn if_range_etag() {
+        let client = client();
+        assert_eq!(ranged(&client, &[]), (Status::PartialContent, "234".into()));
+        assert_eq!(ranged(&client, &[r#""v2""#]), (Status::PartialContent, "234".into()));
+        assert_eq!(ranged(&client, &[r#" "v2" "#]), (Status::PartialContent, "234".into()));
+
+        // A stale tag, and weak tags, which never match, yield the full file.
+        assert_eq!(ranged(&client, &[r#""v1""#]), (Status::Ok, "0123456789".into()));
+        assert_eq!(ranged(&client, &[r#"W/"v2""#]), (Status::Ok, "0123456789".into()));
+        assert_eq!(ranged(&client, &[r#""V2""#]), (Status::Ok, "0123456789".into()));
+
+        let conditions = conditions(&client, &[("If-Range", r#"W/"v2""#)]);
+        assert_eq!(conditions.if_range(), Some(&IfRange::Tag(EntityTag::weak("v2").unwrap())));
+        let weak = EntityTag::weak("v2").unwrap();
+        assert_eq!(conditions.range(Some(&weak), None), RangeCondition::Full);
+    }
+
+    #[test]
+    fn if_range_date() {
+        let client = client();
+        let partial = (Status::PartialContent, "234".into());
+        assert_eq!(ranged(&client, &["Sun, 06 Nov 1994 08:49:37 GMT"]), partial);
+        assert_eq!(ranged(&client, &["Sunday, 06-Nov-94 08:49:37 GMT"]), partial);
+        assert_eq!(ranged(&client, &["Sun Nov  6 08:49:37 1994"]), partial);
+
+        // Only an identical date matches, unlike `If-Unmodified-Since`.
+        let full = (Status::Ok, "0123456789".into());
+        assert_eq!(ranged(&client, &["Sun, 06 Nov 1994 08:49:36 GMT"]), full);
+        assert_eq!(ranged(&client, &["Mon, 07 Nov 1994 00:00:00 GMT"]), full);
+
+        let conditions = conditions(&client, &[("If-Range", "Sun, 06 Nov 1994 08:49:37 GMT")]);
+        assert_eq!(conditions.if_range(), Some(&IfRange::Date(modified())));
+        assert_eq!(conditions.range(None, None), RangeCondition::Full);
+        assert!(!conditions.is_conditional());
+    }
+
+    #[test]
+    fn if_range_malformed() {
+        let client = client();
+        let full = (Status::Ok, "0123456789".into());
+        let malformed = [
+            "", "v2", r#""v2"#, r#""v2", "v3""#, "yesterday", "2021-01-01", r#"w/"v2""#,
+        ];
+
+        for value in &malformed {
+            assert_eq!(ranged(&client, &[value]), full, "{:?}", value);
+            assert!(conditions(&client, &[("If-Range", value)]).if_range().is_none());
+        }
+
+        // A repeated header never matches, even if every value would.
+        assert_eq!(ranged(&client, &[r#""v2""#, r#""v2""#]), full);
+        assert!(conditions(&client, &[("If-Range", r#""v2""#), ("If-Range", r#""v2""#)])
+            .if_range().is_none());
+
+        assert_eq!(RangeCondition::Partial.status(), Status::PartialContent);
+        assert_eq!(RangeCondition::Full.status(), Status::Ok);
+    }
+}
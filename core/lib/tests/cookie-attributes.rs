@@ -0,0 +1,146 @@
+#[macro_use] extern crate rocket;
+
+use rocket::http::SameSite;
+use rocket::response::{Flash, Flashes};
+use rocket::time::Duration;
+
+#[post("/default")]
+fn default() -> Flash<()> {
+    Flash::success((), "Saved.")
+}
+
+#[post("/custom")]
+fn custom() -> Flash<()> {
+    Flash::success((), "Saved.")
+        .same_site(SameSite::Strict)
+        .secure(true)
+        .http_only(false)
+        .path("/account")
+        .max_age(Duration::seconds(30))
+}
+
+#[post("/many")]
+fn many() -> Flashes<()> {
+    Flashes::new(()).success("Saved.").same_site(SameSite::None).secure(true)
+}
+
+#[cfg(feature = "secrets")]
+#[post("/private")]
+fn private(jar: &rocket::http::CookieJar<'_>) {
+    use rocket::http::Cookie;
+
+    jar.add_private(Cookie::new("plain", "value"));
+    jar.add_private(Cookie::build("custom", "value")
+        .same_site(SameSite::Lax)
+        .http_only(false)
+        .secure(true)
+        .path("/app")
+        .max_age(Duration::hours(1))
+        .finish());
+}
+
+mod cookie_attributes_tests {
+    use super::*;
+    use rocket::http::Header;
+    use rocket::local::blocking::Client;
+
+    fn client() -> Client {
+        #[allow(unused_mut)]
+        let mut routes = routes![default, custom, many];
+        #[cfg(feature = "secrets")]
+        routes.extend(routes![private]);
+        Client::debug_with(routes).unwrap()
+    }
+
+    fn proxied_client() -> Client {
+        #[allow(unused_mut)]
+        let mut routes = routes![default];
+        #[cfg(feature = "secrets")]
+        routes.extend(routes![private]);
+
+        let config = rocket::Config {
+            trusted_proxies: vec!["10.0.0.0/8".parse().unwrap()],
+            ..rocket::Config::debug_default()
+        };
+
+        Client::debug(rocket::custom(config).mount("/", routes)).unwrap()
+    }
+
+    /// Returns the attributes of the `Set-Cookie` header for `name`, sorted,
+    /// without the cookie's value.
+    fn attributes(client: &Client, uri: &str, name: &str) -> Vec<String> {
+        proxied_attributes(client, uri, name, "127.0.0.1:8000")
+    }
+
+    /// Like [`attributes()`], but with a request from `remote` claiming to be
+    /// forwarded from an `https` connection.
+    fn proxied_attributes(client: &Client, uri: &str, name: &str, remote: &str) -> Vec<String> {
+        let response = client.post(uri)
+            .remote(remote.parse().unwrap())
+            .header(Header::new("X-Forwarded-Proto", "https"))
+            .dispatch();
+
+        let prefix = format!("{}=", name);
+        let header = response.headers()
+            .get("Set-Cookie")
+            .find(|header| header.starts_with(&prefix))
+            .expect("Set-Cookie header")
+            .to_string();
+
+        let mut attributes: Vec<_> = header.split("; ").skip(1).map(String::from).collect();
+        attributes.sort();
+        attributes
+    }
+
+    #[test]
+    fn flash_defaults() {
+        let client = client();
+        assert_eq!(attributes(&client, "/default", "_flash"),
+            ["HttpOnly", "Max-Age=300", "Path=/", "SameSite=Lax"]);
+    }
+
+    #[test]
+    fn flash_overrides() {
+        let client = client();
+        assert_eq!(attributes(&client, "/custom", "_flash"),
+            ["Max-Age=30", "Path=/account", "SameSite=Strict", "Secure"]);
+
+        assert_eq!(attributes(&client, "/many", "_flash"),
+            ["HttpOnly", "Max-Age=300", "Path=/", "SameSite=None", "Secure"]);
+    }
+
+    #[test]
+    #[cfg(feature = "secrets")]
+    fn private_cookie_attributes() {
+        let client = client();
+        let plain = attributes(&client, "/private", "plain");
+        assert_eq!(plain.len(), 4, "{:?}", plain);
+        assert_eq!(&plain[1..], ["HttpOnly", "Path=/", "SameSite=Strict"]);
+        assert!(plain[0].starts_with("Expires="), "{:?}", plain);
+
+        assert_eq!(attributes(&client, "/private", "custom"),
+            ["Max-Age=3600", "Path=/app", "SameSite=Lax", "Secure"]);
+    }
+
+    #[test]
+    fn secure_behind_trusted_proxy() {
+        let client = proxied_client();
+        assert_eq!(proxied_attributes(&client, "/default", "_flash", "10.0.0.1:8000"),
+            ["HttpOnly", "Max-Age=300", "Path=/", "SameSite=Lax", "Secure"]);
+
+        // The forwarded scheme of an untrusted peer is ignored.
+        assert_eq!(proxied_attributes(&client, "/default", "_flash", "1.2.3.4:8000"),
+            ["HttpOnly", "Max-Age=300", "Path=/", "SameSite=Lax"]);
+    }
+
+    #[test]
+    #[cfg(feature = "secrets")]
+    fn private_secure_behind_trusted_proxy() {
+        let client = proxied_client();
+        let plain = proxied_attributes(&client, "/private", "plain", "10.0.0.1:8000");
+        assert_eq!(&plain[1..], ["HttpOnly", "Path=/", "SameSite=Strict", "Secure"]);
+
+        let plain = proxied_attributes(&client, "/private", "plain", "1.2.3.4:8000");
+        assert_eq!(&plain[1..], ["HttpOnly", "Path=/", "SameSite=Strict"]);
+    }
+}
@@ -0,0 +1,83 @@
+#[macro_use] extern crate rocket;
+
+use std::panic::{catch_unwind, AssertUnwindSafe};
+
+use rocket::http::{Cookie, CookieJar};
+
+#[post("/login/<user>")]
+fn login(jar: &CookieJar<'_>, user: &str) -> String {
+    jar.add(Cookie::new("visited", "yes"));
+    let result = jar.batch(|jar| {
+        jar.add(Cookie::new("session", user.to_string()));
+        jar.add(Cookie::new("csrf", "token"));
+        jar.remove(Cookie::named("prefs"));
+
+        // The staged view sees its own pending changes...
+        assert_eq!(jar.get_pending("session").unwrap().value(), user);
+        assert!(jar.get_pending("prefs").is_none());
+        assert_eq!(jar.get_pending("visited").unwrap().value(), "yes");
+        match user {
+            "root" => Err("root may not log in"),
+            _ => Ok(user.len()),
+        }
+    });
+
+    // ...while the jar only sees them once the batch is committed.
+    let session = jar.get_pending("session").map(|c| c.value().to_string());
+    format!("{:?} {:?}", result, session)
+}
+
+#[post("/panic")]
+fn panic(jar: &CookieJar<'_>) -> &'static str {
+    let result = catch_unwind(AssertUnwindSafe(|| {
+        jar.batch(|jar| -> Result<(), ()> {
+            jar.add(Cookie::new("session", "half"));
+            panic!("failed midway");
+        })
+    }));
+
+    assert!(result.is_err());
+    assert!(jar.get_pending("session").is_none());
+    "recovered"
+}
+
+mod cookie_batch_tests {
+    use super::*;
+    use rocket::local::blocking::Client;
+
+    fn client() -> Client {
+        Client::debug_with(routes![login, panic]).unwrap()
+    }
+
+    #[test]
+    fn successful_batch_applies_all_changes() {
+        let client = client();
+        let response = client.post("/login/bob").cookie(Cookie::new("prefs", "dark")).dispatch();
+        let cookies = response.cookies();
+        assert_eq!(cookies.get("visited").unwrap().value(), "yes");
+        assert_eq!(cookies.get("session").unwrap().value(), "bob");
+        assert_eq!(cookies.get("csrf").unwrap().value(), "token");
+        assert_eq!(cookies.get("prefs").unwrap().value(), "");
+        assert_eq!(response.into_string().unwrap(), "Ok(3) Some(\"bob\")");
+    }
+
+    #[test]
+    fn failed_batch_applies_nothing() {
+        let client = client();
+        let response = client.post("/login/root").cookie(Cookie::new("prefs", "dark")).dispatch();
+        let cookies = response.cookies();
+        assert_eq!(cookies.iter().count(), 1);
+        assert_eq!(cookies.get("visited").unwrap().value(), "yes");
+        assert!(cookies.get("session").is_none());
+        assert!(cookies.get("prefs").is_none());
+        assert_eq!(response.into_string().unwrap(), "Err(\"root may not log in\") None");
+    }
+
+    #[test]
+    fn panicking_batch_applies_nothing() {
+        let client = client();
+        let response = client.post("/panic").dispatch();
+        assert_eq!(response.cookies().iter().count(), 0);
+        assert_eq!(response.into_string().unwrap(), "recovered");
+    }
+}
@@ -0,0 +1,88 @@
+#[macro_use] extern crate rocket;
+
+use rocket::request::{Latitude, Longitude, LatLng, CoordinateError};
+
+#[get("/at/<lat>/<lng>")]
+fn at(lat: Latitude, lng: Longitude) -> String {
+    format!("{} {}", lat, lng)
+}
+
+#[get("/near/<point>")]
+fn near(point: Result<LatLng, CoordinateError<'_>>) -> String {
+    match point {
+        Ok(point) => point.to_string(),
+        Err(e) => e.to_string(),
+    }
+}
+
+mod coordinate_params_tests {
+    use super::*;
+    use rocket::http::Status;
+    use rocket::request::FromParam;
+    use rocket::local::blocking::Client;
+
+    #[test]
+    fn in_range() {
+        let lats = [("0", 0.0), ("-90", -90.0), ("90", 90.0), ("45.5", 45.5), ("-0.001", -0.001)];
+        for (s, v) in lats {
+            assert_eq!(Latitude::from_param(s).unwrap().into_inner(), v);
+        }
+
+        for (s, v) in [("0", 0.0), ("-180", -180.0), ("180", 180.0), ("24.94", 24.94)] {
+            assert_eq!(Longitude::from_param(s).unwrap().into_inner(), v);
+        }
+
+        assert_eq!(*Latitude::from_param("1e1").unwrap(), 10.0);
+    }
+
+    #[test]
+    fn out_of_range_per_axis() {
+        assert_eq!(Latitude::from_param("90.0001"), Err(CoordinateError::OutOfRange(90.0001)));
+        assert_eq!(Latitude::from_param("-91"), Err(CoordinateError::OutOfRange(-91.0)));
+        assert_eq!(Latitude::from_param("120"), Err(CoordinateError::OutOfRange(120.0)));
+        assert!(Longitude::from_param("120").is_ok());
+
+        assert_eq!(Longitude::from_param("180.5"), Err(CoordinateError::OutOfRange(180.5)));
+        assert_eq!(Longitude::from_param("-181"), Err(CoordinateError::OutOfRange(-181.0)));
+    }
+
+    #[test]
+    fn non_finite_and_invalid() {
+        for s in ["NaN", "nan", "inf", "-inf", "infinity", "-Infinity"] {
+            assert_eq!(Latitude::from_param(s), Err(CoordinateError::NotFinite(s)));
+            assert_eq!(Longitude::from_param(s), Err(CoordinateError::NotFinite(s)));
+        }
+
+        for s in ["", "north", "1.2.3", " 1", "1,2"] {
+            assert_eq!(Latitude::from_param(s), Err(CoordinateError::Invalid(s)));
+        }
+    }
+
+    #[test]
+    fn combined() {
+        let point = LatLng::from_param("60.17,24.94").unwrap();
+        assert_eq!((*point.lat, *point.lng), (60.17, 24.94));
+        assert_eq!(point.to_string(), "60.17,24.94");
+
+        assert_eq!(LatLng::from_param("60.17"), Err(CoordinateError::Malformed("60.17")));
+        assert_eq!(LatLng::from_param("1,2,3"), Err(CoordinateError::Malformed("1,2,3")));
+        assert_eq!(LatLng::from_param("91,0"), Err(CoordinateError::OutOfRange(91.0)));
+        assert_eq!(LatLng::from_param("0,-181"), Err(CoordinateError::OutOfRange(-181.0)));
+        assert_eq!(LatLng::from_param("NaN,0"), Err(CoordinateError::NotFinite("NaN")));
+        assert_eq!(LatLng::from_param("0, 1"), Err(CoordinateError::Invalid(" 1")));
+    }
+
+    #[test]
+    fn routes() {
+        let client = Client::debug_with(routes![at, near]).unwrap();
+        let get = |uri: &str| client.get(uri.to_string()).dispatch();
+
+        assert_eq!(get("/at/60.17/24.94").into_string().unwrap(), "60.17 24.94");
+        assert_eq!(get("/at/91/0").status(), Status::NotFound);
+        assert_eq!(get("/at/0/181").status(), Status::NotFound);
+        assert_eq!(get("/at/NaN/0").status(), Status::NotFound);
+
+        assert_eq!(get("/near/-33.9,151.2").into_string().unwrap(), "-33.9,151.2");
+        assert_eq!(get("/near/0,inf").into_string().unwrap(), "coordinate `inf` is not finite");
+    }
+}
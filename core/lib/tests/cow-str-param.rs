@@ -0,0 +1,100 @@
+#[macro_use] extern crate rocket;
+
+use std::borrow::Cow;
+use std::str::Utf8Error;
+
+use rocket::request::FromParam;
+
+fn describe(name: &Cow<'_, str>) -> String {
+    match name {
+        Cow::Borrowed(name) => format!("borrowed {}", name),
+        Cow::Owned(name) => format!("owned {}", name),
+    }
+}
+
+#[get("/name/<name>")]
+fn name(name: Cow<'_, str>) -> String {
+    describe(&name)
+}
+
+#[get("/checked/<name>")]
+fn checked(name: Result<Cow<'_, str>, Utf8Error>) -> String {
+    match name {
+        Ok(name) => describe(&name),
+        Err(e) => format!("error: {}", e),
+    }
+}
+
+#[get("/lossy/<name>")]
+fn lossy(name: &str) -> String {
+    name.into()
+}
+
+mod cow_str_param_tests {
+    use super::*;
+    use rocket::http::{RawStr, Status};
+    use rocket::local::blocking::Client;
+
+    fn client() -> Client {
+        Client::debug_with(routes![name, checked, lossy]).unwrap()
+    }
+
+    fn get(client: &Client, uri: &str) -> String {
+        client.get(uri.to_string()).dispatch().into_string().unwrap()
+    }
+
+    /// Parses the raw segment `raw` as a route would, returning whether the
+    /// value is borrowed along with the value.
+    fn from_raw(raw: &str) -> Result<(bool, String), Utf8Error> {
+        let raw = RawStr::new(raw);
+        let decoded = raw.percent_decode_lossy();
+        <Cow<'_, str>>::from_raw_param(raw, &decoded)
+            .map(|cow| (matches!(cow, Cow::Borrowed(_)), cow.into_owned()))
+    }
+
+    #[test]
+    fn borrowed_without_escapes() {
+        assert_eq!(from_raw("hello"), Ok((true, "hello".into())));
+        assert_eq!(from_raw("a-b_c.d~e+f"), Ok((true, "a-b_c.d~e+f".into())));
+        assert_eq!(from_raw("\u{FFFD}"), Ok((true, "\u{FFFD}".into())));
+        assert_eq!(from_raw(""), Ok((true, "".into())));
+
+        let client = client();
+        assert_eq!(get(&client, "/name/rocket"), "borrowed rocket");
+        assert_eq!(get(&client, "/name/a+b"), "borrowed a+b");
+    }
+
+    #[test]
+    fn owned_with_escapes() {
+        assert_eq!(from_raw("hello%20world"), Ok((false, "hello world".into())));
+        assert_eq!(from_raw("caf%C3%A9"), Ok((false, "café".into())));
+        assert_eq!(from_raw("100%25"), Ok((false, "100%".into())));
+
+        // A replacement character that was sent as such is kept.
+        assert_eq!(from_raw("%EF%BF%BD%20"), Ok((false, "\u{FFFD} ".into())));
+        assert_eq!(from_raw("\u{FFFD}%20"), Ok((false, "\u{FFFD} ".into())));
+
+        let client = client();
+        assert_eq!(get(&client, "/name/hello%20world"), "owned hello world");
+        assert_eq!(get(&client, "/name/caf%C3%A9"), "owned café");
+    }
+
+    #[test]
+    fn invalid_utf8_is_rejected() {
+        assert!(from_raw("%FF").is_err());
+        assert!(from_raw("ok%C3").is_err());
+        assert!(from_raw("\u{FFFD}%FF").is_err());
+
+        let client = client();
+        assert!(get(&client, "/checked/%FFabc").starts_with("error: invalid utf-8"));
+        assert_eq!(client.get("/name/%FFabc").dispatch().status(), Status::NotFound);
+
+        // `&str` repairs the same segment instead.
+        assert_eq!(get(&client, "/lossy/%FFabc"), "\u{FFFD}abc");
+    }
+
+    #[test]
+    fn from_param_borrows() {
+        assert!(matches!(<Cow<'_, str>>::from_param("a b"), Ok(Cow::Borrowed("a b"))));
+    }
+}
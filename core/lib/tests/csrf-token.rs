@@ -0,0 +1,192 @@
+#![cfg(feature = "secrets")]
+
+#[macro_use] extern crate rocket;
+
+use rocket::form::{CsrfForm, Errors};
+use rocket::request::{CsrfToken, CsrfError};
+
+#[get("/")]
+fn token(csrf: CsrfToken) -> String {
+    csrf.to_string()
+}
+
+#[post("/")]
+fn submit(_csrf: CsrfToken) -> &'static str {
+    "accepted"
+}
+
+#[delete("/")]
+fn delete(csrf: Result<CsrfToken, CsrfError>) -> String {
+    match csrf {
+        Ok(_) => "deleted".into(),
+        Err(e) => e.to_string(),
+    }
+}
+
+#[derive(FromForm)]
+struct Transfer<'r> {
+    amount: u64,
+    note: &'r str,
+}
+
+#[post("/transfer", data = "<transfer>")]
+fn transfer(transfer: Result<CsrfForm<Transfer<'_>>, Errors<'_>>) -> String {
+    match transfer {
+        Ok(transfer) => format!("{}: {}", transfer.note, transfer.amount),
+        Err(errors) => errors.iter()
+            .map(|e| format!("{}: {}", e.name.as_ref().unwrap(), e))
+            .collect::<Vec<_>>()
+            .join(", "),
+    }
+}
+
+#[options("/")]
+fn options(_csrf: CsrfToken) -> &'static str {
+    "options"
+}
+
+mod csrf_token_tests {
+    use super::*;
+    use rocket::http::{ContentType, Header, Status};
+    use rocket::local::blocking::Client;
+
+    fn client() -> Client {
+        let routes = routes![token, submit, delete, options, transfer];
+        Client::tracked(rocket::build().mount("/", routes)).unwrap()
+    }
+
+    fn session_token(client: &Client) -> String {
+        client.get("/").dispatch().into_string().unwrap()
+    }
+
+    #[test]
+    fn matching_token() {
+        let client = client();
+        let token = session_token(&client);
+        assert_eq!(token.len(), 32);
+
+        let response = client.post("/").header(Header::new("X-CSRF-Token", token.clone()));
+        assert_eq!(response.dispatch().into_string().unwrap(), "accepted");
+
+        // The session's token is stable across requests.
+        assert_eq!(session_token(&client), token);
+    }
+
+    #[test]
+    fn missing_token() {
+        let client = client();
+        session_token(&client);
+
+        assert_eq!(client.post("/").dispatch().status(), Status::Forbidden);
+        assert_eq!(client.delete("/").dispatch().into_string().unwrap(), "missing CSRF token");
+    }
+
+    #[test]
+    fn query_token_is_ignored() {
+        let client = client();
+        let token = session_token(&client);
+
+        let response = client.post(format!("/?csrf_token={}", token)).dispatch();
+        assert_eq!(response.status(), Status::Forbidden);
+
+        let response = client.post(format!("/transfer?csrf_token={}", token))
+            .header(ContentType::Form)
+            .body("amount=10&note=rent")
+            .dispatch();
+
+        assert_eq!(response.into_string().unwrap(), "csrf_token: missing");
+    }
+
+    #[test]
+    fn form_body_token() {
+        let client = client();
+        let token = session_token(&client);
+
+        let post = |body: String| client.post("/transfer")
+            .header(ContentType::Form)
+            .body(body)
+            .dispatch();
+
+        let response = post(format!("amount=10&csrf_token={}&note=rent", token));
+        assert_eq!(response.status(), Status::Ok);
+        assert_eq!(response.into_string().unwrap(), "rent: 10");
+
+        let response = post(format!("amount=10&csrf_token={}x&note=rent", token));
+        assert_eq!(response.into_string().unwrap(), "csrf_token: CSRF token mismatch");
+
+        let response = post("amount=10&note=rent".into());
+        assert_eq!(response.into_string().unwrap(), "csrf_token: missing");
+
+        // The header is accepted in place of the field.
+        let response = client.post("/transfer")
+            .header(ContentType::Form)
+            .header(Header::new("X-CSRF-Token", token))
+            .body("amount=10&note=rent")
+            .dispatch();
+
+        assert_eq!(response.into_string().unwrap(), "rent: 10");
+    }
+
+    #[test]
+    fn multipart_body_token() {
+        let client = client();
+        let token = session_token(&client);
+
+        let field = |name: &str, value: &str| format!(
+            "--X-BOUNDARY\r\nContent-Disposition: form-data; name=\"{}\"\r\n\r\n{}\r\n",
+            name, value
+        );
+
+        let body = [field("csrf_token", &token), field("amount", "7"), field("note", "gift")]
+            .concat() + "--X-BOUNDARY--\r\n";
+
+        let response = client.post("/transfer")
+            .header("multipart/form-data; boundary=X-BOUNDARY".parse::<ContentType>().unwrap())
+            .body(body)
+            .dispatch();
+
+        assert_eq!(response.into_string().unwrap(), "gift: 7");
+    }
+
+    #[test]
+    fn mismatched_token() {
+        let client = client();
+        let token = session_token(&client);
+
+        let forged = Header::new("X-CSRF-Token", "0".repeat(32));
+        assert_eq!(client.post("/").header(forged).dispatch().status(), Status::Forbidden);
+
+        let forged = Header::new("X-CSRF-Token", format!("{}x", token));
+        let response = client.delete("/").header(forged).dispatch();
+        assert_eq!(response.into_string().unwrap(), "CSRF token mismatch");
+
+        // A token from another session is rejected.
+        let other = self::client();
+        let response = other.delete("/").header(Header::new("X-CSRF-Token", token)).dispatch();
+        assert_eq!(response.into_string().unwrap(), "CSRF token mismatch");
+    }
+
+    #[test]
+    fn forged_session_cookie() {
+        let client = client();
+        let token = session_token(&client);
+
+        let response = client.delete("/")
+            .cookie(rocket::http::Cookie::new("_csrf", token.clone()))
+            .header(Header::new("X-CSRF-Token", token))
+            .dispatch();
+
+        assert_eq!(response.into_string().unwrap(), "CSRF token mismatch");
+    }
+
+    #[test]
+    fn safe_method_bypass() {
+        let client = client();
+        assert_eq!(client.get("/").dispatch().status(), Status::Ok);
+        assert_eq!(client.head("/").dispatch().status(), Status::Ok);
+        assert_eq!(client.options("/").dispatch().into_string().unwrap(), "options");
+
+        let response = client.get("/").header(Header::new("X-CSRF-Token", "bogus")).dispatch();
+        assert_eq!(response.status(), Status::Ok);
+    }
+}
@@ -0,0 +1,118 @@
+#![cfg(feature = "rust_decimal")]
+
+#[macro_use] extern crate rocket;
+
+use rocket::rust_decimal::Decimal;
+use rocket::request::{Money, DecimalParamError};
+
+#[post("/charge/<amount>")]
+fn charge(amount: Result<Money, DecimalParamError<'_>>) -> String {
+    match amount {
+        Ok(amount) => format!("charged {}", amount),
+        Err(e) => e.to_string(),
+    }
+}
+
+#[get("/rate/<rate>")]
+fn rate(rate: Decimal) -> String {
+    (rate * Decimal::from(3)).to_string()
+}
+
+mod decimal_param_tests {
+    use super::*;
+    use std::str::FromStr;
+
+    use rocket::http::Status;
+    use rocket::request::FromParam;
+    use rocket::local::blocking::Client;
+
+    fn decimal(s: &str) -> Decimal {
+        Decimal::from_str(s).unwrap()
+    }
+
+    #[test]
+    fn valid_amounts() {
+        for (param, expected) in [
+            ("0", "0.00"), ("5", "5.00"), ("5.0", "5.00"), ("19.99", "19.99"),
+            ("0.1", "0.10"), ("007.50", "7.50"), ("1000000000.01", "1000000000.01"),
+        ] {
+            let money = Money::from_param(param).unwrap();
+            assert_eq!(money.to_string(), expected, "{}", param);
+            assert_eq!(money.scale(), 2);
+        }
+
+        // No float rounding: 0.1 + 0.2 is exactly 0.3.
+        let sum = *Money::from_param("0.1").unwrap() + *Money::from_param("0.2").unwrap();
+        assert_eq!(sum, decimal("0.3"));
+        assert_eq!(Money::from_param("0.30").unwrap().into_inner(), sum);
+    }
+
+    #[test]
+    fn negative_amounts_are_rejected() {
+        assert_eq!(Money::from_param("-5"), Err(DecimalParamError::Negative("-5")));
+        assert_eq!(Money::from_param("-0.01"), Err(DecimalParamError::Negative("-0.01")));
+        assert_eq!(Money::from_param("-0"), Err(DecimalParamError::Negative("-0")));
+
+        // ...but are fine as a `Decimal`.
+        assert_eq!(Decimal::from_param("-5.125"), Ok(decimal("-5.125")));
+    }
+
+    #[test]
+    fn too_many_decimal_places() {
+        let error = DecimalParamError::TooManyDecimalPlaces { value: "1.001", places: 3, max: 2 };
+        assert_eq!(Money::from_param("1.001"), Err(error));
+        assert!(matches!(Money::from_param("0.000"),
+            Err(DecimalParamError::TooManyDecimalPlaces { places: 3, .. })));
+
+        // A negative amount with too many places is reported as the latter.
+        assert!(matches!(Money::from_param("-1.001"),
+            Err(DecimalParamError::TooManyDecimalPlaces { .. })));
+
+        // `Decimal` allows up to 28 places.
+        let places = format!("0.{}", "1".repeat(28));
+        assert_eq!(Decimal::from_param(&places), Ok(decimal(&places)));
+
+        let places = format!("0.{}", "1".repeat(29));
+        assert!(matches!(Decimal::from_param(&places),
+            Err(DecimalParamError::TooManyDecimalPlaces { places: 29, max: 28, .. })));
+    }
+
+    #[test]
+    fn exponents_and_non_numbers_are_rejected() {
+        for param in [
+            "1e3", "1E3", "1.5e2", "1e-2", "", "-", ".", "abc", "1.", ".5", "+5", "1,000",
+            "1_000", " 1", "1 ", "1.2.3", "0x10", "NaN", "inf", "١٢",
+        ] {
+            assert_eq!(Money::from_param(param), Err(DecimalParamError::NotANumber(param)));
+            assert_eq!(Decimal::from_param(param), Err(DecimalParamError::NotANumber(param)));
+        }
+    }
+
+    #[test]
+    fn out_of_range() {
+        let max = Decimal::MAX.to_string();
+        assert_eq!(Decimal::from_param(&max), Ok(Decimal::MAX));
+
+        let over = format!("{}0", max);
+        assert_eq!(Decimal::from_param(&over), Err(DecimalParamError::OutOfRange(&over)));
+        assert_eq!(Money::from_param(&over), Err(DecimalParamError::OutOfRange(&over)));
+    }
+
+    #[test]
+    fn routes() {
+        let client = Client::debug_with(routes![charge, rate]).unwrap();
+        let post = |uri: &str| client.post(uri.to_string()).dispatch().into_string().unwrap();
+
+        assert_eq!(post("/charge/12.5"), "charged 12.50");
+        assert_eq!(post("/charge/-12.5"), "negative amount `-12.5`");
+        assert_eq!(post("/charge/12.505"),
+            "`12.505` has 3 decimal places, more than the maximum of 2");
+        assert_eq!(post("/charge/1.2e1"), "invalid decimal number `1.2e1`");
+
+        let response = client.get("/rate/0.1").dispatch();
+        assert_eq!(response.into_string().unwrap(), "0.3");
+
+        let response = client.get("/rate/1e2").dispatch();
+        assert_eq!(response.status(), Status::NotFound);
+    }
+}
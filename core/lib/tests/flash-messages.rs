@@ -0,0 +1,146 @@
+#[macro_use] extern crate rocket;
+
+use rocket::request::{FlashMessage, FlashMessages};
+use rocket::response::{Flash, Flashes};
+
+#[post("/none")]
+fn none() -> Flashes<&'static str> {
+    Flashes::new("set")
+}
+
+#[post("/one")]
+fn one() -> Flashes<&'static str> {
+    Flashes::new("set").success("It worked!")
+}
+
+#[post("/several")]
+fn several() -> Flashes<&'static str> {
+    Flashes::new("set")
+        .success("Saved.")
+        .warning("2:3:ab~ 100:200:")
+        .warning("Ünïcödé ✨ 🚀")
+        .messages(vec![("", ""), ("7:tricky:", "::~~::")])
+}
+
+#[post("/single")]
+fn single() -> Flash<&'static str> {
+    Flash::error("set", "Whoops: 5:oh no")
+}
+
+#[get("/all")]
+fn all(flashes: FlashMessages<'_>) -> String {
+    flashes.iter()
+        .map(|flash| format!("[{}|{}]", flash.kind(), flash.message()))
+        .collect()
+}
+
+#[get("/count")]
+fn count(flashes: FlashMessages<'_>) -> String {
+    flashes.len().to_string()
+}
+
+#[get("/first")]
+fn first(flash: Option<FlashMessage<'_>>) -> String {
+    flash.map(|f| format!("[{}|{}]", f.kind(), f.message()))
+        .unwrap_or_else(|| "none".into())
+}
+
+mod flash_messages_tests {
+    use super::*;
+    use rocket::http::{Cookie, Status};
+    use rocket::local::blocking::Client;
+
+    fn client() -> Client {
+        // Cookies are injected by hand below, so they can't be signed.
+        let figment = rocket::Config::figment().merge(("unsigned_flash", true));
+        let rocket = rocket::custom(figment)
+            .mount("/", routes![none, one, several, single, all, count, first]);
+
+        Client::debug(rocket).unwrap()
+    }
+
+    fn get(client: &Client, uri: &str) -> String {
+        let response = client.get(uri).dispatch();
+        assert_eq!(response.status(), Status::Ok);
+        response.into_string().unwrap()
+    }
+
+    fn get_with(client: &Client, uri: &str, flash: &str) -> (Status, String) {
+        let response = client.get(uri).cookie(Cookie::new("_flash", flash)).dispatch();
+        (response.status(), response.into_string().unwrap_or_default())
+    }
+
+    #[test]
+    fn zero_messages() {
+        let client = client();
+        assert_eq!(get(&client, "/all"), "");
+
+        client.post("/none").dispatch();
+        assert!(client.cookies().get("_flash").is_none());
+        assert_eq!(get(&client, "/all"), "");
+        assert_eq!(get(&client, "/first"), "none");
+
+        // An explicitly empty cookie yields no messages.
+        assert_eq!(get_with(&client, "/count", "~"), (Status::Ok, "0".into()));
+        assert_eq!(get_with(&client, "/first", "~"), (Status::Ok, "none".into()));
+    }
+
+    #[test]
+    fn one_message() {
+        let client = client();
+        client.post("/one").dispatch();
+        assert_eq!(get(&client, "/all"), "[success|It worked!]");
+        assert_eq!(get(&client, "/all"), "");
+
+        client.post("/one").dispatch();
+        assert_eq!(get(&client, "/first"), "[success|It worked!]");
+        assert_eq!(get(&client, "/first"), "none");
+    }
+
+    #[test]
+    fn several_messages() {
+        let client = client();
+        client.post("/several").dispatch();
+
+        // Counting doesn't access any message, so the cookie is kept.
+        assert_eq!(get(&client, "/count"), "5");
+        assert_eq!(get(&client, "/all"),
+            "[success|Saved.][warning|2:3:ab~ 100:200:][warning|Ünïcödé ✨ 🚀]\
+            [|][7:tricky:|::~~::]");
+
+        assert_eq!(get(&client, "/count"), "0");
+
+        // A single-message guard sees the first message and clears the rest.
+        client.post("/several").dispatch();
+        assert_eq!(get(&client, "/first"), "[success|Saved.]");
+        assert_eq!(get(&client, "/count"), "0");
+    }
+
+    #[test]
+    fn single_message_cookie_compat() {
+        let client = client();
+        client.post("/single").dispatch();
+        assert_eq!(get(&client, "/all"), "[error|Whoops: 5:oh no]");
+
+        // A cookie as written by `Flash` before `Flashes` existed.
+        let (status, body) = get_with(&client, "/all", "7:successHello, world!");
+        assert_eq!((status, body.as_str()), (Status::Ok, "[success|Hello, world!]"));
+
+        let (status, body) = get_with(&client, "/all", "0:");
+        assert_eq!((status, body.as_str()), (Status::Ok, "[|]"));
+    }
+
+    #[test]
+    fn malformed_cookies() {
+        let client = client();
+        let bad = ["", "hello", "7:short", "-1:x", "+1:ab", "~1:", "~1:1:a",
+            "~1:1:ab1:1:a", "~x:1:ab", "~1:x:ab", "1:ü", "~1:4:üabc"];
+
+        for value in &bad {
+            let (status, _) = get_with(&client, "/count", value);
+            assert_eq!(status, Status::BadRequest, "{:?}", value);
+            let (_, body) = get_with(&client, "/first", value);
+            assert_eq!(body, "none", "{:?}", value);
+        }
+    }
+}
@@ -0,0 +1,124 @@
+#![cfg(feature = "secrets")]
+
+#[macro_use] extern crate rocket;
+
+use rocket::request::{FlashMessage, FlashMessages};
+use rocket::response::{Flash, Flashes};
+
+#[post("/one")]
+fn one() -> Flash<&'static str> {
+    Flash::success("set", "Saved.")
+}
+
+#[post("/many")]
+fn many() -> Flashes<&'static str> {
+    Flashes::new("set").success("Saved.").warning("Careful.")
+}
+
+#[get("/first")]
+fn first(flash: Option<FlashMessage<'_>>) -> String {
+    flash.map(|f| format!("[{}|{}]", f.kind(), f.message()))
+        .unwrap_or_else(|| "none".into())
+}
+
+#[get("/all")]
+fn all(flashes: FlashMessages<'_>) -> String {
+    flashes.iter()
+        .map(|flash| format!("[{}|{}]", flash.kind(), flash.message()))
+        .collect()
+}
+
+mod flash_signing_tests {
+    use super::*;
+    use rocket::http::Cookie;
+    use rocket::local::blocking::Client;
+
+    fn client(unsigned: bool) -> Client {
+        let figment = rocket::Config::figment().merge(("unsigned_flash", unsigned));
+        let rocket = rocket::custom(figment).mount("/", routes![one, many, first, all]);
+        Client::untracked(rocket).unwrap()
+    }
+
+    /// Sets a flash via `uri` and returns the flash cookie's raw value.
+    fn set_flash(client: &Client, uri: &str) -> String {
+        let response = client.post(uri).dispatch();
+        let cookie = response.cookies().get("_flash").expect("flash cookie");
+        cookie.value().to_string()
+    }
+
+    fn get_with(client: &Client, uri: &str, value: &str) -> (String, Option<String>) {
+        let response = client.get(uri).cookie(Cookie::new("_flash", value.to_string())).dispatch();
+        let removal = response.cookies().get("_flash").map(|c| c.value().to_string());
+        (response.into_string().unwrap(), removal)
+    }
+
+    fn flip_byte(value: &str, index: usize) -> String {
+        let mut bytes = value.as_bytes().to_vec();
+        bytes[index] = match bytes[index] {
+            b'A' => b'B',
+            _ => b'A',
+        };
+
+        String::from_utf8(bytes).unwrap()
+    }
+
+    #[test]
+    fn signed_cookie_round_trips() {
+        let client = client(false);
+        let value = set_flash(&client, "/one");
+        assert_ne!(value, "7:successSaved.");
+        assert!(value.ends_with("7:successSaved."));
+
+        assert_eq!(get_with(&client, "/first", &value).0, "[success|Saved.]");
+
+        let value = set_flash(&client, "/many");
+        assert_eq!(get_with(&client, "/all", &value).0, "[success|Saved.][warning|Careful.]");
+    }
+
+    #[test]
+    fn tampered_cookie_is_dropped() {
+        let client = client(false);
+        let value = set_flash(&client, "/one");
+
+        // Flip a byte in the signature, then in the payload.
+        for &index in &[0, 10, value.len() - 1] {
+            let tampered = flip_byte(&value, index);
+            let (body, removal) = get_with(&client, "/first", &tampered);
+            assert_eq!(body, "none", "{:?}", tampered);
+            assert_eq!(removal.as_deref(), Some(""), "{:?}", tampered);
+
+            let (body, removal) = get_with(&client, "/all", &tampered);
+            assert_eq!(body, "", "{:?}", tampered);
+            assert_eq!(removal.as_deref(), Some(""), "{:?}", tampered);
+        }
+
+        // Swapping in a different payload with the original signature fails.
+        let forged = value.replace("Saved.", "<script>");
+        assert_eq!(get_with(&client, "/first", &forged).0, "none");
+    }
+
+    #[test]
+    fn unsigned_cookie_is_dropped() {
+        let client = client(false);
+        let (body, removal) = get_with(&client, "/first", "7:successInjected!");
+        assert_eq!(body, "none");
+        assert_eq!(removal.as_deref(), Some(""));
+
+        assert_eq!(get_with(&client, "/all", "~7:5:successhello").0, "");
+    }
+
+    #[test]
+    fn unsigned_flash_opt_out() {
+        let client = client(true);
+        let value = set_flash(&client, "/one");
+        assert_eq!(value, "7:successSaved.");
+
+        let (body, removal) = get_with(&client, "/first", "7:successInjected!");
+        assert_eq!(body, "[success|Injected!]");
+        assert_eq!(removal.as_deref(), Some(""));
+
+        // A signed cookie isn't understood when signing is disabled.
+        let signed = set_flash(&self::client(false), "/one");
+        assert_eq!(get_with(&client, "/first", &signed).0, "none");
+    }
+}
@@ -0,0 +1,144 @@
+#[macro_use] extern crate rocket;
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use rocket::Request;
+use rocket::request::{self, FromRequest};
+
+/// Counts requests against a limit, once per request.
+#[derive(Default)]
+struct Hits(AtomicUsize);
+
+/// A guard that counts the request on the first dispatch only and records the
+/// forward count it observed.
+struct RateLimit(usize);
+
+#[rocket::async_trait]
+impl<'r> FromRequest<'r> for RateLimit {
+    type Error = ();
+
+    async fn from_request(req: &'r Request<'_>) -> request::Outcome<Self, ()> {
+        if req.forward_count() == 0 {
+            req.rocket().state::<Hits>().unwrap().0.fetch_add(1, Ordering::SeqCst);
+        }
+
+        request::Outcome::Success(RateLimit(req.forward_count()))
+    }
+}
+
+/// A guard that forwards unless `?pass` is present.
+struct Pass;
+
+#[rocket::async_trait]
+impl<'r> FromRequest<'r> for Pass {
+    type Error = ();
+
+    async fn from_request(req: &'r Request<'_>) -> request::Outcome<Self, ()> {
+        match req.query_value::<&str>("pass") {
+            Some(_) => request::Outcome::Success(Pass),
+            None => request::Outcome::Forward(()),
+        }
+    }
+}
+
+#[get("/item", rank = 1)]
+fn first(limit: RateLimit, _pass: Pass) -> String {
+    format!("first {}", limit.0)
+}
+
+#[get("/item", rank = 2)]
+fn second(limit: RateLimit) -> String {
+    format!("second {}", limit.0)
+}
+
+#[get("/<id>", rank = 3)]
+fn by_id(limit: RateLimit, id: usize) -> String {
+    format!("id {} {}", id, limit.0)
+}
+
+#[get("/<name>", rank = 4)]
+fn by_name(limit: RateLimit, name: &str, _pass: Pass) -> String {
+    format!("name {} {}", name, limit.0)
+}
+
+#[catch(404)]
+fn not_found(req: &Request<'_>) -> String {
+    format!("not found {}", req.forward_count())
+}
+
+mod forward_count_tests {
+    use super::*;
+    use rocket::http::Status;
+    use rocket::fairing::AdHoc;
+    use rocket::local::blocking::Client;
+
+    fn client() -> Client {
+        let rocket = rocket::build()
+            .manage(Hits::default())
+            .mount("/", routes![first, second, by_id, by_name])
+            .register("/", catchers![not_found])
+            .attach(AdHoc::on_response("Forward Count", |req, res| Box::pin(async move {
+                res.set_raw_header("X-Forward-Count", req.forward_count().to_string());
+            })));
+
+        Client::debug(rocket).unwrap()
+    }
+
+    fn hits(client: &Client) -> usize {
+        client.rocket().state::<Hits>().unwrap().0.load(Ordering::SeqCst)
+    }
+
+    #[test]
+    fn initial_dispatch_has_no_forwards() {
+        let client = client();
+        assert_eq!(client.get("/item?pass").dispatch().into_string().unwrap(), "first 0");
+        assert_eq!(client.get("/7").dispatch().into_string().unwrap(), "id 7 0");
+        assert_eq!(hits(&client), 2);
+    }
+
+    #[test]
+    fn forward_is_observed_by_next_route() {
+        let client = client();
+        assert_eq!(client.get("/item").dispatch().into_string().unwrap(), "second 1");
+        assert_eq!(hits(&client), 1);
+
+        // `/<id>` forwards since `abc` isn't a `usize`.
+        assert_eq!(client.get("/abc?pass").dispatch().into_string().unwrap(), "name abc 1");
+        assert_eq!(hits(&client), 2);
+    }
+
+    #[test]
+    fn count_is_per_request() {
+        let client = client();
+        for _ in 0..3 {
+            assert_eq!(client.get("/item").dispatch().into_string().unwrap(), "second 1");
+        }
+
+        assert_eq!(hits(&client), 3);
+    }
+
+    #[test]
+    fn catchers_see_all_forwards() {
+        let client = client();
+        let response = client.get("/abc").dispatch();
+        assert_eq!(response.status(), Status::NotFound);
+        assert_eq!(response.into_string().unwrap(), "not found 2");
+
+        let response = client.get("/a/b").dispatch();
+        assert_eq!(response.into_string().unwrap(), "not found 0");
+        assert_eq!(hits(&client), 1);
+    }
+
+    #[test]
+    fn head_requests_count_get_forwards() {
+        let client = client();
+        let response = client.head("/item").dispatch();
+        assert_eq!(response.status(), Status::Ok);
+        assert_eq!(response.headers().get_one("X-Forward-Count"), Some("1"));
+        assert_eq!(hits(&client), 1);
+
+        let response = client.head("/item?pass").dispatch();
+        assert_eq!(response.headers().get_one("X-Forward-Count"), Some("0"));
+        assert_eq!(hits(&client), 2);
+    }
+}
@@ -0,0 +1,104 @@
+#[macro_use] extern crate rocket;
+
+use rocket::request::{Forwarded, ForwardedNode};
+
+#[get("/")]
+fn hops(forwarded: Forwarded) -> String {
+    let node = |n: &Option<ForwardedNode>| n.as_ref().map(|n| n.to_string());
+    forwarded.iter()
+        .map(|hop| format!("{:?} {:?} {:?} {:?}",
+            node(&hop.for_node), node(&hop.by_node), hop.host, hop.proto))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+mod forwarded_header_tests {
+    use super::*;
+    use std::net::IpAddr;
+    use rocket::http::Header;
+    use rocket::local::blocking::Client;
+
+    fn node(s: &str) -> Option<ForwardedNode> {
+        Forwarded::parse(&format!("for={}", s)).first().and_then(|hop| hop.for_node.clone())
+    }
+
+    fn addr(ip: &str, port: Option<u16>) -> Option<ForwardedNode> {
+        Some(ForwardedNode::Addr(ip.parse::<IpAddr>().unwrap(), port))
+    }
+
+    #[test]
+    fn quoted_ipv6() {
+        assert_eq!(node(r#""[2001:db8::1]:8080""#), addr("2001:db8::1", Some(8080)));
+        assert_eq!(node(r#""[2001:db8::1]""#), addr("2001:db8::1", None));
+        assert_eq!(node(r#""[2001:db8::1]:_port""#), addr("2001:db8::1", None));
+        assert_eq!(node(r#""192.0.2.43:47011""#), addr("192.0.2.43", Some(47011)));
+        assert_eq!(node("192.0.2.43"), addr("192.0.2.43", None));
+        assert_eq!(node(r#""\[::1\]""#), addr("::1", None));
+
+        // Unbracketed IPv6 addresses and bad ports are malformed.
+        assert_eq!(node(r#""2001:db8::1""#), None);
+        assert_eq!(node(r#""[2001:db8::1]8080""#), None);
+        assert_eq!(node(r#""192.0.2.43:99999""#), None);
+        assert_eq!(node(r#""[1.2.3.4]""#), None);
+    }
+
+    #[test]
+    fn obfuscated_identifiers() {
+        assert_eq!(node("unknown"), Some(ForwardedNode::Unknown));
+        assert_eq!(node("UNKNOWN"), Some(ForwardedNode::Unknown));
+        assert_eq!(node("_hidden"), Some(ForwardedNode::Obfuscated("_hidden".into())));
+        let obfuscated = Some(ForwardedNode::Obfuscated("_SEVKISEK".into()));
+        assert_eq!(node(r#""_SEVKISEK:_port""#), obfuscated);
+        assert_eq!(node("_"), None);
+        assert_eq!(node("_a/b"), None);
+        assert_eq!(node("hidden"), None);
+    }
+
+    #[test]
+    fn multiple_hops() {
+        let client = Client::debug_with(routes![hops]).unwrap();
+        let response = client.get("/")
+            .header(Header::new("Forwarded",
+                r#"for="[2001:db8:cafe::17]:4711";proto=https;host=example.com"#))
+            .header(Header::new("Forwarded", "For=192.0.2.60;By=203.0.113.43, for=_gw;by=unknown"))
+            .dispatch();
+
+        assert_eq!(response.into_string().unwrap(), [
+            r#"Some("[2001:db8:cafe::17]:4711") None Some("example.com") Some("https")"#,
+            r#"Some("192.0.2.60") Some("203.0.113.43") None None"#,
+            r#"Some("_gw") Some("unknown") None None"#,
+        ].join("\n"));
+
+        assert_eq!(client.get("/").dispatch().into_string().unwrap(), "");
+    }
+
+    #[test]
+    fn quoted_values_and_delimiters() {
+        let forwarded = Forwarded::parse(r#"host="a,b;c";for=1.2.3.4, proto="ht\"tp""#);
+        assert_eq!(forwarded.len(), 2);
+        assert_eq!(forwarded[0].host.as_deref(), Some("a,b;c"));
+        assert_eq!(forwarded[1].proto.as_deref(), Some("ht\"tp"));
+
+        // Unknown parameters are ignored; a trailing `;` is allowed.
+        let forwarded = Forwarded::parse("for=1.2.3.4;secret=abc;");
+        assert_eq!(forwarded.hops().len(), 1);
+    }
+
+    #[test]
+    fn malformed_hops_are_skipped() {
+        let forwarded = Forwarded::parse(concat!(
+            "for=1.1.1.1, for=nonsense, for=2.2.2.2;for=3.3.3.3, proto, ",
+            "host=\"unterminated, for=4.4.4.4"
+        ));
+
+        let ips: Vec<_> = forwarded.iter()
+            .map(|hop| hop.for_node.as_ref().and_then(|n| n.ip()).unwrap().to_string())
+            .collect();
+
+        assert_eq!(ips, ["1.1.1.1"]);
+
+        let forwarded = Forwarded::parse("for=1.1.1.1, ,for=_x, by=\"\", host=a b, for=5.5.5.5");
+        assert_eq!(forwarded.len(), 3);
+        assert_eq!(forwarded.into_inner()[2].for_node, addr("5.5.5.5", None));
+    }
+}
@@ -0,0 +1,124 @@
+#[macro_use] extern crate rocket;
+
+use rocket::request::{Bounded, Radix, IntParamError};
+
+#[get("/page/<n>")]
+fn page(n: Bounded<u32, 1, 100>) -> String {
+    format!("page {}", *n)
+}
+
+#[get("/temp/<t>")]
+fn temp(t: Result<Bounded<i8, -40, 50>, IntParamError<'_>>) -> String {
+    match t {
+        Ok(t) => t.into_inner().to_string(),
+        Err(e) => e.to_string(),
+    }
+}
+
+#[get("/hex/<n>")]
+fn hex(n: Radix<u32, 16>) -> String {
+    n.to_string()
+}
+
+#[get("/oct/<n>")]
+fn oct(n: Result<Radix<i16, 8>, IntParamError<'_>>) -> String {
+    match n {
+        Ok(n) => n.to_string(),
+        Err(e) => e.to_string(),
+    }
+}
+
+mod from_param_int_wrappers_tests {
+    use super::*;
+    use rocket::http::Status;
+    use rocket::local::blocking::Client;
+    use rocket::request::FromParam;
+
+    fn client() -> Client {
+        Client::debug_with(routes![page, temp, hex, oct]).unwrap()
+    }
+
+    fn get(client: &Client, uri: &str) -> Option<String> {
+        let response = client.get(uri).dispatch();
+        match response.status() {
+            status if status == Status::Ok => response.into_string(),
+            status if status == Status::NotFound => None,
+            status => panic!("{}: unexpected status {}", uri, status),
+        }
+    }
+
+    #[test]
+    fn bounded_boundaries() {
+        let client = client();
+        assert_eq!(get(&client, "/page/1").unwrap(), "page 1");
+        assert_eq!(get(&client, "/page/100").unwrap(), "page 100");
+        assert_eq!(get(&client, "/page/42").unwrap(), "page 42");
+        assert_eq!(get(&client, "/page/0"), None);
+        assert_eq!(get(&client, "/page/101"), None);
+        assert_eq!(get(&client, "/page/4294967295"), None);
+
+        assert_eq!(get(&client, "/temp/-40").unwrap(), "-40");
+        assert_eq!(get(&client, "/temp/50").unwrap(), "50");
+        assert_eq!(get(&client, "/temp/-41").unwrap(),
+            "value -41 is less than the minimum of -40");
+        assert_eq!(get(&client, "/temp/51").unwrap(),
+            "value 51 is greater than the maximum of 50");
+    }
+
+    #[test]
+    fn bounded_errors() {
+        type Page = Bounded<u8, 1, 100>;
+
+        assert_eq!(Page::from_param("0"), Err(IntParamError::TooSmall { value: 0, min: 1 }));
+        assert_eq!(Page::from_param("255"), Err(IntParamError::TooLarge { value: 255, max: 100 }));
+
+        // Values that don't fit the integer type fail to parse.
+        for bad in &["256", "-1", "", "1.0", "0x10", " 1", "ten"] {
+            assert!(matches!(Page::from_param(bad), Err(IntParamError::Parse(s, _)) if s == *bad));
+        }
+
+        let client = client();
+        assert!(get(&client, "/temp/128").unwrap().starts_with("invalid integer `128`"));
+        assert!(get(&client, "/temp/abc").unwrap().starts_with("invalid integer `abc`"));
+
+        // Extreme bounds.
+        type Any = Bounded<i64, { i64::MIN as i128 }, { i64::MAX as i128 }>;
+        assert_eq!(*Any::from_param("-9223372036854775808").unwrap(), i64::MIN);
+        assert_eq!(*Any::from_param("9223372036854775807").unwrap(), i64::MAX);
+
+        type One = Bounded<usize, 7, 7>;
+        assert_eq!(*One::from_param("7").unwrap(), 7);
+        assert!(One::from_param("6").is_err());
+        assert!(One::from_param("8").is_err());
+    }
+
+    #[test]
+    fn hex_params() {
+        let client = client();
+        assert_eq!(get(&client, "/hex/ff").unwrap(), "255");
+        assert_eq!(get(&client, "/hex/FF").unwrap(), "255");
+        assert_eq!(get(&client, "/hex/0").unwrap(), "0");
+        assert_eq!(get(&client, "/hex/ffffffff").unwrap(), "4294967295");
+        assert_eq!(get(&client, "/hex/100000000"), None);
+        assert_eq!(get(&client, "/hex/0xff"), None);
+        assert_eq!(get(&client, "/hex/fg"), None);
+        assert_eq!(get(&client, "/hex/-1"), None);
+
+        assert_eq!(*Radix::<u8, 16>::from_param("7f").unwrap(), 0x7f);
+        assert_eq!(*Radix::<i64, 16>::from_param("-DEADbeef").unwrap(), -0xdeadbeef);
+    }
+
+    #[test]
+    fn octal_params() {
+        let client = client();
+        assert_eq!(get(&client, "/oct/755").unwrap(), "493");
+        assert_eq!(get(&client, "/oct/-17").unwrap(), "-15");
+        assert_eq!(get(&client, "/oct/77777").unwrap(), "32767");
+        assert!(get(&client, "/oct/8").unwrap().starts_with("invalid integer `8`"));
+        assert!(get(&client, "/oct/100000").unwrap().starts_with("invalid integer `100000`"));
+
+        assert_eq!(*Radix::<u8, 2>::from_param("11111111").unwrap(), 255);
+        assert_eq!(*Radix::<u32, 36>::from_param("zz").unwrap(), 36 * 36 - 1);
+        assert!(Radix::<u8, 2>::from_param("2").is_err());
+    }
+}
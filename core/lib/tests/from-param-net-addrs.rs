@@ -0,0 +1,110 @@
+#[macro_use] extern crate rocket;
+
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr};
+
+#[get("/ip/<addr>")]
+fn ip(addr: IpAddr) -> String {
+    addr.to_string()
+}
+
+#[get("/v4/<addr>")]
+fn v4(addr: Ipv4Addr) -> String {
+    addr.to_string()
+}
+
+#[get("/v6/<addr>")]
+fn v6(addr: Ipv6Addr) -> String {
+    addr.to_string()
+}
+
+#[get("/peer/<addr>")]
+fn peer(addr: SocketAddr) -> String {
+    addr.to_string()
+}
+
+#[get("/lenient/<addr>")]
+fn lenient(addr: Result<SocketAddr, &str>) -> String {
+    match addr {
+        Ok(addr) => addr.to_string(),
+        Err(segment) => format!("bad: {}", segment),
+    }
+}
+
+mod from_param_net_addrs_tests {
+    use super::*;
+    use rocket::http::Status;
+    use rocket::local::blocking::Client;
+    use rocket::request::FromParam;
+
+    fn client() -> Client {
+        Client::debug_with(routes![ip, v4, v6, peer, lenient]).unwrap()
+    }
+
+    fn assert_ok(client: &Client, uri: &str, expected: &str) {
+        let response = client.get(uri).dispatch();
+        assert_eq!(response.status(), Status::Ok, "{}", uri);
+        assert_eq!(response.into_string().unwrap(), expected);
+    }
+
+    fn assert_forwarded(client: &Client, uri: &str) {
+        let response = client.get(uri).dispatch();
+        assert_eq!(response.status(), Status::NotFound, "{}", uri);
+    }
+
+    #[test]
+    fn valid_ip_addrs() {
+        let client = client();
+        assert_ok(&client, "/ip/127.0.0.1", "127.0.0.1");
+        assert_ok(&client, "/ip/::1", "::1");
+        assert_ok(&client, "/ip/fe80::1:2", "fe80::1:2");
+        assert_ok(&client, "/v4/10.0.0.255", "10.0.0.255");
+        assert_ok(&client, "/v6/2001:db8::ff00:42:8329", "2001:db8::ff00:42:8329");
+    }
+
+    #[test]
+    fn valid_socket_addrs() {
+        let client = client();
+        assert_ok(&client, "/peer/127.0.0.1:8000", "127.0.0.1:8000");
+        assert_ok(&client, "/peer/[::1]:8080", "[::1]:8080");
+        assert_ok(&client, "/peer/%5B::1%5D:8080", "[::1]:8080");
+        assert_ok(&client, "/peer/[2001:db8::1]:443", "[2001:db8::1]:443");
+    }
+
+    #[test]
+    fn mismatched_families_forward() {
+        let client = client();
+        assert_forwarded(&client, "/v4/::1");
+        assert_forwarded(&client, "/v6/127.0.0.1");
+    }
+
+    #[test]
+    fn missing_ports_forward() {
+        let client = client();
+        assert_forwarded(&client, "/peer/127.0.0.1");
+        assert_forwarded(&client, "/peer/127.0.0.1:");
+        assert_forwarded(&client, "/peer/[::1]");
+        assert_forwarded(&client, "/peer/::1:8080");
+    }
+
+    #[test]
+    fn garbage_forwards() {
+        let client = client();
+        for uri in &["/ip/hello", "/ip/256.0.0.1", "/ip/1.2.3", "/ip/%00",
+                     "/v6/:::", "/peer/[::1]:99999", "/peer/a:b", "/peer/%FF"]
+        {
+            assert_forwarded(&client, uri);
+        }
+    }
+
+    #[test]
+    fn error_carries_segment() {
+        let client = client();
+        assert_ok(&client, "/lenient/[::1]:80", "[::1]:80");
+        assert_ok(&client, "/lenient/localhost:80", "bad: localhost:80");
+        assert_ok(&client, "/lenient/1.2.3.4", "bad: 1.2.3.4");
+
+        assert_eq!(IpAddr::from_param("nope"), Err("nope"));
+        assert_eq!(Ipv4Addr::from_param("::1"), Err("::1"));
+        assert_eq!(SocketAddr::from_param("[::1]"), Err("[::1]"));
+    }
+}
@@ -0,0 +1,109 @@
+#[macro_use] extern crate rocket;
+
+use rocket::request::{Glob, GlobError};
+
+const FILES: &[&str] = &["notes.txt", "todo.txt", "photo.png", "a.md", "ab.md", "[draft].txt"];
+
+#[get("/files/<pattern>")]
+fn files(pattern: Result<Glob, GlobError>) -> String {
+    match pattern {
+        Ok(glob) => FILES.iter().filter(|f| glob.matches(f)).cloned().collect::<Vec<_>>().join(","),
+        Err(e) => e.to_string(),
+    }
+}
+
+#[get("/strict/<pattern>")]
+fn strict(pattern: Glob) -> String {
+    pattern.to_string()
+}
+
+mod glob_param_tests {
+    use super::*;
+    use rocket::http::Status;
+    use rocket::local::blocking::Client;
+
+    fn glob(pattern: &str) -> Glob {
+        Glob::new(pattern).unwrap()
+    }
+
+    #[test]
+    fn star() {
+        let g = glob("*.txt");
+        assert!(g.matches("notes.txt"));
+        assert!(g.matches(".txt"));
+        assert!(!g.matches("notes.txt.bak"));
+        assert!(!g.matches("notes.TXT"));
+
+        let g = glob("a*b*c");
+        assert!(g.matches("abc"));
+        assert!(g.matches("aXbYbZc"));
+        assert!(g.matches("abcbc"));
+        assert!(!g.matches("acb"));
+
+        assert!(glob("*").matches(""));
+        assert!(glob("**").matches("anything"));
+        assert!(!glob("").matches("x"));
+        assert!(glob("").matches(""));
+    }
+
+    #[test]
+    fn question_mark() {
+        let g = glob("?.md");
+        assert!(g.matches("a.md"));
+        assert!(!g.matches("ab.md"));
+        assert!(!g.matches(".md"));
+
+        // `?` matches a single character, not a single byte.
+        assert!(glob("caf?").matches("café"));
+        assert!(glob("*?").matches("é"));
+        assert!(!glob("*?").matches(""));
+    }
+
+    #[test]
+    fn character_classes() {
+        let g = glob("file[0-9][a-c].log");
+        assert!(g.matches("file1a.log"));
+        assert!(g.matches("file9c.log"));
+        assert!(!g.matches("file1d.log"));
+        assert!(!g.matches("fileXa.log"));
+
+        let g = glob("[!aeiou]*");
+        assert!(g.matches("rocket"));
+        assert!(!g.matches("apple"));
+        assert!(glob("[^aeiou]").matches("x"));
+
+        // Literal `]`, `-`, and glob metacharacters inside a class.
+        assert!(glob("[]]").matches("]"));
+        assert!(glob("[!]]").matches("a"));
+        assert!(glob("[a-]").matches("-"));
+        assert!(glob("[-a]").matches("-"));
+        assert!(glob("[*?[]").matches("*"));
+        assert!(glob("[*?[]").matches("["));
+        assert!(!glob("[*?[]").matches("a"));
+    }
+
+    #[test]
+    fn malformed_classes() {
+        assert_eq!(Glob::new("[a-z"), Err(GlobError::UnclosedClass(0)));
+        assert_eq!(Glob::new("*.[ch"), Err(GlobError::UnclosedClass(2)));
+        assert_eq!(Glob::new("[]"), Err(GlobError::UnclosedClass(0)));
+        assert_eq!(Glob::new("[!]"), Err(GlobError::UnclosedClass(0)));
+        assert_eq!(Glob::new("[z-a]"), Err(GlobError::InvalidRange('z', 'a')));
+    }
+
+    #[test]
+    fn param_is_decoded_and_compiled() {
+        let client = Client::debug_with(routes![files, strict]).unwrap();
+        let get = |uri: &str| client.get(uri.to_string()).dispatch().into_string().unwrap();
+
+        assert_eq!(get("/files/*.txt"), "notes.txt,todo.txt,[draft].txt");
+        assert_eq!(get("/files/%3F.md"), "a.md");
+        assert_eq!(get("/files/%5B%5B%5D*"), "[draft].txt");
+        assert_eq!(get("/files/%5Bnp%5D*"), "notes.txt,photo.png");
+        assert_eq!(get("/files/%5Bnp"), "unclosed character class at byte 0");
+
+        assert_eq!(get("/strict/*.t%5Bx%5Dt"), "*.t[x]t");
+        let response = client.get("/strict/%5Bz-a%5D").dispatch();
+        assert_eq!(response.status(), Status::NotFound);
+    }
+}
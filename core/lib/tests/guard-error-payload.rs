@@ -0,0 +1,125 @@
+#[macro_use] extern crate rocket;
+
+use rocket::Request;
+use rocket::http::Status;
+use rocket::request::{self, FromRequest};
+
+/// Why `Quota` rejected a request.
+#[derive(Debug, Clone, PartialEq)]
+struct QuotaExceeded {
+    used: u32,
+    limit: u32,
+}
+
+struct Quota(u32);
+
+#[rocket::async_trait]
+impl<'r> FromRequest<'r> for Quota {
+    type Error = QuotaExceeded;
+
+    async fn from_request(req: &'r Request<'_>) -> request::Outcome<Self, Self::Error> {
+        let used = req.headers().get_one("X-Used").and_then(|u| u.parse().ok()).unwrap_or(0);
+        if used >= 10 {
+            let payload = QuotaExceeded { used, limit: 10 };
+            return request::Outcome::error_with(req, Status::TooManyRequests, payload);
+        }
+
+        request::Outcome::Success(Quota(10 - used))
+    }
+}
+
+/// A second failing guard with the same payload type.
+struct Strict;
+
+#[rocket::async_trait]
+impl<'r> FromRequest<'r> for Strict {
+    type Error = QuotaExceeded;
+
+    async fn from_request(req: &'r Request<'_>) -> request::Outcome<Self, Self::Error> {
+        let payload = QuotaExceeded { used: 0, limit: 0 };
+        request::Outcome::error_with(req, Status::TooManyRequests, payload)
+    }
+}
+
+#[get("/")]
+fn index(quota: Quota) -> String {
+    format!("{} left", quota.0)
+}
+
+#[get("/both")]
+fn both(_quota: Quota, _strict: Strict) { }
+
+#[get("/result")]
+fn result(quota: Result<Quota, QuotaExceeded>) -> String {
+    format!("{:?}", quota.map(|q| q.0))
+}
+
+#[catch(429)]
+fn too_many(req: &Request<'_>) -> String {
+    match req.local_cache_get::<QuotaExceeded>() {
+        Some(e) => format!("quota exceeded: {}/{}", e.used, e.limit),
+        None => "quota exceeded".into(),
+    }
+}
+
+#[get("/plain")]
+fn plain() -> Status {
+    Status::TooManyRequests
+}
+
+mod guard_error_payload_tests {
+    use super::*;
+    use rocket::http::Header;
+    use rocket::local::blocking::Client;
+
+    fn client() -> Client {
+        let rocket = rocket::build()
+            .mount("/", routes![index, both, result, plain])
+            .register("/", catchers![too_many]);
+
+        Client::debug(rocket).unwrap()
+    }
+
+    fn get(client: &Client, uri: &str, used: &str) -> (Status, String) {
+        let response = client.get(uri.to_string()).header(Header::new("X-Used", used.to_string()));
+        let response = response.dispatch();
+        (response.status(), response.into_string().unwrap())
+    }
+
+    #[test]
+    fn success_stores_nothing() {
+        let client = client();
+        assert_eq!(get(&client, "/", "3"), (Status::Ok, "7 left".into()));
+    }
+
+    #[test]
+    fn catcher_recovers_payload() {
+        let client = client();
+        let expected = (Status::TooManyRequests, "quota exceeded: 12/10".into());
+        assert_eq!(get(&client, "/", "12"), expected);
+    }
+
+    #[test]
+    fn catcher_without_payload() {
+        let client = client();
+        let expected = (Status::TooManyRequests, "quota exceeded".into());
+        assert_eq!(get(&client, "/plain", "12"), expected);
+    }
+
+    #[test]
+    fn first_payload_wins() {
+        let client = client();
+        let expected = (Status::TooManyRequests, "quota exceeded: 0/0".into());
+        assert_eq!(get(&client, "/both", "3"), expected);
+
+        let expected = (Status::TooManyRequests, "quota exceeded: 15/10".into());
+        assert_eq!(get(&client, "/both", "15"), expected);
+    }
+
+    #[test]
+    fn payload_still_reaches_handlers() {
+        let client = client();
+        let expected = (Status::Ok, "Err(QuotaExceeded { used: 11, limit: 10 })".into());
+        assert_eq!(get(&client, "/result", "11"), expected);
+    }
+}
@@ -0,0 +1,135 @@
+#[macro_use] extern crate rocket;
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::Duration;
+
+use rocket::{Request, State};
+use rocket::http::Status;
+use rocket::request::{self, FromRequest, WithTimeout, TimeoutError};
+use rocket::tokio::time::sleep;
+
+/// Counts how many slow lookups ran to completion.
+#[derive(Default)]
+struct Completed(AtomicUsize);
+
+/// A session computed by a lookup that takes `X-Delay` milliseconds.
+#[derive(Debug, Clone, PartialEq)]
+struct Session(u64);
+
+#[rocket::async_trait]
+impl<'r> FromRequest<'r> for &'r Session {
+    type Error = &'static str;
+
+    async fn from_request(req: &'r Request<'_>) -> request::Outcome<Self, Self::Error> {
+        let delay = match req.headers().get_one("X-Delay").and_then(|d| d.parse().ok()) {
+            Some(delay) => delay,
+            None => return request::Outcome::Failure((Status::Unauthorized, "no delay")),
+        };
+
+        let session = req.local_cache_async(async {
+            sleep(Duration::from_millis(delay)).await;
+            req.rocket().state::<Completed>().unwrap().0.fetch_add(1, Ordering::SeqCst);
+            Session(delay)
+        }).await;
+
+        request::Outcome::Success(session)
+    }
+}
+
+/// Reports whether a `Session` is in the request-local cache.
+struct Cached(Option<Session>);
+
+#[rocket::async_trait]
+impl<'r> FromRequest<'r> for Cached {
+    type Error = ();
+
+    async fn from_request(req: &'r Request<'_>) -> request::Outcome<Self, ()> {
+        request::Outcome::Success(Cached(req.local_cache_get::<Session>().cloned()))
+    }
+}
+
+#[get("/")]
+fn guarded(session: WithTimeout<&Session, 100>) -> String {
+    format!("session {}", session.into_inner().0)
+}
+
+#[get("/result")]
+fn result(
+    session: Result<WithTimeout<&Session, 100>, TimeoutError<&'static str>>,
+    cached: Cached,
+) -> String {
+    format!("{:?} {:?}", session.map(|s| s.into_inner().0), cached.0)
+}
+
+#[get("/completed")]
+fn completed(count: &State<Completed>) -> String {
+    count.0.load(Ordering::SeqCst).to_string()
+}
+
+mod guard_timeout_tests {
+    use super::*;
+    use rocket::http::Header;
+    use rocket::local::asynchronous::Client;
+
+    async fn client() -> Client {
+        let rocket = rocket::build()
+            .manage(Completed::default())
+            .mount("/", routes![guarded, result, completed]);
+
+        Client::debug(rocket).await.unwrap()
+    }
+
+    async fn completed_count(client: &Client) -> String {
+        client.get("/completed").dispatch().await.into_string().await.unwrap()
+    }
+
+    #[rocket::async_test]
+    async fn fast_path_passes_through() {
+        let client = client().await;
+        let response = client.get("/").header(Header::new("X-Delay", "5")).dispatch().await;
+        assert_eq!(response.status(), Status::Ok);
+        assert_eq!(response.into_string().await.unwrap(), "session 5");
+        assert_eq!(completed_count(&client).await, "1");
+    }
+
+    #[rocket::async_test]
+    async fn timeout_path_fires() {
+        let client = client().await;
+        let start = std::time::Instant::now();
+        let response = client.get("/").header(Header::new("X-Delay", "5000")).dispatch().await;
+        assert_eq!(response.status(), Status::GatewayTimeout);
+        assert!(start.elapsed() < Duration::from_millis(2500));
+
+        // The slow lookup was cancelled, not left running in the background.
+        sleep(Duration::from_millis(200)).await;
+        assert_eq!(completed_count(&client).await, "0");
+    }
+
+    #[rocket::async_test]
+    async fn cancellation_leaves_cache_empty() {
+        let client = client().await;
+        let response = client.get("/result").header(Header::new("X-Delay", "5000")).dispatch().await;
+        assert_eq!(response.into_string().await.unwrap(), "Err(Elapsed(100ms)) None");
+
+        let response = client.get("/result").header(Header::new("X-Delay", "1")).dispatch().await;
+        assert_eq!(response.into_string().await.unwrap(), "Ok(1) Some(Session(1))");
+    }
+
+    #[rocket::async_test]
+    async fn inner_failure_is_preserved() {
+        let client = client().await;
+        let response = client.get("/").dispatch().await;
+        assert_eq!(response.status(), Status::Unauthorized);
+
+        let response = client.get("/result").dispatch().await;
+        assert_eq!(response.into_string().await.unwrap(), "Err(Guard(\"no delay\")) None");
+    }
+
+    #[test]
+    fn error_display() {
+        let error: TimeoutError<()> = TimeoutError::Elapsed(Duration::from_millis(250));
+        assert_eq!(error.to_string(), "guard timed out after 250ms");
+        assert_eq!(TimeoutError::Guard("bad").to_string(), "guard failed: \"bad\"");
+        assert_eq!(WithTimeout::<(), 1500>::TIMEOUT, Duration::from_millis(1500));
+    }
+}
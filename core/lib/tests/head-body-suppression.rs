@@ -0,0 +1,126 @@
+#[macro_use] extern crate rocket;
+
+use std::io::Cursor;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use rocket::{Request, Response};
+use rocket::request::{self, FromRequest};
+use rocket::response::{self, Responder};
+use rocket::response::stream::TextStream;
+
+#[get("/sized")]
+fn sized() -> &'static str {
+    "Hello, world!"
+}
+
+#[get("/streamed")]
+fn streamed() -> TextStream![&'static str] {
+    TextStream! {
+        yield "Hello, ";
+        yield "world!";
+    }
+}
+
+static GENERATED: AtomicUsize = AtomicUsize::new(0);
+
+/// A responder that skips generating its body when it would be stripped.
+struct Report;
+
+impl Report {
+    const LEN: usize = 11;
+
+    fn generate() -> Vec<u8> {
+        GENERATED.fetch_add(1, Ordering::SeqCst);
+        b"report body".to_vec()
+    }
+}
+
+impl<'r> Responder<'r, 'static> for Report {
+    fn respond_to(self, req: &'r Request<'_>) -> response::Result<'static> {
+        let body = if req.strips_body() { vec![] } else { Report::generate() };
+        Response::build()
+            .raw_header("X-Report", "1")
+            .sized_body(Report::LEN, Cursor::new(body))
+            .ok()
+    }
+}
+
+#[get("/report")]
+fn report() -> Report {
+    Report
+}
+
+/// The method a route sees and whether the body will be stripped.
+struct Stripping(String);
+
+#[rocket::async_trait]
+impl<'r> FromRequest<'r> for Stripping {
+    type Error = ();
+
+    async fn from_request(req: &'r Request<'_>) -> request::Outcome<Self, ()> {
+        request::Outcome::Success(Stripping(format!("{} {}", req.method(), req.strips_body())))
+    }
+}
+
+#[get("/method")]
+fn method(stripping: Stripping) -> String {
+    stripping.0
+}
+
+mod head_body_suppression_tests {
+    use super::*;
+    use rocket::local::blocking::{Client, LocalResponse};
+
+    fn client() -> Client {
+        Client::debug_with(routes![sized, streamed, report, method]).unwrap()
+    }
+
+    fn headers(response: &LocalResponse<'_>) -> Vec<String> {
+        response.headers().iter().map(|h| h.to_string()).collect()
+    }
+
+    #[test]
+    fn head_keeps_headers_and_size_of_get() {
+        let client = client();
+        for uri in ["/sized", "/streamed", "/report"] {
+            let get = client.get(uri).dispatch();
+            let head = client.head(uri).dispatch();
+
+            assert_eq!(head.status(), get.status(), "{}", uri);
+            assert_eq!(headers(&head), headers(&get), "{}", uri);
+            assert_eq!(head.body().preset_size(), get.body().preset_size(), "{}", uri);
+            assert!(head.into_bytes().unwrap_or_default().is_empty(), "{}", uri);
+            assert!(!get.into_bytes().unwrap().is_empty(), "{}", uri);
+        }
+    }
+
+    #[test]
+    fn sizes_match_get() {
+        let client = client();
+        assert_eq!(client.head("/sized").dispatch().body().preset_size(), Some(13));
+        assert_eq!(client.head("/report").dispatch().body().preset_size(), Some(Report::LEN));
+
+        // A streamed response has no length; stripping it doesn't make it `0`.
+        assert_eq!(client.head("/streamed").dispatch().body().preset_size(), None);
+    }
+
+    #[test]
+    fn responders_can_skip_generating_body() {
+        let client = client();
+        let before = GENERATED.load(Ordering::SeqCst);
+        client.head("/report").dispatch();
+        assert_eq!(GENERATED.load(Ordering::SeqCst), before);
+
+        client.get("/report").dispatch();
+        assert!(GENERATED.load(Ordering::SeqCst) > before);
+    }
+
+    #[test]
+    fn strips_body_while_rerouted() {
+        let client = client();
+        assert_eq!(client.get("/method").dispatch().into_string().unwrap(), "GET false");
+
+        let response = client.head("/method").dispatch();
+        assert_eq!(response.body().preset_size(), Some("GET true".len()));
+    }
+}
@@ -0,0 +1,73 @@
+#[macro_use] extern crate rocket;
+
+use rocket::Request;
+use rocket::request::{self, FromRequest};
+
+/// Joins every `Via` value, in order, the way a proxy chain guard would.
+struct ViaChain(String);
+
+#[rocket::async_trait]
+impl<'r> FromRequest<'r> for ViaChain {
+    type Error = ();
+
+    async fn from_request(req: &'r Request<'_>) -> request::Outcome<Self, ()> {
+        let hops: Vec<_> = req.headers_get_all("Via").collect();
+        request::Outcome::Success(ViaChain(hops.join(", ")))
+    }
+}
+
+#[get("/")]
+fn via(chain: ViaChain) -> String {
+    chain.0
+}
+
+mod header_order_tests {
+    use super::*;
+    use rocket::http::Header;
+    use rocket::local::blocking::Client;
+
+    #[test]
+    fn get_all_returns_values_in_insertion_order() {
+        let client = Client::debug_with(vec![]).unwrap();
+        let request = client.get("/")
+            .header(Header::new("Forwarded", "for=192.0.2.60"))
+            .header(Header::new("X-Other", "1"))
+            .header(Header::new("forwarded", "for=198.51.100.17"))
+            .header(Header::new("X-Other", "2"))
+            .header(Header::new("FORWARDED", "for=203.0.113.43"));
+
+        let values: Vec<_> = request.inner().headers_get_all("Forwarded").collect();
+        assert_eq!(values, ["for=192.0.2.60", "for=198.51.100.17", "for=203.0.113.43"]);
+
+        let values: Vec<_> = request.inner().headers_get_all("x-other").collect();
+        assert_eq!(values, ["1", "2"]);
+
+        assert_eq!(request.inner().headers_get_all("Via").next(), None);
+    }
+
+    #[test]
+    fn order_survives_dispatch() {
+        let client = Client::debug_with(routes![via]).unwrap();
+        let response = client.get("/")
+            .header(Header::new("Via", "1.0 fred"))
+            .header(Header::new("Via", "1.1 p.example.net"))
+            .header(Header::new("Via", "1.1 nowhere.com (Apache/1.1)"))
+            .dispatch();
+
+        assert_eq!(response.into_string().unwrap(),
+            "1.0 fred, 1.1 p.example.net, 1.1 nowhere.com (Apache/1.1)");
+    }
+
+    #[test]
+    fn header_iteration_is_deterministic() {
+        let client = Client::debug_with(vec![]).unwrap();
+        let mut request = client.get("/");
+        for (name, value) in &[("C", "1"), ("A", "2"), ("B", "3"), ("a", "4"), ("D", "5")] {
+            request.add_header(Header::new(*name, *value));
+        }
+
+        request.inner_mut().add_header(Header::new("E", "6"));
+        let headers: Vec<_> = request.inner().headers().iter().map(|h| h.to_string()).collect();
+        assert_eq!(headers, ["C: 1", "A: 2", "A: 4", "B: 3", "D: 5", "E: 6"]);
+    }
+}
@@ -0,0 +1,119 @@
+#[macro_use] extern crate rocket;
+
+use rocket::Request;
+use rocket::request::{self, FromRequest};
+
+/// The result of `Request::host_normalized()`, formatted.
+struct HostInfo(String);
+
+#[rocket::async_trait]
+impl<'r> FromRequest<'r> for HostInfo {
+    type Error = ();
+
+    async fn from_request(req: &'r Request<'_>) -> request::Outcome<Self, ()> {
+        let info = match req.host_normalized() {
+            Ok(host) => format!("{} {:?} {}", host.name, host.port, host),
+            Err(e) => format!("error: {}", e),
+        };
+
+        request::Outcome::Success(HostInfo(info))
+    }
+}
+
+#[get("/")]
+fn index(host: HostInfo) -> String {
+    host.0
+}
+
+mod host_normalized_tests {
+    use super::*;
+    use rocket::http::Header;
+    use rocket::local::blocking::Client;
+
+    fn client() -> Client {
+        let config = rocket::Config {
+            trusted_proxies: vec!["10.0.0.0/8".parse().unwrap()],
+            ..rocket::Config::debug_default()
+        };
+
+        Client::debug(rocket::custom(config).mount("/", routes![index])).unwrap()
+    }
+
+    fn host(client: &Client, hosts: &[&str]) -> String {
+        host_via(client, hosts, None)
+    }
+
+    /// Requests `/` with the `Host` headers `hosts`, proxied with scheme
+    /// `proto` by a trusted proxy if `proto` is `Some`.
+    fn host_via(client: &Client, hosts: &[&str], proto: Option<&str>) -> String {
+        let mut request = client.get("/");
+        for host in hosts {
+            request.add_header(Header::new("Host", host.to_string()));
+        }
+
+        if let Some(proto) = proto {
+            request.add_header(Header::new("X-Forwarded-Proto", proto.to_string()));
+            request.set_remote("10.0.0.1:8000".parse().unwrap());
+        }
+
+        request.dispatch().into_string().unwrap()
+    }
+
+    #[test]
+    fn lowercases_and_strips_default_port() {
+        let client = client();
+        assert_eq!(host(&client, &["Example.COM"]), "example.com None example.com");
+        assert_eq!(host(&client, &["example.com:80"]), "example.com None example.com");
+        assert_eq!(host(&client, &["example.com:8080"]),
+            "example.com Some(8080) example.com:8080");
+
+        // `443` is only the default for `https`.
+        assert_eq!(host(&client, &["example.com:443"]), "example.com Some(443) example.com:443");
+        assert_eq!(host_via(&client, &["example.com:443"], Some("https")),
+            "example.com None example.com");
+        assert_eq!(host_via(&client, &["example.com:80"], Some("https")),
+            "example.com Some(80) example.com:80");
+
+        assert_eq!(host(&client, &["127.0.0.1:80"]), "127.0.0.1 None 127.0.0.1");
+        assert_eq!(host(&client, &["xn--bcher-kva.example"]),
+            "xn--bcher-kva.example None xn--bcher-kva.example");
+    }
+
+    #[test]
+    fn bracketed_ipv6() {
+        let client = client();
+        assert_eq!(host(&client, &["[::1]"]), "[::1] None [::1]");
+        assert_eq!(host(&client, &["[::1]:80"]), "[::1] None [::1]");
+        assert_eq!(host(&client, &["[2001:DB8:0:0::1]:8000"]),
+            "[2001:db8::1] Some(8000) [2001:db8::1]:8000");
+
+        assert_eq!(host(&client, &["[::1"]), "error: invalid IPv6 host");
+        assert_eq!(host(&client, &["[example.com]"]), "error: invalid IPv6 host");
+        assert_eq!(host(&client, &["[::1]x"]), "error: invalid character 'x' in host");
+        assert_eq!(host(&client, &["::1"]), "error: invalid character ':' in host");
+    }
+
+    #[test]
+    fn invalid_characters_and_ports() {
+        let client = client();
+        assert_eq!(host(&client, &["user@example.com"]), "error: invalid character '@' in host");
+        assert_eq!(host(&client, &["exa mple.com"]), "error: invalid character ' ' in host");
+        assert_eq!(host(&client, &["bücher.example"]), "error: invalid character 'ü' in host");
+        assert_eq!(host(&client, &["example.com/path"]), "error: invalid character '/' in host");
+
+        assert_eq!(host(&client, &[""]), "error: empty host name");
+        assert_eq!(host(&client, &[":80"]), "error: empty host name");
+        assert_eq!(host(&client, &["example.com:"]), "error: invalid host port");
+        assert_eq!(host(&client, &["example.com:+80"]), "error: invalid host port");
+        assert_eq!(host(&client, &["example.com:65536"]), "error: invalid host port");
+    }
+
+    #[test]
+    fn missing_and_duplicate_hosts() {
+        let client = client();
+        assert_eq!(host(&client, &[]), "error: missing host");
+        assert_eq!(host(&client, &["example.com", "evil.com"]), "error: multiple `Host` headers");
+        assert_eq!(host(&client, &["example.com", "example.com"]),
+            "error: multiple `Host` headers");
+    }
+}
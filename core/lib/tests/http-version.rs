@@ -0,0 +1,51 @@
+#[macro_use] extern crate rocket;
+
+use rocket::Request;
+use rocket::http::HttpVersion;
+use rocket::request::{self, FromRequest};
+
+/// Whether the response may use `Connection: keep-alive`.
+struct KeepAlive(bool);
+
+#[rocket::async_trait]
+impl<'r> FromRequest<'r> for KeepAlive {
+    type Error = ();
+
+    async fn from_request(req: &'r Request<'_>) -> request::Outcome<Self, ()> {
+        request::Outcome::Success(KeepAlive(!req.http_version().is_multiplexed()))
+    }
+}
+
+struct Version(HttpVersion);
+
+#[rocket::async_trait]
+impl<'r> FromRequest<'r> for Version {
+    type Error = ();
+
+    async fn from_request(req: &'r Request<'_>) -> request::Outcome<Self, ()> {
+        request::Outcome::Success(Version(req.http_version()))
+    }
+}
+
+#[get("/")]
+fn index(version: Version, keep_alive: KeepAlive) -> String {
+    format!("{} {}", version.0, keep_alive.0)
+}
+
+mod http_version_tests {
+    use super::*;
+    use rocket::local::blocking::Client;
+
+    #[test]
+    fn local_default_and_override() {
+        let client = Client::debug_with(routes![index]).unwrap();
+        let response = client.get("/").dispatch();
+        assert_eq!(response.into_string().unwrap(), "HTTP/1.1 true");
+
+        let response = client.get("/").http_version(HttpVersion::Http2).dispatch();
+        assert_eq!(response.into_string().unwrap(), "HTTP/2 false");
+
+        let response = client.get("/").http_version(HttpVersion::Http10).dispatch();
+        assert_eq!(response.into_string().unwrap(), "HTTP/1.0 true");
+    }
+}
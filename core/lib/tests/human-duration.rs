@@ -0,0 +1,103 @@
+#[macro_use] extern crate rocket;
+
+use std::time::Duration;
+
+use rocket::form::Form;
+use rocket::request::{HumanDuration, DurationError};
+
+#[get("/<ttl>")]
+fn ttl(ttl: Result<HumanDuration, DurationError>) -> String {
+    match ttl {
+        Ok(ttl) => ttl.as_nanos().to_string(),
+        Err(e) => e.to_string(),
+    }
+}
+
+#[derive(FromForm)]
+struct Timeouts {
+    connect: HumanDuration,
+    idle: Option<HumanDuration>,
+}
+
+#[post("/", data = "<form>")]
+fn timeouts(form: Form<Timeouts>) -> String {
+    format!("{:?} {:?}", *form.connect, form.idle.map(Duration::from))
+}
+
+mod human_duration_tests {
+    use super::*;
+    use rocket::http::{ContentType, Status};
+    use rocket::local::blocking::Client;
+
+    fn parse(s: &str) -> Result<Duration, DurationError> {
+        s.parse::<HumanDuration>().map(|d| d.into_inner())
+    }
+
+    #[test]
+    fn each_unit() {
+        assert_eq!(parse("7ns"), Ok(Duration::from_nanos(7)));
+        assert_eq!(parse("7us"), Ok(Duration::from_micros(7)));
+        assert_eq!(parse("7ms"), Ok(Duration::from_millis(7)));
+        assert_eq!(parse("7s"), Ok(Duration::from_secs(7)));
+        assert_eq!(parse("7m"), Ok(Duration::from_secs(7 * 60)));
+        assert_eq!(parse("7h"), Ok(Duration::from_secs(7 * 3600)));
+        assert_eq!(parse("7d"), Ok(Duration::from_secs(7 * 86400)));
+        assert_eq!(parse("0s"), Ok(Duration::ZERO));
+    }
+
+    #[test]
+    fn compound_values() {
+        assert_eq!(parse("1h30m"), Ok(Duration::from_secs(5400)));
+        assert_eq!(parse("1d2h3m4s5ms"), Ok(Duration::new(93784, 5_000_000)));
+        assert_eq!(parse("30m1h"), Ok(Duration::from_secs(5400)));
+        assert_eq!(parse("1s1s"), Ok(Duration::from_secs(2)));
+        assert_eq!(parse("1500ms"), Ok(Duration::from_millis(1500)));
+    }
+
+    #[test]
+    fn missing_unit() {
+        assert_eq!(parse("30"), Err(DurationError::MissingUnit));
+        assert_eq!(parse("1h30"), Err(DurationError::MissingUnit));
+        assert_eq!(parse("1h 30m"), Err(DurationError::UnexpectedChar(' ')));
+        assert_eq!(parse("h"), Err(DurationError::MissingNumber));
+        assert_eq!(parse("1hm"), Err(DurationError::UnknownUnit("hm".into())));
+        assert_eq!(parse("-1s"), Err(DurationError::UnexpectedChar('-')));
+        assert_eq!(parse("1.5s"), Err(DurationError::UnexpectedChar('.')));
+        assert_eq!(parse("5S"), Err(DurationError::UnknownUnit("S".into())));
+        assert_eq!(parse(""), Err(DurationError::Empty));
+    }
+
+    #[test]
+    fn overflow() {
+        let max = u64::MAX;
+        assert_eq!(parse(&format!("{}s", max)), Ok(Duration::from_secs(max)));
+        assert_eq!(parse(&format!("{}s1s", max)), Err(DurationError::Overflow));
+        assert_eq!(parse(&format!("{}m", max)), Err(DurationError::Overflow));
+        assert_eq!(parse(&format!("{}d", max / 86400 + 1)), Err(DurationError::Overflow));
+        assert_eq!(parse("99999999999999999999ns"), Err(DurationError::Overflow));
+    }
+
+    #[test]
+    fn param_and_form_field() {
+        let client = Client::debug_with(routes![ttl, timeouts]).unwrap();
+        let response = client.get("/1m30s").dispatch();
+        assert_eq!(response.into_string().unwrap(), "90000000000");
+
+        let response = client.get("/90").dispatch();
+        assert_eq!(response.into_string().unwrap(), "duration number without a unit");
+
+        let response = client.post("/")
+            .header(ContentType::Form)
+            .body("connect=250ms&idle=2h")
+            .dispatch();
+
+        assert_eq!(response.into_string().unwrap(), "250ms Some(7200s)");
+
+        let response = client.post("/")
+            .header(ContentType::Form)
+            .body("connect=5")
+            .dispatch();
+
+        assert_eq!(response.status(), Status::UnprocessableEntity);
+    }
+}
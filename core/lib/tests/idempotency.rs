@@ -0,0 +1,243 @@
+#[macro_use] extern crate rocket;
+
+use std::time::Duration;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use rocket::State;
+use rocket::http::{ContentType, Header, Status};
+use rocket::idempotency::{Idempotency, IdempotentBody, IdempotencyError};
+
+#[derive(Default)]
+struct Charges(AtomicUsize);
+
+#[post("/pay", data = "<amount>")]
+async fn pay(amount: IdempotentBody<'_>, charges: &State<Charges>) -> (ContentType, String) {
+    rocket::tokio::time::sleep(Duration::from_millis(50)).await;
+    let n = charges.0.fetch_add(1, Ordering::SeqCst) + 1;
+    let amount = String::from_utf8_lossy(&amount);
+    (ContentType::Plain, format!("charge #{} of {}", n, amount))
+}
+
+#[post("/check", data = "<body>")]
+fn check(body: Result<IdempotentBody<'_>, IdempotencyError>) -> String {
+    match body {
+        Ok(body) => body.key().to_string(),
+        Err(e) => e.to_string(),
+    }
+}
+
+#[post("/echo", data = "<body>")]
+fn echo(body: &str) -> String {
+    body.into()
+}
+
+#[catch(default)]
+fn catcher(status: Status, _: &rocket::Request<'_>) -> String {
+    format!("caught {}", status.code)
+}
+
+fn rocket(ttl: Duration) -> rocket::Rocket<rocket::Build> {
+    with(Idempotency::new(ttl))
+}
+
+fn with(idempotency: Idempotency) -> rocket::Rocket<rocket::Build> {
+    rocket::build()
+        .attach(idempotency)
+        .manage(Charges::default())
+        .mount("/", routes![pay, check, echo])
+        .register("/", catchers![catcher])
+}
+
+mod idempotency_tests {
+    use super::*;
+    use rocket::local::blocking::{Client, LocalResponse};
+
+    fn pay<'c>(client: &'c Client, key: &str, amount: &str) -> LocalResponse<'c> {
+        pay_from(client, "1.2.3.4", key, amount)
+    }
+
+    fn pay_from<'c>(client: &'c Client, ip: &str, key: &str, amount: &str) -> LocalResponse<'c> {
+        client.post("/pay")
+            .remote(format!("{}:80", ip).parse().unwrap())
+            .header(Header::new("Idempotency-Key", key.to_string()))
+            .body(amount)
+            .dispatch()
+    }
+
+    fn charges(client: &Client) -> usize {
+        client.rocket().state::<Charges>().unwrap().0.load(Ordering::SeqCst)
+    }
+
+    #[test]
+    fn first_request_runs_handler() {
+        let client = Client::debug(rocket(Duration::from_secs(60))).unwrap();
+        let response = pay(&client, "k1", "10");
+        assert_eq!(response.status(), Status::Ok);
+        assert!(response.headers().get_one("Idempotent-Replayed").is_none());
+        assert_eq!(response.into_string().unwrap(), "charge #1 of 10");
+
+        // A different key is a different request.
+        assert_eq!(pay(&client, "k2", "10").into_string().unwrap(), "charge #2 of 10");
+        assert_eq!(charges(&client), 2);
+    }
+
+    #[test]
+    fn exact_replay_returns_recorded_response() {
+        let client = Client::debug(rocket(Duration::from_secs(60))).unwrap();
+        assert_eq!(pay(&client, "k", "10").into_string().unwrap(), "charge #1 of 10");
+
+        for _ in 0..3 {
+            let response = pay(&client, "k", "10");
+            assert_eq!(response.status(), Status::Ok);
+            assert_eq!(response.content_type(), Some(ContentType::Plain));
+            assert_eq!(response.headers().get_one("Idempotent-Replayed"), Some("true"));
+            assert_eq!(response.into_string().unwrap(), "charge #1 of 10");
+        }
+
+        assert_eq!(charges(&client), 1);
+    }
+
+    #[test]
+    fn conflicting_body_is_rejected() {
+        let client = Client::debug(rocket(Duration::from_secs(60))).unwrap();
+        assert_eq!(pay(&client, "k", "10").into_string().unwrap(), "charge #1 of 10");
+
+        let response = pay(&client, "k", "9999");
+        assert_eq!(response.status(), Status::Conflict);
+        assert!(response.headers().get_one("Idempotent-Replayed").is_none());
+        assert_eq!(charges(&client), 1);
+
+        // The original is still replayed.
+        assert_eq!(pay(&client, "k", "10").into_string().unwrap(), "charge #1 of 10");
+    }
+
+    #[test]
+    fn recordings_expire() {
+        let client = Client::debug(rocket(Duration::from_millis(200))).unwrap();
+        assert_eq!(pay(&client, "k", "10").into_string().unwrap(), "charge #1 of 10");
+        assert_eq!(pay(&client, "k", "10").into_string().unwrap(), "charge #1 of 10");
+
+        std::thread::sleep(Duration::from_millis(300));
+        assert_eq!(pay(&client, "k", "5").into_string().unwrap(), "charge #2 of 5");
+    }
+
+    #[test]
+    fn key_count_is_capped() {
+        let idempotency = Idempotency::new(Duration::from_millis(200)).max_keys(2);
+        let client = Client::debug(with(idempotency)).unwrap();
+        assert_eq!(pay(&client, "k1", "10").into_string().unwrap(), "charge #1 of 10");
+        assert_eq!(pay(&client, "k2", "20").into_string().unwrap(), "charge #2 of 20");
+
+        let response = pay(&client, "k3", "30");
+        assert_eq!(response.status(), Status::ServiceUnavailable);
+        assert_eq!(charges(&client), 2);
+
+        // Recorded keys are still replayed.
+        let response = pay(&client, "k1", "10");
+        assert_eq!(response.headers().get_one("Idempotent-Replayed"), Some("true"));
+        assert_eq!(response.into_string().unwrap(), "charge #1 of 10");
+
+        // Once recordings expire, there's room for new keys.
+        std::thread::sleep(Duration::from_millis(300));
+        assert_eq!(pay(&client, "k3", "30").into_string().unwrap(), "charge #3 of 30");
+        assert_eq!(pay(&client, "k4", "40").into_string().unwrap(), "charge #4 of 40");
+        assert_eq!(pay(&client, "k5", "50").status(), Status::ServiceUnavailable);
+    }
+
+    #[test]
+    fn missing_and_invalid_keys() {
+        let client = Client::debug(rocket(Duration::from_secs(60))).unwrap();
+        let response = client.post("/pay").remote("1.2.3.4:80".parse().unwrap()).body("10");
+        assert_eq!(response.dispatch().status(), Status::BadRequest);
+
+        let check = |key: Option<&str>| {
+            let mut request = client.post("/check").remote("1.2.3.4:80".parse().unwrap());
+            if let Some(key) = key {
+                request = request.header(Header::new("Idempotency-Key", key.to_string()));
+            }
+
+            request.dispatch().into_string().unwrap()
+        };
+
+        assert_eq!(check(None), "missing idempotency key");
+        assert_eq!(check(Some("has space")), "invalid idempotency key");
+        assert_eq!(check(Some(&"a".repeat(256))), "invalid idempotency key");
+        assert_eq!(check(Some("ok")), "ok");
+        assert_eq!(charges(&client), 0);
+    }
+
+    #[rocket::async_test]
+    async fn concurrent_requests_serialize() {
+        use rocket::local::asynchronous::Client;
+
+        let client = Client::debug(rocket(Duration::from_secs(60))).await.unwrap();
+        let request = || client.post("/pay")
+            .remote("1.2.3.4:80".parse().unwrap())
+            .header(Header::new("Idempotency-Key", "k"))
+            .body("10")
+            .dispatch();
+
+        let (a, b) = rocket::tokio::join!(request(), request());
+        let a = a.into_string().await.unwrap();
+        let b = b.into_string().await.unwrap();
+        assert_eq!(a, "charge #1 of 10");
+        assert_eq!(b, "charge #1 of 10");
+        assert_eq!(client.rocket().state::<Charges>().unwrap().0.load(Ordering::SeqCst), 1);
+    }
+
+    #[test]
+    fn replays_skip_catchers() {
+        let client = Client::debug(rocket(Duration::from_secs(60))).unwrap();
+        assert_eq!(pay(&client, "k", "10").into_string().unwrap(), "charge #1 of 10");
+
+        let response = pay(&client, "k", "10");
+        assert_eq!(response.status(), Status::Ok);
+        assert_eq!(response.into_string().unwrap(), "charge #1 of 10");
+
+        // Errors are still handled by catchers.
+        let response = pay(&client, "k", "11");
+        assert_eq!(response.into_string().unwrap(), "caught 409");
+    }
+
+    #[test]
+    fn keys_are_scoped_per_client() {
+        let client = Client::debug(rocket(Duration::from_secs(60))).unwrap();
+        let response = pay_from(&client, "1.1.1.1", "k", "10");
+        assert_eq!(response.into_string().unwrap(), "charge #1 of 10");
+
+        // Another client's use of the key neither replays nor conflicts.
+        let response = pay_from(&client, "2.2.2.2", "k", "20");
+        assert!(response.headers().get_one("Idempotent-Replayed").is_none());
+        assert_eq!(response.into_string().unwrap(), "charge #2 of 20");
+
+        let response = pay_from(&client, "1.1.1.1", "k", "10");
+        assert_eq!(response.into_string().unwrap(), "charge #1 of 10");
+
+        // Without a scope, the request fails.
+        let response = client.post("/pay").header(Header::new("Idempotency-Key", "k")).body("1");
+        assert_eq!(response.dispatch().status(), Status::BadRequest);
+        assert_eq!(charges(&client), 2);
+    }
+
+    #[test]
+    fn other_routes_receive_the_whole_body() {
+        let config = rocket::Config {
+            limits: rocket::data::Limits::default().limit("raw-body", 4.into()),
+            ..rocket::Config::debug_default()
+        };
+
+        let rocket = rocket(Duration::from_secs(60)).configure(config);
+        let client = Client::debug(rocket).unwrap();
+        let response = client.post("/echo")
+            .header(Header::new("Idempotency-Key", "k"))
+            .body("0123456789")
+            .dispatch();
+
+        assert_eq!(response.into_string().unwrap(), "0123456789");
+
+        // An idempotent body over the limit fails rather than being truncated.
+        let response = pay(&client, "k", "0123456789");
+        assert_eq!(response.status(), Status::PayloadTooLarge);
+        assert_eq!(charges(&client), 0);
+    }
+}
@@ -0,0 +1,95 @@
+#[macro_use] extern crate rocket;
+
+use rocket::request::{LimitedSegments, SegmentsError};
+
+#[get("/files/<path..>")]
+fn files(path: LimitedSegments<3>) -> String {
+    path.display().to_string()
+}
+
+#[get("/files/<_path..>", rank = 2)]
+fn forwarded(_path: std::path::PathBuf) -> &'static str {
+    "forwarded"
+}
+
+#[get("/result/<path..>")]
+fn result(path: Result<LimitedSegments<3>, SegmentsError>) -> String {
+    match path {
+        Ok(path) => path.display().to_string(),
+        Err(e) => e.to_string(),
+    }
+}
+
+mod limited_segments_tests {
+    use super::*;
+    use rocket::http::Status;
+    use rocket::http::uri::Origin;
+    use rocket::request::FromSegments;
+    use rocket::local::blocking::Client;
+
+    fn limited<const N: usize>(path: &str) -> Result<String, SegmentsError> {
+        let uri = Origin::parse(path).unwrap();
+        LimitedSegments::<N>::from_segments(uri.path().segments())
+            .map(|p| p.into_inner().display().to_string())
+    }
+
+    #[test]
+    fn at_limit() {
+        assert_eq!(limited::<3>("/a/b/c"), Ok("a/b/c".into()));
+        assert_eq!(limited::<3>("/a//b///c/"), Ok("a/b/c".into()));
+        assert_eq!(limited::<1>("/a%20b"), Ok("a b".into()));
+        assert_eq!(limited::<0>("/"), Ok("".into()));
+    }
+
+    #[test]
+    fn over_limit() {
+        assert_eq!(limited::<3>("/a/b/c/d"), Err(SegmentsError::TooDeep { depth: 4, limit: 3 }));
+        assert_eq!(limited::<0>("/a"), Err(SegmentsError::TooDeep { depth: 1, limit: 0 }));
+
+        let deep = "/a".repeat(5000);
+        assert_eq!(limited::<64>(&deep), Err(SegmentsError::TooDeep { depth: 5000, limit: 64 }));
+    }
+
+    #[test]
+    fn traversal_is_rejected_at_any_depth() {
+        assert_eq!(limited::<3>("/.."), Err(SegmentsError::Traversal));
+        assert_eq!(limited::<3>("/a/../b"), Err(SegmentsError::Traversal));
+        assert_eq!(limited::<3>("/a/b/.."), Err(SegmentsError::Traversal));
+        assert_eq!(limited::<3>("/a/%2E%2E"), Err(SegmentsError::Traversal));
+        assert_eq!(limited::<3>("/%2e%2e/a"), Err(SegmentsError::Traversal));
+        assert_eq!(limited::<100>("/a/b/c/../../../../etc"), Err(SegmentsError::Traversal));
+    }
+
+    #[test]
+    fn path_checks_still_apply() {
+        use rocket::http::uri::error::PathError;
+
+        assert_eq!(limited::<3>("/.env"), Err(SegmentsError::Path(PathError::BadStart('.'))));
+        assert_eq!(limited::<3>("/a/*b"), Err(SegmentsError::Path(PathError::BadStart('*'))));
+        assert_eq!(limited::<3>("/a%2Fb"), Err(SegmentsError::Path(PathError::BadChar('/'))));
+        assert_eq!(limited::<3>("/a:"), Err(SegmentsError::Path(PathError::BadEnd(':'))));
+    }
+
+    #[test]
+    fn guard_forwards_over_limit_or_on_traversal() {
+        let client = Client::debug_with(routes![files, forwarded, result]).unwrap();
+
+        let response = client.get("/files/a/b/c").dispatch();
+        assert_eq!(response.into_string().unwrap(), "a/b/c");
+
+        let response = client.get("/files/a/b/c/d").dispatch();
+        assert_eq!(response.into_string().unwrap(), "forwarded");
+
+        let response = client.get("/files/a/../b").dispatch();
+        assert_eq!(response.into_string().unwrap(), "forwarded");
+
+        let response = client.get("/result/a/b/c/d").dispatch();
+        assert_eq!(response.into_string().unwrap(), "4 segments exceed the limit of 3");
+
+        let response = client.get("/result/a/%2E%2E").dispatch();
+        assert_eq!(response.into_string().unwrap(), "path contains a `..` segment");
+
+        let response = client.get("/nope/a/b/c/d").dispatch();
+        assert_eq!(response.status(), Status::NotFound);
+    }
+}
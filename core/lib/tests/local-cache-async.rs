@@ -0,0 +1,129 @@
+#[macro_use] extern crate rocket;
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use rocket::State;
+use rocket::request::{self, Request, FromRequest, local_cache_async};
+
+#[derive(Default)]
+struct Lookups(AtomicUsize);
+
+#[derive(Debug, PartialEq)]
+struct User(String);
+
+async fn lookup(req: &Request<'_>) -> User {
+    let lookups = req.rocket().state::<Lookups>().unwrap();
+    lookups.0.fetch_add(1, Ordering::SeqCst);
+    rocket::tokio::task::yield_now().await;
+    User(req.headers().get_one("X-User").unwrap_or("anonymous").to_string())
+}
+
+#[rocket::async_trait]
+impl<'r> FromRequest<'r> for &'r User {
+    type Error = ();
+
+    async fn from_request(req: &'r Request<'_>) -> request::Outcome<Self, ()> {
+        request::Outcome::Success(req.local_cache_async(lookup(req)).await)
+    }
+}
+
+#[get("/")]
+fn index(a: &User, b: &User, lookups: &State<Lookups>) -> String {
+    format!("{} {} {}", a.0, b.0, lookups.0.load(Ordering::SeqCst))
+}
+
+mod local_cache_async_tests {
+    use super::*;
+    use rocket::tokio::join;
+    use rocket::local::asynchronous::Client;
+
+    async fn client() -> Client {
+        let rocket = rocket::build()
+            .mount("/", routes![index])
+            .manage(Lookups::default());
+
+        Client::debug(rocket).await.unwrap()
+    }
+
+    fn lookups(client: &Client) -> usize {
+        client.rocket().state::<Lookups>().unwrap().0.load(Ordering::SeqCst)
+    }
+
+    #[rocket::async_test]
+    async fn guard_initializer_runs_once() {
+        let client = client().await;
+        let response = client.get("/").header(rocket::http::Header::new("X-User", "bob"));
+        assert_eq!(response.dispatch().await.into_string().await.unwrap(), "bob bob 1");
+
+        // The cache is per-request, so a new request looks the user up again.
+        let response = client.get("/").dispatch().await;
+        assert_eq!(response.into_string().await.unwrap(), "anonymous anonymous 2");
+    }
+
+    #[rocket::async_test]
+    async fn async_and_sync_views_agree() {
+        let client = client().await;
+        let request = client.get("/");
+
+        let user = request.local_cache_async(lookup(&request)).await;
+        assert_eq!(user, &User("anonymous".into()));
+        assert_eq!(lookups(&client), 1);
+
+        let sync = request.local_cache(|| User("never".into()));
+        assert!(std::ptr::eq(user, sync));
+
+        let again = request.local_cache_async(lookup(&request)).await;
+        assert!(std::ptr::eq(user, again));
+        assert_eq!(lookups(&client), 1);
+    }
+
+    #[rocket::async_test]
+    async fn sync_store_seen_by_async() {
+        let client = client().await;
+        let request = client.get("/");
+
+        request.local_cache(|| User("sync".into()));
+        let user = request.local_cache_async(lookup(&request)).await;
+        assert_eq!(user, &User("sync".into()));
+        assert_eq!(lookups(&client), 0);
+    }
+
+    #[rocket::async_test]
+    async fn concurrent_first_store_wins() {
+        let client = client().await;
+        let request = client.get("/");
+
+        let (a, b) = join!(
+            request.local_cache_async(async { "first" }),
+            request.local_cache_async(async {
+                rocket::tokio::task::yield_now().await;
+                "second"
+            }),
+        );
+
+        assert_eq!((*a, *b), ("first", "first"));
+        assert_eq!(*request.local_cache(|| "third"), "first");
+    }
+
+    #[rocket::async_test]
+    async fn macro_uses_unique_types() {
+        let client = client().await;
+        let request = client.get("/");
+
+        let a = local_cache_async!(request, async { String::from("a") });
+        let b = local_cache_async!(request, async { String::from("b") });
+        assert_eq!((a.as_str(), b.as_str()), ("a", "b"));
+
+        let mut runs = 0;
+        for i in 0..3 {
+            let value = local_cache_async!(request, async {
+                runs += 1;
+                i.to_string()
+            });
+
+            assert_eq!(value, "0");
+        }
+
+        assert_eq!(runs, 1);
+    }
+}
@@ -0,0 +1,95 @@
+#![cfg(debug_assertions)]
+
+#[macro_use] extern crate rocket;
+
+use std::any::TypeId;
+
+use rocket::{Request, Response};
+use rocket::fairing::AdHoc;
+use rocket::http::Header;
+use rocket::request::{self, FromRequest};
+
+#[derive(Debug, PartialEq)]
+struct User(&'static str);
+
+#[derive(Debug, PartialEq)]
+struct Session(u32);
+
+struct Guard;
+
+#[rocket::async_trait]
+impl<'r> FromRequest<'r> for Guard {
+    type Error = ();
+
+    async fn from_request(req: &'r Request<'_>) -> request::Outcome<Self, ()> {
+        req.local_cache(|| User("bob"));
+        req.local_cache_async(async { Session(7) }).await;
+        request::Outcome::Success(Guard)
+    }
+}
+
+#[get("/")]
+fn index(_guard: Guard) { }
+
+fn list(req: &Request<'_>) -> String {
+    let names: Vec<_> = req.local_cache_debug().iter().map(|t| t.type_name).collect();
+    names.join(", ")
+}
+
+mod local_cache_debug_tests {
+    use super::*;
+    use rocket::local::blocking::Client;
+
+    #[test]
+    fn lists_cached_types() {
+        let client = Client::debug_with(vec![]).unwrap();
+        let request = client.get("/");
+        assert!(request.inner().local_cache_debug().is_empty());
+
+        request.inner().local_cache(|| User("alice"));
+        request.inner().local_cache(|| Session(1));
+        request.inner().local_cache(|| User("ignored"));
+
+        let types = request.inner().local_cache_debug();
+        assert_eq!(types.len(), 2);
+        assert_eq!(types[0].type_id, TypeId::of::<User>());
+        assert!(types[0].type_name.ends_with("::User"));
+        assert_eq!(types[1].type_id, TypeId::of::<Session>());
+        assert!(types[1].type_name.ends_with("::Session"));
+
+        // Listing doesn't disturb the cached values.
+        assert_eq!(request.inner().local_cache_get::<User>(), Some(&User("alice")));
+        assert_eq!(request.inner().local_cache(|| Session(2)), &Session(1));
+    }
+
+    #[test]
+    fn replaced_types_are_listed_once() {
+        let client = Client::debug_with(vec![]).unwrap();
+        let mut request = client.get("/");
+        let req = request.inner_mut();
+
+        req.local_cache_replace(Session(1));
+        req.local_cache(|| User("alice"));
+        req.local_cache_replace(Session(2));
+
+        let types: Vec<_> = req.local_cache_debug().iter().map(|t| t.type_id).collect();
+        assert_eq!(types, [TypeId::of::<Session>(), TypeId::of::<User>()]);
+    }
+
+    #[test]
+    fn listing_from_fairing() {
+        let rocket = rocket::build()
+            .mount("/", routes![index])
+            .attach(AdHoc::on_response("Cache Log", |req, res: &mut Response<'_>| {
+                Box::pin(async move {
+                    res.set_header(Header::new("X-Cached", list(req)));
+                })
+            }));
+
+        let client = Client::debug(rocket).unwrap();
+        let response = client.get("/").dispatch();
+        let cached = response.headers().get_one("X-Cached").unwrap();
+        assert!(cached.contains("::User"), "{}", cached);
+        assert!(cached.contains("::Session"), "{}", cached);
+    }
+}
@@ -0,0 +1,169 @@
+#[macro_use] extern crate rocket;
+
+use rocket::{Request, Data};
+use rocket::fairing::AdHoc;
+use rocket::request::{self, FromRequest};
+
+#[derive(Debug, PartialEq)]
+struct Token(String);
+
+#[rocket::async_trait]
+impl<'r> FromRequest<'r> for &'r Token {
+    type Error = ();
+
+    async fn from_request(req: &'r Request<'_>) -> request::Outcome<Self, ()> {
+        request::Outcome::Success(req.local_cache(|| Token("guard".into())))
+    }
+}
+
+#[get("/")]
+fn index(token: &Token) -> String {
+    token.0.clone()
+}
+
+struct Peek(Option<String>);
+
+#[rocket::async_trait]
+impl<'r> FromRequest<'r> for Peek {
+    type Error = ();
+
+    async fn from_request(req: &'r Request<'_>) -> request::Outcome<Self, ()> {
+        request::Outcome::Success(Peek(req.local_cache_get::<Token>().map(|t| t.0.clone())))
+    }
+}
+
+#[get("/peek")]
+fn peek(peek: Peek) -> String {
+    format!("{:?}", peek.0)
+}
+
+fn renew(req: &mut Request<'_>, _: &Data<'_>) {
+    let stale = req.local_cache(|| Token("stale".into())).0.clone();
+    if req.headers().contains("X-Renew") {
+        req.local_cache_replace(Token(format!("{}-renewed", stale)));
+    }
+}
+
+mod local_cache_replace_tests {
+    use super::*;
+    use rocket::local::blocking::Client;
+    use rocket::http::Header;
+
+    #[test]
+    fn get_before_any_store() {
+        let client = Client::debug_with(vec![]).unwrap();
+        let request = client.get("/");
+        assert_eq!(request.inner().local_cache_get::<Token>(), None);
+        assert_eq!(request.inner().local_cache_get::<u32>(), None);
+
+        request.inner().local_cache(|| 7u32);
+        assert_eq!(request.inner().local_cache_get::<Token>(), None);
+        assert_eq!(request.inner().local_cache_get::<u32>(), Some(&7));
+    }
+
+    #[test]
+    fn replace_then_read() {
+        let client = Client::debug_with(vec![]).unwrap();
+        let mut request = client.get("/");
+        let req = request.inner_mut();
+
+        assert_eq!(req.local_cache_replace(Token("a".into())), &Token("a".into()));
+        assert_eq!(req.local_cache_get::<Token>(), Some(&Token("a".into())));
+        assert_eq!(req.local_cache(|| Token("unused".into())), &Token("a".into()));
+
+        assert_eq!(req.local_cache_replace(Token("b".into())), &Token("b".into()));
+        assert_eq!(req.local_cache_get::<Token>(), Some(&Token("b".into())));
+        assert_eq!(req.local_cache(|| Token("unused".into())), &Token("b".into()));
+
+        // Other types are unaffected.
+        assert_eq!(req.local_cache(|| 1u8), &1);
+        req.local_cache_replace(2u8);
+        assert_eq!(req.local_cache_get::<u8>(), Some(&2));
+        assert_eq!(req.local_cache_get::<Token>(), Some(&Token("b".into())));
+    }
+
+    #[test]
+    fn replacing_twice_drops_the_first_replacement() {
+        use std::sync::Arc;
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        struct Counted(usize, Arc<AtomicUsize>);
+
+        impl Drop for Counted {
+            fn drop(&mut self) {
+                self.1.fetch_add(1, Ordering::SeqCst);
+            }
+        }
+
+        let drops = Arc::new(AtomicUsize::new(0));
+        let client = Client::debug_with(vec![]).unwrap();
+        let mut request = client.get("/");
+        let req = request.inner_mut();
+
+        // The cached value is hidden, not dropped, by a replacement.
+        req.local_cache(|| Counted(0, drops.clone()));
+        req.local_cache_replace(Counted(1, drops.clone()));
+        assert_eq!(drops.load(Ordering::SeqCst), 0);
+
+        // A replacement is dropped as soon as it's replaced.
+        req.local_cache_replace(Counted(2, drops.clone()));
+        assert_eq!(drops.load(Ordering::SeqCst), 1);
+        assert_eq!(req.local_cache_get::<Counted>().unwrap().0, 2);
+
+        drop(request);
+        assert_eq!(drops.load(Ordering::SeqCst), 3);
+    }
+
+    #[test]
+    fn clones_do_not_share_replacements() {
+        let client = Client::debug_with(vec![]).unwrap();
+        let mut request = client.get("/");
+        request.inner().local_cache(|| Token("cached".into()));
+        request.inner_mut().local_cache_replace(Token("a".into()));
+
+        let before = request.clone();
+        request.inner_mut().local_cache_replace(Token("b".into()));
+        assert_eq!(before.inner().local_cache_get::<Token>(), Some(&Token("a".into())));
+        assert_eq!(request.inner().local_cache_get::<Token>(), Some(&Token("b".into())));
+
+        let mut after = request.clone();
+        assert_eq!(after.inner().local_cache_get::<Token>(), Some(&Token("b".into())));
+        after.inner_mut().local_cache_replace(Token("c".into()));
+        assert_eq!(request.inner().local_cache_get::<Token>(), Some(&Token("b".into())));
+
+        // Values cached by `local_cache()` are shared.
+        after.inner().local_cache(|| 1u8);
+        assert_eq!(request.inner().local_cache_get::<u8>(), Some(&1));
+    }
+
+    #[test]
+    fn replace_is_visible_to_async_reads() {
+        let client = Client::debug_with(vec![]).unwrap();
+        let mut request = client.get("/");
+        let req = request.inner_mut();
+
+        req.local_cache(|| Token("old".into()));
+        req.local_cache_replace(Token("new".into()));
+        let value = rocket::async_test(req.local_cache_async(async { Token("unused".into()) }));
+        assert_eq!(value, &Token("new".into()));
+    }
+
+    #[test]
+    fn replace_in_fairing_is_seen_by_guards() {
+        let rocket = rocket::build()
+            .mount("/", routes![index, peek])
+            .attach(AdHoc::on_request("Renew", |req, data| Box::pin(async move {
+                renew(req, data)
+            })));
+
+        let client = Client::debug(rocket).unwrap();
+        let response = client.get("/").dispatch();
+        assert_eq!(response.into_string().unwrap(), "stale");
+
+        let response = client.get("/").header(Header::new("X-Renew", "1")).dispatch();
+        assert_eq!(response.into_string().unwrap(), "stale-renewed");
+
+        let response = client.get("/peek").header(Header::new("X-Renew", "1")).dispatch();
+        assert_eq!(response.into_string().unwrap(), "Some(\"stale-renewed\")");
+    }
+}
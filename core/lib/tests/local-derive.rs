@@ -0,0 +1,138 @@
+#[macro_use] extern crate rocket;
+
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use rocket::Request;
+use rocket::http::Status;
+use rocket::request::{self, FromRequest};
+
+/// Global tenant configuration, counting how often a tenant is resolved.
+struct Tenants {
+    names: HashMap<&'static str, &'static str>,
+    resolved: AtomicUsize,
+}
+
+#[derive(Debug, PartialEq)]
+struct Tenant(Option<&'static str>);
+
+/// A second value derived from the same state.
+struct TenantCount(usize);
+
+fn tenant<'r>(req: &'r Request<'_>) -> Option<&'r Tenant> {
+    req.local_derive(|tenants: &Tenants| {
+        tenants.resolved.fetch_add(1, Ordering::SeqCst);
+        let id = req.headers().get_one("X-Tenant");
+        Tenant(id.and_then(|id| tenants.names.get(id).copied()))
+    })
+}
+
+/// Two guards that both resolve the tenant.
+macro_rules! tenant_guard {
+    ($name:ident) => (
+        struct $name(&'static str);
+
+        #[rocket::async_trait]
+        impl<'r> FromRequest<'r> for $name {
+            type Error = ();
+
+            async fn from_request(req: &'r Request<'_>) -> request::Outcome<Self, ()> {
+                match tenant(req) {
+                    Some(Tenant(Some(name))) => request::Outcome::Success($name(name)),
+                    Some(Tenant(None)) => request::Outcome::Failure((Status::NotFound, ())),
+                    None => request::Outcome::Failure((Status::InternalServerError, ())),
+                }
+            }
+        }
+    )
+}
+
+tenant_guard!(Admin);
+tenant_guard!(Billing);
+
+#[get("/")]
+fn index(admin: Admin, billing: Billing) -> String {
+    format!("{} {}", admin.0, billing.0)
+}
+
+/// Derives a `TenantCount` and a `Tenant` and reports both.
+struct RequestCount(String);
+
+#[rocket::async_trait]
+impl<'r> FromRequest<'r> for RequestCount {
+    type Error = ();
+
+    async fn from_request(req: &'r Request<'_>) -> request::Outcome<Self, ()> {
+        let count = req.local_derive(|t: &Tenants| TenantCount(t.names.len())).unwrap();
+        let tenant = tenant(req).unwrap();
+        request::Outcome::Success(RequestCount(format!("{} {:?}", count.0, tenant.0)))
+    }
+}
+
+#[get("/count")]
+fn count(count: RequestCount) -> String {
+    count.0
+}
+
+mod local_derive_tests {
+    use super::*;
+    use rocket::http::Header;
+    use rocket::local::blocking::Client;
+
+    fn client() -> Client {
+        let tenants = Tenants {
+            names: HashMap::from([("acme", "ACME"), ("initech", "Initech")]),
+            resolved: AtomicUsize::new(0),
+        };
+
+        let rocket = rocket::build().manage(tenants).mount("/", routes![index, count]);
+        Client::debug(rocket).unwrap()
+    }
+
+    fn resolved(client: &Client) -> usize {
+        client.rocket().state::<Tenants>().unwrap().resolved.load(Ordering::SeqCst)
+    }
+
+    fn get(client: &Client, uri: &'static str, tenant: &'static str) -> (Status, String) {
+        let response = client.get(uri).header(Header::new("X-Tenant", tenant)).dispatch();
+        (response.status(), response.into_string().unwrap_or_default())
+    }
+
+    #[test]
+    fn derived_once_per_request() {
+        let client = client();
+        assert_eq!(get(&client, "/", "acme"), (Status::Ok, "ACME ACME".into()));
+        assert_eq!(resolved(&client), 1);
+
+        // Each request derives its own value.
+        assert_eq!(get(&client, "/", "initech"), (Status::Ok, "Initech Initech".into()));
+        assert_eq!(resolved(&client), 2);
+
+        assert_eq!(get(&client, "/", "globex").0, Status::NotFound);
+        assert_eq!(resolved(&client), 3);
+    }
+
+    #[test]
+    fn values_are_keyed_by_state_and_type() {
+        let client = client();
+        assert_eq!(get(&client, "/count", "acme"), (Status::Ok, "2 Some(\"ACME\")".into()));
+        assert_eq!(resolved(&client), 1);
+
+        let request = client.get("/").header(Header::new("X-Tenant", "acme"));
+        request.local_cache(|| Tenant(Some("cached directly")));
+        assert_eq!(tenant(&request), Some(&Tenant(Some("ACME"))));
+        assert_eq!(request.local_cache(|| Tenant(None)), &Tenant(Some("cached directly")));
+        assert_eq!(resolved(&client), 2);
+    }
+
+    #[test]
+    fn unmanaged_state_is_none() {
+        let client = Client::debug(rocket::build().mount("/", routes![index])).unwrap();
+        let response = client.get("/").header(Header::new("X-Tenant", "acme")).dispatch();
+        assert_eq!(response.status(), Status::InternalServerError);
+
+        let request = client.get("/");
+        assert!(tenant(&request).is_none());
+        assert!(request.local_derive(|_: &Tenants| -> Tenant { unreachable!() }).is_none());
+    }
+}
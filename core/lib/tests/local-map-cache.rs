@@ -0,0 +1,126 @@
+#[macro_use] extern crate rocket;
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use rocket::Request;
+use rocket::request::{self, FromRequest};
+
+/// Counts how many times a sub-resource was "parsed".
+#[derive(Default)]
+struct Parses(AtomicUsize);
+
+/// Parses the query parameter `name` once per request, caching it under
+/// `name` in the keyed cache.
+fn parsed<'r>(req: &'r Request<'_>, name: &str) -> &'r String {
+    req.local_map_get_or_insert(name, || {
+        req.rocket().state::<Parses>().unwrap().0.fetch_add(1, Ordering::SeqCst);
+        req.query_value::<String>(name).and_then(|r| r.ok()).unwrap_or_default()
+    })
+}
+
+struct Owner<'r>(&'r String);
+
+struct Repo<'r>(&'r String);
+
+#[rocket::async_trait]
+impl<'r> FromRequest<'r> for Owner<'r> {
+    type Error = ();
+
+    async fn from_request(req: &'r Request<'_>) -> request::Outcome<Self, ()> {
+        request::Outcome::Success(Owner(parsed(req, "owner")))
+    }
+}
+
+#[rocket::async_trait]
+impl<'r> FromRequest<'r> for Repo<'r> {
+    type Error = ();
+
+    async fn from_request(req: &'r Request<'_>) -> request::Outcome<Self, ()> {
+        request::Outcome::Success(Repo(parsed(req, "repo")))
+    }
+}
+
+#[get("/?<owner>&<repo>")]
+fn index(owner: &str, repo: &str, a: Owner<'_>, b: Repo<'_>, c: Owner<'_>) -> String {
+    assert_eq!((owner, repo), (a.0.as_str(), b.0.as_str()));
+    assert!(std::ptr::eq(a.0, c.0));
+    format!("{}/{}", a.0, b.0)
+}
+
+mod local_map_cache_tests {
+    use super::*;
+    use rocket::local::blocking::Client;
+
+    #[test]
+    fn same_type_different_keys_are_independent() {
+        let client = Client::debug_with(vec![]).unwrap();
+        let request = client.get("/");
+
+        let a = request.local_map_get_or_insert("a", || String::from("alpha"));
+        let b = request.local_map_get_or_insert("b", || String::from("beta"));
+        assert_eq!(a, "alpha");
+        assert_eq!(b, "beta");
+
+        // Neither key's value is affected by later stores under the other.
+        let b2 = request.local_map_get_or_insert("b", || String::from("ignored"));
+        assert!(std::ptr::eq(b, b2));
+        assert_eq!(request.local_map_get::<String>("a"), Some(&String::from("alpha")));
+        assert_eq!(request.local_map_get::<String>("b"), Some(&String::from("beta")));
+        assert_eq!(request.local_map_get::<String>("c"), None);
+    }
+
+    #[test]
+    fn memoized_per_type_and_key() {
+        let client = Client::debug_with(vec![]).unwrap();
+        let request = client.get("/");
+        let calls = AtomicUsize::new(0);
+        let count = || calls.fetch_add(1, Ordering::SeqCst);
+
+        assert_eq!(*request.local_map_get_or_insert("k", count), 0);
+        assert_eq!(*request.local_map_get_or_insert("k", count), 0);
+        assert_eq!(*request.local_map_get_or_insert("k", || count() as u32), 1);
+        assert_eq!(*request.local_map_get_or_insert("j", count), 2);
+        assert_eq!(calls.load(Ordering::SeqCst), 3);
+
+        assert_eq!(request.local_map_get::<usize>("k"), Some(&0));
+        assert_eq!(request.local_map_get::<u32>("k"), Some(&1));
+        assert_eq!(request.local_map_get::<u64>("k"), None);
+    }
+
+    #[test]
+    fn separate_from_type_keyed_cache() {
+        let client = Client::debug_with(vec![]).unwrap();
+        let request = client.get("/");
+
+        request.local_cache(|| 1u8);
+        assert_eq!(request.local_map_get::<u8>(""), None);
+        assert_eq!(*request.local_map_get_or_insert("", || 2u8), 2);
+        assert_eq!(*request.local_cache(|| 3u8), 1);
+    }
+
+    #[test]
+    fn nested_insertion() {
+        let client = Client::debug_with(vec![]).unwrap();
+        let request = client.get("/");
+
+        let outer = request.local_map_get_or_insert("outer", || {
+            let inner = request.local_map_get_or_insert("inner", || 10u32);
+            *inner + 1
+        });
+
+        assert_eq!(*outer, 11);
+        assert_eq!(request.local_map_get::<u32>("inner"), Some(&10));
+    }
+
+    #[test]
+    fn guards_share_keyed_values() {
+        let rocket = rocket::build().manage(Parses::default()).mount("/", routes![index]);
+        let client = Client::debug(rocket).unwrap();
+
+        let response = client.get("/?owner=rust-lang&repo=rust").dispatch();
+        assert_eq!(response.into_string().unwrap(), "rust-lang/rust");
+
+        let parses = client.rocket().state::<Parses>().unwrap();
+        assert_eq!(parses.0.load(Ordering::SeqCst), 2);
+    }
+}
@@ -0,0 +1,79 @@
+#[macro_use] extern crate rocket;
+
+use rocket::request::{CountryCode, LanguageCode, IsoCodeError};
+
+#[get("/c/<country>/<lang>")]
+fn locale(country: CountryCode, lang: LanguageCode) -> String {
+    format!("{}-{}", lang, country)
+}
+
+#[get("/check/<country>")]
+fn check(country: Result<CountryCode, IsoCodeError<'_>>) -> String {
+    match country {
+        Ok(country) => country.to_string(),
+        Err(e) => e.to_string(),
+    }
+}
+
+mod locale_code_params_tests {
+    use super::*;
+    use rocket::http::Status;
+    use rocket::request::FromParam;
+    use rocket::local::blocking::Client;
+
+    #[test]
+    fn mixed_case_codes_are_normalized() {
+        for code in ["fi", "Fi", "fI", "FI"] {
+            assert_eq!(CountryCode::from_param(code).unwrap().as_str(), "FI");
+            assert_eq!(LanguageCode::from_param(code).unwrap().as_str(), "fi");
+        }
+
+        assert_eq!(&*CountryCode::from_param("gb").unwrap(), "GB");
+        assert_eq!(&*LanguageCode::from_param("Zu").unwrap(), "zu");
+        assert_eq!(CountryCode::from_param("ad"), CountryCode::from_param("AD"));
+        assert_eq!(LanguageCode::from_param("AA"), LanguageCode::from_param("aa"));
+    }
+
+    #[test]
+    fn unknown_codes() {
+        for code in ["ZZ", "uk", "xx", "EU", "aa"] {
+            assert_eq!(CountryCode::from_param(code), Err(IsoCodeError::Unknown(code)));
+        }
+
+        for code in ["xx", "US", "bh", "zz"] {
+            assert_eq!(LanguageCode::from_param(code), Err(IsoCodeError::Unknown(code)));
+        }
+
+        // Non-letters are never codes.
+        assert_eq!(CountryCode::from_param("1A"), Err(IsoCodeError::Unknown("1A")));
+        assert_eq!(LanguageCode::from_param("e-"), Err(IsoCodeError::Unknown("e-")));
+    }
+
+    #[test]
+    fn wrong_length() {
+        for code in ["", "F", "FIN", "en-US", "ééé"] {
+            assert_eq!(CountryCode::from_param(code), Err(IsoCodeError::InvalidLength(code)));
+            assert_eq!(LanguageCode::from_param(code), Err(IsoCodeError::InvalidLength(code)));
+        }
+
+        // Length is counted in characters, not bytes.
+        assert_eq!(CountryCode::from_param("éé"), Err(IsoCodeError::Unknown("éé")));
+    }
+
+    #[test]
+    fn routes() {
+        let client = Client::debug_with(routes![locale, check]).unwrap();
+        let get = |uri: &str| client.get(uri.to_string()).dispatch();
+
+        assert_eq!(get("/c/fi/FI").into_string().unwrap(), "fi-FI");
+        assert_eq!(get("/c/Us/eN").into_string().unwrap(), "en-US");
+        assert_eq!(get("/c/zz/en").status(), Status::NotFound);
+        assert_eq!(get("/c/us/xx").status(), Status::NotFound);
+        assert_eq!(get("/c/usa/en").status(), Status::NotFound);
+
+        assert_eq!(get("/check/de").into_string().unwrap(), "DE");
+        assert_eq!(get("/check/qq").into_string().unwrap(), "unknown code `qq`");
+        assert_eq!(get("/check/deu").into_string().unwrap(),
+            "code `deu` is not two characters long");
+    }
+}
@@ -0,0 +1,81 @@
+#[macro_use] extern crate rocket;
+
+use rocket::{Request, Response};
+use rocket::fairing::AdHoc;
+use rocket::http::Header;
+use rocket::request::{self, FromRequest};
+
+struct User;
+
+#[rocket::async_trait]
+impl<'r> FromRequest<'r> for User {
+    type Error = ();
+
+    async fn from_request(req: &'r Request<'_>) -> request::Outcome<Self, ()> {
+        req.log_field("user", "bob");
+        request::Outcome::Success(User)
+    }
+}
+
+struct Tenant;
+
+#[rocket::async_trait]
+impl<'r> FromRequest<'r> for Tenant {
+    type Error = ();
+
+    async fn from_request(req: &'r Request<'_>) -> request::Outcome<Self, ()> {
+        req.log_field("tenant", 42);
+        request::Outcome::Success(Tenant)
+    }
+}
+
+#[get("/")]
+fn index(_user: User, _tenant: Tenant) { }
+
+fn render(fields: Vec<(String, String)>) -> String {
+    fields.into_iter()
+        .map(|(k, v)| format!("{}={}", k, v))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+mod log_fields_tests {
+    use super::*;
+    use rocket::local::blocking::Client;
+
+    #[test]
+    fn fields_keep_insertion_order_and_duplicates() {
+        let client = Client::debug_with(vec![]).unwrap();
+        let request = client.get("/");
+        let req = request.inner();
+        assert!(req.log_fields().is_empty());
+
+        req.log_field("b", 1);
+        req.log_field("a", "x");
+        req.log_field("b", 2.5);
+        assert_eq!(render(req.log_fields()), "b=1 a=x b=2.5");
+
+        // Reading doesn't drain; taking does.
+        assert_eq!(render(req.take_log_fields()), "b=1 a=x b=2.5");
+        assert!(req.log_fields().is_empty());
+
+        req.log_field("c", 'c');
+        assert_eq!(render(req.take_log_fields()), "c=c");
+    }
+
+    #[test]
+    fn guard_fields_visible_to_response_fairing() {
+        let rocket = rocket::build()
+            .mount("/", routes![index])
+            .attach(AdHoc::on_response("Log", |req, res: &mut Response<'_>| {
+                Box::pin(async move {
+                    let fields = render(req.take_log_fields());
+                    res.set_header(Header::new("X-Log", fields));
+                })
+            }));
+
+        let client = Client::debug(rocket).unwrap();
+        let response = client.get("/").dispatch();
+        assert_eq!(response.headers().get_one("X-Log").unwrap(), "user=bob tenant=42");
+    }
+}
@@ -0,0 +1,96 @@
+#[macro_use] extern crate rocket;
+
+use rocket::data::{MaxBodySize, BodySizeError};
+
+#[post("/small", data = "<body>")]
+fn small(body: MaxBodySize<8>) -> String {
+    format!("small {}", body.len())
+}
+
+#[post("/large", data = "<body>")]
+fn large(body: MaxBodySize<{ 64 * 1024 }>) -> String {
+    format!("large {}", body.len())
+}
+
+#[post("/error", data = "<body>")]
+fn error(body: Result<MaxBodySize<8>, BodySizeError>) -> String {
+    match body {
+        Ok(body) => String::from_utf8(body.into_inner()).unwrap(),
+        Err(e) => e.to_string(),
+    }
+}
+
+mod max_body_size_tests {
+    use super::*;
+    use rocket::http::{Header, Status};
+    use rocket::local::blocking::{Client, LocalResponse};
+
+    fn client() -> Client {
+        Client::debug_with(routes![small, large, error]).unwrap()
+    }
+
+    fn post<'c>(
+        client: &'c Client,
+        uri: &str,
+        body: &str,
+        length: Option<&str>
+    ) -> LocalResponse<'c> {
+        let mut request = client.post(uri.to_string()).body(body);
+        if let Some(length) = length {
+            request.add_header(Header::new("Content-Length", length.to_string()));
+        }
+
+        request.dispatch()
+    }
+
+    #[test]
+    fn within_limit() {
+        let client = client();
+        let response = post(&client, "/small", "12345678", Some("8"));
+        assert_eq!(response.into_string().unwrap(), "small 8");
+
+        let response = post(&client, "/small", "", None);
+        assert_eq!(response.into_string().unwrap(), "small 0");
+
+        let body = "x".repeat(64 * 1024);
+        let response = post(&client, "/large", &body, None);
+        assert_eq!(response.into_string().unwrap(), "large 65536");
+
+        // The same body is too large for the other route.
+        assert_eq!(post(&client, "/small", &body, None).status(), Status::PayloadTooLarge);
+    }
+
+    #[test]
+    fn declared_over_limit() {
+        let client = client();
+
+        // The declared length alone is enough to reject the request.
+        let response = post(&client, "/small", "1234", Some("9"));
+        assert_eq!(response.status(), Status::PayloadTooLarge);
+
+        let response = post(&client, "/error", "1234", Some(" 1000000 "));
+        assert_eq!(response.into_string().unwrap(),
+            "declared length 1000000 exceeds the limit of 8");
+
+        // A malformed length is ignored in favor of the streamed length.
+        let response = post(&client, "/error", "1234", Some("lots"));
+        assert_eq!(response.into_string().unwrap(), "1234");
+    }
+
+    #[test]
+    fn undeclared_but_streams_over_limit() {
+        let client = client();
+        let response = post(&client, "/small", "123456789", None);
+        assert_eq!(response.status(), Status::PayloadTooLarge);
+
+        let response = post(&client, "/error", "123456789", None);
+        assert_eq!(response.into_string().unwrap(), "body exceeds the limit of 8");
+
+        // An understated length doesn't let a larger body through.
+        let response = post(&client, "/error", "123456789", Some("4"));
+        assert_eq!(response.into_string().unwrap(), "body exceeds the limit of 8");
+
+        let body = "x".repeat(64 * 1024 + 1);
+        assert_eq!(post(&client, "/large", &body, None).status(), Status::PayloadTooLarge);
+    }
+}
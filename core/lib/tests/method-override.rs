@@ -0,0 +1,128 @@
+#[macro_use] extern crate rocket;
+
+use rocket::Request;
+use rocket::request::{self, FromRequest};
+
+/// The routed and the received method of a request.
+struct Methods(String);
+
+#[rocket::async_trait]
+impl<'r> FromRequest<'r> for Methods {
+    type Error = ();
+
+    async fn from_request(req: &'r Request<'_>) -> request::Outcome<Self, ()> {
+        let methods = format!("{} (was {})", req.method(), req.original_method());
+        request::Outcome::Success(Methods(methods))
+    }
+}
+
+#[get("/")]
+fn get(methods: Methods) -> String { methods.0 }
+
+#[post("/")]
+fn post(methods: Methods) -> String { methods.0 }
+
+#[put("/")]
+fn put(methods: Methods) -> String { methods.0 }
+
+#[patch("/")]
+fn patch(methods: Methods) -> String { methods.0 }
+
+#[delete("/")]
+fn delete(methods: Methods) -> String { methods.0 }
+
+mod method_override_tests {
+    use super::*;
+    use rocket::http::{ContentType, Header, Method};
+    use rocket::fairing::AdHoc;
+    use rocket::local::blocking::Client;
+
+    fn client(method_override: bool) -> Client {
+        let config = rocket::Config { method_override, ..rocket::Config::debug_default() };
+        let rocket = rocket::custom(config)
+            .mount("/", routes![get, post, put, patch, delete])
+            .attach(AdHoc::on_response("Methods", |req, res| Box::pin(async move {
+                res.set_raw_header("X-Routed-Method", req.method().as_str());
+                res.set_raw_header("X-Original-Method", req.original_method().as_str());
+            })));
+
+        Client::debug(rocket).unwrap()
+    }
+
+    fn dispatch(client: &Client, method: Method, header: Option<&str>) -> String {
+        let mut request = client.req(method, "/");
+        if let Some(value) = header {
+            request.add_header(Header::new("X-HTTP-Method-Override", value.to_string()));
+        }
+
+        request.dispatch().into_string().unwrap()
+    }
+
+    #[test]
+    fn header_override_when_enabled() {
+        let client = client(true);
+        assert_eq!(dispatch(&client, Method::Post, None), "POST (was POST)");
+        assert_eq!(dispatch(&client, Method::Post, Some("DELETE")), "DELETE (was POST)");
+        assert_eq!(dispatch(&client, Method::Post, Some("put")), "PUT (was POST)");
+        assert_eq!(dispatch(&client, Method::Post, Some(" PATCH ")), "PATCH (was POST)");
+    }
+
+    #[test]
+    fn header_ignored_by_default() {
+        let client = client(false);
+        assert_eq!(dispatch(&client, Method::Post, Some("DELETE")), "POST (was POST)");
+        assert_eq!(dispatch(&client, Method::Post, Some("PUT")), "POST (was POST)");
+    }
+
+    #[test]
+    fn disallowed_overrides_are_ignored() {
+        let client = client(true);
+
+        // Only a `POST` can be overridden...
+        assert_eq!(dispatch(&client, Method::Get, Some("DELETE")), "GET (was GET)");
+        assert_eq!(dispatch(&client, Method::Put, Some("DELETE")), "PUT (was PUT)");
+
+        // ...and only into an unsafe method.
+        assert_eq!(dispatch(&client, Method::Post, Some("GET")), "POST (was POST)");
+        assert_eq!(dispatch(&client, Method::Post, Some("OPTIONS")), "POST (was POST)");
+        assert_eq!(dispatch(&client, Method::Post, Some("BREW")), "POST (was POST)");
+    }
+
+    #[test]
+    fn form_field_override() {
+        for enabled in [false, true] {
+            let client = client(enabled);
+            let response = client.post("/")
+                .header(ContentType::Form)
+                .body("_method=delete&item=1")
+                .dispatch();
+
+            assert_eq!(response.into_string().unwrap(), "DELETE (was POST)");
+        }
+
+        // The form field takes precedence over the header.
+        let client = client(true);
+        let response = client.post("/")
+            .header(ContentType::Form)
+            .header(Header::new("X-HTTP-Method-Override", "PATCH"))
+            .body("_method=put")
+            .dispatch();
+
+        assert_eq!(response.into_string().unwrap(), "PUT (was POST)");
+    }
+
+    #[test]
+    fn original_method_is_visible_after_routing() {
+        let client = client(true);
+        let response = client.head("/").dispatch();
+        assert_eq!(response.headers().get_one("X-Routed-Method"), Some("GET"));
+        assert_eq!(response.headers().get_one("X-Original-Method"), Some("HEAD"));
+
+        let response = client.post("/")
+            .header(Header::new("X-HTTP-Method-Override", "DELETE"))
+            .dispatch();
+
+        assert_eq!(response.headers().get_one("X-Routed-Method"), Some("DELETE"));
+        assert_eq!(response.headers().get_one("X-Original-Method"), Some("POST"));
+    }
+}
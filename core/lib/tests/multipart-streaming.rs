@@ -0,0 +1,161 @@
+#[macro_use] extern crate rocket;
+
+use rocket::{Rocket, Build};
+use rocket::form::{Multipart, MultipartField};
+use rocket::data::{Limits, ToByteUnit};
+use rocket::http::{ContentType, Status};
+use rocket::local::blocking::Client;
+use rocket::tokio::io::AsyncReadExt;
+
+const BOUNDARY: &str = "X-BOUNDARY";
+
+#[post("/", data = "<form>")]
+async fn summarize(mut form: Multipart<'_>) -> String {
+    let mut summary = vec![];
+    while let Some(field) = form.next().await {
+        match field {
+            Ok(MultipartField::Value(v)) => summary.push(format!("{}={}", v.name, v.value)),
+            Ok(MultipartField::Data(d)) => {
+                let file_name = d.file_name.and_then(|f| f.as_str()).unwrap_or("-");
+                let mut stream = d.data.open(16.mebibytes());
+                let (mut len, mut sum) = (0u64, 0u64);
+                let mut buf = vec![0; 8192];
+                loop {
+                    let n = match stream.read(&mut buf).await {
+                        Ok(0) => break,
+                        Ok(n) => n,
+                        Err(_) => {
+                            summary.push(format!("{}({}):incomplete", d.name, file_name));
+                            return summary.join(",");
+                        }
+                    };
+
+                    len += n as u64;
+                    sum = buf[..n].iter().fold(sum, |s, &b| s.wrapping_add(b as u64));
+                }
+
+                summary.push(format!("{}({}):{}:{}", d.name, file_name, len, sum));
+            }
+            Err(e) => summary.push(format!("error: {}", e)),
+        }
+    }
+
+    summary.join(",")
+}
+
+fn rocket() -> Rocket<Build> {
+    let mut config = rocket::Config::debug_default();
+    config.limits = Limits::default().limit("data-form", 32.mebibytes());
+    rocket::custom(config).mount("/", routes![summarize])
+}
+
+fn part(name: &str, file: Option<&str>, data: &[u8]) -> Vec<u8> {
+    let mut part = format!("--{}\r\n", BOUNDARY).into_bytes();
+    match file {
+        Some(file) => {
+            let disposition = format!("form-data; name=\"{}\"; filename=\"{}\"", name, file);
+            part.extend(format!("Content-Disposition: {}\r\n", disposition).bytes());
+            part.extend(b"Content-Type: application/octet-stream\r\n");
+        }
+        None => part.extend(format!("Content-Disposition: form-data; name=\"{}\"\r\n", name).bytes()),
+    }
+
+    part.extend(b"\r\n");
+    part.extend(data);
+    part.extend(b"\r\n");
+    part
+}
+
+fn finish(mut body: Vec<u8>) -> Vec<u8> {
+    body.extend(format!("--{}--\r\n", BOUNDARY).bytes());
+    body
+}
+
+fn content_type() -> ContentType {
+    format!("multipart/form-data; boundary={}", BOUNDARY).parse().unwrap()
+}
+
+fn checksum(data: &[u8]) -> u64 {
+    data.iter().fold(0u64, |s, &b| s.wrapping_add(b as u64))
+}
+
+#[test]
+fn test_streams_multi_megabyte_parts() {
+    let big: Vec<u8> = (0..(6 * 1024 * 1024 + 5)).map(|i| (i % 253) as u8).collect();
+    let small: Vec<u8> = (0..3000).map(|i| (i % 7) as u8).collect();
+
+    let mut body = part("title", None, b"holiday photos");
+    body.extend(part("big", Some("big.bin"), &big));
+    body.extend(part("small", Some("small.bin"), &small));
+    body.extend(part("note", None, b"thanks"));
+
+    let client = Client::debug(rocket()).unwrap();
+    let response = client.post("/")
+        .header(content_type())
+        .body(finish(body))
+        .dispatch();
+
+    assert_eq!(response.status(), Status::Ok);
+    assert_eq!(response.into_string().unwrap(), format!(
+        "title=holiday photos,big(big):{}:{},small(small):3000:{},note=thanks",
+        big.len(), checksum(&big), checksum(&small)
+    ));
+}
+
+#[test]
+fn test_parts_containing_boundary_prefixes() {
+    // Data that looks almost, but not quite, like a delimiter.
+    let tricky = format!("--{}\n\r-{}\r\n--X-BOUND\r\n-", BOUNDARY, BOUNDARY).into_bytes();
+    let body = finish(part("file", Some("f.txt"), &tricky));
+
+    let client = Client::debug(rocket()).unwrap();
+    let response = client.post("/").header(content_type()).body(body).dispatch();
+    assert_eq!(response.into_string().unwrap(), format!(
+        "file(f):{}:{}", tricky.len(), checksum(&tricky)
+    ));
+}
+
+#[test]
+fn test_unread_parts_are_skipped() {
+    #[post("/", data = "<form>")]
+    async fn names(mut form: Multipart<'_>) -> String {
+        let mut names = vec![];
+        while let Some(Ok(field)) = form.next().await {
+            match field {
+                MultipartField::Value(v) => names.push(v.name.to_string()),
+                MultipartField::Data(d) => names.push(d.name.to_string()),
+            }
+        }
+
+        names.join(",")
+    }
+
+    let large = vec![b'x'; 3 * 1024 * 1024];
+    let mut body = part("a", Some("a.bin"), &large);
+    body.extend(part("b", None, b"value"));
+    body.extend(part("c", Some("c.bin"), &large));
+
+    let rocket = rocket().mount("/names", routes![names]);
+    let client = Client::debug(rocket).unwrap();
+    let response = client.post("/names").header(content_type()).body(finish(body)).dispatch();
+    assert_eq!(response.into_string().unwrap(), "a,b,c");
+}
+
+#[test]
+fn test_forwards_non_multipart() {
+    let client = Client::debug(rocket()).unwrap();
+    let response = client.post("/").header(ContentType::Form).body("a=b").dispatch();
+    assert_eq!(response.status(), Status::NotFound);
+}
+
+#[test]
+fn test_data_form_limit_applies() {
+    let mut config = rocket::Config::debug_default();
+    config.limits = Limits::default().limit("data-form", 64.kibibytes());
+    let rocket = rocket::custom(config).mount("/", routes![summarize]);
+
+    let body = finish(part("big", Some("big.bin"), &vec![0; 256 * 1024]));
+    let client = Client::debug(rocket).unwrap();
+    let response = client.post("/").header(content_type()).body(body).dispatch();
+    assert_eq!(response.into_string().unwrap(), "big(big):incomplete");
+}
@@ -0,0 +1,156 @@
+#[macro_use] extern crate rocket;
+
+use std::path::PathBuf;
+
+use rocket::Request;
+use rocket::request::{self, FromRequest, RawBytes};
+
+fn describe(req: &Request<'_>) -> String {
+    req.params()
+        .map(|(name, value)| format!("{}={}", name, value))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+struct Params(String);
+
+#[rocket::async_trait]
+impl<'r> FromRequest<'r> for Params {
+    type Error = std::convert::Infallible;
+
+    async fn from_request(req: &'r Request<'_>) -> request::Outcome<Self, Self::Error> {
+        request::Outcome::Success(Params(describe(req)))
+    }
+}
+
+struct Tenant(u32);
+
+#[rocket::async_trait]
+impl<'r> FromRequest<'r> for Tenant {
+    type Error = ();
+
+    async fn from_request(req: &'r Request<'_>) -> request::Outcome<Self, ()> {
+        match req.param_named::<u32>("tenant") {
+            Some(Ok(id)) => request::Outcome::Success(Tenant(id)),
+            _ => request::Outcome::Forward(()),
+        }
+    }
+}
+
+struct Bytes(String);
+
+#[rocket::async_trait]
+impl<'r> FromRequest<'r> for Bytes {
+    type Error = ();
+
+    async fn from_request(req: &'r Request<'_>) -> request::Outcome<Self, ()> {
+        match req.param_named::<RawBytes<'_>>("id") {
+            Some(Ok(bytes)) => request::Outcome::Success(Bytes(hex(&bytes))),
+            _ => request::Outcome::Forward(()),
+        }
+    }
+}
+
+struct Checks(String);
+
+#[rocket::async_trait]
+impl<'r> FromRequest<'r> for Checks {
+    type Error = std::convert::Infallible;
+
+    async fn from_request(req: &'r Request<'_>) -> request::Outcome<Self, Self::Error> {
+        let single = req.param_named::<&str>("path").map(|r| r.unwrap().to_string());
+        let trailing = req.segments_named::<PathBuf>("path").map(|r| r.unwrap());
+        let as_single = req.segments_named::<PathBuf>("user").is_some();
+        let ignored = req.param_named::<&str>("_").is_some();
+        let string = format!("{:?} {:?} {} {}", single, trailing, as_single, ignored);
+        request::Outcome::Success(Checks(string))
+    }
+}
+
+#[get("/<user>/<_>/files/<path..>", rank = 2)]
+fn files(user: &str, path: PathBuf, params: Params, checks: Checks) -> String {
+    let _ = (user, path);
+    format!("{} | {}", params.0, checks.0)
+}
+
+#[get("/<tenant>/items/<id>")]
+fn items(tenant: &str, id: &str, guard: Tenant, params: Params) -> String {
+    let _ = tenant;
+    format!("{}:{} | {}", guard.0, id, params.0)
+}
+
+#[get("/bytes/<id>")]
+fn bytes(id: RawBytes<'_>, guard: Bytes) -> String {
+    format!("{} | {}", hex(&id), guard.0)
+}
+
+#[get("/<_..>", rank = 20)]
+fn ignored(params: Params) -> String {
+    format!("ignored [{}]", params.0)
+}
+
+mod named_route_params_tests {
+    use super::*;
+    use rocket::local::blocking::Client;
+
+    fn client() -> Client {
+        let rocket = rocket::build()
+            .mount("/", routes![files, items, bytes, ignored])
+            .mount("/api/v1", routes![items, bytes]);
+
+        Client::debug(rocket).unwrap()
+    }
+
+    fn get(client: &Client, uri: &str) -> String {
+        client.get(uri).dispatch().into_string().unwrap()
+    }
+
+    #[test]
+    fn mixed_single_and_trailing() {
+        let client = client();
+        assert_eq!(get(&client, "/bob/x/files/a/b/c.txt"),
+            r#"user=bob path=a/b/c.txt | None Some("a/b/c.txt") false false"#);
+
+        assert_eq!(get(&client, "/bob/x/files/a//b"),
+            r#"user=bob path=a//b | None Some("a/b") false false"#);
+
+        // Trailing parameters may match nothing.
+        assert_eq!(get(&client, "/bob/x/files"),
+            r#"user=bob path= | None Some("") false false"#);
+
+        assert_eq!(get(&client, "/bob/x/files/"),
+            r#"user=bob path= | None Some("") false false"#);
+    }
+
+    #[test]
+    fn values_are_raw() {
+        let client = client();
+        assert_eq!(get(&client, "/b%20b/x/files/a%20b/%C3%A9"),
+            r#"user=b%20b path=a%20b/%C3%A9 | None Some("a b/é") false false"#);
+
+        // Only a trailing parameter's raw value contains a `/`.
+        assert_eq!(get(&client, "/a%2Fb/x/files/c"),
+            r#"user=a%2Fb path=c | None Some("c") false false"#);
+    }
+
+    #[test]
+    fn named_params_with_base() {
+        let client = client();
+        assert_eq!(get(&client, "/7/items/abc"), "7:abc | tenant=7 id=abc");
+        assert_eq!(get(&client, "/api/v1/42/items/x%20y"), "42:x y | tenant=42 id=x%20y");
+
+        // `Tenant` forwards when the named parameter doesn't parse.
+        assert_eq!(get(&client, "/seven/items/abc"), "ignored []");
+    }
+
+    #[test]
+    fn named_params_see_raw_segment() {
+        let client = client();
+        assert_eq!(get(&client, "/bytes/%FF%00a"), "ff0061 | ff0061");
+        assert_eq!(get(&client, "/api/v1/bytes/%c3%28"), "c328 | c328");
+    }
+}
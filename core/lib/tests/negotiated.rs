@@ -0,0 +1,110 @@
+#[macro_use] extern crate rocket;
+
+use rocket::http::MediaType;
+use rocket::request::{Negotiate, Negotiated};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Format {
+    Json,
+    Html,
+    Csv,
+}
+
+impl Negotiate for Format {
+    const SUPPORTED: &'static [Self] = &[Format::Json, Format::Html, Format::Csv];
+
+    fn media_type(&self) -> MediaType {
+        match self {
+            Format::Json => MediaType::JSON,
+            Format::Html => MediaType::HTML,
+            Format::Csv => MediaType::CSV,
+        }
+    }
+}
+
+#[get("/")]
+fn index(format: Negotiated<Format>) -> String {
+    format!("{:?}", format.into_inner())
+}
+
+#[get("/fallback")]
+fn fallback(format: Option<Negotiated<Format>>) -> String {
+    format!("{:?}", format.map(|f| *f))
+}
+
+mod negotiated_tests {
+    use super::*;
+    use rocket::http::{Header, Status};
+    use rocket::local::blocking::Client;
+
+    fn negotiate(client: &Client, uri: &str, accept: Option<&str>) -> (Status, Option<String>) {
+        let mut request = client.get(uri.to_string());
+        if let Some(accept) = accept {
+            request.add_header(Header::new("Accept", accept.to_string()));
+        }
+
+        let response = request.dispatch();
+        (response.status(), response.into_string())
+    }
+
+    fn best(client: &Client, accept: &str) -> String {
+        let (status, body) = negotiate(client, "/", Some(accept));
+        assert_eq!(status, Status::Ok, "{}", accept);
+        body.unwrap()
+    }
+
+    fn client() -> Client {
+        Client::debug_with(routes![index, fallback]).unwrap()
+    }
+
+    #[test]
+    fn explicit_match() {
+        let client = client();
+        assert_eq!(best(&client, "text/html"), "Html");
+        assert_eq!(best(&client, "TEXT/CSV"), "Csv");
+        assert_eq!(best(&client, "application/json"), "Json");
+        assert_eq!(best(&client, "image/png, text/html;charset=utf-8"), "Html");
+        assert_eq!(best(&client, "text/html; charset=utf-8; q=0.5, text/csv;q=0.6"), "Csv");
+    }
+
+    #[test]
+    fn wildcard_match() {
+        let client = client();
+        assert_eq!(best(&client, "*/*"), "Json");
+        assert_eq!(best(&client, "text/*"), "Html");
+        assert_eq!(best(&client, "text/*, text/html;q=0"), "Csv");
+        assert_eq!(best(&client, "*/*;q=0.1, text/csv"), "Csv");
+
+        // Without a usable header, the first supported format is chosen.
+        assert_eq!(negotiate(&client, "/", None).1.unwrap(), "Json");
+        assert_eq!(best(&client, "not a media type"), "Json");
+    }
+
+    #[test]
+    fn quality_ordering() {
+        let client = client();
+        assert_eq!(best(&client, "application/json;q=0.5, text/html;q=0.9"), "Html");
+        assert_eq!(best(&client, "text/*;q=0.4, application/json;q=0.3"), "Html");
+        assert_eq!(best(&client, "*/*;q=0.8, application/json;q=0.2"), "Html");
+
+        // Ties are broken by the order of the supported formats.
+        assert_eq!(best(&client, "text/csv;q=0.5, text/html;q=0.5"), "Html");
+        assert_eq!(best(&client, "text/csv, application/json"), "Json");
+    }
+
+    #[test]
+    fn unacceptable() {
+        let client = client();
+        let (status, _) = negotiate(&client, "/", Some("image/png"));
+        assert_eq!(status, Status::NotAcceptable);
+
+        let (status, _) = negotiate(&client, "/", Some("*/*;q=0"));
+        assert_eq!(status, Status::NotAcceptable);
+
+        let (status, body) = negotiate(&client, "/fallback", Some("image/*, audio/ogg"));
+        assert_eq!((status, body.unwrap().as_str()), (Status::Ok, "None"));
+
+        let (_, body) = negotiate(&client, "/fallback", Some("text/csv"));
+        assert_eq!(body.unwrap(), "Some(Csv)");
+    }
+}
@@ -0,0 +1,80 @@
+#[macro_use] extern crate rocket;
+
+use std::num::{NonZeroU32, NonZeroI64};
+
+use rocket::request::{FromParam, NonZeroParamError};
+
+#[get("/page/<n>")]
+fn page(n: NonZeroU32) -> String {
+    format!("page {}", n)
+}
+
+#[get("/offset/<n>")]
+fn offset(n: Result<NonZeroI64, NonZeroParamError<'_>>) -> String {
+    match n {
+        Ok(n) => format!("offset {}", n),
+        Err(e) => e.to_string(),
+    }
+}
+
+mod nonzero_params_tests {
+    use super::*;
+    use std::num::*;
+    use rocket::http::Status;
+    use rocket::local::blocking::Client;
+
+    macro_rules! check_widths {
+        ($($T:ident: $Int:ident),+) => ($({
+            let max = $Int::MAX.to_string();
+            assert_eq!($T::from_param(&max).map(|n| n.get()), Ok($Int::MAX));
+            assert_eq!($T::from_param("1").map(|n| n.get()), Ok(1));
+            assert_eq!($T::from_param("0"), Err(NonZeroParamError::Zero("0")));
+            assert_eq!($T::from_param("+0"), Err(NonZeroParamError::Zero("+0")));
+
+            let over = format!("{}0", max);
+            assert_eq!($T::from_param(&over), Err(NonZeroParamError::Overflow(&over)));
+
+            let min = $Int::MIN.to_string();
+            let under = format!("{}0", min);
+            if min == "0" {
+                assert!(matches!($T::from_param("-1"), Err(NonZeroParamError::NotANumber(..))));
+            } else {
+                assert_eq!($T::from_param(&min).map(|n| n.get()), Ok($Int::MIN));
+                assert_eq!($T::from_param(&under), Err(NonZeroParamError::Overflow(&under)));
+            }
+
+            for bad in &["", "one", "1.0", "0x1", " 1"] {
+                assert!(matches!($T::from_param(bad), Err(NonZeroParamError::NotANumber(..))));
+            }
+        })+)
+    }
+
+    #[test]
+    fn every_width() {
+        check_widths! {
+            NonZeroU8: u8, NonZeroU16: u16, NonZeroU32: u32, NonZeroU64: u64,
+            NonZeroU128: u128, NonZeroUsize: usize,
+            NonZeroI8: i8, NonZeroI16: i16, NonZeroI32: i32, NonZeroI64: i64,
+            NonZeroI128: i128, NonZeroIsize: isize
+        }
+    }
+
+    #[test]
+    fn routes() {
+        let client = Client::debug_with(routes![page, offset]).unwrap();
+        let get = |uri: &str| client.get(uri.to_string()).dispatch();
+
+        assert_eq!(get("/page/1").into_string().unwrap(), "page 1");
+        assert_eq!(get("/page/4294967295").into_string().unwrap(), "page 4294967295");
+        assert_eq!(get("/page/0").status(), Status::NotFound);
+        assert_eq!(get("/page/4294967296").status(), Status::NotFound);
+        assert_eq!(get("/page/-1").status(), Status::NotFound);
+
+        assert_eq!(get("/offset/-3").into_string().unwrap(), "offset -3");
+        assert_eq!(get("/offset/0").into_string().unwrap(), "integer `0` is zero");
+        assert_eq!(get("/offset/9223372036854775808").into_string().unwrap(),
+            "integer `9223372036854775808` is out of range");
+        assert_eq!(get("/offset/ten").into_string().unwrap(),
+            "invalid integer `ten`: invalid digit found in string");
+    }
+}
@@ -0,0 +1,76 @@
+use std::borrow::Cow;
+
+use rocket::local::blocking::Client;
+
+fn normalized(client: &Client, uri: &str) -> (String, bool) {
+    let request = client.get(uri.to_string());
+    let path = request.inner().normalized_path();
+    let borrowed = matches!(path, Cow::Borrowed(_));
+    (path.into_owned(), borrowed)
+}
+
+fn assert_normalized(client: &Client, uri: &str, expected: &str) {
+    assert_eq!(normalized(client, uri).0, expected, "{:?}", uri);
+}
+
+#[test]
+fn canonical_paths_are_borrowed() {
+    let client = Client::debug_with(vec![]).unwrap();
+    for uri in &["/", "/a", "/a/b/c", "/a%2F..b/c", "/%2E%2E/a", "/.a/b..", "/a?x=//../"] {
+        let (path, borrowed) = normalized(&client, uri);
+        assert!(borrowed, "{:?} should be borrowed", uri);
+        assert_eq!(path, uri.split('?').next().unwrap());
+    }
+}
+
+#[test]
+fn duplicate_slashes_collapse() {
+    let client = Client::debug_with(vec![]).unwrap();
+    assert_normalized(&client, "//", "/");
+    assert_normalized(&client, "/a//b", "/a/b");
+    assert_normalized(&client, "///a///b///", "/a/b");
+}
+
+#[test]
+fn dot_segments_are_resolved() {
+    let client = Client::debug_with(vec![]).unwrap();
+    assert_normalized(&client, "/a//b/../c", "/a/c");
+    assert_normalized(&client, "/./a/./b/.", "/a/b");
+    assert_normalized(&client, "/a/b/c/../../d", "/a/d");
+    assert_normalized(&client, "/a/b/..", "/a");
+    assert_normalized(&client, "/a/..", "/");
+    assert_normalized(&client, "/a/.../b", "/a/.../b");
+    assert_normalized(&client, "/a/%2E/b/%2e%2e", "/a/%2E/b/%2e%2e");
+
+    let (slashy, _) = normalized(&client, "/a//b/../c");
+    let (plain, borrowed) = normalized(&client, "/a/c");
+    assert_eq!(slashy, plain);
+    assert!(borrowed);
+}
+
+#[test]
+fn trailing_slashes_are_removed() {
+    let client = Client::debug_with(vec![]).unwrap();
+    assert_normalized(&client, "/a/", "/a");
+    assert_normalized(&client, "/a/b//", "/a/b");
+    assert_normalized(&client, "/a/b/./", "/a/b");
+    assert_normalized(&client, "/a/b/../", "/a");
+}
+
+#[test]
+fn escaping_root_clamps_at_root() {
+    let client = Client::debug_with(vec![]).unwrap();
+    assert_normalized(&client, "/..", "/");
+    assert_normalized(&client, "/../..", "/");
+    assert_normalized(&client, "/../../etc/passwd", "/etc/passwd");
+    assert_normalized(&client, "/a/../../b", "/b");
+    assert_normalized(&client, "/a/b/../../../../c/./d/..", "/c");
+}
+
+#[test]
+fn query_is_untouched() {
+    let client = Client::debug_with(vec![]).unwrap();
+    let request = client.get("/a/./b/..//?q=/x/../y&&z");
+    assert_eq!(request.inner().normalized_path(), "/a");
+    assert_eq!(request.inner().uri().query().unwrap(), "q=/x/../y&&z");
+}
@@ -0,0 +1,159 @@
+#[macro_use] extern crate rocket;
+
+use std::sync::Mutex;
+
+use rocket::Request;
+use rocket::http::Status;
+use rocket::request::{self, FromRequest, Matched, OneOf, OneOfError};
+
+/// Records the order in which guards ran.
+#[derive(Default)]
+struct Log(Mutex<Vec<&'static str>>);
+
+impl Log {
+    fn take(&self) -> Vec<&'static str> {
+        std::mem::take(&mut *self.0.lock().unwrap())
+    }
+}
+
+macro_rules! guard {
+    ($name:ident => $outcome:expr) => (
+        #[derive(Debug, PartialEq)]
+        struct $name(&'static str);
+
+        #[rocket::async_trait]
+        impl<'r> FromRequest<'r> for $name {
+            type Error = &'static str;
+
+            async fn from_request(req: &'r Request<'_>) -> request::Outcome<Self, Self::Error> {
+                req.rocket().state::<Log>().unwrap().0.lock().unwrap().push(stringify!($name));
+                rocket::tokio::task::yield_now().await;
+                $outcome
+            }
+        }
+    )
+}
+
+guard!(ApiKey => request::Outcome::Success(ApiKey("key")));
+guard!(Session => request::Outcome::Success(Session("session")));
+guard!(Fails => request::Outcome::Failure((Status::Unauthorized, "no way")));
+guard!(Denied => request::Outcome::Failure((Status::Forbidden, "denied")));
+guard!(Forwards => request::Outcome::Forward(()));
+
+fn describe<A: std::fmt::Debug, B: std::fmt::Debug, C: std::fmt::Debug>(
+    matched: Matched<A, B, C>
+) -> String {
+    match matched {
+        Matched::A(a) => format!("A {:?}", a),
+        Matched::B(b) => format!("B {:?}", b),
+        Matched::C(c) => format!("C {:?}", c),
+    }
+}
+
+#[get("/first")]
+fn first(auth: OneOf<(ApiKey, Session, Fails)>) -> String {
+    describe(auth.into_inner())
+}
+
+#[get("/second")]
+fn second(auth: OneOf<(Forwards, Session, ApiKey)>) -> String {
+    describe(auth.into_inner())
+}
+
+#[get("/after-failure")]
+fn after_failure(auth: OneOf<(Fails, Forwards, Session)>) -> String {
+    describe(auth.into_inner())
+}
+
+#[get("/all-fail")]
+fn all_fail(_auth: OneOf<(Denied, Forwards, Fails)>) { }
+
+#[get("/forward-then-error")]
+fn forward_then_error(auth: Result<OneOf<(Forwards, Denied)>, OneOfError<'_>>) -> String {
+    match auth {
+        Ok(_) => "unreachable".into(),
+        Err(e) => format!("{} {:?}", e.index, e.error),
+    }
+}
+
+#[get("/all-forward")]
+fn all_forward(_auth: OneOf<(Forwards, Forwards)>) { }
+
+#[get("/all-forward", rank = 2)]
+fn all_forward_fallback() -> &'static str {
+    "fallback"
+}
+
+mod one_of_tests {
+    use super::*;
+    use rocket::local::blocking::Client;
+
+    fn client() -> Client {
+        let rocket = rocket::build()
+            .manage(Log::default())
+            .mount("/", routes![
+                first, second, after_failure, all_fail, forward_then_error,
+                all_forward, all_forward_fallback
+            ]);
+
+        Client::debug(rocket).unwrap()
+    }
+
+    fn ran(client: &Client) -> Vec<&'static str> {
+        client.rocket().state::<Log>().unwrap().take()
+    }
+
+    #[test]
+    fn first_success_stops_chain() {
+        let client = client();
+        let response = client.get("/first").dispatch();
+        assert_eq!(response.into_string().unwrap(), "A ApiKey(\"key\")");
+        assert_eq!(ran(&client), ["ApiKey"]);
+    }
+
+    #[test]
+    fn only_second_succeeds() {
+        let client = client();
+        let response = client.get("/second").dispatch();
+        assert_eq!(response.into_string().unwrap(), "B Session(\"session\")");
+        assert_eq!(ran(&client), ["Forwards", "Session"]);
+
+        // An earlier failure doesn't stop the chain either.
+        let response = client.get("/after-failure").dispatch();
+        assert_eq!(response.into_string().unwrap(), "C Session(\"session\")");
+        assert_eq!(ran(&client), ["Fails", "Forwards", "Session"]);
+    }
+
+    #[test]
+    fn all_fail_uses_last_failure() {
+        let client = client();
+        let response = client.get("/all-fail").dispatch();
+        assert_eq!(response.status(), Status::Unauthorized);
+        assert_eq!(ran(&client), ["Denied", "Forwards", "Fails"]);
+    }
+
+    #[test]
+    fn forward_then_error() {
+        let client = client();
+        let response = client.get("/forward-then-error").dispatch();
+        assert_eq!(response.into_string().unwrap(), "1 \"denied\"");
+        assert_eq!(ran(&client), ["Forwards", "Denied"]);
+    }
+
+    #[test]
+    fn all_forward_forwards() {
+        let client = client();
+        let response = client.get("/all-forward").dispatch();
+        assert_eq!(response.into_string().unwrap(), "fallback");
+        assert_eq!(ran(&client), ["Forwards", "Forwards"]);
+    }
+
+    #[test]
+    fn matched_index() {
+        let matched: Matched<u8, &str, char> = Matched::C('c');
+        assert_eq!(matched.index(), 2);
+
+        let error = OneOfError { index: 1, guard: "my::Guard", error: Box::new("bad") };
+        assert_eq!(error.to_string(), "guard 1 (`my::Guard`) failed: \"bad\"");
+    }
+}
@@ -0,0 +1,122 @@
+#[macro_use] extern crate rocket;
+
+use std::num::ParseIntError;
+use std::str::ParseBoolError;
+
+use rocket::http::Status;
+use rocket::outcome::{try_outcome, Outcome::*};
+use rocket::request::{self, Request, FromRequest, Outcome};
+
+#[derive(Debug)]
+enum PageError {
+    Missing,
+    Invalid(ParseIntError),
+}
+
+struct Page(u32);
+
+#[rocket::async_trait]
+impl<'r> FromRequest<'r> for Page {
+    type Error = PageError;
+
+    async fn from_request(req: &'r Request<'_>) -> request::Outcome<Self, PageError> {
+        let page = req.headers().get_one("X-Page");
+        let page = try_outcome!(page, (Status::BadRequest, PageError::Missing));
+        let page = page.parse::<u32>().map_err(PageError::Invalid);
+        Success(Page(try_outcome!(page, Status::UnprocessableEntity)))
+    }
+}
+
+struct Verbose(bool);
+
+#[rocket::async_trait]
+impl<'r> FromRequest<'r> for Verbose {
+    type Error = ParseBoolError;
+
+    async fn from_request(req: &'r Request<'_>) -> request::Outcome<Self, ParseBoolError> {
+        let value = req.headers().get_one("X-Verbose");
+        let value = try_outcome!(Outcome::from_option_or_forward(value));
+        Outcome::from_result_or_forward(value.parse().map(Verbose))
+    }
+}
+
+#[get("/")]
+fn page(page: Page, verbose: Option<Verbose>) -> String {
+    format!("{} {}", page.0, verbose.map_or(false, |v| v.0))
+}
+
+mod outcome_conversions_tests {
+    use super::*;
+    use rocket::http::Header;
+    use rocket::local::blocking::Client;
+
+    type Test = rocket::outcome::Outcome<u8, &'static str, ()>;
+
+    #[test]
+    fn from_result() {
+        assert_eq!(Test::from(Ok(1)), Success(1));
+        assert_eq!(Test::from(Err("bad")), Failure("bad"));
+
+        let outcome: Outcome<u8, &str> = Ok(1).into();
+        assert_eq!(outcome, Success(1));
+
+        let outcome: Outcome<u8, &str> = Err((Status::Conflict, "bad")).into();
+        assert_eq!(outcome, Failure((Status::Conflict, "bad")));
+    }
+
+    #[test]
+    fn from_option() {
+        assert_eq!(Test::from(Some(1)), Success(1));
+        assert_eq!(Test::from(None), Forward(()));
+
+        let outcome: Outcome<u8, &str> = None.into();
+        assert_eq!(outcome, Forward(()));
+    }
+
+    #[test]
+    fn status_mapping() {
+        let ok: Result<u8, &str> = Ok(1);
+        let err: Result<u8, &str> = Err("bad");
+
+        assert_eq!(Outcome::from_result_or(Status::BadRequest, ok), Success(1));
+        assert_eq!(Outcome::from_result_or(Status::Forbidden, err),
+            Failure((Status::Forbidden, "bad")));
+        assert_eq!(Outcome::from_result_or(Status::ImATeapot, err),
+            Failure((Status::ImATeapot, "bad")));
+
+        assert_eq!(Outcome::from_result_or_forward(ok), Success(1));
+        assert_eq!(Outcome::from_result_or_forward(err), Forward(()));
+
+        assert_eq!(Outcome::from_option_or(Status::Unauthorized, "none", Some(1)), Success(1));
+        assert_eq!(Outcome::<u8, _>::from_option_or(Status::Unauthorized, "none", None),
+            Failure((Status::Unauthorized, "none")));
+
+        assert_eq!(Outcome::<u8, ()>::from_option_or_forward(Some(1)), Success(1));
+        assert_eq!(Outcome::<u8, ()>::from_option_or_forward(None), Forward(()));
+    }
+
+    #[test]
+    fn try_outcome_with_failure() {
+        let client = Client::debug_with(routes![page]).unwrap();
+        let get = |headers: &[(&'static str, &'static str)]| {
+            let mut request = client.get("/");
+            for &(name, value) in headers {
+                request.add_header(Header::new(name, value));
+            }
+
+            request.dispatch()
+        };
+
+        let response = get(&[("X-Page", "3")]);
+        assert_eq!(response.into_string().unwrap(), "3 false");
+
+        let response = get(&[("X-Page", "3"), ("X-Verbose", "true")]);
+        assert_eq!(response.into_string().unwrap(), "3 true");
+
+        let response = get(&[("X-Page", "3"), ("X-Verbose", "maybe")]);
+        assert_eq!(response.into_string().unwrap(), "3 false");
+
+        assert_eq!(get(&[]).status(), Status::BadRequest);
+        assert_eq!(get(&[("X-Page", "three")]).status(), Status::UnprocessableEntity);
+    }
+}
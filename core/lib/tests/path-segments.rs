@@ -0,0 +1,58 @@
+use rocket::local::blocking::Client;
+
+fn segments(client: &Client, uri: &str) -> Vec<String> {
+    let request = client.get(uri.to_string());
+    let segments = request.inner().path_segments().unwrap().map(String::from).collect();
+    segments
+}
+
+#[test]
+fn multiple_segments() {
+    let client = Client::debug_with(vec![]).unwrap();
+    assert!(segments(&client, "/").is_empty());
+    assert_eq!(segments(&client, "/a"), ["a"]);
+    assert_eq!(segments(&client, "/a/b/c"), ["a", "b", "c"]);
+    assert_eq!(segments(&client, "/a//b///c?x=/y/"), ["a", "b", "c"]);
+    assert_eq!(segments(&client, "//a"), ["a"]);
+}
+
+#[test]
+fn trailing_slash_is_an_empty_segment() {
+    let client = Client::debug_with(vec![]).unwrap();
+    assert_eq!(segments(&client, "/a"), ["a"]);
+    assert_eq!(segments(&client, "/a/"), ["a", ""]);
+    assert_eq!(segments(&client, "/a/b/"), ["a", "b", ""]);
+    assert_eq!(segments(&client, "/a/b//"), ["a", "b", ""]);
+    assert_eq!(segments(&client, "/a/?q"), ["a", ""]);
+}
+
+#[test]
+fn segments_are_percent_decoded() {
+    let client = Client::debug_with(vec![]).unwrap();
+    assert_eq!(segments(&client, "/a%20b/c"), ["a b", "c"]);
+    assert_eq!(segments(&client, "/a%2Fb/"), ["a/b", ""]);
+    assert_eq!(segments(&client, "/%E2%9C%93"), ["\u{2713}"]);
+}
+
+#[test]
+fn invalid_utf8_is_an_error() {
+    let client = Client::debug_with(vec![]).unwrap();
+    for uri in &["/%FF", "/a/%C3", "/a/b%E2%9C/c"] {
+        let request = client.get(*uri);
+        assert!(request.inner().path_segments().is_err(), "{}", uri);
+    }
+
+    // The router still sees the lossily decoded segment.
+    let request = client.get("/%FF");
+    assert_eq!(request.inner().routed_segment(0), Some("\u{FFFD}"));
+}
+
+#[test]
+fn segments_match_routed_segments() {
+    let client = Client::debug_with(vec![]).unwrap();
+    let request = client.get("/x%20y/z/");
+    let routed: Vec<_> = request.inner().routed_segments(0..).collect();
+    let all: Vec<_> = request.inner().path_segments().unwrap().collect();
+    assert_eq!(&all[..routed.len()], &routed[..]);
+    assert_eq!(all.last(), Some(&""));
+}
@@ -0,0 +1,102 @@
+#[macro_use] extern crate rocket;
+
+use rocket::{Request, Response};
+use rocket::fairing::AdHoc;
+use rocket::http::{Cookie, CookieJar, Header, PendingCookie, SameSite};
+use rocket::request::{self, FromRequest};
+
+struct Tracker;
+
+#[rocket::async_trait]
+impl<'r> FromRequest<'r> for Tracker {
+    type Error = ();
+
+    async fn from_request(req: &'r Request<'_>) -> request::Outcome<Self, ()> {
+        req.cookies().add(Cookie::new("visit", "1"));
+        req.cookies().add(Cookie::new("theme", "light"));
+        request::Outcome::Success(Tracker)
+    }
+}
+
+#[get("/")]
+fn index(_tracker: Tracker, jar: &CookieJar<'_>) {
+    jar.remove(Cookie::named("visit"));
+    jar.add(Cookie::build("theme", "dark").same_site(SameSite::Lax).finish());
+}
+
+fn describe(pending: &PendingCookie) -> String {
+    let (op, c) = match pending {
+        PendingCookie::Add(c) => ("add", c),
+        PendingCookie::AddPrivate(c) => ("add_private", c),
+        PendingCookie::Remove(c) => ("remove", c),
+        PendingCookie::RemovePrivate(c) => ("remove_private", c),
+    };
+
+    format!("{} {}={} {:?}", op, c.name(), c.value(), c.same_site())
+}
+
+mod pending_cookies_tests {
+    use super::*;
+    use rocket::local::blocking::Client;
+
+    #[test]
+    fn pending_reflects_queue_order() {
+        let client = Client::debug_with(vec![]).unwrap();
+        let request = client.get("/");
+        let req = request.inner();
+        assert!(req.pending_cookies().is_empty());
+
+        req.cookies().add(Cookie::new("a", "1"));
+        req.cookies().add(Cookie::new("b", "2"));
+        req.cookies().remove(Cookie::named("a"));
+
+        let pending: Vec<_> = req.pending_cookies().iter().map(describe).collect();
+        assert_eq!(pending, [
+            "add a=1 Some(Strict)",
+            "add b=2 Some(Strict)",
+            "remove a= None",
+        ]);
+
+        // Reading the queue doesn't change it.
+        assert_eq!(req.pending_cookies().len(), 3);
+        assert!(req.cookies().get_pending("a").is_none());
+        assert_eq!(req.cookies().get_pending("b").unwrap().value(), "2");
+    }
+
+    #[test]
+    #[cfg(feature = "secrets")]
+    fn pending_private_cookies() {
+        let client = Client::debug_with(vec![]).unwrap();
+        let request = client.get("/");
+        let req = request.inner();
+
+        req.cookies().add_private(Cookie::new("token", "secret"));
+        req.cookies().remove_private(Cookie::named("token"));
+
+        let pending: Vec<_> = req.pending_cookies().iter().map(describe).collect();
+        assert_eq!(pending, ["add_private token=secret Some(Strict)", "remove_private token= None"]);
+    }
+
+    #[test]
+    fn pending_visible_to_response_fairing() {
+        let rocket = rocket::build()
+            .mount("/", routes![index])
+            .attach(AdHoc::on_response("Audit", |req, res: &mut Response<'_>| {
+                Box::pin(async move {
+                    let ops: Vec<_> = req.pending_cookies().iter().map(describe).collect();
+                    res.set_header(Header::new("X-Cookie-Ops", ops.join("; ")));
+                })
+            }));
+
+        let client = Client::debug(rocket).unwrap();
+        let response = client.get("/").dispatch();
+        assert_eq!(response.headers().get_one("X-Cookie-Ops").unwrap(),
+            "add visit=1 Some(Strict); add theme=light Some(Strict); \
+             remove visit= None; add theme=dark Some(Lax)");
+
+        // The changes are still sent with the response.
+        let cookies: Vec<_> = response.headers().get("Set-Cookie").collect();
+        assert_eq!(cookies.len(), 1, "{:?}", cookies);
+        assert!(cookies[0].starts_with("theme=dark"));
+    }
+}
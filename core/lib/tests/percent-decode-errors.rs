@@ -0,0 +1,156 @@
+#[macro_use] extern crate rocket;
+
+use std::convert::Infallible;
+
+use rocket::Request;
+use rocket::request::{PercentDecodeError, Strict, StrictError};
+
+#[get("/name/<name>")]
+fn name(name: String) -> String {
+    name
+}
+
+#[get("/strict/<name>")]
+fn strict(name: Strict<String>) -> String {
+    name.into_inner()
+}
+
+#[get("/result/<name>")]
+fn result(name: Result<Strict<&str>, StrictError<'_, Infallible>>) -> String {
+    match name {
+        Ok(name) => name.to_string(),
+        Err(e) => format!("error: {}", e),
+    }
+}
+
+#[get("/option/<name>")]
+fn option(name: Option<Strict<&str>>) -> String {
+    name.map(|name| name.to_string()).unwrap_or_else(|| "none".into())
+}
+
+#[get("/mounted/<a>/<b>")]
+fn two(a: Strict<&str>, b: Strict<&str>) -> String {
+    format!("{}+{}", a, b)
+}
+
+#[catch(404)]
+fn not_found(req: &Request<'_>) -> String {
+    req.uri().path().raw_segments()
+        .find_map(|s| PercentDecodeError::check(s.as_str()).err())
+        .map(|e| e.to_string())
+        .unwrap_or_else(|| "not found".into())
+}
+
+mod percent_decode_error_tests {
+    use super::*;
+    use rocket::http::Status;
+    use rocket::local::blocking::Client;
+
+    fn check(segment: &str) -> Result<(), (usize, &str)> {
+        PercentDecodeError::check(segment).map_err(|e| (e.offset(), e.bytes()))
+    }
+
+    #[test]
+    fn valid_escapes_pass() {
+        for segment in &["", "abc", "%20", "a%2Fb", "%C3%BC%e2%9c%93", "%25", "100%25"] {
+            assert_eq!(check(segment), Ok(()), "{:?}", segment);
+        }
+    }
+
+    #[test]
+    fn truncated_escapes_at_end() {
+        assert_eq!(check("%"), Err((0, "%")));
+        assert_eq!(check("abc%"), Err((3, "%")));
+        assert_eq!(check("abc%2"), Err((3, "%2")));
+        assert_eq!(check("abc%f"), Err((3, "%f")));
+    }
+
+    #[test]
+    fn non_hex_digits() {
+        assert_eq!(check("%2G"), Err((0, "%2G")));
+        assert_eq!(check("%G2"), Err((0, "%G2")));
+        assert_eq!(check("ab%zz"), Err((2, "%zz")));
+        assert_eq!(check("%%41"), Err((0, "%%4")));
+        assert_eq!(check("%+1"), Err((0, "%+1")));
+        assert_eq!(check("%é1"), Err((0, "%é1")));
+        assert_eq!(check("%1é"), Err((0, "%1é")));
+    }
+
+    #[test]
+    fn offset_is_relative_to_encoded_segment() {
+        // Decoded, the first bad escape is at byte 2; encoded, it's at byte 7.
+        assert_eq!(check("%41%42%2G"), Err((6, "%2G")));
+        assert_eq!(check("%C3%BCx%Z0"), Err((7, "%Z0")));
+        assert_eq!(check("a%20b%20c%"), Err((9, "%")));
+
+        // Only the first malformed escape is reported.
+        assert_eq!(check("%41%xx%yy"), Err((3, "%xx")));
+    }
+
+    #[test]
+    fn display_points_at_bad_byte() {
+        let error = PercentDecodeError::check("abcdefg%2G").unwrap_err();
+        assert_eq!(error.to_string(), "invalid percent-encoding at byte 7: '%2G'");
+        assert_eq!(error.segment(), "abcdefg%2G");
+
+        let error = PercentDecodeError::check("%41%4").unwrap_err();
+        assert_eq!(error.to_string(), "invalid percent-encoding at byte 3: '%4'");
+    }
+
+    #[test]
+    fn malformed_params_are_lenient_by_default() {
+        let client = Client::debug_with(routes![name]).unwrap();
+        for (uri, expected) in &[
+            ("/name/caf%C3%A9", "café"),
+            ("/name/100%25", "100%"),
+            ("/name/a%20b%2G", "a b%2G"),
+            ("/name/abc%", "abc%"),
+        ] {
+            let response = client.get(*uri).dispatch();
+            assert_eq!(response.status(), Status::Ok);
+            assert_eq!(response.into_string().unwrap(), *expected);
+        }
+    }
+
+    #[test]
+    fn strict_params_forward_to_catcher() {
+        let rocket = rocket::build()
+            .mount("/", routes![strict])
+            .mount("/base/x", routes![two])
+            .register("/", catchers![not_found]);
+
+        let client = Client::debug(rocket).unwrap();
+
+        let response = client.get("/strict/caf%C3%A9").dispatch();
+        assert_eq!(response.into_string().unwrap(), "café");
+
+        let response = client.get("/strict/100%25").dispatch();
+        assert_eq!(response.into_string().unwrap(), "100%");
+
+        let response = client.get("/strict/a%20b%2G").dispatch();
+        assert_eq!(response.status(), Status::NotFound);
+        assert_eq!(response.into_string().unwrap(), "invalid percent-encoding at byte 5: '%2G'");
+
+        let response = client.get("/strict/abc%").dispatch();
+        assert_eq!(response.status(), Status::NotFound);
+        assert_eq!(response.into_string().unwrap(), "invalid percent-encoding at byte 3: '%'");
+
+        let response = client.get("/base/x/mounted/%41/b").dispatch();
+        assert_eq!(response.into_string().unwrap(), "A+b");
+
+        let response = client.get("/base/x/mounted/a/%4x").dispatch();
+        assert_eq!(response.status(), Status::NotFound);
+        assert_eq!(response.into_string().unwrap(), "invalid percent-encoding at byte 0: '%4x'");
+    }
+
+    #[test]
+    fn strict_errors_reach_result_and_option() {
+        let client = Client::debug_with(routes![result, option]).unwrap();
+        let get = |uri: &str| client.get(uri.to_string()).dispatch().into_string().unwrap();
+
+        assert_eq!(get("/result/a%20b"), "a b");
+        assert_eq!(get("/result/a%2Gb"), "error: invalid percent-encoding at byte 1: '%2G'");
+        assert_eq!(get("/option/a%20b"), "a b");
+        assert_eq!(get("/option/a%2Gb"), "none");
+    }
+}
@@ -0,0 +1,138 @@
+#[macro_use] extern crate rocket;
+
+use rocket::request::Prefer;
+use rocket::response::Responder;
+use rocket::http::Header;
+
+#[derive(Responder)]
+struct Applied(&'static str, Header<'static>);
+
+#[get("/")]
+fn index(prefer: Prefer) -> Applied {
+    let applied = prefer.iter().map(|p| p.to_string()).collect::<Vec<_>>().join(", ");
+    let body = match prefer.return_minimal() {
+        true => "",
+        false => "full representation",
+    };
+
+    Applied(body, Header::new("Preference-Applied", applied))
+}
+
+mod prefer_header_tests {
+    use super::*;
+    use std::time::Duration;
+    use rocket::local::blocking::Client;
+
+    #[test]
+    fn return_minimal() {
+        let prefer = Prefer::parse("return=minimal");
+        assert!(prefer.return_minimal());
+        assert!(!prefer.return_representation());
+        assert_eq!(prefer.len(), 1);
+
+        let prefer = Prefer::parse("Return=\"minimal\"");
+        assert!(prefer.return_minimal());
+
+        let prefer = Prefer::parse("return=representation");
+        assert!(!prefer.return_minimal());
+        assert!(prefer.return_representation());
+
+        assert!(!Prefer::parse("").return_minimal());
+        assert!(!Prefer::parse("return").return_minimal());
+    }
+
+    #[test]
+    fn wait() {
+        assert_eq!(Prefer::parse("wait=10").wait(), Some(Duration::from_secs(10)));
+        assert_eq!(Prefer::parse("wait=\"5\"").wait(), Some(Duration::from_secs(5)));
+        assert_eq!(Prefer::parse("wait=soon").wait(), None);
+        assert_eq!(Prefer::parse("wait=-1").wait(), None);
+        assert_eq!(Prefer::parse("wait").wait(), None);
+        assert_eq!(Prefer::parse("respond-async").wait(), None);
+    }
+
+    #[test]
+    fn handling() {
+        let prefer = Prefer::parse("handling=strict");
+        assert!(prefer.handling_strict());
+        assert!(!prefer.handling_lenient());
+        assert_eq!(prefer.unknown().count(), 0);
+
+        let prefer = Prefer::parse("HANDLING=\"Lenient\"");
+        assert!(prefer.handling_lenient());
+        assert!(!prefer.handling_strict());
+
+        assert!(!Prefer::parse("handling").handling_strict());
+        assert!(!Prefer::parse("handling=loose").handling_lenient());
+    }
+
+    #[test]
+    fn multiple_preferences() {
+        let prefer = Prefer::parse("respond-async, wait=100,return=minimal");
+        assert!(prefer.respond_async());
+        assert!(prefer.return_minimal());
+        assert_eq!(prefer.wait(), Some(Duration::from_secs(100)));
+        assert_eq!(prefer.len(), 3);
+        assert_eq!(prefer.unknown().count(), 0);
+
+        // Only the first instance of a preference counts.
+        let prefer = Prefer::parse("return=minimal, RETURN=representation, wait=1, wait=2");
+        assert!(prefer.return_minimal());
+        assert!(!prefer.return_representation());
+        assert_eq!(prefer.wait(), Some(Duration::from_secs(1)));
+        assert_eq!(prefer.len(), 2);
+    }
+
+    #[test]
+    fn quoted_values_and_params() {
+        let prefer = Prefer::parse(r#"foo="a, b; c"; x=1; y="q\"z";; flag, bar"#);
+        assert_eq!(prefer.len(), 2);
+
+        let foo = prefer.get("FOO").unwrap();
+        assert_eq!(foo.name(), "foo");
+        assert_eq!(foo.value(), Some("a, b; c"));
+        assert_eq!(foo.params().len(), 3);
+        assert_eq!(foo.param("x"), Some(Some("1")));
+        assert_eq!(foo.param("Y"), Some(Some("q\"z")));
+        assert_eq!(foo.param("flag"), Some(None));
+        assert_eq!(foo.param("missing"), None);
+        assert_eq!(foo.to_string(), r#"foo="a, b; c""#);
+
+        let bar = prefer.get("bar").unwrap();
+        assert_eq!(bar.value(), None);
+        assert!(bar.params().is_empty());
+        assert_eq!(bar.to_string(), "bar");
+    }
+
+    #[test]
+    fn malformed_preferences_skipped() {
+        let prefer = Prefer::parse(r#"=x, a b, d=, , e; =f, g=minimal, c="open, h"#);
+        let names: Vec<_> = prefer.iter().map(|p| p.name()).collect();
+        assert_eq!(names, ["g"]);
+    }
+
+    #[test]
+    fn unknown_tokens_preserved() {
+        let value = "return=minimal, lenient, x-custom=\"on it\"; p=1, handling=strict";
+        let prefer = Prefer::parse(value);
+        let unknown: Vec<_> = prefer.unknown().map(|p| p.to_string()).collect();
+        assert_eq!(unknown, ["lenient", "x-custom=\"on it\""]);
+        assert_eq!(prefer.get("x-custom").unwrap().param("p"), Some(Some("1")));
+    }
+
+    #[test]
+    fn guard() {
+        let client = Client::debug_with(routes![index]).unwrap();
+        let response = client.get("/")
+            .header(Header::new("Prefer", "return=minimal, foo"))
+            .header(Header::new("Prefer", "wait=5, return=representation"))
+            .dispatch();
+
+        let applied = response.headers().get_one("Preference-Applied").unwrap();
+        assert_eq!(applied, "return=minimal, foo, wait=5");
+        assert_eq!(response.into_string().unwrap_or_default(), "");
+
+        let response = client.get("/").dispatch();
+        assert_eq!(response.into_string().unwrap(), "full representation");
+    }
+}
@@ -0,0 +1,144 @@
+#[macro_use] extern crate rocket;
+
+use rocket::request::{PreferredLanguage, SupportedLanguages};
+
+#[get("/")]
+fn index(lang: PreferredLanguage) -> String {
+    lang.to_string()
+}
+
+#[get("/", rank = 2)]
+fn fallback() -> &'static str {
+    "forwarded"
+}
+
+mod preferred_language_tests {
+    use super::*;
+    use rocket::http::Header;
+    use rocket::local::blocking::Client;
+    use rocket::request::LanguageTag;
+
+    fn client(languages: &[&str]) -> Client {
+        let rocket = rocket::build()
+            .manage(SupportedLanguages::new(languages).unwrap())
+            .mount("/", routes![index, fallback]);
+
+        Client::debug(rocket).unwrap()
+    }
+
+    fn negotiate(client: &Client, headers: &[&str]) -> String {
+        let mut request = client.get("/");
+        for value in headers {
+            request.add_header(Header::new("Accept-Language", value.to_string()));
+        }
+
+        request.dispatch().into_string().unwrap()
+    }
+
+    #[test]
+    fn browser_headers() {
+        let client = client(&["en-US", "en-GB", "fr", "de", "pt-BR"]);
+
+        // Chrome, Firefox, and Safari style headers.
+        assert_eq!(negotiate(&client, &["en-US,en;q=0.9"]), "en-US");
+        assert_eq!(negotiate(&client, &["en-GB,en;q=0.5"]), "en-GB");
+        assert_eq!(negotiate(&client, &["fr-FR,fr;q=0.9,en-US;q=0.8,en;q=0.7"]), "fr");
+        assert_eq!(negotiate(&client, &["de-CH"]), "forwarded");
+        assert_eq!(negotiate(&client, &["de-CH, de;q=0.8, *;q=0.1"]), "de");
+        assert_eq!(negotiate(&client, &["pt-BR,pt;q=0.9,en-US;q=0.8,en;q=0.7"]), "pt-BR");
+        assert_eq!(negotiate(&client, &["ja,en-US;q=0.9,en;q=0.8"]), "en-US");
+        assert_eq!(negotiate(&client, &["zh-CN,zh;q=0.9"]), "forwarded");
+
+        // A range matches every more specific supported tag.
+        assert_eq!(negotiate(&client, &["en"]), "en-US");
+        assert_eq!(negotiate(&client, &["pt"]), "pt-BR");
+
+        // Split across several headers.
+        assert_eq!(negotiate(&client, &["ja", "fr;q=0.9"]), "fr");
+    }
+
+    #[test]
+    fn case_insensitive() {
+        let client = client(&["en-US", "fr"]);
+        assert_eq!(negotiate(&client, &["EN-us"]), "en-US");
+        assert_eq!(negotiate(&client, &["FR;Q=0.5, ja"]), "fr");
+    }
+
+    #[test]
+    fn wildcard_and_exclusions() {
+        let client = client(&["en", "fr", "de"]);
+        assert_eq!(negotiate(&client, &["*"]), "en");
+        assert_eq!(negotiate(&client, &["*;q=0.5, de"]), "de");
+        assert_eq!(negotiate(&client, &["*, en;q=0"]), "fr");
+        assert_eq!(negotiate(&client, &["*, en;q=0, fr;q=0.000"]), "de");
+        assert_eq!(negotiate(&client, &["*;q=0"]), "forwarded");
+        assert_eq!(negotiate(&client, &["en;q=0, fr;q=0, de;q=0"]), "forwarded");
+    }
+
+    #[test]
+    fn specific_exclusions() {
+        // A more specific exclusion doesn't exclude the broader language.
+        let client = client(&["en-US", "en"]);
+        assert_eq!(negotiate(&client, &["en, en-US;q=0"]), "en");
+        assert_eq!(negotiate(&client, &["en;q=0, en-US;q=0.5"]), "en-US");
+    }
+
+    #[test]
+    fn empty_header_yields_default() {
+        let client = client(&["de", "en"]);
+        assert_eq!(negotiate(&client, &[]), "de");
+        assert_eq!(negotiate(&client, &[""]), "de");
+        assert_eq!(negotiate(&client, &[" , ,"]), "de");
+
+        // A header with only malformed entries is treated as empty.
+        assert_eq!(negotiate(&client, &["en_US, ;q=1, fr;q=2"]), "de");
+    }
+
+    #[test]
+    fn malformed_qvalues_ignored() {
+        let client = client(&["en", "fr", "de"]);
+        for header in &["fr;q=x, de;q=0.5", "fr;q=1.5, de;q=0.5", "fr;q=, de;q=0.5",
+                        "fr;q=0.12345, de;q=0.5", "fr;q=-1, de;q=0.5", "fr;z=1, de;q=0.5",
+                        "fr;q=1;q=1, de;q=0.5", "fr;q=1.001, de;q=0.5"] {
+            assert_eq!(negotiate(&client, &[header]), "de", "{:?}", header);
+        }
+
+        let ranges = PreferredLanguage::parse("en;q=1.000, fr;q=0.5, de;q=0.");
+        let ranges: Vec<_> = ranges.iter().map(|(t, q)| (t.as_str(), *q)).collect();
+        assert_eq!(ranges, [("en", 1.0), ("fr", 0.5), ("de", 0.0)]);
+    }
+
+    #[test]
+    fn duplicate_tags() {
+        let client = client(&["en", "fr"]);
+
+        // Only the first occurrence of a range counts.
+        assert_eq!(negotiate(&client, &["en;q=0.1, fr;q=0.5, en"]), "fr");
+        assert_eq!(negotiate(&client, &["en;q=0, EN, fr;q=0.1"]), "fr");
+
+        let ranges = PreferredLanguage::parse("en, fr;q=0.5, En;q=0.9, fr");
+        let tags: Vec<_> = ranges.iter().map(|(t, _)| t.as_str()).collect();
+        assert_eq!(tags, ["en", "fr"]);
+    }
+
+    #[test]
+    fn language_tags() {
+        let valid = ["en", "EN", "en-US", "zh-Hant-TW", "de-CH-1901", "x-private", "*"];
+        for tag in &valid {
+            assert!(tag.parse::<LanguageTag>().is_ok(), "{:?}", tag);
+        }
+
+        let invalid = ["", "-", "en-", "-en", "en--US", "e1", "toolongtag", "en-toolongtag",
+                       "en US", "en_US", "**", "en-*"];
+        for tag in &invalid {
+            assert!(tag.parse::<LanguageTag>().is_err(), "{:?}", tag);
+        }
+    }
+
+    #[test]
+    fn unmanaged_languages_abort() {
+        let rocket = rocket::build().mount("/", routes![index]);
+        let error = Client::debug(rocket).unwrap_err();
+        assert!(matches!(error.kind(), rocket::error::ErrorKind::SentinelAborts(..)));
+    }
+}
@@ -0,0 +1,109 @@
+#[macro_use] extern crate rocket;
+
+use rocket::request::{RangeHeader, ByteRangeSpec::*, Unsatisfiable};
+
+#[get("/")]
+fn ranges(range: RangeHeader) -> String {
+    format!("{:?}", range.resolve(1000))
+}
+
+#[get("/", rank = 2)]
+fn full() -> &'static str {
+    "full"
+}
+
+mod range_header_tests {
+    use super::*;
+    use rocket::http::Header;
+    use rocket::local::blocking::Client;
+
+    fn resolve(header: &str, len: u64) -> Result<Vec<(u64, u64)>, Unsatisfiable> {
+        RangeHeader::parse(header).expect("valid range header").resolve(len)
+    }
+
+    #[test]
+    fn parses_specs_in_request_order() {
+        let range = RangeHeader::parse("bytes=500-999, -100 ,0-0,,9500-").unwrap();
+        assert_eq!(range.specs(), &[FromTo(500, 999), Suffix(100), FromTo(0, 0), From(9500)]);
+
+        let range = RangeHeader::parse("BYTES=1-2").unwrap();
+        assert_eq!(range.specs(), &[FromTo(1, 2)]);
+    }
+
+    #[test]
+    fn rejects_malformed_headers() {
+        for header in &[
+            "", "bytes", "bytes=", "bytes=,", "bytes=-", "bytes=a-b", "bytes=1-a",
+            "bytes=5-4", "bytes=0-1,5-4", "bytes=1--2", "bytes=-+5", "bytes=+1-2",
+            "lines=0-5", "bytes 0-5", "bytes=0-99999999999999999999999",
+        ] {
+            assert_eq!(RangeHeader::parse(header), None, "{:?}", header);
+        }
+    }
+
+    #[test]
+    fn suffix_ranges() {
+        assert_eq!(resolve("bytes=-500", 10000), Ok(vec![(9500, 9999)]));
+        assert_eq!(resolve("bytes=-1", 10000), Ok(vec![(9999, 9999)]));
+        assert_eq!(resolve("bytes=-10000", 10000), Ok(vec![(0, 9999)]));
+        assert_eq!(resolve("bytes=-20000", 10000), Ok(vec![(0, 9999)]));
+        assert_eq!(resolve("bytes=-0", 10000), Err(Unsatisfiable { len: 10000 }));
+        assert_eq!(resolve("bytes=-5", 0), Err(Unsatisfiable { len: 0 }));
+    }
+
+    #[test]
+    fn open_ended_ranges() {
+        assert_eq!(resolve("bytes=0-", 10), Ok(vec![(0, 9)]));
+        assert_eq!(resolve("bytes=9-", 10), Ok(vec![(9, 9)]));
+        assert_eq!(resolve("bytes=10-", 10), Err(Unsatisfiable { len: 10 }));
+        assert_eq!(resolve("bytes=0-", 0), Err(Unsatisfiable { len: 0 }));
+    }
+
+    #[test]
+    fn overlapping_ranges_coalesce() {
+        assert_eq!(resolve("bytes=0-9,5-14", 100), Ok(vec![(0, 14)]));
+        assert_eq!(resolve("bytes=5-14,0-9", 100), Ok(vec![(0, 14)]));
+        assert_eq!(resolve("bytes=0-9,10-19", 100), Ok(vec![(0, 19)]));
+        assert_eq!(resolve("bytes=0-99,10-19", 100), Ok(vec![(0, 99)]));
+        assert_eq!(resolve("bytes=90-,-20", 100), Ok(vec![(80, 99)]));
+        assert_eq!(resolve("bytes=0-0,-1,50-", 100), Ok(vec![(0, 0), (50, 99)]));
+    }
+
+    #[test]
+    fn disjoint_ranges_are_kept() {
+        assert_eq!(resolve("bytes=0-9,20-29,40-49", 100), Ok(vec![(0, 9), (20, 29), (40, 49)]));
+        assert_eq!(resolve("bytes=40-49,0-9", 100), Ok(vec![(0, 9), (40, 49)]));
+        assert_eq!(resolve("bytes=0-9,11-19", 100), Ok(vec![(0, 9), (11, 19)]));
+    }
+
+    #[test]
+    fn out_of_bounds() {
+        assert_eq!(resolve("bytes=0-999", 100), Ok(vec![(0, 99)]));
+        assert_eq!(resolve("bytes=100-199", 100), Err(Unsatisfiable { len: 100 }));
+        assert_eq!(resolve("bytes=100-199,200-", 100), Err(Unsatisfiable { len: 100 }));
+        assert_eq!(resolve("bytes=100-199,0-4", 100), Ok(vec![(0, 4)]));
+        assert_eq!(resolve(&format!("bytes={}-", u64::MAX), u64::MAX), Err(Unsatisfiable {
+            len: u64::MAX
+        }));
+        assert_eq!(resolve(&format!("bytes=0-{}", u64::MAX), u64::MAX), Ok(vec![(0, u64::MAX - 1)]));
+
+        let error = Unsatisfiable { len: 100 };
+        assert_eq!(error.content_range(), "bytes */100");
+    }
+
+    #[test]
+    fn guard_forwards_when_absent_or_invalid() {
+        let client = Client::debug_with(routes![ranges, full]).unwrap();
+        let response = client.get("/").dispatch();
+        assert_eq!(response.into_string().unwrap(), "full");
+
+        let response = client.get("/").header(Header::new("Range", "pages=1-2")).dispatch();
+        assert_eq!(response.into_string().unwrap(), "full");
+
+        let response = client.get("/").header(Header::new("Range", "bytes=-500,0-0")).dispatch();
+        assert_eq!(response.into_string().unwrap(), "Ok([(0, 0), (500, 999)])");
+
+        let response = client.get("/").header(Header::new("Range", "bytes=1000-")).dispatch();
+        assert_eq!(response.into_string().unwrap(), "Err(Unsatisfiable { len: 1000 })");
+    }
+}
@@ -0,0 +1,169 @@
+#[macro_use] extern crate rocket;
+
+use std::time::Duration;
+
+use rocket::request::{self, Request, FromRequest};
+use rocket::rate_limit::{RateLimit, RateLimiter, RateLimitError};
+
+struct Never;
+
+#[rocket::async_trait]
+impl<'r> FromRequest<'r> for Never {
+    type Error = ();
+
+    async fn from_request(_: &'r Request<'_>) -> request::Outcome<Self, ()> {
+        request::Outcome::Forward(())
+    }
+}
+
+#[get("/")]
+fn index(limit: RateLimit) -> String {
+    limit.remaining().to_string()
+}
+
+#[get("/check")]
+fn check(limit: Result<RateLimit, RateLimitError>) -> String {
+    match limit {
+        Ok(limit) => format!("ok: {}", limit.remaining()),
+        Err(RateLimitError::Exhausted(_)) => "exhausted".into(),
+        Err(e) => e.to_string(),
+    }
+}
+
+#[get("/forwarded")]
+fn forwarding(_limit: RateLimit, _never: Never) -> &'static str {
+    unreachable!("the `Never` guard always forwards")
+}
+
+#[get("/forwarded", rank = 2)]
+fn forwarded(limit: RateLimit) -> String {
+    limit.remaining().to_string()
+}
+
+#[get("/", rank = 2)]
+fn unkeyed() -> &'static str {
+    "unkeyed"
+}
+
+mod rate_limit_guard_tests {
+    use super::*;
+    use std::net::SocketAddr;
+
+    use rocket::http::{Header, Status};
+    use rocket::local::blocking::{Client, LocalResponse};
+
+    const INTERVAL: Duration = Duration::from_millis(300);
+
+    fn client(limiter: RateLimiter) -> Client {
+        let rocket = rocket::build()
+            .attach(limiter)
+            .mount("/", routes![index, check, forwarding, forwarded, unkeyed]);
+
+        Client::debug(rocket).unwrap()
+    }
+
+    fn from<'c>(client: &'c Client, ip: &str) -> LocalResponse<'c> {
+        let remote: SocketAddr = format!("{}:8000", ip).parse().unwrap();
+        client.get("/").remote(remote).dispatch()
+    }
+
+    #[test]
+    fn burst_trips_limit_then_refills() {
+        let client = client(RateLimiter::new(3, INTERVAL));
+        for remaining in ["2", "1", "0"] {
+            let response = from(&client, "10.0.0.1");
+            assert_eq!(response.status(), Status::Ok);
+            assert!(response.headers().get_one("Retry-After").is_none());
+            assert_eq!(response.into_string().unwrap(), remaining);
+        }
+
+        let response = from(&client, "10.0.0.1");
+        assert_eq!(response.status(), Status::TooManyRequests);
+        assert_eq!(response.headers().get_one("Retry-After"), Some("1"));
+
+        // Another client has its own bucket.
+        assert_eq!(from(&client, "10.0.0.2").into_string().unwrap(), "2");
+
+        std::thread::sleep(INTERVAL);
+        let response = from(&client, "10.0.0.1");
+        assert_eq!(response.status(), Status::Ok);
+        assert_eq!(response.into_string().unwrap(), "0");
+        assert_eq!(from(&client, "10.0.0.1").status(), Status::TooManyRequests);
+    }
+
+    #[test]
+    fn spoofed_real_ip_is_ignored() {
+        let client = client(RateLimiter::new(2, Duration::from_secs(60)));
+        let get = |spoofed: &str| client.get("/")
+            .remote("10.0.0.1:8000".parse().unwrap())
+            .header(Header::new("X-Real-IP", spoofed.to_string()))
+            .dispatch();
+
+        assert_eq!(get("1.1.1.1").into_string().unwrap(), "1");
+        assert_eq!(get("2.2.2.2").into_string().unwrap(), "0");
+        assert_eq!(get("3.3.3.3").status(), Status::TooManyRequests);
+    }
+
+    #[test]
+    fn custom_key() {
+        let limiter = RateLimiter::new(1, Duration::from_secs(60))
+            .key(|req| req.headers().get_one("X-Api-Token").map(|t| t.to_string()));
+
+        let client = client(limiter);
+        let get = |token: &'static str| client.get("/check")
+            .header(Header::new("X-Api-Token", token))
+            .dispatch()
+            .into_string()
+            .unwrap();
+
+        assert_eq!(get("alpha"), "ok: 0");
+        assert_eq!(get("alpha"), "exhausted");
+        assert_eq!(get("beta"), "ok: 0");
+
+        // Requests without a key aren't limited: the guard forwards.
+        for _ in 0..3 {
+            assert_eq!(client.get("/").dispatch().into_string().unwrap(), "unkeyed");
+        }
+    }
+
+    #[test]
+    fn forwarded_requests_are_charged_once() {
+        let client = client(RateLimiter::new(2, Duration::from_secs(60)));
+        let get = || client.get("/forwarded").remote("10.0.0.1:8000".parse().unwrap()).dispatch();
+
+        assert_eq!(get().into_string().unwrap(), "1");
+        assert_eq!(get().into_string().unwrap(), "0");
+        assert_eq!(get().status(), Status::TooManyRequests);
+    }
+
+    #[rocket::async_test]
+    async fn concurrent_requests_share_a_bucket() {
+        use std::sync::Arc;
+        use rocket::local::asynchronous::Client;
+
+        let rocket = rocket::build()
+            .attach(RateLimiter::new(20, Duration::from_secs(60)))
+            .mount("/", routes![index]);
+
+        let client = Arc::new(Client::tracked(rocket).await.unwrap());
+        let remote: SocketAddr = "10.0.0.1:8000".parse().unwrap();
+        let requests = (0..40).map(|_| {
+            let client = client.clone();
+            rocket::tokio::spawn(async move {
+                client.get("/").remote(remote).dispatch().await.status()
+            })
+        });
+
+        let statuses = rocket::futures::future::join_all(requests).await;
+        let count = |status| statuses.iter().filter(|s| *s.as_ref().unwrap() == status).count();
+        assert_eq!(count(Status::Ok), 20);
+        assert_eq!(count(Status::TooManyRequests), 20);
+    }
+
+    #[test]
+    fn unattached_limiter_aborts_launch() {
+        let rocket = rocket::build().mount("/", routes![index]);
+        let error = Client::debug(rocket).unwrap_err();
+        assert!(matches!(error.kind(), rocket::error::ErrorKind::SentinelAborts(..)));
+    }
+}
@@ -0,0 +1,145 @@
+#![cfg(feature = "json")]
+
+#[macro_use] extern crate rocket;
+
+use rocket::Request;
+use rocket::data::{self, Data, FromData, RawBody};
+use rocket::http::Status;
+use rocket::outcome::Outcome::*;
+use rocket::serde::{Deserialize, json};
+
+/// A stand-in for an HMAC: the wrapping sum of the bytes, in hex.
+fn checksum(bytes: &[u8]) -> String {
+    format!("{:02x}", bytes.iter().fold(0u8, |sum, b| sum.wrapping_add(*b)))
+}
+
+#[derive(Deserialize)]
+#[serde(crate = "rocket::serde")]
+struct Event {
+    kind: String,
+}
+
+/// Verifies `X-Checksum` over the raw body, then parses the same bytes.
+struct Signed<'r> {
+    body: RawBody<'r>,
+    event: Event,
+}
+
+#[rocket::async_trait]
+impl<'r> FromData<'r> for Signed<'r> {
+    type Error = &'static str;
+
+    async fn from_data(req: &'r Request<'_>, data: Data<'r>) -> data::Outcome<'r, Self> {
+        let body = match RawBody::from_data(req, data).await {
+            Success(body) => body,
+            Failure((status, _)) => return Failure((status, "unreadable")),
+            Forward(data) => return Forward(data),
+        };
+
+        if req.headers().get_one("X-Checksum") != Some(&checksum(&body)) {
+            return Failure((Status::Unauthorized, "bad checksum"));
+        }
+
+        // The parser sees exactly the bytes that were verified.
+        let cached = RawBody::cached(req).unwrap();
+        assert_eq!(cached.as_ptr(), body.as_ptr());
+        match json::from_slice(&cached) {
+            Ok(event) => Success(Signed { body, event }),
+            Err(_) => Failure((Status::UnprocessableEntity, "malformed")),
+        }
+    }
+}
+
+#[post("/webhook", data = "<signed>")]
+fn webhook(signed: Signed<'_>) -> String {
+    format!("{} {:p}", signed.event.kind, signed.body.as_ptr())
+}
+
+#[post("/echo", data = "<body>")]
+fn echo(body: RawBody<'_>) -> Vec<u8> {
+    body.to_vec()
+}
+
+mod raw_body_tests {
+    use super::*;
+    use rocket::fairing::AdHoc;
+    use rocket::http::Header;
+    use rocket::local::blocking::Client;
+
+    fn client(limit: Option<u64>) -> Client {
+        let mut figment = rocket::Config::figment();
+        if let Some(limit) = limit {
+            figment = figment.merge(("limits.raw-body", limit));
+        }
+
+        let rocket = rocket::custom(figment)
+            .mount("/", routes![webhook, echo])
+            .attach(AdHoc::on_response("Raw Body", |req, res| Box::pin(async move {
+                if let Some(body) = RawBody::cached(req) {
+                    res.set_raw_header("X-Body-Ptr", format!("{:p}", body.as_ptr()));
+                    res.set_raw_header("X-Body-Sum", checksum(&body));
+                }
+            })));
+
+        Client::debug(rocket).unwrap()
+    }
+
+    #[test]
+    fn guards_observe_identical_bytes() {
+        let client = client(None);
+        let body = r#"{ "kind" : "push",  "extra": [1, 2] }"#;
+        let response = client.post("/webhook")
+            .header(Header::new("X-Checksum", checksum(body.as_bytes())))
+            .body(body)
+            .dispatch();
+
+        assert_eq!(response.status(), Status::Ok);
+        let ptr = response.headers().get_one("X-Body-Ptr").unwrap().to_string();
+        let sum = response.headers().get_one("X-Body-Sum").unwrap().to_string();
+        assert_eq!(sum, checksum(body.as_bytes()));
+        assert_eq!(response.into_string().unwrap(), format!("push {}", ptr));
+    }
+
+    #[test]
+    fn bytes_are_exact() {
+        let client = client(None);
+        let body: Vec<u8> = vec![0, 159, 146, 150, b'\r', b'\n', 255, b' '];
+        let response = client.post("/echo").body(&body).dispatch();
+        assert_eq!(response.headers().get_one("X-Body-Sum"), Some(&*checksum(&body)));
+        assert_eq!(response.into_bytes().unwrap(), body);
+
+        let response = client.post("/echo").dispatch();
+        assert_eq!(response.into_bytes().unwrap(), b"");
+    }
+
+    #[test]
+    fn verification_failure() {
+        let client = client(None);
+        let response = client.post("/webhook")
+            .header(Header::new("X-Checksum", "00"))
+            .body(r#"{"kind":"push"}"#)
+            .dispatch();
+
+        assert_eq!(response.status(), Status::Unauthorized);
+    }
+
+    #[test]
+    fn limit_is_enforced() {
+        let client = client(Some(16));
+        let response = client.post("/echo").body("0123456789abcdef").dispatch();
+        assert_eq!(response.status(), Status::Ok);
+        assert_eq!(response.into_string().unwrap(), "0123456789abcdef");
+
+        let response = client.post("/echo").body("0123456789abcdefg").dispatch();
+        assert_eq!(response.status(), Status::PayloadTooLarge);
+        assert!(response.headers().get_one("X-Body-Ptr").is_none());
+
+        let body = r#"{"kind":"push"}  "#;
+        let response = client.post("/webhook")
+            .header(Header::new("X-Checksum", checksum(body.as_bytes())))
+            .body(body)
+            .dispatch();
+
+        assert_eq!(response.status(), Status::PayloadTooLarge);
+    }
+}
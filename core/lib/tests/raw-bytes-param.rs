@@ -0,0 +1,77 @@
+#[macro_use] extern crate rocket;
+
+use rocket::request::{FromParam, RawBytes};
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+#[get("/bytes/<id>")]
+fn bytes(id: RawBytes<'_>) -> String {
+    hex(&id)
+}
+
+#[get("/text/<id>")]
+fn text(id: String) -> String {
+    hex(id.as_bytes())
+}
+
+#[get("/maybe/<id>")]
+fn maybe(id: Option<RawBytes<'_>>) -> String {
+    id.map(|id| hex(&id)).unwrap_or_else(|| "none".into())
+}
+
+mod raw_bytes_param_tests {
+    use super::*;
+    use std::borrow::Cow;
+    use rocket::http::{RawStr, Status};
+    use rocket::local::blocking::Client;
+
+    fn client() -> Client {
+        Client::debug_with(routes![bytes, text, maybe]).unwrap()
+    }
+
+    fn get(client: &Client, uri: &str) -> String {
+        client.get(uri.to_string()).dispatch().into_string().unwrap()
+    }
+
+    #[test]
+    fn decodes_non_utf8_bytes() {
+        let client = client();
+        assert_eq!(get(&client, "/bytes/%FF%FE%00%80"), "fffe0080");
+        assert_eq!(get(&client, "/bytes/a%C3%28b"), "61c32862");
+        assert_eq!(get(&client, "/bytes/%c3%bc"), "c3bc");
+        assert_eq!(get(&client, "/bytes/plain"), "706c61696e");
+        assert_eq!(get(&client, "/maybe/%ff"), "ff");
+
+        // The same segments are lossily decoded as strings.
+        assert_eq!(get(&client, "/text/%FF"), "efbfbd");
+        assert_eq!(get(&client, "/text/%c3%bc"), "c3bc");
+    }
+
+    #[test]
+    fn rejects_malformed_escapes() {
+        let client = client();
+        for uri in &["/bytes/%", "/bytes/%F", "/bytes/ab%4", "/bytes/%GG", "/bytes/%2G%20"] {
+            let response = client.get(*uri).dispatch();
+            assert_eq!(response.status(), Status::NotFound, "{}", uri);
+        }
+
+        let error = RawBytes::from_raw_param(RawStr::new("a%4"), "a%4").unwrap_err();
+        assert_eq!(error.offset(), 1);
+        assert_eq!(error.bytes(), "%4");
+    }
+
+    #[test]
+    fn borrows_without_escapes() {
+        let raw = RawStr::new("abc");
+        let bytes = RawBytes::from_raw_param(raw, "abc").unwrap();
+        assert!(matches!(bytes.into_inner(), Cow::Borrowed(b"abc")));
+
+        let bytes = RawBytes::from_raw_param(RawStr::new("%61bc"), "abc").unwrap();
+        assert!(matches!(bytes.into_inner(), Cow::Owned(_)));
+
+        // `from_param` sees the already-decoded segment.
+        assert_eq!(&*RawBytes::from_param("a%20").unwrap(), b"a%20");
+    }
+}
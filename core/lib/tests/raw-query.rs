@@ -0,0 +1,49 @@
+#[macro_use] extern crate rocket;
+
+use rocket::request::{RawQuery, SortedQuery};
+
+#[get("/callback")]
+fn callback(query: RawQuery<'_>) -> String {
+    format!("{}:{}", query.is_present(), query)
+}
+
+#[get("/both?<code>")]
+fn both(code: &str, raw: RawQuery<'_>, sorted: SortedQuery) -> String {
+    format!("{} {} {}", code, raw, sorted)
+}
+
+mod raw_query_tests {
+    use super::*;
+    use rocket::local::blocking::Client;
+
+    fn get(client: &Client, uri: &'static str) -> String {
+        client.get(uri).dispatch().into_string().unwrap()
+    }
+
+    #[test]
+    fn encoded_characters_are_verbatim() {
+        let client = Client::debug_with(routes![callback, both]).unwrap();
+        assert_eq!(get(&client, "/callback?state=a%2Fb&code=x+y%20z"),
+            "true:state=a%2Fb&code=x+y%20z");
+
+        // Case of escapes, empty segments, and order are all preserved.
+        assert_eq!(get(&client, "/callback?z=%2f&&a=%2F&flag&a="), "true:z=%2f&&a=%2F&flag&a=");
+        assert_eq!(get(&client, "/callback?%E2%9C%93=%e2%9c%93"), "true:%E2%9C%93=%e2%9c%93");
+
+        // ...while `SortedQuery` and query parameters see decoded values.
+        assert_eq!(get(&client, "/both?state=b&code=1+2"),
+            "1 2 state=b&code=1+2 code=1%202&state=b");
+    }
+
+    #[test]
+    fn empty_query() {
+        let client = Client::debug_with(routes![callback]).unwrap();
+        assert_eq!(get(&client, "/callback?"), "true:");
+    }
+
+    #[test]
+    fn no_query() {
+        let client = Client::debug_with(routes![callback]).unwrap();
+        assert_eq!(get(&client, "/callback"), "false:");
+    }
+}
@@ -0,0 +1,90 @@
+#[macro_use] extern crate rocket;
+
+use std::path::PathBuf;
+
+use rocket::request::RawSegments;
+use rocket::http::uri::error::PathError;
+
+#[get("/raw/<path..>")]
+fn raw(path: RawSegments<'_>) -> String {
+    path.to_string()
+}
+
+#[get("/each/<path..>")]
+fn each(path: RawSegments<'_>) -> String {
+    path.iter().map(|s| format!("[{}]", s)).collect()
+}
+
+#[get("/checked/<path..>")]
+fn checked(path: Result<RawSegments<'_>, PathError>) -> String {
+    match path {
+        Ok(path) => path.to_string(),
+        Err(e) => format!("{:?}", e),
+    }
+}
+
+#[get("/decoded/<path..>")]
+fn decoded(path: PathBuf) -> String {
+    path.display().to_string()
+}
+
+mod raw_segments_tests {
+    use super::*;
+    use rocket::http::Status;
+    use rocket::local::blocking::Client;
+
+    fn client() -> Client {
+        let rocket = rocket::build()
+            .mount("/", routes![raw, each, checked, decoded])
+            .mount("/mounted", routes![raw]);
+
+        Client::debug(rocket).unwrap()
+    }
+
+    #[test]
+    fn raw_segments_round_trip() {
+        let client = client();
+        for path in &["a%2Fb", "c%20d", "%C3%BC%E2%9C%93", "a+b", "x/%2F/y%2f",
+                      "caf%C3%A9/na%C3%AFve", "..", "%2E%2E/etc", "a/b/c"]
+        {
+            let response = client.get(format!("/raw/{}", path)).dispatch();
+            assert_eq!(response.into_string().unwrap(), *path);
+
+            let response = client.get(format!("/mounted/raw/{}", path)).dispatch();
+            assert_eq!(response.into_string().unwrap(), *path);
+        }
+    }
+
+    #[test]
+    fn raw_segments_differ_from_decoded() {
+        let client = client();
+        let response = client.get("/decoded/a%20b/c").dispatch();
+        assert_eq!(response.into_string().unwrap(), "a b/c");
+
+        let response = client.get("/raw/a%20b/c").dispatch();
+        assert_eq!(response.into_string().unwrap(), "a%20b/c");
+    }
+
+    #[test]
+    fn raw_segment_iter() {
+        let client = client();
+        let response = client.get("/each/a%2Fb//c%20d/%C3%BC").dispatch();
+        assert_eq!(response.into_string().unwrap(), "[a%2Fb][c%20d][%C3%BC]");
+
+        let response = client.get("/each").dispatch();
+        assert_eq!(response.into_string().unwrap(), "");
+    }
+
+    #[test]
+    fn raw_segments_reject_nul() {
+        let client = client();
+        let response = client.get("/raw/a/b%00c").dispatch();
+        assert_eq!(response.status(), Status::NotFound);
+
+        let response = client.get("/checked/a/b%00c").dispatch();
+        assert_eq!(response.into_string().unwrap(), "BadChar('\\0')");
+
+        let response = client.get("/checked/a/b%01c").dispatch();
+        assert_eq!(response.into_string().unwrap(), "a/b%01c");
+    }
+}
@@ -0,0 +1,104 @@
+#[macro_use] extern crate rocket;
+
+use std::time::Duration;
+
+use rocket::Request;
+use rocket::config::{Config, Deadline};
+use rocket::request::{self, FromRequest};
+
+/// The request's timeout, in milliseconds, as seen by a guard.
+struct Timeout(Option<u128>);
+
+#[rocket::async_trait]
+impl<'r> FromRequest<'r> for Timeout {
+    type Error = ();
+
+    async fn from_request(req: &'r Request<'_>) -> request::Outcome<Self, ()> {
+        let timeout = req.deadline().map(|d| (d - req.received_at()).as_millis());
+        request::Outcome::Success(Timeout(timeout))
+    }
+}
+
+#[get("/")]
+fn index(timeout: Timeout) -> String {
+    format!("{:?}", timeout.0)
+}
+
+mod request_deadline_tests {
+    use super::*;
+    use rocket::http::Header;
+    use rocket::local::blocking::Client;
+
+    fn client_with(deadline: Deadline) -> Client {
+        let config = Config { deadline, ..Config::debug_default() };
+        Client::debug(rocket::custom(config).mount("/", routes![index])).unwrap()
+    }
+
+    fn timeout(client: &Client, header: Option<(&'static str, &'static str)>) -> String {
+        let mut request = client.get("/");
+        if let Some((name, value)) = header {
+            request.add_header(Header::new(name, value));
+        }
+
+        request.dispatch().into_string().unwrap()
+    }
+
+    #[test]
+    fn header_provided_deadline() {
+        let client = client_with(Deadline::default());
+        assert_eq!(timeout(&client, None), "None");
+        assert_eq!(timeout(&client, Some(("X-Timeout-Ms", "1500"))), "Some(1500)");
+        assert_eq!(timeout(&client, Some(("x-timeout-ms", " 20 "))), "Some(20)");
+
+        // Values above the maximum are clamped.
+        assert_eq!(timeout(&client, Some(("X-Timeout-Ms", "90000"))), "Some(60000)");
+
+        let deadline = Deadline { header: "X-Budget".into(), max: 500, ..Default::default() };
+        let client = client_with(deadline);
+        assert_eq!(timeout(&client, Some(("X-Budget", "400"))), "Some(400)");
+        assert_eq!(timeout(&client, Some(("X-Budget", "4000"))), "Some(500)");
+        assert_eq!(timeout(&client, Some(("X-Timeout-Ms", "400"))), "None");
+    }
+
+    #[test]
+    fn default_deadline() {
+        let client = client_with(Deadline { default: 750, ..Default::default() });
+        assert_eq!(timeout(&client, None), "Some(750)");
+        assert_eq!(timeout(&client, Some(("X-Timeout-Ms", "100"))), "Some(100)");
+
+        let client = client_with(Deadline { default: 750, max: 300, ..Default::default() });
+        assert_eq!(timeout(&client, None), "Some(300)");
+
+        // With no header, the default always applies.
+        let deadline = Deadline { header: "".into(), default: 750, ..Default::default() };
+        let client = client_with(deadline);
+        assert_eq!(timeout(&client, Some(("X-Timeout-Ms", "100"))), "Some(750)");
+    }
+
+    #[test]
+    fn invalid_header_is_ignored() {
+        let client = client_with(Deadline { default: 750, ..Default::default() });
+        for bad in &["", "0", "-5", "1.5", "10ms", "abc", "99999999999999999999999"] {
+            assert_eq!(timeout(&client, Some(("X-Timeout-Ms", bad))), "Some(750)", "{:?}", bad);
+        }
+
+        let client = client_with(Deadline::default());
+        assert_eq!(timeout(&client, Some(("X-Timeout-Ms", "soon"))), "None");
+    }
+
+    #[test]
+    fn time_remaining() {
+        let client = client_with(Deadline::default());
+        let mut request = client.get("/");
+        assert_eq!(request.time_remaining(), None);
+
+        request.add_header(Header::new("X-Timeout-Ms", "60000"));
+        let remaining = request.time_remaining().unwrap();
+        assert!(remaining <= Duration::from_secs(60) && remaining > Duration::from_secs(50));
+
+        let mut request = client.get("/");
+        request.add_header(Header::new("X-Timeout-Ms", "1"));
+        std::thread::sleep(Duration::from_millis(5));
+        assert_eq!(request.time_remaining(), Some(Duration::ZERO));
+    }
+}
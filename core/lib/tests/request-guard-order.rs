@@ -0,0 +1,174 @@
+#[macro_use] extern crate rocket;
+
+use std::sync::Mutex;
+use std::time::Duration;
+
+use rocket::Request;
+use rocket::http::Status;
+use rocket::request::{self, FromRequest};
+use rocket::tokio::time::sleep;
+
+/// The per-request log of guard events, kept in request-local cache.
+struct Trace(Mutex<Vec<String>>);
+
+fn trace<'r>(req: &'r Request<'_>) -> &'r Trace {
+    req.local_cache(|| Trace(Mutex::new(vec![])))
+}
+
+fn record(req: &Request<'_>, event: String) {
+    trace(req).0.lock().unwrap().push(event);
+}
+
+fn events(req: &Request<'_>) -> String {
+    trace(req).0.lock().unwrap().join(",")
+}
+
+/// Guards that sleep for a decreasing amount of time: if they were polled
+/// concurrently, later guards would finish first.
+macro_rules! slow_guard {
+    ($name:ident, $millis:expr) => (
+        struct $name;
+
+        #[rocket::async_trait]
+        impl<'r> FromRequest<'r> for $name {
+            type Error = ();
+
+            async fn from_request(req: &'r Request<'_>) -> request::Outcome<Self, ()> {
+                record(req, format!("+{}", stringify!($name)));
+                sleep(Duration::from_millis($millis)).await;
+                record(req, format!("-{}", stringify!($name)));
+                request::Outcome::Success($name)
+            }
+        }
+    )
+}
+
+slow_guard!(A, 30);
+slow_guard!(B, 20);
+slow_guard!(C, 10);
+
+/// Stores the number of events seen so far, for `Reads` to retrieve.
+struct Writes;
+
+struct Count(usize);
+
+#[rocket::async_trait]
+impl<'r> FromRequest<'r> for Writes {
+    type Error = ();
+
+    async fn from_request(req: &'r Request<'_>) -> request::Outcome<Self, ()> {
+        rocket::tokio::task::yield_now().await;
+        let count = trace(req).0.lock().unwrap().len();
+        req.local_cache(|| Some(Count(count)));
+        record(req, "Writes".into());
+        request::Outcome::Success(Writes)
+    }
+}
+
+/// Fails unless `Writes` has already run.
+struct Reads(usize);
+
+#[rocket::async_trait]
+impl<'r> FromRequest<'r> for Reads {
+    type Error = &'static str;
+
+    async fn from_request(req: &'r Request<'_>) -> request::Outcome<Self, Self::Error> {
+        match req.local_cache(|| None::<Count>) {
+            Some(count) => request::Outcome::Success(Reads(count.0)),
+            None => request::Outcome::Failure((Status::InternalServerError, "no count")),
+        }
+    }
+}
+
+struct Fails;
+
+#[rocket::async_trait]
+impl<'r> FromRequest<'r> for Fails {
+    type Error = ();
+
+    async fn from_request(req: &'r Request<'_>) -> request::Outcome<Self, ()> {
+        record(req, "Fails".into());
+        request::Outcome::Failure((Status::Forbidden, ()))
+    }
+}
+
+/// Hands the request's trace to the handler.
+struct Events(String);
+
+#[rocket::async_trait]
+impl<'r> FromRequest<'r> for Events {
+    type Error = ();
+
+    async fn from_request(req: &'r Request<'_>) -> request::Outcome<Self, ()> {
+        request::Outcome::Success(Events(events(req)))
+    }
+}
+
+#[get("/abc")]
+fn abc(_a: A, _b: B, _c: C, events: Events) -> String {
+    events.0
+}
+
+#[get("/cba")]
+fn cba(_c: C, _b: B, _a: A, events: Events) -> String {
+    events.0
+}
+
+#[get("/<_id>/cache")]
+fn cache(_a: A, _w: Writes, _id: usize, reads: Reads, events: Events) -> String {
+    format!("{} {}", reads.0, events.0)
+}
+
+#[get("/fail")]
+fn fail(_a: A, _f: Fails, _b: B) { }
+
+#[catch(403)]
+fn forbidden(req: &Request<'_>) -> String {
+    events(req)
+}
+
+mod request_guard_order_tests {
+    use super::*;
+    use rocket::local::asynchronous::Client;
+
+    async fn client() -> Client {
+        let rocket = rocket::build()
+            .mount("/", routes![abc, cba, cache, fail])
+            .register("/", catchers![forbidden]);
+
+        Client::debug(rocket).await.unwrap()
+    }
+
+    async fn get(client: &Client, uri: &'static str) -> String {
+        client.get(uri).dispatch().await.into_string().await.unwrap()
+    }
+
+    #[rocket::async_test]
+    async fn guards_are_awaited_in_parameter_order() {
+        let client = client().await;
+        assert_eq!(get(&client, "/abc").await, "+A,-A,+B,-B,+C,-C");
+        assert_eq!(get(&client, "/cba").await, "+C,-C,+B,-B,+A,-A");
+    }
+
+    #[rocket::async_test]
+    async fn later_guards_see_earlier_local_cache() {
+        let client = client().await;
+        assert_eq!(get(&client, "/1/cache").await, "2 +A,-A,Writes");
+    }
+
+    #[rocket::async_test]
+    async fn failure_stops_later_guards() {
+        let client = client().await;
+        let response = client.get("/fail").dispatch().await;
+        assert_eq!(response.status(), Status::Forbidden);
+        assert_eq!(response.into_string().await.unwrap(), "+A,-A,Fails");
+    }
+
+    #[rocket::async_test]
+    async fn concurrent_requests_keep_their_own_order() {
+        let client = client().await;
+        let (abc, cba) = rocket::tokio::join!(get(&client, "/abc"), get(&client, "/cba"));
+        assert_eq!(abc, "+A,-A,+B,-B,+C,-C");
+        assert_eq!(cba, "+C,-C,+B,-B,+A,-A");
+    }
+}
@@ -0,0 +1,99 @@
+#[macro_use] extern crate rocket;
+
+use rocket::Request;
+use rocket::fairing::AdHoc;
+use rocket::request::{self, FromRequest, RequestId};
+
+/// A guard that also asks for the ID, as a logging guard might.
+struct Logged(String);
+
+#[rocket::async_trait]
+impl<'r> FromRequest<'r> for Logged {
+    type Error = ();
+
+    async fn from_request(req: &'r Request<'_>) -> request::Outcome<Self, ()> {
+        let id = req.guard::<&RequestId>().await.unwrap();
+        request::Outcome::Success(Logged(id.to_string()))
+    }
+}
+
+#[get("/")]
+fn index(id: &RequestId, logged: Logged) -> String {
+    assert_eq!(id.as_str(), logged.0);
+    id.to_string()
+}
+
+mod request_id_tests {
+    use super::*;
+    use rocket::http::Header;
+    use rocket::local::blocking::{Client, LocalResponse};
+
+    fn client() -> Client {
+        let rocket = rocket::build()
+            .mount("/", routes![index])
+            .attach(AdHoc::on_response("Request ID", |req, res| Box::pin(async move {
+                let id = req.guard::<&RequestId>().await.unwrap();
+                res.set_header(id);
+            })));
+
+        Client::debug(rocket).unwrap()
+    }
+
+    /// Returns the echoed header and the body, checking that they agree.
+    fn id_of(response: LocalResponse<'_>) -> String {
+        let header = response.headers().get_one("X-Request-Id").unwrap().to_string();
+        let body = response.into_string().unwrap();
+        assert_eq!(header, body);
+        body
+    }
+
+    fn is_generated(id: &str) -> bool {
+        id.len() == 32 && id.bytes().all(|b| b.is_ascii_hexdigit())
+    }
+
+    #[test]
+    fn header_present_and_valid() {
+        let client = client();
+        for id in &["abc-123", "0", "req_42/x:y", "f47ac10b-58cc-4372-a567-0e02b2c3d479"] {
+            let response = client.get("/").header(Header::new("X-Request-Id", *id)).dispatch();
+            assert_eq!(id_of(response), *id);
+        }
+
+        let max = "x".repeat(RequestId::MAX_LEN);
+        let response = client.get("/").header(Header::new("X-Request-Id", max.clone())).dispatch();
+        assert_eq!(id_of(response), max);
+    }
+
+    #[test]
+    fn header_present_and_garbage() {
+        let client = client();
+        let long = "x".repeat(RequestId::MAX_LEN + 1);
+        for garbage in &["", "has space", "tab\there", "ünïcödé", long.as_str()] {
+            let header = Header::new("X-Request-Id", garbage.to_string());
+            let id = id_of(client.get("/").header(header).dispatch());
+            assert!(is_generated(&id), "{:?} -> {:?}", garbage, id);
+        }
+    }
+
+    #[test]
+    fn header_absent() {
+        let client = client();
+        let first = id_of(client.get("/").dispatch());
+        let second = id_of(client.get("/").dispatch());
+        assert!(is_generated(&first));
+        assert!(is_generated(&second));
+        assert_ne!(first, second);
+    }
+
+    #[test]
+    fn parse_and_header_conversion() {
+        assert!(RequestId::parse("ok").is_some());
+        assert!(RequestId::parse("\u{7f}").is_none());
+
+        let id = RequestId::generate();
+        assert!(is_generated(&id));
+        let header: Header<'static> = (&id).into();
+        assert_eq!(header.name(), "X-Request-Id");
+        assert_eq!(header.value(), id.as_str());
+    }
+}
@@ -0,0 +1,69 @@
+#[macro_use] extern crate rocket;
+
+use std::time::{Duration, Instant};
+
+use rocket::Request;
+use rocket::fairing::AdHoc;
+use rocket::request::{self, FromRequest};
+
+/// The request's `received_at()` and the instant the guard ran.
+struct Timing {
+    received_at: Instant,
+    guard_ran: Instant,
+}
+
+#[rocket::async_trait]
+impl<'r> FromRequest<'r> for Timing {
+    type Error = ();
+
+    async fn from_request(req: &'r Request<'_>) -> request::Outcome<Self, ()> {
+        let timing = Timing { received_at: req.received_at(), guard_ran: Instant::now() };
+        request::Outcome::Success(timing)
+    }
+}
+
+#[get("/")]
+fn index(a: Timing, b: Timing) -> String {
+    let now = Instant::now();
+    format!("{} {}", a.received_at == b.received_at, a.received_at <= now)
+}
+
+#[get("/delayed")]
+fn delayed(timing: Timing) -> String {
+    let waited = timing.guard_ran.saturating_duration_since(timing.received_at);
+    (waited >= Duration::from_millis(50)).to_string()
+}
+
+mod request_received_at_tests {
+    use super::*;
+    use rocket::local::blocking::Client;
+
+    fn client() -> Client {
+        let rocket = rocket::build()
+            .mount("/", routes![index, delayed])
+            .attach(AdHoc::on_request("Delay", |req, _| Box::pin(async move {
+                if req.uri().path() == "/delayed" {
+                    rocket::tokio::time::sleep(Duration::from_millis(50)).await;
+                }
+            })));
+
+        Client::debug(rocket).unwrap()
+    }
+
+    #[test]
+    fn received_at_is_stable() {
+        let client = client();
+        let request = client.get("/");
+        let received = request.received_at();
+        assert_eq!(request.received_at(), received);
+        assert!(received <= Instant::now());
+
+        assert_eq!(request.dispatch().into_string().unwrap(), "true true");
+    }
+
+    #[test]
+    fn received_at_precedes_fairings_and_guards() {
+        let client = client();
+        assert_eq!(client.get("/delayed").dispatch().into_string().unwrap(), "true");
+    }
+}
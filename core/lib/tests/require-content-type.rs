@@ -0,0 +1,91 @@
+#[macro_use] extern crate rocket;
+
+use rocket::http::MediaType;
+use rocket::request::{RequireContentType, RequiredMediaType, ContentTypeError};
+
+struct Json;
+
+impl RequiredMediaType for Json {
+    fn media_type() -> MediaType {
+        MediaType::JSON
+    }
+}
+
+#[post("/strict", data = "<body>")]
+fn strict(ct: RequireContentType<Json>, body: &str) -> String {
+    format!("{}: {}", ct.content_type(), body)
+}
+
+#[post("/lenient", data = "<body>")]
+fn lenient(ct: Result<RequireContentType<Json>, ContentTypeError>, body: &str) -> String {
+    match ct {
+        Ok(_) => format!("ok: {}", body),
+        Err(e) => format!("error: {}", e),
+    }
+}
+
+mod require_content_type_tests {
+    use super::*;
+    use rocket::http::{Header, Status};
+    use rocket::local::blocking::{Client, LocalResponse};
+
+    const BODY: &str = "{ \"a\": 1 }";
+
+    fn post<'c>(client: &'c Client, uri: &'static str, ct: Option<&str>) -> LocalResponse<'c> {
+        let mut request = client.post(uri).body(BODY);
+        if let Some(ct) = ct {
+            request.add_header(Header::new("Content-Type", ct.to_string()));
+        }
+
+        request.dispatch()
+    }
+
+    #[test]
+    fn exact_match() {
+        let client = Client::debug_with(routes![strict, lenient]).unwrap();
+        let response = post(&client, "/strict", Some("application/json"));
+        assert_eq!(response.status(), Status::Ok);
+        assert_eq!(response.into_string().unwrap(), format!("application/json: {}", BODY));
+
+        let response = post(&client, "/strict", Some("APPLICATION/Json"));
+        assert_eq!(response.status(), Status::Ok);
+    }
+
+    #[test]
+    fn match_with_charset() {
+        let client = Client::debug_with(routes![strict, lenient]).unwrap();
+        let response = post(&client, "/strict", Some("application/json; charset=utf-8"));
+        assert_eq!(response.status(), Status::Ok);
+        assert_eq!(response.into_string().unwrap(),
+            format!("application/json; charset=utf-8: {}", BODY));
+
+        let response = post(&client, "/lenient", Some("application/json;charset=UTF-8;v=2"));
+        assert_eq!(response.into_string().unwrap(), format!("ok: {}", BODY));
+    }
+
+    #[test]
+    fn wrong_type() {
+        let client = Client::debug_with(routes![strict, lenient]).unwrap();
+        for ct in ["text/plain", "application/jsonx", "text/json", "application/*"] {
+            let response = post(&client, "/strict", Some(ct));
+            assert_eq!(response.status(), Status::UnsupportedMediaType, "{}", ct);
+        }
+
+        let response = post(&client, "/lenient", Some("text/plain; charset=utf-8"));
+        assert_eq!(response.into_string().unwrap(),
+            "error: unsupported media type `text/plain; charset=utf-8`");
+
+        let response = post(&client, "/lenient", Some("json"));
+        assert_eq!(response.into_string().unwrap(), "error: malformed `Content-Type` header");
+    }
+
+    #[test]
+    fn missing_header() {
+        let client = Client::debug_with(routes![strict, lenient]).unwrap();
+        let response = post(&client, "/strict", None);
+        assert_eq!(response.status(), Status::UnsupportedMediaType);
+
+        let response = post(&client, "/lenient", None);
+        assert_eq!(response.into_string().unwrap(), "error: missing `Content-Type` header");
+    }
+}
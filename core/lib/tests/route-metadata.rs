@@ -0,0 +1,98 @@
+#[macro_use] extern crate rocket;
+
+use rocket::{Request, Route};
+use rocket::fairing::AdHoc;
+use rocket::request::{self, FromRequest};
+
+/// Records what a guard can see of the matched route.
+struct RouteInfo(String);
+
+#[rocket::async_trait]
+impl<'r> FromRequest<'r> for RouteInfo {
+    type Error = ();
+
+    async fn from_request(req: &'r Request<'_>) -> request::Outcome<Self, ()> {
+        let info = match req.route() {
+            Some(route) => format!("{} {} {:?} {}",
+                route.method, route.uri, route.name.as_deref(), route.rank),
+            None => "none".into(),
+        };
+
+        request::Outcome::Success(RouteInfo(info))
+    }
+}
+
+#[get("/users/<_id>")]
+fn user(_id: usize, info: RouteInfo) -> String {
+    info.0
+}
+
+#[post("/users/<_id>/<_rest..>", rank = 7)]
+fn user_rest(_id: &str, _rest: std::path::PathBuf, info: RouteInfo) -> String {
+    info.0
+}
+
+#[get("/items/<_id>")]
+fn item_numeric(_id: usize, info: RouteInfo) -> String {
+    info.0
+}
+
+#[get("/items/<_id>", rank = 2)]
+fn item_any(_id: &str, info: RouteInfo) -> String {
+    info.0
+}
+
+#[get("/guard")]
+fn guard(route: &Route) -> String {
+    route.uri.to_string()
+}
+
+#[catch(404)]
+fn not_found(req: &Request<'_>) -> String {
+    format!("{:?}", req.route().map(|r| r.uri.to_string()))
+}
+
+mod route_metadata_tests {
+    use super::*;
+    use rocket::local::blocking::Client;
+
+    fn client() -> Client {
+        let rocket = rocket::build()
+            .mount("/api", routes![user, user_rest, item_numeric, item_any, guard])
+            .register("/", catchers![not_found])
+            .attach(AdHoc::on_request("Before Routing", |req, _| Box::pin(async move {
+                assert!(req.route().is_none());
+            })));
+
+        Client::debug(rocket).unwrap()
+    }
+
+    fn get(client: &Client, uri: &str) -> String {
+        client.get(uri.to_string()).dispatch().into_string().unwrap()
+    }
+
+    #[test]
+    fn guard_sees_pattern_and_method() {
+        let client = client();
+        assert_eq!(get(&client, "/api/users/42"), "GET /api/users/<_id> Some(\"user\") -5");
+
+        let response = client.post("/api/users/7/a/b").dispatch();
+        assert_eq!(response.into_string().unwrap(),
+            "POST /api/users/<_id>/<_rest..> Some(\"user_rest\") 7");
+
+        assert_eq!(get(&client, "/api/guard"), "/api/guard");
+    }
+
+    #[test]
+    fn forwarded_route_is_replaced() {
+        let client = client();
+        assert_eq!(get(&client, "/api/items/3"), "GET /api/items/<_id> Some(\"item_numeric\") -5");
+        assert_eq!(get(&client, "/api/items/x"), "GET /api/items/<_id> Some(\"item_any\") 2");
+    }
+
+    #[test]
+    fn unrouted_requests_have_no_route() {
+        let client = client();
+        assert_eq!(get(&client, "/nowhere"), "None");
+    }
+}
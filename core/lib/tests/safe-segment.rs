@@ -0,0 +1,73 @@
+#[macro_use] extern crate rocket;
+
+use rocket::request::{FromParam, SafeSegment, SafeSegmentError};
+
+#[get("/files/<name>")]
+fn file(name: SafeSegment<'_>) -> String {
+    format!("file {}", name)
+}
+
+#[get("/files/<_name>", rank = 2)]
+fn fallback(_name: &str) -> &'static str {
+    "rejected"
+}
+
+#[get("/check/<name>")]
+fn check(name: Result<SafeSegment<'_>, SafeSegmentError<'_>>) -> String {
+    match name {
+        Ok(name) => name.as_str().to_string(),
+        Err(e) => e.to_string(),
+    }
+}
+
+mod safe_segment_tests {
+    use super::*;
+    use rocket::local::blocking::Client;
+
+    fn get(client: &Client, uri: &str) -> String {
+        client.get(uri.to_string()).dispatch().into_string().unwrap()
+    }
+
+    #[test]
+    fn unicode_passes() {
+        for name in &["report.pdf", "día.png", "日本語", "🚀 launch", "a.b.c", ".hidden", "...", "a:b"] {
+            assert_eq!(SafeSegment::from_param(name).map(|s| s.as_str()), Ok(*name));
+        }
+    }
+
+    #[test]
+    fn path_significant_characters_are_rejected() {
+        assert_eq!(SafeSegment::from_param("a/b"), Err(SafeSegmentError::BadChar("a/b", '/')));
+        assert_eq!(SafeSegment::from_param("/"), Err(SafeSegmentError::BadChar("/", '/')));
+        assert_eq!(SafeSegment::from_param("a\\b"), Err(SafeSegmentError::BadChar("a\\b", '\\')));
+        assert_eq!(SafeSegment::from_param("a\0b"), Err(SafeSegmentError::BadChar("a\0b", '\0')));
+        assert_eq!(SafeSegment::from_param("a\nb"), Err(SafeSegmentError::BadChar("a\nb", '\n')));
+        assert_eq!(SafeSegment::from_param("."), Err(SafeSegmentError::DotSegment(".")));
+        assert_eq!(SafeSegment::from_param(".."), Err(SafeSegmentError::DotSegment("..")));
+    }
+
+    #[test]
+    fn decode_errors_are_distinguished() {
+        assert_eq!(SafeSegment::from_param("a\u{FFFD}"), Err(SafeSegmentError::Decode("a\u{FFFD}")));
+        assert!(matches!(SafeSegment::from_param("a/\u{FFFD}"), Err(SafeSegmentError::Decode(_))));
+    }
+
+    #[test]
+    fn routes() {
+        let client = Client::debug_with(routes![file, fallback, check]).unwrap();
+
+        assert_eq!(get(&client, "/files/r%C3%A9sum%C3%A9.txt"), "file résumé.txt");
+        assert_eq!(get(&client, "/files/a%20b"), "file a b");
+        assert_eq!(get(&client, "/files/a%2Fb"), "rejected");
+        assert_eq!(get(&client, "/files/a%2fb"), "rejected");
+        assert_eq!(get(&client, "/files/%00"), "rejected");
+        assert_eq!(get(&client, "/files/x%5C..%5Cy"), "rejected");
+        assert_eq!(get(&client, "/files/%2E%2E"), "rejected");
+        assert_eq!(get(&client, "/files/%FF"), "rejected");
+
+        assert_eq!(get(&client, "/check/a%2Fb"), "segment `a/b` contains forbidden character '/'");
+        assert_eq!(get(&client, "/check/a%00"), "segment `a\\0` contains forbidden character '\\0'");
+        assert_eq!(get(&client, "/check/%2E"), "segment `.` is a dot-segment");
+        assert_eq!(get(&client, "/check/%C3"), "segment `\u{FFFD}` is not valid UTF-8");
+    }
+}
@@ -0,0 +1,68 @@
+#![cfg(feature = "semver")]
+
+#[macro_use] extern crate rocket;
+
+use rocket::semver::{Version, VersionReq, Error};
+
+#[get("/v/<version>/resource")]
+fn resource(version: Version) -> String {
+    format!("{}.{}.{} {:?} {:?}", version.major, version.minor, version.patch,
+        version.pre.as_str(), version.build.as_str())
+}
+
+#[get("/compatible/<req>/<version>")]
+fn compatible(req: VersionReq, version: Version) -> String {
+    req.matches(&version).to_string()
+}
+
+#[get("/checked/<version>")]
+fn checked(version: Result<Version, Error>) -> String {
+    match version {
+        Ok(version) => version.to_string(),
+        Err(e) => format!("invalid: {}", e),
+    }
+}
+
+mod semver_param_tests {
+    use super::*;
+    use rocket::http::Status;
+    use rocket::local::blocking::Client;
+
+    fn client() -> Client {
+        Client::debug_with(routes![resource, compatible, checked]).unwrap()
+    }
+
+    fn get(client: &Client, uri: &str) -> String {
+        client.get(uri.to_string()).dispatch().into_string().unwrap()
+    }
+
+    #[test]
+    fn plain_versions() {
+        let client = client();
+        assert_eq!(get(&client, "/v/1.2.3/resource"), "1.2.3 \"\" \"\"");
+        assert_eq!(get(&client, "/v/0.0.0/resource"), "0.0.0 \"\" \"\"");
+        assert_eq!(get(&client, "/compatible/%5E1.2/1.4.0"), "true");
+        assert_eq!(get(&client, "/compatible/%3E=2,%20%3C3/1.4.0"), "false");
+    }
+
+    #[test]
+    fn pre_release_and_build_metadata() {
+        let client = client();
+        assert_eq!(get(&client, "/v/1.2.3-rc.1+build/resource"), "1.2.3 \"rc.1\" \"build\"");
+        assert_eq!(get(&client, "/v/1.0.0-alpha.beta.7/resource"), "1.0.0 \"alpha.beta.7\" \"\"");
+        assert_eq!(get(&client, "/v/2.0.0+sha.5114f85/resource"), "2.0.0 \"\" \"sha.5114f85\"");
+        assert_eq!(get(&client, "/checked/1.2.3-rc.1+build.5"), "1.2.3-rc.1+build.5");
+    }
+
+    #[test]
+    fn invalid_versions() {
+        let client = client();
+        for uri in ["/v/1.2/resource", "/v/v1.2.3/resource", "/v/1.2.3-/resource"] {
+            assert_eq!(client.get(uri).dispatch().status(), Status::NotFound, "{}", uri);
+        }
+
+        assert_eq!(get(&client, "/checked/1.2"),
+            "invalid: unexpected end of input while parsing minor version number");
+        assert!(get(&client, "/checked/01.2.3").starts_with("invalid: invalid leading zero"));
+    }
+}
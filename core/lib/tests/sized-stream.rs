@@ -0,0 +1,118 @@
+#[macro_use] extern crate rocket;
+
+use std::io::Cursor;
+
+use rocket::request::RangeHeader;
+use rocket::response::SizedStream;
+
+const DATA: &[u8] = b"0123456789abcdefghij";
+
+#[get("/known")]
+fn known(range: Option<RangeHeader>) -> SizedStream<Cursor<&'static [u8]>> {
+    SizedStream::new(Cursor::new(DATA), DATA.len() as u64).range(range)
+}
+
+#[get("/unknown")]
+fn unknown(range: Option<RangeHeader>) -> SizedStream<Cursor<&'static [u8]>> {
+    SizedStream::new(Cursor::new(DATA), None).range(range)
+}
+
+#[get("/short")]
+fn short(range: Option<RangeHeader>) -> SizedStream<Cursor<&'static [u8]>> {
+    SizedStream::new(Cursor::new(DATA), DATA.len() as u64 + 10).range(range)
+}
+
+#[get("/long")]
+fn long() -> SizedStream<Cursor<&'static [u8]>> {
+    SizedStream::new(Cursor::new(DATA), 5)
+}
+
+mod sized_stream_tests {
+    use super::*;
+    use rocket::http::{Header, Status};
+    use rocket::local::blocking::{Client, LocalResponse};
+
+    fn client() -> Client {
+        Client::debug_with(routes![known, unknown, short, long]).unwrap()
+    }
+
+    fn get<'c>(client: &'c Client, uri: &'static str, range: Option<&str>) -> LocalResponse<'c> {
+        let mut request = client.get(uri);
+        if let Some(range) = range {
+            request.add_header(Header::new("Range", range.to_string()));
+        }
+
+        request.dispatch()
+    }
+
+    #[test]
+    fn known_length() {
+        let client = client();
+        let response = get(&client, "/known", None);
+        assert_eq!(response.status(), Status::Ok);
+        assert_eq!(response.body().preset_size(), Some(DATA.len()));
+        assert!(response.headers().get_one("Content-Range").is_none());
+        assert_eq!(response.into_bytes().unwrap(), DATA);
+
+        // Bytes past the declared length are never sent.
+        let response = get(&client, "/long", None);
+        assert_eq!(response.body().preset_size(), Some(5));
+        assert_eq!(response.into_bytes().unwrap(), &DATA[..5]);
+    }
+
+    #[test]
+    fn unknown_length() {
+        let client = client();
+        let response = get(&client, "/unknown", None);
+        assert_eq!(response.status(), Status::Ok);
+        assert_eq!(response.body().preset_size(), None);
+        assert_eq!(response.into_bytes().unwrap(), DATA);
+
+        // Ranges can't be resolved without a length.
+        let response = get(&client, "/unknown", Some("bytes=0-4"));
+        assert_eq!(response.status(), Status::Ok);
+        assert_eq!(response.into_bytes().unwrap(), DATA);
+    }
+
+    #[test]
+    fn short_read_fails() {
+        let client = client();
+        let response = get(&client, "/short", None);
+        assert_eq!(response.body().preset_size(), Some(DATA.len() + 10));
+        assert!(response.into_bytes().is_none());
+
+        // A short read while skipping to the range fails, too.
+        let response = get(&client, "/short", Some("bytes=25-"));
+        assert_eq!(response.status(), Status::PartialContent);
+        assert!(response.into_bytes().is_none());
+    }
+
+    #[test]
+    fn sub_ranges() {
+        let client = client();
+        let response = get(&client, "/known", Some("bytes=5-9"));
+        assert_eq!(response.status(), Status::PartialContent);
+        assert_eq!(response.headers().get_one("Content-Range"), Some("bytes 5-9/20"));
+        assert_eq!(response.body().preset_size(), Some(5));
+        assert_eq!(response.into_bytes().unwrap(), b"56789");
+
+        let response = get(&client, "/known", Some("bytes=-3"));
+        assert_eq!(response.headers().get_one("Content-Range"), Some("bytes 17-19/20"));
+        assert_eq!(response.into_bytes().unwrap(), b"hij");
+
+        let response = get(&client, "/known", Some("bytes=15-100"));
+        assert_eq!(response.headers().get_one("Content-Range"), Some("bytes 15-19/20"));
+        assert_eq!(response.into_bytes().unwrap(), b"fghij");
+
+        let response = get(&client, "/known", Some("bytes=20-"));
+        assert_eq!(response.status(), Status::RangeNotSatisfiable);
+        assert_eq!(response.headers().get_one("Content-Range"), Some("bytes */20"));
+        assert!(response.into_bytes().unwrap_or_default().is_empty());
+
+        // Multiple disjoint ranges are ignored in favor of the full body.
+        let response = get(&client, "/known", Some("bytes=0-1, 5-6"));
+        assert_eq!(response.status(), Status::Ok);
+        assert!(response.headers().get_one("Content-Range").is_none());
+        assert_eq!(response.into_bytes().unwrap(), DATA);
+    }
+}
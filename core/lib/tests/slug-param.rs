@@ -0,0 +1,75 @@
+#[macro_use] extern crate rocket;
+
+use rocket::request::{Slug, SlugError};
+
+#[get("/posts/<slug>")]
+fn post(slug: Slug) -> String {
+    slug.into_inner()
+}
+
+#[get("/check/<slug>")]
+fn check(slug: Result<Slug, SlugError>) -> String {
+    match slug {
+        Ok(slug) => slug.to_string(),
+        Err(e) => e.to_string(),
+    }
+}
+
+mod slug_param_tests {
+    use super::*;
+    use rocket::http::Status;
+    use rocket::request::FromParam;
+    use rocket::local::blocking::Client;
+
+    #[test]
+    fn valid_slugs() {
+        for slug in ["a", "my-slug", "post-2021-10-01", "0", "a-b-c-d"] {
+            assert_eq!(Slug::from_param(slug).unwrap().as_str(), slug);
+        }
+    }
+
+    #[test]
+    fn uppercase_is_normalized() {
+        for slug in ["My-Slug", "MY-SLUG", "my-Slug", "my-slug"] {
+            assert_eq!(&*Slug::from_param(slug).unwrap(), "my-slug");
+        }
+
+        assert_eq!(Slug::from_param("My-Slug"), Slug::from_param("my-slug"));
+    }
+
+    #[test]
+    fn invalid_characters() {
+        assert_eq!(Slug::from_param(""), Err(SlugError::Empty));
+        assert_eq!(Slug::from_param("my_slug"), Err(SlugError::InvalidChar('_')));
+        assert_eq!(Slug::from_param("my slug"), Err(SlugError::InvalidChar(' ')));
+        assert_eq!(Slug::from_param("a.b"), Err(SlugError::InvalidChar('.')));
+        assert_eq!(Slug::from_param("café"), Err(SlugError::InvalidChar('é')));
+        assert_eq!(Slug::from_param("a/b"), Err(SlugError::InvalidChar('/')));
+    }
+
+    #[test]
+    fn hyphen_edge_cases() {
+        assert_eq!(Slug::from_param("my--slug").unwrap().as_str(), "my-slug");
+        assert_eq!(Slug::from_param("a---b--c").unwrap().as_str(), "a-b-c");
+
+        assert_eq!(Slug::from_param("-"), Err(SlugError::LeadingHyphen));
+        assert_eq!(Slug::from_param("--"), Err(SlugError::LeadingHyphen));
+        assert_eq!(Slug::from_param("-slug"), Err(SlugError::LeadingHyphen));
+        assert_eq!(Slug::from_param("slug-"), Err(SlugError::TrailingHyphen));
+        assert_eq!(Slug::from_param("slug--"), Err(SlugError::TrailingHyphen));
+    }
+
+    #[test]
+    fn routes() {
+        let client = Client::debug_with(routes![post, check]).unwrap();
+        let get = |uri: &str| client.get(uri.to_string()).dispatch();
+
+        assert_eq!(get("/posts/Hello--World").into_string().unwrap(), "hello-world");
+        assert_eq!(get("/posts/hello_world").status(), Status::NotFound);
+        assert_eq!(get("/posts/-hello").status(), Status::NotFound);
+
+        assert_eq!(get("/check/a-b").into_string().unwrap(), "a-b");
+        assert_eq!(get("/check/a%20b").into_string().unwrap(), "unexpected ' ' in slug");
+        assert_eq!(get("/check/ab-").into_string().unwrap(), "slug ends with `-`");
+    }
+}
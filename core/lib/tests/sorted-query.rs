@@ -0,0 +1,87 @@
+#[macro_use] extern crate rocket;
+
+use rocket::request::SortedQuery;
+
+#[get("/")]
+fn canonical(query: SortedQuery) -> String {
+    query.to_canonical_string()
+}
+
+mod sorted_query_tests {
+    use super::*;
+    use rocket::local::blocking::Client;
+
+    fn pairs(query: &str) -> Vec<(String, Option<String>)> {
+        SortedQuery::parse(query).iter()
+            .map(|(k, v)| (k.to_string(), v.map(|v| v.to_string())))
+            .collect()
+    }
+
+    fn pair(k: &str, v: Option<&str>) -> (String, Option<String>) {
+        (k.to_string(), v.map(|v| v.to_string()))
+    }
+
+    #[test]
+    fn empty_and_valueless() {
+        assert_eq!(pairs("a=&b"), [pair("a", Some("")), pair("b", None)]);
+        assert_eq!(pairs("flag"), [pair("flag", None)]);
+        assert_eq!(pairs("flag=&flag"), [pair("flag", None), pair("flag", Some(""))]);
+        assert_eq!(pairs("=x"), [pair("", Some("x"))]);
+        assert_eq!(pairs(""), []);
+        assert_eq!(pairs("&&&"), []);
+
+        let query = SortedQuery::parse("a=&b");
+        assert_eq!(query.to_string(), "a=&b");
+        assert!(query.contains_key("b"));
+        assert!(!query.contains_key("c"));
+    }
+
+    #[test]
+    fn duplicates_sort_by_value() {
+        let query = SortedQuery::parse("k=3&j=9&k=1&k=2&k=1");
+        assert_eq!(query.len(), 5);
+        assert_eq!(query.get_all("k").collect::<Vec<_>>(), [Some("1"), Some("1"), Some("2"), Some("3")]);
+        assert_eq!(query.get_all("j").collect::<Vec<_>>(), [Some("9")]);
+        assert_eq!(query.to_string(), "j=9&k=1&k=1&k=2&k=3");
+    }
+
+    #[test]
+    fn decoding() {
+        assert_eq!(pairs("q=hello+world"), [pair("q", Some("hello world"))]);
+        assert_eq!(pairs("q=a%2Bb"), [pair("q", Some("a+b"))]);
+        assert_eq!(pairs("a%20key=%C3%A9t%C3%A9"), [pair("a key", Some("été"))]);
+        assert_eq!(pairs("eq=a%3Db%26c"), [pair("eq", Some("a=b&c"))]);
+        assert_eq!(pairs("x=1=2"), [pair("x", Some("1=2"))]);
+    }
+
+    #[test]
+    fn equivalent_queries_share_canonical_form() {
+        let canonical = "a=1&b=x%20y&c";
+        for query in &["c&b=x+y&a=1", "a=1&c&b=x%20y", "b=x%20y&&a=%31&c&"] {
+            assert_eq!(SortedQuery::parse(query).to_canonical_string(), canonical, "{:?}", query);
+        }
+    }
+
+    #[test]
+    fn canonicalization_is_idempotent() {
+        for query in &[
+            "", "a", "a=", "b=2&a=1&b=1", "q=a+b%2Bc", "k=%26%3D%25&k", "%E2%9C%93=ok&=&==",
+            "z&y=&x=+&w=%20", "path=/a/b?c", "x=%zz&y=%", "emoji=%F0%9F%9A%80",
+        ] {
+            let once = SortedQuery::parse(query);
+            let twice = SortedQuery::parse(&once.to_canonical_string());
+            assert_eq!(once, twice, "{:?}", query);
+            assert_eq!(once.to_canonical_string(), twice.to_canonical_string(), "{:?}", query);
+        }
+    }
+
+    #[test]
+    fn guard_parses_request_query() {
+        let client = Client::debug_with(routes![canonical]).unwrap();
+        let response = client.get("/?b=2&a=+&flag&b=1").dispatch();
+        assert_eq!(response.into_string().unwrap(), "a=%20&b=1&b=2&flag");
+
+        let response = client.get("/").dispatch();
+        assert_eq!(response.into_string().unwrap(), "");
+    }
+}
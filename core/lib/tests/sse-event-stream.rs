@@ -0,0 +1,106 @@
+#[macro_use] extern crate rocket;
+
+use rocket::response::stream::{Event, EventStream, LastEventId};
+use rocket::tokio::time::{sleep, Duration};
+
+#[get("/framing")]
+fn framing() -> EventStream![] {
+    EventStream::from(EventStream! {
+        yield Event::data("line one\nline two\r\nline three").event("update");
+        yield Event::data("{\"cpu\": 42}").id("7");
+        yield Event::data("bare");
+    }).heartbeat(None)
+}
+
+#[get("/idle")]
+fn idle() -> EventStream![] {
+    EventStream::from(EventStream! {
+        yield Event::data("first");
+        sleep(Duration::from_millis(350)).await;
+        yield Event::data("second");
+    }).heartbeat(Duration::from_millis(100))
+}
+
+#[get("/resume")]
+fn resume(last: Option<LastEventId<'_>>) -> EventStream![] {
+    let start = last.and_then(|id| id.parse::<usize>().ok()).map_or(0, |id| id + 1);
+    EventStream::from(EventStream! {
+        for i in start..4 {
+            yield Event::data(format!("event {}", i)).id(i.to_string());
+        }
+    }).heartbeat(None)
+}
+
+#[get("/last")]
+fn last(id: LastEventId<'_>) -> &str {
+    id.as_str()
+}
+
+mod sse_event_stream_tests {
+    use super::*;
+    use rocket::http::{ContentType, Header, Status};
+    use rocket::local::blocking::Client;
+
+    fn client() -> Client {
+        Client::debug_with(routes![framing, idle, resume, last]).unwrap()
+    }
+
+    #[test]
+    fn multi_line_data_and_id_framing() {
+        let client = client();
+        let response = client.get("/framing").dispatch();
+        assert_eq!(response.content_type(), Some(ContentType::EventStream));
+        assert_eq!(response.into_string().unwrap(), concat!(
+            "event:update\ndata:line one\ndata:line two\ndata:line three\n\n",
+            "id:7\ndata:{\"cpu\": 42}\n\n",
+            "data:bare\n\n",
+        ));
+    }
+
+    #[test]
+    fn buffering_and_compression_are_disabled() {
+        let client = client();
+        let response = client.get("/framing").dispatch();
+        let headers = response.headers();
+        assert_eq!(headers.get_one("Cache-Control"), Some("no-cache, no-transform"));
+        assert_eq!(headers.get_one("X-Accel-Buffering"), Some("no"));
+        assert!(headers.get_one("Content-Encoding").is_none());
+    }
+
+    #[test]
+    fn keep_alive_comments_are_emitted_on_idle() {
+        let client = client();
+        let body = client.get("/idle").dispatch().into_string().unwrap();
+        let events: String = body.split_inclusive('\n').filter(|l| *l != ":\n").collect();
+        assert_eq!(events, "data:first\n\ndata:second\n\n");
+
+        // 350ms of idleness with a 100ms heartbeat: about three comments, plus
+        // possibly one sent eagerly.
+        let beats = body.matches(":\n").count();
+        assert!((2..=5).contains(&beats), "got {} beat(s) in {:?}", beats, body);
+        let idle = body.split("data:first\n").nth(1).unwrap();
+        assert!(idle.matches(":\n").count() >= 2, "{:?}", idle);
+    }
+
+    #[test]
+    fn last_event_id_resumes_stream() {
+        let client = client();
+        let body = client.get("/resume").dispatch().into_string().unwrap();
+        assert_eq!(body.matches("data:").count(), 4);
+        assert!(body.starts_with("id:0\ndata:event 0\n\n"));
+
+        let body = client.get("/resume")
+            .header(Header::new("Last-Event-ID", "1"))
+            .dispatch()
+            .into_string()
+            .unwrap();
+
+        assert_eq!(body, "id:2\ndata:event 2\n\nid:3\ndata:event 3\n\n");
+
+        let response = client.get("/last").header(Header::new("Last-Event-ID", "abc")).dispatch();
+        assert_eq!(response.into_string().unwrap(), "abc");
+
+        // Without the header, the guard forwards.
+        assert_eq!(client.get("/last").dispatch().status(), Status::NotFound);
+    }
+}
@@ -0,0 +1,111 @@
+#[macro_use] extern crate rocket;
+
+use rocket::data::{Text, TextError};
+
+#[post("/", data = "<text>")]
+fn echo(text: Text) -> String {
+    text.into_inner()
+}
+
+#[post("/check", data = "<text>")]
+fn check(text: Result<Text, TextError>) -> String {
+    match text {
+        Ok(text) => format!("ok: {}", &*text),
+        Err(e) => e.to_string(),
+    }
+}
+
+mod text_data_guard_tests {
+    use super::*;
+    use rocket::http::{ContentType, Status};
+    use rocket::local::blocking::Client;
+
+    fn client() -> Client {
+        Client::debug_with(routes![echo, check]).unwrap()
+    }
+
+    fn post(client: &Client, content_type: &str, body: &[u8]) -> (Status, String) {
+        let content_type: ContentType = content_type.parse().unwrap();
+        let response = client.post("/").header(content_type).body(body).dispatch();
+        (response.status(), response.into_string().unwrap_or_default())
+    }
+
+    fn check(client: &Client, content_type: &str, body: &[u8]) -> String {
+        let content_type: ContentType = content_type.parse().unwrap();
+        let response = client.post("/check").header(content_type).body(body).dispatch();
+        response.into_string().unwrap()
+    }
+
+    #[test]
+    fn utf8_is_the_default() {
+        let client = client();
+        let response = client.post("/").body("héllo ✓").dispatch();
+        assert_eq!(response.into_string().unwrap(), "héllo ✓");
+
+        let body = "héllo ✓".as_bytes();
+        assert_eq!(post(&client, "text/plain", body), (Status::Ok, "héllo ✓".into()));
+        assert_eq!(post(&client, "text/plain; charset=utf-8", body).1, "héllo ✓");
+        assert_eq!(post(&client, "text/plain; charset=UTF8", body).1, "héllo ✓");
+    }
+
+    #[test]
+    fn latin1_is_decoded() {
+        let client = client();
+        let body = b"caf\xe9 \xa3 \xff";
+        for content_type in [
+            "text/plain; charset=ISO-8859-1",
+            "text/plain; charset=iso-8859-1",
+            "text/plain; charset=latin1",
+            "text/plain; charset=\"ISO-8859-1\"",
+        ] {
+            let (status, body) = post(&client, content_type, body);
+            assert_eq!(status, Status::Ok, "{}", content_type);
+            assert_eq!(body, "café £ ÿ");
+        }
+
+        // The same bytes aren't valid UTF-8...
+        assert_eq!(post(&client, "text/plain", body).0, Status::BadRequest);
+
+        // ...and are valid US-ASCII only without the high bytes.
+        let (_, body) = post(&client, "text/plain; charset=us-ascii", b"cafe");
+        assert_eq!(body, "cafe");
+    }
+
+    #[test]
+    fn unsupported_charset() {
+        let client = client();
+        let body = b"\x82\xa0";
+        let (status, _) = post(&client, "text/plain; charset=Shift_JIS", body);
+        assert_eq!(status, Status::UnsupportedMediaType);
+        assert_eq!(check(&client, "text/plain; charset=Shift_JIS", body),
+            "unsupported charset `Shift_JIS`");
+
+        assert_eq!(check(&client, "text/plain; charset=utf-16", b"h\0i\0"),
+            "unsupported charset `utf-16`");
+    }
+
+    #[test]
+    fn invalid_bytes_are_rejected() {
+        let client = client();
+        assert_eq!(post(&client, "text/plain", b"ab\xff").0, Status::BadRequest);
+        assert_eq!(check(&client, "text/plain", b"ab\xffcd"),
+            "invalid UTF-8 byte sequence at offset 2");
+
+        // A truncated multi-byte sequence isn't replaced.
+        assert_eq!(check(&client, "text/plain; charset=utf-8", b"abc\xe2\x9c"),
+            "invalid UTF-8 byte sequence at offset 3");
+
+        assert_eq!(check(&client, "text/plain; charset=ascii", "naïve".as_bytes()),
+            "invalid US-ASCII byte sequence at offset 2");
+    }
+
+    #[test]
+    fn limit_is_enforced() {
+        let rocket = rocket::custom(rocket::Config::figment().merge(("limits.string", 4)))
+            .mount("/", routes![echo, check]);
+
+        let client = Client::debug(rocket).unwrap();
+        assert_eq!(post(&client, "text/plain", b"abcd"), (Status::Ok, "abcd".into()));
+        assert_eq!(post(&client, "text/plain", b"abcde").0, Status::PayloadTooLarge);
+    }
+}
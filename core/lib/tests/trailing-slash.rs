@@ -0,0 +1,160 @@
+#[macro_use] extern crate rocket;
+
+use std::path::PathBuf;
+
+use rocket::http::Status;
+
+#[get("/")]
+fn index() -> &'static str { "index" }
+
+#[get("/foo?<q>")]
+fn foo(q: Option<&str>) -> String { format!("foo {}", q.unwrap_or("-")) }
+
+#[post("/foo")]
+fn create() -> &'static str { "created" }
+
+#[get("/<id>")]
+fn item(id: &str) -> String { id.into() }
+
+#[get("/files/<path..>")]
+fn files(path: PathBuf) -> String { path.display().to_string() }
+
+mod trailing_slash_tests {
+    use super::*;
+    use rocket::config::{Config, TrailingSlash};
+    use rocket::local::blocking::{Client, LocalResponse};
+
+    fn client(trailing_slash: TrailingSlash) -> Client {
+        let config = Config { trailing_slash, ..Config::debug_default() };
+        let routes = routes![index, foo, create, files];
+        Client::debug(rocket::custom(config).mount("/", routes)).unwrap()
+    }
+
+    fn location(response: &LocalResponse<'_>) -> Option<String> {
+        response.headers().get_one("Location").map(String::from)
+    }
+
+    #[test]
+    fn strict_never_redirects() {
+        let client = client(TrailingSlash::Strict);
+        for uri in &["/foo", "/foo/"] {
+            let response = client.get(*uri).dispatch();
+            assert_eq!(response.status(), Status::Ok);
+            assert_eq!(response.into_string().unwrap(), "foo -");
+        }
+    }
+
+    #[test]
+    fn redirect_to_no_slash() {
+        let client = client(TrailingSlash::RedirectToNoSlash);
+        let response = client.get("/foo/").dispatch();
+        assert_eq!(response.status(), Status::MovedPermanently);
+        assert_eq!(location(&response).as_deref(), Some("/foo"));
+
+        let response = client.get("/foo").dispatch();
+        assert_eq!(response.status(), Status::Ok);
+        assert_eq!(location(&response), None);
+    }
+
+    #[test]
+    fn redirect_to_slash() {
+        let client = client(TrailingSlash::RedirectToSlash);
+        let response = client.get("/foo").dispatch();
+        assert_eq!(response.status(), Status::MovedPermanently);
+        assert_eq!(location(&response).as_deref(), Some("/foo/"));
+
+        let response = client.get("/foo/").dispatch();
+        assert_eq!(response.status(), Status::Ok);
+        assert_eq!(location(&response), None);
+    }
+
+    #[test]
+    fn redirect_preserves_query() {
+        let no_slash = client(TrailingSlash::RedirectToNoSlash);
+        let response = no_slash.get("/foo/?q=bar&x=1").dispatch();
+        assert_eq!(location(&response).as_deref(), Some("/foo?q=bar&x=1"));
+
+        let slash = client(TrailingSlash::RedirectToSlash);
+        let response = slash.get("/foo?q=bar").dispatch();
+        assert_eq!(location(&response).as_deref(), Some("/foo/?q=bar"));
+
+        let response = slash.get("/foo/?q=bar").dispatch();
+        assert_eq!(response.into_string().unwrap(), "foo bar");
+    }
+
+    #[test]
+    fn redirect_keeps_method() {
+        let client = client(TrailingSlash::RedirectToNoSlash);
+        let response = client.post("/foo/").dispatch();
+        assert_eq!(response.status(), Status::PermanentRedirect);
+        assert_eq!(location(&response).as_deref(), Some("/foo"));
+
+        let response = client.head("/foo/").dispatch();
+        assert_eq!(response.status(), Status::MovedPermanently);
+    }
+
+    #[test]
+    fn unroutable_requests_are_not_redirected() {
+        for policy in &[TrailingSlash::RedirectToNoSlash, TrailingSlash::RedirectToSlash] {
+            let client = client(*policy);
+            for uri in &["/missing", "/missing/"] {
+                let response = client.get(*uri).dispatch();
+                assert_eq!(response.status(), Status::NotFound);
+                assert_eq!(location(&response), None);
+            }
+
+            // Only the `GET` route matches, so a `PUT` is a 404, not a 308.
+            let response = client.put("/foo/").dispatch();
+            assert_eq!(response.status(), Status::NotFound);
+        }
+    }
+
+    #[test]
+    fn redirects_never_loop() {
+        for policy in &[TrailingSlash::RedirectToNoSlash, TrailingSlash::RedirectToSlash] {
+            let client = client(*policy);
+            for uri in &["/foo", "/foo/", "/foo//", "/foo/?q=a"] {
+                let mut uri = uri.to_string();
+                let mut redirects = 0;
+                loop {
+                    let response = client.get(uri.clone()).dispatch();
+                    match location(&response) {
+                        Some(next) => uri = next,
+                        None => {
+                            assert_eq!(response.status(), Status::Ok);
+                            break;
+                        }
+                    }
+
+                    redirects += 1;
+                    assert!(redirects <= 1, "{} redirected more than once", uri);
+                }
+            }
+
+            // The root and `<path..>` routes are never redirected.
+            assert_eq!(client.get("/").dispatch().status(), Status::Ok);
+            assert_eq!(client.get("/files/a/b").dispatch().status(), Status::Ok);
+            assert_eq!(client.get("/files/a/b/").dispatch().status(), Status::Ok);
+        }
+    }
+
+    #[test]
+    fn redirects_are_never_protocol_relative() {
+        use TrailingSlash::*;
+
+        let cases = &[
+            (RedirectToNoSlash, "//evil.example/", "/evil.example"),
+            (RedirectToNoSlash, "///evil.example//?a=b", "/evil.example?a=b"),
+            (RedirectToSlash, "//evil.example", "/evil.example/"),
+            (RedirectToSlash, "//evil.example?a=b", "/evil.example/?a=b"),
+        ];
+
+        for (trailing_slash, uri, expected) in cases {
+            let config = Config { trailing_slash: *trailing_slash, ..Config::debug_default() };
+            let client = Client::debug(rocket::custom(config).mount("/", routes![item])).unwrap();
+            let response = client.get(*uri).dispatch();
+            assert_eq!(response.status(), Status::MovedPermanently);
+            assert_eq!(location(&response).as_deref(), Some(*expected));
+        }
+    }
+}
@@ -0,0 +1,57 @@
+#[macro_use] extern crate rocket;
+
+use rocket::request::Trimmed;
+
+#[get("/n/<n>")]
+fn number(n: Trimmed<u32>) -> String {
+    format!("n = {}", n)
+}
+
+#[get("/s/<s>")]
+fn string(s: Trimmed<&str>) -> String {
+    format!("[{}]", *s)
+}
+
+#[get("/n/<_s>", rank = 2)]
+fn fallback(_s: &str) -> &'static str {
+    "not a number"
+}
+
+mod trimmed_param_tests {
+    use super::*;
+    use rocket::local::blocking::Client;
+    use rocket::request::FromParam;
+
+    fn get(client: &Client, uri: &str) -> String {
+        client.get(uri.to_string()).dispatch().into_string().unwrap()
+    }
+
+    #[test]
+    fn surrounding_whitespace() {
+        let client = Client::debug_with(routes![number, string, fallback]).unwrap();
+        assert_eq!(get(&client, "/n/42"), "n = 42");
+        assert_eq!(get(&client, "/n/%2042%20"), "n = 42");
+        assert_eq!(get(&client, "/n/%09%0A42%0D"), "n = 42");
+        assert_eq!(get(&client, "/s/%20%20hello%20"), "[hello]");
+
+        // Only ASCII whitespace is trimmed.
+        assert_eq!(get(&client, "/s/%C2%A0hi"), "[\u{a0}hi]");
+    }
+
+    #[test]
+    fn inner_whitespace_is_preserved() {
+        let client = Client::debug_with(routes![number, string, fallback]).unwrap();
+        assert_eq!(get(&client, "/s/%20a%20%20b%20"), "[a  b]");
+        assert_eq!(get(&client, "/n/4%202"), "not a number");
+    }
+
+    #[test]
+    fn whitespace_only() {
+        let client = Client::debug_with(routes![number, string, fallback]).unwrap();
+        assert_eq!(get(&client, "/n/%20%20"), "not a number");
+        assert_eq!(get(&client, "/s/%20%09"), "[]");
+
+        assert!(Trimmed::<u32>::from_param(" \t ").is_err());
+        assert_eq!(Trimmed::<String>::from_param(" ").unwrap().into_inner(), "");
+    }
+}
@@ -0,0 +1,75 @@
+#![cfg(unix)]
+
+#[macro_use] extern crate rocket;
+
+use rocket::request::PeerAddr;
+
+#[get("/")]
+fn peer(peer: PeerAddr<'_>) -> String {
+    let uid = peer.credentials().map(|c| c.uid.to_string());
+    format!("unix: {}, uid: {}", peer.is_unix(), uid.as_deref().unwrap_or("-"))
+}
+
+mod unix_socket_tests {
+    use super::*;
+
+    use std::os::unix::fs::MetadataExt;
+
+    use rocket::config::Config;
+    use rocket::fairing::AdHoc;
+    use rocket::futures::channel::oneshot;
+    use rocket::tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use rocket::tokio::net::UnixStream;
+
+    async fn get(path: &std::path::Path) -> String {
+        let mut stream = UnixStream::connect(path).await.unwrap();
+        let request = "GET / HTTP/1.1\r\nHost: localhost\r\nConnection: close\r\n\r\n";
+        stream.write_all(request.as_bytes()).await.unwrap();
+
+        let mut response = String::new();
+        stream.read_to_string(&mut response).await.unwrap();
+        response
+    }
+
+    #[rocket::async_test]
+    async fn serves_over_unix_socket() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("rocket.sock");
+
+        // A stale socket from a previous run doesn't prevent binding.
+        drop(std::os::unix::net::UnixListener::bind(&path).unwrap());
+
+        let config = Config { unix_socket: Some(path.clone()), ..Config::debug_default() };
+        let (tx, rx) = oneshot::channel();
+        let rocket = rocket::custom(config)
+            .mount("/", routes![peer])
+            .attach(AdHoc::on_liftoff("Send Shutdown", move |rocket| Box::pin(async move {
+                tx.send(rocket.shutdown()).unwrap();
+            })));
+
+        let server = rocket::tokio::spawn(rocket.launch());
+        let shutdown = rx.await.unwrap();
+
+        let response = get(&path).await;
+        assert!(response.starts_with("HTTP/1.1 200 OK"), "{}", response);
+        let uid = std::fs::metadata(dir.path()).unwrap().uid();
+        assert!(response.ends_with(&format!("unix: true, uid: {}", uid)), "{}", response);
+
+        shutdown.notify();
+        server.await.unwrap().unwrap();
+        assert!(!path.exists());
+    }
+
+    #[rocket::async_test]
+    async fn live_sockets_are_not_replaced() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("rocket.sock");
+
+        // Another process is listening on the socket: it isn't removed.
+        let _live = std::os::unix::net::UnixListener::bind(&path).unwrap();
+        let config = Config { unix_socket: Some(path.clone()), ..Config::debug_default() };
+        let error = rocket::custom(config).launch().await.unwrap_err();
+        assert!(matches!(error.kind(), rocket::error::ErrorKind::Bind(_)));
+        assert!(path.exists());
+    }
+}
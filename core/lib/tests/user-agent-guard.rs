@@ -0,0 +1,83 @@
+#[macro_use] extern crate rocket;
+
+use rocket::request::UserAgent;
+
+#[get("/")]
+fn index(ua: UserAgent) -> String {
+    format!("{:?} {:?} {:?} {:?}", ua.product(), ua.version(), ua.os(), ua.raw())
+}
+
+mod user_agent_guard_tests {
+    use super::*;
+    use rocket::http::Header;
+    use rocket::local::blocking::Client;
+
+    const CHROME: &str = "Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36 \
+        (KHTML, like Gecko) Chrome/94.0.4606.81 Safari/537.36";
+
+    const GOOGLEBOT: &str = "Mozilla/5.0 (Linux; Android 6.0.1; Nexus 5X Build/MMB29P) \
+        AppleWebKit/537.36 (KHTML, like Gecko) Chrome/94.0.4606.81 Mobile Safari/537.36 \
+        (compatible; Googlebot/2.1; +http://www.google.com/bot.html)";
+
+    fn parts(ua: &UserAgent) -> (Option<&str>, Option<&str>, Option<&str>) {
+        (ua.product(), ua.version(), ua.os())
+    }
+
+    #[test]
+    fn common_browsers() {
+        let ua = UserAgent::parse(CHROME);
+        assert_eq!(parts(&ua), (Some("Chrome"), Some("94.0.4606.81"), Some("Windows")));
+        assert_eq!(ua.raw(), CHROME);
+
+        let edge = UserAgent::parse(&format!("{} Edg/94.0.992.50", CHROME));
+        assert_eq!(parts(&edge), (Some("Edge"), Some("94.0.992.50"), Some("Windows")));
+
+        let safari = "Mozilla/5.0 (iPhone; CPU iPhone OS 15_0 like Mac OS X) \
+            AppleWebKit/605.1.15 (KHTML, like Gecko) Version/15.0 Mobile/15E148 Safari/604.1";
+        let safari = UserAgent::parse(safari);
+        assert_eq!(parts(&safari), (Some("Safari"), Some("15.0"), Some("iOS")));
+
+        let firefox = "Mozilla/5.0 (Macintosh; Intel Mac OS X 10.15; rv:93.0) \
+            Gecko/20100101 Firefox/93.0";
+        let firefox = UserAgent::parse(firefox);
+        assert_eq!(parts(&firefox), (Some("Firefox"), Some("93.0"), Some("macOS")));
+    }
+
+    #[test]
+    fn bots_and_tools() {
+        let ua = UserAgent::parse(GOOGLEBOT);
+        assert_eq!(parts(&ua), (Some("Googlebot"), Some("2.1"), Some("Android")));
+
+        let bing = "Mozilla/5.0 (compatible; bingbot/2.0; +http://www.bing.com/bingbot.htm)";
+        assert_eq!(parts(&UserAgent::parse(bing)), (Some("bingbot"), Some("2.0"), None));
+
+        assert_eq!(parts(&UserAgent::parse("curl/7.68.0")), (Some("curl"), Some("7.68.0"), None));
+    }
+
+    #[test]
+    fn uncertain_agents_fall_back_to_raw() {
+        for raw in ["", "Mozilla/5.0", "MyApp", "MyApp/", "/1.0", "Mozilla/5.0 (Unknown) Thing"] {
+            let ua = UserAgent::parse(raw);
+            assert_eq!(ua.raw(), raw);
+            assert_eq!(parts(&ua), (None, None, None), "{:?}", raw);
+        }
+
+        let ua = UserAgent::parse("Mozilla/5.0 (X11; Linux x86_64) Exotic/1.0 Thing/2.0");
+        assert_eq!(parts(&ua), (None, None, Some("Linux")));
+    }
+
+    #[test]
+    fn guard_never_fails() {
+        let client = Client::debug_with(routes![index]).unwrap();
+
+        let response = client.get("/").header(Header::new("User-Agent", CHROME)).dispatch();
+        assert_eq!(response.into_string().unwrap(),
+            format!("Some(\"Chrome\") Some(\"94.0.4606.81\") Some(\"Windows\") {:?}", CHROME));
+
+        let response = client.get("/").header(Header::new("User-Agent", GOOGLEBOT)).dispatch();
+        assert!(response.into_string().unwrap().starts_with("Some(\"Googlebot\") Some(\"2.1\")"));
+
+        let response = client.get("/").dispatch();
+        assert_eq!(response.into_string().unwrap(), "None None None \"\"");
+    }
+}
@@ -0,0 +1,131 @@
+#[macro_use] extern crate rocket;
+
+use rocket::form::{self, Form, Contextual, Error, Errors, Validate, Validated};
+
+struct Even;
+
+impl Validate<usize> for Even {
+    fn validate<'v>(value: &usize) -> form::Result<'v, ()> {
+        match value % 2 {
+            0 => Ok(()),
+            _ => Err(Error::validation("must be even"))?,
+        }
+    }
+}
+
+struct Lowercase;
+
+impl<'a> Validate<&'a str> for Lowercase {
+    fn validate<'v>(value: &&'a str) -> form::Result<'v, ()> {
+        let mut errors = Errors::new();
+        if value.is_empty() {
+            errors.push(Error::validation("must not be empty"));
+        }
+
+        if value.chars().any(|c| c.is_uppercase()) {
+            errors.push(Error::validation("must be lowercase"));
+        }
+
+        match errors.is_empty() {
+            true => Ok(()),
+            false => Err(errors),
+        }
+    }
+}
+
+#[derive(Debug, FromForm)]
+struct Order<'r> {
+    count: Validated<usize, Even>,
+    tag: Validated<&'r str, Lowercase>,
+    note: Validated<&'r str, Lowercase>,
+}
+
+#[post("/", data = "<form>")]
+fn order(form: Form<Contextual<'_, Order<'_>>>) -> String {
+    match form.value {
+        Some(ref order) => format!("{} {}", *order.count, *order.tag),
+        None => {
+            let mut errors: Vec<_> = form.context.errors()
+                .map(|e| format!("{}: {}", e.name.as_ref().unwrap(), e))
+                .collect();
+
+            errors.sort();
+            errors.join(", ")
+        }
+    }
+}
+
+mod validated_form_field_tests {
+    use super::*;
+    use rocket::http::ContentType;
+    use rocket::local::blocking::Client;
+
+    fn errors(string: &str) -> Vec<(String, String)> {
+        let mut errors: Vec<_> = Form::<Order<'_>>::parse(string)
+            .expect_err("expected an error")
+            .iter()
+            .map(|e| (e.name.as_ref().unwrap().to_string(), e.to_string()))
+            .collect();
+
+        errors.sort();
+        errors
+    }
+
+    #[test]
+    fn valid_values() {
+        let order = Form::<Order<'_>>::parse("count=4&tag=red&note=hi").unwrap();
+        assert_eq!(*order.count, 4);
+        assert_eq!(order.tag.into_inner(), "red");
+        assert_eq!(*order.note, "hi");
+    }
+
+    #[test]
+    fn invalid_value_is_scoped_to_field() {
+        let errors = Form::<Order<'_>>::parse("count=3&tag=red&note=hi").unwrap_err();
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].name.as_ref().unwrap(), "count");
+        assert_eq!(errors[0].value.as_deref(), Some("3"));
+        assert_eq!(errors[0].to_string(), "must be even");
+
+        // Values that fail to parse aren't validated.
+        let errors = Form::<Order<'_>>::parse("count=x&tag=red&note=hi").unwrap_err();
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].name.as_ref().unwrap(), "count");
+        assert_ne!(errors[0].to_string(), "must be even");
+    }
+
+    #[test]
+    fn errors_are_aggregated_across_fields() {
+        assert_eq!(errors("count=3&tag=Red&note=OK"), vec![
+            ("count".into(), "must be even".into()),
+            ("note".into(), "must be lowercase".into()),
+            ("tag".into(), "must be lowercase".into()),
+        ]);
+
+        // A single value may fail more than one check.
+        assert_eq!(errors("count=2&tag=&note=hi"), vec![
+            ("tag".into(), "must not be empty".into()),
+        ]);
+
+        assert_eq!(errors("count=1&tag=OK&note="), vec![
+            ("count".into(), "must be even".into()),
+            ("note".into(), "must not be empty".into()),
+            ("tag".into(), "must be lowercase".into()),
+        ]);
+    }
+
+    #[test]
+    fn contextual_reports_every_field() {
+        let client = Client::debug_with(routes![order]).unwrap();
+        let post = |body: &'static str| client.post("/")
+            .header(ContentType::Form)
+            .body(body)
+            .dispatch()
+            .into_string()
+            .unwrap();
+
+        assert_eq!(post("count=8&tag=blue&note=hi"), "8 blue");
+        assert_eq!(post("count=7&tag=Blue&note=hi"), "count: must be even, tag: must be lowercase");
+        assert_eq!(post("count=7&tag=blue&note=X"), "count: must be even, note: must be lowercase");
+    }
+}
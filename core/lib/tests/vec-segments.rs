@@ -0,0 +1,64 @@
+#[macro_use] extern crate rocket;
+
+use std::path::PathBuf;
+
+#[get("/split/<path..>")]
+fn split(path: Vec<String>) -> String {
+    path.iter().map(|s| format!("[{}]", s)).collect()
+}
+
+#[get("/fs/<path..>")]
+fn fs(path: PathBuf) -> String {
+    path.display().to_string()
+}
+
+mod vec_segments_tests {
+    use super::*;
+    use rocket::local::blocking::Client;
+
+    fn get(client: &Client, uri: &str) -> String {
+        client.get(uri.to_string()).dispatch().into_string().unwrap()
+    }
+
+    fn client() -> Client {
+        Client::debug_with(routes![split, fs]).unwrap()
+    }
+
+    #[test]
+    fn decoded_segments() {
+        let client = client();
+        assert_eq!(get(&client, "/split/a/b/c"), "[a][b][c]");
+        assert_eq!(get(&client, "/split/a%20b/c%2Fd"), "[a b][c/d]");
+        assert_eq!(get(&client, "/split/.hidden/*star"), "[.hidden][*star]");
+    }
+
+    #[test]
+    fn empty_segments() {
+        let client = client();
+        assert_eq!(get(&client, "/split/a//b"), "[a][][b]");
+        assert_eq!(get(&client, "/split/a///b/c"), "[a][][][b][c]");
+
+        // Slashes before the first or after the last segment are not segments.
+        assert_eq!(get(&client, "/split//a/"), "[a]");
+        assert_eq!(get(&client, "//split/a//"), "[a]");
+    }
+
+    #[test]
+    fn literal_dot_dot() {
+        let client = client();
+        assert_eq!(get(&client, "/split/a/../b"), "[a][..][b]");
+        assert_eq!(get(&client, "/split/../../etc"), "[..][..][etc]");
+        assert_eq!(get(&client, "/split/a/%2E%2E"), "[a][..]");
+
+        // `PathBuf`, on the other hand, resolves them.
+        assert_eq!(get(&client, "/fs/a/../b"), "b");
+    }
+
+    #[test]
+    fn unicode_segments() {
+        let client = client();
+        assert_eq!(get(&client, "/split/%C3%BCber/%E6%97%A5%E6%9C%AC"), "[über][日本]");
+        assert_eq!(get(&client, "/split/caf%C3%A9//na%C3%AFve"), "[café][][naïve]");
+        assert_eq!(get(&client, "/split/%FF"), "[\u{FFFD}]");
+    }
+}
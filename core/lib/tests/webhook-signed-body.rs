@@ -0,0 +1,194 @@
+#![cfg(feature = "webhook")]
+
+#[macro_use] extern crate rocket;
+
+use rocket::webhook::{SignedBody, SignatureError};
+
+struct GitHub;
+
+struct Timed;
+
+#[post("/github", data = "<body>")]
+fn github(body: Result<SignedBody<'_, GitHub>, SignatureError>) -> String {
+    match body {
+        Ok(body) => format!("ok: {}", std::str::from_utf8(&body).unwrap()),
+        Err(e) => format!("error: {}", e),
+    }
+}
+
+#[post("/github/strict", data = "<body>")]
+fn strict(body: SignedBody<'_, GitHub>) -> Vec<u8> {
+    body.to_vec()
+}
+
+#[post("/timed", data = "<body>")]
+fn timed(body: Result<SignedBody<'_, Timed>, SignatureError>) -> String {
+    match body {
+        Ok(body) => format!("ok: {}", body.len()),
+        Err(e) => format!("error: {}", e),
+    }
+}
+
+mod webhook_signed_body_tests {
+    use super::*;
+    use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+    use rocket::http::{Header, Status};
+    use rocket::local::blocking::{Client, LocalResponse};
+    use rocket::webhook::{Algorithm, WebhookKey};
+
+    const SECRET: &str = "It's a Secret to Everybody";
+    const BODY: &str = "Hello, World!";
+    const SIGNATURE: &str =
+        "sha256=757107ea0eb2509fc211221cce984b8a37570b6d7586c22c46f4379c8b043e17";
+
+    fn github_key() -> WebhookKey<GitHub> {
+        WebhookKey::new(Algorithm::Sha256, SECRET).header("X-Hub-Signature-256")
+    }
+
+    fn timed_key() -> WebhookKey<Timed> {
+        WebhookKey::new(Algorithm::Sha512, SECRET)
+            .prefix("")
+            .timestamp("X-Timestamp", Duration::from_secs(300))
+    }
+
+    fn client() -> Client {
+        let rocket = rocket::build()
+            .manage(github_key())
+            .manage(timed_key())
+            .mount("/", routes![github, strict, timed]);
+
+        Client::debug(rocket).unwrap()
+    }
+
+    fn post<'c>(client: &'c Client, body: &str, signature: Option<&str>) -> LocalResponse<'c> {
+        let mut request = client.post("/github/strict").body(body);
+        if let Some(signature) = signature {
+            request.add_header(Header::new("X-Hub-Signature-256", signature.to_string()));
+        }
+
+        request.dispatch()
+    }
+
+    #[test]
+    fn valid_signature() {
+        let client = client();
+        let response = post(&client, BODY, Some(SIGNATURE));
+        assert_eq!(response.status(), Status::Ok);
+        assert_eq!(response.into_string().unwrap(), BODY);
+
+        // Hex digits and the prefix are case-insensitive.
+        let upper = SIGNATURE.to_uppercase();
+        assert_eq!(post(&client, BODY, Some(&upper)).into_string().unwrap(), BODY);
+    }
+
+    #[test]
+    fn tampered_body() {
+        let client = client();
+        let response = post(&client, "Hello, World?", Some(SIGNATURE));
+        assert_eq!(response.status(), Status::Unauthorized);
+
+        let mut flipped = SIGNATURE.to_string();
+        flipped.replace_range(7..8, "8");
+        let response = post(&client, BODY, Some(&flipped));
+        assert_eq!(response.status(), Status::Unauthorized);
+    }
+
+    #[test]
+    fn wrong_prefix() {
+        let client = client();
+        let sha1 = SIGNATURE.replace("sha256=", "sha1=");
+        assert_eq!(post(&client, BODY, Some(&sha1)).status(), Status::Unauthorized);
+
+        let bare = SIGNATURE.trim_start_matches("sha256=");
+        assert_eq!(post(&client, BODY, Some(bare)).status(), Status::Unauthorized);
+
+        let key = github_key();
+        let error = key.sign(BODY.as_bytes(), None).replace("sha256=", "sha512=");
+        assert_eq!(post(&client, BODY, Some(&error)).status(), Status::Unauthorized);
+
+        // Malformed hex is rejected, too.
+        let truncated = &SIGNATURE[..SIGNATURE.len() - 2];
+        assert_eq!(post(&client, BODY, Some(truncated)).status(), Status::Unauthorized);
+    }
+
+    #[test]
+    fn missing_header() {
+        let client = client();
+        assert_eq!(post(&client, BODY, None).status(), Status::Unauthorized);
+
+        // Another header with the signature doesn't count.
+        let response = client.post("/github/strict")
+            .header(Header::new("X-Signature", SIGNATURE))
+            .body(BODY)
+            .dispatch();
+
+        assert_eq!(response.status(), Status::Unauthorized);
+    }
+
+    #[test]
+    fn errors_are_reported() {
+        let client = client();
+        let message = |signature: Option<&str>| {
+            let mut request = client.post("/github").body(BODY);
+            if let Some(signature) = signature {
+                request.add_header(Header::new("X-Hub-Signature-256", signature.to_string()));
+            }
+
+            request.dispatch().into_string().unwrap()
+        };
+
+        assert_eq!(message(Some(SIGNATURE)), "ok: Hello, World!");
+        assert_eq!(message(None), "error: missing signature header");
+        assert_eq!(message(Some("sha1=00")), "error: signature has an unexpected prefix");
+        assert_eq!(message(Some("sha256=zz")), "error: malformed signature");
+
+        let other = WebhookKey::<()>::new(Algorithm::Sha256, "another secret");
+        let signature = other.sign(BODY.as_bytes(), None);
+        assert_eq!(message(Some(&signature)), "error: signature does not match body");
+    }
+
+    #[test]
+    fn optional_timestamp_replay_protection() {
+        let client = client();
+        let key = timed_key();
+        let now = SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_secs();
+        let send = |timestamp: Option<String>, signed_at: &str| {
+            let mut request = client.post("/timed")
+                .header(Header::new("X-Signature", key.sign(BODY.as_bytes(), Some(signed_at))))
+                .body(BODY);
+
+            if let Some(timestamp) = timestamp {
+                request.add_header(Header::new("X-Timestamp", timestamp));
+            }
+
+            request.dispatch().into_string().unwrap()
+        };
+
+        let fresh = now.to_string();
+        assert_eq!(send(Some(fresh.clone()), &fresh), "ok: 13");
+
+        // The timestamp is signed: it can't be swapped for a fresh one.
+        let old = (now - 3600).to_string();
+        assert_eq!(send(Some(fresh.clone()), &old), "error: signature does not match body");
+        assert_eq!(send(Some(old.clone()), &old), "error: timestamp is outside the tolerance");
+
+        let future = (now + 3600).to_string();
+        assert_eq!(send(Some(future.clone()), &future),
+            "error: timestamp is outside the tolerance");
+
+        assert_eq!(send(None, &fresh), "error: missing timestamp header");
+        assert_eq!(send(Some("soon".into()), "soon"), "error: invalid timestamp");
+    }
+
+    #[test]
+    fn unmanaged_key_fails() {
+        let rocket = rocket::build().manage(timed_key()).mount("/", routes![github, strict]);
+        let client = Client::debug(rocket).unwrap();
+        let response = post(&client, BODY, Some(SIGNATURE));
+        assert_eq!(response.status(), Status::InternalServerError);
+
+        let response = client.post("/github").body(BODY).dispatch();
+        assert_eq!(response.into_string().unwrap(), "error: no webhook key is configured");
+    }
+}
@@ -0,0 +1,66 @@
+#[macro_use] extern crate rocket;
+
+use rocket::request::{WithRaw, WithRawError, RawBytes};
+
+#[get("/n/<n>")]
+fn number(n: Result<WithRaw<'_, u8>, WithRawError<'_, &str>>) -> String {
+    match n {
+        Ok(n) => format!("ok {} {}", n.value, n.raw),
+        Err(e) => format!("err {:?} {}", e.error, e.raw),
+    }
+}
+
+#[get("/s/<s>")]
+fn string(s: WithRaw<'_, &str>) -> String {
+    format!("{}|{}", *s, s.raw)
+}
+
+#[get("/b/<b>")]
+fn bytes(b: WithRaw<'_, RawBytes<'_>>) -> String {
+    format!("{:?}|{}", &*b.value, b.raw)
+}
+
+mod with_raw_param_tests {
+    use super::*;
+    use rocket::http::RawStr;
+    use rocket::request::FromParam;
+    use rocket::local::blocking::Client;
+
+    #[test]
+    fn both_fields_are_populated() {
+        let n = WithRaw::<u8>::from_param("42").unwrap();
+        assert_eq!(n.value, 42);
+        assert_eq!(n.raw, "42");
+        assert_eq!(*n, 42);
+        assert_eq!(n.into_inner(), 42);
+
+        let n = WithRaw::<u8>::from_raw_param(RawStr::new("%34%32"), "42").unwrap();
+        assert_eq!(n.value, 42);
+        assert_eq!(n.raw, "%34%32");
+        assert_eq!(n.to_string(), "42");
+
+        let e = WithRaw::<u8>::from_raw_param(RawStr::new("2%35%36"), "256").unwrap_err();
+        assert_eq!(e.error, "256");
+        assert_eq!(e.raw, "2%35%36");
+    }
+
+    #[test]
+    fn raw_matches_encoded_input() {
+        let client = Client::debug_with(routes![number, string, bytes]).unwrap();
+        let get = |uri: &str| client.get(uri.to_string()).dispatch().into_string().unwrap();
+
+        assert_eq!(get("/n/7"), "ok 7 7");
+        assert_eq!(get("/n/%31%32"), "ok 12 %31%32");
+        assert_eq!(get("/n/999"), "err \"999\" 999");
+        assert_eq!(get("/n/1%2f2"), "err \"1/2\" 1%2f2");
+
+        // Escapes and their case are kept verbatim in `raw` only.
+        assert_eq!(get("/s/a%20b%2Fc"), "a b/c|a%20b%2Fc");
+        assert_eq!(get("/s/caf%C3%A9"), "café|caf%C3%A9");
+        assert_eq!(get("/s/caf%c3%a9"), "café|caf%c3%a9");
+        assert_eq!(get("/s/plain"), "plain|plain");
+
+        // The inner type still sees the raw segment if it asks for it.
+        assert_eq!(get("/b/%FF%00a"), "[255, 0, 97]|%FF%00a");
+    }
+}